@@ -60,7 +60,10 @@
 //! 1. [tensor::Cpu] - for tensors stored on the heap
 //! 2. [tensor::Cuda] - for tensors stored in GPU memory
 //!
-//! Both devices implement [Default], you can also create them with a certain seed
+//! There is also [tensor::Reference], a deliberately naive cpu device that is only
+//! meant for checking the other devices against.
+//!
+//! All devices implement [Default], you can also create them with a certain seed
 //! and ordinal.
 //!
 //! Here's how you might use a device:
@@ -86,6 +89,7 @@
 //! | gemm/gemv | [tensor_ops::matmul] | `a @ b` | `a @ b` |
 //! | 2d Convolution | [tensor_ops::TryConv2D] | - | `torch.conv2d` |
 //! | 2d Transposed Convolution | [tensor_ops::TryConvTrans2D] | - | `torch.conv_transpose2d` |
+//! | 1d & 3d Convolution | [tensor_ops::TryConv1D], [tensor_ops::TryConv3D] | - | `torch.conv1d`, `torch.conv3d` |
 //! | Slicing | [tensor_ops::slice] | `a[...]` | `a[...]` |
 //! | Select | [tensor_ops::SelectTo] | `a[...]` | `torch.select` |
 //! | Gather | [tensor_ops::GatherTo] | `np.take` | `torch.gather` |
@@ -97,6 +101,7 @@
 //! | Roll | [tensor_ops::Roll] | `np.rollaxis(...)` | `a.roll(...)` |
 //! | Stack | [tensor_ops::TryStack] | `np.stack` | `torch.stack` |
 //! | Concat | [tensor_ops::TryConcat] | `np.concatenate` | `torch.concat` |
+//! | Linear algebra | [tensor_ops::linalg] | `np.linalg` | `torch.linalg` |
 //!
 //! and **much much more!**
 //!
@@ -261,6 +266,20 @@ pub(crate) mod tests {
     #[cfg(feature = "test-f64")]
     pub type TestDtype = f64;
 
+    /// Extracts the [crate::shapes::ShapeError] from an error returned by [TestDevice].
+    pub fn shape_error(
+        err: <TestDevice as crate::tensor::HasErr>::Err,
+    ) -> Option<crate::shapes::ShapeError> {
+        #[cfg(not(feature = "cuda"))]
+        use crate::tensor::CpuError as TestErr;
+        #[cfg(feature = "cuda")]
+        use crate::tensor::CudaError as TestErr;
+        match err {
+            TestErr::Shape(err) => Some(err),
+            _ => None,
+        }
+    }
+
     pub trait AssertClose {
         type Elem: std::fmt::Display + std::fmt::Debug + Copy;
         const DEFAULT_TOLERANCE: Self::Elem;
@@ -26,6 +26,12 @@ macro_rules! activation_impls {
 
 activation_impls!(ReLU, try_relu, #[doc="Calls [relu()]."]);
 activation_impls!(GeLU, try_gelu, #[doc="Calls [gelu()]."]);
+activation_impls!(AccurateGeLU, try_accurate_gelu, #[doc="Calls [accurate_gelu()]."]);
+activation_impls!(SiLU, try_silu, #[doc="Calls [silu()]."]);
+activation_impls!(Mish, try_mish, #[doc="Calls [mish()]."]);
+activation_impls!(SeLU, try_selu, #[doc="Calls [selu()]."]);
+activation_impls!(HardSigmoid, try_hard_sigmoid, #[doc="Calls [hard_sigmoid()]."]);
+activation_impls!(HardSwish, try_hard_swish, #[doc="Calls [hard_swish()]."]);
 activation_impls!(Sin, try_sin, #[doc="Calls [sin()]."]);
 activation_impls!(Cos, try_cos, #[doc="Calls [cos()]."]);
 activation_impls!(Ln, try_ln, #[doc="Calls [ln()]."]);
@@ -74,6 +80,60 @@ impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Module<Tensor<S, E, D, T>>
     }
 }
 
+/// Calls [elu()] with constant alpha - defaults to 1.0
+#[derive(Debug, Clone, Copy)]
+pub struct ELU<E: Dtype>(pub E);
+
+impl<E: Dtype> Default for ELU<E> {
+    fn default() -> Self {
+        Self(E::ONE)
+    }
+}
+
+impl<E: Dtype> ZeroSizedModule for ELU<E> {}
+impl<E: Dtype> NonMutableModule for ELU<E> {}
+
+impl<S: Shape, E: Dtype + Into<f64>, D: Device<E>, T: Tape<E, D>> Module<Tensor<S, E, D, T>>
+    for ELU<E>
+{
+    type Output = Tensor<S, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, input: Tensor<S, E, D, T>) -> Result<Self::Output, D::Err> {
+        input.try_elu(self.0)
+    }
+}
+
+/// Calls [softplus()] - defaults to `beta = 1.0` and `threshold = 20.0`
+#[derive(Debug, Clone, Copy)]
+pub struct Softplus<E: Dtype> {
+    pub beta: E,
+    pub threshold: E,
+}
+
+impl<E: Dtype> Default for Softplus<E> {
+    fn default() -> Self {
+        Self {
+            beta: E::ONE,
+            threshold: E::from_f32(20.0).unwrap(),
+        }
+    }
+}
+
+impl<E: Dtype> ZeroSizedModule for Softplus<E> {}
+impl<E: Dtype> NonMutableModule for Softplus<E> {}
+
+impl<S: Shape, E: Dtype + Into<f64>, D: Device<E>, T: Tape<E, D>> Module<Tensor<S, E, D, T>>
+    for Softplus<E>
+{
+    type Output = Tensor<S, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, input: Tensor<S, E, D, T>) -> Result<Self::Output, D::Err> {
+        input.try_softplus(self.beta, self.threshold)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{nn::*, tests::TestDevice};
@@ -204,4 +264,55 @@ mod tests {
         let r2 = t.prelu(dev.tensor([[0.05, 0.05, 0.05], [0.05, 0.05, 0.05]]));
         assert_eq!(r1.array(), r2.array());
     }
+
+    #[test]
+    fn test_nn_activations_new_unary() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([-4.0, -2.0, -1.0, 0.0, 1.0, 2.0, 4.0]);
+        assert_eq!(
+            AccurateGeLU.forward_mut(t.clone()).array(),
+            accurate_gelu(t.clone()).array()
+        );
+        assert_eq!(SiLU.forward_mut(t.clone()).array(), silu(t.clone()).array());
+        assert_eq!(Mish.forward_mut(t.clone()).array(), mish(t.clone()).array());
+        assert_eq!(SeLU.forward_mut(t.clone()).array(), selu(t.clone()).array());
+        assert_eq!(
+            HardSigmoid.forward_mut(t.clone()).array(),
+            hard_sigmoid(t.clone()).array()
+        );
+        assert_eq!(
+            HardSwish.forward_mut(t.clone()).array(),
+            hard_swish(t).array()
+        );
+    }
+
+    #[test]
+    fn test_nn_activations_elu() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([-2.0, -1.0, 0.0, 1.0, 2.0]);
+        let r1 = ELU::default().forward_mut(t.clone());
+        let r2 = t.clone().elu(1.0);
+        assert_eq!(r1.array(), r2.array());
+
+        let r1 = ELU(0.5).forward_mut(t.clone());
+        let r2 = elu(t, 0.5);
+        assert_eq!(r1.array(), r2.array());
+    }
+
+    #[test]
+    fn test_nn_activations_softplus() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([-2.0, -1.0, 0.0, 1.0, 30.0]);
+        let r1 = Softplus::default().forward_mut(t.clone());
+        let r2 = t.clone().softplus(1.0, 20.0);
+        assert_eq!(r1.array(), r2.array());
+
+        let r1 = Softplus {
+            beta: 2.0,
+            threshold: 3.0,
+        }
+        .forward_mut(t.clone());
+        let r2 = softplus(t, 2.0, 3.0);
+        assert_eq!(r1.array(), r2.array());
+    }
 }
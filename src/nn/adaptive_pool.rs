@@ -0,0 +1,167 @@
+use crate::{
+    shapes::Const,
+    tensor_ops::{Pool2DKind, TryAdaptivePool1D, TryAdaptivePool2D, TryAdaptivePool3D},
+};
+
+use super::{Module, NonMutableModule, ZeroSizedModule};
+
+/// Average pools sequences (2d) and batches of sequences (3d) to a fixed length `L`,
+/// no matter the input length.
+///
+/// **Pytorch equivalent**: `torch.nn.AdaptiveAvgPool1d(L)`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let m: AdaptiveAvgPool1D<4> = Default::default();
+/// let _: Tensor<Rank2<3, 4>, f32, _> = m.forward(dev.zeros::<Rank2<3, 10>>());
+/// let _: Tensor<Rank3<2, 3, 4>, f32, _> = m.forward(dev.zeros::<Rank3<2, 3, 7>>());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct AdaptiveAvgPool1D<const L: usize>;
+
+/// Max pools sequences (2d) and batches of sequences (3d) to a fixed length `L`,
+/// no matter the input length.
+///
+/// **Pytorch equivalent**: `torch.nn.AdaptiveMaxPool1d(L)`
+#[derive(Debug, Default, Clone)]
+pub struct AdaptiveMaxPool1D<const L: usize>;
+
+/// Average pools images (3d) and batches of images (4d) to a fixed `H`x`W`,
+/// no matter the input size. `W` defaults to `H`.
+///
+/// **Pytorch equivalent**: `torch.nn.AdaptiveAvgPool2d((H, W))`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let m: AdaptiveAvgPool2D<7> = Default::default();
+/// let _: Tensor<Rank3<3, 7, 7>, f32, _> = m.forward(dev.zeros::<Rank3<3, 32, 24>>());
+/// let _: Tensor<Rank4<2, 3, 7, 7>, f32, _> = m.forward(dev.zeros::<Rank4<2, 3, 9, 9>>());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct AdaptiveAvgPool2D<const H: usize, const W: usize = H>;
+
+/// Max pools images (3d) and batches of images (4d) to a fixed `H`x`W`,
+/// no matter the input size. `W` defaults to `H`.
+///
+/// **Pytorch equivalent**: `torch.nn.AdaptiveMaxPool2d((H, W))`
+#[derive(Debug, Default, Clone)]
+pub struct AdaptiveMaxPool2D<const H: usize, const W: usize = H>;
+
+/// Average pools volumes (4d) and batches of volumes (5d) to a fixed `D`x`H`x`W`,
+/// no matter the input size. `H` defaults to `D`, and `W` defaults to `H`.
+///
+/// **Pytorch equivalent**: `torch.nn.AdaptiveAvgPool3d((D, H, W))`
+#[derive(Debug, Default, Clone)]
+pub struct AdaptiveAvgPool3D<const D: usize, const H: usize = D, const W: usize = H>;
+
+/// Max pools volumes (4d) and batches of volumes (5d) to a fixed `D`x`H`x`W`,
+/// no matter the input size. `H` defaults to `D`, and `W` defaults to `H`.
+///
+/// **Pytorch equivalent**: `torch.nn.AdaptiveMaxPool3d((D, H, W))`
+#[derive(Debug, Default, Clone)]
+pub struct AdaptiveMaxPool3D<const D: usize, const H: usize = D, const W: usize = H>;
+
+macro_rules! impl_pools {
+    ($PoolTy:tt, $Op:expr, 1) => {
+        impl<const L: usize> ZeroSizedModule for $PoolTy<L> {}
+        impl<const L: usize> NonMutableModule for $PoolTy<L> {}
+
+        impl<const L: usize, Inp: TryAdaptivePool1D<Const<L>>> Module<Inp> for $PoolTy<L> {
+            type Output = Inp::Pooled;
+            type Error = Inp::Error;
+
+            fn try_forward(&self, x: Inp) -> Result<Self::Output, Self::Error> {
+                x.try_adaptive_pool1d($Op, Const)
+            }
+        }
+    };
+    ($PoolTy:tt, $Op:expr, 2) => {
+        impl<const H: usize, const W: usize> ZeroSizedModule for $PoolTy<H, W> {}
+        impl<const H: usize, const W: usize> NonMutableModule for $PoolTy<H, W> {}
+
+        impl<const H: usize, const W: usize, Inp: TryAdaptivePool2D<Const<H>, Const<W>>>
+            Module<Inp> for $PoolTy<H, W>
+        {
+            type Output = Inp::Pooled;
+            type Error = Inp::Error;
+
+            fn try_forward(&self, x: Inp) -> Result<Self::Output, Self::Error> {
+                x.try_adaptive_pool2d($Op, Const, Const)
+            }
+        }
+    };
+    ($PoolTy:tt, $Op:expr, 3) => {
+        impl<const D: usize, const H: usize, const W: usize> ZeroSizedModule for $PoolTy<D, H, W> {}
+        impl<const D: usize, const H: usize, const W: usize> NonMutableModule
+            for $PoolTy<D, H, W>
+        {
+        }
+
+        impl<
+                const D: usize,
+                const H: usize,
+                const W: usize,
+                Inp: TryAdaptivePool3D<Const<D>, Const<H>, Const<W>>,
+            > Module<Inp> for $PoolTy<D, H, W>
+        {
+            type Output = Inp::Pooled;
+            type Error = Inp::Error;
+
+            fn try_forward(&self, x: Inp) -> Result<Self::Output, Self::Error> {
+                x.try_adaptive_pool3d($Op, Const, Const, Const)
+            }
+        }
+    };
+}
+
+impl_pools!(AdaptiveAvgPool1D, Pool2DKind::Avg, 1);
+impl_pools!(AdaptiveMaxPool1D, Pool2DKind::Max, 1);
+impl_pools!(AdaptiveAvgPool2D, Pool2DKind::Avg, 2);
+impl_pools!(AdaptiveMaxPool2D, Pool2DKind::Max, 2);
+impl_pools!(AdaptiveAvgPool3D, Pool2DKind::Avg, 3);
+impl_pools!(AdaptiveMaxPool3D, Pool2DKind::Max, 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tests::*};
+
+    #[test]
+    fn test_adaptive_pool_sizes() {
+        let dev: TestDevice = Default::default();
+        let _: Tensor<Rank2<3, 4>, TestDtype, _> =
+            AdaptiveMaxPool1D::<4>::default().forward(dev.zeros::<Rank2<3, 11>>());
+        let _: Tensor<Rank3<2, 3, 4>, TestDtype, _> =
+            AdaptiveAvgPool1D::<4>::default().forward(dev.zeros::<Rank3<2, 3, 3>>());
+        let _: Tensor<Rank3<3, 5, 5>, TestDtype, _> =
+            AdaptiveMaxPool2D::<5>::default().forward(dev.zeros::<Rank3<3, 13, 8>>());
+        let _: Tensor<Rank4<2, 3, 2, 4>, TestDtype, _> =
+            AdaptiveAvgPool2D::<2, 4>::default().forward(dev.zeros::<Rank4<2, 3, 9, 9>>());
+        let _: Tensor<Rank4<3, 2, 2, 2>, TestDtype, _> =
+            AdaptiveMaxPool3D::<2>::default().forward(dev.zeros::<Rank4<3, 5, 6, 7>>());
+        let _: Tensor<Rank5<2, 3, 1, 2, 3>, TestDtype, _> =
+            AdaptiveAvgPool3D::<1, 2, 3>::default().forward(dev.zeros::<Rank5<2, 3, 4, 4, 4>>());
+    }
+
+    #[test]
+    fn test_adaptive_avg_pool2d_forward() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]])
+            .to_dtype::<TestDtype>();
+        let y = AdaptiveAvgPool2D::<2>::default().forward(x);
+        assert_close_to_literal!(y, [[[3.0, 4.0], [6.0, 7.0]]]);
+    }
+
+    #[test]
+    fn test_adaptive_max_pool_tuple() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<4, 17, 17>, TestDtype, _> = dev.sample_normal();
+        let y = <(AdaptiveMaxPool2D<8>, AdaptiveMaxPool2D<3, 2>)>::default().forward(x);
+        assert_eq!(y.shape(), &(Const::<4>, Const::<3>, Const::<2>));
+    }
+}
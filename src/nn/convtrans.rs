@@ -6,6 +6,15 @@ use crate::{shapes::*, tensor::*, tensor_ops::*};
 use super::*;
 
 pub mod builder {
+    #[derive(Debug)]
+    pub struct ConvTrans1D<
+        const IN_CHAN: usize,
+        const OUT_CHAN: usize,
+        const KERNEL_SIZE: usize,
+        const STRIDE: usize = 1,
+        const PADDING: usize = 0,
+    >;
+
     #[derive(Debug)]
     pub struct ConvTrans2D<
         const IN_CHAN: usize,
@@ -14,6 +23,15 @@ pub mod builder {
         const STRIDE: usize = 1,
         const PADDING: usize = 0,
     >;
+
+    #[derive(Debug)]
+    pub struct ConvTrans3D<
+        const IN_CHAN: usize,
+        const OUT_CHAN: usize,
+        const KERNEL_SIZE: usize,
+        const STRIDE: usize = 1,
+        const PADDING: usize = 0,
+    >;
 }
 
 impl<const I: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D>
@@ -29,7 +47,7 @@ where
     }
 }
 
-/// **Requires Nightly** Performs *unbiased* 2d deconvolutions on 3d and 4d images.
+/// Performs *unbiased* 2d deconvolutions on 3d and 4d images.
 ///
 /// **Pytorch Equivalent**: `torch.nn.ConvTranspose2d(..., bias=False)`
 ///
@@ -45,6 +63,8 @@ where
 /// - `KERNEL_SIZE`: The size of the kernel applied to both width and height of the images.
 /// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
 /// - `PADDING`: How much zero padding to add around the images. Defaults to `0`.
+///
+/// The output height and width are `usize` unless the `nightly` feature is enabled.
 #[derive(Debug, Clone)]
 pub struct ConvTrans2D<
     const IN_CHAN: usize,
@@ -84,7 +104,6 @@ where
     }
 }
 
-#[cfg(feature = "nightly")]
 impl<const C: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D, Img>
     Module<Img> for ConvTrans2D<C, O, K, S, P, E, D>
 where
@@ -108,7 +127,167 @@ where
 {
 }
 
-#[cfg(feature = "nightly")]
+impl<const I: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D>
+    BuildOnDevice<D, E> for builder::ConvTrans1D<I, O, K, S, P>
+where
+    E: Dtype,
+    D: Device<E>,
+    ConvTrans1D<I, O, K, S, P, E, D>: BuildModule<D, E>,
+{
+    type Built = ConvTrans1D<I, O, K, S, P, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// Performs *unbiased* 1d deconvolutions on 2d and 3d sequences.
+///
+/// **Pytorch Equivalent**: `torch.nn.ConvTranspose1d(..., bias=False)`
+///
+/// Generics are the same as [ConvTrans2D], with `KERNEL_SIZE` being the length of the kernel.
+#[derive(Debug, Clone)]
+pub struct ConvTrans1D<
+    const IN_CHAN: usize,
+    const OUT_CHAN: usize,
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize,
+    const PADDING: usize,
+    E: Dtype,
+    D: DeviceStorage,
+> {
+    pub weight: Tensor<Rank3<OUT_CHAN, IN_CHAN, KERNEL_SIZE>, E, D>,
+}
+
+impl<const I: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D>
+    TensorCollection<E, D> for ConvTrans1D<I, O, K, S, P, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+    D: Device<E>,
+{
+    type To<E2: Dtype, D2: Device<E2>> = ConvTrans1D<I, O, K, S, P, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            Self::tensor(
+                "weight",
+                |s| &s.weight,
+                |s| &mut s.weight,
+                TensorOptions::reset_with(|t| {
+                    let b = E::ONE / E::from_usize(I * K).unwrap().sqrt();
+                    t.try_fill_with_distr(rand_distr::Uniform::new(-b, b))
+                }),
+            ),
+            |weight| ConvTrans1D { weight },
+        )
+    }
+}
+
+impl<const C: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D, Img>
+    Module<Img> for ConvTrans1D<C, O, K, S, P, E, D>
+where
+    E: Dtype,
+    D: Device<E>,
+    Img: TryConvTrans1DTo<Tensor<Rank3<O, C, K>, E, D>, S, P> + HasErr<Err = D::Err>,
+{
+    type Output = Img::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Img) -> Result<Self::Output, D::Err> {
+        x.try_convtrans1d_to(self.weight.clone())
+    }
+}
+
+impl<const I: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D>
+    NonMutableModule for ConvTrans1D<I, O, K, S, P, E, D>
+where
+    E: Dtype,
+    D: DeviceStorage,
+{
+}
+
+impl<const I: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D>
+    BuildOnDevice<D, E> for builder::ConvTrans3D<I, O, K, S, P>
+where
+    E: Dtype,
+    D: Device<E>,
+    ConvTrans3D<I, O, K, S, P, E, D>: BuildModule<D, E>,
+{
+    type Built = ConvTrans3D<I, O, K, S, P, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// Performs *unbiased* 3d deconvolutions on 4d and 5d volumes.
+///
+/// **Pytorch Equivalent**: `torch.nn.ConvTranspose3d(..., bias=False)`
+///
+/// Generics are the same as [ConvTrans2D], with `KERNEL_SIZE` being applied to depth, height
+/// and width.
+#[derive(Debug, Clone)]
+pub struct ConvTrans3D<
+    const IN_CHAN: usize,
+    const OUT_CHAN: usize,
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize,
+    const PADDING: usize,
+    E: Dtype,
+    D: DeviceStorage,
+> {
+    pub weight: Tensor<Rank5<OUT_CHAN, IN_CHAN, KERNEL_SIZE, KERNEL_SIZE, KERNEL_SIZE>, E, D>,
+}
+
+impl<const I: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D>
+    TensorCollection<E, D> for ConvTrans3D<I, O, K, S, P, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+    D: Device<E>,
+{
+    type To<E2: Dtype, D2: Device<E2>> = ConvTrans3D<I, O, K, S, P, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            Self::tensor(
+                "weight",
+                |s| &s.weight,
+                |s| &mut s.weight,
+                TensorOptions::reset_with(|t| {
+                    let b = E::ONE / E::from_usize(I * K * K * K).unwrap().sqrt();
+                    t.try_fill_with_distr(rand_distr::Uniform::new(-b, b))
+                }),
+            ),
+            |weight| ConvTrans3D { weight },
+        )
+    }
+}
+
+impl<const C: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D, Img>
+    Module<Img> for ConvTrans3D<C, O, K, S, P, E, D>
+where
+    E: Dtype,
+    D: Device<E>,
+    Img: TryConvTrans3DTo<Tensor<Rank5<O, C, K, K, K>, E, D>, S, P> + HasErr<Err = D::Err>,
+{
+    type Output = Img::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Img) -> Result<Self::Output, D::Err> {
+        x.try_convtrans3d_to(self.weight.clone())
+    }
+}
+
+impl<const I: usize, const O: usize, const K: usize, const S: usize, const P: usize, E, D>
+    NonMutableModule for ConvTrans3D<I, O, K, S, P, E, D>
+where
+    E: Dtype,
+    D: DeviceStorage,
+{
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -117,22 +296,25 @@ mod tests {
         tests::*,
     };
 
-    use super::{builder::ConvTrans2D, *};
+    use super::{
+        builder::{ConvTrans1D, ConvTrans2D, ConvTrans3D},
+        *,
+    };
 
     #[rustfmt::skip]
     #[test]
     fn test_forward_3d_sizes() {
         let dev: TestDevice = Default::default();
         let x = dev.zeros::<Rank3<3, 8, 8>>();
-        let _: Tensor<Rank3<2, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<4, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 3>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<4, 9, 9>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 2>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<4, 11, 11>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 4>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<2, 17, 17>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<2, 24, 24>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 3>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<2, 8, 8>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 1>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<2, 6, 6>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 2>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank3<2, 13, 13>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2, 2>, TestDtype>().forward(x.clone());
+        let _: Tensor<Rank3<2, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<4, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<4, 9, 9>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<4, 11, 11>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 4>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<2, 17, 17>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<2, 24, 24>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<2, 8, 8>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 1>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<2, 6, 6>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<2, 13, 13>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2, 2>, TestDtype>().forward(x.clone()).realize();
     }
 
     #[rustfmt::skip]
@@ -140,15 +322,15 @@ mod tests {
     fn test_forward_4d_sizes() {
         let dev: TestDevice = Default::default();
         let x = dev.zeros::<Rank4<5, 3, 8, 8>>();
-        let _: Tensor<Rank4<5, 2, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 4, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 3>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 4, 9, 9>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 2>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 4, 11, 11>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 4>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 2, 17, 17>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 2, 24, 24>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 3>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 2, 8, 8>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 1>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 2, 6, 6>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 2>, TestDtype>().forward(x.clone());
-        let _: Tensor<Rank4<5, 2, 13, 13>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2, 2>, TestDtype>().forward(x.clone());
+        let _: Tensor<Rank4<5, 2, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 4, 10, 10>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 4, 9, 9>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 4, 11, 11>, _, _, _> = dev.build_module::<ConvTrans2D<3, 4, 4>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 2, 17, 17>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 2, 24, 24>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 2, 8, 8>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 1>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 2, 6, 6>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 1, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 2, 13, 13>, _, _, _> = dev.build_module::<ConvTrans2D<3, 2, 3, 2, 2>, TestDtype>().forward(x.clone()).realize();
     }
 
     #[test]
@@ -158,7 +340,8 @@ mod tests {
         type B = ConvTrans2D<2, 1, 3>;
         let _: Tensor<Rank3<1, 10, 10>, _, _> = dev
             .build_module::<(A, B), TestDtype>()
-            .forward(dev.zeros::<Rank3<4, 6, 6>>());
+            .forward(dev.zeros::<Rank3<4, 6, 6>>())
+            .realize();
     }
 
     #[test]
@@ -170,7 +353,8 @@ mod tests {
         let dev = Cpu::default();
         let _: Tensor<Rank3<1, 10, 10>, _, _> = dev
             .build_module::<(C, B, A), TestDtype>()
-            .forward_mut(dev.zeros::<Rank3<1, 8, 8>>());
+            .forward_mut(dev.zeros::<Rank3<1, 8, 8>>())
+            .realize();
     }
 
     #[test]
@@ -194,4 +378,33 @@ mod tests {
 
         assert_ne!(weight_init.array(), m.weight.array());
     }
+
+    #[rustfmt::skip]
+    #[test]
+    fn test_forward_convtrans1d_sizes() {
+        let dev: TestDevice = Default::default();
+        let x = dev.zeros::<Rank2<3, 8>>();
+        let _: Tensor<Rank2<2, 10>, _, _, _> = dev.build_module::<ConvTrans1D<3, 2, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank2<4, 9>, _, _, _> = dev.build_module::<ConvTrans1D<3, 4, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank2<2, 17>, _, _, _> = dev.build_module::<ConvTrans1D<3, 2, 3, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank2<2, 13>, _, _, _> = dev.build_module::<ConvTrans1D<3, 2, 3, 2, 2>, TestDtype>().forward(x.clone()).realize();
+
+        let x = dev.zeros::<Rank3<5, 3, 8>>();
+        let _: Tensor<Rank3<5, 2, 10>, _, _, _> = dev.build_module::<ConvTrans1D<3, 2, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank3<5, 2, 8>, _, _, _> = dev.build_module::<ConvTrans1D<3, 2, 3, 1, 1>, TestDtype>().forward(x.clone()).realize();
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn test_forward_convtrans3d_sizes() {
+        let dev: TestDevice = Default::default();
+        let x = dev.zeros::<Rank4<3, 4, 5, 6>>();
+        let _: Tensor<Rank4<2, 6, 7, 8>, _, _, _> = dev.build_module::<ConvTrans3D<3, 2, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<2, 9, 11, 13>, _, _, _> = dev.build_module::<ConvTrans3D<3, 2, 3, 2>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank4<2, 4, 5, 6>, _, _, _> = dev.build_module::<ConvTrans3D<3, 2, 3, 1, 1>, TestDtype>().forward(x.clone()).realize();
+
+        let x = dev.zeros::<Rank5<5, 3, 4, 5, 6>>();
+        let _: Tensor<Rank5<5, 2, 6, 7, 8>, _, _, _> = dev.build_module::<ConvTrans3D<3, 2, 3>, TestDtype>().forward(x.clone()).realize();
+        let _: Tensor<Rank5<5, 2, 7, 9, 11>, _, _, _> = dev.build_module::<ConvTrans3D<3, 2, 3, 2, 1>, TestDtype>().forward(x.clone()).realize();
+    }
 }
@@ -0,0 +1,399 @@
+use super::{tensor_collection::*, ToDevice};
+
+use crate::{
+    optim::{Optimizer, OptimizerUpdateError},
+    shapes::{Dtype, Shape},
+    tensor::{Gradients, Tensor},
+    tensor_ops::{axpy::AxpyKernel, Device},
+};
+
+use std::{marker::PhantomData, vec::Vec};
+
+/// Trains a model on several shards of a batch at the same time.
+///
+/// Holds `N` replicas of a module, the first of which is the **primary** replica.
+/// Each replica can live on its own device instance (e.g. one [crate::tensor::Cpu] per
+/// replica). Every call to [DataParallel::step()]:
+///
+/// 1. Runs a closure on each replica with one shard of the batch, each on its own thread.
+///    The closure runs forward & backward, and returns the replica's [Gradients].
+/// 2. Sums the gradients of all replicas into the gradients of the primary replica.
+/// 3. Updates the primary replica using the [Optimizer].
+/// 4. Copies all tensors of the primary replica back into the other replicas.
+///
+/// Since gradients are **summed**, a loss that is a mean over a shard results in a
+/// gradient that is `N` times larger than the mean over the whole batch. Divide the
+/// loss (or learning rate) by the number of shards if that is not desired.
+///
+/// The optimizer should be constructed with [DataParallel::primary()].
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::Sgd};
+/// # let dev: Cpu = Default::default();
+/// type Model = Linear<3, 2>;
+/// let model = dev.build_module::<Model, f32>();
+/// let mut dp = DataParallel::new(model, &[Cpu::seed_from_u64(1), Cpu::seed_from_u64(2)]);
+/// assert_eq!(dp.replicas().len(), 3);
+///
+/// let mut opt = Sgd::new(dp.primary(), Default::default());
+/// let x: Tensor<Rank2<12, 3>, f32, _> = dev.sample_normal();
+/// let shards = [
+///     x.clone().slice((0..4, ..)).realize::<Rank2<4, 3>>(),
+///     x.clone().slice((4..8, ..)).realize::<Rank2<4, 3>>(),
+///     x.slice((8..12, ..)).realize::<Rank2<4, 3>>(),
+/// ];
+/// dp.step(&mut opt, shards, |model, x| {
+///     let y = model.forward_mut(x.leaky_traced());
+///     y.square().mean().backward()
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct DataParallel<M, E: Dtype, D: Device<E>> {
+    replicas: Vec<M>,
+    marker: PhantomData<(E, D)>,
+}
+
+impl<M, E: Dtype, D: Device<E>> DataParallel<M, E, D>
+where
+    M: TensorCollection<E, D, To<E, D> = M>,
+{
+    /// Creates `1 + devices.len()` replicas. `model` is used as the primary replica
+    /// as is, and a copy of it is created on each device in `devices`.
+    pub fn new(model: M, devices: &[D]) -> Self {
+        Self::try_new(model, devices).unwrap()
+    }
+
+    /// Fallible version of [DataParallel::new]
+    pub fn try_new(model: M, devices: &[D]) -> Result<Self, D::Err> {
+        let mut replicas = Vec::with_capacity(1 + devices.len());
+        for dev in devices {
+            replicas.push(model.try_to_device(dev)?);
+        }
+        replicas.insert(0, model);
+        Ok(Self {
+            replicas,
+            marker: PhantomData,
+        })
+    }
+
+    /// The replica that the optimizer updates.
+    pub fn primary(&self) -> &M {
+        &self.replicas[0]
+    }
+
+    /// Mutable access to the primary replica. Call [DataParallel::broadcast()]
+    /// after modifying it to update the other replicas.
+    pub fn primary_mut(&mut self) -> &mut M {
+        &mut self.replicas[0]
+    }
+
+    /// All replicas, starting with the primary replica.
+    pub fn replicas(&self) -> &[M] {
+        &self.replicas
+    }
+
+    /// Returns the primary replica, dropping all others.
+    pub fn into_primary(mut self) -> M {
+        self.replicas.swap_remove(0)
+    }
+
+    /// Copies all tensors (including non-trainable ones, like batch norm's running
+    /// statistics) of the primary replica into all other replicas.
+    pub fn broadcast(&mut self) {
+        self.try_broadcast().unwrap()
+    }
+
+    /// Fallible version of [DataParallel::broadcast]
+    pub fn try_broadcast(&mut self) -> Result<(), D::Err> {
+        let (primary, others) = self.replicas.split_first_mut().unwrap();
+        for replica in others {
+            M::iter_tensors(&mut RecursiveWalker {
+                m: (replica, &*primary),
+                f: &mut Broadcaster,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Runs `f` on each replica with one shard in parallel, sums the gradients into the
+    /// primary replica's gradients, updates the primary replica with `opt`,
+    /// and broadcasts the result to the other replicas.
+    ///
+    /// The replicas are synced even if `opt` returns [OptimizerUpdateError::UnusedParams],
+    /// since the used parameters of the primary replica are still updated.
+    ///
+    /// If there are less shards than replicas, the last replicas are not used for
+    /// computing gradients.
+    ///
+    /// **Panics** if there are no shards or more shards than replicas.
+    pub fn step<B, O, F>(&mut self, opt: &mut O, shards: impl IntoIterator<Item = B>, f: F)
+    where
+        B: Send,
+        M: Send,
+        O: Optimizer<M, D, E>,
+        F: Fn(&mut M, B) -> Gradients<E, D> + Sync,
+    {
+        self.try_step(opt, shards, f).unwrap()
+    }
+
+    /// Fallible version of [DataParallel::step]
+    pub fn try_step<B, O, F>(
+        &mut self,
+        opt: &mut O,
+        shards: impl IntoIterator<Item = B>,
+        f: F,
+    ) -> Result<(), OptimizerUpdateError<D>>
+    where
+        B: Send,
+        M: Send,
+        O: Optimizer<M, D, E>,
+        F: Fn(&mut M, B) -> Gradients<E, D> + Sync,
+    {
+        let shards: Vec<B> = shards.into_iter().collect();
+        assert!(
+            !shards.is_empty(),
+            "DataParallel::step requires at least one shard"
+        );
+        assert!(
+            shards.len() <= self.replicas.len(),
+            "Got {} shards for {} replicas",
+            shards.len(),
+            self.replicas.len()
+        );
+
+        let f = &f;
+        let mut grads: Vec<Gradients<E, D>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .replicas
+                .iter_mut()
+                .zip(shards)
+                .map(|(replica, shard)| scope.spawn(move || f(replica, shard)))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let (primary, others) = self.replicas.split_first_mut().unwrap();
+        let (primary_grads, other_grads) = grads.split_first_mut().unwrap();
+        for (replica, replica_grads) in others.iter().zip(other_grads.iter()) {
+            let mut op = GradientSummer {
+                dst: primary_grads,
+                src: replica_grads,
+            };
+            M::iter_tensors(&mut RecursiveWalker {
+                m: (&*primary, replica),
+                f: &mut op,
+            })
+            .map_err(OptimizerUpdateError::DeviceError)?;
+        }
+
+        // the primary is still updated when some parameters are unused, so the
+        // replicas are synced before that error is returned
+        let updated = opt.update(primary, primary_grads);
+        let broadcast = self.try_broadcast();
+        updated?;
+        broadcast.map_err(OptimizerUpdateError::DeviceError)
+    }
+}
+
+/// Adds the gradients of the second module's parameters to the gradients of the first's.
+struct GradientSummer<'a, E: Dtype, D: Device<E>> {
+    dst: &'a mut Gradients<E, D>,
+    src: &'a Gradients<E, D>,
+}
+
+impl<'a, E: Dtype, D: Device<E>> TensorVisitor<E, D> for GradientSummer<'a, E, D> {
+    type Viewer = (ViewTensorRef, ViewTensorRef);
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        (dst, src): (&Tensor<S, E, D>, &Tensor<S, E, D>),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if opts.do_gradient_update && self.src.get_ref_checked(src).is_some() {
+            // the replica's gradient lives on the replica's device
+            let src_grad = self.src.get(src).try_to_device(&dst.device)?;
+            let dst_grad = self.dst.get_or_alloc_mut(dst)?;
+            AxpyKernel::forward(&dst.device, dst_grad, E::ONE, &src_grad.data, E::ONE)?;
+        }
+        Ok(None)
+    }
+}
+
+/// Copies the second module's tensors into the first's.
+struct Broadcaster;
+
+impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for Broadcaster {
+    type Viewer = (ViewTensorMut, ViewTensorRef);
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        _opts: TensorOptions<S, E, D>,
+        (dst, src): (&mut Tensor<S, E, D>, &Tensor<S, E, D>),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        // copy to the replica's device first, so the replica never shares the
+        // primary replica's buffer
+        dst.try_copy_from_tensor(&src.try_to_device(&dst.device)?)?;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, ModuleMut, ZeroGrads},
+        optim::*,
+        shapes::*,
+        tensor::*,
+        tensor_ops::*,
+        tests::*,
+    };
+
+    #[test]
+    fn test_data_parallel_matches_full_batch() {
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<3, 4>, ReLU, Linear<4, 2>);
+        let model = dev.build_module::<Model, TestDtype>();
+        let mut single = model.clone();
+
+        let x: Tensor<Rank2<6, 3>, TestDtype, _> = dev.sample_normal();
+        let cfg = SgdConfig {
+            lr: 0.1,
+            momentum: None,
+            weight_decay: None,
+        };
+
+        // full batch, with a loss that sums the per shard means
+        let mut opt = Sgd::new(&single, cfg);
+        let grads = single.alloc_grads();
+        let y = single.forward_mut(x.clone().traced(grads));
+        let y: Tensor<Rank3<3, 2, 2>, _, _, _> = y.reshape();
+        let grads = y.square().mean::<Rank1<3>, _>().sum().backward();
+        opt.update(&mut single, &grads).unwrap();
+
+        let devices = [dev.clone(), dev.clone()];
+        let mut dp = DataParallel::new(model, &devices);
+        let mut opt = Sgd::new(dp.primary(), cfg);
+        let shards = [
+            x.clone().slice((0..2, ..)).realize::<Rank2<2, 3>>(),
+            x.clone().slice((2..4, ..)).realize::<Rank2<2, 3>>(),
+            x.clone().slice((4..6, ..)).realize::<Rank2<2, 3>>(),
+        ];
+        dp.step(&mut opt, shards, |m, x| {
+            let y = m.forward_mut(x.leaky_traced());
+            y.square().mean().backward()
+        });
+
+        for replica in dp.replicas() {
+            assert_close!(replica.0.weight.array(), single.0.weight.array());
+            assert_close!(replica.0.bias.array(), single.0.bias.array());
+            assert_close!(replica.2.weight.array(), single.2.weight.array());
+            assert_close!(replica.2.bias.array(), single.2.bias.array());
+        }
+    }
+
+    #[test]
+    fn test_data_parallel_keeps_replica_ids() {
+        let dev: Cpu = Default::default();
+        let devices = [Cpu::seed_from_u64(1)];
+        let model = dev.build_module::<Linear<2, 2>, f32>();
+        let mut dp = DataParallel::new(model, &devices);
+        let ids: Vec<_> = dp.replicas().iter().map(|m| m.weight.id).collect();
+        assert_ne!(ids[0], ids[1]);
+
+        let mut opt = Sgd::new(dp.primary(), Default::default());
+        let shards: [Tensor<Rank2<3, 2>, f32, _>; 2] =
+            [dev.sample_normal(), devices[0].sample_normal()];
+        dp.step(&mut opt, shards, |m, x| {
+            m.forward_mut(x.leaky_traced()).sum().backward()
+        });
+        let new_ids: Vec<_> = dp.replicas().iter().map(|m| m.weight.id).collect();
+        assert_eq!(ids, new_ids);
+        assert_eq!(
+            dp.replicas()[0].weight.array(),
+            dp.replicas()[1].weight.array()
+        );
+    }
+
+    #[test]
+    fn test_data_parallel_replicas_own_their_data() {
+        let dev: Cpu = Default::default();
+        let devices = [Cpu::seed_from_u64(1)];
+        let model = dev.build_module::<Linear<2, 2>, f32>();
+        let mut dp = DataParallel::new(model, &devices);
+
+        let mut opt = Sgd::new(dp.primary(), Default::default());
+        let shards: [Tensor<Rank2<3, 2>, f32, _>; 2] =
+            [dev.sample_normal(), devices[0].sample_normal()];
+        dp.step(&mut opt, shards, |m, x| {
+            m.forward_mut(x.leaky_traced()).sum().backward()
+        });
+        let (primary, replica) = (&dp.replicas()[0], &dp.replicas()[1]);
+        assert!(!std::sync::Arc::ptr_eq(
+            &primary.weight.data,
+            &replica.weight.data
+        ));
+        assert_eq!(primary.weight.array(), replica.weight.array());
+    }
+
+    #[test]
+    fn test_data_parallel_fewer_shards() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<2, 2>, TestDtype>();
+        let mut single = model.clone();
+        let x: Tensor<Rank2<3, 2>, TestDtype, _> = dev.sample_normal();
+
+        let mut opt = Sgd::new(&single, Default::default());
+        let grads = single.forward_mut(x.leaky_trace()).sum().backward();
+        opt.update(&mut single, &grads).unwrap();
+
+        let mut dp = DataParallel::new(model, &[dev.clone(), dev.clone()]);
+        let mut opt = Sgd::new(dp.primary(), Default::default());
+        dp.step(&mut opt, [x], |m, x| {
+            m.forward_mut(x.leaky_traced()).sum().backward()
+        });
+        for replica in dp.replicas() {
+            assert_close!(replica.weight.array(), single.weight.array());
+        }
+    }
+
+    #[test]
+    fn test_data_parallel_unused_params() {
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<2, 2>, Linear<2, 2>);
+        let model = dev.build_module::<Model, TestDtype>();
+        let before = model.0.weight.array();
+
+        let mut dp = DataParallel::new(model, &[dev.clone(), dev.clone()]);
+        let mut opt = Sgd::new(dp.primary(), Default::default());
+        let x: Tensor<Rank2<3, 2>, TestDtype, _> = dev.sample_normal();
+        let result = dp.try_step(&mut opt, [x.clone(), x], |m, x| {
+            m.0.forward_mut(x.leaky_traced()).sum().backward()
+        });
+        assert!(matches!(result, Err(OptimizerUpdateError::UnusedParams(_))));
+
+        let (primary, replica) = (&dp.replicas()[0], &dp.replicas()[1]);
+        assert_ne!(primary.0.weight.array(), before);
+        assert_eq!(primary.0.weight.array(), replica.0.weight.array());
+        assert_eq!(primary.0.bias.array(), replica.0.bias.array());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_data_parallel_too_many_shards() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<2, 2>, TestDtype>();
+        let mut dp = DataParallel::new(model, &[]);
+        let mut opt = Sgd::new(dp.primary(), Default::default());
+        let x: Tensor<Rank1<2>, TestDtype, _> = dev.zeros();
+        dp.step(&mut opt, [x.clone(), x], |m, x| {
+            m.forward_mut(x.leaky_traced()).sum().backward()
+        });
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    shapes::Const,
+    tensor_ops::{TryMaxPool2DWithIndices, TryMaxUnpool2D},
+};
+
+use super::{Module, NonMutableModule, ZeroSizedModule};
+
+/// Max pool with 2d kernel that also returns the position of each maximum, for use
+/// with [MaxUnpool2D]. Operates on images (3d) and batches of images (4d), and outputs
+/// a tuple of the pooled images and a `usize` tensor of indices with the same shape.
+///
+/// Generics are the same as [super::modules::MaxPool2D].
+///
+/// **Pytorch equivalent**: `torch.nn.MaxPool2d(..., return_indices=True)`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = (MaxPool2DWithIndices<2, 2>, MaxUnpool2D<4>);
+/// let m: Model = Default::default();
+/// let x: Tensor<Rank4<2, 3, 4, 4>, f32, _> = dev.sample_normal();
+/// let y: Tensor<Rank4<2, 3, 4, 4>, f32, _> = m.forward(x);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct MaxPool2DWithIndices<
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize = 1,
+    const PADDING: usize = 0,
+    const DILATION: usize = 1,
+>;
+
+/// Undoes [MaxPool2DWithIndices] by scattering the pooled values back to `H`x`W` images
+/// at their indices, and filling the rest with zeros. `W` defaults to `H`.
+///
+/// Takes a tuple of the pooled images and their indices, as output by [MaxPool2DWithIndices].
+///
+/// **Pytorch equivalent**: `torch.nn.MaxUnpool2d` called with `output_size=(H, W)`
+#[derive(Debug, Default, Clone)]
+pub struct MaxUnpool2D<const H: usize, const W: usize = H>;
+
+impl<const K: usize, const S: usize, const P: usize, const L: usize> ZeroSizedModule
+    for MaxPool2DWithIndices<K, S, P, L>
+{
+}
+impl<const K: usize, const S: usize, const P: usize, const L: usize> NonMutableModule
+    for MaxPool2DWithIndices<K, S, P, L>
+{
+}
+
+impl<
+        const K: usize,
+        const S: usize,
+        const P: usize,
+        const L: usize,
+        Img: TryMaxPool2DWithIndices<Const<K>, Const<S>, Const<P>, Const<L>>,
+    > Module<Img> for MaxPool2DWithIndices<K, S, P, L>
+{
+    type Output = (Img::Pooled, Img::Indices);
+    type Error = Img::Error;
+
+    fn try_forward(&self, x: Img) -> Result<Self::Output, Self::Error> {
+        x.try_max_pool2d_with_indices(Const, Const, Const, Const)
+    }
+}
+
+impl<const H: usize, const W: usize> ZeroSizedModule for MaxUnpool2D<H, W> {}
+impl<const H: usize, const W: usize> NonMutableModule for MaxUnpool2D<H, W> {}
+
+impl<const H: usize, const W: usize, Img: TryMaxUnpool2D<Const<H>, Const<W>>>
+    Module<(Img, Img::Indices)> for MaxUnpool2D<H, W>
+{
+    type Output = Img::Unpooled;
+    type Error = Img::Error;
+
+    fn try_forward(&self, (x, indices): (Img, Img::Indices)) -> Result<Self::Output, Self::Error> {
+        x.try_max_unpool2d(indices, Const, Const)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_max_pool_with_indices_sizes() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<5, 3, 10, 10>, TestDtype, _> = dev.zeros();
+        let (y, idx) = MaxPool2DWithIndices::<3, 2>::default().forward(x.clone());
+        let _: Tensor<Rank4<5, 3, 4, 4>, _, _> = y.realize();
+        let _: Tensor<Rank4<5, 3, 4, 4>, usize, _> = idx.realize();
+        let (y, idx) =
+            MaxPool2DWithIndices::<2, 2, 1>::default().forward(x.sum::<Rank3<3, 10, 10>, _>());
+        let _: Tensor<Rank3<3, 6, 6>, _, _> = y.realize();
+        let _: Tensor<Rank3<3, 6, 6>, usize, _> = idx.realize();
+    }
+
+    #[test]
+    fn test_max_unpool_forward_backward() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<2, 3, 6, 4>, TestDtype, _> = dev.sample_normal();
+        let m = <(MaxPool2DWithIndices<2, 2>, MaxUnpool2D<6, 4>)>::default();
+        let y = m.forward(x.leaky_trace());
+
+        // unpooling keeps the maximum of each window in place, and zeros the rest
+        let pooled = x
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank4<2, 3, 3, 2>>();
+        assert_close_to_tensor!(
+            y.retaped::<NoneTape>().abs().sum::<Rank0, _>(),
+            pooled.retaped::<NoneTape>().abs().sum::<Rank0, _>()
+        );
+
+        // and gradients only flow back to the maxima
+        let g_unpool = y.sum().backward();
+        let g_pool = pooled.sum().backward();
+        assert_close_to_tensor!(g_unpool.get(&x), g_pool.get(&x));
+    }
+}
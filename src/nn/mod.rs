@@ -30,7 +30,8 @@
 //! and [Module::try_forward].
 //!
 //! Similar to fallible tensor_ops, the main purpose of this is to handle out of memory
-//! errors at the device level.
+//! errors at the device level, as well as [crate::shapes::ShapeError]s from inputs whose
+//! runtime dimensions don't match.
 //!
 //! # Initializing
 //!
@@ -91,6 +92,12 @@
 //! ema_model.ema(&model, 0.001);
 //! ```
 //!
+//! # Data parallel training
+//!
+//! [DataParallel] keeps one replica of a model per device, computes gradients for
+//! each shard of a batch on its own thread, and updates all replicas with the summed
+//! gradients. See [DataParallel] for an example.
+//!
 //! # Resetting parameters
 //!
 //! All modules implement [ResetParams], which allows you to reset a module back to a randomized
@@ -184,12 +191,15 @@ mod zero_grads;
 mod module;
 
 mod activations;
+mod adaptive_pool;
 mod add_into;
 mod batchnorm1d;
 mod batchnorm2d;
 mod bias2d;
 mod conv;
 mod convtrans;
+#[cfg(feature = "std")]
+mod data_parallel;
 mod dropout;
 mod ema;
 mod embedding;
@@ -198,9 +208,12 @@ mod generalized_residual;
 mod impl_module_for_tuples;
 mod layer_norm;
 mod linear;
+mod max_unpool2d;
 #[cfg(feature = "numpy")]
 mod npz;
+mod pool1d;
 mod pool2d;
+mod pool3d;
 mod pool_global;
 pub mod prelu;
 mod repeated;
@@ -222,6 +235,8 @@ pub use tensor_collection::*;
 
 #[cfg(feature = "safetensors")]
 pub use self::safetensors::{LoadFromSafetensors, SaveToSafetensors};
+#[cfg(feature = "std")]
+pub use data_parallel::DataParallel;
 pub use ema::ModelEMA;
 #[cfg(feature = "numpy")]
 pub use npz::{LoadFromNpz, SaveToNpz};
@@ -236,14 +251,16 @@ pub mod modules {
     //! [super::builders] for helpful utilities in creating these
     //! in a device/dtype agnostic way.
     pub use super::activations::*;
+    pub use super::adaptive_pool::{
+        AdaptiveAvgPool1D, AdaptiveAvgPool2D, AdaptiveAvgPool3D, AdaptiveMaxPool1D,
+        AdaptiveMaxPool2D, AdaptiveMaxPool3D,
+    };
     pub use super::add_into::AddInto;
     pub use super::batchnorm1d::BatchNorm1D;
     pub use super::batchnorm2d::BatchNorm2D;
     pub use super::bias2d::Bias2D;
-    #[cfg(feature = "nightly")]
-    pub use super::conv::Conv2D;
-    #[cfg(feature = "nightly")]
-    pub use super::convtrans::ConvTrans2D;
+    pub use super::conv::{Conv1D, Conv2D, Conv3D};
+    pub use super::convtrans::{ConvTrans1D, ConvTrans2D, ConvTrans3D};
     pub use super::dropout::{Dropout, DropoutOneIn};
     pub use super::embedding::Embedding;
     #[cfg(feature = "nightly")]
@@ -251,8 +268,10 @@ pub mod modules {
     pub use super::generalized_residual::GeneralizedResidual;
     pub use super::layer_norm::LayerNorm1D;
     pub use super::linear::Linear;
-    #[cfg(feature = "nightly")]
+    pub use super::max_unpool2d::{MaxPool2DWithIndices, MaxUnpool2D};
+    pub use super::pool1d::{AvgPool1D, MaxPool1D, MinPool1D};
     pub use super::pool2d::{AvgPool2D, MaxPool2D, MinPool2D};
+    pub use super::pool3d::{AvgPool3D, MaxPool3D, MinPool3D};
     pub use super::pool_global::{AvgPoolGlobal, MaxPoolGlobal, MinPoolGlobal};
     pub use super::repeated::Repeated;
     pub use super::residual::Residual;
@@ -272,14 +291,16 @@ pub mod builders {
     //! Simple specification of network structure, without
     //! worrying about device or dtype.
     pub use super::activations::*;
+    pub use super::adaptive_pool::{
+        AdaptiveAvgPool1D, AdaptiveAvgPool2D, AdaptiveAvgPool3D, AdaptiveMaxPool1D,
+        AdaptiveMaxPool2D, AdaptiveMaxPool3D,
+    };
     pub use super::add_into::AddInto;
     pub use super::batchnorm1d::builder::BatchNorm1D;
     pub use super::batchnorm2d::builder::BatchNorm2D;
     pub use super::bias2d::builder::Bias2D;
-    #[cfg(feature = "nightly")]
-    pub use super::conv::builder::Conv2D;
-    #[cfg(feature = "nightly")]
-    pub use super::convtrans::builder::ConvTrans2D;
+    pub use super::conv::builder::{Conv1D, Conv2D, Conv3D};
+    pub use super::convtrans::builder::{ConvTrans1D, ConvTrans2D, ConvTrans3D};
     pub use super::dropout::{Dropout, DropoutOneIn};
     pub use super::embedding::builder::Embedding;
     #[cfg(feature = "nightly")]
@@ -287,8 +308,10 @@ pub mod builders {
     pub use super::generalized_residual::GeneralizedResidual;
     pub use super::layer_norm::builder::LayerNorm1D;
     pub use super::linear::builder::Linear;
-    #[cfg(feature = "nightly")]
+    pub use super::max_unpool2d::{MaxPool2DWithIndices, MaxUnpool2D};
+    pub use super::pool1d::{AvgPool1D, MaxPool1D, MinPool1D};
     pub use super::pool2d::{AvgPool2D, MaxPool2D, MinPool2D};
+    pub use super::pool3d::{AvgPool3D, MaxPool3D, MinPool3D};
     pub use super::pool_global::{AvgPoolGlobal, MaxPoolGlobal, MinPoolGlobal};
     pub use super::prelu::builder::{PReLU, PReLU1D};
     pub use super::repeated::Repeated;
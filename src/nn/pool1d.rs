@@ -0,0 +1,137 @@
+use crate::{
+    shapes::Const,
+    tensor_ops::{Pool2DKind, TryPool1D},
+};
+
+use super::{Module, NonMutableModule, ZeroSizedModule};
+
+/// Average pool with 1d kernel that operates on sequences (2d) and batches of sequences (3d).
+/// Each patch reduces to the average of the values in the patch.
+///
+/// Generics:
+/// - `KERNEL_SIZE`: The size of the kernel applied along the sequences.
+/// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
+/// - `PADDING`: How much zero padding to add around the sequences. Defaults to `0`.
+/// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled length is `usize`.
+#[derive(Debug, Default, Clone)]
+pub struct AvgPool1D<
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize = 1,
+    const PADDING: usize = 0,
+    const DILATION: usize = 1,
+>;
+
+/// Max pool with 1d kernel that operates on sequences (2d) and batches of sequences (3d).
+/// Each patch reduces to the maximum value in that patch.
+///
+/// Generics:
+/// - `KERNEL_SIZE`: The size of the kernel applied along the sequences.
+/// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
+/// - `PADDING`: How much zero padding to add around the sequences. Defaults to `0`.
+/// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled length is `usize`.
+#[derive(Debug, Default, Clone)]
+pub struct MaxPool1D<
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize = 1,
+    const PADDING: usize = 0,
+    const DILATION: usize = 1,
+>;
+
+/// Minimum pool with 1d kernel that operates on sequences (2d) and batches of sequences (3d).
+/// Each patch reduces to the minimum of the values in the patch.
+///
+/// Generics:
+/// - `KERNEL_SIZE`: The size of the kernel applied along the sequences.
+/// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
+/// - `PADDING`: How much zero padding to add around the sequences. Defaults to `0`.
+/// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled length is `usize`.
+#[derive(Debug, Default, Clone)]
+pub struct MinPool1D<
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize = 1,
+    const PADDING: usize = 0,
+    const DILATION: usize = 1,
+>;
+
+macro_rules! impl_pools {
+    ($PoolTy:tt, $Op:expr) => {
+        impl<const K: usize, const S: usize, const P: usize, const L: usize> ZeroSizedModule
+            for $PoolTy<K, S, P, L>
+        {
+        }
+        impl<const K: usize, const S: usize, const P: usize, const L: usize> NonMutableModule
+            for $PoolTy<K, S, P, L>
+        {
+        }
+
+        impl<
+                const K: usize,
+                const S: usize,
+                const P: usize,
+                const L: usize,
+                Inp: TryPool1D<Const<K>, Const<S>, Const<P>, Const<L>>,
+            > Module<Inp> for $PoolTy<K, S, P, L>
+        {
+            type Output = Inp::Pooled;
+            type Error = Inp::Error;
+
+            fn try_forward(&self, x: Inp) -> Result<Self::Output, Self::Error> {
+                x.try_pool1d($Op, Const, Const, Const, Const)
+            }
+        }
+    };
+}
+
+impl_pools!(AvgPool1D, Pool2DKind::Avg);
+impl_pools!(MaxPool1D, Pool2DKind::Max);
+impl_pools!(MinPool1D, Pool2DKind::Min);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::RealizeTo, tests::*};
+
+    #[test]
+    fn test_max_forward_2d_sizes() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<3, 10>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank2<3, 8>, _, _> = MaxPool1D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank2<3, 4>, _, _> =
+            MaxPool1D::<3, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank2<3, 10>, _, _> =
+            MaxPool1D::<3, 1, 1>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank2<3, 6>, _, _> = MaxPool1D::<3, 1, 0, 2>::default()
+            .forward(x.clone())
+            .realize();
+    }
+
+    #[test]
+    fn test_avg_forward_3d_sizes() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<5, 3, 10>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank3<5, 3, 9>, _, _> =
+            AvgPool1D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<5, 3, 3>, _, _> =
+            AvgPool1D::<3, 3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<5, 3, 6>, _, _> =
+            AvgPool1D::<3, 2, 2>::default().forward(x.clone()).realize();
+    }
+
+    #[test]
+    fn test_min_pool1d_forward() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[3.0, -1.0, 2.0, 0.5, 4.0, -2.0]])
+            .to_dtype::<TestDtype>();
+        let y = MinPool1D::<2, 2>::default()
+            .forward(x)
+            .realize::<Rank2<1, 3>>();
+        assert_close_to_literal!(y, [[-1.0, 0.5, -2.0]]);
+    }
+}
@@ -1,4 +1,3 @@
-#[cfg(feature = "nightly")]
 use crate::{
     shapes::Const,
     tensor_ops::{Pool2DKind, TryPool2D},
@@ -15,6 +14,8 @@ use super::{BuildModule, Module, NonMutableModule, ZeroSizedModule};
 /// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
 /// - `PADDING`: How much zero padding to add around the images. Defaults to `0`.
 /// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled height and width are `usize`.
 #[derive(Debug, Default, Clone)]
 pub struct AvgPool2D<
     const KERNEL_SIZE: usize,
@@ -31,6 +32,8 @@ pub struct AvgPool2D<
 /// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
 /// - `PADDING`: How much zero padding to add around the images. Defaults to `0`.
 /// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled height and width are `usize`.
 #[derive(Debug, Default, Clone)]
 pub struct MaxPool2D<
     const KERNEL_SIZE: usize,
@@ -47,6 +50,8 @@ pub struct MaxPool2D<
 /// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
 /// - `PADDING`: How much zero padding to add around the images. Defaults to `0`.
 /// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled height and width are `usize`.
 #[derive(Debug, Default, Clone)]
 pub struct MinPool2D<
     const KERNEL_SIZE: usize,
@@ -66,7 +71,6 @@ macro_rules! impl_pools {
         {
         }
 
-        #[cfg(feature = "nightly")]
         impl<
                 const K: usize,
                 const S: usize,
@@ -89,40 +93,53 @@ impl_pools!(AvgPool2D, Pool2DKind::Avg);
 impl_pools!(MaxPool2D, Pool2DKind::Max);
 impl_pools!(MinPool2D, Pool2DKind::Min);
 
-#[cfg(feature = "nightly")]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{shapes::*, tensor::*, tests::*};
+    use crate::{shapes::*, tensor::*, tensor_ops::RealizeTo, tests::*};
 
     #[test]
     fn test_max_forward_3d_sizes() {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank3<3, 10, 10>, TestDtype, _> = dev.zeros();
-        let _: Tensor<Rank3<3, 8, 8>, _, _> = MaxPool2D::<3>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 9, 9>, _, _> = MaxPool2D::<2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 7, 7>, _, _> = MaxPool2D::<4>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 4, 4>, _, _> = MaxPool2D::<3, 2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 3, 3>, _, _> = MaxPool2D::<3, 3>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 10, 10>, _, _> = MaxPool2D::<3, 1, 1>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 12, 12>, _, _> = MaxPool2D::<3, 1, 2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 6, 6>, _, _> = MaxPool2D::<3, 2, 2>::default().forward(x.clone());
+        let _: Tensor<Rank3<3, 8, 8>, _, _> =
+            MaxPool2D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 9, 9>, _, _> =
+            MaxPool2D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 7, 7>, _, _> =
+            MaxPool2D::<4>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 4, 4>, _, _> =
+            MaxPool2D::<3, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 3, 3>, _, _> =
+            MaxPool2D::<3, 3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 10, 10>, _, _> =
+            MaxPool2D::<3, 1, 1>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 12, 12>, _, _> =
+            MaxPool2D::<3, 1, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 6, 6>, _, _> =
+            MaxPool2D::<3, 2, 2>::default().forward(x.clone()).realize();
     }
 
     #[test]
     fn test_max_forward_4d_sizes() {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank4<5, 3, 10, 10>, TestDtype, _> = dev.zeros();
-        let _: Tensor<Rank4<5, 3, 7, 7>, _, _> = MaxPool2D::<4>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 8, 8>, _, _> = MaxPool2D::<3>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 9, 9>, _, _> = MaxPool2D::<2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 4, 4>, _, _> = MaxPool2D::<3, 2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 3, 3>, _, _> = MaxPool2D::<3, 3>::default().forward(x.clone());
+        let _: Tensor<Rank4<5, 3, 7, 7>, _, _> =
+            MaxPool2D::<4>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 8, 8>, _, _> =
+            MaxPool2D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 9, 9>, _, _> =
+            MaxPool2D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 4, 4>, _, _> =
+            MaxPool2D::<3, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 3, 3>, _, _> =
+            MaxPool2D::<3, 3>::default().forward(x.clone()).realize();
         let _: Tensor<Rank4<5, 3, 10, 10>, _, _> =
-            MaxPool2D::<3, 1, 1>::default().forward(x.clone());
+            MaxPool2D::<3, 1, 1>::default().forward(x.clone()).realize();
         let _: Tensor<Rank4<5, 3, 12, 12>, _, _> =
-            MaxPool2D::<3, 1, 2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 6, 6>, _, _> = MaxPool2D::<3, 2, 2>::default().forward(x.clone());
+            MaxPool2D::<3, 1, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 6, 6>, _, _> =
+            MaxPool2D::<3, 2, 2>::default().forward(x.clone()).realize();
     }
 
     #[test]
@@ -132,38 +149,52 @@ mod tests {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank3<1, 10, 10>, TestDtype, _> = dev.zeros();
 
-        let _: Tensor<Rank3<1, 6, 6>, _, _> = <(A, A)>::default().forward(x.clone());
-        let _: Tensor<Rank3<1, 8, 8>, _, _> = <(A, A, B)>::default().forward(x.clone());
+        let _: Tensor<Rank3<1, 6, 6>, _, _> = <(A, A)>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<1, 8, 8>, _, _> = <(A, A, B)>::default().forward(x.clone()).realize();
     }
 
     #[test]
     fn test_min_forward_3d_sizes() {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank3<3, 10, 10>, TestDtype, _> = dev.zeros();
-        let _: Tensor<Rank3<3, 8, 8>, _, _> = MinPool2D::<3>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 9, 9>, _, _> = MinPool2D::<2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 7, 7>, _, _> = MinPool2D::<4>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 4, 4>, _, _> = MinPool2D::<3, 2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 3, 3>, _, _> = MinPool2D::<3, 3>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 10, 10>, _, _> = MinPool2D::<3, 1, 1>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 12, 12>, _, _> = MinPool2D::<3, 1, 2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 6, 6>, _, _> = MinPool2D::<3, 2, 2>::default().forward(x.clone());
+        let _: Tensor<Rank3<3, 8, 8>, _, _> =
+            MinPool2D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 9, 9>, _, _> =
+            MinPool2D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 7, 7>, _, _> =
+            MinPool2D::<4>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 4, 4>, _, _> =
+            MinPool2D::<3, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 3, 3>, _, _> =
+            MinPool2D::<3, 3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 10, 10>, _, _> =
+            MinPool2D::<3, 1, 1>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 12, 12>, _, _> =
+            MinPool2D::<3, 1, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 6, 6>, _, _> =
+            MinPool2D::<3, 2, 2>::default().forward(x.clone()).realize();
     }
 
     #[test]
     fn test_min_forward_4d_sizes() {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank4<5, 3, 10, 10>, TestDtype, _> = dev.zeros();
-        let _: Tensor<Rank4<5, 3, 7, 7>, _, _> = MinPool2D::<4>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 8, 8>, _, _> = MinPool2D::<3>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 9, 9>, _, _> = MinPool2D::<2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 4, 4>, _, _> = MinPool2D::<3, 2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 3, 3>, _, _> = MinPool2D::<3, 3>::default().forward(x.clone());
+        let _: Tensor<Rank4<5, 3, 7, 7>, _, _> =
+            MinPool2D::<4>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 8, 8>, _, _> =
+            MinPool2D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 9, 9>, _, _> =
+            MinPool2D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 4, 4>, _, _> =
+            MinPool2D::<3, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 3, 3>, _, _> =
+            MinPool2D::<3, 3>::default().forward(x.clone()).realize();
         let _: Tensor<Rank4<5, 3, 10, 10>, _, _> =
-            MinPool2D::<3, 1, 1>::default().forward(x.clone());
+            MinPool2D::<3, 1, 1>::default().forward(x.clone()).realize();
         let _: Tensor<Rank4<5, 3, 12, 12>, _, _> =
-            MinPool2D::<3, 1, 2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 6, 6>, _, _> = MinPool2D::<3, 2, 2>::default().forward(x.clone());
+            MinPool2D::<3, 1, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 6, 6>, _, _> =
+            MinPool2D::<3, 2, 2>::default().forward(x.clone()).realize();
     }
 
     #[test]
@@ -173,38 +204,52 @@ mod tests {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank3<1, 10, 10>, TestDtype, _> = dev.zeros();
 
-        let _: Tensor<Rank3<1, 6, 6>, _, _> = <(A, A)>::default().forward(x.clone());
-        let _: Tensor<Rank3<1, 8, 8>, _, _> = <(A, A, B)>::default().forward(x.clone());
+        let _: Tensor<Rank3<1, 6, 6>, _, _> = <(A, A)>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<1, 8, 8>, _, _> = <(A, A, B)>::default().forward(x.clone()).realize();
     }
 
     #[test]
     fn test_avgforward_3d_sizes() {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank3<3, 10, 10>, TestDtype, _> = dev.zeros();
-        let _: Tensor<Rank3<3, 8, 8>, _, _> = AvgPool2D::<3>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 9, 9>, _, _> = AvgPool2D::<2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 7, 7>, _, _> = AvgPool2D::<4>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 4, 4>, _, _> = AvgPool2D::<3, 2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 3, 3>, _, _> = AvgPool2D::<3, 3>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 10, 10>, _, _> = AvgPool2D::<3, 1, 1>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 12, 12>, _, _> = AvgPool2D::<3, 1, 2>::default().forward(x.clone());
-        let _: Tensor<Rank3<3, 6, 6>, _, _> = AvgPool2D::<3, 2, 2>::default().forward(x.clone());
+        let _: Tensor<Rank3<3, 8, 8>, _, _> =
+            AvgPool2D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 9, 9>, _, _> =
+            AvgPool2D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 7, 7>, _, _> =
+            AvgPool2D::<4>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 4, 4>, _, _> =
+            AvgPool2D::<3, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 3, 3>, _, _> =
+            AvgPool2D::<3, 3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 10, 10>, _, _> =
+            AvgPool2D::<3, 1, 1>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 12, 12>, _, _> =
+            AvgPool2D::<3, 1, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<3, 6, 6>, _, _> =
+            AvgPool2D::<3, 2, 2>::default().forward(x.clone()).realize();
     }
 
     #[test]
     fn test_avgforward_4d_sizes() {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank4<5, 3, 10, 10>, TestDtype, _> = dev.zeros();
-        let _: Tensor<Rank4<5, 3, 7, 7>, _, _> = AvgPool2D::<4>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 8, 8>, _, _> = AvgPool2D::<3>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 9, 9>, _, _> = AvgPool2D::<2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 4, 4>, _, _> = AvgPool2D::<3, 2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 3, 3>, _, _> = AvgPool2D::<3, 3>::default().forward(x.clone());
+        let _: Tensor<Rank4<5, 3, 7, 7>, _, _> =
+            AvgPool2D::<4>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 8, 8>, _, _> =
+            AvgPool2D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 9, 9>, _, _> =
+            AvgPool2D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 4, 4>, _, _> =
+            AvgPool2D::<3, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 3, 3>, _, _> =
+            AvgPool2D::<3, 3>::default().forward(x.clone()).realize();
         let _: Tensor<Rank4<5, 3, 10, 10>, _, _> =
-            AvgPool2D::<3, 1, 1>::default().forward(x.clone());
+            AvgPool2D::<3, 1, 1>::default().forward(x.clone()).realize();
         let _: Tensor<Rank4<5, 3, 12, 12>, _, _> =
-            AvgPool2D::<3, 1, 2>::default().forward(x.clone());
-        let _: Tensor<Rank4<5, 3, 6, 6>, _, _> = AvgPool2D::<3, 2, 2>::default().forward(x.clone());
+            AvgPool2D::<3, 1, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<5, 3, 6, 6>, _, _> =
+            AvgPool2D::<3, 2, 2>::default().forward(x.clone()).realize();
     }
 
     #[test]
@@ -214,7 +259,7 @@ mod tests {
         let dev: TestDevice = Default::default();
         let x: Tensor<Rank3<1, 10, 10>, TestDtype, _> = dev.zeros();
 
-        let _: Tensor<Rank3<1, 6, 6>, _, _> = <(A, A)>::default().forward(x.clone());
-        let _: Tensor<Rank3<1, 8, 8>, _, _> = <(A, A, B)>::default().forward(x.clone());
+        let _: Tensor<Rank3<1, 6, 6>, _, _> = <(A, A)>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank3<1, 8, 8>, _, _> = <(A, A, B)>::default().forward(x.clone()).realize();
     }
 }
@@ -0,0 +1,124 @@
+use crate::{
+    shapes::Const,
+    tensor_ops::{Pool2DKind, TryPool3D},
+};
+
+use super::{Module, NonMutableModule, ZeroSizedModule};
+
+/// Average pool with 3d kernel that operates on volumes (4d) and batches of volumes (5d).
+/// Each patch reduces to the average of the values in the patch.
+///
+/// Generics:
+/// - `KERNEL_SIZE`: The size of the kernel applied to the depth, height and width of the volumes.
+/// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
+/// - `PADDING`: How much zero padding to add around the volumes. Defaults to `0`.
+/// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled depth, height and width are `usize`.
+#[derive(Debug, Default, Clone)]
+pub struct AvgPool3D<
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize = 1,
+    const PADDING: usize = 0,
+    const DILATION: usize = 1,
+>;
+
+/// Max pool with 3d kernel that operates on volumes (4d) and batches of volumes (5d).
+/// Each patch reduces to the maximum value in that patch.
+///
+/// Generics:
+/// - `KERNEL_SIZE`: The size of the kernel applied to the depth, height and width of the volumes.
+/// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
+/// - `PADDING`: How much zero padding to add around the volumes. Defaults to `0`.
+/// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled depth, height and width are `usize`.
+#[derive(Debug, Default, Clone)]
+pub struct MaxPool3D<
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize = 1,
+    const PADDING: usize = 0,
+    const DILATION: usize = 1,
+>;
+
+/// Minimum pool with 3d kernel that operates on volumes (4d) and batches of volumes (5d).
+/// Each patch reduces to the minimum of the values in the patch.
+///
+/// Generics:
+/// - `KERNEL_SIZE`: The size of the kernel applied to the depth, height and width of the volumes.
+/// - `STRIDE`: How far to move the kernel each step. Defaults to `1`
+/// - `PADDING`: How much zero padding to add around the volumes. Defaults to `0`.
+/// - `DILATION` How dilated the kernel should be. Defaults to `1`.
+///
+/// On stable rust the pooled depth, height and width are `usize`.
+#[derive(Debug, Default, Clone)]
+pub struct MinPool3D<
+    const KERNEL_SIZE: usize,
+    const STRIDE: usize = 1,
+    const PADDING: usize = 0,
+    const DILATION: usize = 1,
+>;
+
+macro_rules! impl_pools {
+    ($PoolTy:tt, $Op:expr) => {
+        impl<const K: usize, const S: usize, const P: usize, const L: usize> ZeroSizedModule
+            for $PoolTy<K, S, P, L>
+        {
+        }
+        impl<const K: usize, const S: usize, const P: usize, const L: usize> NonMutableModule
+            for $PoolTy<K, S, P, L>
+        {
+        }
+
+        impl<
+                const K: usize,
+                const S: usize,
+                const P: usize,
+                const L: usize,
+                Inp: TryPool3D<Const<K>, Const<S>, Const<P>, Const<L>>,
+            > Module<Inp> for $PoolTy<K, S, P, L>
+        {
+            type Output = Inp::Pooled;
+            type Error = Inp::Error;
+
+            fn try_forward(&self, x: Inp) -> Result<Self::Output, Self::Error> {
+                x.try_pool3d($Op, Const, Const, Const, Const)
+            }
+        }
+    };
+}
+
+impl_pools!(AvgPool3D, Pool2DKind::Avg);
+impl_pools!(MaxPool3D, Pool2DKind::Max);
+impl_pools!(MinPool3D, Pool2DKind::Min);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::RealizeTo, tests::*};
+
+    #[test]
+    fn test_max_forward_4d_sizes() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<3, 6, 8, 10>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank4<3, 4, 6, 8>, _, _> =
+            MaxPool3D::<3>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<3, 3, 4, 5>, _, _> =
+            MaxPool3D::<2, 2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank4<3, 6, 8, 10>, _, _> =
+            MaxPool3D::<3, 1, 1>::default().forward(x.clone()).realize();
+    }
+
+    #[test]
+    fn test_avg_forward_5d_sizes() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank5<2, 3, 6, 8, 10>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank5<2, 3, 5, 7, 9>, _, _> =
+            AvgPool3D::<2>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank5<2, 3, 4, 5, 6>, _, _> =
+            AvgPool3D::<2, 2, 1>::default().forward(x.clone()).realize();
+        let _: Tensor<Rank5<2, 3, 2, 4, 6>, _, _> = MinPool3D::<3, 1, 0, 2>::default()
+            .forward(x.clone())
+            .realize();
+    }
+}
@@ -210,10 +210,9 @@ mod tests {
         assert_eq!(loaded.forward(x).array(), y.array());
     }
 
-    #[cfg(feature = "nightly")]
     #[test]
     fn test_save_load_conv() {
-        type T = Conv2D<2, 4, 3>;
+        type T = (Conv2D<2, 4, 3>, AvgPoolGlobal);
         let dev: TestDevice = Default::default();
         test_save_load::<Rank3<2, 8, 8>, TestDtype, TestDevice, T>(&dev);
     }
@@ -110,7 +110,9 @@ where
             Tensor<(S2, Const<M>), E, D>,
         ),
     ) -> Result<Self::Output, D::Err> {
-        assert_eq!(k.shape.0, v.shape.0);
+        if k.shape.0 != v.shape.0 {
+            return Err(ShapeError::new("MultiHeadAttention", &k.shape, &v.shape).into());
+        }
         let s1 = q.shape.0;
         let s2 = k.shape.0;
         let q = q.broadcast_like(&(Const::<1>, s1, Const::<M>));
@@ -147,9 +149,12 @@ where
             Tensor<(B, S2, Const<M>), E, D>,
         ),
     ) -> Result<Self::Output, D::Err> {
-        assert_eq!(q.shape.0, k.shape.0);
-        assert_eq!(q.shape.0, v.shape.0);
-        assert_eq!(k.shape.1, v.shape.1);
+        if q.shape.0 != k.shape.0 {
+            return Err(ShapeError::new("MultiHeadAttention", &q.shape, &k.shape).into());
+        }
+        if q.shape.0 != v.shape.0 || k.shape.1 != v.shape.1 {
+            return Err(ShapeError::new("MultiHeadAttention", &k.shape, &v.shape).into());
+        }
 
         let b = q.shape.0;
         let s1 = q.shape.1;
@@ -317,4 +322,24 @@ mod tests {
         let mut opt = Sgd::new(&mha, Default::default());
         opt.update(&mut mha, &g).expect("");
     }
+
+    #[test]
+    fn test_mha_shape_error() {
+        let dev: TestDevice = Default::default();
+
+        let mha = dev.build_module::<builder::MultiHeadAttention<12, 4>, TestDtype>();
+
+        let q: Tensor<(usize, Const<12>), TestDtype, _> = dev.sample_normal_like(&(3, Const));
+        let k: Tensor<(usize, Const<12>), TestDtype, _> = dev.sample_normal_like(&(4, Const));
+        let v: Tensor<(usize, Const<12>), TestDtype, _> = dev.sample_normal_like(&(5, Const));
+        let err = mha.try_forward((q, k, v)).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new(
+                "MultiHeadAttention",
+                &(4, Const::<12>),
+                &(5, Const::<12>)
+            ))
+        );
+    }
 }
@@ -0,0 +1,35 @@
+use super::{AdamConfig, AdamKernel};
+use crate::{optim::WeightDecay, shapes::Dtype, tensor::Reference};
+
+impl<E: num_traits::Float + Dtype> AdamKernel<E> for Reference {
+    fn update(
+        &self,
+        t: i32,
+        cfg: &AdamConfig,
+        param: &mut Self::Vec<E>,
+        moment1: &mut Self::Vec<E>,
+        moment2: &mut Self::Vec<E>,
+        grad: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let [b1, b2] = cfg.betas;
+        for i in 0..param.len() {
+            let p = param[i].to_f64().unwrap();
+            let mut g = grad[i].to_f64().unwrap();
+            if let Some(WeightDecay::L2(wd)) = cfg.weight_decay {
+                g += wd * p;
+            }
+            let m = b1 * moment1[i].to_f64().unwrap() + (1.0 - b1) * g;
+            let v = b2 * moment2[i].to_f64().unwrap() + (1.0 - b2) * g * g;
+            let m_hat = m / (1.0 - b1.powi(t));
+            let v_hat = v / (1.0 - b2.powi(t));
+            let mut update = cfg.lr * m_hat / (v_hat.sqrt() + cfg.eps);
+            if let Some(WeightDecay::Decoupled(wd)) = cfg.weight_decay {
+                update += wd * cfg.lr * p;
+            }
+            moment1[i] = E::from_f64(m).unwrap();
+            moment2[i] = E::from_f64(v).unwrap();
+            param[i] = E::from_f64(p - update).unwrap();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{optim::WeightDecay, shapes::Dtype, tensor::Reference};
+
+use super::{RMSpropConfig, RMSpropKernel};
+
+impl<E: num_traits::Float + Dtype> RMSpropKernel<E> for Reference {
+    fn update(
+        &self,
+        cfg: &RMSpropConfig,
+        param: &mut Self::Vec<E>,
+        momentum: &mut Self::Vec<E>,
+        square_avg: &mut Self::Vec<E>,
+        grad_avg: &mut Self::Vec<E>,
+        grad: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let a = cfg.alpha;
+        for i in 0..param.len() {
+            let p = param[i].to_f64().unwrap();
+            let mut g = grad[i].to_f64().unwrap();
+            if let Some(WeightDecay::L2(wd)) = cfg.weight_decay {
+                g += wd * p;
+            }
+            let s_avg = a * square_avg[i].to_f64().unwrap() + (1.0 - a) * g * g;
+            square_avg[i] = E::from_f64(s_avg).unwrap();
+            let denom = if cfg.centered {
+                let g_avg = a * grad_avg[i].to_f64().unwrap() + (1.0 - a) * g;
+                grad_avg[i] = E::from_f64(g_avg).unwrap();
+                (s_avg - g_avg * g_avg + cfg.eps).sqrt()
+            } else {
+                (s_avg + cfg.eps).sqrt()
+            };
+            let mut update = match cfg.momentum {
+                Some(u) => {
+                    let m = u * momentum[i].to_f64().unwrap() + g / denom;
+                    momentum[i] = E::from_f64(m).unwrap();
+                    cfg.lr * m
+                }
+                None => cfg.lr * g / denom,
+            };
+            if let Some(WeightDecay::Decoupled(wd)) = cfg.weight_decay {
+                update += wd * cfg.lr * p;
+            }
+            param[i] = E::from_f64(p - update).unwrap();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    optim::optimizer::{Momentum, WeightDecay},
+    shapes::Dtype,
+    tensor::Reference,
+};
+
+use super::{SgdConfig, SgdKernel};
+
+impl<E: Dtype> SgdKernel<E> for Reference {
+    fn update(
+        &self,
+        cfg: &SgdConfig,
+        param: &mut Self::Vec<E>,
+        velocity: &mut Self::Vec<E>,
+        grad: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let lr = E::from_f64(cfg.lr).unwrap();
+        for i in 0..param.len() {
+            let p = param[i];
+            let mut g = grad[i];
+            if let Some(WeightDecay::L2(wd)) = cfg.weight_decay {
+                g += E::from_f64(wd).unwrap() * p;
+            }
+            let mut update = match cfg.momentum {
+                None => g,
+                Some(Momentum::Classic(u)) => {
+                    velocity[i] = g + E::from_f64(u).unwrap() * velocity[i];
+                    velocity[i]
+                }
+                Some(Momentum::Nesterov(u)) => {
+                    let u = E::from_f64(u).unwrap();
+                    velocity[i] = g + u * velocity[i];
+                    g + u * velocity[i]
+                }
+            } * lr;
+            if let Some(WeightDecay::Decoupled(wd)) = cfg.weight_decay {
+                update += E::from_f64(wd * cfg.lr).unwrap() * p;
+            }
+            param[i] = p - update;
+        }
+        Ok(())
+    }
+}
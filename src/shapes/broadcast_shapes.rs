@@ -0,0 +1,278 @@
+use super::{shape::*, DynShape, MAX_DYN_DIMS};
+
+/// A [Dim] that can be broadcasted with the [Dim] `Rhs` following numpy's rules:
+/// two dimensions are compatible if they are equal, or if one of them is 1.
+///
+/// The resulting dimension is known at compile time if both dimensions are
+/// the same compile time dimension, or if one of them is `Const<1>` and the other
+/// is `Const<N>` for `N` up to 256. Combinations of a compile time dimension and
+/// a `usize` result in a `usize`. Other pairs of compile time dimensions (e.g.
+/// `Const<1>` and `Const<512>`) need one of them realized as a `usize` first.
+pub trait BroadcastDim<Rhs: Dim>: Dim {
+    type Output: Dim;
+    fn broadcast_dim(&self, rhs: &Rhs) -> Option<Self::Output>;
+}
+
+#[inline(always)]
+fn broadcast_sizes(lhs: usize, rhs: usize) -> Option<usize> {
+    if lhs == rhs || rhs == 1 {
+        Some(lhs)
+    } else if lhs == 1 {
+        Some(rhs)
+    } else {
+        None
+    }
+}
+
+impl<D: Dim> BroadcastDim<D> for D {
+    type Output = D;
+    #[inline(always)]
+    fn broadcast_dim(&self, rhs: &D) -> Option<Self::Output> {
+        D::from_size(broadcast_sizes(self.size(), rhs.size())?)
+    }
+}
+
+impl<const N: usize> BroadcastDim<usize> for Const<N> {
+    type Output = usize;
+    #[inline(always)]
+    fn broadcast_dim(&self, rhs: &usize) -> Option<Self::Output> {
+        broadcast_sizes(N, *rhs)
+    }
+}
+
+impl<const N: usize> BroadcastDim<Const<N>> for usize {
+    type Output = usize;
+    #[inline(always)]
+    fn broadcast_dim(&self, _: &Const<N>) -> Option<Self::Output> {
+        broadcast_sizes(*self, N)
+    }
+}
+
+// `Const<1>` with `Const<N>` would overlap with the impl for equal dims when `N` is 1,
+// so the sizes are listed out instead.
+macro_rules! broadcast_const_one {
+    ($($N:literal)*) => {
+        $(
+        impl BroadcastDim<Const<$N>> for Const<1> {
+            type Output = Const<$N>;
+            #[inline(always)]
+            fn broadcast_dim(&self, _: &Const<$N>) -> Option<Self::Output> {
+                Some(Const)
+            }
+        }
+
+        impl BroadcastDim<Const<1>> for Const<$N> {
+            type Output = Const<$N>;
+            #[inline(always)]
+            fn broadcast_dim(&self, _: &Const<1>) -> Option<Self::Output> {
+                Some(Const)
+            }
+        }
+        )*
+    };
+}
+
+broadcast_const_one!(
+    2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17
+    18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33
+    34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49
+    50 51 52 53 54 55 56 57 58 59 60 61 62 63 64 65
+    66 67 68 69 70 71 72 73 74 75 76 77 78 79 80 81
+    82 83 84 85 86 87 88 89 90 91 92 93 94 95 96 97
+    98 99 100 101 102 103 104 105 106 107 108 109 110 111 112 113
+    114 115 116 117 118 119 120 121 122 123 124 125 126 127 128 129
+    130 131 132 133 134 135 136 137 138 139 140 141 142 143 144 145
+    146 147 148 149 150 151 152 153 154 155 156 157 158 159 160 161
+    162 163 164 165 166 167 168 169 170 171 172 173 174 175 176 177
+    178 179 180 181 182 183 184 185 186 187 188 189 190 191 192 193
+    194 195 196 197 198 199 200 201 202 203 204 205 206 207 208 209
+    210 211 212 213 214 215 216 217 218 219 220 221 222 223 224 225
+    226 227 228 229 230 231 232 233 234 235 236 237 238 239 240 241
+    242 243 244 245 246 247 248 249 250 251 252 253 254 255 256
+);
+
+/// A [Shape] that can be broadcasted with the [Shape] `Rhs` following numpy's rules.
+/// The shapes are aligned by their last dimension, the missing leading dimensions
+/// of the shape with fewer dimensions are taken from the other shape, and
+/// each pair of aligned dimensions is combined using [BroadcastDim].
+///
+/// ```rust
+/// # use dfdx::shapes::*;
+/// let a: Rank2<2, 3> = Default::default();
+/// let b: Rank1<3> = Default::default();
+/// let c: Rank2<2, 3> = a.broadcast_shapes(&b).unwrap();
+///
+/// let b: Rank2<1, 3> = Default::default();
+/// let c: Rank2<2, 3> = a.broadcast_shapes(&b).unwrap();
+///
+/// let a = (2, 1);
+/// let b = (Const::<4>, 1, 3);
+/// let c: (Const<4>, usize, usize) = a.broadcast_shapes(&b).unwrap();
+/// assert_eq!(c, (Const, 2, 3));
+///
+/// assert!((2, 3).broadcast_shapes(&(3, 2)).is_none());
+/// ```
+pub trait BroadcastShapes<Rhs: Shape>: Shape {
+    type Output: Shape;
+    /// Returns the broadcasted shape, or `None` if the shapes are incompatible.
+    fn broadcast_shapes(&self, rhs: &Rhs) -> Option<Self::Output>;
+}
+
+macro_rules! broadcast_shapes {
+    (lhs [$($Lead:ident $li:tt),*] [$($L:ident $i:tt $R:ident $j:tt),*]) => {
+        impl<$($Lead: Dim, )* $($L: BroadcastDim<$R>, $R: Dim, )*> BroadcastShapes<($($R, )*)>
+            for ($($Lead, )* $($L, )*)
+        {
+            type Output = ($($Lead, )* $($L::Output, )*);
+            #[allow(unused_variables)]
+            #[inline(always)]
+            fn broadcast_shapes(&self, rhs: &($($R, )*)) -> Option<Self::Output> {
+                Some(($(self.$li, )* $(self.$i.broadcast_dim(&rhs.$j)?, )*))
+            }
+        }
+    };
+    (rhs [$($Lead:ident $li:tt),*] [$($L:ident $i:tt $R:ident $j:tt),*]) => {
+        impl<$($Lead: Dim, )* $($L: BroadcastDim<$R>, $R: Dim, )*> BroadcastShapes<($($Lead, )* $($R, )*)>
+            for ($($L, )*)
+        {
+            type Output = ($($Lead, )* $($L::Output, )*);
+            #[inline(always)]
+            fn broadcast_shapes(&self, rhs: &($($Lead, )* $($R, )*)) -> Option<Self::Output> {
+                Some(($(rhs.$li, )* $(self.$i.broadcast_dim(&rhs.$j)?, )*))
+            }
+        }
+    };
+}
+
+broadcast_shapes!(lhs [] []);
+broadcast_shapes!(rhs [R0 0] []);
+broadcast_shapes!(rhs [R0 0, R1 1] []);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2] []);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2, R3 3] []);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2, R3 3, R4 4] []);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2, R3 3, R4 4, R5 5] []);
+broadcast_shapes!(lhs [L0 0] []);
+broadcast_shapes!(lhs [] [L0 0 R0 0]);
+broadcast_shapes!(rhs [R0 0] [L0 0 R1 1]);
+broadcast_shapes!(rhs [R0 0, R1 1] [L0 0 R2 2]);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2] [L0 0 R3 3]);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2, R3 3] [L0 0 R4 4]);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2, R3 3, R4 4] [L0 0 R5 5]);
+broadcast_shapes!(lhs [L0 0, L1 1] []);
+broadcast_shapes!(lhs [L0 0] [L1 1 R0 0]);
+broadcast_shapes!(lhs [] [L0 0 R0 0, L1 1 R1 1]);
+broadcast_shapes!(rhs [R0 0] [L0 0 R1 1, L1 1 R2 2]);
+broadcast_shapes!(rhs [R0 0, R1 1] [L0 0 R2 2, L1 1 R3 3]);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2] [L0 0 R3 3, L1 1 R4 4]);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2, R3 3] [L0 0 R4 4, L1 1 R5 5]);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2] []);
+broadcast_shapes!(lhs [L0 0, L1 1] [L2 2 R0 0]);
+broadcast_shapes!(lhs [L0 0] [L1 1 R0 0, L2 2 R1 1]);
+broadcast_shapes!(lhs [] [L0 0 R0 0, L1 1 R1 1, L2 2 R2 2]);
+broadcast_shapes!(rhs [R0 0] [L0 0 R1 1, L1 1 R2 2, L2 2 R3 3]);
+broadcast_shapes!(rhs [R0 0, R1 1] [L0 0 R2 2, L1 1 R3 3, L2 2 R4 4]);
+broadcast_shapes!(rhs [R0 0, R1 1, R2 2] [L0 0 R3 3, L1 1 R4 4, L2 2 R5 5]);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2, L3 3] []);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2] [L3 3 R0 0]);
+broadcast_shapes!(lhs [L0 0, L1 1] [L2 2 R0 0, L3 3 R1 1]);
+broadcast_shapes!(lhs [L0 0] [L1 1 R0 0, L2 2 R1 1, L3 3 R2 2]);
+broadcast_shapes!(lhs [] [L0 0 R0 0, L1 1 R1 1, L2 2 R2 2, L3 3 R3 3]);
+broadcast_shapes!(rhs [R0 0] [L0 0 R1 1, L1 1 R2 2, L2 2 R3 3, L3 3 R4 4]);
+broadcast_shapes!(rhs [R0 0, R1 1] [L0 0 R2 2, L1 1 R3 3, L2 2 R4 4, L3 3 R5 5]);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2, L3 3, L4 4] []);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2, L3 3] [L4 4 R0 0]);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2] [L3 3 R0 0, L4 4 R1 1]);
+broadcast_shapes!(lhs [L0 0, L1 1] [L2 2 R0 0, L3 3 R1 1, L4 4 R2 2]);
+broadcast_shapes!(lhs [L0 0] [L1 1 R0 0, L2 2 R1 1, L3 3 R2 2, L4 4 R3 3]);
+broadcast_shapes!(lhs [] [L0 0 R0 0, L1 1 R1 1, L2 2 R2 2, L3 3 R3 3, L4 4 R4 4]);
+broadcast_shapes!(rhs [R0 0] [L0 0 R1 1, L1 1 R2 2, L2 2 R3 3, L3 3 R4 4, L4 4 R5 5]);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2, L3 3, L4 4, L5 5] []);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2, L3 3, L4 4] [L5 5 R0 0]);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2, L3 3] [L4 4 R0 0, L5 5 R1 1]);
+broadcast_shapes!(lhs [L0 0, L1 1, L2 2] [L3 3 R0 0, L4 4 R1 1, L5 5 R2 2]);
+broadcast_shapes!(lhs [L0 0, L1 1] [L2 2 R0 0, L3 3 R1 1, L4 4 R2 2, L5 5 R3 3]);
+broadcast_shapes!(lhs [L0 0] [L1 1 R0 0, L2 2 R1 1, L3 3 R2 2, L4 4 R3 3, L5 5 R4 4]);
+broadcast_shapes!(lhs [] [L0 0 R0 0, L1 1 R1 1, L2 2 R2 2, L3 3 R3 3, L4 4 R4 4, L5 5 R5 5]);
+
+impl BroadcastShapes<DynShape> for DynShape {
+    type Output = DynShape;
+    fn broadcast_shapes(&self, rhs: &DynShape) -> Option<Self::Output> {
+        let lhs_dims = self.concrete();
+        let rhs_dims = rhs.concrete();
+        let mut dims = [0; MAX_DYN_DIMS];
+        for i in 0..MAX_DYN_DIMS {
+            dims[i] = broadcast_sizes(lhs_dims[i], rhs_dims[i])?;
+        }
+        let rank = self.rank().max(rhs.rank());
+        DynShape::try_new(&dims[MAX_DYN_DIMS - rank..])
+    }
+}
+
+/// Computes the strides of `src` viewed as the broadcasted shape `dst`, where
+/// `src` and `dst` are aligned by their last dimension.
+pub(crate) fn broadcast_strides_aligned<Src: Shape, Dst: Shape>(
+    src: &Src,
+    strides: Src::Concrete,
+    dst: &Dst,
+) -> Dst::Concrete {
+    let src_dims = src.concrete();
+    let dst_dims = dst.concrete();
+    let mut dst_strides: Dst::Concrete = Default::default();
+    let num_leading = Dst::NUM_DIMS - Src::NUM_DIMS;
+    for i in num_leading..Dst::NUM_DIMS {
+        let j = i - num_leading;
+        if src_dims[j] == dst_dims[i] {
+            dst_strides[i] = strides[j];
+        } else {
+            assert_eq!(src_dims[j], 1);
+        }
+    }
+    dst_strides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_shapes_const() {
+        let _: Rank3<2, 3, 4> = Rank3::<2, 3, 4>::default()
+            .broadcast_shapes(&Rank1::<4>::default())
+            .unwrap();
+        let _: Rank3<2, 3, 4> = Rank2::<3, 4>::default()
+            .broadcast_shapes(&Rank3::<2, 3, 4>::default())
+            .unwrap();
+        let _: Rank2<3, 4> = ().broadcast_shapes(&Rank2::<3, 4>::default()).unwrap();
+        let _: Rank2<2, 3> = Rank2::<2, 3>::default()
+            .broadcast_shapes(&Rank2::<1, 3>::default())
+            .unwrap();
+        let _: Rank3<4, 2, 3> = Rank2::<1, 3>::default()
+            .broadcast_shapes(&Rank3::<4, 2, 1>::default())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_broadcast_shapes_runtime() {
+        assert_eq!((2, 1).broadcast_shapes(&(1, 3)), Some((2, 3)));
+        assert_eq!((Const::<1>, 3).broadcast_shapes(&(5, 1)), Some((5, 3)));
+        assert_eq!((Const::<2>, 3).broadcast_shapes(&(5, 1)), None);
+        assert_eq!((4,).broadcast_shapes(&(2, 4)), Some((2, 4)));
+        assert_eq!((4,).broadcast_shapes(&(2, 3)), None);
+    }
+
+    #[test]
+    fn test_broadcast_shapes_dyn() {
+        let a = DynShape::new(&[2, 1, 3]);
+        let b = DynShape::new(&[4, 1]);
+        assert_eq!(a.broadcast_shapes(&b), Some(DynShape::new(&[2, 4, 3])));
+        assert_eq!(b.broadcast_shapes(&a), Some(DynShape::new(&[2, 4, 3])));
+        assert_eq!(a.broadcast_shapes(&DynShape::new(&[2])), None);
+    }
+
+    #[test]
+    fn test_broadcast_strides_aligned() {
+        let src = (Const::<3>, 1);
+        let dst = (2, Const::<3>, 4);
+        assert_eq!(broadcast_strides_aligned(&src, [1, 1], &dst), [0, 1, 0]);
+    }
+}
@@ -78,7 +78,7 @@ broadcast_to_all!([] [] [] [A B C D E F] [() Axis Axes2 Axes3 Axes4 Axes5 Axes6]
 
 /// Internal implementation for broadcasting strides
 pub trait BroadcastStridesTo<S: Shape, Ax>: Shape + BroadcastShapeTo<S, Ax> {
-    fn check(&self, dst: &S);
+    fn check(&self, dst: &S) -> Result<(), ShapeError>;
     fn broadcast_strides(&self, strides: Self::Concrete) -> S::Concrete;
 }
 
@@ -87,16 +87,19 @@ where
     Self: BroadcastShapeTo<Dst, Ax>,
 {
     #[inline(always)]
-    fn check(&self, dst: &Dst) {
+    fn check(&self, dst: &Dst) -> Result<(), ShapeError> {
         let src_dims = self.concrete();
         let dst_dims = dst.concrete();
         let mut j = 0;
         for i in 0..Dst::NUM_DIMS {
             if !Ax::as_array().into_iter().any(|x| x == i as isize) {
-                assert_eq!(dst_dims[i], src_dims[j]);
+                if dst_dims[i] != src_dims[j] {
+                    return Err(ShapeError::new("broadcast", self, dst));
+                }
                 j += 1;
             }
         }
+        Ok(())
     }
 
     #[inline(always)]
@@ -143,13 +146,18 @@ mod tests {
 
     #[test]
     fn test_check() {
-        BroadcastStridesTo::<(usize, usize), Axis<1>>::check(&(1,), &(1, 2));
+        assert_eq!(
+            BroadcastStridesTo::<(usize, usize), Axis<1>>::check(&(1,), &(1, 2)),
+            Ok(())
+        );
     }
 
     #[test]
-    #[should_panic]
     fn test_check_failures() {
-        BroadcastStridesTo::<(usize, usize), Axis<1>>::check(&(1,), &(2, 2));
+        assert_eq!(
+            BroadcastStridesTo::<(usize, usize), Axis<1>>::check(&(1,), &(2, 2)),
+            Err(ShapeError::new("broadcast", &(1,), &(2, 2)))
+        );
     }
 
     #[test]
@@ -0,0 +1,183 @@
+use super::{axes::*, broadcasts::*, shape::*};
+
+/// The maximum number of dimensions a [DynShape] can have.
+pub const MAX_DYN_DIMS: usize = 6;
+
+/// A [Shape] whose number of dimensions is only known at runtime, with up to
+/// [MAX_DYN_DIMS] dimensions.
+///
+/// Internally this is always stored as [MAX_DYN_DIMS] dimensions, where the unused
+/// leading dimensions have size 1. This means that the axes of a [DynShape]
+/// always refer to these padded dimensions, e.g. the last dimension is always `Axis<5>`,
+/// and the typed reductions like [crate::tensor_ops::SumTo] can only reduce `Axis<5>` or
+/// all dimensions. Use [crate::tensor::Tensor::sum_axis()] and friends to reduce along any other
+/// dimension, given as an index into [DynShape::dims()].
+///
+/// Use [crate::tensor_ops::RealizeTo::realize()] to convert tensors between
+/// static shapes and [DynShape].
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let shape = DynShape::new(&[2, 3]);
+/// assert_eq!(shape.rank(), 2);
+/// assert_eq!(shape.dims(), &[2, 3]);
+/// let a = dev.tensor_from_vec(vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0], shape);
+/// let b: Tensor<Rank0, f32, _> = (a.clone() + a).exp().sum();
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynShape {
+    dims: [usize; MAX_DYN_DIMS],
+    rank: usize,
+}
+
+impl Default for DynShape {
+    fn default() -> Self {
+        Self {
+            dims: [1; MAX_DYN_DIMS],
+            rank: 0,
+        }
+    }
+}
+
+impl core::fmt::Debug for DynShape {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("DynShape").field(&self.dims()).finish()
+    }
+}
+
+impl DynShape {
+    /// Creates a shape with the given dimensions.
+    ///
+    /// **Panics** if there are more than [MAX_DYN_DIMS] dimensions.
+    pub fn new(dims: &[usize]) -> Self {
+        Self::try_new(dims).unwrap_or_else(|| {
+            panic!(
+                "DynShape supports at most {MAX_DYN_DIMS} dimensions, found {}",
+                dims.len()
+            )
+        })
+    }
+
+    /// Creates a shape with the given dimensions, or returns `None` if there are
+    /// more than [MAX_DYN_DIMS] dimensions.
+    pub fn try_new(dims: &[usize]) -> Option<Self> {
+        let rank = dims.len();
+        if rank > MAX_DYN_DIMS {
+            return None;
+        }
+        let mut shape = Self {
+            rank,
+            ..Default::default()
+        };
+        shape.dims[MAX_DYN_DIMS - rank..].copy_from_slice(dims);
+        Some(shape)
+    }
+
+    /// The number of dimensions this shape was created with.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// The dimensions this shape was created with, without the padding.
+    pub fn dims(&self) -> &[usize] {
+        &self.dims[MAX_DYN_DIMS - self.rank..]
+    }
+
+    /// Pads strides of a shape with `strides.len()` dimensions to [MAX_DYN_DIMS] dimensions.
+    /// The padded dimensions are given the strides they would have if they were contiguous.
+    pub(crate) fn pad_strides(&self, strides: &[usize]) -> [usize; MAX_DYN_DIMS] {
+        let num_padded = MAX_DYN_DIMS - strides.len();
+        let mut padded = [0; MAX_DYN_DIMS];
+        padded[num_padded..].copy_from_slice(strides);
+        for i in (0..num_padded).rev() {
+            padded[i] = if i + 1 == MAX_DYN_DIMS {
+                1
+            } else {
+                padded[i + 1] * self.dims[i + 1]
+            };
+        }
+        padded
+    }
+}
+
+impl Shape for DynShape {
+    const NUM_DIMS: usize = MAX_DYN_DIMS;
+    type Concrete = [usize; MAX_DYN_DIMS];
+    type AllAxes = Axes6<0, 1, 2, 3, 4, 5>;
+    type LastAxis = Axis<5>;
+
+    #[inline(always)]
+    fn concrete(&self) -> Self::Concrete {
+        self.dims
+    }
+
+    /// The padding can't be recovered from the concrete dimensions, so the
+    /// result always has [MAX_DYN_DIMS] dimensions.
+    #[inline(always)]
+    fn from_concrete(concrete: &Self::Concrete) -> Option<Self> {
+        Some(Self {
+            dims: *concrete,
+            rank: MAX_DYN_DIMS,
+        })
+    }
+
+    #[inline(always)]
+    fn rank(&self) -> usize {
+        self.rank
+    }
+
+    #[inline(always)]
+    fn from_concrete_rank(concrete: &Self::Concrete, rank: usize) -> Option<Self> {
+        Self::try_new(&concrete[MAX_DYN_DIMS - rank..])
+    }
+}
+
+macro_rules! dyn_has_axis {
+    ($($Axis:tt),*) => {
+        $(
+        impl HasAxes<Axis<$Axis>> for DynShape {
+            #[inline(always)]
+            fn size(&self) -> usize {
+                self.dims[$Axis]
+            }
+        }
+        )*
+    };
+}
+
+dyn_has_axis!(0, 1, 2, 3, 4, 5);
+
+impl ReduceShapeTo<(), Axes6<0, 1, 2, 3, 4, 5>> for DynShape {}
+impl ReduceShape<Axes6<0, 1, 2, 3, 4, 5>> for DynShape {
+    type Reduced = ();
+}
+
+impl ReduceShapeTo<[usize; 5], Axis<5>> for DynShape {}
+impl ReduceShape<Axis<5>> for DynShape {
+    type Reduced = [usize; 5];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_shape_padding() {
+        let shape = DynShape::new(&[2, 3]);
+        assert_eq!(shape.rank(), 2);
+        assert_eq!(shape.dims(), &[2, 3]);
+        assert_eq!(shape.concrete(), [1, 1, 1, 1, 2, 3]);
+        assert_eq!(shape.num_elements(), 6);
+        assert_eq!(shape.strides(), [6, 6, 6, 6, 3, 1]);
+        assert_eq!(shape.pad_strides(&[3, 1]), shape.strides());
+
+        let scalar = DynShape::new(&[]);
+        assert_eq!(scalar, DynShape::default());
+        assert_eq!(scalar.num_elements(), 1);
+        assert_eq!(scalar.pad_strides(&[]), scalar.strides());
+
+        assert_ne!(DynShape::new(&[1, 3]), DynShape::new(&[3]));
+        assert!(DynShape::try_new(&[1; 7]).is_none());
+    }
+}
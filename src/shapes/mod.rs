@@ -10,27 +10,36 @@
 //! ```
 
 mod axes;
+mod broadcast_shapes;
 mod broadcasts;
+mod dyn_shape;
+mod pad;
 mod permutes;
 mod realize;
 mod replace_dim;
 mod same_numel;
 mod shape;
+mod shape_error;
 mod slice;
 
 pub(crate) use axes::Axes;
+pub(crate) use broadcast_shapes::broadcast_strides_aligned;
 pub(crate) use broadcasts::{
     BroadcastShapeTo, BroadcastStridesTo, ReduceShape, ReduceShapeTo, ReduceStridesTo,
 };
+pub(crate) use pad::PadShape;
 pub(crate) use permutes::{PermuteShapeTo, PermuteStridesTo};
 pub(crate) use realize::RealizeShapeTo;
-pub(crate) use replace_dim::{RemoveDimTo, ReplaceDimTo};
+pub(crate) use replace_dim::{RemoveDimTo, ReplaceDimAlong, ReplaceDimTo};
 
 pub(crate) use same_numel::AssertSameNumel;
 pub(crate) use slice::SliceShape;
 
 pub use axes::{Axes2, Axes3, Axes4, Axes5, Axes6, Axis, HasAxes};
+pub use broadcast_shapes::{BroadcastDim, BroadcastShapes};
+pub use dyn_shape::{DynShape, MAX_DYN_DIMS};
 pub use shape::{Array, Const, ConstDim, Dim};
 pub use shape::{ConstShape, HasShape, Shape};
 pub use shape::{Dtype, HasDtype, HasUnitType, Unit};
 pub use shape::{Rank0, Rank1, Rank2, Rank3, Rank4, Rank5, Rank6};
+pub use shape_error::ShapeError;
@@ -0,0 +1,93 @@
+use super::*;
+
+/// A [Dim] that can be padded with `P`, which is either `(before, after)`, or `()`
+/// for no padding.
+pub trait PadDim<P>: Dim {
+    type Padded: Dim;
+
+    /// The number of entries added before and after this dimension.
+    fn pad_amounts(pad: &P) -> (usize, usize);
+
+    fn pad(&self, pad: &P) -> Self::Padded {
+        let (before, after) = Self::pad_amounts(pad);
+        Self::Padded::from_size(before + self.size() + after).unwrap()
+    }
+}
+
+impl<D: Dim> PadDim<(usize, usize)> for D {
+    type Padded = usize;
+
+    fn pad_amounts(pad: &(usize, usize)) -> (usize, usize) {
+        *pad
+    }
+}
+
+impl<D: Dim> PadDim<()> for D {
+    type Padded = D;
+
+    fn pad_amounts(_: &()) -> (usize, usize) {
+        (0, 0)
+    }
+
+    fn pad(&self, _: &()) -> D {
+        *self
+    }
+}
+
+/// A [Shape] that can be padded with a tuple of [PadDim] amounts, one for each dimension.
+/// Padded dimensions are changed to be of type usize, except those padded with `()`,
+/// whose types are not modified.
+pub trait PadShape<P>: Shape {
+    type Padded: Shape<Concrete = Self::Concrete>;
+
+    fn pad(&self, pads: &P) -> Self::Padded;
+
+    /// The number of entries added before and after each dimension.
+    fn pad_amounts(pads: &P) -> (Self::Concrete, Self::Concrete);
+}
+
+macro_rules! pad_shape {
+    ([$($dim:ident)*] [$($pad:ident)*] [$($idx:tt)*]) => {
+        impl<$($dim: Dim),*, $($pad),*> PadShape<($($pad,)*)> for ($($dim,)*)
+        where
+            $($dim: PadDim<$pad>),*
+        {
+            type Padded = ($($dim::Padded,)*);
+
+            fn pad(&self, pads: &($($pad,)*)) -> Self::Padded {
+                ($(self.$idx.pad(&pads.$idx),)*)
+            }
+
+            fn pad_amounts(pads: &($($pad,)*)) -> (Self::Concrete, Self::Concrete) {
+                let mut before: Self::Concrete = Default::default();
+                let mut after: Self::Concrete = Default::default();
+                $((before[$idx], after[$idx]) = $dim::pad_amounts(&pads.$idx);)*
+                (before, after)
+            }
+        }
+    };
+}
+
+pad_shape!([D1][P1][0]);
+pad_shape!([D1 D2] [P1 P2] [0 1]);
+pad_shape!([D1 D2 D3] [P1 P2 P3] [0 1 2]);
+pad_shape!([D1 D2 D3 D4] [P1 P2 P3 P4] [0 1 2 3]);
+pad_shape!([D1 D2 D3 D4 D5] [P1 P2 P3 P4 P5] [0 1 2 3 4]);
+pad_shape!([D1 D2 D3 D4 D5 D6] [P1 P2 P3 P4 P5 P6] [0 1 2 3 4 5]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pad_shape() {
+        let s: (usize, Const<3>) = Rank2::<2, 3>::default().pad(&((1, 2), ()));
+        assert_eq!(s, (5, Const));
+        let s: (usize, usize) = (2, 3).pad(&((0, 0), (4, 1)));
+        assert_eq!(s, (2, 8));
+        assert_eq!(
+            <Rank3<1, 2, 3> as PadShape<_>>::pad_amounts(&((), (1, 2), (3, 4))),
+            ([0, 1, 3], [0, 2, 4])
+        );
+    }
+}
@@ -3,11 +3,63 @@ use super::*;
 /// Marker for shapes that can be converted using their concrete types.
 pub trait RealizeShapeTo<Dst: Shape>: Shape {
     fn realized(&self) -> Option<Dst>;
+    fn realized_strides(&self, strides: Self::Concrete) -> Dst::Concrete;
 }
 
 impl<Src: Shape<Concrete = Dst::Concrete>, Dst: Shape> RealizeShapeTo<Dst> for Src {
     #[inline(always)]
     fn realized(&self) -> Option<Dst> {
-        Dst::from_concrete(&self.concrete())
+        // rank 6 shapes have the same concrete type as DynShape, so the runtime
+        // rank has to be carried over and checked as well
+        Dst::from_concrete_rank(&self.concrete(), self.rank())
+    }
+
+    #[inline(always)]
+    fn realized_strides(&self, strides: Self::Concrete) -> Dst::Concrete {
+        strides
     }
 }
+
+macro_rules! realize_dyn {
+    (($($D:tt $Idx:tt),*), rank=$Num:expr) => {
+        impl<$($D: Dim, )*> RealizeShapeTo<DynShape> for ($($D, )*) {
+            #[inline(always)]
+            fn realized(&self) -> Option<DynShape> {
+                DynShape::try_new(self.concrete().as_ref())
+            }
+
+            #[inline(always)]
+            fn realized_strides(&self, strides: Self::Concrete) -> [usize; MAX_DYN_DIMS] {
+                DynShape::new(self.concrete().as_ref()).pad_strides(strides.as_ref())
+            }
+        }
+
+        impl<$($D: Dim, )*> RealizeShapeTo<($($D, )*)> for DynShape {
+            #[inline(always)]
+            fn realized(&self) -> Option<($($D, )*)> {
+                if self.rank() != $Num {
+                    return None;
+                }
+                #[allow(unused_variables)]
+                let dims = self.dims();
+                Some(($($D::from_size(dims[$Idx])?, )*))
+            }
+
+            #[inline(always)]
+            fn realized_strides(&self, strides: Self::Concrete) -> [usize; $Num] {
+                let mut dst = [0; $Num];
+                dst.copy_from_slice(&strides[MAX_DYN_DIMS - $Num..]);
+                dst
+            }
+        }
+    };
+}
+
+realize_dyn!((), rank = 0);
+realize_dyn!((D1 0), rank = 1);
+realize_dyn!((D1 0, D2 1), rank = 2);
+realize_dyn!((D1 0, D2 1, D3 2), rank = 3);
+realize_dyn!((D1 0, D2 1, D3 2, D4 3), rank = 4);
+realize_dyn!((D1 0, D2 1, D3 2, D4 3, D5 4), rank = 5);
+// rank 6 is covered by the blanket impl above, since its concrete shape is the
+// same as DynShape's
@@ -1,6 +1,7 @@
 use super::{
-    axes::{Axes, Axis},
+    axes::{Axes, Axis, HasAxes},
     shape::{Dim, Shape},
+    shape_error::ShapeError,
 };
 
 /// Marker for shapes that can be indexed and have a dimension removed
@@ -9,13 +10,16 @@ pub trait RemoveDimTo<Dst: Shape, Idx: Shape>: Shape {
 
     /// All dimensions of idx should be the same as the dimensions of Self
     #[inline(always)]
-    fn check(&self, idx: &Idx) {
+    fn check(&self, idx: &Idx) -> Result<(), ShapeError> {
         assert!(Idx::NUM_DIMS <= Self::NUM_DIMS);
         let src_dims = self.concrete();
         let idx_dims = idx.concrete();
         for i in 0..Idx::NUM_DIMS {
-            assert_eq!(src_dims[i], idx_dims[i], "dimension {i} not the same");
+            if src_dims[i] != idx_dims[i] {
+                return Err(ShapeError::new("select", self, idx));
+            }
         }
+        Ok(())
     }
 
     #[inline]
@@ -42,19 +46,22 @@ pub trait ReplaceDimTo<Dst: Shape, Idx: Shape>: Shape {
     /// All dimensions of idx *up to last dimension* (which is new)
     /// should be the same as the dimensions of Self
     #[inline(always)]
-    fn check(&self, idx: &Idx) {
+    fn check(&self, idx: &Idx) -> Result<(), ShapeError> {
         if Self::NUM_DIMS == Dst::NUM_DIMS {
             // replace 1 dim case
             assert!(Idx::NUM_DIMS <= Self::NUM_DIMS);
             let src_dims = self.concrete();
             let idx_dims = idx.concrete();
             for i in 0..Idx::NUM_DIMS - 1 {
-                assert_eq!(src_dims[i], idx_dims[i], "dimension {i} not the same");
+                if src_dims[i] != idx_dims[i] {
+                    return Err(ShapeError::new("gather", self, idx));
+                }
             }
         } else {
             // batch replace case - we actually don't need to check this case
             // at all
         }
+        Ok(())
     }
 
     #[inline]
@@ -89,6 +96,19 @@ pub trait ReplaceDimTo<Dst: Shape, Idx: Shape>: Shape {
     }
 }
 
+/// Marker for shapes that can have the dimension along `Ax` replaced with `New`,
+/// keeping the number of dimensions the same.
+pub trait ReplaceDimAlong<Ax: Axes<Array = [isize; 1]>, New: Dim>: Shape + HasAxes<Ax> {
+    type Replaced: Shape<Concrete = Self::Concrete>;
+
+    #[inline]
+    fn replace_along(&self, new: New) -> Self::Replaced {
+        let mut dims = self.concrete();
+        dims[Ax::as_array()[0] as usize] = new.size();
+        Self::Replaced::from_concrete(&dims).unwrap()
+    }
+}
+
 macro_rules! replace {
     (($($DimVars:tt),*), $Ax:ty, $Dst:ty, $Idx:ty) => {
 impl<$($DimVars: Dim, )* New: Dim> ReplaceDimTo<$Dst, $Idx> for ($($DimVars, )*) {
@@ -105,12 +125,21 @@ impl<$($DimVars: Dim, )*> RemoveDimTo<$Dst, $Idx> for ($($DimVars, )*) {
     };
 }
 
+macro_rules! replace_along {
+    (($($DimVars:tt),*), $Ax:ty, $Dst:ty) => {
+impl<$($DimVars: Dim, )* New: Dim> ReplaceDimAlong<$Ax, New> for ($($DimVars, )*) {
+    type Replaced = $Dst;
+}
+    };
+}
+
 macro_rules! replace_and_remove_all {
     ($(@ $x:tt)? [] $i:tt) => {
     };
     (@ [$($befores:ident)*] [$cur:ident $($afters:ident)*] [$idx:tt $($idxs:tt)*]) => {
         replace!(($($befores,)* $cur $(,$afters)*), Axis<$idx>, ($($befores,)* New, $($afters),*), ($($befores,)* New,));
         removed!(($($befores,)* $cur $(,$afters)*), Axis<$idx>, ($($befores,)* $($afters,)*), ($($befores,)*));
+        replace_along!(($($befores,)* $cur $(,$afters)*), Axis<$idx>, ($($befores,)* New, $($afters,)*));
 
         replace_and_remove_all!(@ [$($befores)* $cur] [$($afters)*] [$($idxs)*]);
     };
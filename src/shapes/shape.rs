@@ -21,6 +21,12 @@ pub trait Unit:
     + SafeZeros
 {
     const ONE: Self;
+
+    /// The value as an `f64` for floating point types, and `None` otherwise. This is
+    /// used to print floats with a fixed precision.
+    fn to_float(self) -> Option<f64> {
+        None
+    }
 }
 
 macro_rules! unit {
@@ -30,10 +36,19 @@ macro_rules! unit {
             const ONE: Self = $one;
         }
     };
+    ($type:ty, $one:expr, float) => {
+        impl SafeZeros for $type {}
+        impl Unit for $type {
+            const ONE: Self = $one;
+            fn to_float(self) -> Option<f64> {
+                Some(self.into())
+            }
+        }
+    };
 }
 
-unit!(f32, 1.0);
-unit!(f64, 1.0);
+unit!(f32, 1.0, float);
+unit!(f64, 1.0, float);
 unit!(usize, 1);
 unit!(isize, 1);
 unit!(u8, 1);
@@ -48,7 +63,7 @@ unit!(u128, 1);
 unit!(i128, 1);
 unit!(bool, true);
 #[cfg(feature = "f16")]
-unit!(half::f16, half::f16::ONE);
+unit!(half::f16, half::f16::ONE, float);
 
 /// Represents something that has a [Unit].
 pub trait HasUnitType {
@@ -249,6 +264,23 @@ pub trait Shape:
     fn concrete(&self) -> Self::Concrete;
     fn from_concrete(concrete: &Self::Concrete) -> Option<Self>;
 
+    /// The number of dimensions of this value. This is always [Shape::NUM_DIMS],
+    /// except for [DynShape](super::DynShape), whose rank is only known at runtime.
+    #[inline(always)]
+    fn rank(&self) -> usize {
+        Self::NUM_DIMS
+    }
+
+    /// Like [Shape::from_concrete], but only uses the last `rank` dimensions of
+    /// `concrete`. Returns `None` if this shape can't have `rank` dimensions.
+    #[inline(always)]
+    fn from_concrete_rank(concrete: &Self::Concrete, rank: usize) -> Option<Self> {
+        if rank != Self::NUM_DIMS {
+            return None;
+        }
+        Self::from_concrete(concrete)
+    }
+
     /// The number of elements in this shape; the product of all dimensions.
     #[inline(always)]
     fn num_elements(&self) -> usize {
@@ -0,0 +1,90 @@
+use super::{
+    dyn_shape::{DynShape, MAX_DYN_DIMS},
+    shape::Shape,
+};
+
+/// The error returned by fallible operations when the runtime dimensions of
+/// their arguments don't match, for example the inner dimensions of a matmul.
+///
+/// The shapes are returned as [DynShape]s by [ShapeError::lhs()] and [ShapeError::rhs()],
+/// so the error doesn't depend on the shape types of the operation and is [Copy].
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<(usize, Const<3>), f32, _> = dev.zeros_like(&(2, Const));
+/// let b: Tensor<(usize, Const<3>), f32, _> = dev.zeros_like(&(4, Const));
+/// let err = a.try_add(b).unwrap_err();
+/// assert_eq!(err, CpuError::Shape(ShapeError::new("add", &(2, Const::<3>), &(4, Const::<3>))));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ShapeError {
+    /// The name of the operation that failed.
+    pub op: &'static str,
+    // the unused leading dims are stored as `PADDING` instead of keeping a rank, so
+    // that the `Result`s of the devices stay small while the error is still [Copy]
+    lhs: [usize; MAX_DYN_DIMS],
+    rhs: [usize; MAX_DYN_DIMS],
+}
+
+/// No tensor can have a dimension of this size, so it marks the unused dims.
+const PADDING: usize = usize::MAX;
+
+fn pack(dims: &[usize]) -> [usize; MAX_DYN_DIMS] {
+    // keeps the same limit as [DynShape::new()]
+    let rank = DynShape::new(dims).rank();
+    let mut packed = [PADDING; MAX_DYN_DIMS];
+    packed[MAX_DYN_DIMS - rank..].copy_from_slice(dims);
+    packed
+}
+
+fn unpack(packed: &[usize; MAX_DYN_DIMS]) -> DynShape {
+    let padding = packed.iter().take_while(|&&d| d == PADDING).count();
+    DynShape::new(&packed[padding..])
+}
+
+impl ShapeError {
+    /// Creates an error for operation `op` with arguments of shapes `lhs` and `rhs`.
+    pub fn new<L: Shape, R: Shape>(op: &'static str, lhs: &L, rhs: &R) -> Self {
+        Self {
+            op,
+            lhs: pack(lhs.concrete().as_ref()),
+            rhs: pack(rhs.concrete().as_ref()),
+        }
+    }
+
+    /// The shape of the first argument.
+    pub fn lhs(&self) -> DynShape {
+        unpack(&self.lhs)
+    }
+
+    /// The shape of the second argument.
+    pub fn rhs(&self) -> DynShape {
+        unpack(&self.rhs)
+    }
+}
+
+impl core::fmt::Debug for ShapeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ShapeError")
+            .field("op", &self.op)
+            .field("lhs", &self.lhs())
+            .field("rhs", &self.rhs())
+            .finish()
+    }
+}
+
+impl core::fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}: incompatible shapes {:?} and {:?}",
+            self.op,
+            self.lhs().dims(),
+            self.rhs().dims()
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ShapeError {}
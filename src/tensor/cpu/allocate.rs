@@ -2,10 +2,10 @@
 
 use crate::{
     shapes::*,
-    tensor::{masks::triangle_mask, storage_traits::*, unique_id, Tensor},
+    tensor::{is_dense, masks::triangle_mask, storage_traits::*, unique_id, Tensor},
 };
 
-use super::{CachableVec, Cpu, CpuError, LendingIterator};
+use super::{CachableVec, Cpu, CpuError, LendingIterator, NdIndex};
 
 use rand::{distributions::Distribution, Rng};
 use std::{sync::Arc, vec::Vec};
@@ -27,7 +27,7 @@ impl Cpu {
     ) -> Result<CachableVec<E>, CpuError> {
         let data = self.cache.try_pop::<E>(numel).map_or_else(
             #[cfg(feature = "fast-alloc")]
-            || Ok(std::vec![elem; numel]),
+            || Ok::<_, CpuError>(std::vec![elem; numel]),
             #[cfg(not(feature = "fast-alloc"))]
             || {
                 let mut data: Vec<E> = Vec::new();
@@ -57,6 +57,22 @@ impl Cpu {
     }
 }
 
+impl<S: Shape, E: Unit, T> Tensor<S, E, Cpu, T> {
+    /// Copies views into a buffer of their own that holds just their span, so
+    /// that they can be modified in place without touching the parent tensor.
+    /// Strides are kept as is. Does nothing for tensors that are not views.
+    pub(crate) fn try_compact(mut self) -> Result<Self, CpuError> {
+        if !self.is_whole() {
+            let span = self.span();
+            let mut data = self.device.try_alloc_zeros::<E>(span)?;
+            data.copy_from_slice(&self.data[self.offset..self.offset + span]);
+            self.data = Arc::new(data);
+            self.offset = 0;
+        }
+        Ok(self)
+    }
+}
+
 impl<E: Unit> ZerosTensor<E> for Cpu {
     fn try_zeros_like<S: HasShape>(&self, src: &S) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
         let shape = *src.shape();
@@ -68,6 +84,7 @@ impl<E: Unit> ZerosTensor<E> for Cpu {
             data,
             shape,
             strides,
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         })
@@ -92,6 +109,7 @@ impl<E: Unit> OnesTensor<E> for Cpu {
             data,
             shape,
             strides,
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         })
@@ -116,6 +134,7 @@ impl<E: Unit> TriangleTensor<E> for Cpu {
             data,
             shape,
             strides,
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         })
@@ -138,6 +157,7 @@ impl<E: Unit> TriangleTensor<E> for Cpu {
             data,
             shape,
             strides,
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         })
@@ -187,12 +207,46 @@ impl<E: Unit> SampleTensor<E> for Cpu {
     }
 }
 
+/// Indexes the physical elements of a view with gaps, i.e. every element once
+/// with broadcasted dimensions skipped.
+fn physical_index<S: Shape>(shape: S, strides: S::Concrete) -> NdIndex<S> {
+    let mut index = NdIndex::new(shape, strides);
+    for i in 0..S::NUM_DIMS {
+        if strides[i] == 0 {
+            index.shape[i] = 1;
+        }
+    }
+    index
+}
+
 impl<E: Unit> CopySlice<E> for Cpu {
     fn copy_from<S: Shape, T>(dst: &mut Tensor<S, E, Self, T>, src: &[E]) {
-        std::sync::Arc::make_mut(&mut dst.data).copy_from_slice(src);
+        let (offset, span) = (dst.offset, dst.span());
+        if is_dense(&dst.shape, &dst.strides) {
+            Arc::make_mut(&mut dst.data)[offset..offset + span].copy_from_slice(src);
+        } else {
+            let mut index = physical_index(dst.shape, dst.strides);
+            let data = &mut Arc::make_mut(&mut dst.data)[offset..];
+            let mut src = src.iter();
+            while let Some(i) = index.next() {
+                data[i] = *src.next().unwrap();
+            }
+            assert!(src.next().is_none());
+        }
     }
     fn copy_into<S: Shape, T>(src: &Tensor<S, E, Self, T>, dst: &mut [E]) {
-        dst.copy_from_slice(src.data.as_ref());
+        let (offset, span) = (src.offset, src.span());
+        if is_dense(&src.shape, &src.strides) {
+            dst.copy_from_slice(&src.data[offset..offset + span]);
+        } else {
+            let mut index = physical_index(src.shape, src.strides);
+            let data = &src.data[offset..];
+            let mut dst = dst.iter_mut();
+            while let Some(i) = index.next() {
+                *dst.next().unwrap() = data[i];
+            }
+            assert!(dst.next().is_none());
+        }
     }
 }
 
@@ -216,6 +270,7 @@ impl<E: Unit> TensorFromVec<E> for Cpu {
                 data: Arc::new(src),
                 shape,
                 strides: shape.strides(),
+                offset: 0,
                 device: self.clone(),
                 tape: Default::default(),
             })
@@ -227,7 +282,7 @@ impl<E: Unit> TensorToArray<Rank0, E> for Cpu {
     type Array = E;
     fn tensor_to_array<T>(&self, tensor: &Tensor<Rank0, E, Self, T>) -> Self::Array {
         let mut out: Self::Array = Default::default();
-        out.clone_from(&tensor.data[0]);
+        out.clone_from(&tensor.data[tensor.offset]);
         out
     }
 }
@@ -285,3 +340,17 @@ impl<E: Unit, const M: usize, const N: usize, const O: usize, const P: usize>
         out
     }
 }
+
+impl<E: Unit, const M: usize, const N: usize, const O: usize, const P: usize, const Q: usize>
+    TensorToArray<Rank5<M, N, O, P, Q>, E> for Cpu
+{
+    type Array = [[[[[E; Q]; P]; O]; N]; M];
+    fn tensor_to_array<T>(&self, tensor: &Tensor<Rank5<M, N, O, P, Q>, E, Self, T>) -> Self::Array {
+        let mut out: Self::Array = [[[[[Default::default(); Q]; P]; O]; N]; M];
+        let mut iter = tensor.iter_with_index();
+        while let Some((v, [m, n, o, p, q])) = iter.next() {
+            out[m][n][o][p][q].clone_from(v);
+        }
+        out
+    }
+}
@@ -1,7 +1,13 @@
-use crate::shapes::{Shape, Unit};
+use crate::shapes::{Shape, ShapeError, Unit};
 use crate::tensor::{cache::TensorCache, cpu::LendingIterator, storage_traits::*, Tensor};
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::{sync::Arc, vec::Vec};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    vec::Vec,
+};
 
 #[cfg(feature = "no-std")]
 use spin::Mutex;
@@ -24,16 +30,19 @@ unsafe impl Sync for BytesPtr {}
 pub struct Cpu {
     /// A thread safe random number generator.
     pub(crate) rng: Arc<Mutex<StdRng>>,
+    /// The seed that [Cpu::rng] was created with, used to derive per op rng streams.
+    pub(crate) seed: u64,
+    /// The number of per op rng streams that have been created.
+    pub(crate) num_streams: Arc<AtomicU64>,
+    /// Whether deterministic mode is enabled.
+    pub(crate) deterministic: Arc<AtomicBool>,
     /// A thread safe cache of memory allocations that can be reused.
     pub(crate) cache: Arc<TensorCache<BytesPtr>>,
 }
 
 impl Default for Cpu {
     fn default() -> Self {
-        Self {
-            rng: Arc::new(Mutex::new(StdRng::seed_from_u64(0))),
-            cache: Arc::new(Default::default()),
-        }
+        Self::seed_from_u64(0)
     }
 }
 
@@ -42,17 +51,50 @@ impl Cpu {
     pub fn seed_from_u64(seed: u64) -> Self {
         Self {
             rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+            seed,
+            num_streams: Arc::new(AtomicU64::new(0)),
+            deterministic: Arc::new(AtomicBool::new(false)),
             cache: Arc::new(Default::default()),
         }
     }
+
+    /// Returns the seed of the next per op rng stream. Uses splitmix64 to mix
+    /// the device's seed with the index of the stream.
+    fn next_stream_seed(&self) -> u64 {
+        let stream = self.num_streams.fetch_add(1, Ordering::Relaxed);
+        let mut z = self
+            .seed
+            .wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9E3779B97F4A7C15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// The parallelism that gemm should use, which is single threaded in deterministic mode.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn gemm_parallelism(&self) -> gemm::Parallelism {
+        if self.is_deterministic() {
+            gemm::Parallelism::None
+        } else {
+            gemm::Parallelism::Rayon(rayon::current_num_threads())
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CpuError {
     /// Device is out of memory
     OutOfMemory,
     /// Not enough elements were provided when creating a tensor
     WrongNumElements,
+    /// The runtime shapes of the arguments to an operation are incompatible
+    Shape(ShapeError),
+}
+
+impl From<ShapeError> for CpuError {
+    fn from(value: ShapeError) -> Self {
+        Self::Shape(value)
+    }
 }
 
 impl std::fmt::Display for CpuError {
@@ -60,6 +102,7 @@ impl std::fmt::Display for CpuError {
         match self {
             Self::OutOfMemory => f.write_str("CpuError::OutOfMemory"),
             Self::WrongNumElements => f.write_str("CpuError::WrongNumElements"),
+            Self::Shape(err) => write!(f, "CpuError::Shape({err})"),
         }
     }
 }
@@ -108,6 +151,11 @@ impl<E: Clone> Clone for CachableVec<E> {
             },
         )
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        // re-uses the existing allocation when the lengths match
+        self.data.clone_from(&source.data);
+    }
 }
 
 impl<E> Drop for CachableVec<E> {
@@ -146,6 +194,9 @@ impl DeviceStorage for Cpu {
     }
 
     fn random_u64(&self) -> u64 {
+        if self.is_deterministic() {
+            return self.next_stream_seed();
+        }
         #[cfg(not(feature = "no-std"))]
         {
             self.rng.lock().unwrap().gen()
@@ -225,4 +276,38 @@ impl DeviceStorage for Cpu {
         cache.clear();
         Ok(())
     }
+
+    fn enable_deterministic(&self) {
+        self.deterministic.store(true, Ordering::Relaxed);
+    }
+
+    fn disable_deterministic(&self) {
+        self.deterministic.store(false, Ordering::Relaxed);
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.deterministic.load(Ordering::Relaxed)
+    }
+
+    fn rng_state(&self) -> RngState {
+        #[cfg(not(feature = "no-std"))]
+        let rng = self.rng.lock().unwrap();
+        #[cfg(feature = "no-std")]
+        let rng = self.rng.lock();
+        RngState {
+            rng: Some(rng.clone()),
+            num_streams: self.num_streams.load(Ordering::Relaxed),
+        }
+    }
+
+    fn set_rng_state(&self, state: &RngState) {
+        if let Some(state_rng) = state.rng.as_ref() {
+            #[cfg(not(feature = "no-std"))]
+            let mut rng = self.rng.lock().unwrap();
+            #[cfg(feature = "no-std")]
+            let mut rng = self.rng.lock();
+            *rng = state_rng.clone();
+            self.num_streams.store(state.num_streams, Ordering::Relaxed);
+        }
+    }
 }
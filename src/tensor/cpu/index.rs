@@ -23,7 +23,7 @@ impl<S: Shape, E: Unit, T> std::ops::Index<S::Concrete> for Tensor<S, E, Cpu, T>
     #[inline(always)]
     fn index(&self, index: S::Concrete) -> &Self::Output {
         let i = index_to_i(&self.shape, &self.strides, index);
-        &self.data[i]
+        &self.data[self.offset + i]
     }
 }
 
@@ -32,6 +32,6 @@ impl<S: Shape, E: Unit, T> std::ops::IndexMut<S::Concrete> for Tensor<S, E, Cpu,
     fn index_mut(&mut self, index: S::Concrete) -> &mut Self::Output {
         let i = index_to_i(&self.shape, &self.strides, index);
         let data = Arc::make_mut(&mut self.data);
-        &mut data[i]
+        &mut data[self.offset + i]
     }
 }
@@ -1,6 +1,5 @@
 use super::{super::Tensor, Cpu};
 use crate::shapes::{Shape, Unit};
-use std::vec::Vec;
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) struct NdIndex<S: Shape> {
@@ -18,7 +17,7 @@ impl<S: Shape> NdIndex<S> {
             indices: Default::default(),
             shape: shape.concrete(),
             strides,
-            next: Some(0),
+            next: (shape.num_elements() > 0).then_some(0),
             contiguous: (strides == shape.strides()).then(|| shape.num_elements()),
         }
     }
@@ -96,40 +95,44 @@ impl<S: Shape> NdIndex<S> {
 }
 
 pub(crate) struct StridedRefIter<'a, S: Shape, E> {
-    data: &'a Vec<E>,
+    data: &'a [E],
     index: NdIndex<S>,
 }
 
 pub(crate) struct StridedMutIter<'a, S: Shape, E> {
-    data: &'a mut Vec<E>,
+    data: &'a mut [E],
     index: NdIndex<S>,
 }
 
 pub(crate) struct StridedRefIndexIter<'a, S: Shape, E> {
-    data: &'a Vec<E>,
+    data: &'a [E],
     index: NdIndex<S>,
 }
 
 pub(crate) struct StridedMutIndexIter<'a, S: Shape, E> {
-    data: &'a mut Vec<E>,
+    data: &'a mut [E],
     index: NdIndex<S>,
 }
 
 impl<S: Shape, E: Unit, T> Tensor<S, E, Cpu, T> {
+    /// Iterates over `data[offset..offset + span]` in memory order.
     #[inline]
     pub(crate) fn buf_iter(&self) -> std::slice::Iter<'_, E> {
-        self.data.iter()
+        let span = self.span();
+        self.data[self.offset..self.offset + span].iter()
     }
 
+    /// Iterates over `data[offset..offset + span]` in memory order.
     #[inline]
     pub(crate) fn buf_iter_mut(&mut self) -> std::slice::IterMut<'_, E> {
-        std::sync::Arc::make_mut(&mut self.data).iter_mut()
+        let span = self.span();
+        std::sync::Arc::make_mut(&mut self.data)[self.offset..self.offset + span].iter_mut()
     }
 
     #[inline]
     pub(crate) fn iter(&self) -> StridedRefIter<S, E> {
         StridedRefIter {
-            data: self.data.as_ref(),
+            data: &self.data[self.offset..],
             index: NdIndex::new(self.shape, self.strides),
         }
     }
@@ -137,7 +140,7 @@ impl<S: Shape, E: Unit, T> Tensor<S, E, Cpu, T> {
     #[inline]
     pub(crate) fn iter_mut(&mut self) -> StridedMutIter<S, E> {
         StridedMutIter {
-            data: &mut std::sync::Arc::make_mut(&mut self.data).data,
+            data: &mut std::sync::Arc::make_mut(&mut self.data).data[self.offset..],
             index: NdIndex::new(self.shape, self.strides),
         }
     }
@@ -145,7 +148,7 @@ impl<S: Shape, E: Unit, T> Tensor<S, E, Cpu, T> {
     #[inline]
     pub(crate) fn iter_with_index(&self) -> StridedRefIndexIter<S, E> {
         StridedRefIndexIter {
-            data: self.data.as_ref(),
+            data: &self.data[self.offset..],
             index: NdIndex::new(self.shape, self.strides),
         }
     }
@@ -153,7 +156,7 @@ impl<S: Shape, E: Unit, T> Tensor<S, E, Cpu, T> {
     #[inline]
     pub(crate) fn iter_mut_with_index(&mut self) -> StridedMutIndexIter<S, E> {
         StridedMutIndexIter {
-            data: &mut std::sync::Arc::make_mut(&mut self.data).data,
+            data: &mut std::sync::Arc::make_mut(&mut self.data).data[self.offset..],
             index: NdIndex::new(self.shape, self.strides),
         }
     }
@@ -204,7 +207,7 @@ impl<'q, S: Shape, E> LendingIterator for StridedMutIndexIter<'q, S, E> {
 
 #[cfg(test)]
 mod tests {
-    use crate::shapes::{Rank1, Rank2, Rank3};
+    use crate::shapes::{Const, Rank1, Rank2, Rank3};
 
     use super::*;
 
@@ -215,6 +218,13 @@ mod tests {
         assert!(i.next().is_none());
     }
 
+    #[test]
+    fn test_empty_iter() {
+        let shape = (0, Const::<3>);
+        let mut i = NdIndex::new(shape, shape.strides());
+        assert!(i.next().is_none());
+    }
+
     #[test]
     fn test_1d_contiguous_iter() {
         let shape: Rank1<3> = Default::default();
@@ -12,7 +12,7 @@ pub use device::{Cpu, CpuError};
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{shapes::*, tensor::*};
+    use crate::{shapes::*, tensor::*, tensor_ops::*};
 
     #[test]
     fn test_empty_cache() {
@@ -68,4 +68,75 @@ mod tests {
         std::sync::Arc::make_mut(&mut b.data);
         assert_eq!(dev.cache.len(), 0);
     }
+
+    #[test]
+    fn test_restore_rng_state() {
+        let dev: Cpu = Default::default();
+        let _: Tensor<Rank1<5>, f32, _> = dev.sample_normal();
+        let state = dev.rng_state();
+        let a: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+        let b: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+        assert_ne!(a.array(), b.array());
+        dev.set_rng_state(&state);
+        let c: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+        assert_eq!(a.array(), c.array());
+    }
+
+    #[test]
+    fn test_deterministic_dropout_ignores_sampling() {
+        let dev: Cpu = Default::default();
+        dev.enable_deterministic();
+        assert!(dev.is_deterministic());
+        let t: Tensor<Rank1<16>, f32, _> = dev.ones();
+        let state = dev.rng_state();
+        let a = t.clone().dropout(0.5);
+
+        // sampling in between dropouts doesn't change the dropout masks
+        dev.set_rng_state(&state);
+        let _: Tensor<Rank1<5>, f32, _> = dev.sample_normal();
+        let b = t.clone().dropout(0.5);
+        assert_eq!(a.array(), b.array());
+
+        // but each dropout still gets a different stream
+        let c = t.dropout(0.5);
+        assert_ne!(b.array(), c.array());
+    }
+
+    #[test]
+    fn test_deterministic_matmul() {
+        let dev: Cpu = Default::default();
+        dev.enable_deterministic();
+        let state = dev.rng_state();
+
+        let to_bits = |v: Vec<f32>| v.into_iter().map(f32::to_bits).collect::<Vec<_>>();
+        let run = || {
+            let a: Tensor<Rank2<256, 512>, f32, _> = dev.sample_normal();
+            let b: Tensor<Rank2<512, 256>, f32, _> = dev.sample_normal();
+            let mask = dev.ones_like(&a).dropout(0.5);
+            let c = a.clone().matmul(b.clone());
+            let s = c.clone().sum::<Rank0, _>();
+            (
+                to_bits(a.as_vec()),
+                to_bits(b.as_vec()),
+                to_bits(mask.as_vec()),
+                to_bits(c.as_vec()),
+                s.array().to_bits(),
+            )
+        };
+
+        // restoring the rng state repeats the samples, dropout masks and products
+        // bit-for-bit, regardless of how many threads rayon has available
+        let expected = run();
+        for num_threads in [1, 2, 4] {
+            dev.set_rng_state(&state);
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap();
+            assert!(pool.install(run) == expected, "{num_threads} threads");
+        }
+
+        dev.disable_deterministic();
+        assert!(!dev.is_deterministic());
+    }
 }
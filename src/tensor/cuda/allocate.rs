@@ -37,6 +37,7 @@ impl Cuda {
             data: Arc::new(data),
             shape,
             strides,
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         }
@@ -206,6 +207,7 @@ where
             data: Arc::new(buf),
             shape: tensor.shape,
             strides: tensor.strides,
+            offset: tensor.offset,
             device: self.cpu.clone(),
             tape: Default::default(),
         };
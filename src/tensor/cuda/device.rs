@@ -1,6 +1,6 @@
-use crate::shapes::{Shape, Unit};
+use crate::shapes::{Shape, ShapeError, Unit};
 use crate::tensor::cpu::{Cpu, CpuError};
-use crate::tensor::{cache::TensorCache, DeviceStorage, HasErr, NoneTape, Tensor};
+use crate::tensor::{cache::TensorCache, DeviceStorage, HasErr, NoneTape, RngState, Tensor};
 
 use cudarc::driver::{DevicePtr, DevicePtrMut, DeviceRepr};
 use cudarc::{
@@ -37,6 +37,13 @@ pub enum CudaError {
     Cudnn(cudarc::cudnn::CudnnError),
     Driver(DriverError),
     Cpu(CpuError),
+    Shape(ShapeError),
+}
+
+impl From<ShapeError> for CudaError {
+    fn from(value: ShapeError) -> Self {
+        Self::Shape(value)
+    }
 }
 
 impl From<CpuError> for CudaError {
@@ -258,22 +265,25 @@ impl DeviceStorage for Cuda {
     }
 
     fn tensor_to_vec<S: Shape, E: Unit, T>(&self, tensor: &Tensor<S, E, Self, T>) -> Vec<E> {
-        let buf = self
-            .cpu
-            .try_alloc_elem(tensor.data.data.len(), Default::default())
-            .unwrap();
+        // only the part of `data` that the tensor can see is copied, which is
+        // less than all of it for views
+        let span = tensor.span();
+        if span == 0 {
+            return Vec::new();
+        }
+        let buf = self.cpu.try_alloc_elem(span, Default::default()).unwrap();
         let mut cpu_tensor = Tensor {
             id: tensor.id,
             data: Arc::new(buf),
             shape: tensor.shape,
             strides: tensor.strides,
+            offset: 0,
             device: self.cpu.clone(),
             tape: NoneTape,
         };
         let buf = std::sync::Arc::get_mut(&mut cpu_tensor.data).unwrap();
-        self.dev
-            .dtoh_sync_copy_into(&tensor.data.data, &mut buf.data)
-            .unwrap();
+        let src = tensor.data.data.slice(tensor.offset..tensor.offset + span);
+        self.dev.dtoh_sync_copy_into(&src, &mut buf.data).unwrap();
         self.cpu.tensor_to_vec::<S, E, _>(&cpu_tensor)
     }
 
@@ -305,4 +315,24 @@ impl DeviceStorage for Cuda {
         cache.clear();
         Ok(())
     }
+
+    fn enable_deterministic(&self) {
+        self.cpu.enable_deterministic()
+    }
+
+    fn disable_deterministic(&self) {
+        self.cpu.disable_deterministic()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.cpu.is_deterministic()
+    }
+
+    fn rng_state(&self) -> RngState {
+        self.cpu.rng_state()
+    }
+
+    fn set_rng_state(&self, state: &RngState) {
+        self.cpu.set_rng_state(state)
+    }
 }
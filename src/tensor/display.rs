@@ -0,0 +1,374 @@
+use super::{DeviceStorage, NoneTape, Tensor};
+use crate::shapes::{Shape, Unit};
+
+use std::{format, string::String, vec::Vec};
+
+/// Controls how tensors are formatted by [Tensor::display()]. The [std::fmt::Display]
+/// and [std::fmt::Debug] impls of [Tensor] use [PrintOptions::default()], where the
+/// precision can be overridden with the format string, e.g. `{:.2}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintOptions {
+    /// The number of digits printed after the decimal point of floats. `None` prints
+    /// the shortest representation that round trips.
+    pub precision: Option<usize>,
+    /// Tensors with more elements than this are summarised with `...`.
+    pub threshold: usize,
+    /// The number of entries shown at the start and end of each dimension of
+    /// summarised tensors.
+    pub edge_items: usize,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            precision: Some(4),
+            threshold: 1000,
+            edge_items: 3,
+        }
+    }
+}
+
+/// Formats a single element. Floats are printed with `precision` digits after the
+/// decimal point if it is set, and everything else with its debug representation.
+fn format_elem<E: Unit>(x: E, precision: Option<usize>) -> String {
+    match (precision, x.to_float()) {
+        (Some(p), Some(v)) if v.is_finite() => {
+            if v != 0.0 && (v.abs() >= 1e8 || v.abs() < 1e-4) {
+                format!("{v:.p$e}")
+            } else {
+                format!("{v:.p$}")
+            }
+        }
+        _ => format!("{x:?}"),
+    }
+}
+
+/// The indices shown along a dimension of size `n`, where `None` stands for `...`.
+fn visible(n: usize, edge_items: usize, summarise: bool) -> Vec<Option<usize>> {
+    if summarise && n > 2 * edge_items {
+        (0..edge_items)
+            .map(Some)
+            .chain(core::iter::once(None))
+            .chain((n - edge_items..n).map(Some))
+            .collect()
+    } else {
+        (0..n).map(Some).collect()
+    }
+}
+
+/// The values of `t` that are shown with `opts`, in row major order. Only the shown
+/// values of summarised tensors are copied from the device, one run along the last
+/// dimension at a time.
+fn shown_values<S: Shape, E: Unit, D: DeviceStorage, T>(
+    t: &Tensor<S, E, D, T>,
+    opts: &PrintOptions,
+) -> Vec<E> {
+    if S::NUM_DIMS == 0 || t.shape.num_elements() <= opts.threshold {
+        return t.as_vec();
+    }
+    let dims = t.shape.concrete();
+    let (dims, strides) = (dims.as_ref(), t.strides.as_ref());
+    let last = S::NUM_DIMS - 1;
+
+    // the offsets of the first element of every shown run along the last dimension
+    let mut starts = std::vec![t.offset];
+    for d in 0..last {
+        starts = starts
+            .into_iter()
+            .flat_map(|start| {
+                visible(dims[d], opts.edge_items, true)
+                    .into_iter()
+                    .flatten()
+                    .map(move |i| start + i * strides[d])
+            })
+            .collect();
+    }
+    let n = dims[last];
+    let runs = if n > 2 * opts.edge_items {
+        std::vec![0..opts.edge_items, n - opts.edge_items..n]
+    } else {
+        std::vec![0..n]
+    };
+
+    let mut values = Vec::new();
+    for start in starts {
+        for run in runs.iter() {
+            let view = Tensor {
+                id: t.id,
+                data: t.data.clone(),
+                shape: (run.len(),),
+                strides: [strides[last]],
+                offset: start + run.start * strides[last],
+                device: t.device.clone(),
+                tape: NoneTape,
+            };
+            values.extend(view.as_vec());
+        }
+    }
+    values
+}
+
+/// Formats the shown `values` of a tensor with `dims` as nested brackets. `indent` is
+/// the number of columns the first bracket is printed at, which is used to align
+/// later lines.
+fn write_nested<E: Unit>(
+    f: &mut std::fmt::Formatter<'_>,
+    values: &[E],
+    dims: &[usize],
+    opts: &PrintOptions,
+    indent: usize,
+) -> std::fmt::Result {
+    let summarise = dims.iter().product::<usize>() > opts.threshold;
+
+    // format the elements first, so the columns can be aligned
+    let elems: Vec<String> = values
+        .iter()
+        .map(|&x| format_elem(x, opts.precision))
+        .collect();
+    let width = elems.iter().map(|s| s.len()).max().unwrap_or(0);
+
+    let mut elems = elems.into_iter();
+    write_dim(f, &mut elems, dims, 0, width, opts, summarise, indent)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_dim(
+    f: &mut std::fmt::Formatter<'_>,
+    elems: &mut impl Iterator<Item = String>,
+    dims: &[usize],
+    depth: usize,
+    width: usize,
+    opts: &PrintOptions,
+    summarise: bool,
+    indent: usize,
+) -> std::fmt::Result {
+    if depth == dims.len() {
+        let s = elems.next().unwrap();
+        return write!(f, "{s:>width$}");
+    }
+    let inner = dims.len() - depth - 1;
+    f.write_str("[")?;
+    for (k, i) in visible(dims[depth], opts.edge_items, summarise)
+        .into_iter()
+        .enumerate()
+    {
+        if k > 0 {
+            if inner == 0 {
+                f.write_str(", ")?;
+            } else {
+                f.write_str(",")?;
+                for _ in 0..inner {
+                    f.write_str("\n")?;
+                }
+                write!(f, "{:1$}", "", indent + depth + 1)?;
+            }
+        }
+        match i {
+            Some(_) => write_dim(f, elems, dims, depth + 1, width, opts, summarise, indent)?,
+            None => f.write_str("...")?,
+        }
+    }
+    f.write_str("]")
+}
+
+/// Formats a [Tensor] with custom [PrintOptions]. Created by [Tensor::display()].
+pub struct TensorDisplay<'a, S: Shape, E: Unit, D: DeviceStorage, T> {
+    tensor: &'a Tensor<S, E, D, T>,
+    opts: PrintOptions,
+}
+
+impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensor<S, E, D, T> {
+    /// Formats the values of the tensor as nested brackets, like numpy does.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, -2.5], [3.25, 4.0]]);
+    /// let opts = PrintOptions {
+    ///     precision: Some(2),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     std::format!("{}", t.display(opts)),
+    ///     "[[ 1.00, -2.50],\n [ 3.25,  4.00]]"
+    /// );
+    /// ```
+    pub fn display(&self, opts: PrintOptions) -> TensorDisplay<'_, S, E, D, T> {
+        TensorDisplay { tensor: self, opts }
+    }
+}
+
+impl<'a, S: Shape, E: Unit, D: DeviceStorage, T> std::fmt::Display
+    for TensorDisplay<'a, S, E, D, T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dims = self.tensor.shape.concrete();
+        let values = shown_values(self.tensor, &self.opts);
+        write_nested(f, &values, dims.as_ref(), &self.opts, 0)
+    }
+}
+
+/// The options used by the [std::fmt] impls of [Tensor], with the precision of the
+/// format string if there is one.
+fn fmt_options(f: &std::fmt::Formatter<'_>) -> PrintOptions {
+    let mut opts = PrintOptions::default();
+    if let Some(p) = f.precision() {
+        opts.precision = Some(p);
+    }
+    opts
+}
+
+/// Strips the module path of a type name, e.g. `dfdx::tensor::Cpu` to `Cpu`.
+fn short_type_name<T>() -> &'static str {
+    let name = core::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Prints the values of the tensor as nested brackets. Use `{:.N}` to print floats
+/// with `N` digits after the decimal point, or [Tensor::display()] for more options.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([1.0f32, 2.0, 3.0]);
+/// assert_eq!(std::format!("{t}"), "[1.0000, 2.0000, 3.0000]");
+/// assert_eq!(std::format!("{t:.1}"), "[1.0, 2.0, 3.0]");
+/// ```
+impl<S: Shape, E: Unit, D: DeviceStorage, T> std::fmt::Display for Tensor<S, E, D, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dims = self.shape.concrete();
+        let opts = fmt_options(f);
+        write_nested(f, &shown_values(self, &opts), dims.as_ref(), &opts, 0)
+    }
+}
+
+/// Prints the values of the tensor along with its shape, dtype and device.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([[1, 2], [3, 4]]);
+/// assert_eq!(
+///     std::format!("{t:?}"),
+///     "Tensor([[1, 2],\n        [3, 4]], shape=[2, 2], dtype=i32, device=Cpu)"
+/// );
+/// ```
+impl<S: Shape, E: Unit, D: DeviceStorage, T> std::fmt::Debug for Tensor<S, E, D, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const PREFIX: &str = "Tensor(";
+        let dims = self.shape.concrete();
+        let opts = fmt_options(f);
+        f.write_str(PREFIX)?;
+        write_nested(
+            f,
+            &shown_values(self, &opts),
+            dims.as_ref(),
+            &opts,
+            PREFIX.len(),
+        )?;
+        write!(
+            f,
+            ", shape={:?}, dtype={}, device={})",
+            dims,
+            short_type_name::<E>(),
+            short_type_name::<D>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_display_nested() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([[[1, 2], [3, 4]], [[5, 6], [7, 8]]]);
+        assert_eq!(
+            format!("{t}"),
+            "[[[1, 2],\n  [3, 4]],\n\n [[5, 6],\n  [7, 8]]]"
+        );
+
+        let t = dev.tensor([true, false]);
+        assert_eq!(format!("{t}"), "[ true, false]");
+    }
+
+    #[test]
+    fn test_display_precision() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([0.5, -12.0, 1e-5, 3e9]);
+        assert_eq!(format!("{t:.2}"), "[   0.50,  -12.00, 1.00e-5,  3.00e9]");
+        let opts = PrintOptions {
+            precision: None,
+            ..Default::default()
+        };
+        assert_eq!(
+            format!("{}", t.display(opts)),
+            "[         0.5,        -12.0,         1e-5, 3000000000.0]"
+        );
+    }
+
+    #[test]
+    fn test_display_summarised() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([0usize, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let opts = PrintOptions {
+            threshold: 5,
+            edge_items: 2,
+            ..Default::default()
+        };
+        assert_eq!(format!("{}", t.display(opts)), "[0, 1, ..., 8, 9]");
+
+        let t: Tensor<Rank2<4, 4>, f32, _> = dev.zeros();
+        let opts = PrintOptions {
+            precision: Some(1),
+            threshold: 10,
+            edge_items: 1,
+        };
+        assert_eq!(
+            format!("{}", t.display(opts)),
+            "[[0.0, ..., 0.0],\n ...,\n [0.0, ..., 0.0]]"
+        );
+    }
+
+    #[test]
+    fn test_display_summarised_view() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([[0usize, 1, 2, 3], [4, 5, 6, 7], [8, 9, 10, 11]]);
+        let t = t.permute::<Rank2<4, 3>, _>().slice((1.., ..));
+        let opts = PrintOptions {
+            threshold: 5,
+            edge_items: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            format!("{}", t.display(opts)),
+            "[[ 1, ...,  9],\n ...,\n [ 3, ..., 11]]"
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor(1.5f64);
+        assert_eq!(
+            format!("{t:?}"),
+            format!(
+                "Tensor(1.5000, shape=[], dtype=f64, device={})",
+                short_type_name::<TestDevice>()
+            )
+        );
+
+        let t: Tensor<Rank2<2, 2>, f32, _> = dev.ones();
+        let t: Tensor<_, _, _, OwnedTape<f32, _>> = t.leaky_trace();
+        assert_eq!(
+            format!("{t:.1?}"),
+            format!(
+                "Tensor([[1.0, 1.0],\n        [1.0, 1.0]], shape=[2, 2], dtype=f32, device={})",
+                short_type_name::<TestDevice>()
+            )
+        );
+    }
+}
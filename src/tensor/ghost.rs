@@ -10,6 +10,7 @@ pub struct GhostTensor<S: Shape, E: Unit, D: DeviceStorage> {
     pub(crate) len: usize,
     pub(crate) shape: S,
     pub(crate) strides: S::Concrete,
+    pub(crate) offset: usize,
     pub(crate) dev: D,
     marker: std::marker::PhantomData<E>,
 }
@@ -20,9 +21,10 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensor<S, E, D, T> {
     pub(crate) fn ghost(&self) -> GhostTensor<S, E, D> {
         GhostTensor {
             id: self.id,
-            len: self.device.len(&self.data),
+            len: self.span(),
             shape: self.shape,
             strides: self.strides,
+            offset: self.offset,
             dev: self.device.clone(),
             marker: std::marker::PhantomData,
         }
@@ -36,6 +38,7 @@ impl<S: Shape, E: Unit, D: DeviceStorage> Clone for GhostTensor<S, E, D> {
             len: self.len,
             shape: self.shape,
             strides: self.strides,
+            offset: self.offset,
             dev: self.dev.clone(),
             marker: self.marker,
         }
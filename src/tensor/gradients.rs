@@ -2,7 +2,13 @@
 #![allow(clippy::type_complexity)]
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::{boxed::Box, vec::Vec};
+use std::{boxed::Box, sync::Arc, vec::Vec};
+
+#[cfg(feature = "no-std")]
+use spin::Mutex;
+
+#[cfg(not(feature = "no-std"))]
+use std::sync::Mutex;
 
 use super::tensorlike::Tensorlike;
 use super::{storage_traits::DeviceStorage, unique_id, Tensor, UniqueId};
@@ -114,6 +120,7 @@ impl<E: Unit, D: DeviceStorage> Gradients<E, D> {
             data: std::sync::Arc::new(buf),
             shape: t.shape,
             strides: t.strides,
+            offset: 0,
             device: t.device.clone(),
             tape: Default::default(),
         }
@@ -186,6 +193,9 @@ pub struct OwnedTape<E: Unit, D: DeviceStorage> {
     /// from merged tapes are executed in the correct order.
     pub(crate) operations: Vec<(UniqueId, BackwardOp<E, D, D::Err>)>,
     pub(crate) gradients: Gradients<E, D>,
+    /// Tapes that were shared between several tensors with [Tape::share]. Each of
+    /// them is merged into this tape once when it is executed.
+    pub(crate) shared: Vec<SharedTape<E, D>>,
 }
 
 impl<E: Unit, D: DeviceStorage> Default for OwnedTape<E, D> {
@@ -193,6 +203,7 @@ impl<E: Unit, D: DeviceStorage> Default for OwnedTape<E, D> {
         Self {
             operations: Default::default(),
             gradients: Gradients::leaky(),
+            shared: Default::default(),
         }
     }
 }
@@ -211,6 +222,18 @@ impl<E: Unit, D: DeviceStorage> OwnedTape<E, D> {
     ///
     /// Note that this method takes ownership of self, so it can't be called twice!
     pub(crate) fn execute(mut self) -> Result<Gradients<E, D>, D::Err> {
+        // A shared tape is taken by the first tape that gets to it, so its operations
+        // run once even if several of the tensors sharing it end up in this tape.
+        while let Some(shared) = self.shared.pop() {
+            #[cfg(not(feature = "no-std"))]
+            let tape = shared.lock().unwrap().take();
+            #[cfg(feature = "no-std")]
+            let tape = shared.lock().take();
+            if let Some(tape) = tape {
+                self = self.merge(tape);
+            }
+        }
+
         // We must ensure that the operations are sorted in execution time order.
         // Otherwise an backward operation may not be executed in the right order
         // if multiple tapes were merged together.
@@ -223,6 +246,7 @@ impl<E: Unit, D: DeviceStorage> OwnedTape<E, D> {
 }
 
 type BackwardOp<E, D, Err> = Box<dyn FnOnce(&mut Gradients<E, D>) -> Result<(), Err>>;
+type SharedTape<E, D> = Arc<Mutex<Option<OwnedTape<E, D>>>>;
 
 /// Contains nothing. When [Tape::add_backward_op] is called, this struct does nothing.
 #[derive(Default, Debug, Clone, Copy)]
@@ -235,6 +259,11 @@ pub trait Tape<E: Unit, D: DeviceStorage>: Default + Merge<Self> + Merge<NoneTap
     fn add_backward_op<F>(&mut self, operation: F)
     where
         F: 'static + FnOnce(&mut Gradients<E, D>) -> Result<(), D::Err>;
+
+    /// Splits this tape into `n` tapes for tensors that are all computed from the
+    /// same input, e.g. the pieces of [Tensor::split_along]. Backward
+    /// then reaches the operations recorded so far through any of them.
+    fn share(self, n: usize) -> Vec<Self>;
 }
 
 impl<E: Unit, D: DeviceStorage> Tape<E, D> for OwnedTape<E, D> {
@@ -245,6 +274,15 @@ impl<E: Unit, D: DeviceStorage> Tape<E, D> for OwnedTape<E, D> {
     {
         self.operations.push((unique_id(), Box::new(operation)));
     }
+    fn share(self, n: usize) -> Vec<Self> {
+        let shared = Arc::new(Mutex::new(Some(self)));
+        (0..n)
+            .map(|_| Self {
+                shared: std::vec![shared.clone()],
+                ..Default::default()
+            })
+            .collect()
+    }
 }
 
 impl<E: Unit, D: DeviceStorage> Tape<E, D> for NoneTape {
@@ -254,6 +292,9 @@ impl<E: Unit, D: DeviceStorage> Tape<E, D> for NoneTape {
         F: 'static + FnOnce(&mut Gradients<E, D>) -> Result<(), D::Err>,
     {
     }
+    fn share(self, n: usize) -> Vec<Self> {
+        std::vec![self; n]
+    }
 }
 
 /// Combine two things
@@ -286,6 +327,7 @@ impl<E: Unit, D: DeviceStorage> Merge<OwnedTape<E, D>> for OwnedTape<E, D> {
                 .extend(leafs);
         }
         self.operations.append(&mut other.operations);
+        self.shared.append(&mut other.shared);
         self
     }
 }
@@ -96,6 +96,21 @@
 //! let t: [[f32; 3]; 2] = t.array();
 //! ```
 //!
+//! # Printing tensors
+//!
+//! Tensors implement [std::fmt::Display], which prints their values as nested brackets,
+//! and [std::fmt::Debug], which also prints their shape, dtype and device. Large tensors
+//! are summarised with `...`. Use [Tensor::display()] with [PrintOptions] to change
+//! the precision or when to summarise.
+//!
+//! ```rust
+//! # use dfdx::prelude::*;
+//! # let dev: Cpu = Default::default();
+//! let t: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+//! println!("{t:.2}");
+//! println!("{t:?}");
+//! ```
+//!
 //! # Tracing gradients
 //!
 //! Use the [Tensor::trace] or [Tensor::traced] methods to add [OwnedTape] to the [Tensor].
@@ -140,11 +155,13 @@ pub(crate) mod cache;
 pub(crate) mod cpu;
 #[cfg(feature = "cuda")]
 pub(crate) mod cuda;
+mod display;
 mod ghost;
 mod gradients;
 mod masks;
 #[cfg(feature = "numpy")]
 pub(crate) mod numpy;
+pub(crate) mod reference;
 #[cfg(feature = "safetensors")]
 pub mod safetensors;
 mod tensorlike;
@@ -155,9 +172,12 @@ mod tensor_impls;
 
 pub(crate) use ghost::GhostTensor;
 pub(crate) use storage_traits::{OneFillStorage, ZeroFillStorage};
+pub(crate) use tensor_impls::is_dense;
 pub(crate) use tensorlike::Tensorlike;
 
 pub use cpu::{Cpu, CpuError};
+pub use display::{PrintOptions, TensorDisplay};
+pub use reference::Reference;
 #[cfg(not(feature = "cuda"))]
 pub type AutoDevice = Cpu;
 
@@ -169,7 +189,7 @@ pub use cuda::{Cuda, CudaError};
 pub type AutoDevice = Cuda;
 
 pub use storage_traits::{AsArray, CopySlice, TensorFrom, TensorFromVec};
-pub use storage_traits::{DeviceStorage, HasErr};
+pub use storage_traits::{DeviceStorage, HasErr, RngState};
 pub use storage_traits::{OnesTensor, SampleTensor, TriangleTensor, ZerosTensor};
 
 pub use tensor_impls::{PutTape, SplitTape, Tensor, Trace, WithEmptyTape};
@@ -184,6 +204,7 @@ pub use gradients::{Gradients, Merge, NoneTape, OwnedTape, Tape};
 mod tests {
     use super::*;
     use crate::shapes::*;
+    use crate::tensor_ops::RealizeTo;
     use crate::tests::*;
     use std::collections::HashSet;
 
@@ -265,6 +286,23 @@ mod tests {
         assert_eq!(t.array(), [[1.0, 2.0], [3.0, 4.0]]);
     }
 
+    #[test]
+    fn test_copy_slice_view() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<4, 4>, TestDtype, _> = dev.zeros();
+        let mut v: Tensor<Rank2<4, 2>, TestDtype, _> = t.slice((.., 1..3)).realize();
+        let data =
+            [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0].map(|x| TestDtype::from_f32(x).unwrap());
+        v.copy_from(&data);
+        assert_close_to_literal!(v, [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0], [7.0, 8.0]]);
+        let mut buf = [TestDtype::default(); 8];
+        v.copy_into(&mut buf);
+        assert_eq!(buf, data);
+        let mut buf = [TestDtype::default(); 2];
+        v.slice((2..3, ..)).copy_into(&mut buf);
+        assert_eq!(buf, data[4..6]);
+    }
+
     #[test]
     fn fuzz_test_rand() {
         let dev: TestDevice = Default::default();
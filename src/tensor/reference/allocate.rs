@@ -0,0 +1,188 @@
+use crate::{
+    shapes::*,
+    tensor::{
+        cpu::Cpu, is_dense, masks::triangle_mask, storage_traits::*, unique_id, CpuError, NoneTape,
+        Tensor,
+    },
+};
+
+use super::{ravel_index, unravel_dims, Reference};
+
+use rand::{distributions::Distribution, Rng};
+use std::{sync::Arc, vec::Vec};
+
+impl Reference {
+    pub(crate) fn try_alloc_elem<E: Unit>(
+        &self,
+        numel: usize,
+        elem: E,
+    ) -> Result<Vec<E>, CpuError> {
+        let mut data: Vec<E> = Vec::new();
+        data.try_reserve(numel).map_err(|_| CpuError::OutOfMemory)?;
+        data.resize(numel, elem);
+        Ok(data)
+    }
+
+    /// Wraps a contiguous buffer into a new tensor of `shape`.
+    pub(crate) fn build_tensor<S: Shape, E: Unit>(
+        &self,
+        shape: S,
+        data: Vec<E>,
+    ) -> Tensor<S, E, Self> {
+        debug_assert_eq!(data.len(), shape.num_elements());
+        Tensor {
+            id: unique_id(),
+            data: Arc::new(data),
+            shape,
+            strides: shape.strides(),
+            offset: 0,
+            device: self.clone(),
+            tape: Default::default(),
+        }
+    }
+}
+
+impl<E: Unit> ZerosTensor<E> for Reference {
+    fn try_zeros_like<S: HasShape>(&self, src: &S) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
+        let shape = *src.shape();
+        let data = self.try_alloc_elem(shape.num_elements(), Default::default())?;
+        Ok(self.build_tensor(shape, data))
+    }
+}
+
+impl<E: Unit> ZeroFillStorage<E> for Reference {
+    fn try_fill_with_zeros(&self, storage: &mut Self::Vec<E>) -> Result<(), Self::Err> {
+        storage.fill(Default::default());
+        Ok(())
+    }
+}
+
+impl<E: Unit> OnesTensor<E> for Reference {
+    fn try_ones_like<S: HasShape>(&self, src: &S) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
+        let shape = *src.shape();
+        let data = self.try_alloc_elem(shape.num_elements(), E::ONE)?;
+        Ok(self.build_tensor(shape, data))
+    }
+}
+
+impl<E: Unit> OneFillStorage<E> for Reference {
+    fn try_fill_with_ones(&self, storage: &mut Self::Vec<E>) -> Result<(), Self::Err> {
+        storage.fill(E::ONE);
+        Ok(())
+    }
+}
+
+impl<E: Unit> TriangleTensor<E> for Reference {
+    fn try_upper_tri_like<S: HasShape>(
+        &self,
+        src: &S,
+        val: E,
+        diagonal: impl Into<Option<isize>>,
+    ) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
+        let shape = *src.shape();
+        let mut data = self.try_alloc_elem(shape.num_elements(), val)?;
+        triangle_mask(&mut data, &shape, true, diagonal.into().unwrap_or(0));
+        Ok(self.build_tensor(shape, data))
+    }
+
+    fn try_lower_tri_like<S: HasShape>(
+        &self,
+        src: &S,
+        val: E,
+        diagonal: impl Into<Option<isize>>,
+    ) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
+        let shape = *src.shape();
+        let mut data = self.try_alloc_elem(shape.num_elements(), val)?;
+        triangle_mask(&mut data, &shape, false, diagonal.into().unwrap_or(0));
+        Ok(self.build_tensor(shape, data))
+    }
+}
+
+impl<E: Unit> SampleTensor<E> for Reference {
+    fn try_sample_like<S: HasShape, D: Distribution<E>>(
+        &self,
+        src: &S,
+        distr: D,
+    ) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
+        let shape = *src.shape();
+        let mut data = self.try_alloc_elem(shape.num_elements(), Default::default())?;
+        self.try_fill_with_distr(&mut data, distr)?;
+        Ok(self.build_tensor(shape, data))
+    }
+    fn try_fill_with_distr<D: Distribution<E>>(
+        &self,
+        storage: &mut Self::Vec<E>,
+        distr: D,
+    ) -> Result<(), Self::Err> {
+        #[cfg(not(feature = "no-std"))]
+        let mut rng = self.cpu.rng.lock().unwrap();
+        #[cfg(feature = "no-std")]
+        let mut rng = self.cpu.rng.lock();
+        for v in storage.iter_mut() {
+            *v = rng.sample(&distr);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the offsets of the physical elements of a tensor, in memory order for
+/// tensors without gaps, and otherwise in row major order with broadcasted
+/// dimensions skipped.
+fn physical_indices<S: Shape, E: Unit, T>(t: &Tensor<S, E, Reference, T>) -> Vec<usize> {
+    if is_dense(&t.shape, &t.strides) {
+        return (t.offset..t.offset + t.span()).collect();
+    }
+    let mut dims = t.shape.concrete();
+    for d in 0..S::NUM_DIMS {
+        if t.strides[d] == 0 {
+            dims[d] = 1;
+        }
+    }
+    let numel = (0..S::NUM_DIMS).map(|d| dims[d]).product();
+    (0..numel)
+        .map(|i| t.offset + ravel_index::<S>(&t.strides, &unravel_dims::<S>(&dims, i)))
+        .collect()
+}
+
+impl<E: Unit> CopySlice<E> for Reference {
+    fn copy_from<S: Shape, T>(dst: &mut Tensor<S, E, Self, T>, src: &[E]) {
+        let indices = physical_indices(dst);
+        assert_eq!(src.len(), indices.len());
+        let data = Arc::make_mut(&mut dst.data);
+        for (i, v) in indices.into_iter().zip(src.iter()) {
+            data[i] = *v;
+        }
+    }
+    fn copy_into<S: Shape, T>(src: &Tensor<S, E, Self, T>, dst: &mut [E]) {
+        let indices = physical_indices(src);
+        assert_eq!(dst.len(), indices.len());
+        for (v, i) in dst.iter_mut().zip(indices) {
+            *v = src.data[i];
+        }
+    }
+}
+
+impl<E: Unit> TensorFromVec<E> for Reference {
+    fn try_tensor_from_vec<S: Shape>(
+        &self,
+        src: Vec<E>,
+        shape: S,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        if src.len() != shape.num_elements() {
+            Err(CpuError::WrongNumElements)
+        } else {
+            Ok(self.build_tensor(shape, src))
+        }
+    }
+}
+
+impl<S: Shape, E: Unit> TensorToArray<S, E> for Reference
+where
+    Cpu: TensorToArray<S, E>,
+{
+    type Array = <Cpu as TensorToArray<S, E>>::Array;
+    fn tensor_to_array<T>(&self, tensor: &Tensor<S, E, Self, T>) -> Self::Array {
+        let cpu_tensor = self.cpu.tensor_from_vec(tensor.as_vec(), tensor.shape);
+        self.cpu.tensor_to_array::<NoneTape>(&cpu_tensor)
+    }
+}
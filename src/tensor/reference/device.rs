@@ -0,0 +1,109 @@
+use crate::shapes::{Shape, Unit};
+use crate::tensor::{
+    cpu::{Cpu, CpuError},
+    storage_traits::*,
+    Tensor,
+};
+
+use std::vec::Vec;
+
+use super::physical_index;
+
+/// A deliberately naive device that stores data on the heap and executes
+/// every kernel with straightforward loops over logical indices.
+///
+/// This device is meant for cross-checking the optimized kernels of other devices
+/// (e.g. the gemm based matmul & im2col based convolutions of [Cpu]). None of its
+/// kernels use blas, parallelism, buffer re-use or any other optimization, and all
+/// outputs are allocated as new contiguous tensors.
+///
+/// Random numbers are generated by an internal [Cpu], so a [Reference] and a [Cpu]
+/// that are seeded with the same value will sample the same tensors:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// let cpu = Cpu::seed_from_u64(0);
+/// let reference = Reference::seed_from_u64(0);
+/// let a: Tensor<Rank1<3>, f32, _> = cpu.sample_normal();
+/// let b: Tensor<Rank1<3>, f32, _> = reference.sample_normal();
+/// assert_eq!(a.array(), b.array());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Reference {
+    /// Used for all random number generation.
+    pub(crate) cpu: Cpu,
+}
+
+impl Reference {
+    /// Constructs rng with the given seed.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self {
+            cpu: Cpu::seed_from_u64(seed),
+        }
+    }
+}
+
+impl HasErr for Reference {
+    type Err = CpuError;
+}
+
+impl DeviceStorage for Reference {
+    type Vec<E: Unit> = Vec<E>;
+
+    fn try_alloc_len<E: Unit>(&self, len: usize) -> Result<Self::Vec<E>, Self::Err> {
+        self.try_alloc_elem(len, Default::default())
+    }
+
+    fn random_u64(&self) -> u64 {
+        self.cpu.random_u64()
+    }
+
+    fn len<E: Unit>(&self, v: &Self::Vec<E>) -> usize {
+        v.len()
+    }
+
+    fn tensor_to_vec<S: Shape, E: Unit, T>(&self, tensor: &Tensor<S, E, Self, T>) -> Vec<E> {
+        let mut buf = Vec::with_capacity(tensor.shape.num_elements());
+        for i in 0..tensor.shape.num_elements() {
+            buf.push(
+                tensor.data[tensor.offset + physical_index(&tensor.shape, &tensor.strides, i)],
+            );
+        }
+        buf
+    }
+
+    fn try_synchronize(&self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+
+    fn try_enable_cache(&self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+
+    fn try_disable_cache(&self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+
+    fn try_empty_cache(&self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+
+    fn enable_deterministic(&self) {
+        self.cpu.enable_deterministic()
+    }
+
+    fn disable_deterministic(&self) {
+        self.cpu.disable_deterministic()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.cpu.is_deterministic()
+    }
+
+    fn rng_state(&self) -> RngState {
+        self.cpu.rng_state()
+    }
+
+    fn set_rng_state(&self, state: &RngState) {
+        self.cpu.set_rng_state(state)
+    }
+}
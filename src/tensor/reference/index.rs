@@ -0,0 +1,98 @@
+use crate::{
+    shapes::{Axes, Shape, Unit},
+    tensor::{cpu::index_to_i, Tensor},
+};
+
+use super::Reference;
+
+use std::sync::Arc;
+
+/// Returns the index of the `i`th element of `shape`, in row major order.
+pub(crate) fn unravel_index<S: Shape>(shape: &S, i: usize) -> S::Concrete {
+    unravel_dims::<S>(&shape.concrete(), i)
+}
+
+/// Returns the index of the `i`th element of a shape with `dims`, in row major order.
+pub(crate) fn unravel_dims<S: Shape>(dims: &S::Concrete, mut i: usize) -> S::Concrete {
+    let mut idx: S::Concrete = Default::default();
+    for d in (0..S::NUM_DIMS).rev() {
+        idx[d] = i % dims[d];
+        i /= dims[d];
+    }
+    idx
+}
+
+/// Returns the offset of `idx` into a buffer layed out with `strides`.
+pub(crate) fn ravel_index<S: Shape>(strides: &S::Concrete, idx: &S::Concrete) -> usize {
+    (0..S::NUM_DIMS).map(|d| idx[d] * strides[d]).sum()
+}
+
+/// Returns the offset of the `i`th (row major) element of `shape` into a buffer
+/// layed out with `strides`.
+pub(crate) fn physical_index<S: Shape>(shape: &S, strides: &S::Concrete, i: usize) -> usize {
+    ravel_index::<S>(strides, &unravel_index(shape, i))
+}
+
+/// Returns the index into `Dst` of `idx`, after removing the axes in `Ax`.
+pub(crate) fn reduce_index<Src: Shape, Dst: Shape, Ax: Axes>(idx: &Src::Concrete) -> Dst::Concrete {
+    let mut out: Dst::Concrete = Default::default();
+    let mut j = 0;
+    for i in 0..Src::NUM_DIMS {
+        if !Ax::as_array().into_iter().any(|a| a == i as isize) {
+            out[j] = idx[i];
+            j += 1;
+        }
+    }
+    out
+}
+
+impl<S: Shape, E: Unit, T> std::ops::Index<S::Concrete> for Tensor<S, E, Reference, T> {
+    type Output = E;
+    fn index(&self, index: S::Concrete) -> &Self::Output {
+        let i = index_to_i(&self.shape, &self.strides, index);
+        &self.data[self.offset + i]
+    }
+}
+
+impl<S: Shape, E: Unit, T> std::ops::IndexMut<S::Concrete> for Tensor<S, E, Reference, T> {
+    fn index_mut(&mut self, index: S::Concrete) -> &mut Self::Output {
+        let i = index_to_i(&self.shape, &self.strides, index);
+        let data = Arc::make_mut(&mut self.data);
+        &mut data[self.offset + i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::*;
+
+    #[test]
+    fn test_unravel_index() {
+        let shape: Rank3<2, 3, 4> = Default::default();
+        assert_eq!(unravel_index(&shape, 0), [0, 0, 0]);
+        assert_eq!(unravel_index(&shape, 5), [0, 1, 1]);
+        assert_eq!(unravel_index(&shape, 23), [1, 2, 3]);
+        assert_eq!(unravel_index(&(), 0), []);
+    }
+
+    #[test]
+    fn test_physical_index_broadcasted() {
+        let shape: Rank2<2, 3> = Default::default();
+        let strides = [0, 1];
+        let idx: std::vec::Vec<usize> = (0..6)
+            .map(|i| physical_index(&shape, &strides, i))
+            .collect();
+        assert_eq!(idx, [0, 1, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_physical_index_permuted() {
+        let shape: Rank2<3, 2> = Default::default();
+        let strides = [1, 3];
+        let idx: std::vec::Vec<usize> = (0..6)
+            .map(|i| physical_index(&shape, &strides, i))
+            .collect();
+        assert_eq!(idx, [0, 3, 1, 4, 2, 5]);
+    }
+}
@@ -0,0 +1,10 @@
+mod allocate;
+mod device;
+mod index;
+
+pub(crate) use index::{physical_index, ravel_index, reduce_index, unravel_dims, unravel_index};
+
+pub use device::Reference;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,589 @@
+//! Runs every tensor op forward & backward on both [Cpu] and [Reference], and
+//! asserts that the outputs & input gradients agree.
+
+use crate::{
+    nn::{builders::*, ModuleMut, ZeroGrads},
+    optim::*,
+    prelude::*,
+    tests::TestDtype,
+};
+
+use std::vec::Vec;
+
+const ABS_TOL: f64 = 1e-5;
+const REL_TOL: f64 = 1e-4;
+
+fn assert_agree<E: Dtype + num_traits::Float>(what: &str, cpu: &[E], reference: &[E]) {
+    assert_eq!(cpu.len(), reference.len(), "{what}: different lengths");
+    for (i, (&c, &r)) in cpu.iter().zip(reference.iter()).enumerate() {
+        let (c, r) = (c.to_f64().unwrap(), r.to_f64().unwrap());
+        let ok = (c.is_nan() && r.is_nan()) || (c - r).abs() <= ABS_TOL + REL_TOL * r.abs();
+        assert!(
+            ok,
+            "{what}[{i}]: cpu={c} reference={r}\n{cpu:?}\n{reference:?}"
+        );
+    }
+}
+
+/// Defines a test that runs `$body` on [Cpu] and [Reference] with the same inputs,
+/// then compares the outputs and the gradients of `out.square().sum()` with respect
+/// to every input. Inputs are sampled from a standard normal unless an initializer
+/// is given, both devices are seeded with the same value.
+macro_rules! cross_check {
+    ($name:ident, |$dev:ident $(, $x:ident: $S:ty $(= $init:block)?)*| $body:expr) => {
+        #[test]
+        fn $name() {
+            let (out_cpu, grads_cpu) =
+                cross_check!(@run Cpu, |$dev $(, $x: $S $(= $init)?)*| $body);
+            let (out_ref, grads_ref) =
+                cross_check!(@run Reference, |$dev $(, $x: $S $(= $init)?)*| $body);
+            assert_agree("out", &out_cpu, &out_ref);
+            for (i, (c, r)) in grads_cpu.iter().zip(grads_ref.iter()).enumerate() {
+                assert_agree(&std::format!("grad{i}"), c, r);
+            }
+        }
+    };
+    (@run $Dev:ident, |$dev:ident $(, $x:ident: $S:ty $(= $init:block)?)*| $body:expr) => {{
+        let $dev = $Dev::seed_from_u64(0);
+        $(let $x: Tensor<$S, TestDtype, $Dev> = cross_check!(@sample $dev $(, $init)?);)*
+        let y = {
+            $(let $x: Tensor<$S, TestDtype, $Dev, OwnedTape<TestDtype, $Dev>> = $x.leaky_trace();)*
+            $body
+        };
+        let out = y.as_vec();
+        let grads = y.square().sum::<Rank0, _>().backward();
+        let grads: Vec<Vec<TestDtype>> = std::vec![$(grads.get(&$x).as_vec()),*];
+        (out, grads)
+    }};
+    (@sample $dev:ident) => {
+        $dev.sample_normal()
+    };
+    (@sample $dev:ident, $init:block) => {
+        $init
+    };
+}
+
+/// Defines a test that compares the output of `$body` on [Cpu] and [Reference],
+/// for ops that have no gradients.
+macro_rules! cross_check_forward {
+    ($name:ident, |$dev:ident $(, $x:ident: $S:ty)*| $body:expr) => {
+        #[test]
+        fn $name() {
+            let out_cpu = {
+                let $dev = Cpu::seed_from_u64(0);
+                $(let $x: Tensor<$S, TestDtype, _> = $dev.sample_normal();)*
+                $body.as_vec()
+            };
+            let out_ref = {
+                let $dev = Reference::seed_from_u64(0);
+                $(let $x: Tensor<$S, TestDtype, _> = $dev.sample_normal();)*
+                $body.as_vec()
+            };
+            assert_eq!(out_cpu, out_ref);
+        }
+    };
+}
+
+/// Samples a view into every other element of a larger buffer, starting at
+/// offset 3, so kernels that ignore `offset` or `strides` read the wrong data.
+fn sample_view<S: ConstShape, D: Device<TestDtype>>(dev: &D) -> Tensor<S, TestDtype, D> {
+    let buf: Tensor<(usize,), TestDtype, D> = dev.sample_normal_like(&(2 * S::NUMEL + 3,));
+    let shape = S::default();
+    let mut strides = shape.strides();
+    for i in 0..S::NUM_DIMS {
+        strides[i] *= 2;
+    }
+    Tensor {
+        id: crate::tensor::unique_id(),
+        data: buf.data,
+        shape,
+        strides,
+        offset: 3,
+        device: dev.clone(),
+        tape: NoneTape,
+    }
+}
+
+#[test]
+fn test_same_samples() {
+    let cpu = Cpu::seed_from_u64(1);
+    let reference = Reference::seed_from_u64(1);
+    let a: Tensor<Rank2<3, 5>, TestDtype, _> = cpu.sample_normal();
+    let b: Tensor<Rank2<3, 5>, TestDtype, _> = reference.sample_normal();
+    assert_eq!(a.array(), b.array());
+}
+
+#[test]
+fn test_to_device_round_trip() {
+    let cpu: Cpu = Default::default();
+    let reference: Reference = Default::default();
+    let a: Tensor<Rank3<2, 3, 4>, TestDtype, _> = cpu.sample_normal();
+    let b = a
+        .clone()
+        .permute::<Rank3<4, 2, 3>, _>()
+        .to_device(&reference);
+    assert_eq!(
+        b.to_device(&cpu).array(),
+        a.permute::<Rank3<4, 2, 3>, _>().array()
+    );
+}
+
+// unary
+cross_check!(test_abs, |dev, x: Rank2<3, 5>| x.abs());
+cross_check!(test_clamp, |dev, x: Rank2<3, 5>| x.clamp(-0.5, 0.5));
+cross_check!(test_cos, |dev, x: Rank2<3, 5>| x.cos());
+cross_check!(test_exp, |dev, x: Rank2<3, 5>| x.exp());
+cross_check!(test_ln, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| x.ln());
+cross_check!(test_nans_to, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| (x - 0.5)
+    .sqrt()
+    .nans_to(0.25));
+cross_check!(test_negate, |dev, x: Rank2<3, 5>| -x);
+cross_check!(test_relu, |dev, x: Rank2<3, 5>| x.relu());
+cross_check!(test_gelu, |dev, x: Rank2<3, 5>| x.gelu());
+cross_check!(test_sigmoid, |dev, x: Rank2<3, 5>| x.sigmoid());
+cross_check!(test_sin, |dev, x: Rank2<3, 5>| x.sin());
+cross_check!(test_sqrt, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| x.sqrt());
+cross_check!(test_square, |dev, x: Rank2<3, 5>| x.square());
+cross_check!(test_tanh, |dev, x: Rank2<3, 5>| x.tanh());
+cross_check!(test_powf, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| x.powf(2.5));
+cross_check!(test_powi, |dev, x: Rank2<3, 5>| x.powi(3));
+cross_check!(test_recip, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| (x + 0.5).recip());
+cross_check!(test_scalar_arith, |dev, x: Rank2<3, 5>| ((x + 1.0) * 2.0
+    - 0.5)
+    / 3.0);
+cross_check!(test_dropout, |dev, x: Rank2<8, 8>| x.dropout(0.5));
+cross_check!(test_silu, |dev, x: Rank2<3, 5>| x.silu());
+cross_check!(test_mish, |dev, x: Rank2<3, 5>| x.mish());
+cross_check!(test_elu, |dev, x: Rank2<3, 5>| x.elu(0.7));
+cross_check!(test_selu, |dev, x: Rank2<3, 5>| x.selu());
+cross_check!(test_softplus, |dev, x: Rank2<3, 5>| x.softplus(2.0, 1.0));
+cross_check!(test_hard_sigmoid, |dev, x: Rank2<3, 5>| (x * 4.0)
+    .hard_sigmoid());
+cross_check!(test_hard_swish, |dev, x: Rank2<3, 5>| (x * 4.0)
+    .hard_swish());
+cross_check!(test_accurate_gelu, |dev, x: Rank2<3, 5>| x.accurate_gelu());
+cross_check!(test_erf, |dev, x: Rank2<3, 5>| x.erf());
+cross_check!(test_erfc, |dev, x: Rank2<3, 5>| x.erfc());
+cross_check!(test_lgamma, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| (x + 0.5)
+    .lgamma());
+cross_check!(test_digamma, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| (x + 0.5)
+    .digamma());
+cross_check!(test_log1p, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| x.log1p());
+cross_check!(test_expm1, |dev, x: Rank2<3, 5>| x.expm1());
+cross_check!(test_asin, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| (x - 0.5).asin());
+cross_check!(test_acos, |dev, x: Rank2<3, 5> = { dev.sample_uniform() }| (x - 0.5).acos());
+cross_check!(test_atan, |dev, x: Rank2<3, 5>| x.atan());
+cross_check!(test_sinh, |dev, x: Rank2<3, 5>| x.sinh());
+cross_check!(test_cosh, |dev, x: Rank2<3, 5>| x.cosh());
+cross_check!(test_floor, |dev, x: Rank2<3, 5>| (x * 4.0).floor());
+cross_check!(test_ceil, |dev, x: Rank2<3, 5>| (x * 4.0).ceil());
+cross_check!(test_round, |dev, x: Rank2<3, 5>| (x * 4.0).round());
+
+// binary
+cross_check!(test_add, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x + y);
+cross_check!(test_sub, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x - y);
+cross_check!(test_mul, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x * y);
+cross_check!(test_div, |dev, x: Rank2<3, 5>, y: Rank2<3, 5> = { dev.sample_uniform() }| x
+    / (y + 0.5));
+cross_check!(test_broadcasted_mul, |dev, x: Rank2<3, 5>, y: Rank1<5>| x
+    * y.broadcast::<Rank2<3, 5>, _>());
+cross_check!(test_permuted_add, |dev, x: Rank2<3, 5>, y: Rank2<5, 3>| x
+    + y.permute::<Rank2<3, 5>, _>());
+cross_check!(test_bce, |dev, x: Rank2<3, 5>, y: Rank2<3, 5> = { dev.sample_uniform() }| x
+    .bce_with_logits(y));
+cross_check!(test_huber_error, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x
+    .huber_error(y, 0.5));
+cross_check!(test_maximum, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x
+    .maximum(y));
+cross_check!(test_minimum, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x
+    .minimum(y));
+cross_check!(test_prelu, |dev, x: Rank2<3, 5>, a: Rank2<3, 5>| x.prelu(a));
+cross_check!(test_atan2, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x.atan2(y));
+
+// reductions
+cross_check!(test_sum_axis, |dev, x: Rank3<2, 3, 4>| x
+    .sum::<Rank2<2, 4>, _>());
+cross_check!(test_sum_all, |dev, x: Rank3<2, 3, 4>| x.sum::<Rank0, _>());
+cross_check!(test_sum_broadcasted, |dev, x: Rank1<4>| x
+    .broadcast::<Rank3<2, 3, 4>, _>()
+    .sum::<Rank1<3>, _>());
+cross_check!(test_max, |dev, x: Rank3<2, 3, 4>| x.max::<Rank2<2, 4>, _>());
+cross_check!(test_min, |dev, x: Rank3<2, 3, 4>| x.min::<Rank1<3>, _>());
+cross_check!(test_mean, |dev, x: Rank3<2, 3, 4>| x.mean::<Rank1<4>, _>());
+cross_check!(test_var, |dev, x: Rank2<3, 5>| x.var::<Rank1<3>, _>());
+cross_check!(test_stddev, |dev, x: Rank2<3, 5>| x
+    .stddev::<Rank1<5>, _>(1e-5));
+cross_check!(test_logsumexp, |dev, x: Rank2<3, 5>| x
+    .logsumexp::<Rank1<3>, _>());
+cross_check!(test_softmax, |dev, x: Rank2<3, 5>| x.softmax::<Axis<1>>());
+cross_check!(test_log_softmax, |dev, x: Rank3<2, 3, 4>| x
+    .log_softmax::<Axis<1>>());
+cross_check!(test_normalize, |dev, x: Rank2<3, 5>| x
+    .normalize::<Axis<1>>(1e-5));
+
+// shapes & indexing
+cross_check!(test_reshape, |dev, x: Rank2<3, 4>| x
+    .reshape::<Rank3<2, 2, 3>>());
+cross_check!(test_reshape_permuted, |dev, x: Rank2<3, 4>| x
+    .permute::<Rank2<4, 3>, _>()
+    .reshape::<Rank1<12>>());
+cross_check!(test_slice, |dev, x: Rank3<3, 4, 5>| x.slice((
+    1..,
+    1..3,
+    ..4
+)));
+cross_check!(test_roll, |dev, x: Rank3<2, 3, 4>| x.roll::<Axis<1>>(2));
+cross_check!(test_concat_along, |dev,
+    x: (Const<3>, usize) = { dev.sample_normal_like(&(Const, 4)) },
+    y: (Const<3>, usize) = { dev.sample_normal_like(&(Const, 2)) }
+| (x, y).concat_along(Axis::<1>));
+cross_check!(test_stack, |dev, x: Rank2<3, 4>, y: Rank2<3, 4>| [x, y]
+    .stack());
+cross_check!(test_select, |dev, x: Rank2<3, 4>| x
+    .select(dev.tensor([2, 0, 1])));
+cross_check!(test_gather, |dev, x: Rank2<3, 4>| x
+    .gather::<Rank2<3, 2>, _>(dev.tensor([[2, 0], [1, 3], [0, 0]])));
+cross_check!(test_choose, |dev, x: Rank2<2, 3>, y: Rank2<2, 3>| dev
+    .tensor([[true, false, true], [false, false, true]])
+    .choose(x, y));
+cross_check!(test_upper_tri, |dev, x: Rank2<4, 4>| x.upper_tri(1));
+cross_check!(test_upscale2d_nearest, |dev, x: Rank3<2, 3, 4>| x
+    .upscale2d::<5, 7, _>(NearestNeighbor));
+cross_check!(test_upscale2d_bilinear, |dev, x: Rank4<2, 3, 3, 4>| x
+    .upscale2d::<5, 7, _>(Bilinear));
+cross_check!(test_pool1d_max, |dev, x: Rank3<2, 3, 7>| x.pool1d(
+    Pool2DKind::Max,
+    Const::<3>,
+    Const::<2>,
+    Const::<1>,
+    Const::<1>
+));
+cross_check!(test_pool3d_avg, |dev, x: Rank5<2, 2, 3, 4, 5>| x.pool3d(
+    Pool2DKind::Avg,
+    Const::<2>,
+    Const::<1>,
+    Const::<1>,
+    Const::<2>
+));
+cross_check!(test_max_unpool2d, |dev, x: Rank4<2, 3, 5, 6>| {
+    let (y, idx) = x.max_pool2d_with_indices(Const::<3>, Const::<2>, Const::<1>, Const::<1>);
+    y.max_unpool2d(idx, Const::<5>, Const::<6>)
+});
+cross_check!(test_adaptive_pool2d_avg, |dev, x: Rank4<2, 3, 7, 5>| x
+    .adaptive_pool2d(Pool2DKind::Avg, Const::<3>, Const::<4>));
+cross_check!(test_adaptive_pool3d_max, |dev, x: Rank4<2, 5, 4, 3>| x
+    .adaptive_pool3d(Pool2DKind::Max, Const::<2>, Const::<3>, Const::<5>));
+cross_check!(test_flip, |dev, x: Rank3<2, 3, 4>| x.flip::<Axes2<0, 2>>());
+cross_check!(test_pad_constant, |dev, x: Rank2<3, 4>| x
+    .pad(((1, 0), (0, 2)), PadMode::Constant(0.5)));
+cross_check!(test_pad_reflect, |dev, x: Rank2<3, 4>| x
+    .pad(((2, 1), (1, 3)), PadMode::Reflect));
+cross_check!(test_pad_replicate, |dev, x: Rank2<3, 4>| x
+    .pad(((), (3, 2)), PadMode::Replicate));
+cross_check!(test_pad_circular, |dev, x: Rank2<3, 4>| x
+    .pad(((4, 1), ()), PadMode::Circular));
+cross_check!(test_tile, |dev, x: Rank2<2, 3>| x.tile(Axis::<1>, 3));
+cross_check!(test_repeat_interleave, |dev, x: Rank2<2, 3>| x
+    .repeat_interleave(Axis::<0>, 2));
+cross_check!(test_expand_copy, |dev, x: Rank1<3>| x
+    .broadcast::<Rank2<2, 3>, _>()
+    .expand_copy::<Rank3<4, 2, 3>, _>());
+cross_check!(test_index_select, |dev, x: Rank2<3, 4>| x
+    .index_select(Axis::<1>, dev.tensor([2, 0, 0, 1, 3])));
+cross_check!(test_scatter, |dev, x: Rank2<2, 4>, y: Rank2<2, 3>| x
+    .scatter(Axis::<1>, dev.tensor([[3, 0, 1], [2, 1, 0]]), y));
+cross_check!(test_scatter_add, |dev, x: Rank2<2, 4>, y: Rank2<2, 3>| x
+    .scatter_add(Axis::<1>, dev.tensor([[3, 0, 3], [1, 1, 0]]), y));
+cross_check!(test_masked_fill, |dev, x: Rank2<2, 3>| x
+    .masked_fill(dev.tensor([true, false, true]), -1.0));
+cross_check!(test_masked_select, |dev, x: Rank2<2, 3>| x.masked_select(
+    dev.tensor([[true, false, true], [false, false, true]])
+));
+cross_check!(test_split_along, |dev, x: Rank2<3, 5>| {
+    let [a, b] = x.split_along(Axis::<1>, [2, 3]);
+    a.sum::<Rank1<3>, _>() * b.sum::<Rank1<3>, _>()
+});
+cross_check!(test_chunk, |dev, x: Rank2<4, 3>| {
+    let mut chunks = x.chunk(Axis::<0>, 2);
+    let b = chunks.pop().unwrap();
+    let a = chunks.pop().unwrap();
+    a * b.exp()
+});
+cross_check!(test_concat, |dev, x: Rank2<3, 4>, y: Rank2<2, 4>| {
+    #[allow(deprecated)]
+    let c = x
+        .realize::<(usize, Const<4>)>()
+        .concat(y.realize::<(usize, Const<4>)>());
+    c
+});
+cross_check!(test_cumsum, |dev, x: Rank2<3, 4>| x.cumsum(Axis::<1>));
+cross_check!(test_cumprod, |dev, x: Rank2<3, 4>| x.cumprod(Axis::<0>));
+cross_check!(test_cummax, |dev, x: Rank2<3, 4>| x.cummax(Axis::<1>));
+cross_check!(test_cummin, |dev, x: Rank2<3, 4>| x.cummin(Axis::<0>));
+cross_check!(test_logcumsumexp, |dev, x: Rank2<3, 4>| x
+    .logcumsumexp(Axis::<1>));
+cross_check!(test_scan_reverse, |dev, x: Rank2<3, 4>| x.scan(
+    Axis::<1>,
+    ScanKind::Prod,
+    true
+));
+cross_check!(test_sort, |dev, x: Rank2<3, 4>| x.sort(Axis::<1>, false).0);
+cross_check!(test_sort_descending, |dev, x: Rank2<3, 4>| x
+    .sort(Axis::<0>, true)
+    .0);
+cross_check!(test_topk, |dev, x: Rank2<3, 5>| x.topk::<2, _>(Axis::<1>).0);
+cross_check!(test_unfold1d, |dev, x: Rank3<2, 3, 7>| x
+    .unfold1d(3, 2, 1, 1));
+cross_check!(test_unfold2d, |dev, x: Rank4<2, 2, 5, 4>| x
+    .unfold2d(2, 1, 1, 2));
+cross_check!(test_fold1d, |dev, x: Rank3<2, 6, 4>| x
+    .realize::<(Const<2>, usize, usize)>()
+    .fold1d(7, 3, 2, 1, 1));
+cross_check!(test_fold2d, |dev, x: Rank3<1, 8, 9>| x
+    .realize::<(Const<1>, usize, usize)>()
+    .fold2d((4, 4), 2, 1, 0, 1));
+
+// matmuls
+cross_check!(test_matmul_vec_mat, |dev, x: Rank1<3>, y: Rank2<3, 4>| x
+    .matmul(y));
+cross_check!(
+    test_matmul_mat_mat,
+    |dev, x: Rank2<2, 3>, y: Rank2<3, 4>| x.matmul(y)
+);
+cross_check!(
+    test_matmul_transposed,
+    |dev, x: Rank2<3, 2>, y: Rank2<4, 3>| x
+        .permute::<Rank2<2, 3>, _>()
+        .matmul(y.permute::<Rank2<3, 4>, _>())
+);
+cross_check!(
+    test_matmul_broadcast,
+    |dev, x: Rank3<2, 3, 4>, y: Rank2<4, 5>| x.matmul(y)
+);
+cross_check!(
+    test_matmul_batch3,
+    |dev, x: Rank3<2, 3, 4>, y: Rank3<2, 4, 5>| x.matmul(y)
+);
+cross_check!(
+    test_matmul_batch4,
+    |dev, x: Rank4<2, 2, 3, 4>, y: Rank4<2, 2, 4, 5>| x.matmul(y)
+);
+cross_check!(test_einsum, |dev, x: Rank3<2, 3, 4>, y: Rank2<4, 5>| x
+    .einsum::<Rank3<2, 5, 3>, _, _>("bij,jk->bki", y));
+
+// convolutions
+cross_check!(test_conv1d, |dev, x: Rank3<2, 4, 7>, w: Rank3<4, 2, 3>| (
+    x,
+    w.retaped::<NoneTape>()
+)
+    .conv1d(Const::<2>, Const::<1>, Const::<1>, Const::<2>));
+cross_check!(
+    test_conv2d,
+    |dev, x: Rank4<2, 3, 6, 5>, w: Rank4<4, 3, 3, 3>| (x, w.retaped::<NoneTape>())
+        .conv2d(Const::<1>, Const::<1>, Const::<2>, Const::<1>)
+);
+cross_check!(
+    test_conv3d,
+    |dev, x: Rank4<2, 4, 5, 3>, w: Rank5<2, 2, 2, 2, 2>| (x, w.retaped::<NoneTape>())
+        .conv3d(Const::<2>, Const::<1>, Const::<1>, Const::<1>)
+);
+cross_check!(
+    test_convtrans1d,
+    |dev, x: Rank3<2, 3, 6>, w: Rank3<4, 3, 3>| x.convtrans1d::<2, 1>(w.retaped::<NoneTape>())
+);
+cross_check!(
+    test_convtrans2d,
+    |dev, x: Rank3<2, 4, 3>, w: Rank4<3, 2, 3, 3>| x.convtrans2d::<2, 1>(w.retaped::<NoneTape>())
+);
+cross_check!(
+    test_convtrans3d,
+    |dev, x: Rank4<2, 3, 2, 3>, w: Rank5<2, 2, 2, 2, 2>| x
+        .convtrans3d::<1, 0>(w.retaped::<NoneTape>())
+);
+cross_check!(test_pool2d_avg, |dev, x: Rank4<2, 3, 6, 5>| x.pool2d(
+    Pool2DKind::Avg,
+    Const::<3>,
+    Const::<2>,
+    Const::<1>,
+    Const::<1>
+));
+cross_check!(test_pool2d_max, |dev, x: Rank3<3, 6, 5>| x.pool2d(
+    Pool2DKind::Max,
+    Const::<2>,
+    Const::<1>,
+    Const::<0>,
+    Const::<2>
+));
+
+// views
+cross_check!(test_view_exp, |dev, x: Rank2<3, 4> = { sample_view(&dev) }| x.exp());
+cross_check!(
+    test_view_add,
+    |dev, x: Rank2<3, 4> = { sample_view(&dev) }, y: Rank2<3, 4>| x + y
+);
+cross_check!(
+    test_view_mul,
+    |dev, x: Rank2<3, 4> = { sample_view(&dev) }, y: Rank2<3, 4> = { sample_view(&dev) }| x * y
+);
+cross_check!(test_view_sum, |dev, x: Rank3<2, 3, 4> = { sample_view(&dev) }| x
+    .sum::<Rank2<2, 4>, _>());
+cross_check!(
+    test_view_matmul,
+    |dev, x: Rank2<3, 4> = { sample_view(&dev) }, y: Rank2<4, 5> = { sample_view(&dev) }| x
+        .matmul(y)
+);
+cross_check!(
+    test_view_conv2d,
+    |dev, x: Rank3<2, 5, 5> = { sample_view(&dev) }| {
+        let w: Tensor<Rank4<3, 2, 2, 2>, TestDtype, _> = dev.sample_normal();
+        (x, w).conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+    }
+);
+cross_check!(test_view_pool2d, |dev, x: Rank3<2, 4, 4> = { sample_view(&dev) }| x
+    .pool2d(Pool2DKind::Max, Const::<2>, Const::<2>, Const::<0>, Const::<1>));
+cross_check!(test_view_slice, |dev, x: Rank2<3, 4> = { sample_view(&dev) }| x
+    .slice((1.., 1..3)));
+cross_check!(
+    test_view_stack,
+    |dev, x: Rank2<3, 4> = { sample_view(&dev) }, y: Rank2<3, 4> = { sample_view(&dev) }| [x, y]
+        .stack()
+);
+cross_check!(test_view_select, |dev, x: Rank2<3, 4> = { sample_view(&dev) }| x
+    .select(dev.tensor([2, 0, 1])));
+
+// no gradients
+cross_check_forward!(test_cmp, |dev, x: Rank2<3, 5>, y: Rank2<3, 5>| x
+    .clone()
+    .lt(&y)
+    & x.clone().ge(&y.clone().negate())
+    | x.clone().eq(&x) ^ x.gt(0.5));
+cross_check_forward!(test_bool_not, |dev, x: Rank2<3, 5>| !x.le(0.0));
+cross_check_forward!(test_argsort, |dev, x: Rank2<3, 5>| x
+    .argsort(Axis::<1>, true));
+cross_check_forward!(test_argmax, |dev, x: Rank2<3, 5>| x.argmax::<Rank1<3>, _>());
+cross_check_forward!(test_to_dtype, |dev, x: Rank2<3, 5>| x.to_dtype::<f64>());
+
+#[test]
+fn test_attention_reshape() {
+    fn run<D: Device<TestDtype> + TryAttentionReshape<TestDtype>>(dev: D) -> [Vec<TestDtype>; 3] {
+        let qkv: Tensor<(usize, Const<12>), TestDtype, D> = dev.sample_normal_like(&(3, Const));
+        let k: Tensor<(Const<2>, Const<2>, usize), TestDtype, D> =
+            dev.sample_normal_like(&(Const, Const, 2));
+        let v: Tensor<(Const<2>, usize, Const<2>), TestDtype, D> =
+            dev.sample_normal_like(&(Const, 2, Const));
+        let (q, k, v) = dev.attention_reshape(&qkv, &k, &v);
+        [q.as_vec(), k.as_vec(), v.as_vec()]
+    }
+    assert_eq!(run(Cpu::seed_from_u64(0)), run(Reference::seed_from_u64(0)));
+}
+
+#[test]
+fn test_axpy() {
+    fn run<D: Device<TestDtype>>(dev: D) -> Vec<TestDtype> {
+        let mut a: Tensor<Rank2<3, 4>, TestDtype, D> = dev.sample_normal();
+        let b: Tensor<Rank2<3, 4>, TestDtype, D> = dev.sample_normal();
+        a.axpy(0.9, &b, -0.3);
+        a.as_vec()
+    }
+    assert_agree(
+        "axpy",
+        &run(Cpu::seed_from_u64(0)),
+        &run(Reference::seed_from_u64(0)),
+    );
+}
+
+#[test]
+fn test_copy_slice_view() {
+    fn run<D: Device<TestDtype>>(dev: D) -> Vec<TestDtype> {
+        let a: Tensor<Rank2<4, 4>, TestDtype, D> = dev.sample_normal();
+        // columns 1..3 of `a`, built by hand because only Cpu slices into views
+        let mut view = Tensor {
+            id: crate::tensor::unique_id(),
+            data: a.data.clone(),
+            shape: (Const::<4>, Const::<2>),
+            strides: [4, 1],
+            offset: 1,
+            device: dev.clone(),
+            tape: NoneTape,
+        };
+        let mut buf = std::vec![TestDtype::default(); 8];
+        view.copy_into(&mut buf);
+        let doubled: Vec<TestDtype> = buf.iter().map(|&x| x + x).collect();
+        view.copy_from(&doubled);
+        let mut out = buf;
+        out.extend(view.as_vec());
+        out.extend(a.as_vec());
+        out
+    }
+    assert_eq!(run(Cpu::seed_from_u64(0)), run(Reference::seed_from_u64(0)));
+}
+
+/// Takes a few optimizer steps on a small mlp, and returns the final parameters.
+fn train<D: Device<TestDtype>, O: Optimizer<Mlp<TestDtype, D>, D, TestDtype>>(
+    dev: D,
+    make_opt: impl FnOnce(&Mlp<TestDtype, D>) -> O,
+) -> Vec<Vec<TestDtype>> {
+    let mut model = dev.build_module::<MlpBuilder, TestDtype>();
+    let mut opt = make_opt(&model);
+    let mut grads = model.alloc_grads();
+    for _ in 0..3 {
+        let x: Tensor<Rank2<4, 3>, TestDtype, D> = dev.sample_normal();
+        let loss = model.forward_mut(x.traced(grads)).square().mean();
+        grads = loss.backward();
+        opt.update(&mut model, &grads).unwrap();
+        model.zero_grads(&mut grads);
+    }
+    std::vec![
+        model.0.weight.as_vec(),
+        model.0.bias.as_vec(),
+        model.2.weight.as_vec(),
+        model.2.bias.as_vec(),
+    ]
+}
+
+type MlpBuilder = (Linear<3, 5>, ReLU, Linear<5, 2>);
+type Mlp<E, D> = <MlpBuilder as BuildOnDevice<D, E>>::Built;
+
+fn assert_params_agree(cpu: Vec<Vec<TestDtype>>, reference: Vec<Vec<TestDtype>>) {
+    for (i, (c, r)) in cpu.iter().zip(reference.iter()).enumerate() {
+        assert_agree(&std::format!("param{i}"), c, r);
+    }
+}
+
+#[test]
+fn test_sgd() {
+    let cfg = SgdConfig {
+        lr: 1e-1,
+        momentum: Some(Momentum::Nesterov(0.9)),
+        weight_decay: Some(WeightDecay::L2(1e-2)),
+    };
+    assert_params_agree(
+        train(Cpu::seed_from_u64(0), |m| Sgd::new(m, cfg)),
+        train(Reference::seed_from_u64(0), |m| Sgd::new(m, cfg)),
+    );
+}
+
+#[test]
+fn test_adam() {
+    let cfg = AdamConfig {
+        weight_decay: Some(WeightDecay::Decoupled(1e-2)),
+        ..Default::default()
+    };
+    assert_params_agree(
+        train(Cpu::seed_from_u64(0), |m| Adam::new(m, cfg)),
+        train(Reference::seed_from_u64(0), |m| Adam::new(m, cfg)),
+    );
+}
+
+#[test]
+fn test_rmsprop() {
+    let cfg = RMSpropConfig {
+        centered: true,
+        momentum: Some(0.5),
+        ..Default::default()
+    };
+    assert_params_agree(
+        train(Cpu::seed_from_u64(0), |m| RMSprop::new(m, cfg)),
+        train(Reference::seed_from_u64(0), |m| RMSprop::new(m, cfg)),
+    );
+}
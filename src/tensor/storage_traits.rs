@@ -1,4 +1,4 @@
-use rand::distributions::Distribution;
+use rand::{distributions::Distribution, rngs::StdRng};
 use rand_distr::{Standard, StandardNormal};
 use std::vec::Vec;
 
@@ -8,7 +8,7 @@ use super::Tensor;
 
 /// Represents something that has an error associated type
 pub trait HasErr: Sized {
-    type Err: std::fmt::Debug + std::fmt::Display;
+    type Err: std::fmt::Debug + std::fmt::Display + From<ShapeError>;
 }
 
 /// Convert tensors to [std::vec::Vec]
@@ -78,6 +78,64 @@ pub trait DeviceStorage: 'static + std::fmt::Debug + Default + Clone + HasErr {
     /// Tries to empty the cache of the device. See [DeviceStorage::empty_cache] for
     /// details of when this is useful.
     fn try_empty_cache(&self) -> Result<(), Self::Err>;
+
+    /// Enables deterministic mode, where ops that need randomness (e.g.
+    /// [crate::tensor_ops::dropout()]) draw from their own stream, derived from the device's
+    /// seed & the number of streams created so far, instead of from the device's shared rng.
+    /// Their results then don't depend on what else was sampled from the device.
+    ///
+    /// On [crate::tensor::Cpu] matmuls also run single threaded, so running the same ops
+    /// with the same seed produces bit-for-bit identical results. This may make them slower.
+    /// On `Cuda` only the randomness is affected, and kernels that reduce in parallel
+    /// (including cuBLAS) may still differ between runs.
+    ///
+    /// By default this does nothing, for devices without a deterministic mode.
+    fn enable_deterministic(&self) {}
+
+    /// Disables deterministic mode. See [DeviceStorage::enable_deterministic].
+    fn disable_deterministic(&self) {}
+
+    /// Whether deterministic mode is enabled. See [DeviceStorage::enable_deterministic].
+    ///
+    /// By default this is always `false`.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    /// Returns a snapshot of the device's random number generation state, which
+    /// can be restored later with [DeviceStorage::set_rng_state].
+    ///
+    /// By default the snapshot is empty, for devices without random number
+    /// generation state of their own, and restoring it does nothing.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// let dev: Cpu = Default::default();
+    /// let state = dev.rng_state();
+    /// let a: Tensor<Rank1<3>, f32, _> = dev.sample_normal();
+    /// dev.set_rng_state(&state);
+    /// let b: Tensor<Rank1<3>, f32, _> = dev.sample_normal();
+    /// assert_eq!(a.array(), b.array());
+    /// ```
+    fn rng_state(&self) -> RngState {
+        RngState {
+            rng: None,
+            num_streams: 0,
+        }
+    }
+
+    /// Restores a snapshot taken with [DeviceStorage::rng_state]. Empty snapshots
+    /// are ignored.
+    fn set_rng_state(&self, _state: &RngState) {}
+}
+
+/// A snapshot of a device's random number generation state.
+/// See [DeviceStorage::rng_state].
+#[derive(Clone, Debug)]
+pub struct RngState {
+    /// `None` for devices that don't have their own rng.
+    pub(crate) rng: Option<StdRng>,
+    pub(crate) num_streams: u64,
 }
 
 /// Internal trait - Represents something that can allocate its own gradient.
@@ -89,7 +147,7 @@ pub trait AllocGrad: HasErr {
 impl<S: Shape, E: Unit, D: DeviceStorage, T> AllocGrad for Tensor<S, E, D, T> {
     type Gradient = D::Vec<E>;
     fn try_alloc_grad(&self) -> Result<Self::Gradient, D::Err> {
-        self.device.try_alloc_grad(self.data.as_ref())
+        self.device.try_alloc_len(self.span())
     }
 }
 
@@ -101,6 +159,7 @@ pub trait CopySlice<E: Unit>: DeviceStorage {
 
 impl<S: Shape, E: Unit, D: CopySlice<E>, T> Tensor<S, E, D, T> {
     /// Copy *physical* data from a slice - **panics** if there are not enough elements in the slice.
+    /// Views of part of a tensor only copy the elements they can see.
     ///
     /// ```rust
     /// # use dfdx::prelude::*;
@@ -115,6 +174,7 @@ impl<S: Shape, E: Unit, D: CopySlice<E>, T> Tensor<S, E, D, T> {
     }
 
     /// Copy *physical* data into a slice - **panics** if there are not enough elements in the tensor.
+    /// Views of part of a tensor only copy the elements they can see.
     ///
     /// ```rust
     /// # use dfdx::prelude::*;
@@ -566,6 +626,37 @@ where
     }
 }
 
+impl<
+        E: Unit,
+        const M: usize,
+        const N: usize,
+        const O: usize,
+        const P: usize,
+        const Q: usize,
+        D,
+    > TensorFrom<[[[[[E; Q]; P]; O]; N]; M], Rank5<M, N, O, P, Q>, E> for D
+where
+    D: DeviceStorage + TensorFromVec<E>,
+{
+    fn try_tensor(
+        &self,
+        src: [[[[[E; Q]; P]; O]; N]; M],
+    ) -> Result<Tensor<Rank5<M, N, O, P, Q>, E, Self>, Self::Err> {
+        let vec: Vec<E> = src
+            .iter()
+            .flat_map(|v| v.iter())
+            .flat_map(|v| v.iter())
+            .flat_map(|v| v.iter())
+            .flat_map(|v| v.iter().copied())
+            .collect();
+
+        self.try_tensor_from_vec(
+            vec,
+            (Const::<M>, Const::<N>, Const::<O>, Const::<P>, Const::<Q>),
+        )
+    }
+}
+
 impl<E: Unit, S: ConstShape, D: DeviceStorage + TensorFromVec<E>> TensorFrom<Vec<E>, S, E> for D {
     fn try_tensor(&self, src: Vec<E>) -> Result<Tensor<S, E, Self>, Self::Err> {
         self.try_tensor_from_vec(src, S::default())
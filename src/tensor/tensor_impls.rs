@@ -28,16 +28,50 @@ use std::sync::Arc;
 /// // A 3d tensor with usize elements, stored on the Cpu, without any tape
 /// type C = Tensor<Rank3<4, 2, 3>, usize, Cpu, NoneTape>;
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Tensor<S: Shape, E: Unit, D: DeviceStorage, T = NoneTape> {
     pub(crate) id: UniqueId,
     pub(crate) data: Arc<D::Vec<E>>,
     pub(crate) shape: S,
     pub(crate) strides: S::Concrete,
+    /// The index into `data` of the first element. This is only non-zero for
+    /// views into part of another tensor, e.g. from [crate::tensor_ops::slice()].
+    pub(crate) offset: usize,
     pub(crate) device: D,
     pub(crate) tape: T,
 }
 
+/// The number of elements between the first & last (inclusive) elements of
+/// a tensor with `shape` & `strides`.
+pub(crate) fn span<S: Shape>(shape: &S, strides: &S::Concrete) -> usize {
+    if shape.num_elements() == 0 {
+        return 0;
+    }
+    let dims = shape.concrete();
+    1 + (0..S::NUM_DIMS)
+        .map(|i| (dims[i] - 1) * strides[i])
+        .sum::<usize>()
+}
+
+/// Whether every position in `0..span(shape, strides)` is reached by some
+/// index, i.e. the elements have no gaps between them.
+pub(crate) fn is_dense<S: Shape>(shape: &S, strides: &S::Concrete) -> bool {
+    let dims = shape.concrete();
+    let mut dims: std::vec::Vec<(usize, usize)> = (0..S::NUM_DIMS)
+        .map(|i| (strides[i], dims[i]))
+        .filter(|&(stride, dim)| stride != 0 && dim != 1)
+        .collect();
+    dims.sort_unstable();
+    let mut expected = 1;
+    for (stride, dim) in dims {
+        if stride != expected {
+            return false;
+        }
+        expected *= dim;
+    }
+    true
+}
+
 impl<S: Shape, E: Unit, D: DeviceStorage, T> HasShape for Tensor<S, E, D, T> {
     type WithShape<New: Shape> = Tensor<New, E, D, T>;
     type Shape = S;
@@ -95,6 +129,7 @@ impl<S: Shape, E: Unit, F: Unit, D: DeviceStorage> Trace<E, D> for Tensor<S, F,
         self.put_tape(OwnedTape {
             gradients,
             operations: std::vec::Vec::new(),
+            shared: std::vec::Vec::new(),
         })
     }
 }
@@ -107,6 +142,7 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensor<S, E, D, T> {
             data: self.data.clone(),
             shape: self.shape,
             strides: self.strides,
+            offset: self.offset,
             device: self.device.clone(),
             tape: Default::default(),
         }
@@ -116,6 +152,29 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensor<S, E, D, T> {
     pub fn device(&self) -> &D {
         &self.device
     }
+
+    /// The length of the part of `data` that holds this tensor's elements,
+    /// starting at `offset`. Gradients are allocated with this length, and are
+    /// indexed with `strides` (without `offset`).
+    pub(crate) fn span(&self) -> usize {
+        span(&self.shape, &self.strides)
+    }
+
+    /// Whether this tensor's elements span all of `data`, which is only false
+    /// for views into part of another tensor.
+    pub(crate) fn is_whole(&self) -> bool {
+        self.offset == 0 && self.device.len(&self.data) == self.span()
+    }
+
+    /// Replaces the data of views with a new buffer, so they can be written to
+    /// without copying the entire buffer they are a view into.
+    fn try_detach_view(&mut self) -> Result<(), D::Err> {
+        if !self.is_whole() {
+            self.data = Arc::new(self.device.try_alloc_len(self.span())?);
+            self.offset = 0;
+        }
+        Ok(())
+    }
 }
 
 /// Put a tape of type `T` into the tensor
@@ -138,6 +197,7 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T> PutTape<T> for Tensor<S, E, D> {
             data: self.data,
             shape: self.shape,
             strides: self.strides,
+            offset: self.offset,
             device: self.device,
             tape,
         }
@@ -171,6 +231,7 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T> SplitTape for Tensor<S, E, D, T> {
                 data: self.data,
                 shape: self.shape,
                 strides: self.strides,
+                offset: self.offset,
                 device: self.device,
                 tape: NoneTape,
             },
@@ -192,6 +253,7 @@ impl<S: Shape, E: Dtype, D: DeviceStorage, T: Default> WithEmptyTape for Tensor<
             data: self.data.clone(),
             shape: self.shape,
             strides: self.strides,
+            offset: self.offset,
             device: self.device.clone(),
             tape: Default::default(),
         }
@@ -205,6 +267,7 @@ impl<S: Shape, E: Dtype, D: ZeroFillStorage<E>, T> Tensor<S, E, D, T> {
     }
     /// Fallible version of [Tensor::fill_with_zeros]
     pub fn try_fill_with_zeros(&mut self) -> Result<(), D::Err> {
+        self.try_detach_view()?;
         self.device
             .try_fill_with_zeros(Arc::make_mut(&mut self.data))
     }
@@ -217,6 +280,7 @@ impl<S: Shape, E: Dtype, D: OneFillStorage<E>, T> Tensor<S, E, D, T> {
     }
     /// Fallible version of [Tensor::fill_with_ones]
     pub fn try_fill_with_ones(&mut self) -> Result<(), D::Err> {
+        self.try_detach_view()?;
         self.device
             .try_fill_with_ones(Arc::make_mut(&mut self.data))
     }
@@ -233,6 +297,7 @@ impl<S: Shape, E: Unit, D: SampleTensor<E>, T> Tensor<S, E, D, T> {
         &mut self,
         distr: Distr,
     ) -> Result<(), D::Err> {
+        self.try_detach_view()?;
         self.device
             .try_fill_with_distr(Arc::make_mut(&mut self.data), distr)
     }
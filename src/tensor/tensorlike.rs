@@ -14,8 +14,11 @@ pub trait Tensorlike<S: Shape, E: Unit, D: DeviceStorage>:
     AllocGrad<Gradient = D::Vec<E>> + HasErr<Err = D::Err> + HasShape<Shape = S>
 {
     fn id(&self) -> UniqueId;
+    /// The length of the gradient of this tensor.
     fn len(&self) -> usize;
     fn strides(&self) -> S::Concrete;
+    /// The index into [Tensorlike::data] of the first element.
+    fn offset(&self) -> usize;
     fn dev(&self) -> &D;
     fn data(&self) -> Option<&D::Vec<E>>;
 }
@@ -26,13 +29,17 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensorlike<S, E, D> for Tensor<S, E
     }
 
     fn len(&self) -> usize {
-        self.device.len(&self.data)
+        self.span()
     }
 
     fn strides(&self) -> S::Concrete {
         self.strides
     }
 
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
     fn dev(&self) -> &D {
         &self.device
     }
@@ -55,6 +62,10 @@ impl<S: Shape, E: Unit, D: DeviceStorage> Tensorlike<S, E, D> for GhostTensor<S,
         self.strides
     }
 
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
     fn dev(&self) -> &D {
         &self.dev
     }
@@ -0,0 +1,36 @@
+#include "unary_op_macros.cuh"
+#define _USE_MATH_DEFINES
+#include <math.h>
+
+struct AccurateGeLUKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T accurate_gelu_fwd(T x) {
+    T one = 1.0;
+    T half = 0.5;
+    T frac_1_sqrt_2 = M_SQRT1_2;
+    return half * x * (one + erfg(x * frac_1_sqrt_2));
+}
+
+template<typename T>
+__device__ __forceinline__ T accurate_gelu_bwd(T x) {
+    T one = 1.0;
+    T half = 0.5;
+    T frac_1_sqrt_2 = M_SQRT1_2;
+    T frac_1_sqrt_2pi = M_2_SQRTPI * M_SQRT1_2 * 0.5;
+    T cdf = half * (one + erfg(x * frac_1_sqrt_2));
+    T pdf = expg(-half * x * x) * frac_1_sqrt_2pi;
+    return cdf + x * pdf;
+}
+
+UNARY_OP(__half, accurate_gelu_fwd_f16, accurate_gelu_bwd_f16, AccurateGeLUKernelOp,
+        accurate_gelu_fwd(x),
+        accurate_gelu_bwd(x))
+
+UNARY_OP(float, accurate_gelu_fwd_f32, accurate_gelu_bwd_f32, AccurateGeLUKernelOp,
+        accurate_gelu_fwd(x),
+        accurate_gelu_bwd(x))
+
+UNARY_OP(double, accurate_gelu_fwd_f64, accurate_gelu_bwd_f64, AccurateGeLUKernelOp,
+        accurate_gelu_fwd(x),
+        accurate_gelu_bwd(x))
@@ -0,0 +1,21 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use crate::tensor_ops::special::erf;
+use num_traits::{Float, FloatConst};
+
+impl<F: Float + FloatConst> UnaryDerivative<F> for super::AccurateGeLUKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        let half = F::from(0.5).unwrap();
+        half * x * (F::one() + F::from(erf((x * F::FRAC_1_SQRT_2()).to_f64().unwrap())).unwrap())
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        let half = F::from(0.5).unwrap();
+        let cdf =
+            half * (F::one() + F::from(erf((x * F::FRAC_1_SQRT_2()).to_f64().unwrap())).unwrap());
+        let pdf = (-half * x * x).exp() * F::FRAC_2_SQRT_PI() * F::FRAC_1_SQRT_2() * half;
+        cdf + x * pdf
+    }
+}
@@ -0,0 +1,61 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AccurateGeLUKernelOp;
+
+/// The exact [Gaussian Linear Unit (GeLU)](https://paperswithcode.com/method/gelu). `0.5 * x * (1 + erf(x / sqrt(2)))`
+///
+/// [gelu()](super::gelu()) is a faster approximation of this using `tanh`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.accurate_gelu();
+/// ```
+pub fn accurate_gelu<S: Shape, E: Dtype, D: UnaryKernel<AccurateGeLUKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.accurate_gelu()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<AccurateGeLUKernelOp, E>, T: Tape<E, D>>
+    Tensor<S, E, D, T>
+{
+    /// See [accurate_gelu]
+    pub fn accurate_gelu(self) -> Self {
+        self.try_accurate_gelu().unwrap()
+    }
+    /// See [accurate_gelu]
+    pub fn try_accurate_gelu(self) -> Result<Self, D::Err> {
+        try_unary_op(AccurateGeLUKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_accurate_gelu() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().accurate_gelu();
+        assert_close_to_literal!(r, [-0.04550026, -0.15865525, 0.0, 0.84134475, 1.95449974]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [-0.01704636, -0.01666309, 0.1, 0.21666309, 0.21704636]
+        );
+    }
+}
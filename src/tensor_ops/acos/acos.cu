@@ -0,0 +1,21 @@
+#include "unary_op_macros.cuh"
+
+struct AcosKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T acos_bwd(T x) {
+    T one = 1.0;
+    return -recipg(sqrtg(one - x * x));
+}
+
+UNARY_OP(__half, acos_fwd_f16, acos_bwd_f16, AcosKernelOp,
+        acosg(x),
+        acos_bwd(x))
+
+UNARY_OP(float, acos_fwd_f32, acos_bwd_f32, AcosKernelOp,
+        acosg(x),
+        acos_bwd(x))
+
+UNARY_OP(double, acos_fwd_f64, acos_bwd_f64, AcosKernelOp,
+        acosg(x),
+        acos_bwd(x))
@@ -0,0 +1,15 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use num_traits::Float;
+
+impl<F: Float> UnaryDerivative<F> for super::AcosKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        x.acos()
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        -(F::one() - x * x).sqrt().recip()
+    }
+}
@@ -0,0 +1,11 @@
+use super::AcosKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for AcosKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/acos.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(AcosKernelOp, half::f16, PTX, "acos_fwd_f16", "acos_bwd_f16");
+cuda_unary!(AcosKernelOp, f32, PTX, "acos_fwd_f32", "acos_bwd_f32");
+cuda_unary!(AcosKernelOp, f64, PTX, "acos_fwd_f64", "acos_bwd_f64");
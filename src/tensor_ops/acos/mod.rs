@@ -0,0 +1,63 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AcosKernelOp;
+
+/// [Inverse cosine](https://en.wikipedia.org/wiki/Inverse_trigonometric_functions). `acos(t)`
+///
+/// The derivative is `-1 / sqrt(1 - t^2)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, -0.5, 0.0, 0.5]);
+/// let r = t.acos();
+/// ```
+pub fn acos<S: Shape, E: Dtype, D: UnaryKernel<AcosKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.acos()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<AcosKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [acos]
+    pub fn acos(self) -> Self {
+        self.try_acos().unwrap()
+    }
+    /// See [acos]
+    pub fn try_acos(self) -> Result<Self, D::Err> {
+        try_unary_op(AcosKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_3};
+
+    #[test]
+    fn test_acos() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-0.5, -0.25, 0.0, 0.25, 0.5])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().acos();
+        assert_close_to_literal!(
+            r,
+            [2.0 * FRAC_PI_3, 1.8234766, FRAC_PI_2, 1.3181161, FRAC_PI_3]
+        );
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [-0.23094011, -0.20655911, -0.2, -0.20655911, -0.23094011]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+#include "cuda_utils.cuh"
+
+enum AdaptivePoolKind {
+    AVG,
+    MIN,
+    MAX,
+};
+
+struct AdaptivePoolOp {
+    AdaptivePoolKind kind;
+    size_t batch;
+    size_t chan;
+    size_t d_in;
+    size_t d_out;
+    size_t h_in;
+    size_t h_out;
+    size_t w_in;
+    size_t w_out;
+};
+
+// first input of the window of output `o`
+__device__ size_t window_start(const size_t o, const size_t dim_in, const size_t dim_out) {
+    return (o * dim_in) / dim_out;
+}
+
+// one past the last input of the window of output `o`
+__device__ size_t window_end(const size_t o, const size_t dim_in, const size_t dim_out) {
+    return ((o + 1) * dim_in + dim_out - 1) / dim_out;
+}
+
+__device__ double init(const AdaptivePoolOp op) {
+    switch(op.kind) {
+        case AVG:
+            return 0.0;
+        case MIN:
+            return INFINITY;
+        case MAX:
+            return -INFINITY;
+    }
+}
+
+template<typename T>
+__device__ T accum(const AdaptivePoolOp op, const T accum, const T item) {
+    switch(op.kind) {
+        case AVG:
+            return accum + item;
+        case MIN:
+            return ming(accum, item);
+        case MAX:
+            return maxg(accum, item);
+    }
+}
+
+template<typename T>
+__device__ T normalize(const AdaptivePoolOp op, const T item, const size_t num_elements) {
+    double num_f64 = num_elements;
+    double scale_f64 = 1.0 / num_f64;
+    T scale = scale_f64;
+    switch(op.kind) {
+        case AVG:
+            return item * scale;
+        case MIN:
+            return item;
+        case MAX:
+            return item;
+    }
+}
+
+template<typename T>
+__device__ T filter(const AdaptivePoolOp op, const T item, const T needle, const T haystack) {
+    T zero = 0.0;
+    switch(op.kind){
+        case AVG:
+            return item;
+        case MIN:
+            return (needle == haystack) ? item : zero;
+        case MAX:
+            return (needle == haystack) ? item : zero;
+    }
+}
+
+template<typename T>
+__device__ void adaptive_pool_fwd(
+    const AdaptivePoolOp op,
+    const size_t *inp_strides,
+    const size_t *out_strides,
+    const T *inp, // 5d (Batch, Channels, Depth, Height, Width)
+    T *out // 5d (Batch, Channels, DepthOut, HeightOut, WidthOut)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    const size_t numel = op.batch * op.chan * op.d_out * op.h_out * op.w_out;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t ow = idx % op.w_out;
+    idx /= op.w_out;
+    const size_t oh = idx % op.h_out;
+    idx /= op.h_out;
+    const size_t od = idx % op.d_out;
+    idx /= op.d_out;
+    const size_t c = idx % op.chan;
+    idx /= op.chan;
+    const size_t b = idx % op.batch;
+    idx /= op.batch;
+
+    const size_t z0 = window_start(od, op.d_in, op.d_out);
+    const size_t z1 = window_end(od, op.d_in, op.d_out);
+    const size_t y0 = window_start(oh, op.h_in, op.h_out);
+    const size_t y1 = window_end(oh, op.h_in, op.h_out);
+    const size_t x0 = window_start(ow, op.w_in, op.w_out);
+    const size_t x1 = window_end(ow, op.w_in, op.w_out);
+
+    T tmp = init(op);
+    for (size_t z = z0; z < z1; z++) {
+        for (size_t y = y0; y < y1; y++) {
+            for (size_t x = x0; x < x1; x++) {
+                auto inp_i = b * inp_strides[0] + c * inp_strides[1] + z * inp_strides[2] + y * inp_strides[3] + x * inp_strides[4];
+                tmp = accum(op, tmp, inp[inp_i]);
+            }
+        }
+    }
+
+    out[i] = normalize(op, tmp, (z1 - z0) * (y1 - y0) * (x1 - x0));
+}
+
+template<typename T>
+__device__ void adaptive_pool_bwd(
+    const AdaptivePoolOp op,
+    const size_t *inp_strides,
+    const size_t *out_strides,
+    const T *inp, // 5d (Batch, Channels, Depth, Height, Width)
+    T *grad_inp,
+    const T *out, // 5d (Batch, Channels, DepthOut, HeightOut, WidthOut)
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    const size_t numel = op.batch * op.chan * op.d_in * op.h_in * op.w_in;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t x = idx % op.w_in;
+    idx /= op.w_in;
+    const size_t y = idx % op.h_in;
+    idx /= op.h_in;
+    const size_t z = idx % op.d_in;
+    idx /= op.d_in;
+    const size_t c = idx % op.chan;
+    idx /= op.chan;
+    const size_t b = idx % op.batch;
+    idx /= op.batch;
+
+    const T inp_v = inp[i];
+
+    // the outputs whose windows contain an input are found by swapping the
+    // roles of the input & output sizes.
+    T tmp = 0.0;
+    for (size_t od = window_start(z, op.d_out, op.d_in); od < window_end(z, op.d_out, op.d_in); od++) {
+        const size_t nz = window_end(od, op.d_in, op.d_out) - window_start(od, op.d_in, op.d_out);
+        for (size_t oh = window_start(y, op.h_out, op.h_in); oh < window_end(y, op.h_out, op.h_in); oh++) {
+            const size_t ny = window_end(oh, op.h_in, op.h_out) - window_start(oh, op.h_in, op.h_out);
+            for (size_t ow = window_start(x, op.w_out, op.w_in); ow < window_end(x, op.w_out, op.w_in); ow++) {
+                const size_t nx = window_end(ow, op.w_in, op.w_out) - window_start(ow, op.w_in, op.w_out);
+                auto out_i = b * out_strides[0] + c * out_strides[1] + od * out_strides[2] + oh * out_strides[3] + ow * out_strides[4];
+                tmp += normalize(op, filter(op, grad_out[out_i], out[out_i], inp_v), nz * ny * nx);
+            }
+        }
+    }
+    grad_inp[i] += tmp;
+}
+
+#define ADAPTIVE_POOL_OP(TYPENAME, fwd, bwd) \
+extern "C" __global__ void fwd( \
+    const AdaptivePoolOp op, \
+    const size_t *inp_strides, \
+    const size_t *out_strides, \
+    const TYPENAME *inp, \
+    TYPENAME *out \
+) { \
+    adaptive_pool_fwd(op, inp_strides, out_strides, inp, out); \
+} \
+extern "C" __global__ void bwd( \
+    const AdaptivePoolOp op, \
+    const size_t *inp_strides, \
+    const size_t *out_strides, \
+    const TYPENAME *inp, \
+    TYPENAME *grad_inp, \
+    const TYPENAME *out, \
+    const TYPENAME *grad_out \
+) { \
+    adaptive_pool_bwd(op, inp_strides, out_strides, inp, grad_inp, out, grad_out); \
+}
+
+ADAPTIVE_POOL_OP(__half, adaptive_pool_fwd_f16, adaptive_pool_bwd_f16);
+ADAPTIVE_POOL_OP(float, adaptive_pool_fwd_f32, adaptive_pool_bwd_f32);
+ADAPTIVE_POOL_OP(double, adaptive_pool_fwd_f64, adaptive_pool_bwd_f64);
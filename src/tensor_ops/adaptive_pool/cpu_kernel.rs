@@ -0,0 +1,109 @@
+use crate::{shapes::*, tensor::*};
+
+use super::window;
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+impl<E: Float + Dtype> super::AdaptivePoolKernel<E> for Cpu {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::AdaptivePoolOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = inp.strides;
+        let ostr = out.strides;
+
+        let buf = &inp.data[inp.offset..];
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    let zs = window(od, op.d_in, op.d_out);
+                    for oh in 0..op.h_out {
+                        let ys = window(oh, op.h_in, op.h_out);
+                        for ow in 0..op.w_out {
+                            let xs = window(ow, op.w_in, op.w_out);
+                            let num_elements = zs.len() * ys.len() * xs.len();
+                            let mut tmp = op.kind.init();
+                            for z in zs.clone() {
+                                for y in ys.clone() {
+                                    for x in xs.clone() {
+                                        let inp_idx = b * istr[0]
+                                            + c * istr[1]
+                                            + z * istr[2]
+                                            + y * istr[3]
+                                            + x * istr[4];
+                                        tmp = op.kind.accum(&tmp, &buf[inp_idx]);
+                                    }
+                                }
+                            }
+                            let out_idx = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            out_buf[out_idx] = op.kind.normalize(tmp, num_elements);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::AdaptivePoolOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = inp.strides;
+        let ostr = out.strides;
+
+        let inp_buf = &inp.data[inp.offset..];
+        let out_buf = out.data.as_ref();
+
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    let zs = window(od, op.d_in, op.d_out);
+                    for oh in 0..op.h_out {
+                        let ys = window(oh, op.h_in, op.h_out);
+                        for ow in 0..op.w_out {
+                            let xs = window(ow, op.w_in, op.w_out);
+                            let num_elements = zs.len() * ys.len() * xs.len();
+                            let out_idx = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            let go = op.kind.normalize(grad_out[out_idx], num_elements);
+                            let vo = out_buf[out_idx];
+                            for z in zs.clone() {
+                                for y in ys.clone() {
+                                    for x in xs.clone() {
+                                        let inp_idx = b * istr[0]
+                                            + c * istr[1]
+                                            + z * istr[2]
+                                            + y * istr[3]
+                                            + x * istr[4];
+                                        grad_inp[inp_idx] +=
+                                            op.kind.filter(go, inp_buf[inp_idx], vo);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
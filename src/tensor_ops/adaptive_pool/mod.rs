@@ -0,0 +1,385 @@
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{shapes::*, tensor::*};
+
+use super::{Pool2DKind, ReshapeTo};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AdaptivePoolOp {
+    pub kind: Pool2DKind,
+    pub batch: usize,
+    pub chan: usize,
+    pub d_in: usize,
+    pub d_out: usize,
+    pub h_in: usize,
+    pub h_out: usize,
+    pub w_in: usize,
+    pub w_out: usize,
+}
+
+/// The range of inputs that output `o` reduces over, when an axis of size
+/// `dim_in` is pooled to size `dim_out`.
+fn window(o: usize, dim_in: usize, dim_out: usize) -> std::ops::Range<usize> {
+    (o * dim_in) / dim_out..((o + 1) * dim_in + dim_out - 1) / dim_out
+}
+
+pub(super) trait AdaptivePoolKernel<E: Dtype>: DeviceStorage {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err>;
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: AdaptivePoolOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: AdaptivePoolOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Pools sequences with shape `(Channels, Length)` or `(Batch, Channels, Length)` to a
+/// fixed output length, no matter the input length.
+///
+/// Output `i` reduces over inputs `floor(i * L / L_OUT)..ceil((i + 1) * L / L_OUT)`,
+/// the same windows as pytorch's `AdaptiveAvgPool1d` & `AdaptiveMaxPool1d`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let x: Tensor<Rank2<3, 10>, f32, _> = dev.zeros();
+/// let _: Tensor<Rank2<3, 4>, f32, _> = x.adaptive_pool1d(Pool2DKind::Avg, Const::<4>);
+/// ```
+pub trait TryAdaptivePool1D<L>: Sized {
+    type Pooled;
+    type Error: std::fmt::Debug;
+
+    fn adaptive_pool1d(self, kind: Pool2DKind, l: L) -> Self::Pooled {
+        self.try_adaptive_pool1d(kind, l).unwrap()
+    }
+
+    fn try_adaptive_pool1d(self, kind: Pool2DKind, l: L) -> Result<Self::Pooled, Self::Error>;
+}
+
+/// Pools images with shape `(Channels, Height, Width)` or `(Batch, Channels, Height, Width)`
+/// to a fixed output height and width, no matter the input size. See [TryAdaptivePool1D]
+/// for the windows along each axis.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let x: Tensor<Rank4<2, 3, 13, 9>, f32, _> = dev.zeros();
+/// let _: Tensor<Rank4<2, 3, 7, 7>, f32, _> =
+///     x.adaptive_pool2d(Pool2DKind::Max, Const::<7>, Const::<7>);
+/// ```
+pub trait TryAdaptivePool2D<H, W>: Sized {
+    type Pooled;
+    type Error: std::fmt::Debug;
+
+    fn adaptive_pool2d(self, kind: Pool2DKind, h: H, w: W) -> Self::Pooled {
+        self.try_adaptive_pool2d(kind, h, w).unwrap()
+    }
+
+    fn try_adaptive_pool2d(self, kind: Pool2DKind, h: H, w: W)
+        -> Result<Self::Pooled, Self::Error>;
+}
+
+/// Pools volumes with shape `(Channels, Depth, Height, Width)` or
+/// `(Batch, Channels, Depth, Height, Width)` to a fixed output depth, height and width,
+/// no matter the input size. See [TryAdaptivePool1D] for the windows along each axis.
+pub trait TryAdaptivePool3D<Z, H, W>: Sized {
+    type Pooled;
+    type Error: std::fmt::Debug;
+
+    fn adaptive_pool3d(self, kind: Pool2DKind, d: Z, h: H, w: W) -> Self::Pooled {
+        self.try_adaptive_pool3d(kind, d, h, w).unwrap()
+    }
+
+    fn try_adaptive_pool3d(
+        self,
+        kind: Pool2DKind,
+        d: Z,
+        h: H,
+        w: W,
+    ) -> Result<Self::Pooled, Self::Error>;
+}
+
+impl<Chan: Dim, L: Dim, OutL: Dim, E: Dtype, D, T: Tape<E, D>> TryAdaptivePool1D<OutL>
+    for Tensor<(Chan, L), E, D, T>
+where
+    D: AdaptivePoolKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+{
+    type Pooled = Tensor<(Chan, OutL), E, D, T>;
+    type Error = D::Err;
+
+    fn try_adaptive_pool1d(
+        self,
+        kind: Pool2DKind,
+        out_l: OutL,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (chan, l) = self.shape;
+        let vol = self.try_reshape_like(&(Const::<1>, chan, Const::<1>, Const::<1>, l))?;
+        let out = vol.try_adaptive_pool3d(kind, Const::<1>, Const::<1>, out_l)?;
+        out.try_reshape_like(&(chan, out_l))
+    }
+}
+
+impl<Batch: Dim, Chan: Dim, L: Dim, OutL: Dim, E: Dtype, D, T: Tape<E, D>> TryAdaptivePool1D<OutL>
+    for Tensor<(Batch, Chan, L), E, D, T>
+where
+    D: AdaptivePoolKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+{
+    type Pooled = Tensor<(Batch, Chan, OutL), E, D, T>;
+    type Error = D::Err;
+
+    fn try_adaptive_pool1d(
+        self,
+        kind: Pool2DKind,
+        out_l: OutL,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (batch, chan, l) = self.shape;
+        let vol = self.try_reshape_like(&(batch, chan, Const::<1>, Const::<1>, l))?;
+        let out = vol.try_adaptive_pool3d(kind, Const::<1>, Const::<1>, out_l)?;
+        out.try_reshape_like(&(batch, chan, out_l))
+    }
+}
+
+impl<Chan: Dim, H: Dim, W: Dim, OutH: Dim, OutW: Dim, E: Dtype, D, T: Tape<E, D>>
+    TryAdaptivePool2D<OutH, OutW> for Tensor<(Chan, H, W), E, D, T>
+where
+    D: AdaptivePoolKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+{
+    type Pooled = Tensor<(Chan, OutH, OutW), E, D, T>;
+    type Error = D::Err;
+
+    fn try_adaptive_pool2d(
+        self,
+        kind: Pool2DKind,
+        out_h: OutH,
+        out_w: OutW,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (chan, h, w) = self.shape;
+        let vol = self.try_reshape_like(&(Const::<1>, chan, Const::<1>, h, w))?;
+        let out = vol.try_adaptive_pool3d(kind, Const::<1>, out_h, out_w)?;
+        out.try_reshape_like(&(chan, out_h, out_w))
+    }
+}
+
+impl<Batch: Dim, Chan: Dim, H: Dim, W: Dim, OutH: Dim, OutW: Dim, E: Dtype, D, T: Tape<E, D>>
+    TryAdaptivePool2D<OutH, OutW> for Tensor<(Batch, Chan, H, W), E, D, T>
+where
+    D: AdaptivePoolKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+{
+    type Pooled = Tensor<(Batch, Chan, OutH, OutW), E, D, T>;
+    type Error = D::Err;
+
+    fn try_adaptive_pool2d(
+        self,
+        kind: Pool2DKind,
+        out_h: OutH,
+        out_w: OutW,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (batch, chan, h, w) = self.shape;
+        let vol = self.try_reshape_like(&(batch, chan, Const::<1>, h, w))?;
+        let out = vol.try_adaptive_pool3d(kind, Const::<1>, out_h, out_w)?;
+        out.try_reshape_like(&(batch, chan, out_h, out_w))
+    }
+}
+
+impl<
+        Chan: Dim,
+        Z: Dim,
+        H: Dim,
+        W: Dim,
+        OutZ: Dim,
+        OutH: Dim,
+        OutW: Dim,
+        E: Dtype,
+        D,
+        T: Tape<E, D>,
+    > TryAdaptivePool3D<OutZ, OutH, OutW> for Tensor<(Chan, Z, H, W), E, D, T>
+where
+    D: AdaptivePoolKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+{
+    type Pooled = Tensor<(Chan, OutZ, OutH, OutW), E, D, T>;
+    type Error = D::Err;
+
+    fn try_adaptive_pool3d(
+        self,
+        kind: Pool2DKind,
+        out_d: OutZ,
+        out_h: OutH,
+        out_w: OutW,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (chan, d, h, w) = self.shape;
+        let vol = self.try_reshape_like(&(Const::<1>, chan, d, h, w))?;
+        let out = vol.try_adaptive_pool3d(kind, out_d, out_h, out_w)?;
+        out.try_reshape_like(&(chan, out_d, out_h, out_w))
+    }
+}
+
+impl<
+        Batch: Dim,
+        Chan: Dim,
+        Z: Dim,
+        H: Dim,
+        W: Dim,
+        OutZ: Dim,
+        OutH: Dim,
+        OutW: Dim,
+        E: Dtype,
+        D: AdaptivePoolKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+        T: Tape<E, D>,
+    > TryAdaptivePool3D<OutZ, OutH, OutW> for Tensor<(Batch, Chan, Z, H, W), E, D, T>
+{
+    type Pooled = Tensor<(Batch, Chan, OutZ, OutH, OutW), E, D, T>;
+    type Error = D::Err;
+
+    fn try_adaptive_pool3d(
+        self,
+        kind: Pool2DKind,
+        out_d: OutZ,
+        out_h: OutH,
+        out_w: OutW,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (batch, chan, d, h, w) = self.shape;
+        // the kernels only handle contiguous inputs, so views are copied first
+        let inp = self.try_contiguous()?;
+        assert!(
+            out_d.size() > 0 && out_h.size() > 0 && out_w.size() > 0,
+            "Adaptive pooling output sizes must be non zero"
+        );
+        let op = AdaptivePoolOp {
+            kind,
+            batch: batch.size(),
+            chan: chan.size(),
+            d_in: d.size(),
+            d_out: out_d.size(),
+            h_in: h.size(),
+            h_out: out_h.size(),
+            w_in: w.size(),
+            w_out: out_w.size(),
+        };
+        let (inp, mut tape) = inp.split_tape();
+        let mut out = inp.device.alloc((batch, chan, out_d, out_h, out_w))?;
+        AdaptivePoolKernel::forward(&inp.device, op, &inp, &mut out)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        let out_clone = out.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            AdaptivePoolKernel::backward(&inp.device, op, &inp, grad_inp, &out_clone, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_window() {
+        let windows: std::vec::Vec<_> = (0..3).map(|o| window(o, 10, 3)).collect();
+        assert_eq!(windows, [0..4, 3..7, 6..10]);
+        let windows: std::vec::Vec<_> = (0..4).map(|o| window(o, 2, 4)).collect();
+        assert_eq!(windows, [0..1, 0..1, 1..2, 1..2]);
+    }
+
+    #[test]
+    fn test_adaptive_pool1d_avg() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[1.0, 2., 3., 4., 5.], [-1., 0., 1., 0., -1.]])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().adaptive_pool1d(Pool2DKind::Avg, Const::<3>);
+        assert_close_to_literal!(r, [[1.5, 3.0, 4.5], [-0.5, 1. / 3., -0.5]]);
+        let g = r.sum().backward();
+        let v = 1.0 / 3.0;
+        assert_close_to_literal!(
+            g.get(&x),
+            [
+                [0.5, 0.5 + v, v, 0.5 + v, 0.5],
+                [0.5, 0.5 + v, v, 0.5 + v, 0.5]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_adaptive_pool1d_upsamples() {
+        let dev: TestDevice = Default::default();
+        let x = dev.tensor([[1.0, 2.]]).to_dtype::<TestDtype>();
+        let r = x.leaky_trace().adaptive_pool1d(Pool2DKind::Max, 5);
+        assert_eq!(r.shape, (Const::<1>, 5));
+        assert_close_to_literal!(r.realize::<Rank2<1, 5>>(), [[1., 1., 2., 2., 2.]]);
+    }
+
+    #[test]
+    fn test_adaptive_pool2d_max() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[[1.0, 5., 2., 0.], [3., 4., -1., 6.], [0., 2., 8., 1.]]])
+            .to_dtype::<TestDtype>();
+        let r = x
+            .leaky_trace()
+            .adaptive_pool2d(Pool2DKind::Max, Const::<2>, Const::<2>);
+        assert_close_to_literal!(r, [[[5., 6.], [4., 8.]]]);
+        let g = r.exp().sum().backward();
+        let (a, b, c, d) = (5f64.exp(), 6f64.exp(), 4f64.exp(), 8f64.exp());
+        assert_close_to_literal!(
+            g.get(&x),
+            [[[0., a, 0., 0.], [0., c, 0., b], [0., 0., d, 0.]]]
+        );
+    }
+
+    #[test]
+    fn test_adaptive_pool2d_global_avg() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<2, 3, 5, 7>, TestDtype, _> = dev.sample_normal();
+        let r = x
+            .leaky_trace()
+            .adaptive_pool2d(Pool2DKind::Avg, Const::<1>, Const::<1>);
+        let r2 = x.leaky_trace().mean::<Rank2<2, 3>, _>();
+        assert_close_to_tensor!(r.retaped::<NoneTape>().reshape::<Rank2<2, 3>>(), r2);
+        let g = r.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_close_to_tensor!(g.get(&x), g2.get(&x));
+    }
+
+    #[test]
+    fn test_adaptive_pool3d_matches_pool3d() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank5<2, 3, 4, 6, 8>, TestDtype, _> = dev.sample_normal();
+        let r =
+            x.leaky_trace()
+                .adaptive_pool3d(Pool2DKind::Max, Const::<2>, Const::<3>, Const::<4>);
+        let r2 = x
+            .leaky_trace()
+            .pool3d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank5<2, 3, 2, 3, 4>>();
+        assert_close_to_tensor!(r.retaped::<NoneTape>(), r2.retaped::<NoneTape>());
+        let g = r.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_close_to_tensor!(g.get(&x), g2.get(&x));
+    }
+}
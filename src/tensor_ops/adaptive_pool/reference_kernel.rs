@@ -0,0 +1,112 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+impl super::AdaptivePoolOp {
+    /// All the `(z, y, x)` input positions that output `(od, oh, ow)` reduces over.
+    fn window(&self, od: usize, oh: usize, ow: usize) -> std::vec::Vec<(usize, usize, usize)> {
+        let mut window = std::vec::Vec::new();
+        for z in super::window(od, self.d_in, self.d_out) {
+            for y in super::window(oh, self.h_in, self.h_out) {
+                for x in super::window(ow, self.w_in, self.w_out) {
+                    window.push((z, y, x));
+                }
+            }
+        }
+        window
+    }
+}
+
+impl<E: Float + Dtype> super::AdaptivePoolKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::AdaptivePoolOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let (istr, ostr) = (inp.strides, out.strides);
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    for oh in 0..op.h_out {
+                        for ow in 0..op.w_out {
+                            let window = op.window(od, oh, ow);
+                            let scale = E::from(window.len()).unwrap();
+                            let values = window.into_iter().map(|(z, y, x)| {
+                                inp.data[inp.offset
+                                    + b * istr[0]
+                                    + c * istr[1]
+                                    + z * istr[2]
+                                    + y * istr[3]
+                                    + x * istr[4]]
+                            });
+                            let i_out = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            out_buf[i_out] = match op.kind {
+                                super::Pool2DKind::Avg => {
+                                    values.fold(E::zero(), |a, v| a + v) / scale
+                                }
+                                super::Pool2DKind::Min => values.fold(E::infinity(), E::min),
+                                super::Pool2DKind::Max => values.fold(E::neg_infinity(), E::max),
+                            };
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::AdaptivePoolOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let (istr, ostr) = (inp.strides, out.strides);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    for oh in 0..op.h_out {
+                        for ow in 0..op.w_out {
+                            let i_out = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            let window = op.window(od, oh, ow);
+                            let scale = E::from(window.len()).unwrap();
+                            for (z, y, x) in window {
+                                let i_inp = b * istr[0]
+                                    + c * istr[1]
+                                    + z * istr[2]
+                                    + y * istr[3]
+                                    + x * istr[4];
+                                grad_inp[i_inp] += match op.kind {
+                                    super::Pool2DKind::Avg => grad_out[i_out] / scale,
+                                    _ if inp.data[inp.offset + i_inp] == out.data[i_out] => {
+                                        grad_out[i_out]
+                                    }
+                                    _ => E::zero(),
+                                };
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -61,7 +61,7 @@ where
 {
     /// See [add]
     fn try_add(self, rhs: Tensor<S, E, D, R>) -> Result<Self, Self::Err> {
-        try_binary_op(BinaryAddKernelOp, self, rhs)
+        try_binary_op("add", BinaryAddKernelOp, self, rhs)
     }
 }
 
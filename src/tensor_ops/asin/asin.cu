@@ -0,0 +1,21 @@
+#include "unary_op_macros.cuh"
+
+struct AsinKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T asin_bwd(T x) {
+    T one = 1.0;
+    return recipg(sqrtg(one - x * x));
+}
+
+UNARY_OP(__half, asin_fwd_f16, asin_bwd_f16, AsinKernelOp,
+        asing(x),
+        asin_bwd(x))
+
+UNARY_OP(float, asin_fwd_f32, asin_bwd_f32, AsinKernelOp,
+        asing(x),
+        asin_bwd(x))
+
+UNARY_OP(double, asin_fwd_f64, asin_bwd_f64, AsinKernelOp,
+        asing(x),
+        asin_bwd(x))
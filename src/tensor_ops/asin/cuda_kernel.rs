@@ -0,0 +1,11 @@
+use super::AsinKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for AsinKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/asin.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(AsinKernelOp, half::f16, PTX, "asin_fwd_f16", "asin_bwd_f16");
+cuda_unary!(AsinKernelOp, f32, PTX, "asin_fwd_f32", "asin_bwd_f32");
+cuda_unary!(AsinKernelOp, f64, PTX, "asin_fwd_f64", "asin_bwd_f64");
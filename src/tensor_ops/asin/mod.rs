@@ -0,0 +1,60 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AsinKernelOp;
+
+/// [Inverse sine](https://en.wikipedia.org/wiki/Inverse_trigonometric_functions). `asin(t)`
+///
+/// The derivative is `1 / sqrt(1 - t^2)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, -0.5, 0.0, 0.5]);
+/// let r = t.asin();
+/// ```
+pub fn asin<S: Shape, E: Dtype, D: UnaryKernel<AsinKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.asin()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<AsinKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [asin]
+    pub fn asin(self) -> Self {
+        self.try_asin().unwrap()
+    }
+    /// See [asin]
+    pub fn try_asin(self) -> Result<Self, D::Err> {
+        try_unary_op(AsinKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use std::f64::consts::FRAC_PI_6;
+
+    #[test]
+    fn test_asin() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-0.5, -0.25, 0.0, 0.25, 0.5])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().asin();
+        assert_close_to_literal!(r, [-FRAC_PI_6, -0.25268026, 0.0, 0.25268026, FRAC_PI_6]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [0.23094011, 0.20655911, 0.2, 0.20655911, 0.23094011]
+        );
+    }
+}
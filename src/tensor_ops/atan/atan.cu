@@ -0,0 +1,21 @@
+#include "unary_op_macros.cuh"
+
+struct AtanKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T atan_bwd(T x) {
+    T one = 1.0;
+    return recipg(one + x * x);
+}
+
+UNARY_OP(__half, atan_fwd_f16, atan_bwd_f16, AtanKernelOp,
+        atang(x),
+        atan_bwd(x))
+
+UNARY_OP(float, atan_fwd_f32, atan_bwd_f32, AtanKernelOp,
+        atang(x),
+        atan_bwd(x))
+
+UNARY_OP(double, atan_fwd_f64, atan_bwd_f64, AtanKernelOp,
+        atang(x),
+        atan_bwd(x))
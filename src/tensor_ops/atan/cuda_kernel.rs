@@ -0,0 +1,11 @@
+use super::AtanKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for AtanKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/atan.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(AtanKernelOp, half::f16, PTX, "atan_fwd_f16", "atan_bwd_f16");
+cuda_unary!(AtanKernelOp, f32, PTX, "atan_fwd_f32", "atan_bwd_f32");
+cuda_unary!(AtanKernelOp, f64, PTX, "atan_fwd_f64", "atan_bwd_f64");
@@ -0,0 +1,57 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AtanKernelOp;
+
+/// [Inverse tangent](https://en.wikipedia.org/wiki/Inverse_trigonometric_functions). `atan(t)`
+///
+/// The derivative is `1 / (1 + t^2)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.atan();
+/// ```
+pub fn atan<S: Shape, E: Dtype, D: UnaryKernel<AtanKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.atan()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<AtanKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [atan]
+    pub fn atan(self) -> Self {
+        self.try_atan().unwrap()
+    }
+    /// See [atan]
+    pub fn try_atan(self) -> Result<Self, D::Err> {
+        try_unary_op(AtanKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use std::f64::consts::FRAC_PI_4;
+
+    #[test]
+    fn test_atan() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().atan();
+        assert_close_to_literal!(r, [-1.1071487, -FRAC_PI_4, 0.0, FRAC_PI_4, 1.1071487]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(g.get(&x), [0.04, 0.1, 0.2, 0.1, 0.04]);
+    }
+}
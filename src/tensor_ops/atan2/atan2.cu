@@ -0,0 +1,36 @@
+#include "binary_op_macros.cuh"
+
+struct Atan2KernelOp {};
+
+template<typename T>
+__device__ T op_f(T x, T y) {
+    return atan2g(x, y);
+}
+
+template<typename T>
+__device__ T op_dfdx(T x, T y) {
+    return y / (x * x + y * y);
+}
+
+template<typename T>
+__device__ T op_dfdy(T x, T y) {
+    return -x / (x * x + y * y);
+}
+
+BINARY_OP(__half, atan2_fwd_f16, atan2_bwd_lhs_f16, atan2_bwd_rhs_f16, Atan2KernelOp,
+    op_f(x, y),
+    op_dfdx(x, y),
+    op_dfdy(x, y)
+)
+
+BINARY_OP(float, atan2_fwd_f32, atan2_bwd_lhs_f32, atan2_bwd_rhs_f32, Atan2KernelOp,
+    op_f(x, y),
+    op_dfdx(x, y),
+    op_dfdy(x, y)
+)
+
+BINARY_OP(double, atan2_fwd_f64, atan2_bwd_lhs_f64, atan2_bwd_rhs_f64, Atan2KernelOp,
+    op_f(x, y),
+    op_dfdx(x, y),
+    op_dfdy(x, y)
+)
@@ -0,0 +1,17 @@
+use crate::tensor_ops::cpu_kernels::BinaryDerivative;
+
+impl<F: num_traits::Float> BinaryDerivative<F> for super::Atan2KernelOp {
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F, &y: &F) -> F {
+        x.atan2(y)
+    }
+    #[inline(always)]
+    fn dfdx(&self, &x: &F, &y: &F) -> F {
+        y / (x * x + y * y)
+    }
+    #[inline(always)]
+    fn dfdy(&self, &x: &F, &y: &F) -> F {
+        -x / (x * x + y * y)
+    }
+}
@@ -0,0 +1,32 @@
+use super::Atan2KernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_binary;
+
+unsafe impl cudarc::driver::DeviceRepr for Atan2KernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/atan2.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_binary!(
+    Atan2KernelOp,
+    half::f16,
+    PTX,
+    "atan2_fwd_f16",
+    "atan2_bwd_lhs_f16",
+    "atan2_bwd_rhs_f16"
+);
+cuda_binary!(
+    Atan2KernelOp,
+    f32,
+    PTX,
+    "atan2_fwd_f32",
+    "atan2_bwd_lhs_f32",
+    "atan2_bwd_rhs_f32"
+);
+cuda_binary!(
+    Atan2KernelOp,
+    f64,
+    PTX,
+    "atan2_fwd_f64",
+    "atan2_bwd_lhs_f64",
+    "atan2_bwd_rhs_f64"
+);
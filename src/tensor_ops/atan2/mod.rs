@@ -0,0 +1,80 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::{ops::try_binary_op, Device};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Atan2KernelOp;
+
+/// Element wise four quadrant arctangent of `lhs / rhs`, in the range `[-pi, pi]`.
+///
+/// The derivatives are `rhs / (lhs^2 + rhs^2)` for `lhs` and `-lhs / (lhs^2 + rhs^2)` for `rhs`.
+///
+/// **Pytorch equivalent**: `torch.atan2(a, b)`
+///
+/// Example:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let y = dev.tensor([1.0, 1.0, -1.0]);
+/// let x = dev.tensor([1.0, -1.0, 0.0]);
+/// let r = y.atan2(x);
+/// ```
+pub fn atan2<S: Shape, E: Dtype, D: Device<E>, LTape: Tape<E, D> + Merge<R>, R: Default>(
+    lhs: Tensor<S, E, D, LTape>,
+    rhs: Tensor<S, E, D, R>,
+) -> Tensor<S, E, D, LTape> {
+    lhs.atan2(rhs)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, LTape: Tape<E, D>> Tensor<S, E, D, LTape> {
+    /// See [atan2]
+    pub fn atan2<R: Default>(self, rhs: Tensor<S, E, D, R>) -> Self
+    where
+        LTape: Merge<R>,
+    {
+        self.try_atan2(rhs).unwrap()
+    }
+
+    /// See [atan2]
+    pub fn try_atan2<R: Default>(self, rhs: Tensor<S, E, D, R>) -> Result<Self, D::Err>
+    where
+        LTape: Merge<R>,
+    {
+        try_binary_op("atan2", Atan2KernelOp, self, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn test_atan2() {
+        let dev: TestDevice = Default::default();
+        let y = dev
+            .tensor([[1.0, -1.0, 0.0], [2.0, 0.0, -3.0]])
+            .to_dtype::<TestDtype>();
+        let x = dev
+            .tensor([[1.0, 1.0, -1.0], [0.0, 2.0, -3.0]])
+            .to_dtype::<TestDtype>();
+
+        let r = y.leaky_trace().atan2(x.clone());
+        assert_close_to_literal!(
+            r,
+            [
+                [FRAC_PI_4, -FRAC_PI_4, PI],
+                [FRAC_PI_2, 0.0, -3.0 * FRAC_PI_4]
+            ]
+        );
+
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&y), [[0.5, 0.5, -1.0], [0.0, 0.5, -0.16666667]]);
+        assert_close_to_literal!(g.get(&x), [[-0.5, 0.5, 0.0], [-0.5, 0.0, 0.16666667]]);
+    }
+}
@@ -3,6 +3,7 @@ use crate::{shapes::*, tensor::*};
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 pub type Query<const NUM_HEADS: usize, const HEAD_DIM: usize, E, D> =
     Tensor<(Const<NUM_HEADS>, usize, Const<HEAD_DIM>), E, D>;
@@ -0,0 +1,57 @@
+use super::*;
+use crate::tensor::Reference;
+
+impl<E: Dtype> super::AttentionReshapeKernel<E> for Reference {
+    fn forward<const THREE_HIDDEN_DIM: usize, const NUM_HEADS: usize, const HEAD_DIM: usize>(
+        &self,
+        qkv: &Tensor<(usize, Const<THREE_HIDDEN_DIM>), E, Self>,
+        past_key: &Tensor<(Const<NUM_HEADS>, Const<HEAD_DIM>, usize), E, Self>,
+        past_value: &Tensor<(Const<NUM_HEADS>, usize, Const<HEAD_DIM>), E, Self>,
+    ) -> Result<QkvTuple<NUM_HEADS, HEAD_DIM, E, Self>, Self::Err> {
+        let seq = qkv.shape.0;
+        let past = past_key.shape.2;
+        let total = seq + past;
+        let hidden = THREE_HIDDEN_DIM / 3;
+
+        let mut q = std::vec::Vec::with_capacity(NUM_HEADS * seq * HEAD_DIM);
+        for h in 0..NUM_HEADS {
+            for s in 0..seq {
+                for d in 0..HEAD_DIM {
+                    q.push(qkv[[s, h * HEAD_DIM + d]]);
+                }
+            }
+        }
+
+        let mut k = std::vec::Vec::with_capacity(NUM_HEADS * HEAD_DIM * total);
+        for h in 0..NUM_HEADS {
+            for d in 0..HEAD_DIM {
+                for j in 0..total {
+                    k.push(if j < past {
+                        past_key[[h, d, j]]
+                    } else {
+                        qkv[[j - past, hidden + h * HEAD_DIM + d]]
+                    });
+                }
+            }
+        }
+
+        let mut v = std::vec::Vec::with_capacity(NUM_HEADS * total * HEAD_DIM);
+        for h in 0..NUM_HEADS {
+            for j in 0..total {
+                for d in 0..HEAD_DIM {
+                    v.push(if j < past {
+                        past_value[[h, j, d]]
+                    } else {
+                        qkv[[j - past, 2 * hidden + h * HEAD_DIM + d]]
+                    });
+                }
+            }
+        }
+
+        Ok((
+            self.build_tensor((Const, seq, Const), q),
+            self.build_tensor((Const, Const, total), k),
+            self.build_tensor((Const, total, Const), v),
+        ))
+    }
+}
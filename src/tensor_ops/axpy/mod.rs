@@ -1,11 +1,14 @@
 use crate::{
-    shapes::{Dtype, Shape},
-    tensor::{DeviceStorage, Tensor},
+    shapes::{Dtype, Shape, ShapeError},
+    tensor::{DeviceStorage, NoneTape, Tensor},
 };
 
+use super::reshape_to::ReshapeKernel;
+
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 /// Elementwise `a * alpha + b * beta`.
 ///
@@ -17,14 +20,14 @@ pub fn axpy<S: Shape, E: Dtype, D>(
     beta: impl Into<f64>,
 ) -> Tensor<S, E, D>
 where
-    D: AxpyKernel<E>,
+    D: AxpyKernel<E> + ReshapeKernel<E>,
 {
     let mut dst = a.clone();
     dst.axpy(alpha, b, beta);
     dst
 }
 
-impl<S: Shape, E: Dtype, D: AxpyKernel<E>> Tensor<S, E, D> {
+impl<S: Shape, E: Dtype, D: AxpyKernel<E> + ReshapeKernel<E>> Tensor<S, E, D> {
     /// Updates self with elementwise function `self = self * alpha + b * beta`.
     pub fn axpy<T>(&mut self, alpha: impl Into<f64>, b: &Tensor<S, E, D, T>, beta: impl Into<f64>) {
         self.try_axpy(alpha, b, beta).unwrap()
@@ -37,9 +40,20 @@ impl<S: Shape, E: Dtype, D: AxpyKernel<E>> Tensor<S, E, D> {
         b: &Tensor<S, E, D, T>,
         beta: impl Into<f64>,
     ) -> Result<(), D::Err> {
-        assert_eq!(self.shape, b.shape);
+        if self.shape != b.shape {
+            return Err(ShapeError::new("axpy", &self.shape, &b.shape).into());
+        }
+        if !self.is_whole() || !b.is_whole() {
+            // views don't own their entire buffer, so copy both into new ones
+            let id = self.id;
+            *self = ReshapeKernel::forward(&self.device, &self.shape, self)?;
+            self.id = id;
+            let b = ReshapeKernel::forward(&b.device, &b.shape, &b.retaped::<NoneTape>())?;
+            return self.try_axpy(alpha, &b, beta);
+        }
         assert_eq!(self.strides, b.strides, "Strides must be equal for axpy");
-        self.device.clone().forward(
+        AxpyKernel::forward(
+            &self.device.clone(),
             std::sync::Arc::make_mut(&mut self.data),
             E::from_f64(alpha.into()).unwrap(),
             b.data.as_ref(),
@@ -63,7 +77,7 @@ mod tests {
     use crate::{shapes::Axis, tensor::*, tensor_ops::BroadcastTo, tests::*};
 
     #[test]
-    #[should_panic = "left: `(5,)`,\n right: `(3,)`"]
+    #[should_panic = "op: \"axpy\""]
     fn test_axpy_wrong_shape() {
         let dev: TestDevice = Default::default();
         let mut a: Tensor<_, TestDtype, _> = dev.zeros_like(&(5,));
@@ -0,0 +1,16 @@
+use crate::{shapes::Dtype, tensor::Reference};
+
+impl<E: Dtype> super::AxpyKernel<E> for Reference {
+    fn forward(
+        &self,
+        a: &mut Self::Vec<E>,
+        alpha: E,
+        b: &Self::Vec<E>,
+        beta: E,
+    ) -> Result<(), Self::Err> {
+        for i in 0..a.len() {
+            a[i] = a[i] * alpha + b[i] * beta;
+        }
+        Ok(())
+    }
+}
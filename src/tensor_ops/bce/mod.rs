@@ -50,7 +50,7 @@ impl<S: Shape, E: Dtype, D: BinaryKernel<BCEKernelOp, E>, LTape: Tape<E, D>>
         RTape: Tape<E, D>,
         LTape: Merge<RTape>,
     {
-        try_binary_op(BCEKernelOp, self, prob)
+        try_binary_op("bce_with_logits", BCEKernelOp, self, prob)
     }
 }
 
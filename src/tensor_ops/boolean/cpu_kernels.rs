@@ -28,7 +28,7 @@ impl BooleanKernel for Cpu {
         &self,
         inp: &Tensor<S, bool, Self>,
     ) -> Result<Tensor<S, bool, Self>, Self::Err> {
-        let mut out = inp.clone();
+        let mut out = inp.clone().try_compact()?;
         for x in out.buf_iter_mut() {
             *x = !*x;
         }
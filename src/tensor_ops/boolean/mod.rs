@@ -1,4 +1,5 @@
 mod cpu_kernels;
+mod reference_kernels;
 
 #[cfg(feature = "cuda")]
 mod cuda_kernels;
@@ -0,0 +1,59 @@
+use crate::{
+    shapes::Shape,
+    tensor::{reference::physical_index, HasErr, Reference, Tensor},
+};
+
+use super::BooleanKernel;
+
+impl Reference {
+    fn eval_boolean<S: Shape, O: Fn(bool, bool) -> bool>(
+        &self,
+        op: O,
+        lhs: &Tensor<S, bool, Self>,
+        rhs: &Tensor<S, bool, Self>,
+    ) -> Result<Tensor<S, bool, Self>, <Self as HasErr>::Err> {
+        let shape = lhs.shape;
+        let out = (0..shape.num_elements())
+            .map(|i| {
+                let l = lhs.data[lhs.offset + physical_index(&shape, &lhs.strides, i)];
+                let r = rhs.data[rhs.offset + physical_index(&shape, &rhs.strides, i)];
+                op(l, r)
+            })
+            .collect();
+        Ok(self.build_tensor(shape, out))
+    }
+}
+
+impl BooleanKernel for Reference {
+    fn not<S: Shape>(
+        &self,
+        inp: &Tensor<S, bool, Self>,
+    ) -> Result<Tensor<S, bool, Self>, Self::Err> {
+        let out = inp.as_vec().into_iter().map(|x| !x).collect();
+        Ok(self.build_tensor(inp.shape, out))
+    }
+
+    fn and<S: Shape>(
+        &self,
+        lhs: &Tensor<S, bool, Self>,
+        rhs: &Tensor<S, bool, Self>,
+    ) -> Result<Tensor<S, bool, Self>, Self::Err> {
+        self.eval_boolean(|l, r| l && r, lhs, rhs)
+    }
+
+    fn or<S: Shape>(
+        &self,
+        lhs: &Tensor<S, bool, Self>,
+        rhs: &Tensor<S, bool, Self>,
+    ) -> Result<Tensor<S, bool, Self>, Self::Err> {
+        self.eval_boolean(|l, r| l || r, lhs, rhs)
+    }
+
+    fn xor<S: Shape>(
+        &self,
+        lhs: &Tensor<S, bool, Self>,
+        rhs: &Tensor<S, bool, Self>,
+    ) -> Result<Tensor<S, bool, Self>, Self::Err> {
+        self.eval_boolean(|l, r| l ^ r, lhs, rhs)
+    }
+}
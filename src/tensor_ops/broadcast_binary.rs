@@ -0,0 +1,300 @@
+//! Binary ops that broadcast their arguments to a common shape following numpy's rules.
+//!
+//! The broadcasted tensors are views into the original data, so the gradients
+//! of the broadcasted dimensions are summed back into the original tensor during
+//! backward, just like with [super::BroadcastTo].
+
+use crate::{
+    shapes::{broadcast_strides_aligned, BroadcastShapes, Dtype, Shape, ShapeError},
+    tensor::{DeviceStorage, HasErr, Tensor},
+};
+
+use super::{TryAdd, TryDiv, TryMul, TrySub};
+
+/// The result of broadcasting `S` with `R`.
+type Broadcasted<S, R, E, D, T> = Tensor<<S as BroadcastShapes<R>>::Output, E, D, T>;
+
+/// Both arguments of a binary op, broadcasted to their common shape.
+type BroadcastedPair<S, R, E, D, T, RT> = (Broadcasted<S, R, E, D, T>, Broadcasted<S, R, E, D, RT>);
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, T> Tensor<S, E, D, T> {
+    /// Views `self` as `dst`, where the shapes are aligned by their last dimension.
+    fn broadcast_aligned<Dst: Shape>(self, dst: Dst) -> Tensor<Dst, E, D, T> {
+        Tensor {
+            id: self.id,
+            strides: broadcast_strides_aligned(&self.shape, self.strides, &dst),
+            data: self.data,
+            shape: dst,
+            offset: self.offset,
+            device: self.device,
+            tape: self.tape,
+        }
+    }
+
+    /// Broadcasts `self` and `rhs` to their common shape, or returns a [ShapeError]
+    /// for `op` if the shapes can't be broadcasted together.
+    fn try_broadcast_pair<R: Shape, RT>(
+        self,
+        op: &'static str,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> Result<BroadcastedPair<S, R, E, D, T, RT>, ShapeError>
+    where
+        S: BroadcastShapes<R>,
+    {
+        match self.shape.broadcast_shapes(&rhs.shape) {
+            Some(dst) => Ok((self.broadcast_aligned(dst), rhs.broadcast_aligned(dst))),
+            None => Err(ShapeError::new(op, &self.shape, &rhs.shape)),
+        }
+    }
+
+    /// Broadcasts `self` and `rhs` to their common shape, following numpy's broadcasting rules.
+    /// See [BroadcastShapes] for how the resulting shape is computed.
+    ///
+    /// **Panics** if the shapes can't be broadcasted together. The `try_broadcast_*`
+    /// ops return a [ShapeError] instead.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+    /// let b: Tensor<Rank1<3>, f32, _> = dev.ones();
+    /// let (a, b) = a.broadcast_with(b);
+    /// let c = a.maximum(b);
+    /// assert_eq!(c.array(), [[1.0; 3]; 2]);
+    /// ```
+    pub fn broadcast_with<R: Shape, RT>(
+        self,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> BroadcastedPair<S, R, E, D, T, RT>
+    where
+        S: BroadcastShapes<R>,
+    {
+        self.try_broadcast_pair("broadcast_with", rhs)
+            .unwrap_or_else(|err| {
+                panic!(
+                    "Shapes {:?} and {:?} can't be broadcasted together",
+                    err.lhs().dims(),
+                    err.rhs().dims()
+                )
+            })
+    }
+
+    /// Adds `self` and `rhs` after broadcasting them to their common shape.
+    /// See [Tensor::broadcast_with].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a = dev.tensor([[1.0], [2.0]]);
+    /// let b = dev.tensor([10.0, 20.0, 30.0]);
+    /// let c: Tensor<Rank2<2, 3>, f32, _> = a.broadcast_add(b);
+    /// assert_eq!(c.as_vec(), [11.0, 21.0, 31.0, 12.0, 22.0, 32.0]);
+    /// ```
+    pub fn broadcast_add<R: Shape, RT>(self, rhs: Tensor<R, E, D, RT>) -> Broadcasted<S, R, E, D, T>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TryAdd<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        self.try_broadcast_add(rhs).unwrap()
+    }
+
+    /// See [Tensor::broadcast_add]
+    pub fn try_broadcast_add<R: Shape, RT>(
+        self,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> Result<Broadcasted<S, R, E, D, T>, D::Err>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TryAdd<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        let (lhs, rhs) = self.try_broadcast_pair("broadcast_add", rhs)?;
+        lhs.try_add(rhs)
+    }
+
+    /// Subtracts `rhs` from `self` after broadcasting them to their common shape.
+    /// See [Tensor::broadcast_with].
+    pub fn broadcast_sub<R: Shape, RT>(self, rhs: Tensor<R, E, D, RT>) -> Broadcasted<S, R, E, D, T>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TrySub<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        self.try_broadcast_sub(rhs).unwrap()
+    }
+
+    /// See [Tensor::broadcast_sub]
+    pub fn try_broadcast_sub<R: Shape, RT>(
+        self,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> Result<Broadcasted<S, R, E, D, T>, D::Err>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TrySub<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        let (lhs, rhs) = self.try_broadcast_pair("broadcast_sub", rhs)?;
+        lhs.try_sub(rhs)
+    }
+
+    /// Multiplies `self` and `rhs` after broadcasting them to their common shape.
+    /// See [Tensor::broadcast_with].
+    pub fn broadcast_mul<R: Shape, RT>(self, rhs: Tensor<R, E, D, RT>) -> Broadcasted<S, R, E, D, T>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TryMul<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        self.try_broadcast_mul(rhs).unwrap()
+    }
+
+    /// See [Tensor::broadcast_mul]
+    pub fn try_broadcast_mul<R: Shape, RT>(
+        self,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> Result<Broadcasted<S, R, E, D, T>, D::Err>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TryMul<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        let (lhs, rhs) = self.try_broadcast_pair("broadcast_mul", rhs)?;
+        lhs.try_mul(rhs)
+    }
+
+    /// Divides `self` by `rhs` after broadcasting them to their common shape.
+    /// See [Tensor::broadcast_with].
+    pub fn broadcast_div<R: Shape, RT>(self, rhs: Tensor<R, E, D, RT>) -> Broadcasted<S, R, E, D, T>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TryDiv<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        self.try_broadcast_div(rhs).unwrap()
+    }
+
+    /// See [Tensor::broadcast_div]
+    pub fn try_broadcast_div<R: Shape, RT>(
+        self,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> Result<Broadcasted<S, R, E, D, T>, D::Err>
+    where
+        S: BroadcastShapes<R>,
+        Broadcasted<S, R, E, D, T>: TryDiv<Broadcasted<S, R, E, D, RT>> + HasErr<Err = D::Err>,
+    {
+        let (lhs, rhs) = self.try_broadcast_pair("broadcast_div", rhs)?;
+        lhs.try_div(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_broadcast_add_const() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+
+        let r1 = a.leaky_trace().broadcast_add(b.clone());
+        let r2 = a.leaky_trace() + b.leaky_trace().broadcast::<Rank2<2, 3>, _>();
+        assert_close_to_tensor!(r1, r2);
+
+        let g1 = r1.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_close_to_tensor!(g1.get(&a), g2.get(&a));
+        assert_close_to_tensor!(g1.get(&b), g2.get(&b));
+
+        // rhs with more dimensions than lhs
+        let r3: Tensor<Rank2<2, 3>, _, _> = b.clone().broadcast_sub(a.clone());
+        assert_close_to_tensor!(r3, -(a - b.broadcast()));
+    }
+
+    #[test]
+    fn test_broadcast_mul_size_one_dims() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([[1.0], [2.0]]).to_dtype::<TestDtype>();
+        let b = dev.tensor([[1.0, 2.0, 3.0]]).to_dtype::<TestDtype>();
+        let a = a.realize::<(usize, usize)>();
+        let b = b.realize::<(usize, usize)>();
+
+        let r = a.leaky_trace().broadcast_mul(b.clone());
+        assert_eq!(r.shape, (2, 3));
+        assert_close_to_literal!(
+            r.retaped::<NoneTape>().realize::<Rank2<2, 3>>(),
+            [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0]]
+        );
+
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&a).realize::<Rank2<2, 1>>(), [[6.0], [6.0]]);
+        assert_close_to_literal!(g.get(&b).realize::<Rank2<1, 3>>(), [[3.0, 3.0, 3.0]]);
+    }
+
+    #[test]
+    fn test_broadcast_mul_const_size_one_dims() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 1>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<1, 3>, TestDtype, _> = dev.sample_normal();
+
+        let r1: Tensor<Rank2<2, 3>, _, _, _> = a.leaky_trace().broadcast_mul(b.clone());
+        let r2 = a
+            .leaky_trace()
+            .realize::<(usize, usize)>()
+            .broadcast_mul(b.clone());
+        assert_close_to_tensor!(
+            r1.retaped::<NoneTape>(),
+            r2.retaped::<NoneTape>().realize::<Rank2<2, 3>>()
+        );
+
+        let g1 = r1.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_close_to_tensor!(g1.get(&a), g2.get(&a));
+        assert_close_to_tensor!(g1.get(&b), g2.get(&b));
+    }
+
+    #[test]
+    fn test_broadcast_div_dyn() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank1<4>, TestDtype, _> = dev.sample_uniform();
+        let b = b + TestDtype::ONE;
+        let a_dyn = a.clone().reshape_like(&DynShape::new(&[2, 1, 3]));
+        let b_dyn = b.clone().reshape_like(&DynShape::new(&[4, 1]));
+        let r = a_dyn.leaky_trace().broadcast_div(b_dyn.clone());
+        assert_eq!(r.shape().dims(), &[2, 4, 3]);
+
+        let expected = a.leaky_trace().broadcast::<Rank3<2, 4, 3>, Axis<1>>()
+            / b.leaky_trace().broadcast::<Rank3<2, 4, 3>, Axes2<0, 2>>();
+        assert_close_to_tensor!(
+            r.retaped::<NoneTape>().realize::<Rank3<2, 4, 3>>(),
+            expected
+        );
+
+        let g1 = r.square().sum().backward();
+        let g2 = expected.square().sum().backward();
+        assert_close_to_tensor!(
+            g1.get(&a_dyn).reshape_like(&Rank2::<2, 3>::default()),
+            g2.get(&a)
+        );
+        assert_close_to_tensor!(
+            g1.get(&b_dyn).reshape_like(&Rank1::<4>::default()),
+            g2.get(&b)
+        );
+    }
+
+    #[test]
+    #[should_panic = "can't be broadcasted together"]
+    fn test_broadcast_incompatible() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<(usize,), TestDtype, _> = dev.zeros_like(&(3,));
+        let b: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(2, 4));
+        let _ = a.broadcast_with(b);
+    }
+
+    #[test]
+    fn test_try_broadcast_incompatible() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<(usize,), TestDtype, _> = dev.zeros_like(&(3,));
+        let b: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(2, 4));
+        let err = a.try_broadcast_mul(b).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("broadcast_mul", &(3,), &(2, 4)))
+        );
+    }
+}
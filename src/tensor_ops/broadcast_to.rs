@@ -63,13 +63,14 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T: Tape<E, D>> BroadcastTo for Tensor<
     where
         Self::Shape: BroadcastShapeTo<Dst::Shape, Ax>,
     {
-        self.shape().check(dst.shape());
+        self.shape().check(dst.shape())?;
 
         Ok(Tensor {
             id: self.id,
             data: self.data,
             shape: *dst.shape(),
             strides: self.shape.broadcast_strides(self.strides),
+            offset: self.offset,
             device: self.device,
             tape: self.tape,
         })
@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+struct CeilKernelOp {};
+
+UNARY_OP(__half, ceil_fwd_f16, ceil_bwd_f16, CeilKernelOp,
+        ceilg(x),
+        0.0)
+
+UNARY_OP(float, ceil_fwd_f32, ceil_bwd_f32, CeilKernelOp,
+        ceilg(x),
+        0.0)
+
+UNARY_OP(double, ceil_fwd_f64, ceil_bwd_f64, CeilKernelOp,
+        ceilg(x),
+        0.0)
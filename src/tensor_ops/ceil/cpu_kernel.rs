@@ -0,0 +1,19 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use num_traits::Float;
+
+impl<F: Float> UnaryDerivative<F> for super::CeilKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = true;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        x.ceil()
+    }
+    #[inline(always)]
+    fn df(&self, _: &F) -> F {
+        F::zero()
+    }
+    #[inline(always)]
+    fn const_df(&self) -> F {
+        F::zero()
+    }
+}
@@ -0,0 +1,11 @@
+use super::CeilKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for CeilKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/ceil.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(const_df() CeilKernelOp, half::f16, PTX, "ceil_fwd_f16", "ceil_bwd_f16");
+cuda_unary!(const_df() CeilKernelOp, f32, PTX, "ceil_fwd_f32", "ceil_bwd_f32");
+cuda_unary!(const_df() CeilKernelOp, f64, PTX, "ceil_fwd_f64", "ceil_bwd_f64");
@@ -0,0 +1,57 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CeilKernelOp;
+
+/// Rounds up to the nearest integer.
+///
+/// The derivative is zero everywhere, so no gradient flows back through this.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.5, 0.5, 1.5, 2.0]);
+/// let r = t.ceil();
+/// assert_eq!(r.array(), [-1.0, 1.0, 2.0, 2.0]);
+/// ```
+pub fn ceil<S: Shape, E: Dtype, D: UnaryKernel<CeilKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.ceil()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<CeilKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [ceil]
+    pub fn ceil(self) -> Self {
+        self.try_ceil().unwrap()
+    }
+    /// See [ceil]
+    pub fn try_ceil(self) -> Result<Self, D::Err> {
+        try_unary_op(CeilKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_ceil() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-1.5, -0.4, 0.0, 0.6, 1.5])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().ceil();
+        assert_close_to_literal!(r, [-1.0, -0.0, 0.0, 1.0, 2.0]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(g.get(&x), [0.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+}
@@ -1,10 +1,11 @@
 mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
 use crate::{
-    shapes::{Dtype, HasShape, Shape},
+    shapes::{Dtype, HasShape, Shape, ShapeError},
     tensor::{DeviceStorage, HasErr, Merge, PutTape, SplitTape, Tape, Tensor},
 };
 
@@ -66,8 +67,12 @@ impl<
         lhs: Tensor<S, E, D, LhsTape>,
         rhs: Tensor<S, E, D, RhsTape>,
     ) -> Result<Self::Output, Self::Err> {
-        assert_eq!(self.shape(), lhs.shape());
-        assert_eq!(lhs.shape(), rhs.shape());
+        if self.shape() != lhs.shape() {
+            return Err(ShapeError::new("choose", self.shape(), lhs.shape()).into());
+        }
+        if lhs.shape() != rhs.shape() {
+            return Err(ShapeError::new("choose", lhs.shape(), rhs.shape()).into());
+        }
 
         let (lhs, tape) = lhs.split_tape();
         let (rhs, rhs_tape) = rhs.split_tape();
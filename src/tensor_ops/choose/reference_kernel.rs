@@ -0,0 +1,44 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{reference::physical_index, Reference, Tensor},
+};
+
+impl<E: Dtype> super::ChooseKernel<E> for Reference {
+    fn forward<S: Shape>(
+        &self,
+        cond: &Tensor<S, bool, Self>,
+        lhs: &Tensor<S, E, Self>,
+        rhs: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let shape = lhs.shape;
+        let mut out = self.try_alloc_elem(shape.num_elements(), E::default())?;
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = if cond.data[cond.offset + physical_index(&shape, &cond.strides, i)] {
+                lhs.data[lhs.offset + physical_index(&shape, &lhs.strides, i)]
+            } else {
+                rhs.data[rhs.offset + physical_index(&shape, &rhs.strides, i)]
+            };
+        }
+        Ok(self.build_tensor(shape, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        cond: &Tensor<S, bool, Self>,
+        lhs: &Tensor<S, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<S, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let shape = lhs.shape;
+        for (i, &go) in grad_out.iter().enumerate() {
+            if cond.data[cond.offset + physical_index(&shape, &cond.strides, i)] {
+                grad_lhs[physical_index(&shape, &lhs.strides, i)] += go;
+            } else {
+                grad_rhs[physical_index(&shape, &rhs.strides, i)] += go;
+            }
+        }
+        Ok(())
+    }
+}
@@ -11,7 +11,7 @@ use super::{
     ScalarCmpKernel,
 };
 
-trait CmpOpCpuKernel<E: Unit> {
+pub(super) trait CmpOpCpuKernel<E: Unit> {
     fn func(lhs: E, rhs: E) -> bool;
 }
 
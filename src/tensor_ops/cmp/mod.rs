@@ -1,11 +1,12 @@
 use crate::{
-    shapes::{HasShape, Shape, Unit},
+    shapes::{HasShape, Shape, ShapeError, Unit},
     tensor::{DeviceStorage, HasErr, NoneTape, Tape, Tensor},
 };
 
 mod cpu_kernels;
 #[cfg(feature = "cuda")]
 mod cuda_kernels;
+mod reference_kernels;
 
 pub trait CmpKernel<Op, E: Unit>: DeviceStorage {
     fn forward<S: Shape, T>(
@@ -16,10 +17,13 @@ pub trait CmpKernel<Op, E: Unit>: DeviceStorage {
 }
 
 fn try_cmp_op<Op, S: Shape, E: Unit, D: CmpKernel<Op, E>, T: Tape<E, D>>(
+    name: &'static str,
     lhs: &Tensor<S, E, D, T>,
     rhs: &Tensor<S, E, D, T>,
 ) -> Result<Tensor<S, bool, D, NoneTape>, D::Err> {
-    assert_eq!(lhs.shape(), rhs.shape());
+    if lhs.shape() != rhs.shape() {
+        return Err(ShapeError::new(name, lhs.shape(), rhs.shape()).into());
+    }
     lhs.device.forward(lhs, rhs)
 }
 
@@ -214,7 +218,7 @@ macro_rules! impl_cmp_kernel_op {
             type Output = Tensor<S, bool, D, NoneTape>;
             #[doc = $doc]
             fn $TryFnName(&self, other: &Self) -> Result<Self::Output, D::Err> {
-                try_cmp_op(self, other)
+                try_cmp_op(stringify!($FnName), self, other)
             }
         }
 
@@ -514,4 +518,16 @@ mod tests {
         let b: Tensor<(usize, usize, usize), TestDtype, TestDevice> = dev.ones_like(&(2, 3, 4));
         a.eq(&b);
     }
+
+    #[test]
+    fn test_try_cmp_shape_mismatch() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<(usize, usize), TestDtype, TestDevice> = dev.zeros_like(&(1, 2));
+        let b: Tensor<(usize, usize), TestDtype, TestDevice> = dev.ones_like(&(2, 3));
+        let err = a.try_lt(&b).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("lt", &(1, 2), &(2, 3)))
+        );
+    }
 }
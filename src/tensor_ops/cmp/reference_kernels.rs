@@ -0,0 +1,39 @@
+use crate::{
+    shapes::{Shape, Unit},
+    tensor::{reference::physical_index, Reference, Tensor},
+};
+
+use super::{cpu_kernels::CmpOpCpuKernel, CmpKernel, ScalarCmpKernel};
+
+impl<Op: CmpOpCpuKernel<E>, E: Unit> CmpKernel<Op, E> for Reference {
+    fn forward<S: Shape, T>(
+        &self,
+        lhs: &Tensor<S, E, Self, T>,
+        rhs: &Tensor<S, E, Self, T>,
+    ) -> Result<Tensor<S, bool, Self>, Self::Err> {
+        let shape = lhs.shape;
+        let out = (0..shape.num_elements())
+            .map(|i| {
+                let l = lhs.data[lhs.offset + physical_index(&shape, &lhs.strides, i)];
+                let r = rhs.data[rhs.offset + physical_index(&shape, &rhs.strides, i)];
+                Op::func(l, r)
+            })
+            .collect();
+        Ok(self.build_tensor(shape, out))
+    }
+}
+
+impl<Op: CmpOpCpuKernel<E>, E: Unit> ScalarCmpKernel<Op, E> for Reference {
+    fn forward<S: Shape, T>(
+        &self,
+        lhs: &Tensor<S, E, Self, T>,
+        scalar: E,
+    ) -> Result<Tensor<S, bool, Self>, Self::Err> {
+        let out = lhs
+            .as_vec()
+            .into_iter()
+            .map(|l| Op::func(l, scalar))
+            .collect();
+        Ok(self.build_tensor(lhs.shape, out))
+    }
+}
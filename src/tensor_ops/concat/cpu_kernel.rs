@@ -16,7 +16,7 @@ impl<E: Dtype> super::ConcatKernel<E> for Cpu {
         let mut data = self.try_alloc_elem(shape.num_elements(), E::default())?;
         let mut i = 0;
         if a.strides == a.shape.strides() {
-            let a: &[E] = a.data.as_ref();
+            let a: &[E] = &a.data[a.offset..a.offset + a.span()];
             data.data[0..a.len()].copy_from_slice(a);
             i += a.len();
         } else {
@@ -25,7 +25,7 @@ impl<E: Dtype> super::ConcatKernel<E> for Cpu {
             i += a_buf.len();
         }
         if b.strides == b.shape.strides() {
-            let b: &[E] = b.data.as_ref();
+            let b: &[E] = &b.data[b.offset..b.offset + b.span()];
             data.data[i..i + b.len()].copy_from_slice(b);
         } else {
             let b_buf = b.as_vec();
@@ -36,6 +36,7 @@ impl<E: Dtype> super::ConcatKernel<E> for Cpu {
             data: std::sync::Arc::new(data),
             shape,
             strides: shape.strides(),
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         })
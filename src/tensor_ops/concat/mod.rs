@@ -3,6 +3,7 @@ use crate::{shapes::*, tensor::*};
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 /// Concatenate two tensors along the first dimension.
 ///
@@ -65,6 +66,9 @@ where
             rhs.shape.strides(),
             "Concat requires contiguous tensors"
         );
+        if self.shape.concrete().as_ref()[1..] != rhs.shape.concrete().as_ref()[1..] {
+            return Err(ShapeError::new("concat", &self.shape, &rhs.shape).into());
+        }
         let (lhs, a_tape) = self.split_tape();
         let (rhs, b_tape) = rhs.split_tape();
         let mut tape = a_tape.merge(b_tape);
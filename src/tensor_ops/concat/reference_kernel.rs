@@ -0,0 +1,35 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{Reference, Tensor},
+};
+
+impl<E: Dtype> super::ConcatKernel<E> for Reference {
+    fn forward<A: Shape, B: Shape>(
+        &self,
+        a: &Tensor<A, E, Self>,
+        b: &Tensor<B, E, Self>,
+    ) -> Result<Tensor<A::Catted, E, Self>, Self::Err>
+    where
+        A: super::ConcatShape<B>,
+    {
+        let shape = a.shape.concat_shape(&b.shape);
+        let mut data = a.as_vec();
+        data.extend(b.as_vec());
+        Ok(self.build_tensor(shape, data))
+    }
+    fn backward(
+        &self,
+        grad_a: &mut Self::Vec<E>,
+        grad_b: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let n = grad_a.len();
+        for i in 0..n {
+            grad_a[i] += grad_out[i];
+        }
+        for i in 0..grad_b.len() {
+            grad_b[i] += grad_out[n + i];
+        }
+        Ok(())
+    }
+}
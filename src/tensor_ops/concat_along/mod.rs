@@ -3,6 +3,7 @@ use crate::{shapes::*, tensor::*};
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 /// Concatenate two tensors along a given axis.
 ///
@@ -84,7 +85,7 @@ where
     D: ConcatAlongKernel<E> + ZerosTensor<E>,
     A: Shape + HasAxes<Ax>,
     B: Shape<Concrete = A::Concrete> + HasAxes<Ax>,
-    (A, B): TryConcatAlong<Ax>,
+    (A, B): TryConcatAlong<Ax, Error = ShapeError>,
     <(A, B) as TryConcatAlong<Ax>>::Output: Shape,
     T: Merge<R>,
 {
@@ -93,7 +94,7 @@ where
     fn try_concat_along(self, ax: Ax) -> Result<Self::Output, Self::Error> {
         let (lhs, rhs) = self;
 
-        let out_shape = (*lhs.shape(), *rhs.shape()).concat_along(ax);
+        let out_shape = (*lhs.shape(), *rhs.shape()).try_concat_along(ax)?;
         let ax = Ax::as_array()[0] as usize;
 
         let (lhs, tape) = lhs.split_tape();
@@ -135,14 +136,14 @@ macro_rules! impl_concat {
                     <A as std::ops::Add<B>>::Output,
                     $($Tail, )*
                 );
-                type Error = std::convert::Infallible;
+                type Error = ShapeError;
                 fn try_concat_along(self, _: Axis<$Ax>) -> Result<Self::Output, Self::Error> {
                     let (lhs, rhs) = self;
                     let lhs_dims = lhs.concrete();
                     let rhs_dims = rhs.concrete();
                     for i in 0..$NumDims {
-                        if i != $Ax {
-                            assert_eq!(lhs_dims[i], rhs_dims[i]);
+                        if i != $Ax && lhs_dims[i] != rhs_dims[i] {
+                            return Err(ShapeError::new("concat_along", &lhs, &rhs));
                         }
                     }
                     let mut out_dims = lhs_dims;
@@ -307,10 +308,22 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `10`,\n right: `7`"]
+    #[should_panic = "ShapeError"]
     fn test_concat_shape_fails() {
         let a = (5, 10);
         let b = (3, 7);
         (a, b).concat_along(Axis::<0>);
     }
+
+    #[test]
+    fn test_concat_shape_error() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(2, 3));
+        let b: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(4, 5));
+        let err = (a, b).try_concat_along(Axis::<0>).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("concat_along", &(2, 3), &(4, 5)))
+        );
+    }
 }
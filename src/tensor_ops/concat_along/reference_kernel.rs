@@ -0,0 +1,83 @@
+use crate::{
+    shapes::*,
+    tensor::{
+        reference::{physical_index, ravel_index, unravel_dims},
+        *,
+    },
+};
+
+/// Returns whether element `i_c` of the concatenated tensor comes from `a`, and its
+/// physical index into either `a` or `b`.
+fn source_index<A: Shape, B: Shape>(
+    ax: usize,
+    a: (&A, &A::Concrete),
+    b: (&B, &B::Concrete),
+    i_c: &A::Concrete,
+) -> (bool, usize) {
+    let a_len = a.0.concrete()[ax];
+    if i_c[ax] < a_len {
+        (true, ravel_index::<A>(a.1, i_c))
+    } else {
+        let mut i_b: B::Concrete = Default::default();
+        for d in 0..B::NUM_DIMS {
+            i_b[d] = i_c[d];
+        }
+        i_b[ax] -= a_len;
+        (false, ravel_index::<B>(b.1, &i_b))
+    }
+}
+
+/// The dimensions of `a` and `b` concatenated along `ax`.
+fn catted_dims<A: Shape, B: Shape>(ax: usize, a: &A, b: &B) -> A::Concrete {
+    let mut dims = a.concrete();
+    dims[ax] += b.concrete()[ax];
+    dims
+}
+
+impl<E: Dtype> super::ConcatAlongKernel<E> for Reference {
+    fn forward<A: Shape, B: Shape, C: Shape>(
+        &self,
+        ax: usize,
+        a: &Tensor<A, E, Self>,
+        b: &Tensor<B, E, Self>,
+        c: &mut Tensor<C, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let dims = catted_dims(ax, &a.shape, &b.shape);
+        let (shape, strides) = (c.shape, c.strides);
+        let buf = std::sync::Arc::make_mut(&mut c.data);
+        for i in 0..shape.num_elements() {
+            let i_c = unravel_dims::<A>(&dims, i);
+            let (from_a, j) =
+                source_index(ax, (&a.shape, &a.strides), (&b.shape, &b.strides), &i_c);
+            buf[physical_index(&shape, &strides, i)] = if from_a {
+                a.data[a.offset + j]
+            } else {
+                b.data[b.offset + j]
+            };
+        }
+        Ok(())
+    }
+    fn backward<A: Shape, B: Shape>(
+        &self,
+        ax: usize,
+        a: &GhostTensor<A, E, Self>,
+        grad_a: &mut Self::Vec<E>,
+        b: &GhostTensor<B, E, Self>,
+        grad_b: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        // NOTE: the output of forward is contiguous
+        let dims = catted_dims(ax, &a.shape, &b.shape);
+        for (i, &go) in grad_out.iter().enumerate() {
+            let i_c = unravel_dims::<A>(&dims, i);
+            let (from_a, j) =
+                source_index(ax, (&a.shape, &a.strides), (&b.shape, &b.strides), &i_c);
+            if from_a {
+                grad_a[j] += go;
+            } else {
+                grad_b[j] += go;
+            }
+        }
+        Ok(())
+    }
+}
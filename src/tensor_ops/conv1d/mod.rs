@@ -0,0 +1,284 @@
+use crate::{shapes::*, tensor::*, tensor_ops::ReshapeTo};
+
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+#[cfg(test)]
+mod tests;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Conv1DOp {
+    pub kernel: usize,
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+    pub groups: usize,
+    pub batch: usize,
+    pub chan_in: usize,
+    pub chan_out: usize,
+    pub l_in: usize,
+    pub l_out: usize,
+}
+
+pub(super) trait Conv1DKernel<E: Dtype>: DeviceStorage {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err>;
+
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv1DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv1DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// 1d convolution of sequences with shape `(Channels, Length)` or `(Batch, Channels, Length)`,
+/// and filters with shape `(OutChan, InpChan, Kernel)`.
+pub trait TryConv1D<Stride, Padding, Dilation, Groups>: Sized {
+    type Convolved;
+    type Error: std::fmt::Debug;
+
+    fn conv1d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Self::Convolved {
+        self.try_conv1d(stride, padding, dilation, groups).unwrap()
+    }
+
+    fn try_conv1d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error>;
+}
+
+#[cfg(feature = "nightly")]
+impl<
+        const KERNEL: usize,
+        const STRIDE: usize,
+        const PADDING: usize,
+        const DILATION: usize,
+        Groups: Dim,
+        const DIM: usize,
+    > TryConv1D<Const<STRIDE>, Const<PADDING>, Const<DILATION>, Groups>
+    for (Const<DIM>, Const<KERNEL>)
+where
+    Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>: Sized,
+{
+    type Convolved = Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>;
+    type Error = std::convert::Infallible;
+    fn try_conv1d(
+        self,
+        _: Const<STRIDE>,
+        _: Const<PADDING>,
+        _: Const<DILATION>,
+        _: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        Ok(Const)
+    }
+}
+
+impl<Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim, Groups: Dim>
+    TryConv1D<Stride, Padding, Dilation, Groups> for (usize, Kernel)
+{
+    type Convolved = usize;
+    type Error = std::convert::Infallible;
+    fn try_conv1d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        _: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        let (dim, kernel) = self;
+        Ok((dim + 2 * padding.size() - 1)
+            .checked_sub(dilation.size() * (kernel.size() - 1))
+            .unwrap()
+            / stride.size()
+            + 1)
+    }
+}
+
+// Without `generic_const_exprs` the convolved size of a [Const] can't be named, so
+// it is computed at runtime instead.
+#[cfg(not(feature = "nightly"))]
+impl<const DIM: usize, Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim, Groups: Dim>
+    TryConv1D<Stride, Padding, Dilation, Groups> for (Const<DIM>, Kernel)
+{
+    type Convolved = usize;
+    type Error = std::convert::Infallible;
+    fn try_conv1d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        (self.0.size(), self.1).try_conv1d(stride, padding, dilation, groups)
+    }
+}
+
+impl<ImgChan, InpChan, OutChan, Kernel, Stride, Padding, Dilation, Groups, L, E, D, T>
+    TryConv1D<Stride, Padding, Dilation, Groups>
+    for (
+        Tensor<(ImgChan, L), E, D, T>,
+        Tensor<(OutChan, InpChan, Kernel), E, D>,
+    )
+where
+    ImgChan: Dim,
+    InpChan: Dim,
+    OutChan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Groups: Dim,
+    L: Dim,
+    E: Dtype,
+    D: Conv1DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+    (L, Kernel): TryConv1D<Stride, Padding, Dilation, Groups>,
+    <(L, Kernel) as TryConv1D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+{
+    type Convolved = Tensor<
+        (
+            OutChan,
+            <(L, Kernel) as TryConv1D<Stride, Padding, Dilation, Groups>>::Convolved,
+        ),
+        E,
+        D,
+        T,
+    >;
+    type Error = D::Err;
+
+    fn try_conv1d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        let (img, filters) = self;
+        let (inp_chan, l) = img.shape;
+        let img = img.try_reshape_like(&(Const::<1>, inp_chan, l))?;
+        let out = (img, filters).try_conv1d(stride, padding, dilation, groups)?;
+        let (_, out_chan, out_l) = out.shape;
+        out.try_reshape_like(&(out_chan, out_l))
+    }
+}
+
+impl<ImgChan, InpChan, OutChan, Kernel, Stride, Padding, Dilation, Groups, Batch, L, E, D, T>
+    TryConv1D<Stride, Padding, Dilation, Groups>
+    for (
+        Tensor<(Batch, ImgChan, L), E, D, T>,
+        Tensor<(OutChan, InpChan, Kernel), E, D>,
+    )
+where
+    ImgChan: Dim,
+    InpChan: Dim,
+    OutChan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Groups: Dim,
+    Batch: Dim,
+    L: Dim,
+    E: Dtype,
+    D: Conv1DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+    (L, Kernel): TryConv1D<Stride, Padding, Dilation, Groups>,
+    <(L, Kernel) as TryConv1D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+{
+    type Convolved = Tensor<
+        (
+            Batch,
+            OutChan,
+            <(L, Kernel) as TryConv1D<Stride, Padding, Dilation, Groups>>::Convolved,
+        ),
+        E,
+        D,
+        T,
+    >;
+    type Error = D::Err;
+
+    fn try_conv1d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        let (img, filters) = self;
+        let (batch, img_chan, l) = img.shape;
+        let (out_chan, inp_chan, kernel) = filters.shape;
+        if img_chan.size() != inp_chan.size() * groups.size()
+            || out_chan.size() % groups.size() != 0
+        {
+            return Err(ShapeError::new("conv1d", &img.shape, &filters.shape).into());
+        }
+        // the kernels only handle contiguous inputs, so views are copied first
+        let img = img.try_contiguous()?;
+        let filters = filters.try_contiguous()?;
+        let l_out = (l, kernel).conv1d(stride, padding, dilation, groups);
+        let op = Conv1DOp {
+            stride: stride.size(),
+            padding: padding.size(),
+            kernel: kernel.size(),
+            dilation: dilation.size(),
+            groups: groups.size(),
+            batch: batch.size(),
+            chan_in: inp_chan.size(),
+            chan_out: out_chan.size(),
+            l_in: l.size(),
+            l_out: l_out.size(),
+        };
+        let (lhs, ltape) = img.split_tape();
+        let (rhs, rtape) = filters.split_tape();
+        let mut out = lhs.device.alloc((batch, out_chan, l_out))?;
+        let mut tape = ltape.merge(rtape);
+        Conv1DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
+        let lhs_ghost = lhs.ghost();
+        let rhs_ghost = rhs.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&rhs_ghost)?;
+            grads.try_alloc_for(&lhs_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_lhs, grad_rhs, grad_out) =
+                grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
+            Conv1DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
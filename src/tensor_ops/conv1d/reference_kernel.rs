@@ -0,0 +1,89 @@
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::*;
+
+use super::{Conv1DKernel, Conv1DOp};
+
+use std::sync::Arc;
+
+fn make_3d<S: Shape>(strides: S::Concrete) -> [usize; 3] {
+    match S::NUM_DIMS {
+        2 => [0, strides[0], strides[1]],
+        3 => [strides[0], strides[1], strides[2]],
+        _ => panic!("Only implemented for 2d & 3d arrays"),
+    }
+}
+
+impl Conv1DOp {
+    /// Calls `f([o, c, k], ol, [c_img, x])` for every multiply-add of the
+    /// convolution, where `(c_img, x)` indexes the image & `(o, c, k)` the filters.
+    fn for_each_tap(&self, mut f: impl FnMut([usize; 3], usize, [usize; 2])) {
+        let o_per_group = self.chan_out / self.groups;
+        for o in 0..self.chan_out {
+            let g = o / o_per_group;
+            for c in 0..self.chan_in {
+                for k in 0..self.kernel {
+                    for ol in 0..self.l_out {
+                        let x = (ol * self.stride + self.dilation * k).checked_sub(self.padding);
+                        if let Some(x) = x {
+                            if x < self.l_in {
+                                f([o, c, k], ol, [g * self.chan_in + c, x]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Dtype> Conv1DKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv1DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_3d::<L>(lhs.strides);
+        let ostr = make_3d::<O>(out.strides);
+        let rstr = rhs.strides;
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k], ol, [ci, x]| {
+                let w = rhs.data[rhs.offset + o * rstr[0] + c * rstr[1] + k * rstr[2]];
+                let v = lhs.data[lhs.offset + b * lstr[0] + ci * lstr[1] + x * lstr[2]];
+                out_buf[b * ostr[0] + o * ostr[1] + ol * ostr[2]] += w * v;
+            });
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv1DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_3d::<L>(lhs.strides);
+        let ostr = make_3d::<O>(out.strides());
+        let rstr = rhs.strides;
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k], ol, [ci, x]| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k * rstr[2];
+                let i_lhs = b * lstr[0] + ci * lstr[1] + x * lstr[2];
+                let go = grad_out[b * ostr[0] + o * ostr[1] + ol * ostr[2]];
+                grad_lhs[i_lhs] += rhs.data[rhs.offset + i_rhs] * go;
+                grad_rhs[i_rhs] += lhs.data[lhs.offset + i_lhs] * go;
+            });
+        }
+        Ok(())
+    }
+}
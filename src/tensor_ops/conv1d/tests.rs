@@ -0,0 +1,191 @@
+use super::*;
+use crate::{tensor_ops::*, tests::*};
+
+#[test]
+/// Matches
+/// ```python
+/// y = torch.conv1d(x, w)
+/// y.exp().mean().backward()
+/// ```
+fn test_conv1d_default_stride_and_padding() {
+    let dev: TestDevice = Default::default();
+    #[rustfmt::skip]
+    let x = dev.tensor([[-0.7313, 0.6949, 0.5275, -0.4899, -0.0091], [-0.101, 0.3032, 0.5774, -0.8123, -0.9433]]).to_dtype::<TestDtype>();
+    #[rustfmt::skip]
+    let w = dev.tensor([[[0.6715, -0.1345, 0.5246], [-0.9958, -0.1092, 0.4431]], [[-0.5425, 0.8905, 0.8029], [-0.9388, -0.9491, 0.0828]], [[0.8783, -0.2376, -0.5668], [-0.1558, -0.9419, -0.5566]]]).to_dtype::<TestDtype>();
+    let y = (x.leaky_trace(), w.clone())
+        .conv1d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank2<3, 3>>();
+    #[rustfmt::skip]
+    assert_close_to_literal!(y, [[0.0155068, -0.5862337, -0.4889141], [1.293929, -1.200498, -0.5789455], [-1.697625, 0.6237065, 1.785049]]);
+    let g = y.exp().mean().backward();
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&x), [[-0.1261909, 0.5462999, 0.9388319, -0.1692649, -0.2895894], [-0.4959753, -0.5413594, -0.391031, -0.7755087, -0.3332339]]);
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&w), [[[-0.003617934, 0.07764654, 0.02861922], [0.04669363, 0.01455972, -0.04934157]], [[-0.2402513, 0.2687309, 0.1968063], [0.005171909, 0.09159272, 0.1480645]], [[0.4784981, -0.2009184, -0.09685675], [0.4431615, -0.4120389, -0.7813141]]]);
+}
+
+#[test]
+/// Matches
+/// ```python
+/// y = torch.conv1d(x, w, stride=2, padding=1, dilation=2)
+/// y.exp().mean().backward()
+/// ```
+fn test_conv1d_stride_2_padding_1_dilation_2() {
+    let dev: TestDevice = Default::default();
+    #[rustfmt::skip]
+    let x = dev.tensor([[0.9121, 0.8957, -0.8869, -0.8303, 0.671, 0.4719, 0.3395], [-0.3837, 0.2119, 0.2136, 0.1624, -0.6832, -0.1387, -0.2129]]).to_dtype::<TestDtype>();
+    #[rustfmt::skip]
+    let w = dev.tensor([[[0.446, 0.9896], [0.8988, 0.0884]], [[-0.1103, -0.4635], [-0.9282, -0.9451]]]).to_dtype::<TestDtype>();
+    let y = (x.leaky_trace(), w.clone())
+        .conv1d(Const::<2>, Const::<1>, Const::<2>, Const::<1>)
+        .realize::<Rank2<2, 4>>();
+    #[rustfmt::skip]
+    assert_close_to_literal!(y, [[0.9051167, -0.2173708, 0.2303825, 0.08580384], [-0.6154236, -0.06412148, -0.1467979, 0.07669077]]);
+    let g = y.exp().mean().backward();
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&x), [[0.0, 0.3064306, 0.0, 0.1034826, 0.0, 0.1515795, 0.0], [0.0, -0.05494333, 0.0, -0.06063505, 0.0, -0.09095257, 0.0]]);
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&w), [[[0.02368398, 0.2675553], [0.02798122, 0.05998763]], [[0.07908068, 0.01409835], [0.02365136, 0.01838296]]]);
+}
+
+#[test]
+fn test_batched_conv1d() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<Rank2<3, 28>, TestDtype, _> = dev.sample_normal();
+    let w: Tensor<Rank3<5, 3, 6>, TestDtype, _> = dev.sample_normal();
+
+    let y: Tensor<Rank2<5, 9>, _, _, _> = (x.leaky_trace(), w.clone())
+        .conv1d(Const::<3>, Const::<2>, Const::<1>, Const::<1>)
+        .realize();
+    let y0 = y.retaped::<NoneTape>();
+    let grads0 = y.square().mean().backward();
+    let x0 = grads0.get(&x);
+    let w0 = grads0.get(&w);
+
+    let x = x
+        .broadcast::<Rank3<10, 3, 28>, _>()
+        .reshape::<Rank3<10, 3, 28>>();
+    assert_eq!(x.strides, x.shape.strides());
+
+    let y: Tensor<Rank3<10, 5, 9>, _, _, _> = (x.leaky_trace(), w.clone())
+        .conv1d(Const::<3>, Const::<2>, Const::<1>, Const::<1>)
+        .realize();
+    for i in 0..10 {
+        assert_close_to_tensor!(y0, y.retaped::<NoneTape>().select(dev.tensor(i)));
+    }
+
+    let grads = y.square().mean().backward();
+
+    assert_close_to_tensor!(w0, grads.get(&w), 1e-3);
+
+    let x_grad = grads.get(&x) * 10.0;
+    for i in 0..10 {
+        assert_close_to_tensor!(x0, x_grad.clone().select(dev.tensor(i)));
+    }
+}
+
+#[test]
+fn test_conv1d_grouped() {
+    const NUM_GROUPS: usize = 3;
+    let dev: TestDevice = Default::default();
+    let x: Tensor<Rank3<2, 9, 14>, TestDtype, _> = dev.sample_normal();
+    let w: Tensor<Rank3<15, 3, 3>, TestDtype, _> = dev.sample_normal();
+
+    let y = (x.leaky_trace(), w.clone()).conv1d(
+        Const::<1>,
+        Const::<0>,
+        Const::<1>,
+        Const::<NUM_GROUPS>,
+    );
+    let y_nt = y.retaped::<NoneTape>();
+    let grads = y.exp().sum().backward();
+    let x_grad = grads.get(&x);
+    let w_grad = grads.get(&w);
+
+    for i in 0..NUM_GROUPS {
+        let x_group = x
+            .clone()
+            .slice((.., 3 * i..3 * (i + 1), ..))
+            .realize::<Rank3<2, 3, 14>>()
+            .contiguous();
+        let w_group = w
+            .clone()
+            .slice((5 * i..5 * (i + 1), .., ..))
+            .realize::<Rank3<5, 3, 3>>()
+            .contiguous();
+        let y_group = (x_group.leaky_trace(), w_group.clone())
+            .conv1d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+            .realize::<Rank3<2, 5, 12>>();
+        let y_group_true = y_nt
+            .clone()
+            .slice((.., 5 * i..5 * (i + 1), ..))
+            .realize::<Rank3<2, 5, 12>>();
+        assert_close_to_tensor!(y_group.retaped::<NoneTape>(), y_group_true);
+
+        let grads = y_group.exp().sum().backward();
+        let x_grad_group_true = x_grad
+            .clone()
+            .slice((.., 3 * i..3 * (i + 1), ..))
+            .realize::<Rank3<2, 3, 14>>();
+        let w_grad_group_true = w_grad
+            .clone()
+            .slice((5 * i..5 * (i + 1), .., ..))
+            .realize::<Rank3<5, 3, 3>>();
+        assert_close_to_tensor!(grads.get(&x_group), x_grad_group_true);
+        assert_close_to_tensor!(grads.get(&w_group), w_grad_group_true);
+    }
+}
+
+#[test]
+fn test_conv1d_sliced_input() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+    let w: Tensor<Rank3<4, 3, 3>, TestDtype, _> = dev.sample_normal();
+
+    let x_view = x
+        .leaky_trace()
+        .slice((.., .., 1..7))
+        .realize::<Rank3<2, 3, 6>>();
+    let y = (x_view, w.clone())
+        .conv1d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank3<2, 4, 4>>();
+
+    let x_copy = x
+        .clone()
+        .slice((.., .., 1..7))
+        .realize::<Rank3<2, 3, 6>>()
+        .contiguous();
+    let y_true = (x_copy.leaky_trace(), w.clone())
+        .conv1d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank3<2, 4, 4>>();
+    assert_close_to_tensor!(y, y_true);
+
+    let g = y.square().sum().backward();
+    let g_true = y_true.square().sum().backward();
+    let x_grad = g.get(&x).slice((.., .., 1..7)).realize::<Rank3<2, 3, 6>>();
+    assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+}
+
+#[test]
+fn test_try_conv1d_shape_errors() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<(Const<2>, usize, Const<7>), TestDtype, _> = dev.zeros_like(&(Const, 4, Const));
+    let w: Tensor<Rank3<3, 3, 2>, TestDtype, _> = dev.zeros();
+    let err = (x.clone(), w.clone())
+        .try_conv1d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv1d", &x.shape, &w.shape))
+    );
+
+    let w: Tensor<Rank3<3, 2, 2>, TestDtype, _> = dev.zeros();
+    let err = (x.clone(), w.clone())
+        .try_conv1d(Const::<1>, Const::<0>, Const::<1>, Const::<2>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv1d", &x.shape, &w.shape))
+    );
+}
@@ -78,7 +78,7 @@ impl Cpu {
         let k = op.chan_in * op.kernel * op.kernel;
         let n = op.w_out * op.h_out;
         for g in 0..op.groups {
-            Self::matmul(
+            self.matmul(
                 (m, k, n),
                 false,
                 filters[g * m * k..].as_ptr(),
@@ -133,7 +133,7 @@ impl Cpu {
             let k = (op.chan_out / op.groups) * op.kernel * op.kernel;
             let n = op.h_in * op.w_in;
             for g in 0..op.groups {
-                Self::matmul(
+                self.matmul(
                     (m, k, n),
                     true,
                     filters_tr[g * m * k..].as_ptr(),
@@ -153,7 +153,7 @@ impl Cpu {
             let k = op.h_in * op.w_in;
             let n = (op.chan_out / op.groups) * op.kernel * op.kernel;
             for g in 0..op.groups {
-                Self::matmul(
+                self.matmul(
                     (m, k, n),
                     true,
                     img[g * m * k..].as_ptr(),
@@ -197,8 +197,8 @@ where
             4 => [lhs.strides[0], out.strides[0]],
             _ => unreachable!(),
         };
-        let lhs = lhs.data.as_ref();
-        let rhs = rhs.data.as_ref();
+        let lhs = &lhs.data[lhs.offset..];
+        let rhs = &rhs.data[rhs.offset..];
         let out = Arc::make_mut(&mut out.data);
         for i_batch in 0..op.batch {
             self.fwd(
@@ -236,7 +236,7 @@ where
 
         {
             // transpose filters in f1023
-            let buf = rhs.data.as_ref();
+            let buf = &rhs.data[rhs.offset..];
             let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
             while let Some((i, [g, c, o, k1, k2])) = f_idx.next_with_idx() {
                 let idx = (g * (op.chan_out / op.groups) + o) * rhs.strides[0]
@@ -252,7 +252,7 @@ where
             4 => [lhs.strides[0], out.strides()[0]],
             _ => unreachable!(),
         };
-        let lhs = lhs.data.as_ref();
+        let lhs = &lhs.data[lhs.offset..];
 
         for i_batch in 0..op.batch {
             self.bwd(
@@ -1,6 +1,7 @@
 use crate::{shapes::*, tensor::*, tensor_ops::ReshapeTo};
 
 mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(all(not(feature = "cudnn"), feature = "cuda"))]
 mod cuda_kernel;
@@ -75,6 +76,7 @@ pub trait TryConv2D<Stride, Padding, Dilation, Groups>: Sized {
     ) -> Result<Self::Convolved, Self::Error>;
 }
 
+#[cfg(feature = "nightly")]
 impl<
         const KERNEL: usize,
         const STRIDE: usize,
@@ -121,13 +123,33 @@ impl<Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim, Groups: Dim>
     }
 }
 
-impl<InpChan, OutChan, Kernel, Stride, Padding, Dilation, Groups, H, W, E, D, T>
+// Without `generic_const_exprs` the convolved size of a [Const] can't be named, so
+// it is computed at runtime instead.
+#[cfg(not(feature = "nightly"))]
+impl<const DIM: usize, Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim, Groups: Dim>
+    TryConv2D<Stride, Padding, Dilation, Groups> for (Const<DIM>, Kernel)
+{
+    type Convolved = usize;
+    type Error = std::convert::Infallible;
+    fn try_conv2d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        (self.0.size(), self.1).try_conv2d(stride, padding, dilation, groups)
+    }
+}
+
+impl<ImgChan, InpChan, OutChan, Kernel, Stride, Padding, Dilation, Groups, H, W, E, D, T>
     TryConv2D<Stride, Padding, Dilation, Groups>
     for (
-        Tensor<(<InpChan as std::ops::Mul<Groups>>::Output, H, W), E, D, T>,
+        Tensor<(ImgChan, H, W), E, D, T>,
         Tensor<(OutChan, InpChan, Kernel, Kernel), E, D>,
     )
 where
+    ImgChan: Dim,
     InpChan: Dim,
     OutChan: Dim,
     Kernel: Dim,
@@ -140,8 +162,6 @@ where
     E: Dtype,
     D: Conv2DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
     T: Tape<E, D>,
-    InpChan: std::ops::Mul<Groups>,
-    <InpChan as std::ops::Mul<Groups>>::Output: Dim,
     (H, Kernel): TryConv2D<Stride, Padding, Dilation, Groups>,
     (W, Kernel): TryConv2D<Stride, Padding, Dilation, Groups>,
     <(H, Kernel) as TryConv2D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
@@ -175,13 +195,28 @@ where
     }
 }
 
-impl<InpChan, OutChan, Kernel, Stride, Padding, Dilation, Groups, Batch, H, W, E, D, T>
-    TryConv2D<Stride, Padding, Dilation, Groups>
+impl<
+        ImgChan,
+        InpChan,
+        OutChan,
+        Kernel,
+        Stride,
+        Padding,
+        Dilation,
+        Groups,
+        Batch,
+        H,
+        W,
+        E,
+        D,
+        T,
+    > TryConv2D<Stride, Padding, Dilation, Groups>
     for (
-        Tensor<(Batch, <InpChan as std::ops::Mul<Groups>>::Output, H, W), E, D, T>,
+        Tensor<(Batch, ImgChan, H, W), E, D, T>,
         Tensor<(OutChan, InpChan, Kernel, Kernel), E, D>,
     )
 where
+    ImgChan: Dim,
     InpChan: Dim,
     OutChan: Dim,
     Kernel: Dim,
@@ -193,10 +228,8 @@ where
     H: Dim,
     W: Dim,
     E: Dtype,
-    D: Conv2DKernel<E>,
+    D: Conv2DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
     T: Tape<E, D>,
-    InpChan: std::ops::Mul<Groups>,
-    <InpChan as std::ops::Mul<Groups>>::Output: Dim,
     (H, Kernel): TryConv2D<Stride, Padding, Dilation, Groups>,
     (W, Kernel): TryConv2D<Stride, Padding, Dilation, Groups>,
     <(H, Kernel) as TryConv2D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
@@ -223,14 +256,17 @@ where
         groups: Groups,
     ) -> Result<Self::Convolved, Self::Error> {
         let (img, filters) = self;
-        assert_eq!(img.shape.1.size(), filters.shape.1.size() * groups.size());
-        assert_eq!(filters.shape.2, filters.shape.3);
-        let (batch, _, h, w) = img.shape;
-        let (out_chan, inp_chan, kernel, _) = filters.shape;
-        assert!(out_chan.size() % groups.size() == 0);
-        if img.strides != img.shape.strides() || filters.strides != filters.shape.strides() {
-            panic!("Image & filter inputs to conv2d must be contiguous");
+        let (batch, img_chan, h, w) = img.shape;
+        let (out_chan, inp_chan, kernel, kernel_w) = filters.shape;
+        if img_chan.size() != inp_chan.size() * groups.size()
+            || kernel != kernel_w
+            || out_chan.size() % groups.size() != 0
+        {
+            return Err(ShapeError::new("conv2d", &img.shape, &filters.shape).into());
         }
+        // the kernels only handle contiguous inputs, so views are copied first
+        let img = img.try_contiguous()?;
+        let filters = filters.try_contiguous()?;
         let h_out = (h, kernel).conv2d(stride, padding, dilation, groups);
         let w_out = (w, kernel).conv2d(stride, padding, dilation, groups);
         let op = Conv2DOp {
@@ -251,7 +287,7 @@ where
         let (rhs, rtape) = filters.split_tape();
         let mut out = lhs.device.alloc((batch, out_chan, h_out, w_out))?;
         let mut tape = ltape.merge(rtape);
-        lhs.device.forward(op, &lhs, &rhs, &mut out)?;
+        Conv2DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
         let lhs_ghost = lhs.ghost();
         let rhs_ghost = rhs.ghost();
         let out_ghost = out.ghost();
@@ -261,8 +297,16 @@ where
             grads.try_alloc_for(&out_ghost)?;
             let (grad_lhs, grad_rhs, grad_out) =
                 grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
-            lhs.device
-                .backward(op, &lhs, grad_lhs, &rhs, grad_rhs, &out_ghost, grad_out)
+            Conv2DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
         });
         Ok(out.put_tape(tape))
     }
@@ -0,0 +1,98 @@
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::*;
+
+use super::{Conv2DKernel, Conv2DOp};
+
+use std::sync::Arc;
+
+fn make_4d<S: Shape>(strides: S::Concrete) -> [usize; 4] {
+    match S::NUM_DIMS {
+        3 => [0, strides[0], strides[1], strides[2]],
+        4 => [strides[0], strides[1], strides[2], strides[3]],
+        _ => panic!("Only implemented for 3d & 4d arrays"),
+    }
+}
+
+impl Conv2DOp {
+    /// Calls `f([o, c, k1, k2], [oh, ow], [c_img, y, x])` for every multiply-add of the
+    /// convolution, where `(c_img, y, x)` indexes the image & `(o, c, k1, k2)` the filters.
+    fn for_each_tap(&self, mut f: impl FnMut([usize; 4], [usize; 2], [usize; 3])) {
+        let o_per_group = self.chan_out / self.groups;
+        for o in 0..self.chan_out {
+            let g = o / o_per_group;
+            for c in 0..self.chan_in {
+                for k1 in 0..self.kernel {
+                    for k2 in 0..self.kernel {
+                        for oh in 0..self.h_out {
+                            for ow in 0..self.w_out {
+                                let y = (oh * self.stride + self.dilation * k1)
+                                    .checked_sub(self.padding);
+                                let x = (ow * self.stride + self.dilation * k2)
+                                    .checked_sub(self.padding);
+                                if let Some((y, x)) = y.zip(x) {
+                                    if y < self.h_in && x < self.w_in {
+                                        f([o, c, k1, k2], [oh, ow], [g * self.chan_in + c, y, x]);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Dtype> Conv2DKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv2DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_4d::<L>(lhs.strides);
+        let ostr = make_4d::<O>(out.strides);
+        let rstr = rhs.strides;
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2], [oh, ow], [ci, y, x]| {
+                let w =
+                    rhs.data[rhs.offset + o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3]];
+                let v =
+                    lhs.data[lhs.offset + b * lstr[0] + ci * lstr[1] + y * lstr[2] + x * lstr[3]];
+                out_buf[b * ostr[0] + o * ostr[1] + oh * ostr[2] + ow * ostr[3]] += w * v;
+            });
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv2DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_4d::<L>(lhs.strides);
+        let ostr = make_4d::<O>(out.strides());
+        let rstr = rhs.strides;
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2], [oh, ow], [ci, y, x]| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3];
+                let i_lhs = b * lstr[0] + ci * lstr[1] + y * lstr[2] + x * lstr[3];
+                let go = grad_out[b * ostr[0] + o * ostr[1] + oh * ostr[2] + ow * ostr[3]];
+                grad_lhs[i_lhs] += rhs.data[rhs.offset + i_rhs] * go;
+                grad_rhs[i_rhs] += lhs.data[lhs.offset + i_lhs] * go;
+            });
+        }
+        Ok(())
+    }
+}
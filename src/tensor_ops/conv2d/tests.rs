@@ -27,6 +27,7 @@ fn test_conv2d_default_stride_and_padding() {
         .to_dtype::<TestDtype>();
     let result = (x.leaky_trace(), weight.clone())
         .conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank3<2, 1, 2>>()
         + bias.leaky_trace().broadcast::<_, Axes2<1, 2>>();
     assert_close_to_literal!(
         result,
@@ -77,6 +78,7 @@ fn test_conv2d_stride_2() {
 
     let result = (x.leaky_trace(), weight.clone())
         .conv2d(Const::<2>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank3<2, 1, 1>>()
         + bias.leaky_trace().broadcast::<_, Axes2<1, 2>>();
     assert_close_to_literal!(result, [[[-0.29368058]], [[0.30018353]]]);
 
@@ -116,6 +118,7 @@ fn test_conv2d_padding_1() {
 
     let result = (x.leaky_trace(), weight.clone())
         .conv2d(Const::<1>, Const::<1>, Const::<1>, Const::<1>)
+        .realize::<Rank3<3, 2, 3>>()
         + bias.leaky_trace().broadcast::<_, Axes2<1, 2>>();
 
     #[rustfmt::skip]
@@ -164,6 +167,7 @@ fn test_conv2d_stride_3_padding_4() {
 
     let result = (x.leaky_trace(), weight.clone())
         .conv2d(Const::<3>, Const::<4>, Const::<1>, Const::<1>)
+        .realize::<Rank3<2, 4, 3>>()
         + bias.leaky_trace().broadcast::<_, Axes2<1, 2>>();
 
     #[rustfmt::skip]
@@ -203,8 +207,9 @@ fn test_conv2d_s4p3k2() {
     let bias: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
     let x: Tensor<Rank3<5, 7, 6>, TestDtype, _> = dev.sample_normal();
 
-    let out =
-        (x.leaky_trace(), weight.clone()).conv2d(Const::<4>, Const::<3>, Const::<1>, Const::<1>);
+    let out = (x.leaky_trace(), weight.clone())
+        .conv2d(Const::<4>, Const::<3>, Const::<1>, Const::<1>)
+        .realize::<Rank3<3, 3, 3>>();
     let out = out + bias.broadcast::<_, Axes2<1, 2>>();
 
     #[rustfmt::skip]
@@ -221,8 +226,9 @@ fn test_batched_conv2d() {
     let x: Tensor<Rank3<3, 28, 28>, TestDtype, _> = dev.sample_normal();
     let w: Tensor<Rank4<5, 3, 6, 6>, TestDtype, _> = dev.sample_normal();
 
-    let y: Tensor<Rank3<5, 9, 9>, _, _, _> =
-        (x.leaky_trace(), w.clone()).conv2d(Const::<3>, Const::<2>, Const::<1>, Const::<1>);
+    let y: Tensor<Rank3<5, 9, 9>, _, _, _> = (x.leaky_trace(), w.clone())
+        .conv2d(Const::<3>, Const::<2>, Const::<1>, Const::<1>)
+        .realize();
     let y0 = y.retaped::<NoneTape>();
     let grads0 = y.square().mean().backward();
     let x0 = grads0.get(&x);
@@ -233,8 +239,9 @@ fn test_batched_conv2d() {
         .reshape::<Rank4<10, 3, 28, 28>>();
     assert_eq!(x.strides, x.shape.strides());
 
-    let y: Tensor<Rank4<10, 5, 9, 9>, _, _, _> =
-        (x.leaky_trace(), w.clone()).conv2d(Const::<3>, Const::<2>, Const::<1>, Const::<1>);
+    let y: Tensor<Rank4<10, 5, 9, 9>, _, _, _> = (x.leaky_trace(), w.clone())
+        .conv2d(Const::<3>, Const::<2>, Const::<1>, Const::<1>)
+        .realize();
     for i in 0..10 {
         assert_close_to_tensor!(y0, y.retaped::<NoneTape>().select(dev.tensor(i)));
     }
@@ -263,7 +270,9 @@ fn test_conv2d_dilated() {
     let w = dev
         .tensor([[[[0.1, 0.5], [1.0, 2.0]]]])
         .to_dtype::<TestDtype>();
-    let y = (x.leaky_trace(), w.clone()).conv2d(Const::<1>, Const::<0>, Const::<2>, Const::<1>);
+    let y = (x.leaky_trace(), w.clone())
+        .conv2d(Const::<1>, Const::<0>, Const::<2>, Const::<1>)
+        .realize::<Rank3<1, 2, 3>>();
     assert_close_to_literal!(y, [[[38.0, 42.1, 45.7], [56.6, 60.2, 63.8]]]);
     let grads = y.mean().backward();
     assert_close_to_literal!(
@@ -296,12 +305,16 @@ fn test_conv2d_grouped_forward() {
         let x_group = x
             .clone()
             .slice((.., 3 * i..3 * (i + 1), .., ..))
-            .realize::<(Const<2>, Const<3>, Const<14>, Const<14>)>();
+            .realize::<(Const<2>, Const<3>, Const<14>, Const<14>)>()
+            .contiguous();
         let w_group = w
             .clone()
             .slice((5 * i..5 * (i + 1), .., .., ..))
-            .realize::<(Const<5>, Const<3>, Const<3>, Const<3>)>();
-        let y_group = (x_group, w_group).conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>);
+            .realize::<(Const<5>, Const<3>, Const<3>, Const<3>)>()
+            .contiguous();
+        let y_group = (x_group, w_group)
+            .conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+            .realize::<(Const<2>, Const<5>, Const<12>, Const<12>)>();
         let y_group_true = y
             .retaped::<NoneTape>()
             .slice((.., 5 * i..5 * (i + 1), .., ..))
@@ -317,11 +330,13 @@ fn test_conv2d_grouped_forward() {
         let x_group = x
             .clone()
             .slice((.., 3 * i..3 * (i + 1), .., ..))
-            .realize::<(Const<2>, Const<3>, Const<14>, Const<14>)>();
+            .realize::<(Const<2>, Const<3>, Const<14>, Const<14>)>()
+            .contiguous();
         let w_group = w
             .clone()
             .slice((5 * i..5 * (i + 1), .., .., ..))
-            .realize::<(Const<5>, Const<3>, Const<3>, Const<3>)>();
+            .realize::<(Const<5>, Const<3>, Const<3>, Const<3>)>()
+            .contiguous();
         let y_group = (x_group.leaky_trace(), w_group.clone())
             .conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>);
         let grads = y_group.exp().sum().backward();
@@ -339,3 +354,73 @@ fn test_conv2d_grouped_forward() {
         assert_close_to_tensor!(grads.get(&w_group), w_grad_group_true);
     }
 }
+
+#[test]
+fn test_conv2d_sliced_input() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<Rank4<2, 3, 6, 6>, TestDtype, _> = dev.sample_normal();
+    let w: Tensor<Rank4<4, 3, 2, 2>, TestDtype, _> = dev.sample_normal();
+
+    let x_view = x
+        .leaky_trace()
+        .slice((.., .., 1..5, 1..5))
+        .realize::<Rank4<2, 3, 4, 4>>();
+    let y = (x_view, w.clone())
+        .conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank4<2, 4, 3, 3>>();
+
+    let x_copy = x
+        .clone()
+        .slice((.., .., 1..5, 1..5))
+        .realize::<Rank4<2, 3, 4, 4>>()
+        .contiguous();
+    let y_true = (x_copy.leaky_trace(), w.clone())
+        .conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank4<2, 4, 3, 3>>();
+    assert_close_to_tensor!(y, y_true);
+
+    let g = y.square().sum().backward();
+    let g_true = y_true.square().sum().backward();
+    let x_grad = g
+        .get(&x)
+        .slice((.., .., 1..5, 1..5))
+        .realize::<Rank4<2, 3, 4, 4>>();
+    assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+}
+
+#[test]
+fn test_try_conv2d_shape_errors() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<(Const<2>, usize, Const<5>, Const<5>), TestDtype, _> =
+        dev.zeros_like(&(Const, 4, Const, Const));
+    let w: Tensor<Rank4<3, 3, 2, 2>, TestDtype, _> = dev.zeros();
+    let err = (x.clone(), w.clone())
+        .try_conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv2d", &x.shape, &w.shape))
+    );
+
+    // with `nightly`, a `usize` kernel only convolves `usize` image dims
+    let x2: Tensor<(Const<2>, usize, usize, usize), TestDtype, _> =
+        dev.zeros_like(&(Const, 4, 5, 5));
+    let w: Tensor<(Const<4>, Const<4>, usize, usize), TestDtype, _> =
+        dev.zeros_like(&(Const, Const, 2, 3));
+    let err = (x2.clone(), w.clone())
+        .try_conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv2d", &x2.shape, &w.shape))
+    );
+
+    let w: Tensor<Rank4<3, 2, 2, 2>, TestDtype, _> = dev.zeros();
+    let err = (x.clone(), w.clone())
+        .try_conv2d(Const::<1>, Const::<0>, Const::<1>, Const::<2>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv2d", &x.shape, &w.shape))
+    );
+}
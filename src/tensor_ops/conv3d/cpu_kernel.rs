@@ -0,0 +1,304 @@
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::{cpu::*, *};
+use crate::tensor_ops::matmul::cpu_kernel::MatMulImpl;
+
+use super::{Conv3DKernel, Conv3DOp};
+
+use std::sync::Arc;
+
+impl Conv3DOp {
+    #[inline(always)]
+    fn unfold_idx(&self, [k1, k2, k3, z, y, x]: [usize; 6]) -> Option<[usize; 3]> {
+        let out_idx = |i: usize, k: usize, out: usize| {
+            let mut o = i + self.padding;
+            if o < self.dilation * k {
+                return None;
+            }
+            o -= self.dilation * k;
+            if o % self.stride != 0 {
+                return None;
+            }
+            o /= self.stride;
+            (o < out).then_some(o)
+        };
+        let od = out_idx(z, k1, self.d_out)?;
+        let oh = out_idx(y, k2, self.h_out)?;
+        let ow = out_idx(x, k3, self.w_out)?;
+        Some([od, oh, ow])
+    }
+}
+
+impl Cpu {
+    #[inline]
+    fn conv3d_fwd<E: Dtype>(
+        &self,
+        op: &Conv3DOp,
+        img: &[E],
+        filters: &[E],
+        out: &mut [E],
+        buf: &mut [E],
+    ) -> Result<(), CpuError>
+    where
+        Self: MatMulImpl<E>,
+    {
+        {
+            let mut i = 0;
+            for c in 0..(op.groups * op.chan_in) {
+                for k1 in 0..op.kernel {
+                    for k2 in 0..op.kernel {
+                        for k3 in 0..op.kernel {
+                            for od in 0..op.d_out {
+                                let z =
+                                    (od * op.stride + op.dilation * k1).wrapping_sub(op.padding);
+                                for oh in 0..op.h_out {
+                                    let y = (oh * op.stride + op.dilation * k2)
+                                        .wrapping_sub(op.padding);
+                                    for ow in 0..op.w_out {
+                                        let x = (ow * op.stride + op.dilation * k3)
+                                            .wrapping_sub(op.padding);
+                                        if z < op.d_in && y < op.h_in && x < op.w_in {
+                                            buf[i] = img[c * (op.d_in * op.h_in * op.w_in)
+                                                + z * (op.h_in * op.w_in)
+                                                + y * op.w_in
+                                                + x];
+                                        }
+                                        i += 1;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // (G, O / G, C * K * K * K) * (G, C * K * K * K, OD * OH * OW) = (G, O / G, OD * OH * OW)
+        let m = op.chan_out / op.groups;
+        let k = op.chan_in * op.kernel * op.kernel * op.kernel;
+        let n = op.d_out * op.h_out * op.w_out;
+        for g in 0..op.groups {
+            self.matmul(
+                (m, k, n),
+                false,
+                filters[g * m * k..].as_ptr(),
+                [k, 1],
+                buf[g * k * n..].as_ptr(),
+                [n, 1],
+                out[g * m * n..].as_mut_ptr(),
+                [n, 1],
+            );
+        }
+        Ok(())
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn conv3d_bwd<E: Dtype>(
+        &self,
+        op: &Conv3DOp,
+        img: &[E],
+        grad_img: &mut [E],
+        filters_tr: &[E],
+        grad_filters_tr: &mut [E],
+        grad_out: &[E],
+        buf: &mut [E],
+    ) -> Result<(), CpuError>
+    where
+        Self: MatMulImpl<E>,
+    {
+        {
+            let out_numel = op.d_out * op.h_out * op.w_out;
+            let mut i = 0;
+            for o in 0..op.chan_out {
+                for k1 in 0..op.kernel {
+                    for k2 in 0..op.kernel {
+                        for k3 in 0..op.kernel {
+                            for z in 0..op.d_in {
+                                for y in 0..op.h_in {
+                                    for x in 0..op.w_in {
+                                        if let Some([od, oh, ow]) =
+                                            op.unfold_idx([k1, k2, k3, z, y, x])
+                                        {
+                                            buf[i] = grad_out[o * out_numel
+                                                + od * (op.h_out * op.w_out)
+                                                + oh * op.w_out
+                                                + ow];
+                                        }
+                                        i += 1;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        {
+            // img_g += filters^T * unfold(grad_out)
+            // (G, C, D * H * W) += (G, C, O/G * K * K * K) * (G, O/G * K * K * K, D * H * W)
+            let m = op.chan_in;
+            let k = (op.chan_out / op.groups) * op.kernel * op.kernel * op.kernel;
+            let n = op.d_in * op.h_in * op.w_in;
+            for g in 0..op.groups {
+                self.matmul(
+                    (m, k, n),
+                    true,
+                    filters_tr[g * m * k..].as_ptr(),
+                    [k, 1],
+                    buf[g * k * n..].as_ptr(),
+                    [n, 1],
+                    grad_img[g * m * n..].as_mut_ptr(),
+                    [n, 1],
+                );
+            }
+        }
+
+        {
+            // weight_g^T += img * unfold(patches)^T
+            // (G, C, O/G * K * K * K) += (G, C, D * H * W) * (G, D * H * W, O/G * K * K * K)
+            let m = op.chan_in;
+            let k = op.d_in * op.h_in * op.w_in;
+            let n = (op.chan_out / op.groups) * op.kernel * op.kernel * op.kernel;
+            for g in 0..op.groups {
+                self.matmul(
+                    (m, k, n),
+                    true,
+                    img[g * m * k..].as_ptr(),
+                    [k, 1],
+                    buf[g * k * n..].as_ptr(),
+                    [1, k],
+                    grad_filters_tr[g * m * n..].as_mut_ptr(),
+                    [n, 1],
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> Conv3DKernel<E> for Cpu
+where
+    Self: MatMulImpl<E>,
+{
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv3DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let patches = [
+            op.groups * op.chan_in,
+            op.kernel,
+            op.kernel,
+            op.kernel,
+            op.d_out,
+            op.h_out,
+            op.w_out,
+        ];
+        let mut patches = self.try_alloc_zeros::<E>(patches.iter().product())?;
+        let [lstride, ostride] = match L::NUM_DIMS {
+            4 => [0; 2],
+            5 => [lhs.strides[0], out.strides[0]],
+            _ => unreachable!(),
+        };
+        let lhs = &lhs.data[lhs.offset..];
+        let rhs = &rhs.data[rhs.offset..];
+        let out = Arc::make_mut(&mut out.data);
+        for i_batch in 0..op.batch {
+            self.conv3d_fwd(
+                &op,
+                &lhs[i_batch * lstride..],
+                rhs,
+                &mut out[i_batch * ostride..],
+                &mut patches,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv3DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let f_tr_shape = [
+            op.groups,
+            op.chan_in,
+            op.chan_out / op.groups,
+            op.kernel,
+            op.kernel,
+            op.kernel,
+        ];
+        let patches_shape = [
+            op.chan_out,
+            op.kernel,
+            op.kernel,
+            op.kernel,
+            op.d_in,
+            op.h_in,
+            op.w_in,
+        ];
+        let mut patches = self.try_alloc_zeros::<E>(patches_shape.iter().product())?;
+        let mut f10234 = self.try_alloc_zeros::<E>(f_tr_shape.num_elements())?;
+        let mut grad_f10234 = self.try_alloc_zeros::<E>(f_tr_shape.num_elements())?;
+
+        {
+            // transpose filters in f10234
+            let buf = &rhs.data[rhs.offset..];
+            let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
+            while let Some((i, [g, c, o, k1, k2, k3])) = f_idx.next_with_idx() {
+                let idx = (g * (op.chan_out / op.groups) + o) * rhs.strides[0]
+                    + c * rhs.strides[1]
+                    + k1 * rhs.strides[2]
+                    + k2 * rhs.strides[3]
+                    + k3 * rhs.strides[4];
+                f10234[i] = buf[idx];
+            }
+        }
+
+        let [lstride, ostride] = match L::NUM_DIMS {
+            4 => [0; 2],
+            5 => [lhs.strides[0], out.strides()[0]],
+            _ => unreachable!(),
+        };
+        let lhs = &lhs.data[lhs.offset..];
+
+        for i_batch in 0..op.batch {
+            self.conv3d_bwd(
+                &op,
+                &lhs[i_batch * lstride..],
+                &mut grad_lhs[i_batch * lstride..],
+                &f10234,
+                &mut grad_f10234,
+                &grad_out[i_batch * ostride..],
+                &mut patches,
+            )?;
+        }
+
+        {
+            // untranspose filters
+            let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
+            while let Some((i, [g, c, o, k1, k2, k3])) = f_idx.next_with_idx() {
+                let idx = (g * (op.chan_out / op.groups) + o) * rhs.strides[0]
+                    + c * rhs.strides[1]
+                    + k1 * rhs.strides[2]
+                    + k2 * rhs.strides[3]
+                    + k3 * rhs.strides[4];
+                grad_rhs[idx] += grad_f10234[i];
+            }
+        }
+
+        Ok(())
+    }
+}
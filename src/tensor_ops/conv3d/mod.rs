@@ -0,0 +1,328 @@
+use crate::{shapes::*, tensor::*, tensor_ops::ReshapeTo};
+
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+#[cfg(test)]
+mod tests;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Conv3DOp {
+    pub kernel: usize,
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+    pub groups: usize,
+    pub batch: usize,
+    pub chan_in: usize,
+    pub chan_out: usize,
+    pub d_in: usize,
+    pub d_out: usize,
+    pub h_in: usize,
+    pub h_out: usize,
+    pub w_in: usize,
+    pub w_out: usize,
+}
+
+pub(super) trait Conv3DKernel<E: Dtype>: DeviceStorage {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err>;
+
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv3DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv3DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// 3d convolution of volumes with shape `(Channels, Depth, Height, Width)` or
+/// `(Batch, Channels, Depth, Height, Width)`, and filters with shape
+/// `(OutChan, InpChan, Kernel, Kernel, Kernel)`.
+pub trait TryConv3D<Stride, Padding, Dilation, Groups>: Sized {
+    type Convolved;
+    type Error: std::fmt::Debug;
+
+    fn conv3d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Self::Convolved {
+        self.try_conv3d(stride, padding, dilation, groups).unwrap()
+    }
+
+    fn try_conv3d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error>;
+}
+
+#[cfg(feature = "nightly")]
+impl<
+        const KERNEL: usize,
+        const STRIDE: usize,
+        const PADDING: usize,
+        const DILATION: usize,
+        Groups: Dim,
+        const DIM: usize,
+    > TryConv3D<Const<STRIDE>, Const<PADDING>, Const<DILATION>, Groups>
+    for (Const<DIM>, Const<KERNEL>)
+where
+    Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>: Sized,
+{
+    type Convolved = Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>;
+    type Error = std::convert::Infallible;
+    fn try_conv3d(
+        self,
+        _: Const<STRIDE>,
+        _: Const<PADDING>,
+        _: Const<DILATION>,
+        _: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        Ok(Const)
+    }
+}
+
+impl<Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim, Groups: Dim>
+    TryConv3D<Stride, Padding, Dilation, Groups> for (usize, Kernel)
+{
+    type Convolved = usize;
+    type Error = std::convert::Infallible;
+    fn try_conv3d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        _: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        let (dim, kernel) = self;
+        Ok((dim + 2 * padding.size() - 1)
+            .checked_sub(dilation.size() * (kernel.size() - 1))
+            .unwrap()
+            / stride.size()
+            + 1)
+    }
+}
+
+// Without `generic_const_exprs` the convolved size of a [Const] can't be named, so
+// it is computed at runtime instead.
+#[cfg(not(feature = "nightly"))]
+impl<const DIM: usize, Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim, Groups: Dim>
+    TryConv3D<Stride, Padding, Dilation, Groups> for (Const<DIM>, Kernel)
+{
+    type Convolved = usize;
+    type Error = std::convert::Infallible;
+    fn try_conv3d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        (self.0.size(), self.1).try_conv3d(stride, padding, dilation, groups)
+    }
+}
+
+impl<ImgChan, InpChan, OutChan, Kernel, Stride, Padding, Dilation, Groups, Z, H, W, E, D, T>
+    TryConv3D<Stride, Padding, Dilation, Groups>
+    for (
+        Tensor<(ImgChan, Z, H, W), E, D, T>,
+        Tensor<(OutChan, InpChan, Kernel, Kernel, Kernel), E, D>,
+    )
+where
+    ImgChan: Dim,
+    InpChan: Dim,
+    OutChan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Groups: Dim,
+    Z: Dim,
+    H: Dim,
+    W: Dim,
+    E: Dtype,
+    D: Conv3DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+    (Z, Kernel): TryConv3D<Stride, Padding, Dilation, Groups>,
+    (H, Kernel): TryConv3D<Stride, Padding, Dilation, Groups>,
+    (W, Kernel): TryConv3D<Stride, Padding, Dilation, Groups>,
+    <(Z, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+    <(H, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+    <(W, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+{
+    type Convolved = Tensor<
+        (
+            OutChan,
+            <(Z, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved,
+            <(H, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved,
+            <(W, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved,
+        ),
+        E,
+        D,
+        T,
+    >;
+    type Error = D::Err;
+
+    fn try_conv3d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        let (img, filters) = self;
+        let (inp_chan, d, h, w) = img.shape;
+        let img = img.try_reshape_like(&(Const::<1>, inp_chan, d, h, w))?;
+        let out = (img, filters).try_conv3d(stride, padding, dilation, groups)?;
+        let (_, out_chan, out_d, out_h, out_w) = out.shape;
+        out.try_reshape_like(&(out_chan, out_d, out_h, out_w))
+    }
+}
+
+impl<
+        ImgChan,
+        InpChan,
+        OutChan,
+        Kernel,
+        Stride,
+        Padding,
+        Dilation,
+        Groups,
+        Batch,
+        Z,
+        H,
+        W,
+        E,
+        D,
+        T,
+    > TryConv3D<Stride, Padding, Dilation, Groups>
+    for (
+        Tensor<(Batch, ImgChan, Z, H, W), E, D, T>,
+        Tensor<(OutChan, InpChan, Kernel, Kernel, Kernel), E, D>,
+    )
+where
+    ImgChan: Dim,
+    InpChan: Dim,
+    OutChan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Groups: Dim,
+    Batch: Dim,
+    Z: Dim,
+    H: Dim,
+    W: Dim,
+    E: Dtype,
+    D: Conv3DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+    (Z, Kernel): TryConv3D<Stride, Padding, Dilation, Groups>,
+    (H, Kernel): TryConv3D<Stride, Padding, Dilation, Groups>,
+    (W, Kernel): TryConv3D<Stride, Padding, Dilation, Groups>,
+    <(Z, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+    <(H, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+    <(W, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved: Dim,
+{
+    type Convolved = Tensor<
+        (
+            Batch,
+            OutChan,
+            <(Z, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved,
+            <(H, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved,
+            <(W, Kernel) as TryConv3D<Stride, Padding, Dilation, Groups>>::Convolved,
+        ),
+        E,
+        D,
+        T,
+    >;
+    type Error = D::Err;
+
+    fn try_conv3d(
+        self,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+        groups: Groups,
+    ) -> Result<Self::Convolved, Self::Error> {
+        let (img, filters) = self;
+        let (batch, img_chan, d, h, w) = img.shape;
+        let (out_chan, inp_chan, kernel, kernel_h, kernel_w) = filters.shape;
+        if img_chan.size() != inp_chan.size() * groups.size()
+            || kernel != kernel_h
+            || kernel != kernel_w
+            || out_chan.size() % groups.size() != 0
+        {
+            return Err(ShapeError::new("conv3d", &img.shape, &filters.shape).into());
+        }
+        // the kernels only handle contiguous inputs, so views are copied first
+        let img = img.try_contiguous()?;
+        let filters = filters.try_contiguous()?;
+        let d_out = (d, kernel).conv3d(stride, padding, dilation, groups);
+        let h_out = (h, kernel).conv3d(stride, padding, dilation, groups);
+        let w_out = (w, kernel).conv3d(stride, padding, dilation, groups);
+        let op = Conv3DOp {
+            stride: stride.size(),
+            padding: padding.size(),
+            kernel: kernel.size(),
+            dilation: dilation.size(),
+            groups: groups.size(),
+            batch: batch.size(),
+            chan_in: inp_chan.size(),
+            chan_out: out_chan.size(),
+            d_in: d.size(),
+            d_out: d_out.size(),
+            h_in: h.size(),
+            h_out: h_out.size(),
+            w_in: w.size(),
+            w_out: w_out.size(),
+        };
+        let (lhs, ltape) = img.split_tape();
+        let (rhs, rtape) = filters.split_tape();
+        let mut out = lhs.device.alloc((batch, out_chan, d_out, h_out, w_out))?;
+        let mut tape = ltape.merge(rtape);
+        Conv3DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
+        let lhs_ghost = lhs.ghost();
+        let rhs_ghost = rhs.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&rhs_ghost)?;
+            grads.try_alloc_for(&lhs_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_lhs, grad_rhs, grad_out) =
+                grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
+            Conv3DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
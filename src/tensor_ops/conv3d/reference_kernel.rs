@@ -0,0 +1,109 @@
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::*;
+
+use super::{Conv3DKernel, Conv3DOp};
+
+use std::sync::Arc;
+
+fn make_5d<S: Shape>(strides: S::Concrete) -> [usize; 5] {
+    match S::NUM_DIMS {
+        4 => [0, strides[0], strides[1], strides[2], strides[3]],
+        5 => [strides[0], strides[1], strides[2], strides[3], strides[4]],
+        _ => panic!("Only implemented for 4d & 5d arrays"),
+    }
+}
+
+impl Conv3DOp {
+    /// Calls `f([o, c, k1, k2, k3], [od, oh, ow], [c_img, z, y, x])` for every multiply-add of
+    /// the convolution, where `(c_img, z, y, x)` indexes the image & `(o, c, k1, k2, k3)` the filters.
+    fn for_each_tap(&self, mut f: impl FnMut([usize; 5], [usize; 3], [usize; 4])) {
+        let o_per_group = self.chan_out / self.groups;
+        let in_idx = |o: usize, k: usize, size: usize| {
+            (o * self.stride + self.dilation * k)
+                .checked_sub(self.padding)
+                .filter(|&i| i < size)
+        };
+        for o in 0..self.chan_out {
+            let g = o / o_per_group;
+            for c in 0..self.chan_in {
+                for k1 in 0..self.kernel {
+                    for k2 in 0..self.kernel {
+                        for k3 in 0..self.kernel {
+                            for od in 0..self.d_out {
+                                for oh in 0..self.h_out {
+                                    for ow in 0..self.w_out {
+                                        let z = in_idx(od, k1, self.d_in);
+                                        let y = in_idx(oh, k2, self.h_in);
+                                        let x = in_idx(ow, k3, self.w_in);
+                                        if let Some(((z, y), x)) = z.zip(y).zip(x) {
+                                            f(
+                                                [o, c, k1, k2, k3],
+                                                [od, oh, ow],
+                                                [g * self.chan_in + c, z, y, x],
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Dtype> Conv3DKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv3DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_5d::<L>(lhs.strides);
+        let ostr = make_5d::<O>(out.strides);
+        let rstr = make_5d::<R>(rhs.strides);
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2, k3], [od, oh, ow], [ci, z, y, x]| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3] + k3 * rstr[4];
+                let i_lhs = b * lstr[0] + ci * lstr[1] + z * lstr[2] + y * lstr[3] + x * lstr[4];
+                let (w, v) = (rhs.data[rhs.offset + i_rhs], lhs.data[lhs.offset + i_lhs]);
+                out_buf[b * ostr[0] + o * ostr[1] + od * ostr[2] + oh * ostr[3] + ow * ostr[4]] +=
+                    w * v;
+            });
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: Conv3DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_5d::<L>(lhs.strides);
+        let ostr = make_5d::<O>(out.strides());
+        let rstr = make_5d::<R>(rhs.strides);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2, k3], [od, oh, ow], [ci, z, y, x]| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3] + k3 * rstr[4];
+                let i_lhs = b * lstr[0] + ci * lstr[1] + z * lstr[2] + y * lstr[3] + x * lstr[4];
+                let go = grad_out
+                    [b * ostr[0] + o * ostr[1] + od * ostr[2] + oh * ostr[3] + ow * ostr[4]];
+                grad_lhs[i_lhs] += rhs.data[rhs.offset + i_rhs] * go;
+                grad_rhs[i_rhs] += lhs.data[lhs.offset + i_lhs] * go;
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use super::*;
+use crate::{tensor_ops::*, tests::*};
+
+#[test]
+/// Matches
+/// ```python
+/// y = torch.conv3d(x, w)
+/// y.exp().mean().backward()
+/// ```
+fn test_conv3d_default_stride_and_padding() {
+    let dev: TestDevice = Default::default();
+    #[rustfmt::skip]
+    let x = dev.tensor([[[[-0.5241, 0.0885, -0.2601], [0.2078, 0.2514, -0.8689], [-0.9737, 0.6749, -0.4813]], [[-0.5313, 0.9913, -0.0595], [0.6729, -0.0473, 0.2781], [-0.6988, 0.2697, 0.7361]], [[0.0464, 0.4825, 0.3428], [-0.8719, 0.5165, 0.1822], [-0.3975, -0.938, 0.7311]]], [[[-0.0545, 0.4376, 0.7576], [0.4283, 0.8422, -0.2101], [0.6018, -0.1108, 0.8712]], [[0.7577, -0.8051, -0.7281], [-0.566, 0.931, -0.1277], [0.2533, -0.3979, 0.0145]], [[-0.2283, -0.2982, 0.1701], [0.1685, 0.8084, 0.364], [0.8579, 0.7128, 0.982]]]]).to_dtype::<TestDtype>();
+    #[rustfmt::skip]
+    let w = dev.tensor([[[[[0.3425, -0.6738], [0.7213, 0.9293]], [[0.8094, 0.1382], [0.4276, -0.5778]]], [[[0.6632, 0.1471], [-0.4301, -0.8731]], [[0.7079, 0.9796], [-0.823, 0.6012]]]], [[[[-0.1791, -0.6985], [-0.4122, 0.5376]], [[0.7455, -0.9116], [0.2291, -0.9101]]], [[[0.4369, -0.3381], [0.7618, 0.9613]], [[0.0108, 0.997], [-0.3807, -0.8461]]]]]).to_dtype::<TestDtype>();
+    let y = (x.leaky_trace(), w.clone())
+        .conv3d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank4<2, 2, 2, 2>>();
+    #[rustfmt::skip]
+    assert_close_to_literal!(y, [[[[0.04832701, -1.71064], [0.2195961, 1.086057]], [[-1.267261, -0.2634934], [0.3803111, 1.984441]]], [[[-1.42362, -0.4819329], [2.107224, 0.3420649]], [[-1.956011, 0.06427081], [-0.8861794, -1.132384]]]]);
+    let g = y.exp().mean().backward();
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&x), [[[[0.01977024, -0.0577558, -0.03457344], [-0.02430099, -0.3025977, -0.1549736], [-0.1557542, 0.4460037, 0.2193736]], [[0.06875895, 0.01974081, -0.1125378], [0.5135073, -0.1795417, -0.3362583], [0.2063871, 0.004954524, 0.2463019]], [[0.02083489, 0.0829311, -0.05412033], [0.1027544, 0.3897765, -0.04392794], [0.0449943, 0.1227671, -0.2810485]]], [[[0.0500788, 0.02891582, -0.01138865], [0.2594879, -0.01937376, 0.02472965], [0.3581481, 0.4136151, -0.07707828]], [[0.06213108, 0.1482459, 0.03408008], [0.07199667, 1.06187, 0.3254584], [-0.2794748, -0.8093204, -0.340755]], [[0.01255459, 0.06076846, 0.1134919], [0.04714549, 0.3755399, 0.4379715], [-0.08504811, -0.3487114, 0.2563157]]]]);
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&w), [[[[[0.1076125, -0.001734523], [0.13395, 0.3419702]], [[0.1990971, 0.2671799], [-0.4142359, 0.4218397]]], [[[0.5368945, 0.0418389], [-0.05907351, 0.1860263]], [[0.5335627, 0.2301273], [0.3637522, 0.5747101]]]], [[[[0.2022319, 0.05325757], [-0.4381254, 0.3147437]], [[0.3925399, 0.05686785], [-0.3296255, 0.2207087]]], [[[0.2675669, 0.4160774], [0.3941419, 0.01402271]], [[-0.2299931, 0.4640152], [0.2144509, -0.1246449]]]]]);
+}
+
+#[test]
+/// Matches
+/// ```python
+/// y = torch.conv3d(x, w, stride=2, padding=1)
+/// y.exp().mean().backward()
+/// ```
+fn test_conv3d_stride_2_padding_1() {
+    let dev: TestDevice = Default::default();
+    #[rustfmt::skip]
+    let x = dev.tensor([[[[-0.5279, -0.7937, -0.2079], [-0.6901, -0.867, -0.1968], [0.8359, 0.6009, 0.5303]], [[-0.5561, 0.0734, -0.4466], [-0.6547, -0.7876, -0.5712], [0.855, 0.6578, 0.6133]], [[0.6009, -0.6131, -0.3803], [0.254, 0.4638, 0.7093], [0.7601, -0.8266, 0.2117]]]]).to_dtype::<TestDtype>();
+    #[rustfmt::skip]
+    let w = dev.tensor([[[[[0.3434, 0.0119], [-0.6444, -0.0528]], [[-0.8213, 0.8692], [0.731, 0.0953]]]], [[[[-0.3995, 0.8177], [0.1447, 0.7646]], [[0.6961, 0.0167], [-0.1721, 0.1978]]]]]).to_dtype::<TestDtype>();
+    let y = (x.leaky_trace(), w.clone())
+        .conv3d(Const::<2>, Const::<1>, Const::<1>, Const::<1>)
+        .realize::<Rank4<2, 2, 2, 2>>();
+    #[rustfmt::skip]
+    assert_close_to_literal!(y, [[[[-0.05030887, -0.6000076], [-0.5201737, 1.030804]], [[0.08662785, -0.5081372], [0.2402794, -1.081993]]], [[[-0.1044186, 0.09547315], [0.1538163, -0.6053268]], [[-0.306336, -0.3005582], [0.2729744, 0.9305174]]]]);
+    let g = y.exp().mean().backward();
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&x), [[[[0.01680075, 0.01323984, 0.0168698], [0.03350902, -0.120148, 0.1528601], [0.01795859, 0.1222048, 0.02344591]], [[0.03157963, -0.01753402, 0.03339676], [0.06809255, -0.05604222, 0.1298484], [0.05859011, 0.009283347, 0.1200621]], [[0.01559576, 0.01952232, 0.01273663], [0.07045126, 0.0929269, 0.02105857], [0.02381667, -0.01179154, 0.03336778]]]]);
+    #[rustfmt::skip]
+    assert_close_to_literal!(g.get(&w), [[[[[-0.01668331, -0.06413189], [0.01669374, 0.02624861]], [[-0.1420804, -0.02490734], [0.03749529, 0.1770097]]]], [[[[-0.1248258, -0.1442906], [0.1076505, 0.1211589]], [[0.04392637, 0.07625625], [-0.1934522, 0.141023]]]]]);
+}
+
+#[test]
+fn test_batched_conv3d() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<Rank4<3, 7, 8, 9>, TestDtype, _> = dev.sample_normal();
+    let w: Tensor<Rank5<5, 3, 3, 3, 3>, TestDtype, _> = dev.sample_normal();
+
+    let y: Tensor<Rank4<5, 4, 4, 5>, _, _, _> = (x.leaky_trace(), w.clone())
+        .conv3d(Const::<2>, Const::<1>, Const::<1>, Const::<1>)
+        .realize();
+    let y0 = y.retaped::<NoneTape>();
+    let grads0 = y.square().mean().backward();
+    let x0 = grads0.get(&x);
+    let w0 = grads0.get(&w);
+
+    let x = x
+        .broadcast::<Rank5<4, 3, 7, 8, 9>, _>()
+        .reshape::<Rank5<4, 3, 7, 8, 9>>();
+    assert_eq!(x.strides, x.shape.strides());
+
+    let y: Tensor<Rank5<4, 5, 4, 4, 5>, _, _, _> = (x.leaky_trace(), w.clone())
+        .conv3d(Const::<2>, Const::<1>, Const::<1>, Const::<1>)
+        .realize();
+    for i in 0..4 {
+        assert_close_to_tensor!(y0, y.retaped::<NoneTape>().select(dev.tensor(i)));
+    }
+
+    let grads = y.square().mean().backward();
+
+    assert_close_to_tensor!(w0, grads.get(&w), 1e-3);
+
+    let x_grad = grads.get(&x) * 4.0;
+    for i in 0..4 {
+        assert_close_to_tensor!(x0, x_grad.clone().select(dev.tensor(i)));
+    }
+}
+
+#[test]
+fn test_conv3d_grouped() {
+    const NUM_GROUPS: usize = 2;
+    let dev: TestDevice = Default::default();
+    let x: Tensor<Rank5<2, 4, 5, 5, 5>, TestDtype, _> = dev.sample_normal();
+    let w: Tensor<Rank5<6, 2, 2, 2, 2>, TestDtype, _> = dev.sample_normal();
+
+    let y = (x.leaky_trace(), w.clone()).conv3d(
+        Const::<1>,
+        Const::<0>,
+        Const::<2>,
+        Const::<NUM_GROUPS>,
+    );
+    let y_nt = y.retaped::<NoneTape>();
+    let grads = y.exp().sum().backward();
+    let x_grad = grads.get(&x);
+    let w_grad = grads.get(&w);
+
+    for i in 0..NUM_GROUPS {
+        let x_group = x
+            .clone()
+            .slice((.., 2 * i..2 * (i + 1), .., .., ..))
+            .realize::<Rank5<2, 2, 5, 5, 5>>()
+            .contiguous();
+        let w_group = w
+            .clone()
+            .slice((3 * i..3 * (i + 1), .., .., .., ..))
+            .realize::<Rank5<3, 2, 2, 2, 2>>()
+            .contiguous();
+        let y_group = (x_group.leaky_trace(), w_group.clone())
+            .conv3d(Const::<1>, Const::<0>, Const::<2>, Const::<1>)
+            .realize::<Rank5<2, 3, 3, 3, 3>>();
+        let y_group_true = y_nt
+            .clone()
+            .slice((.., 3 * i..3 * (i + 1), .., .., ..))
+            .realize::<Rank5<2, 3, 3, 3, 3>>();
+        assert_close_to_tensor!(y_group.retaped::<NoneTape>(), y_group_true);
+
+        let grads = y_group.exp().sum().backward();
+        let x_grad_group_true = x_grad
+            .clone()
+            .slice((.., 2 * i..2 * (i + 1), .., .., ..))
+            .realize::<Rank5<2, 2, 5, 5, 5>>();
+        let w_grad_group_true = w_grad
+            .clone()
+            .slice((3 * i..3 * (i + 1), .., .., .., ..))
+            .realize::<Rank5<3, 2, 2, 2, 2>>();
+        assert_close_to_tensor!(grads.get(&x_group), x_grad_group_true);
+        assert_close_to_tensor!(grads.get(&w_group), w_grad_group_true);
+    }
+}
+
+#[test]
+fn test_conv3d_sliced_input() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<Rank5<1, 2, 5, 5, 5>, TestDtype, _> = dev.sample_normal();
+    let w: Tensor<Rank5<2, 2, 2, 2, 2>, TestDtype, _> = dev.sample_normal();
+
+    let x_view = x
+        .leaky_trace()
+        .slice((.., .., 1..4, 1..4, 1..4))
+        .realize::<Rank5<1, 2, 3, 3, 3>>();
+    let y = (x_view, w.clone())
+        .conv3d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank5<1, 2, 2, 2, 2>>();
+
+    let x_copy = x
+        .clone()
+        .slice((.., .., 1..4, 1..4, 1..4))
+        .realize::<Rank5<1, 2, 3, 3, 3>>()
+        .contiguous();
+    let y_true = (x_copy.leaky_trace(), w.clone())
+        .conv3d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .realize::<Rank5<1, 2, 2, 2, 2>>();
+    assert_close_to_tensor!(y, y_true);
+
+    let g = y.square().sum().backward();
+    let g_true = y_true.square().sum().backward();
+    let x_grad = g
+        .get(&x)
+        .slice((.., .., 1..4, 1..4, 1..4))
+        .realize::<Rank5<1, 2, 3, 3, 3>>();
+    assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+}
+
+#[test]
+fn test_try_conv3d_shape_errors() {
+    let dev: TestDevice = Default::default();
+    let x: Tensor<(Const<1>, usize, Const<4>, Const<4>, Const<4>), TestDtype, _> =
+        dev.zeros_like(&(Const, 4, Const, Const, Const));
+    let w: Tensor<Rank5<2, 3, 2, 2, 2>, TestDtype, _> = dev.zeros();
+    let err = (x.clone(), w.clone())
+        .try_conv3d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv3d", &x.shape, &w.shape))
+    );
+
+    // with `nightly`, a `usize` kernel only convolves `usize` image dims
+    let x2: Tensor<(Const<1>, usize, usize, usize, usize), TestDtype, _> =
+        dev.zeros_like(&(Const, 4, 4, 4, 4));
+    let w: Tensor<(Const<2>, Const<4>, usize, usize, usize), TestDtype, _> =
+        dev.zeros_like(&(Const, Const, 2, 2, 3));
+    let err = (x2.clone(), w.clone())
+        .try_conv3d(Const::<1>, Const::<0>, Const::<1>, Const::<1>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv3d", &x2.shape, &w.shape))
+    );
+
+    let w: Tensor<Rank5<3, 2, 2, 2, 2>, TestDtype, _> = dev.zeros();
+    let err = (x.clone(), w.clone())
+        .try_conv3d(Const::<1>, Const::<0>, Const::<1>, Const::<2>)
+        .unwrap_err();
+    assert_eq!(
+        shape_error(err),
+        Some(ShapeError::new("conv3d", &x.shape, &w.shape))
+    );
+}
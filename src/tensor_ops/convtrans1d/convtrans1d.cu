@@ -0,0 +1,193 @@
+#include "cuda_fp16.h"
+
+struct ConvTrans1DOp {
+    size_t stride;
+    size_t padding;
+    size_t kernel;
+    size_t batch;
+    size_t chan_in;
+    size_t chan_out;
+    size_t l_in;
+    size_t l_out;
+};
+
+template<typename T>
+__device__ void unfold_input_into_patches(
+    const ConvTrans1DOp op,
+    const T *image, // 3d (Batch, Channels, Length)
+    const size_t *strides, // 3d image strides
+    T *patches // 4d (Batch, Channels, KernelSize, LengthOut)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= op.batch * op.chan_in * op.l_out) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t ol = idx % op.l_out;
+    idx /= op.l_out;
+    const size_t c = idx % op.chan_in;
+    idx /= op.chan_in;
+    const size_t b = idx % op.batch;
+
+    image += b * strides[0] + c * strides[1];
+    patches += ol;
+    patches += c * (op.kernel * op.l_out);
+    patches += b * (op.chan_in * op.kernel * op.l_out);
+
+    T zero = 0.0;
+
+    for (int k = 0;k < op.kernel;k++) {
+        const size_t x_ks = ol + op.padding;
+        const size_t x_s = x_ks - k;
+        const size_t x = x_s / op.stride;
+        const bool invalid = (x_ks < k || x_s % op.stride != 0 || x >= op.l_in);
+        *patches = invalid ? zero : image[x * strides[2]];
+        patches += op.l_out;
+    }
+}
+
+template<typename T>
+__device__ void unfold_output_into_patches(
+    const ConvTrans1DOp op,
+    const T *image_out, // 3d (Batch, ChanOut, LengthOut)
+    T *patches // 4d (Batch, ChanOut, KernelSize, LengthIn)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= op.batch * op.chan_out * op.l_in) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t x = idx % op.l_in;
+    idx /= op.l_in;
+    const size_t o = idx % op.chan_out;
+    idx /= op.chan_out;
+    const size_t b = idx % op.batch;
+
+    image_out += b * (op.chan_out * op.l_out) + o * op.l_out;
+    patches += x;
+    patches += o * (op.kernel * op.l_in);
+    patches += b * (op.chan_out * op.kernel * op.l_in);
+
+    T zero = 0.0;
+
+    for (int k = 0;k < op.kernel;k++) {
+        const size_t ol = x * op.stride + k - op.padding;
+        *patches = (ol >= op.l_out) ? zero : image_out[ol];
+        patches += op.l_in;
+    }
+}
+
+template<typename T>
+__device__ void transpose_filters(
+    const ConvTrans1DOp op,
+    const T *filters, // 3d (ChanOut, ChanIn, KernelSize)
+    const size_t *strides, // 3d filters strides
+    T *filters_tr // 3d (ChanIn, ChanOut, KernelSize)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= op.chan_in * op.chan_out * op.kernel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t k = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t o = idx % op.chan_out;
+    idx /= op.chan_out;
+    const size_t c = idx % op.chan_in;
+
+    auto i_no = o * strides[0] + c * strides[1] + k * strides[2];
+
+    filters_tr[i] = filters[i_no];
+}
+
+template<typename T>
+__device__ void sum_transposed_filters(
+    const ConvTrans1DOp op,
+    const T *filters_tr, // 4d (Batch, ChanIn, ChanOut, KernelSize)
+    T *filters, // 3d (ChanOut, ChanIn, KernelSize)
+    const size_t *strides // 3d filter strides
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    auto numel = op.chan_out * op.chan_in * op.kernel;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t k = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t c = idx % op.chan_in;
+    idx /= op.chan_in;
+    const size_t o = idx % op.chan_out;
+
+    auto i_tr = c * (op.chan_out * op.kernel) + o * op.kernel + k;
+    auto i_no = o * strides[0] + c * strides[1] + k * strides[2];
+
+    filters_tr += i_tr;
+
+    T tmp = 0.0;
+    for (int b = 0; b < op.batch; b++) {
+        tmp += *filters_tr;
+        filters_tr += numel;
+    }
+
+    filters[i_no] += tmp;
+}
+
+#define CONV_OP(TYPENAME, UNFOLD_INPUT, UNFOLD_OUTPUT, TR_FILTERS, SUM_TR_FILTERS) \
+extern "C" __global__ void UNFOLD_INPUT( \
+    const ConvTrans1DOp op, \
+    const TYPENAME *image, \
+    const size_t *strides, \
+    TYPENAME *patches \
+) { \
+    unfold_input_into_patches(op, image, strides, patches); \
+} \
+extern "C" __global__ void UNFOLD_OUTPUT( \
+    const ConvTrans1DOp op, \
+    const TYPENAME *image_out, \
+    TYPENAME *patches \
+) { \
+    unfold_output_into_patches(op, image_out, patches); \
+} \
+extern "C" __global__ void TR_FILTERS( \
+    const ConvTrans1DOp op, \
+    const TYPENAME *filters, \
+    const size_t *strides, \
+    TYPENAME *filters_tr \
+) { \
+    transpose_filters(op, filters, strides, filters_tr); \
+} \
+extern "C" __global__ void SUM_TR_FILTERS( \
+    const ConvTrans1DOp op, \
+    const TYPENAME *filters_tr, \
+    TYPENAME *filters, \
+    const size_t *strides \
+) { \
+    sum_transposed_filters(op, filters_tr, filters, strides); \
+}
+
+CONV_OP(
+    __half,
+    unfold_input_into_patches_f16,
+    unfold_output_into_patches_f16,
+    transpose_filters_f16,
+    sum_transposed_filters_f16
+);
+CONV_OP(
+    float,
+    unfold_input_into_patches_f32,
+    unfold_output_into_patches_f32,
+    transpose_filters_f32,
+    sum_transposed_filters_f32
+);
+CONV_OP(
+    double,
+    unfold_input_into_patches_f64,
+    unfold_output_into_patches_f64,
+    transpose_filters_f64,
+    sum_transposed_filters_f64
+);
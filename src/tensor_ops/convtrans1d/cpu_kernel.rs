@@ -0,0 +1,228 @@
+use crate::prelude::Tensorlike;
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::{cpu::*, Tensor};
+use crate::tensor_ops::matmul::cpu_kernel::MatMulImpl;
+
+use std::sync::Arc;
+
+use super::{ConvTrans1DKernel, ConvTrans1DOp};
+
+impl ConvTrans1DOp {
+    #[inline(always)]
+    fn unfold_idx(&self, [k, x]: [usize; 2]) -> Option<usize> {
+        (x * self.stride + k)
+            .checked_sub(self.padding)
+            .filter(|&ol| ol < self.l_out)
+    }
+}
+
+impl Cpu {
+    #[inline]
+    fn convtrans1d_forward<E: Dtype>(
+        &self,
+        op: &ConvTrans1DOp,
+        img: &[E],
+        filters: &[E],
+        out: &mut [E],
+        buf: &mut [E],
+    ) -> Result<(), CpuError>
+    where
+        Self: MatMulImpl<E>,
+    {
+        {
+            let mut i = 0;
+            for c in 0..op.chan_in {
+                for k in 0..op.kernel {
+                    for ol in 0..op.l_out {
+                        i += 1;
+                        let mut x = ol + op.padding;
+                        if x < k {
+                            continue;
+                        }
+                        x -= k;
+                        if x % op.stride != 0 {
+                            continue;
+                        }
+                        x /= op.stride;
+                        if x >= op.l_in {
+                            continue;
+                        }
+                        buf[i - 1] = img[c * op.l_in + x];
+                    }
+                }
+            }
+        }
+
+        // (O, C * K) * (C * K, OL) = (O, OL)
+        let m = op.chan_out;
+        let k = op.chan_in * op.kernel;
+        let n = op.l_out;
+        self.matmul(
+            (m, k, n),
+            false,
+            filters.as_ptr(),
+            [k, 1],
+            buf.as_ptr(),
+            [n, 1],
+            out.as_mut_ptr(),
+            [n, 1],
+        );
+        Ok(())
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn convtrans1d_backward<E: Dtype>(
+        &self,
+        op: &ConvTrans1DOp,
+        img: &[E],
+        grad_img: &mut [E],
+        filters_tr: &[E],
+        grad_filters_tr: &mut [E],
+        grad_out: &[E],
+        buf: &mut [E],
+    ) -> Result<(), CpuError>
+    where
+        Self: MatMulImpl<E>,
+    {
+        {
+            let mut i = 0;
+            for o in 0..op.chan_out {
+                for k in 0..op.kernel {
+                    for x in 0..op.l_in {
+                        if let Some(ol) = op.unfold_idx([k, x]) {
+                            buf[i] = grad_out[o * op.l_out + ol];
+                        }
+                        i += 1;
+                    }
+                }
+            }
+        }
+
+        {
+            // img_g += filters^T * unfold(grad_out)
+            // (C, L) += (C, O * K) * (O * K, L)
+            let m = op.chan_in;
+            let k = op.chan_out * op.kernel;
+            let n = op.l_in;
+            self.matmul(
+                (m, k, n),
+                true,
+                filters_tr.as_ptr(),
+                [k, 1],
+                buf.as_ptr(),
+                [n, 1],
+                grad_img.as_mut_ptr(),
+                [n, 1],
+            );
+        }
+
+        {
+            // weight_g^T += img * patches^T
+            // (C, O * K) += (C, L) * (L, O * K)
+            let m = op.chan_in;
+            let k = op.l_in;
+            let n = op.chan_out * op.kernel;
+            self.matmul(
+                (m, k, n),
+                true,
+                img.as_ptr(),
+                [k, 1],
+                buf.as_ptr(),
+                [1, k],
+                grad_filters_tr.as_mut_ptr(),
+                [n, 1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> ConvTrans1DKernel<E> for Cpu
+where
+    Self: MatMulImpl<E>,
+{
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let mut patches = self.try_alloc_zeros::<E>(op.inp_patches_shape().num_elements())?;
+        let [lstride, ostride] = match L::NUM_DIMS {
+            2 => [0; 2],
+            3 => [lhs.strides[0], out.strides[0]],
+            _ => unreachable!(),
+        };
+        let lhs = &lhs.data[lhs.offset..];
+        let rhs = &rhs.data[rhs.offset..];
+        let out = Arc::make_mut(&mut out.data);
+        for i_batch in 0..op.batch {
+            self.convtrans1d_forward(
+                &op,
+                &lhs[i_batch * lstride..],
+                rhs,
+                &mut out[i_batch * ostride..],
+                &mut patches,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let f_tr_shape = op.filters_tr_shape();
+        let mut patches = self.try_alloc_zeros::<E>(op.out_patches_shape().num_elements())?;
+        let mut f102 = self.try_alloc_zeros::<E>(f_tr_shape.num_elements())?;
+        let mut grad_f102 = self.try_alloc_zeros::<E>(f_tr_shape.num_elements())?;
+
+        {
+            // transpose filters in f102
+            let buf = &rhs.data[rhs.offset..];
+            let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
+            while let Some((i, [c, o, k])) = f_idx.next_with_idx() {
+                let idx = o * rhs.strides[0] + c * rhs.strides[1] + k * rhs.strides[2];
+                f102[i] = buf[idx];
+            }
+        }
+
+        let [lstride, ostride] = match L::NUM_DIMS {
+            2 => [0; 2],
+            3 => [lhs.strides[0], out.strides()[0]],
+            _ => unreachable!(),
+        };
+        let lhs = &lhs.data[lhs.offset..];
+
+        for i_batch in 0..op.batch {
+            self.convtrans1d_backward(
+                &op,
+                &lhs[i_batch * lstride..],
+                &mut grad_lhs[i_batch * lstride..],
+                &f102,
+                &mut grad_f102,
+                &grad_out[i_batch * ostride..],
+                &mut patches,
+            )?;
+        }
+
+        {
+            // untranspose filters
+            let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
+            while let Some((i, [c, o, k])) = f_idx.next_with_idx() {
+                let idx = o * rhs.strides[0] + c * rhs.strides[1] + k * rhs.strides[2];
+                grad_rhs[idx] += grad_f102[i];
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use cudarc::cublas::{CudaBlas, Gemm};
+use cudarc::driver::{DeviceRepr, LaunchAsync, ValidAsZeroBits};
+
+use crate::{
+    shapes::*,
+    tensor::{launch_cfg, Cuda, Tensor, Tensorlike},
+};
+
+use std::sync::Arc;
+
+unsafe impl DeviceRepr for super::ConvTrans1DOp {}
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/convtrans1d.ptx"));
+
+trait HasCudaKernel<E> {
+    const MOD: &'static str;
+    const FNS: &'static [&'static str];
+}
+
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const MOD: &'static str = "convtrans1d_f16";
+    const FNS: &'static [&'static str] = &[
+        "unfold_input_into_patches_f16",
+        "unfold_output_into_patches_f16",
+        "transpose_filters_f16",
+        "sum_transposed_filters_f16",
+    ];
+}
+
+impl HasCudaKernel<f32> for Cuda {
+    const MOD: &'static str = "convtrans1d_f32";
+    const FNS: &'static [&'static str] = &[
+        "unfold_input_into_patches_f32",
+        "unfold_output_into_patches_f32",
+        "transpose_filters_f32",
+        "sum_transposed_filters_f32",
+    ];
+}
+
+impl HasCudaKernel<f64> for Cuda {
+    const MOD: &'static str = "convtrans1d_f64";
+    const FNS: &'static [&'static str] = &[
+        "unfold_input_into_patches_f64",
+        "unfold_output_into_patches_f64",
+        "transpose_filters_f64",
+        "sum_transposed_filters_f64",
+    ];
+}
+
+fn make_3d<S: Shape>(strides: S::Concrete) -> [usize; 3] {
+    match S::NUM_DIMS {
+        2 => [0, strides[0], strides[1]],
+        3 => [strides[0], strides[1], strides[2]],
+        _ => unreachable!("Only implemented for 2d & 3d arrays"),
+    }
+}
+
+impl<E: Dtype + ValidAsZeroBits> super::ConvTrans1DKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+    CudaBlas: Gemm<E>,
+{
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: super::ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        if !self.dev.has_func(Self::MOD, Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::MOD, Self::FNS)?;
+        }
+
+        let patches_numel = op.batch * op.chan_in * op.kernel * op.l_out;
+        let mut patches = unsafe { self.get_workspace::<E>(patches_numel) }?;
+        let mut patches = unsafe { patches.transmute_mut::<E>(patches_numel).unwrap() };
+
+        let img_strides = self.dev.htod_copy(make_3d::<L>(lhs.strides).into())?;
+        let unfold_fn = self.dev.get_func(Self::MOD, Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>((op.batch * op.chan_in * op.l_out) as u32);
+        let params = (op, lhs.data.as_ref(), &img_strides, &mut patches);
+        unsafe { unfold_fn.launch(cfg, params) }?;
+
+        // (O, C * K) * (B, C * K, OL) = (B, O, OL)
+        let m = op.chan_out;
+        let k = op.chan_in * op.kernel;
+        let n = op.l_out;
+        unsafe {
+            self.gemm_batch(
+                (op.batch, m, k, n),
+                rhs.data.as_ref(),
+                [0, k, 1],
+                &patches,
+                [k * n, n, 1],
+                Default::default(),
+                Arc::get_mut(&mut out.data).unwrap(),
+                [m * n, n, 1],
+            )
+            .unwrap();
+        }
+
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: super::ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        _: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let patches_numel = op.batch * op.chan_out * op.kernel * op.l_in;
+        let filters_numel = op.chan_in * op.chan_out * op.kernel;
+
+        let mut patches = unsafe { self.get_workspace::<E>(patches_numel) }?;
+        let mut patches = unsafe { patches.transmute_mut::<E>(patches_numel).unwrap() };
+
+        let mut f_b102 = unsafe { self.alloc_empty::<E>(filters_numel) }?;
+        let mut grad_f_b102 = unsafe { self.alloc_empty::<E>(op.batch * filters_numel) }?;
+        let f_strides = self.dev.htod_copy(rhs.strides.into())?;
+
+        self.par_stream.wait_for_default()?;
+
+        {
+            // unfold grad_out into patches
+            let unfold_fn = self.dev.get_func(Self::MOD, Self::FNS[1]).unwrap();
+            let cfg = launch_cfg::<128>((op.batch * op.chan_out * op.l_in) as u32);
+            unsafe { unfold_fn.launch(cfg, (op, grad_out, &mut patches)) }?;
+        }
+
+        {
+            // prepare filters for backward operations by
+            // swapping dims 0 and 1 and adding a batch dimension
+            let tr_fn = self.dev.get_func(Self::MOD, Self::FNS[2]).unwrap();
+            let cfg = launch_cfg::<128>(rhs.shape.num_elements() as u32);
+            unsafe {
+                tr_fn.launch_on_stream(
+                    self.par_stream.as_ref(),
+                    cfg,
+                    (op, rhs.data.as_ref(), &f_strides, &mut f_b102),
+                )
+            }?;
+
+            self.par_stream.wait_for_default()?;
+
+            // img_g += filters * patches
+            // (B, C, L) += (B, C, O * K) * (B, O * K, L)
+            let m = op.chan_in;
+            let k = op.chan_out * op.kernel;
+            let n = op.l_in;
+            unsafe {
+                self.blas.set_stream(Some(self.par_stream.as_ref()))?;
+                self.gemm_batch(
+                    (op.batch, m, k, n),
+                    &f_b102,
+                    [0, k, 1],
+                    &patches,
+                    [k * n, n, 1],
+                    <E>::ONE,
+                    grad_lhs,
+                    [m * n, n, 1],
+                )
+                .unwrap();
+                self.blas.set_stream(None)?;
+            }
+        }
+
+        {
+            // weight_g += img * patches^T
+            // (B, C, O * K) += (B, C, L) * (B, L, O * K)
+            let m = op.chan_in;
+            let k = op.l_in;
+            let n = op.chan_out * op.kernel;
+            unsafe {
+                self.gemm_batch(
+                    (op.batch, m, k, n),
+                    lhs.data.as_ref(),
+                    [m * k, k, 1],
+                    &patches,
+                    [k * n, 1, k],
+                    Default::default(),
+                    &mut grad_f_b102,
+                    [m * n, n, 1],
+                )
+                .unwrap();
+            }
+
+            // sum all the gradients collected in our broadcasted grad_f
+            // into grad_rhs
+            let sum_fn = self.dev.get_func(Self::MOD, Self::FNS[3]).unwrap();
+            let cfg = launch_cfg::<128>(rhs.shape.num_elements() as u32);
+            unsafe { sum_fn.launch(cfg, (op, &grad_f_b102, grad_rhs, &f_strides)) }?;
+        }
+
+        self.dev.wait_for(self.par_stream.as_ref())?;
+
+        Ok(())
+    }
+}
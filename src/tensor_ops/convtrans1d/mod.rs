@@ -0,0 +1,322 @@
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{shapes::*, tensor::*, tensor_ops::ReshapeTo};
+
+use super::ConvTransAlgebra;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(super) struct ConvTrans1DOp {
+    pub stride: usize,
+    pub padding: usize,
+    pub kernel: usize,
+    pub batch: usize,
+    pub chan_in: usize,
+    pub chan_out: usize,
+    pub l_in: usize,
+    pub l_out: usize,
+}
+
+impl ConvTrans1DOp {
+    fn new(s: usize, p: usize, k: usize, [b, c, l_in]: [usize; 3], o: usize) -> Self {
+        Self {
+            stride: s,
+            padding: p,
+            kernel: k,
+            batch: b,
+            chan_in: c,
+            chan_out: o,
+            l_in,
+            l_out: (l_in - 1) * s - 2 * p + k,
+        }
+    }
+
+    pub(super) fn inp_patches_shape(&self) -> (usize, usize, usize) {
+        (self.chan_in, self.kernel, self.l_out)
+    }
+
+    pub(super) fn out_patches_shape(&self) -> (usize, usize, usize) {
+        (self.chan_out, self.kernel, self.l_in)
+    }
+
+    pub(super) fn filters_tr_shape(&self) -> (usize, usize, usize) {
+        (self.chan_in, self.chan_out, self.kernel)
+    }
+}
+
+pub(super) trait ConvTrans1DKernel<E: Dtype>: DeviceStorage {
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+pub trait TryConvTrans1DTo<F, const S: usize, const P: usize>: HasErr {
+    type Output;
+    fn convtrans1d_to(self, filters: F) -> Self::Output {
+        self.try_convtrans1d_to(filters).unwrap()
+    }
+    fn try_convtrans1d_to(self, filters: F) -> Result<Self::Output, Self::Err>;
+}
+
+pub trait TryConvTrans1D<F> {
+    fn convtrans1d<const S: usize, const P: usize>(self, filters: F) -> Self::Output
+    where
+        Self: TryConvTrans1DTo<F, S, P>,
+    {
+        self.convtrans1d_to(filters)
+    }
+    fn try_convtrans1d<const S: usize, const P: usize>(
+        self,
+        filters: F,
+    ) -> Result<Self::Output, Self::Err>
+    where
+        Self: TryConvTrans1DTo<F, S, P>,
+    {
+        self.try_convtrans1d_to(filters)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, T, F> TryConvTrans1D<F> for Tensor<S, E, D, T> {}
+
+impl<
+        const C: usize,
+        L: Dim + ConvTransAlgebra<K, S, P>,
+        const O: usize,
+        const K: usize,
+        const S: usize,
+        const P: usize,
+        E: Dtype,
+        D: ConvTrans1DKernel<E> + ZerosTensor<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+        T: 'static + Tape<E, D>,
+    > TryConvTrans1DTo<Tensor<Rank3<O, C, K>, E, D>, S, P> for Tensor<(Const<C>, L), E, D, T>
+{
+    type Output = Tensor<(Const<O>, L::Convolved), E, D, T>;
+
+    fn try_convtrans1d_to(
+        self,
+        filters: Tensor<Rank3<O, C, K>, E, D>,
+    ) -> Result<Self::Output, Self::Err> {
+        let l = self.shape.1;
+
+        let op = ConvTrans1DOp::new(S, P, K, [1, C, l.size()], O);
+        // the kernels only handle contiguous inputs, so views are copied first
+        let (lhs, ltape) = self.try_contiguous()?.split_tape();
+        let (rhs, rtape) = filters.try_contiguous()?.split_tape();
+        let mut tape = ltape.merge(rtape);
+        let mut out = lhs.device.try_zeros_like(&(Const, l.convolve_dim()))?;
+        ConvTrans1DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
+        let lhs_ghost = lhs.ghost();
+        let rhs_ghost = rhs.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&rhs_ghost)?;
+            grads.try_alloc_for(&lhs_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_lhs, grad_rhs, grad_out) =
+                grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
+            ConvTrans1DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+impl<
+        B: Dim,
+        const C: usize,
+        L: Dim + ConvTransAlgebra<K, S, P>,
+        const O: usize,
+        const K: usize,
+        const S: usize,
+        const P: usize,
+        E: Dtype,
+        D: ConvTrans1DKernel<E> + ZerosTensor<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+        T: 'static + Tape<E, D>,
+    > TryConvTrans1DTo<Tensor<Rank3<O, C, K>, E, D>, S, P> for Tensor<(B, Const<C>, L), E, D, T>
+{
+    type Output = Tensor<(B, Const<O>, L::Convolved), E, D, T>;
+    fn try_convtrans1d_to(
+        self,
+        filters: Tensor<Rank3<O, C, K>, E, D>,
+    ) -> Result<Self::Output, Self::Err> {
+        let l = self.shape.2;
+
+        let batch = self.shape().0;
+        let op = ConvTrans1DOp::new(S, P, K, [batch.size(), C, l.size()], O);
+        // the kernels only handle contiguous inputs, so views are copied first
+        let (lhs, ltape) = self.try_contiguous()?.split_tape();
+        let (rhs, rtape) = filters.try_contiguous()?.split_tape();
+        let mut out = lhs
+            .device
+            .try_zeros_like(&(batch, Const, l.convolve_dim()))?;
+        let mut tape = ltape.merge(rtape);
+        ConvTrans1DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
+        let lhs_ghost = lhs.ghost();
+        let rhs_ghost = rhs.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&rhs_ghost)?;
+            grads.try_alloc_for(&lhs_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_lhs, grad_rhs, grad_out) =
+                grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
+            ConvTrans1DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    /// Matches
+    /// ```python
+    /// y = torch.conv_transpose1d(x, torch.swapaxes(w, 0, 1))
+    /// y.exp().mean().backward()
+    /// ```
+    fn convtrans1d_test() {
+        let dev: TestDevice = Default::default();
+        #[rustfmt::skip]
+        let x = dev.tensor([[0.2458, 0.4836, 0.5904, 0.8849], [0.4798, 0.8446, -0.942, -0.0688]]).to_dtype::<TestDtype>();
+        #[rustfmt::skip]
+        let w = dev.tensor([[[0.8867, 0.2979, 0.8018], [-0.7736, -0.0619, -0.5069]], [[0.0875, 0.1479, -0.9738], [-0.5665, -0.441, 0.8327]], [[0.5315, -0.6808, 0.5943], [-0.7225, 0.2349, -0.7466]]]).to_dtype::<TestDtype>();
+        let y = x
+            .leaky_trace()
+            .convtrans1d::<1, 0>(w.clone())
+            .realize::<Rank2<3, 6>>();
+        #[rustfmt::skip]
+        assert_close_to_literal!(y, [[-0.1532224, -0.1810502, 1.297894, 1.031677, 1.218753, 0.7443875], [-0.2502992, -0.6113889, 0.4445283, 0.8515148, -1.198117, -0.9190054], [-0.2160128, -0.4078257, 0.6514145, -0.4463627, 0.4355709, 0.5772622]]);
+        let g = y.exp().mean().backward();
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&x), [[0.2050058, 0.08358574, 0.4714873, 0.2995017], [-0.211723, -0.1025644, -0.4875841, -0.3335791]]);
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&w), [[[0.2921674, 0.3681081, 0.3398354], [-0.1403265, 0.034285, 0.04416601]], [[0.1915635, 0.1410066, 0.113762], [-0.04437034, -0.03613019, 0.134207]], [[0.1232509, 0.1576052, 0.1816555], [-0.0501498, 0.06833777, -0.006547174]]]);
+    }
+
+    #[test]
+    /// Matches
+    /// ```python
+    /// y = torch.conv_transpose1d(x, torch.swapaxes(w, 0, 1), stride=2, padding=1)
+    /// y.exp().mean().backward()
+    /// ```
+    fn convtrans1d_s2p1() {
+        let dev: TestDevice = Default::default();
+        #[rustfmt::skip]
+        let x = dev.tensor([[0.5867, 0.6439, -0.0299, -0.4768], [-0.9991, 0.3256, -0.0595, 0.5195]]).to_dtype::<TestDtype>();
+        #[rustfmt::skip]
+        let w = dev.tensor([[[-0.2537, 0.5403, -0.4546], [0.6038, 0.4596, -0.172]], [[0.0766, 0.3641, -0.614], [0.1072, 0.6102, -0.469]]]).to_dtype::<TestDtype>();
+        let y = x
+            .leaky_trace()
+            .convtrans1d::<2, 1>(w.clone())
+            .realize::<Rank2<2, 7>>();
+        #[rustfmt::skip]
+        assert_close_to_literal!(y, [[-0.1421924, -0.06162877, 0.4975449, -0.3770606, -0.04350117, 0.4584648, -0.01885284], [-0.3960333, 0.1925712, 0.4331251, -0.5567297, -0.04719349, 0.06543162, 0.143396]]);
+        let g = y.exp().mean().backward();
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&x), [[-0.0327215, 0.04576769, -0.04571615, 0.04506895], [0.005644215, 0.1434141, 0.0517797, 0.1589105]]);
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&w), [[[-0.01208716, 0.07653011, 0.06756982], [0.07764243, 0.008690298, -0.05786948]], [[0.01817601, 0.05778144, 0.07488412], [0.06537682, 0.02661157, -0.07772858]]]);
+    }
+
+    #[test]
+    fn test_batched_convtrans1d() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<3, 28>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank3<5, 3, 6>, TestDtype, _> = dev.sample_normal();
+
+        let y: Tensor<Rank2<5, 83>, _, _, _> =
+            x.leaky_trace().convtrans1d::<3, 2>(w.clone()).realize();
+        let y0 = y.retaped::<NoneTape>();
+        let grads0 = y.square().mean().backward();
+        let x0 = grads0.get(&x);
+        let w0 = grads0.get(&w);
+
+        let x = x
+            .broadcast::<Rank3<10, 3, 28>, _>()
+            .reshape::<Rank3<10, 3, 28>>();
+
+        let y: Tensor<Rank3<10, 5, 83>, _, _, _> =
+            x.leaky_trace().convtrans1d::<3, 2>(w.clone()).realize();
+        for i in 0..10 {
+            assert_close_to_tensor!(y0, y.retaped::<NoneTape>().select(dev.tensor(i)), 1e-5);
+        }
+
+        let grads = y.square().mean().backward();
+
+        assert_close_to_tensor!(w0, grads.get(&w));
+
+        let x_grad = grads.get(&x) * 10.0;
+        for i in 0..10 {
+            assert_close_to_tensor!(x0, x_grad.clone().select(dev.tensor(i)));
+        }
+    }
+
+    #[test]
+    fn test_convtrans1d_sliced_input() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank3<4, 3, 2>, TestDtype, _> = dev.sample_normal();
+
+        let x_view = x
+            .leaky_trace()
+            .slice((.., .., 1..7))
+            .realize::<Rank3<2, 3, 6>>();
+        let y: Tensor<Rank3<2, 4, 7>, _, _, _> = x_view.convtrans1d::<1, 0>(w.clone()).realize();
+
+        let x_copy = x
+            .clone()
+            .slice((.., .., 1..7))
+            .realize::<Rank3<2, 3, 6>>()
+            .contiguous();
+        let y_true: Tensor<Rank3<2, 4, 7>, _, _, _> = x_copy
+            .leaky_trace()
+            .convtrans1d::<1, 0>(w.clone())
+            .realize();
+        assert_close_to_tensor!(y, y_true);
+
+        let g = y.square().sum().backward();
+        let g_true = y_true.square().sum().backward();
+        let x_grad = g.get(&x).slice((.., .., 1..7)).realize::<Rank3<2, 3, 6>>();
+        assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+    }
+}
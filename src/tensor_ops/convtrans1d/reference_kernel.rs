@@ -0,0 +1,83 @@
+use crate::prelude::Tensorlike;
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::{Reference, Tensor};
+
+use std::sync::Arc;
+
+use super::{ConvTrans1DKernel, ConvTrans1DOp};
+
+fn make_3d<S: Shape>(strides: S::Concrete) -> [usize; 3] {
+    match S::NUM_DIMS {
+        2 => [0, strides[0], strides[1]],
+        3 => [strides[0], strides[1], strides[2]],
+        _ => panic!("Only implemented for 2d & 3d arrays"),
+    }
+}
+
+impl ConvTrans1DOp {
+    /// Calls `f([o, c, k], x, ol)` for every multiply-add of the transposed
+    /// convolution, where input element `(c, x)` is scattered to output element `(o, ol)`.
+    fn for_each_tap(&self, mut f: impl FnMut([usize; 3], usize, usize)) {
+        for o in 0..self.chan_out {
+            for c in 0..self.chan_in {
+                for k in 0..self.kernel {
+                    for x in 0..self.l_in {
+                        if let Some(ol) = (x * self.stride + k).checked_sub(self.padding) {
+                            if ol < self.l_out {
+                                f([o, c, k], x, ol);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Dtype> ConvTrans1DKernel<E> for Reference {
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_3d::<L>(lhs.strides);
+        let ostr = make_3d::<O>(out.strides);
+        let rstr = rhs.strides;
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k], x, ol| {
+                let w = rhs.data[rhs.offset + o * rstr[0] + c * rstr[1] + k * rstr[2]];
+                let v = lhs.data[lhs.offset + b * lstr[0] + c * lstr[1] + x * lstr[2]];
+                out_buf[b * ostr[0] + o * ostr[1] + ol * ostr[2]] += w * v;
+            });
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans1DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_3d::<L>(lhs.strides);
+        let ostr = make_3d::<O>(out.strides());
+        let rstr = rhs.strides;
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k], x, ol| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k * rstr[2];
+                let i_lhs = b * lstr[0] + c * lstr[1] + x * lstr[2];
+                let go = grad_out[b * ostr[0] + o * ostr[1] + ol * ostr[2]];
+                grad_lhs[i_lhs] += rhs.data[rhs.offset + i_rhs] * go;
+                grad_rhs[i_rhs] += lhs.data[lhs.offset + i_lhs] * go;
+            });
+        }
+        Ok(())
+    }
+}
@@ -79,7 +79,7 @@ impl Cpu {
         let m = op.chan_out;
         let k = op.chan_in * op.kernel * op.kernel;
         let n = op.w_out * op.h_out;
-        Self::matmul(
+        self.matmul(
             (m, k, n),
             false,
             filters.as_ptr(),
@@ -132,7 +132,7 @@ impl Cpu {
             let m = op.chan_in;
             let k = op.chan_out * op.kernel * op.kernel;
             let n = op.h_in * op.w_in;
-            Self::matmul(
+            self.matmul(
                 (m, k, n),
                 true,
                 filters_tr.as_ptr(),
@@ -150,7 +150,7 @@ impl Cpu {
             let m = op.chan_in;
             let k = op.h_in * op.w_in;
             let n = op.chan_out * op.kernel * op.kernel;
-            Self::matmul(
+            self.matmul(
                 (m, k, n),
                 true,
                 img.as_ptr(),
@@ -182,8 +182,8 @@ where
             4 => [lhs.strides[0], out.strides[0]],
             _ => unreachable!(),
         };
-        let lhs = lhs.data.as_ref();
-        let rhs = rhs.data.as_ref();
+        let lhs = &lhs.data[lhs.offset..];
+        let rhs = &rhs.data[rhs.offset..];
         let out = Arc::make_mut(&mut out.data);
         for i_batch in 0..op.batch {
             self.convtrans2d_forward(
@@ -214,7 +214,7 @@ where
 
         {
             // transpose filters in f1023
-            let buf = rhs.data.as_ref();
+            let buf = &rhs.data[rhs.offset..];
             let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
             while let Some((i, [c, o, k1, k2])) = f_idx.next_with_idx() {
                 let idx = o * rhs.strides[0]
@@ -230,7 +230,7 @@ where
             4 => [lhs.strides[0], out.strides()[0]],
             _ => unreachable!(),
         };
-        let lhs = lhs.data.as_ref();
+        let lhs = &lhs.data[lhs.offset..];
 
         for i_batch in 0..op.batch {
             self.convtrans2d_backward(
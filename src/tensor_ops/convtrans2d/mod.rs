@@ -1,9 +1,10 @@
 mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
-use crate::{shapes::*, tensor::*};
+use crate::{shapes::*, tensor::*, tensor_ops::ReshapeTo};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -79,6 +80,7 @@ pub trait ConvTransAlgebra<const K: usize, const S: usize, const P: usize>: Dim
     fn convolve_dim(&self) -> Self::Convolved;
 }
 
+#[cfg(feature = "nightly")]
 impl<const D: usize, const K: usize, const S: usize, const P: usize> ConvTransAlgebra<K, S, P>
     for Const<D>
 where
@@ -99,6 +101,19 @@ impl<const K: usize, const S: usize, const P: usize> ConvTransAlgebra<K, S, P> f
     }
 }
 
+// Without `generic_const_exprs` the convolved size of a [Const] can't be named, so
+// it is computed at runtime instead.
+#[cfg(not(feature = "nightly"))]
+impl<const D: usize, const K: usize, const S: usize, const P: usize> ConvTransAlgebra<K, S, P>
+    for Const<D>
+{
+    type Convolved = usize;
+
+    fn convolve_dim(&self) -> Self::Convolved {
+        ConvTransAlgebra::<K, S, P>::convolve_dim(&D)
+    }
+}
+
 pub trait TryConvTrans2DTo<F, const S: usize, const P: usize>: HasErr {
     type Output;
     fn convtrans2d_to(self, filters: F) -> Self::Output {
@@ -136,7 +151,7 @@ impl<
         const S: usize,
         const P: usize,
         E: Dtype,
-        D: ConvTrans2DKernel<E> + ZerosTensor<E>,
+        D: ConvTrans2DKernel<E> + ZerosTensor<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
         T: 'static + Tape<E, D>,
     > TryConvTrans2DTo<Tensor<Rank4<O, C, K, K>, E, D>, S, P>
     for Tensor<(Const<C>, H, W), E, D, T>
@@ -151,13 +166,14 @@ impl<
         let w = self.shape.2;
 
         let op = ConvTrans2DOp::new(S, P, K, [1, C, h.size(), w.size()], O);
-        let (lhs, ltape) = self.split_tape();
-        let (rhs, rtape) = filters.split_tape();
+        // the kernels only handle contiguous inputs, so views are copied first
+        let (lhs, ltape) = self.try_contiguous()?.split_tape();
+        let (rhs, rtape) = filters.try_contiguous()?.split_tape();
         let mut tape = ltape.merge(rtape);
         let mut out = lhs
             .device
             .try_zeros_like(&(Const, h.convolve_dim(), w.convolve_dim()))?;
-        lhs.device.forward(op, &lhs, &rhs, &mut out)?;
+        ConvTrans2DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
         let lhs_ghost = lhs.ghost();
         let rhs_ghost = rhs.ghost();
         let out_ghost = out.ghost();
@@ -167,8 +183,16 @@ impl<
             grads.try_alloc_for(&out_ghost)?;
             let (grad_lhs, grad_rhs, grad_out) =
                 grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
-            lhs.device
-                .backward(op, &lhs, grad_lhs, &rhs, grad_rhs, &out_ghost, grad_out)
+            ConvTrans2DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
         });
         Ok(out.put_tape(tape))
     }
@@ -184,7 +208,7 @@ impl<
         const S: usize,
         const P: usize,
         E: Dtype,
-        D: ConvTrans2DKernel<E> + ZerosTensor<E>,
+        D: ConvTrans2DKernel<E> + ZerosTensor<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
         T: 'static + Tape<E, D>,
     > TryConvTrans2DTo<Tensor<Rank4<O, C, K, K>, E, D>, S, P>
     for Tensor<(B, Const<C>, H, W), E, D, T>
@@ -199,13 +223,14 @@ impl<
 
         let batch = self.shape().0;
         let op = ConvTrans2DOp::new(S, P, K, [batch.size(), C, h.size(), w.size()], O);
-        let (lhs, ltape) = self.split_tape();
-        let (rhs, rtape) = filters.split_tape();
+        // the kernels only handle contiguous inputs, so views are copied first
+        let (lhs, ltape) = self.try_contiguous()?.split_tape();
+        let (rhs, rtape) = filters.try_contiguous()?.split_tape();
         let mut out =
             lhs.device
                 .try_zeros_like(&(batch, Const, h.convolve_dim(), w.convolve_dim()))?;
         let mut tape = ltape.merge(rtape);
-        lhs.device.forward(op, &lhs, &rhs, &mut out)?;
+        ConvTrans2DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
         let lhs_ghost = lhs.ghost();
         let rhs_ghost = rhs.ghost();
         let out_ghost = out.ghost();
@@ -215,8 +240,16 @@ impl<
             grads.try_alloc_for(&out_ghost)?;
             let (grad_lhs, grad_rhs, grad_out) =
                 grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
-            lhs.device
-                .backward(op, &lhs, grad_lhs, &rhs, grad_rhs, &out_ghost, grad_out)
+            ConvTrans2DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
         });
         Ok(out.put_tape(tape))
     }
@@ -255,7 +288,10 @@ mod tests {
             [[[0.8291070, 0.0848221, 0.3680936], [0.4642293, 0.1073243, 0.1073309], [0.7863810, 0.3699800, 0.4956312]], [[0.0681600, 0.5616951, 0.4053129], [0.1850831, 0.8223089, 0.0667553], [0.8905262, 0.6328429, 0.8180532]]],
             [[[0.7582999, 0.9763424, 0.5727801], [0.3743349, 0.4793805, 0.6885015], [0.8183323, 0.1882774, 0.9794642]], [[0.5606869, 0.7552301, 0.6572021], [0.8761331, 0.2401637, 0.1778120], [0.2065960, 0.4133974, 0.8821540]]],
         ]);
-        let y = x.leaky_trace().convtrans2d::<1, 0>(w.clone());
+        let y = x
+            .leaky_trace()
+            .convtrans2d::<1, 0>(w.clone())
+            .realize::<Rank3<3, 5, 5>>();
         #[rustfmt::skip]
         assert_close_to_literal!(
             y,
@@ -312,7 +348,10 @@ mod tests {
             [[[0.0067961, 0.4006048, 0.3549793],[0.5392876, 0.3803764, 0.6090584],[0.4874769, 0.5006863, 0.8963661],],[[0.3751084, 0.5425243, 0.5102475],[0.6024926, 0.2719866, 0.9794098],[0.2236674, 0.1083973, 0.4948432],],],
             [[[0.9486710, 0.9823384, 0.5994584],[0.2740490, 0.2620903, 0.2716798],[0.3620688, 0.9108542, 0.9017550],],[[0.6089512, 0.4252676, 0.2729263],[0.8855131, 0.3937372, 0.3419960],[0.8216078, 0.6664743, 0.5395248],],],
         ]);
-        let y = x.leaky_trace().convtrans2d::<2, 0>(w.clone());
+        let y = x
+            .leaky_trace()
+            .convtrans2d::<2, 0>(w.clone())
+            .realize::<Rank3<3, 7, 7>>();
         #[rustfmt::skip]
         assert_close_to_literal!(
             y,
@@ -350,7 +389,8 @@ mod tests {
         let x: Tensor<Rank3<3, 28, 28>, TestDtype, _> = dev.sample_normal();
         let w: Tensor<Rank4<5, 3, 6, 6>, TestDtype, _> = dev.sample_normal();
 
-        let y: Tensor<Rank3<5, 83, 83>, _, _, _> = x.leaky_trace().convtrans2d::<3, 2>(w.clone());
+        let y: Tensor<Rank3<5, 83, 83>, _, _, _> =
+            x.leaky_trace().convtrans2d::<3, 2>(w.clone()).realize();
         let y0 = y.retaped::<NoneTape>();
         let grads0 = y.square().mean().backward();
         let x0 = grads0.get(&x);
@@ -361,7 +401,7 @@ mod tests {
             .reshape::<Rank4<10, 3, 28, 28>>();
 
         let y: Tensor<Rank4<10, 5, 83, 83>, _, _, _> =
-            x.leaky_trace().convtrans2d::<3, 2>(w.clone());
+            x.leaky_trace().convtrans2d::<3, 2>(w.clone()).realize();
         for i in 0..10 {
             assert_close_to_tensor!(y0, y.retaped::<NoneTape>().select(dev.tensor(i)), 1e-5);
         }
@@ -375,4 +415,36 @@ mod tests {
             assert_close_to_tensor!(x0, x_grad.clone().select(dev.tensor(i)));
         }
     }
+
+    #[test]
+    fn test_convtrans2d_sliced_input() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<2, 3, 6, 6>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank4<4, 3, 2, 2>, TestDtype, _> = dev.sample_normal();
+
+        let x_view = x
+            .leaky_trace()
+            .slice((.., .., 1..5, 1..5))
+            .realize::<Rank4<2, 3, 4, 4>>();
+        let y: Tensor<Rank4<2, 4, 5, 5>, _, _, _> = x_view.convtrans2d::<1, 0>(w.clone()).realize();
+
+        let x_copy = x
+            .clone()
+            .slice((.., .., 1..5, 1..5))
+            .realize::<Rank4<2, 3, 4, 4>>()
+            .contiguous();
+        let y_true: Tensor<Rank4<2, 4, 5, 5>, _, _, _> = x_copy
+            .leaky_trace()
+            .convtrans2d::<1, 0>(w.clone())
+            .realize();
+        assert_close_to_tensor!(y, y_true);
+
+        let g = y.square().sum().backward();
+        let g_true = y_true.square().sum().backward();
+        let x_grad = g
+            .get(&x)
+            .slice((.., .., 1..5, 1..5))
+            .realize::<Rank4<2, 3, 4, 4>>();
+        assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+    }
 }
@@ -0,0 +1,91 @@
+use crate::prelude::Tensorlike;
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::{Reference, Tensor};
+
+use std::sync::Arc;
+
+use super::{ConvTrans2DKernel, ConvTrans2DOp};
+
+fn make_4d<S: Shape>(strides: S::Concrete) -> [usize; 4] {
+    match S::NUM_DIMS {
+        3 => [0, strides[0], strides[1], strides[2]],
+        4 => [strides[0], strides[1], strides[2], strides[3]],
+        _ => panic!("Only implemented for 3d & 4d arrays"),
+    }
+}
+
+impl ConvTrans2DOp {
+    /// Calls `f([o, c, k1, k2], [y, x], [oh, ow])` for every multiply-add of the transposed
+    /// convolution, where input pixel `(c, y, x)` is scattered to output pixel `(o, oh, ow)`.
+    fn for_each_tap(&self, mut f: impl FnMut([usize; 4], [usize; 2], [usize; 2])) {
+        for o in 0..self.chan_out {
+            for c in 0..self.chan_in {
+                for k1 in 0..self.kernel {
+                    for k2 in 0..self.kernel {
+                        for y in 0..self.h_in {
+                            for x in 0..self.w_in {
+                                let oh = (y * self.stride + k1).checked_sub(self.padding);
+                                let ow = (x * self.stride + k2).checked_sub(self.padding);
+                                if let Some((oh, ow)) = oh.zip(ow) {
+                                    if oh < self.h_out && ow < self.w_out {
+                                        f([o, c, k1, k2], [y, x], [oh, ow]);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Dtype> ConvTrans2DKernel<E> for Reference {
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans2DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_4d::<L>(lhs.strides);
+        let ostr = make_4d::<O>(out.strides);
+        let rstr = rhs.strides;
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2], [y, x], [oh, ow]| {
+                let w =
+                    rhs.data[rhs.offset + o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3]];
+                let v =
+                    lhs.data[lhs.offset + b * lstr[0] + c * lstr[1] + y * lstr[2] + x * lstr[3]];
+                out_buf[b * ostr[0] + o * ostr[1] + oh * ostr[2] + ow * ostr[3]] += w * v;
+            });
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans2DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_4d::<L>(lhs.strides);
+        let ostr = make_4d::<O>(out.strides());
+        let rstr = rhs.strides;
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2], [y, x], [oh, ow]| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3];
+                let i_lhs = b * lstr[0] + c * lstr[1] + y * lstr[2] + x * lstr[3];
+                let go = grad_out[b * ostr[0] + o * ostr[1] + oh * ostr[2] + ow * ostr[3]];
+                grad_lhs[i_lhs] += rhs.data[rhs.offset + i_rhs] * go;
+                grad_rhs[i_rhs] += lhs.data[lhs.offset + i_lhs] * go;
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+#include "cuda_fp16.h"
+
+struct ConvTrans3DOp {
+    size_t stride;
+    size_t padding;
+    size_t kernel;
+    size_t batch;
+    size_t chan_in;
+    size_t chan_out;
+    size_t d_in;
+    size_t d_out;
+    size_t h_in;
+    size_t h_out;
+    size_t w_in;
+    size_t w_out;
+};
+
+template<typename T>
+__device__ void unfold_input_into_patches(
+    const ConvTrans3DOp op,
+    const T *image, // 5d (Batch, Channels, Depth, Height, Width)
+    const size_t *strides, // 5d image strides
+    T *patches // 8d (Batch, Channels, KernelSize, KernelSize, KernelSize, DepthOut, HeightOut, WidthOut)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= op.batch * op.chan_in * op.d_out * op.h_out * op.w_out) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t ow = idx % op.w_out;
+    idx /= op.w_out;
+    const size_t oh = idx % op.h_out;
+    idx /= op.h_out;
+    const size_t od = idx % op.d_out;
+    idx /= op.d_out;
+    const size_t c = idx % op.chan_in;
+    idx /= op.chan_in;
+    const size_t b = idx % op.batch;
+
+    const size_t out_numel = op.d_out * op.h_out * op.w_out;
+    const size_t kernel_numel = op.kernel * op.kernel * op.kernel;
+
+    image += b * strides[0] + c * strides[1];
+    patches += od * (op.h_out * op.w_out) + oh * op.w_out + ow;
+    patches += c * (kernel_numel * out_numel);
+    patches += b * (op.chan_in * kernel_numel * out_numel);
+
+    T zero = 0.0;
+
+    for (int k1 = 0;k1 < op.kernel;k1++) {
+        const size_t z_ks = od + op.padding;
+        const size_t z_s = z_ks - k1;
+        const size_t z = z_s / op.stride;
+        const bool k1_invalid = (z_ks < k1 || z_s % op.stride != 0 || z >= op.d_in);
+        for (int k2 = 0;k2 < op.kernel;k2++) {
+            const size_t y_ks = oh + op.padding;
+            const size_t y_s = y_ks - k2;
+            const size_t y = y_s / op.stride;
+            const bool k2_invalid = k1_invalid || (y_ks < k2 || y_s % op.stride != 0 || y >= op.h_in);
+            for (int k3 = 0;k3 < op.kernel;k3++) {
+                const size_t x_ks = ow + op.padding;
+                const size_t x_s = x_ks - k3;
+                const size_t x = x_s / op.stride;
+
+                const bool invalid = k2_invalid || (x_ks < k3 || x_s % op.stride != 0 || x >= op.w_in);
+                *patches = invalid ? zero : image[z * strides[2] + y * strides[3] + x * strides[4]];
+                patches += out_numel;
+            }
+        }
+    }
+}
+
+template<typename T>
+__device__ void unfold_output_into_patches(
+    const ConvTrans3DOp op,
+    const T *image_out, // 5d (Batch, ChanOut, DepthOut, HeightOut, WidthOut)
+    T *patches // 8d (Batch, ChanOut, KernelSize, KernelSize, KernelSize, DepthIn, HeightIn, WidthIn)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= op.batch * op.chan_out * op.d_in * op.h_in * op.w_in) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t x = idx % op.w_in;
+    idx /= op.w_in;
+    const size_t y = idx % op.h_in;
+    idx /= op.h_in;
+    const size_t z = idx % op.d_in;
+    idx /= op.d_in;
+    const size_t o = idx % op.chan_out;
+    idx /= op.chan_out;
+    const size_t b = idx % op.batch;
+
+    const size_t in_numel = op.d_in * op.h_in * op.w_in;
+    const size_t out_numel = op.d_out * op.h_out * op.w_out;
+    const size_t kernel_numel = op.kernel * op.kernel * op.kernel;
+
+    image_out += b * (op.chan_out * out_numel) + o * out_numel;
+    patches += z * (op.h_in * op.w_in) + y * op.w_in + x;
+    patches += o * (kernel_numel * in_numel);
+    patches += b * (op.chan_out * kernel_numel * in_numel);
+
+    T zero = 0.0;
+
+    for (int k1 = 0;k1 < op.kernel;k1++) {
+        const size_t od = z * op.stride + k1 - op.padding;
+        for (int k2 = 0;k2 < op.kernel;k2++) {
+            const size_t oh = y * op.stride + k2 - op.padding;
+            for (int k3 = 0;k3 < op.kernel;k3++) {
+                const size_t ow = x * op.stride + k3 - op.padding;
+                const bool invalid = od >= op.d_out || oh >= op.h_out || ow >= op.w_out;
+                *patches = invalid ? zero : image_out[od * (op.h_out * op.w_out) + oh * op.w_out + ow];
+                patches += in_numel;
+            }
+        }
+    }
+}
+
+template<typename T>
+__device__ void transpose_filters(
+    const ConvTrans3DOp op,
+    const T *filters, // 5d (ChanOut, ChanIn, KernelSize, KernelSize, KernelSize)
+    const size_t *strides, // 5d filters strides
+    T *filters_tr // 5d (ChanIn, ChanOut, KernelSize, KernelSize, KernelSize)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= op.chan_in * op.chan_out * op.kernel * op.kernel * op.kernel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t k3 = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t k2 = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t k1 = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t o = idx % op.chan_out;
+    idx /= op.chan_out;
+    const size_t c = idx % op.chan_in;
+
+    auto i_no = o * strides[0] + c * strides[1] + k1 * strides[2] + k2 * strides[3] + k3 * strides[4];
+
+    filters_tr[i] = filters[i_no];
+}
+
+template<typename T>
+__device__ void sum_transposed_filters(
+    const ConvTrans3DOp op,
+    const T *filters_tr, // 6d (Batch, ChanIn, ChanOut, KernelSize, KernelSize, KernelSize)
+    T *filters, // 5d (ChanOut, ChanIn, KernelSize, KernelSize, KernelSize)
+    const size_t *strides // 5d filter strides
+) {
+    const size_t kernel_numel = op.kernel * op.kernel * op.kernel;
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    auto numel = op.chan_out * op.chan_in * kernel_numel;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t k3 = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t k2 = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t k1 = idx % op.kernel;
+    idx /= op.kernel;
+    const size_t c = idx % op.chan_in;
+    idx /= op.chan_in;
+    const size_t o = idx % op.chan_out;
+
+    auto i_tr = c * (op.chan_out * kernel_numel) + o * kernel_numel + k1 * (op.kernel * op.kernel) + k2 * op.kernel + k3;
+    auto i_no = o * strides[0] + c * strides[1] + k1 * strides[2] + k2 * strides[3] + k3 * strides[4];
+
+    filters_tr += i_tr;
+
+    T tmp = 0.0;
+    for (int b = 0; b < op.batch; b++) {
+        tmp += *filters_tr;
+        filters_tr += numel;
+    }
+
+    filters[i_no] += tmp;
+}
+
+#define CONV_OP(TYPENAME, UNFOLD_INPUT, UNFOLD_OUTPUT, TR_FILTERS, SUM_TR_FILTERS) \
+extern "C" __global__ void UNFOLD_INPUT( \
+    const ConvTrans3DOp op, \
+    const TYPENAME *image, \
+    const size_t *strides, \
+    TYPENAME *patches \
+) { \
+    unfold_input_into_patches(op, image, strides, patches); \
+} \
+extern "C" __global__ void UNFOLD_OUTPUT( \
+    const ConvTrans3DOp op, \
+    const TYPENAME *image_out, \
+    TYPENAME *patches \
+) { \
+    unfold_output_into_patches(op, image_out, patches); \
+} \
+extern "C" __global__ void TR_FILTERS( \
+    const ConvTrans3DOp op, \
+    const TYPENAME *filters, \
+    const size_t *strides, \
+    TYPENAME *filters_tr \
+) { \
+    transpose_filters(op, filters, strides, filters_tr); \
+} \
+extern "C" __global__ void SUM_TR_FILTERS( \
+    const ConvTrans3DOp op, \
+    const TYPENAME *filters_tr, \
+    TYPENAME *filters, \
+    const size_t *strides \
+) { \
+    sum_transposed_filters(op, filters_tr, filters, strides); \
+}
+
+CONV_OP(
+    __half,
+    unfold_input_into_patches_f16,
+    unfold_output_into_patches_f16,
+    transpose_filters_f16,
+    sum_transposed_filters_f16
+);
+CONV_OP(
+    float,
+    unfold_input_into_patches_f32,
+    unfold_output_into_patches_f32,
+    transpose_filters_f32,
+    sum_transposed_filters_f32
+);
+CONV_OP(
+    double,
+    unfold_input_into_patches_f64,
+    unfold_output_into_patches_f64,
+    transpose_filters_f64,
+    sum_transposed_filters_f64
+);
@@ -0,0 +1,270 @@
+use crate::prelude::Tensorlike;
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::{cpu::*, Tensor};
+use crate::tensor_ops::matmul::cpu_kernel::MatMulImpl;
+
+use std::sync::Arc;
+
+use super::{ConvTrans3DKernel, ConvTrans3DOp};
+
+impl ConvTrans3DOp {
+    /// The input index that output index `o` reads with kernel offset `k`, if any.
+    #[inline(always)]
+    fn inp_idx(&self, o: usize, k: usize, inp: usize) -> Option<usize> {
+        let mut i = o + self.padding;
+        if i < k {
+            return None;
+        }
+        i -= k;
+        if i % self.stride != 0 {
+            return None;
+        }
+        i /= self.stride;
+        (i < inp).then_some(i)
+    }
+
+    #[inline(always)]
+    fn unfold_idx(&self, [k1, k2, k3, z, y, x]: [usize; 6]) -> Option<[usize; 3]> {
+        let od = (z * self.stride + k1).checked_sub(self.padding)?;
+        let oh = (y * self.stride + k2).checked_sub(self.padding)?;
+        let ow = (x * self.stride + k3).checked_sub(self.padding)?;
+        (od < self.d_out && oh < self.h_out && ow < self.w_out).then_some([od, oh, ow])
+    }
+}
+
+impl Cpu {
+    #[inline]
+    fn convtrans3d_forward<E: Dtype>(
+        &self,
+        op: &ConvTrans3DOp,
+        img: &[E],
+        filters: &[E],
+        out: &mut [E],
+        buf: &mut [E],
+    ) -> Result<(), CpuError>
+    where
+        Self: MatMulImpl<E>,
+    {
+        {
+            let mut i = 0;
+            for c in 0..op.chan_in {
+                for k1 in 0..op.kernel {
+                    for k2 in 0..op.kernel {
+                        for k3 in 0..op.kernel {
+                            for od in 0..op.d_out {
+                                let z = op.inp_idx(od, k1, op.d_in);
+                                for oh in 0..op.h_out {
+                                    let y = op.inp_idx(oh, k2, op.h_in);
+                                    for ow in 0..op.w_out {
+                                        let x = op.inp_idx(ow, k3, op.w_in);
+                                        if let Some(((z, y), x)) = z.zip(y).zip(x) {
+                                            buf[i] = img[c * (op.d_in * op.h_in * op.w_in)
+                                                + z * (op.h_in * op.w_in)
+                                                + y * op.w_in
+                                                + x];
+                                        }
+                                        i += 1;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // (O, C * K * K * K) * (C * K * K * K, OD * OH * OW) = (O, OD * OH * OW)
+        let m = op.chan_out;
+        let k = op.chan_in * op.kernel * op.kernel * op.kernel;
+        let n = op.d_out * op.h_out * op.w_out;
+        self.matmul(
+            (m, k, n),
+            false,
+            filters.as_ptr(),
+            [k, 1],
+            buf.as_ptr(),
+            [n, 1],
+            out.as_mut_ptr(),
+            [n, 1],
+        );
+        Ok(())
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn convtrans3d_backward<E: Dtype>(
+        &self,
+        op: &ConvTrans3DOp,
+        img: &[E],
+        grad_img: &mut [E],
+        filters_tr: &[E],
+        grad_filters_tr: &mut [E],
+        grad_out: &[E],
+        buf: &mut [E],
+    ) -> Result<(), CpuError>
+    where
+        Self: MatMulImpl<E>,
+    {
+        {
+            let out_numel = op.d_out * op.h_out * op.w_out;
+            let mut i = 0;
+            for o in 0..op.chan_out {
+                for k1 in 0..op.kernel {
+                    for k2 in 0..op.kernel {
+                        for k3 in 0..op.kernel {
+                            for z in 0..op.d_in {
+                                for y in 0..op.h_in {
+                                    for x in 0..op.w_in {
+                                        if let Some([od, oh, ow]) =
+                                            op.unfold_idx([k1, k2, k3, z, y, x])
+                                        {
+                                            buf[i] = grad_out[o * out_numel
+                                                + od * (op.h_out * op.w_out)
+                                                + oh * op.w_out
+                                                + ow];
+                                        }
+                                        i += 1;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        {
+            // img_g += filters^T * unfold(grad_out)
+            // (C, D * H * W) += (C, O * K * K * K) * (O * K * K * K, D * H * W)
+            let m = op.chan_in;
+            let k = op.chan_out * op.kernel * op.kernel * op.kernel;
+            let n = op.d_in * op.h_in * op.w_in;
+            self.matmul(
+                (m, k, n),
+                true,
+                filters_tr.as_ptr(),
+                [k, 1],
+                buf.as_ptr(),
+                [n, 1],
+                grad_img.as_mut_ptr(),
+                [n, 1],
+            );
+        }
+
+        {
+            // weight_g^T += img * patches^T
+            // (C, O * K * K * K) += (C, D * H * W) * (D * H * W, O * K * K * K)
+            let m = op.chan_in;
+            let k = op.d_in * op.h_in * op.w_in;
+            let n = op.chan_out * op.kernel * op.kernel * op.kernel;
+            self.matmul(
+                (m, k, n),
+                true,
+                img.as_ptr(),
+                [k, 1],
+                buf.as_ptr(),
+                [1, k],
+                grad_filters_tr.as_mut_ptr(),
+                [n, 1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> ConvTrans3DKernel<E> for Cpu
+where
+    Self: MatMulImpl<E>,
+{
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans3DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let mut patches = self.try_alloc_zeros::<E>(op.inp_patches_shape().num_elements())?;
+        let [lstride, ostride] = match L::NUM_DIMS {
+            4 => [0; 2],
+            5 => [lhs.strides[0], out.strides[0]],
+            _ => unreachable!(),
+        };
+        let lhs = &lhs.data[lhs.offset..];
+        let rhs = &rhs.data[rhs.offset..];
+        let out = Arc::make_mut(&mut out.data);
+        for i_batch in 0..op.batch {
+            self.convtrans3d_forward(
+                &op,
+                &lhs[i_batch * lstride..],
+                rhs,
+                &mut out[i_batch * ostride..],
+                &mut patches,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans3DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let f_tr_shape = op.filters_tr_shape();
+        let mut patches = self.try_alloc_zeros::<E>(op.out_patches_shape().num_elements())?;
+        let mut f10234 = self.try_alloc_zeros::<E>(f_tr_shape.num_elements())?;
+        let mut grad_f10234 = self.try_alloc_zeros::<E>(f_tr_shape.num_elements())?;
+
+        {
+            // transpose filters in f10234
+            let buf = &rhs.data[rhs.offset..];
+            let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
+            while let Some((i, [c, o, k1, k2, k3])) = f_idx.next_with_idx() {
+                let idx = o * rhs.strides[0]
+                    + c * rhs.strides[1]
+                    + k1 * rhs.strides[2]
+                    + k2 * rhs.strides[3]
+                    + k3 * rhs.strides[4];
+                f10234[i] = buf[idx];
+            }
+        }
+
+        let [lstride, ostride] = match L::NUM_DIMS {
+            4 => [0; 2],
+            5 => [lhs.strides[0], out.strides()[0]],
+            _ => unreachable!(),
+        };
+        let lhs = &lhs.data[lhs.offset..];
+
+        for i_batch in 0..op.batch {
+            self.convtrans3d_backward(
+                &op,
+                &lhs[i_batch * lstride..],
+                &mut grad_lhs[i_batch * lstride..],
+                &f10234,
+                &mut grad_f10234,
+                &grad_out[i_batch * ostride..],
+                &mut patches,
+            )?;
+        }
+
+        {
+            // untranspose filters
+            let mut f_idx = NdIndex::new(f_tr_shape, f_tr_shape.strides());
+            while let Some((i, [c, o, k1, k2, k3])) = f_idx.next_with_idx() {
+                let idx = o * rhs.strides[0]
+                    + c * rhs.strides[1]
+                    + k1 * rhs.strides[2]
+                    + k2 * rhs.strides[3]
+                    + k3 * rhs.strides[4];
+                grad_rhs[idx] += grad_f10234[i];
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{shapes::*, tensor::*, tensor_ops::ReshapeTo};
+
+use super::ConvTransAlgebra;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub(super) struct ConvTrans3DOp {
+    pub stride: usize,
+    pub padding: usize,
+    pub kernel: usize,
+    pub batch: usize,
+    pub chan_in: usize,
+    pub chan_out: usize,
+    pub d_in: usize,
+    pub d_out: usize,
+    pub h_in: usize,
+    pub h_out: usize,
+    pub w_in: usize,
+    pub w_out: usize,
+}
+
+impl ConvTrans3DOp {
+    fn new(s: usize, p: usize, k: usize, [b, c, d_in, h_in, w_in]: [usize; 5], o: usize) -> Self {
+        Self {
+            stride: s,
+            padding: p,
+            kernel: k,
+            batch: b,
+            chan_in: c,
+            chan_out: o,
+            d_in,
+            d_out: (d_in - 1) * s - 2 * p + k,
+            h_in,
+            h_out: (h_in - 1) * s - 2 * p + k,
+            w_in,
+            w_out: (w_in - 1) * s - 2 * p + k,
+        }
+    }
+
+    pub(super) fn inp_patches_shape(&self) -> [usize; 5] {
+        let k = self.kernel;
+        [self.chan_in, k * k * k, self.d_out, self.h_out, self.w_out]
+    }
+
+    pub(super) fn out_patches_shape(&self) -> [usize; 5] {
+        let k = self.kernel;
+        [self.chan_out, k * k * k, self.d_in, self.h_in, self.w_in]
+    }
+
+    pub(super) fn filters_tr_shape(&self) -> [usize; 5] {
+        let k = self.kernel;
+        [self.chan_in, self.chan_out, k, k, k]
+    }
+}
+
+pub(super) trait ConvTrans3DKernel<E: Dtype>: DeviceStorage {
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans3DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans3DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+pub trait TryConvTrans3DTo<F, const S: usize, const P: usize>: HasErr {
+    type Output;
+    fn convtrans3d_to(self, filters: F) -> Self::Output {
+        self.try_convtrans3d_to(filters).unwrap()
+    }
+    fn try_convtrans3d_to(self, filters: F) -> Result<Self::Output, Self::Err>;
+}
+
+pub trait TryConvTrans3D<F> {
+    fn convtrans3d<const S: usize, const P: usize>(self, filters: F) -> Self::Output
+    where
+        Self: TryConvTrans3DTo<F, S, P>,
+    {
+        self.convtrans3d_to(filters)
+    }
+    fn try_convtrans3d<const S: usize, const P: usize>(
+        self,
+        filters: F,
+    ) -> Result<Self::Output, Self::Err>
+    where
+        Self: TryConvTrans3DTo<F, S, P>,
+    {
+        self.try_convtrans3d_to(filters)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, T, F> TryConvTrans3D<F> for Tensor<S, E, D, T> {}
+
+impl<
+        const C: usize,
+        Z: Dim + ConvTransAlgebra<K, S, P>,
+        H: Dim + ConvTransAlgebra<K, S, P>,
+        W: Dim + ConvTransAlgebra<K, S, P>,
+        const O: usize,
+        const K: usize,
+        const S: usize,
+        const P: usize,
+        E: Dtype,
+        D: ConvTrans3DKernel<E> + ZerosTensor<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+        T: 'static + Tape<E, D>,
+    > TryConvTrans3DTo<Tensor<Rank5<O, C, K, K, K>, E, D>, S, P>
+    for Tensor<(Const<C>, Z, H, W), E, D, T>
+{
+    type Output = Tensor<(Const<O>, Z::Convolved, H::Convolved, W::Convolved), E, D, T>;
+
+    fn try_convtrans3d_to(
+        self,
+        filters: Tensor<Rank5<O, C, K, K, K>, E, D>,
+    ) -> Result<Self::Output, Self::Err> {
+        let (_, d, h, w) = self.shape;
+
+        let op = ConvTrans3DOp::new(S, P, K, [1, C, d.size(), h.size(), w.size()], O);
+        // the kernels only handle contiguous inputs, so views are copied first
+        let (lhs, ltape) = self.try_contiguous()?.split_tape();
+        let (rhs, rtape) = filters.try_contiguous()?.split_tape();
+        let mut tape = ltape.merge(rtape);
+        let mut out = lhs.device.try_zeros_like(&(
+            Const,
+            d.convolve_dim(),
+            h.convolve_dim(),
+            w.convolve_dim(),
+        ))?;
+        ConvTrans3DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
+        let lhs_ghost = lhs.ghost();
+        let rhs_ghost = rhs.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&rhs_ghost)?;
+            grads.try_alloc_for(&lhs_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_lhs, grad_rhs, grad_out) =
+                grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
+            ConvTrans3DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+impl<
+        B: Dim,
+        const C: usize,
+        Z: Dim + ConvTransAlgebra<K, S, P>,
+        H: Dim + ConvTransAlgebra<K, S, P>,
+        W: Dim + ConvTransAlgebra<K, S, P>,
+        const O: usize,
+        const K: usize,
+        const S: usize,
+        const P: usize,
+        E: Dtype,
+        D: ConvTrans3DKernel<E> + ZerosTensor<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+        T: 'static + Tape<E, D>,
+    > TryConvTrans3DTo<Tensor<Rank5<O, C, K, K, K>, E, D>, S, P>
+    for Tensor<(B, Const<C>, Z, H, W), E, D, T>
+{
+    type Output = Tensor<(B, Const<O>, Z::Convolved, H::Convolved, W::Convolved), E, D, T>;
+    fn try_convtrans3d_to(
+        self,
+        filters: Tensor<Rank5<O, C, K, K, K>, E, D>,
+    ) -> Result<Self::Output, Self::Err> {
+        let (batch, _, d, h, w) = self.shape;
+
+        let op = ConvTrans3DOp::new(S, P, K, [batch.size(), C, d.size(), h.size(), w.size()], O);
+        // the kernels only handle contiguous inputs, so views are copied first
+        let (lhs, ltape) = self.try_contiguous()?.split_tape();
+        let (rhs, rtape) = filters.try_contiguous()?.split_tape();
+        let mut out = lhs.device.try_zeros_like(&(
+            batch,
+            Const,
+            d.convolve_dim(),
+            h.convolve_dim(),
+            w.convolve_dim(),
+        ))?;
+        let mut tape = ltape.merge(rtape);
+        ConvTrans3DKernel::forward(&lhs.device, op, &lhs, &rhs, &mut out)?;
+        let lhs_ghost = lhs.ghost();
+        let rhs_ghost = rhs.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&rhs_ghost)?;
+            grads.try_alloc_for(&lhs_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_lhs, grad_rhs, grad_out) =
+                grads.muts_and_ref(&lhs_ghost, &rhs_ghost, &out_ghost);
+            ConvTrans3DKernel::backward(
+                &lhs.device,
+                op,
+                &lhs,
+                grad_lhs,
+                &rhs,
+                grad_rhs,
+                &out_ghost,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    /// Matches
+    /// ```python
+    /// y = torch.conv_transpose3d(x, torch.swapaxes(w, 0, 1))
+    /// y.exp().mean().backward()
+    /// ```
+    fn convtrans3d_test() {
+        let dev: TestDevice = Default::default();
+        #[rustfmt::skip]
+        let x = dev.tensor([[[[-0.3523, -0.6983], [0.3019, -0.8551]], [[0.0718, -0.2686], [-0.884, 0.0149]]], [[[-0.925, -0.1327], [-0.8603, -0.8186]], [[-0.151, 0.6537], [-0.7524, -0.5535]]]]).to_dtype::<TestDtype>();
+        #[rustfmt::skip]
+        let w = dev.tensor([[[[[0.2549, 0.8954], [0.1542, -0.2066]], [[0.9525, -0.9068], [0.7169, -0.4208]]], [[[-0.7115, -0.7644], [-0.383, 0.6323]], [[-0.6385, 0.1632], [0.2778, -0.2552]]]], [[[[0.0955, -0.8744], [-0.8808, -0.5881]], [[0.3608, -0.1448], [-0.3717, 0.1711]]], [[[-0.0936, -0.4005], [0.5888, 0.398]], [[-0.5118, 0.1488], [0.0504, 0.7503]]]]]).to_dtype::<TestDtype>();
+        let y = x
+            .leaky_trace()
+            .convtrans3d::<1, 0>(w.clone())
+            .realize::<Rank4<2, 3, 3, 3>>();
+        #[rustfmt::skip]
+        assert_close_to_literal!(y, [[[[0.5683362, 0.30804, -0.5238219], [0.9890081, 0.7234574, -0.07955613], [0.3760479, -0.4246728, -0.3409371]], [[0.3807851, -0.7657557, -0.1286309], [0.706238, -1.08002, 1.874783], [0.1292972, -0.8267393, 0.2156764]], [[0.164803, -0.7629804, 0.3502503], [-0.352077, 1.043782, -0.1576399], [-0.8427563, 0.4209192, 0.1349833]]], [[[0.05293535, 0.6242467, 0.6637399], [-0.1249786, 0.4514942, 1.433404], [-0.7724582, -0.2487664, 0.1770815]], [[0.3672957, -0.3598018, 0.05442507], [0.4674094, 1.08397, 0.4097513], [0.1800387, -0.4358443, -0.9895589]], [[0.1031872, -0.46434, 0.1361638], [0.03183266, 0.3364781, 0.3599953], [0.2906618, -0.7492128, -0.4127417]]]]);
+        let g = y.exp().mean().backward();
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&x), [[[[0.01924043, -0.1474357], [0.04691597, -0.1615531]], [[-0.04464138, -0.07622334], [-0.1008542, 0.1238535]]], [[[-0.008199391, -0.01164789], [-0.08569677, -0.05909279]], [[-0.01544466, 0.1174248], [-0.0441533, -0.1200936]]]]);
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&w), [[[[[-0.08017313, -0.02715076], [-0.0639163, -0.07179219]], [[-0.02108898, -0.1684371], [-0.03799502, -0.1288167]]], [[[-0.1378457, -0.1338195], [-0.1061346, 0.0003769945]], [[-0.1002019, -0.1468111], [-0.04950792, -0.07817363]]]], [[[[-0.07781768, -0.1472139], [-0.0678386, -0.09305829]], [[-0.07450228, -0.05292082], [-0.07954229, -0.05386346]]], [[[-0.1086467, -0.1711376], [-0.03014035, -0.07057927]], [[-0.1209134, -0.106569], [-0.07289707, -0.07021664]]]]]);
+    }
+
+    #[test]
+    /// Matches
+    /// ```python
+    /// y = torch.conv_transpose3d(x, torch.swapaxes(w, 0, 1), stride=2, padding=1)
+    /// y.exp().mean().backward()
+    /// ```
+    fn convtrans3d_s2p1() {
+        let dev: TestDevice = Default::default();
+        #[rustfmt::skip]
+        let x = dev.tensor([[[[-0.5466, 0.9246], [-0.7473, 0.4096]], [[-0.8296, -0.5051], [0.9983, -0.5812]]]]).to_dtype::<TestDtype>();
+        #[rustfmt::skip]
+        let w = dev.tensor([[[[[0.2837, -0.0817, -0.0937], [-0.01, -0.6155, 0.661], [-0.8209, -0.5316, -0.96]], [[-0.4665, -0.1847, 0.8041], [-0.2418, -0.7725, -0.4833], [0.9832, -0.8738, 0.2403]], [[-0.2456, 0.3217, -0.3231], [0.3826, -0.0048, 0.2994], [0.8027, 0.1631, -0.7157]]]], [[[[-0.8713, 0.8921, -0.0227], [-0.6123, 0.8921, 0.1579], [0.4579, 0.7619, -0.4287]], [[-0.2866, 0.7562, -0.73], [0.5286, -0.8048, 0.3804], [0.4043, 0.9, 0.687]], [[0.0072, -0.6047, -0.6997], [0.0574, 0.0196, -0.8572], [0.8064, 0.0148, 0.4026]]]]]).to_dtype::<TestDtype>();
+        let y = x
+            .leaky_trace()
+            .convtrans3d::<2, 1>(w.clone())
+            .realize::<Rank4<2, 3, 3, 3>>();
+        #[rustfmt::skip]
+        assert_close_to_literal!(y, [[[[0.4222485, 0.0406035, -0.7142535], [0.6156454, -0.01426359, -0.8835686], [0.5772892, 0.2621288, -0.316416]], [[0.5132425, -0.3532147, 0.306451], [0.02989738, 2.226858, 0.5985658], [-0.6108666, 0.5986596, 0.3557625]], [[0.640866, 0.5230789, 0.3901898], [0.5405185, 0.3778956, 0.548704], [-0.7711867, -0.3419442, 0.448977]]], [[[0.4399037, 0.2808169, -0.7441181], [-1.057048, 0.4264392, 1.14188], [0.601427, -0.06775836, -0.3296461]], [[-0.7507995, 0.6998964, -0.4324775], [0.7023138, 1.659474, -1.137325], [0.8759363, 1.177597, -0.5104604]], [[0.6676621, -0.5825757, 0.4065045], [0.00827446, -1.336334, -0.8940934], [-0.8034318, 0.072531, 0.4677498]]]]);
+        let g = y.exp().mean().backward();
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&x), [[[[-0.1532974, 0.2982756], [-0.2458868, -0.009787505]], [[-0.2534852, -0.1757306], [0.09308753, -0.1301216]]]]);
+        #[rustfmt::skip]
+        assert_close_to_literal!(g.get(&w), [[[[[-0.09978015, -0.0005351939, 0.1713877], [-0.02615542, -0.04370018, 0.02284911], [-0.08671533, -0.03284834, -0.1424253]], [[-0.008227669, -0.009369163, 0.01333358], [0.00426259, -0.07747164, -0.04131564], [0.003230707, -0.05422598, -0.03239667]], [[0.07031994, -0.0004575823, -0.1282961], [0.0258297, 0.009664208, -0.03229244], [0.1587349, 0.02072459, -0.09384003]]]], [[[[-0.05657598, 0.03386314, 0.09717791], [-0.05377673, 0.02460874, 0.02908533], [-0.04916815, -0.03400838, -0.08075608]], [[0.008790352, 0.03319182, -0.01633979], [0.01296542, -0.08027825, -0.01503804], [0.02376949, 0.0308046, -0.01954265]], [[0.03987185, -0.02550027, -0.07274471], [0.05910213, -0.02234307, -0.06531037], [0.0900037, -0.01494019, -0.0532079]]]]]);
+    }
+
+    #[test]
+    fn test_batched_convtrans3d() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<3, 4, 5, 6>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank5<5, 3, 3, 3, 3>, TestDtype, _> = dev.sample_normal();
+
+        let y: Tensor<Rank4<5, 7, 9, 11>, _, _, _> =
+            x.leaky_trace().convtrans3d::<2, 1>(w.clone()).realize();
+        let y0 = y.retaped::<NoneTape>();
+        let grads0 = y.square().mean().backward();
+        let x0 = grads0.get(&x);
+        let w0 = grads0.get(&w);
+
+        let x = x
+            .broadcast::<Rank5<4, 3, 4, 5, 6>, _>()
+            .reshape::<Rank5<4, 3, 4, 5, 6>>();
+
+        let y: Tensor<Rank5<4, 5, 7, 9, 11>, _, _, _> =
+            x.leaky_trace().convtrans3d::<2, 1>(w.clone()).realize();
+        for i in 0..4 {
+            assert_close_to_tensor!(y0, y.retaped::<NoneTape>().select(dev.tensor(i)), 1e-5);
+        }
+
+        let grads = y.square().mean().backward();
+
+        assert_close_to_tensor!(w0, grads.get(&w));
+
+        let x_grad = grads.get(&x) * 4.0;
+        for i in 0..4 {
+            assert_close_to_tensor!(x0, x_grad.clone().select(dev.tensor(i)));
+        }
+    }
+
+    #[test]
+    fn test_convtrans3d_sliced_input() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank5<1, 2, 5, 5, 5>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank5<2, 2, 2, 2, 2>, TestDtype, _> = dev.sample_normal();
+
+        let x_view = x
+            .leaky_trace()
+            .slice((.., .., 1..4, 1..4, 1..4))
+            .realize::<Rank5<1, 2, 3, 3, 3>>();
+        let y: Tensor<Rank5<1, 2, 4, 4, 4>, _, _, _> =
+            x_view.convtrans3d::<1, 0>(w.clone()).realize();
+
+        let x_copy = x
+            .clone()
+            .slice((.., .., 1..4, 1..4, 1..4))
+            .realize::<Rank5<1, 2, 3, 3, 3>>()
+            .contiguous();
+        let y_true: Tensor<Rank5<1, 2, 4, 4, 4>, _, _, _> = x_copy
+            .leaky_trace()
+            .convtrans3d::<1, 0>(w.clone())
+            .realize();
+        assert_close_to_tensor!(y, y_true);
+
+        let g = y.square().sum().backward();
+        let g_true = y_true.square().sum().backward();
+        let x_grad = g
+            .get(&x)
+            .slice((.., .., 1..4, 1..4, 1..4))
+            .realize::<Rank5<1, 2, 3, 3, 3>>();
+        assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::prelude::Tensorlike;
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::{Reference, Tensor};
+
+use std::sync::Arc;
+
+use super::{ConvTrans3DKernel, ConvTrans3DOp};
+
+fn make_5d<S: Shape>(strides: S::Concrete) -> [usize; 5] {
+    match S::NUM_DIMS {
+        4 => [0, strides[0], strides[1], strides[2], strides[3]],
+        5 => [strides[0], strides[1], strides[2], strides[3], strides[4]],
+        _ => panic!("Only implemented for 4d & 5d arrays"),
+    }
+}
+
+impl ConvTrans3DOp {
+    /// Calls `f([o, c, k1, k2, k3], [z, y, x], [od, oh, ow])` for every multiply-add of the
+    /// transposed convolution, where input voxel `(c, z, y, x)` is scattered to output voxel
+    /// `(o, od, oh, ow)`.
+    fn for_each_tap(&self, mut f: impl FnMut([usize; 5], [usize; 3], [usize; 3])) {
+        let out_idx = |i: usize, k: usize, size: usize| {
+            (i * self.stride + k)
+                .checked_sub(self.padding)
+                .filter(|&o| o < size)
+        };
+        for o in 0..self.chan_out {
+            for c in 0..self.chan_in {
+                for k1 in 0..self.kernel {
+                    for k2 in 0..self.kernel {
+                        for k3 in 0..self.kernel {
+                            for z in 0..self.d_in {
+                                for y in 0..self.h_in {
+                                    for x in 0..self.w_in {
+                                        let od = out_idx(z, k1, self.d_out);
+                                        let oh = out_idx(y, k2, self.h_out);
+                                        let ow = out_idx(x, k3, self.w_out);
+                                        if let Some(((od, oh), ow)) = od.zip(oh).zip(ow) {
+                                            f([o, c, k1, k2, k3], [z, y, x], [od, oh, ow]);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Dtype> ConvTrans3DKernel<E> for Reference {
+    fn forward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans3DOp,
+        lhs: &Tensor<L, E, Self>,
+        rhs: &Tensor<R, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_5d::<L>(lhs.strides);
+        let ostr = make_5d::<O>(out.strides);
+        let rstr = make_5d::<R>(rhs.strides);
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2, k3], [z, y, x], [od, oh, ow]| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3] + k3 * rstr[4];
+                let i_lhs = b * lstr[0] + c * lstr[1] + z * lstr[2] + y * lstr[3] + x * lstr[4];
+                let (w, v) = (rhs.data[rhs.offset + i_rhs], lhs.data[lhs.offset + i_lhs]);
+                out_buf[b * ostr[0] + o * ostr[1] + od * ostr[2] + oh * ostr[3] + ow * ostr[4]] +=
+                    w * v;
+            });
+        }
+        Ok(())
+    }
+
+    fn backward<L: Shape, R: Shape, O: Shape>(
+        &self,
+        op: ConvTrans3DOp,
+        lhs: &Tensor<L, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<R, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        out: &impl Tensorlike<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let lstr = make_5d::<L>(lhs.strides);
+        let ostr = make_5d::<O>(out.strides());
+        let rstr = make_5d::<R>(rhs.strides);
+        for b in 0..op.batch {
+            op.for_each_tap(|[o, c, k1, k2, k3], [z, y, x], [od, oh, ow]| {
+                let i_rhs = o * rstr[0] + c * rstr[1] + k1 * rstr[2] + k2 * rstr[3] + k3 * rstr[4];
+                let i_lhs = b * lstr[0] + c * lstr[1] + z * lstr[2] + y * lstr[3] + x * lstr[4];
+                let go = grad_out
+                    [b * ostr[0] + o * ostr[1] + od * ostr[2] + oh * ostr[3] + ow * ostr[4]];
+                grad_lhs[i_lhs] += rhs.data[rhs.offset + i_rhs] * go;
+                grad_rhs[i_rhs] += lhs.data[lhs.offset + i_lhs] * go;
+            });
+        }
+        Ok(())
+    }
+}
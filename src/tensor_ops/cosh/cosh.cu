@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+struct CoshKernelOp {};
+
+UNARY_OP(__half, cosh_fwd_f16, cosh_bwd_f16, CoshKernelOp,
+        coshg(x),
+        sinhg(x))
+
+UNARY_OP(float, cosh_fwd_f32, cosh_bwd_f32, CoshKernelOp,
+        coshg(x),
+        sinhg(x))
+
+UNARY_OP(double, cosh_fwd_f64, cosh_bwd_f64, CoshKernelOp,
+        coshg(x),
+        sinhg(x))
@@ -0,0 +1,11 @@
+use super::CoshKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for CoshKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/cosh.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(CoshKernelOp, half::f16, PTX, "cosh_fwd_f16", "cosh_bwd_f16");
+cuda_unary!(CoshKernelOp, f32, PTX, "cosh_fwd_f32", "cosh_bwd_f32");
+cuda_unary!(CoshKernelOp, f64, PTX, "cosh_fwd_f64", "cosh_bwd_f64");
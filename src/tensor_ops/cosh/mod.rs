@@ -0,0 +1,59 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CoshKernelOp;
+
+/// [Hyperbolic cosine](https://en.wikipedia.org/wiki/Hyperbolic_functions). `(exp(t) + exp(-t)) / 2`
+///
+/// The derivative is `sinh(t)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.cosh();
+/// ```
+pub fn cosh<S: Shape, E: Dtype, D: UnaryKernel<CoshKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.cosh()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<CoshKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [cosh]
+    pub fn cosh(self) -> Self {
+        self.try_cosh().unwrap()
+    }
+    /// See [cosh]
+    pub fn try_cosh(self) -> Result<Self, D::Err> {
+        try_unary_op(CoshKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_cosh() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().cosh();
+        assert_close_to_literal!(r, [3.7621957, 1.5430806, 1.0, 1.5430806, 3.7621957]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [-0.72537208, -0.23504024, 0.0, 0.23504024, 0.72537208]
+        );
+    }
+}
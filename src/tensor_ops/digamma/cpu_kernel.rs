@@ -0,0 +1,16 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use crate::tensor_ops::special::{digamma, trigamma};
+use num_traits::Float;
+
+impl<F: Float> UnaryDerivative<F> for super::DigammaKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        F::from(digamma(x.to_f64().unwrap())).unwrap()
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        F::from(trigamma(x.to_f64().unwrap())).unwrap()
+    }
+}
@@ -0,0 +1,29 @@
+use super::DigammaKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for DigammaKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/digamma.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(
+    DigammaKernelOp,
+    half::f16,
+    PTX,
+    "digamma_fwd_f16",
+    "digamma_bwd_f16"
+);
+cuda_unary!(
+    DigammaKernelOp,
+    f32,
+    PTX,
+    "digamma_fwd_f32",
+    "digamma_bwd_f32"
+);
+cuda_unary!(
+    DigammaKernelOp,
+    f64,
+    PTX,
+    "digamma_fwd_f64",
+    "digamma_bwd_f64"
+);
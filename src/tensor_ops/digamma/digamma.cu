@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+struct DigammaKernelOp {};
+
+UNARY_OP(__half, digamma_fwd_f16, digamma_bwd_f16, DigammaKernelOp,
+        digammag(x),
+        trigammag(x))
+
+UNARY_OP(float, digamma_fwd_f32, digamma_bwd_f32, DigammaKernelOp,
+        digammag(x),
+        trigammag(x))
+
+UNARY_OP(double, digamma_fwd_f64, digamma_bwd_f64, DigammaKernelOp,
+        digammag(x),
+        trigammag(x))
@@ -0,0 +1,63 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct DigammaKernelOp;
+
+/// The [digamma function](https://en.wikipedia.org/wiki/Digamma_function), the derivative of [lgamma()](super::lgamma()).
+///
+/// The derivative is the [trigamma function](https://en.wikipedia.org/wiki/Trigamma_function).
+/// Non positive integers are poles, where this is `nan`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([0.5, 1.0, 2.0, 3.0]);
+/// let r = t.digamma();
+/// ```
+pub fn digamma<S: Shape, E: Dtype, D: UnaryKernel<DigammaKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.digamma()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<DigammaKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [digamma]
+    pub fn digamma(self) -> Self {
+        self.try_digamma().unwrap()
+    }
+    /// See [digamma]
+    pub fn try_digamma(self) -> Result<Self, D::Err> {
+        try_unary_op(DigammaKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_digamma() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-0.5, 0.5, 1.0, 2.5, 4.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().digamma();
+        assert_close_to_literal!(
+            r,
+            [0.036489974, -1.96351, -0.57721566, 0.70315664, 1.2561177]
+        );
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [1.7869604, 0.98696044, 0.32898681, 0.098071551, 0.056764591]
+        );
+    }
+}
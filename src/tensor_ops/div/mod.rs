@@ -59,7 +59,7 @@ where
 {
     /// See [div]
     fn try_div(self, rhs: Tensor<S, E, D, R>) -> Result<Self, Self::Err> {
-        try_binary_op(BinaryDivKernelOp, self, rhs)
+        try_binary_op("div", BinaryDivKernelOp, self, rhs)
     }
 }
 
@@ -20,9 +20,11 @@ impl<E: Float + Dtype> super::DropoutKernel<E> for Cpu {
             data: inp.data.clone(),
             shape: inp.shape,
             strides: inp.strides,
+            offset: inp.offset,
             device: self.clone(),
             tape: Default::default(),
-        };
+        }
+        .try_compact()?;
         for x in out.buf_iter_mut() {
             *x = if dist.sample(&mut rng) {
                 E::zero()
@@ -43,7 +45,7 @@ impl<E: Float + Dtype> super::DropoutKernel<E> for Cpu {
         let mut rng = StdRng::seed_from_u64(op.seed);
         let dist = Bernoulli::new(op.prob).unwrap();
         debug_assert_eq!(grad_inp.len(), grad_out.len());
-        debug_assert_eq!(inp.data.len(), grad_out.len());
+        debug_assert_eq!(inp.span(), grad_out.len());
         for (i, data_i) in grad_inp.iter_mut().enumerate() {
             *data_i += if dist.sample(&mut rng) {
                 E::zero()
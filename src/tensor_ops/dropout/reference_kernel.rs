@@ -0,0 +1,48 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{reference::physical_index, Reference, Tensor},
+};
+
+use num_traits::Float;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Bernoulli, Distribution};
+
+impl<E: Float + Dtype> super::DropoutKernel<E> for Reference {
+    fn forward<S: Shape>(
+        &self,
+        op: super::DropoutKernelOp,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let mut rng = StdRng::seed_from_u64(op.seed);
+        let dist = Bernoulli::new(op.prob).unwrap();
+        let scale = E::from_f64(1.0 - op.prob).unwrap();
+        let mut out = self.try_alloc_elem(inp.shape.num_elements(), E::zero())?;
+        for (i, o) in out.iter_mut().enumerate() {
+            let x = inp.data[inp.offset + physical_index(&inp.shape, &inp.strides, i)];
+            *o = if dist.sample(&mut rng) {
+                E::zero()
+            } else {
+                x / scale
+            };
+        }
+        Ok(self.build_tensor(inp.shape, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        op: super::DropoutKernelOp,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut rng = StdRng::seed_from_u64(op.seed);
+        let dist = Bernoulli::new(op.prob).unwrap();
+        let scale = E::from_f64(1.0 - op.prob).unwrap();
+        for (i, &go) in grad_out.iter().enumerate() {
+            if !dist.sample(&mut rng) {
+                grad_inp[physical_index(&inp.shape, &inp.strides, i)] += go / scale;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{shapes::*, tensor::*};
+
+use super::{
+    matmul::MatMatBatch3Kernel, reshape_to::ReshapeKernel, sum_to::SumKernel, ReshapeTo, SumTo,
+    TryMatMul,
+};
+
+use std::vec::Vec;
+
+/// Einstein summation over two tensors, e.g. `"bij,bjk->bik"` for a batched matmul.
+///
+/// Each input is labeled with one ascii letter per dimension, and the output lists the
+/// labels to keep. Labels in both inputs that aren't in the output are contracted (multiplied
+/// and summed), labels in only one input that aren't in the output are summed.
+///
+/// The contraction is planned as a single batched [TryMatMul::matmul], with permutes, reshapes
+/// and [SumTo::sum] around it, so backward works like for any other op.
+///
+/// The output shape `Dst` is specified by the caller, and must have one dimension per output label.
+///
+/// Unlike most ops, einsum does **not** check shapes at compile time: the spec is a
+/// runtime `&str`, and stable Rust can't parse strings in const generics to derive `Dst`
+/// from the input shapes, so the spec is only checked against the shapes of the inputs
+/// and `Dst` when the op runs. Use [TryMatMul] and the permute/reshape ops directly
+/// where a compile-time checked contraction is needed.
+///
+/// [Tensor::try_einsum] returns a [ShapeError] if the spec is malformed, if the number of labels
+/// doesn't match the number of dimensions of a tensor, if a label is repeated within an input
+/// (diagonals), if an output label doesn't appear in any input, or if the dimensions of a label
+/// don't match (including [Const] dimensions of `Dst`). [einsum()] and [Tensor::einsum] panic instead.
+///
+/// Batched bilinear form `x^T A y`:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let x: Tensor<Rank2<4, 3>, f32, _> = dev.ones();
+/// let a: Tensor<Rank2<3, 5>, f32, _> = dev.ones();
+/// let y: Tensor<Rank2<4, 5>, f32, _> = dev.ones();
+/// let xa: Tensor<Rank2<4, 5>, f32, _> = einsum("bi,ij->bj", x, a);
+/// let r: Tensor<Rank1<4>, f32, _> = einsum("bj,bj->b", xa, y);
+/// assert_eq!(r.array(), [15.0; 4]);
+/// ```
+///
+/// Attention scores with heads in the middle:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let q: Tensor<Rank4<2, 5, 4, 8>, f32, _> = dev.zeros();
+/// let k: Tensor<Rank4<2, 7, 4, 8>, f32, _> = dev.zeros();
+/// let _: Tensor<Rank4<2, 4, 5, 7>, f32, _> = einsum("bqhd,bkhd->bhqk", q, k);
+/// ```
+pub fn einsum<Dst: Shape, L: Shape, R: Shape, E: Dtype, D, T, RT>(
+    spec: &str,
+    lhs: Tensor<L, E, D, T>,
+    rhs: Tensor<R, E, D, RT>,
+) -> Tensor<Dst, E, D, T>
+where
+    D: MatMatBatch3Kernel<E> + ReshapeKernel<E> + SumKernel<E>,
+    T: Tape<E, D> + Merge<RT>,
+    RT: Tape<E, D>,
+{
+    lhs.einsum(spec, rhs)
+}
+
+/// The labels of each part of an einsum spec.
+struct EinsumSpec {
+    lhs: Vec<char>,
+    rhs: Vec<char>,
+    out: Vec<char>,
+}
+
+impl EinsumSpec {
+    /// Returns `None` if the spec is malformed.
+    fn parse(spec: &str) -> Option<Self> {
+        let (inputs, out) = spec.split_once("->")?;
+        let (lhs, rhs) = inputs.split_once(',')?;
+        let labels = |part: &str| {
+            let labels: Vec<char> = part.chars().filter(|c| !c.is_whitespace()).collect();
+            let valid = labels
+                .iter()
+                .enumerate()
+                .all(|(i, l)| l.is_ascii_alphabetic() && !labels[..i].contains(l));
+            valid.then_some(labels)
+        };
+        let parsed = Self {
+            lhs: labels(lhs)?,
+            rhs: labels(rhs)?,
+            out: labels(out)?,
+        };
+        parsed
+            .out
+            .iter()
+            .all(|l| parsed.lhs.contains(l) || parsed.rhs.contains(l))
+            .then_some(parsed)
+    }
+}
+
+/// The size of the dimension with label `l`.
+fn label_dim(labels: &[char], dims: &[usize], l: char) -> usize {
+    dims[labels.iter().position(|&x| x == l).unwrap()]
+}
+
+/// Where each of `order` is in `labels`.
+fn positions(labels: &[char], order: &[char]) -> Vec<usize> {
+    order
+        .iter()
+        .map(|l| labels.iter().position(|x| x == l).unwrap())
+        .collect()
+}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, T> Tensor<S, E, D, T> {
+    /// Views the dimensions of `self` in the order `perm`, without copying.
+    pub(super) fn permuted_dyn(self, perm: &[usize]) -> Tensor<DynShape, E, D, T> {
+        let dims: Vec<usize> = perm.iter().map(|&i| self.shape.concrete()[i]).collect();
+        let strides: Vec<usize> = perm.iter().map(|&i| self.strides[i]).collect();
+        let shape = DynShape::new(&dims);
+        Tensor {
+            id: self.id,
+            strides: shape.pad_strides(&strides),
+            data: self.data,
+            shape,
+            offset: self.offset,
+            device: self.device,
+            tape: self.tape,
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D, T> Tensor<S, E, D, T>
+where
+    D: MatMatBatch3Kernel<E> + ReshapeKernel<E> + SumKernel<E>,
+    T: Tape<E, D>,
+{
+    /// Einstein summation of `self` and `rhs`. See [einsum()] for examples.
+    pub fn einsum<Dst: Shape, R: Shape, RT>(
+        self,
+        spec: &str,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> Tensor<Dst, E, D, T>
+    where
+        T: Merge<RT>,
+        RT: Tape<E, D>,
+    {
+        self.try_einsum(spec, rhs).unwrap()
+    }
+
+    /// Fallible version of [Tensor::einsum]
+    pub fn try_einsum<Dst: Shape, R: Shape, RT>(
+        self,
+        spec: &str,
+        rhs: Tensor<R, E, D, RT>,
+    ) -> Result<Tensor<Dst, E, D, T>, D::Err>
+    where
+        T: Merge<RT>,
+        RT: Tape<E, D>,
+    {
+        let shape_err = ShapeError::new("einsum", &self.shape, &rhs.shape);
+        let spec = match EinsumSpec::parse(spec) {
+            Some(spec)
+                if spec.lhs.len() == S::NUM_DIMS
+                    && spec.rhs.len() == R::NUM_DIMS
+                    && spec.out.len() == Dst::NUM_DIMS =>
+            {
+                spec
+            }
+            _ => return Err(shape_err.into()),
+        };
+
+        let lhs_dims = self.shape.concrete();
+        let rhs_dims = rhs.shape.concrete();
+        let (lhs_dims, rhs_dims) = (lhs_dims.as_ref(), rhs_dims.as_ref());
+
+        let in_lhs = |l: &char| spec.lhs.contains(l);
+        let in_rhs = |l: &char| spec.rhs.contains(l);
+        let in_out = |l: &char| spec.out.contains(l);
+        let select = |labels: &[char], f: &dyn Fn(&char) -> bool| -> Vec<char> {
+            labels.iter().copied().filter(|l| f(l)).collect()
+        };
+        let batch = select(&spec.out, &|l| in_lhs(l) && in_rhs(l));
+        let lhs_free = select(&spec.out, &|l| !in_rhs(l));
+        let rhs_free = select(&spec.out, &|l| !in_lhs(l));
+        let contracted = select(&spec.lhs, &|l| in_rhs(l) && !in_out(l));
+        let lhs_summed = select(&spec.lhs, &|l| !in_rhs(l) && !in_out(l));
+        let rhs_summed = select(&spec.rhs, &|l| !in_lhs(l) && !in_out(l));
+
+        for &l in batch.iter().chain(contracted.iter()) {
+            if label_dim(&spec.lhs, lhs_dims, l) != label_dim(&spec.rhs, rhs_dims, l) {
+                return Err(shape_err.into());
+            }
+        }
+
+        let lhs_size = |labels: &[char]| -> usize {
+            labels
+                .iter()
+                .map(|&l| label_dim(&spec.lhs, lhs_dims, l))
+                .product()
+        };
+        let rhs_size = |labels: &[char]| -> usize {
+            labels
+                .iter()
+                .map(|&l| label_dim(&spec.rhs, rhs_dims, l))
+                .product()
+        };
+        let b = lhs_size(&batch);
+        let m = lhs_size(&lhs_free);
+        let k = lhs_size(&contracted);
+        let n = rhs_size(&rhs_free);
+
+        // lhs as (batch, lhs_free, contracted), with the lhs only labels summed out
+        let order: Vec<char> = [
+            batch.as_slice(),
+            lhs_free.as_slice(),
+            contracted.as_slice(),
+            lhs_summed.as_slice(),
+        ]
+        .concat();
+        let lhs = self.permuted_dyn(&positions(&spec.lhs, &order));
+        let lhs = if lhs_summed.is_empty() {
+            lhs.try_reshape_like(&(b, m, k))?
+        } else {
+            lhs.try_reshape_like(&(b * m * k, lhs_size(&lhs_summed)))?
+                .try_sum::<(usize,), Axis<1>>()?
+                .try_reshape_like(&(b, m, k))?
+        };
+
+        // rhs as (batch, contracted, rhs_free), with the rhs only labels summed out
+        let order: Vec<char> = [
+            batch.as_slice(),
+            contracted.as_slice(),
+            rhs_free.as_slice(),
+            rhs_summed.as_slice(),
+        ]
+        .concat();
+        let rhs = rhs.permuted_dyn(&positions(&spec.rhs, &order));
+        let rhs = if rhs_summed.is_empty() {
+            rhs.try_reshape_like(&(b, k, n))?
+        } else {
+            rhs.try_reshape_like(&(b * k * n, rhs_size(&rhs_summed)))?
+                .try_sum::<(usize,), Axis<1>>()?
+                .try_reshape_like(&(b, k, n))?
+        };
+
+        // (batch, lhs_free, rhs_free) permuted into the order of the output labels
+        let out = lhs.try_matmul(rhs)?;
+        let labels: Vec<char> =
+            [batch.as_slice(), lhs_free.as_slice(), rhs_free.as_slice()].concat();
+        let dims: Vec<usize> = labels
+            .iter()
+            .map(|l| match spec.lhs.iter().position(|x| x == l) {
+                Some(i) => lhs_dims[i],
+                None => label_dim(&spec.rhs, rhs_dims, *l),
+            })
+            .collect();
+        let out = out.try_reshape_like(&DynShape::new(&dims))?;
+        let perm: Vec<usize> = positions(&labels, &spec.out)
+            .into_iter()
+            .map(|i| MAX_DYN_DIMS - labels.len() + i)
+            .collect();
+        let out = out.permuted_dyn(&perm);
+
+        let mut dst_dims: Dst::Concrete = Default::default();
+        for (i, &d) in out.shape.dims().iter().enumerate() {
+            dst_dims[i] = d;
+        }
+        let dst = Dst::from_concrete(&dst_dims).ok_or(shape_err)?;
+        out.try_reshape_like(&dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_einsum_matmul() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank3<2, 4, 5>, TestDtype, _> = dev.sample_normal();
+        let r1: Tensor<Rank3<2, 3, 5>, _, _, _> = a.leaky_trace().einsum("bij,bjk->bik", b.clone());
+        let r2 = a.leaky_trace().matmul(b.clone());
+        assert_close_to_tensor!(r1, r2);
+
+        let g1 = r1.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_close_to_tensor!(g1.get(&a), g2.get(&a));
+        assert_close_to_tensor!(g1.get(&b), g2.get(&b));
+    }
+
+    #[test]
+    fn test_einsum_permuted_output() {
+        let dev: TestDevice = Default::default();
+        let q: Tensor<Rank4<2, 3, 4, 5>, TestDtype, _> = dev.sample_normal();
+        let k: Tensor<Rank4<2, 6, 4, 5>, TestDtype, _> = dev.sample_normal();
+        let r1: Tensor<Rank4<2, 4, 3, 6>, _, _, _> =
+            einsum("bqhd,bkhd->bhqk", q.leaky_trace(), k.leaky_trace());
+        let r2 = q
+            .leaky_trace()
+            .permute::<Rank4<2, 4, 3, 5>, _>()
+            .matmul(k.leaky_trace().permute::<Rank4<2, 4, 5, 6>, _>());
+        assert_close_to_tensor!(r1, r2);
+
+        let g1 = r1.square().mean().backward();
+        let g2 = r2.square().mean().backward();
+        assert_close_to_tensor!(g1.get(&q), g2.get(&q));
+        assert_close_to_tensor!(g1.get(&k), g2.get(&k));
+    }
+
+    #[test]
+    fn test_einsum_summed_labels() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<5, 4>, TestDtype, _> = dev.sample_normal();
+        // i is only in `a` and k is only in `b`, so both get summed
+        let r1: Tensor<Rank1<2>, _, _, _> = einsum("bij,kj->b", a.leaky_trace(), b.leaky_trace());
+        let a_sum = a.leaky_trace().sum::<Rank2<2, 4>, _>();
+        let b_sum = b.leaky_trace().sum::<Rank1<4>, _>();
+        let r2 = (a_sum * b_sum.broadcast()).sum::<Rank1<2>, _>();
+        assert_close_to_tensor!(r1, r2);
+
+        let g1 = r1.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_close_to_tensor!(g1.get(&a), g2.get(&a));
+        assert_close_to_tensor!(g1.get(&b), g2.get(&b));
+    }
+
+    #[test]
+    fn test_einsum_outer_and_transpose() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor([1.0, 2.0]).to_dtype::<TestDtype>();
+        let b = dev.tensor([3.0, 4.0, 5.0]).to_dtype::<TestDtype>();
+        let r: Tensor<Rank2<3, 2>, _, _> = einsum("i,j->ji", a, b);
+        assert_close_to_literal!(r, [[3.0, 6.0], [4.0, 8.0], [5.0, 10.0]]);
+    }
+
+    #[test]
+    fn test_einsum_shape_error() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(2, 3));
+        let b: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(4, 5));
+        let err = a
+            .try_einsum::<(usize, usize), _, _>("ij,jk->ik", b)
+            .unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("einsum", &(2, 3), &(4, 5)))
+        );
+    }
+
+    #[test]
+    fn test_einsum_bad_specs() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let b: Tensor<Rank2<3, 4>, TestDtype, _> = dev.zeros();
+        let expected = Some(ShapeError::new("einsum", a.shape(), b.shape()));
+        for spec in [
+            "bij,jk->ik",
+            "ij,jk->ijk",
+            "ij,jk",
+            "ij->ik",
+            "ii,jk->ik",
+            "i1,jk->ik",
+            "ij,jk->iz",
+        ] {
+            let err = a
+                .clone()
+                .try_einsum::<Rank2<2, 4>, _, _>(spec, b.clone())
+                .unwrap_err();
+            assert_eq!(shape_error(err), expected, "{spec}");
+        }
+        // a const output dimension that doesn't match
+        let err = a
+            .clone()
+            .try_einsum::<Rank2<2, 5>, _, _>("ij,jk->ik", b.clone())
+            .unwrap_err();
+        assert_eq!(shape_error(err), expected);
+    }
+
+    #[test]
+    #[should_panic = "op: \"einsum\""]
+    fn test_einsum_wrong_num_labels() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let b: Tensor<Rank2<3, 4>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank2<2, 4>, _, _> = einsum("bij,jk->ik", a, b);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::ELUKernelOp<F> {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        if x > F::zero() {
+            x
+        } else {
+            self.alpha * x.exp_m1()
+        }
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        if x > F::zero() {
+            F::one()
+        } else {
+            self.alpha * x.exp()
+        }
+    }
+}
@@ -0,0 +1,32 @@
+#include "unary_op_macros.cuh"
+
+template<typename F>
+struct ELUKernelOp {
+    F alpha;
+};
+
+template<typename T>
+__device__ __forceinline__ T elu_fwd(ELUKernelOp<T> op, T x) {
+    T zero = 0.0;
+    T one = 1.0;
+    return x > zero ? x : op.alpha * (expg(x) - one);
+}
+
+template<typename T>
+__device__ __forceinline__ T elu_bwd(ELUKernelOp<T> op, T x) {
+    T zero = 0.0;
+    T one = 1.0;
+    return x > zero ? one : op.alpha * expg(x);
+}
+
+UNARY_OP(__half, elu_fwd_f16, elu_bwd_f16, ELUKernelOp<__half>,
+    elu_fwd(op, x),
+    elu_bwd(op, x))
+
+UNARY_OP(float, elu_fwd_f32, elu_bwd_f32, ELUKernelOp<float>,
+    elu_fwd(op, x),
+    elu_bwd(op, x))
+
+UNARY_OP(double, elu_fwd_f64, elu_bwd_f64, ELUKernelOp<double>,
+    elu_fwd(op, x),
+    elu_bwd(op, x))
@@ -0,0 +1,59 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ELUKernelOp<E> {
+    pub alpha: E,
+}
+
+/// [Exponential Linear Unit (ELU)](https://paperswithcode.com/method/elu).
+/// `t` if `t > 0`, otherwise `alpha * (exp(t) - 1)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.elu(1.0);
+/// ```
+pub fn elu<S: Shape, E: Dtype, D: UnaryKernel<ELUKernelOp<E>, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    alpha: impl Into<f64>,
+) -> Tensor<S, E, D, T> {
+    t.elu(alpha)
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<ELUKernelOp<E>, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [elu]
+    pub fn elu(self, alpha: impl Into<f64>) -> Self {
+        self.try_elu(alpha).unwrap()
+    }
+    /// See [elu]
+    pub fn try_elu(self, alpha: impl Into<f64>) -> Result<Self, D::Err> {
+        let alpha = E::from_f64(alpha.into()).unwrap();
+        try_unary_op(ELUKernelOp { alpha }, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_elu() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().elu(0.5);
+        assert_close_to_literal!(r, [-0.43233236, -0.31606028, 0.0, 1.0, 2.0]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(g.get(&x), [0.01353353, 0.03678794, 0.1, 0.2, 0.2]);
+    }
+}
@@ -0,0 +1,16 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use crate::tensor_ops::special::erf;
+use num_traits::{Float, FloatConst};
+
+impl<F: Float + FloatConst> UnaryDerivative<F> for super::ErfKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        F::from(erf(x.to_f64().unwrap())).unwrap()
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        F::FRAC_2_SQRT_PI() * (-x * x).exp()
+    }
+}
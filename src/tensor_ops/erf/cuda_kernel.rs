@@ -0,0 +1,11 @@
+use super::ErfKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for ErfKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/erf.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(ErfKernelOp, half::f16, PTX, "erf_fwd_f16", "erf_bwd_f16");
+cuda_unary!(ErfKernelOp, f32, PTX, "erf_fwd_f32", "erf_bwd_f32");
+cuda_unary!(ErfKernelOp, f64, PTX, "erf_fwd_f64", "erf_bwd_f64");
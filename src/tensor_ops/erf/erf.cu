@@ -0,0 +1,23 @@
+#include "unary_op_macros.cuh"
+#define _USE_MATH_DEFINES
+#include <math.h>
+
+struct ErfKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T erf_bwd(T x) {
+    T frac_2_sqrt_pi = M_2_SQRTPI;
+    return frac_2_sqrt_pi * expg(-x * x);
+}
+
+UNARY_OP(__half, erf_fwd_f16, erf_bwd_f16, ErfKernelOp,
+        erfg(x),
+        erf_bwd(x))
+
+UNARY_OP(float, erf_fwd_f32, erf_bwd_f32, ErfKernelOp,
+        erfg(x),
+        erf_bwd(x))
+
+UNARY_OP(double, erf_fwd_f64, erf_bwd_f64, ErfKernelOp,
+        erfg(x),
+        erf_bwd(x))
@@ -0,0 +1,65 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ErfKernelOp;
+
+/// The [error function](https://en.wikipedia.org/wiki/Error_function). `2 / sqrt(pi) * integral(exp(-s^2), s=0..t)`
+///
+/// The derivative is `2 / sqrt(pi) * exp(-t^2)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.erf();
+/// ```
+pub fn erf<S: Shape, E: Dtype, D: UnaryKernel<ErfKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.erf()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<ErfKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [erf]
+    pub fn erf(self) -> Self {
+        self.try_erf().unwrap()
+    }
+    /// See [erf]
+    pub fn try_erf(self) -> Result<Self, D::Err> {
+        try_unary_op(ErfKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_erf() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().erf();
+        assert_close_to_literal!(r, [-0.99532227, -0.84270079, 0.0, 0.84270079, 0.99532227]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [
+                0.0041333971,
+                0.083021499,
+                0.22567583,
+                0.083021499,
+                0.0041333971
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use super::ErfcKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for ErfcKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/erfc.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(ErfcKernelOp, half::f16, PTX, "erfc_fwd_f16", "erfc_bwd_f16");
+cuda_unary!(ErfcKernelOp, f32, PTX, "erfc_fwd_f32", "erfc_bwd_f32");
+cuda_unary!(ErfcKernelOp, f64, PTX, "erfc_fwd_f64", "erfc_bwd_f64");
@@ -0,0 +1,23 @@
+#include "unary_op_macros.cuh"
+#define _USE_MATH_DEFINES
+#include <math.h>
+
+struct ErfcKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T erfc_bwd(T x) {
+    T frac_2_sqrt_pi = M_2_SQRTPI;
+    return -frac_2_sqrt_pi * expg(-x * x);
+}
+
+UNARY_OP(__half, erfc_fwd_f16, erfc_bwd_f16, ErfcKernelOp,
+        erfcg(x),
+        erfc_bwd(x))
+
+UNARY_OP(float, erfc_fwd_f32, erfc_bwd_f32, ErfcKernelOp,
+        erfcg(x),
+        erfc_bwd(x))
+
+UNARY_OP(double, erfc_fwd_f64, erfc_bwd_f64, ErfcKernelOp,
+        erfcg(x),
+        erfc_bwd(x))
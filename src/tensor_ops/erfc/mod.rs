@@ -0,0 +1,65 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ErfcKernelOp;
+
+/// The complementary [error function](https://en.wikipedia.org/wiki/Error_function). `1 - erf(t)`
+///
+/// Unlike `1 - erf(t)`, this keeps its precision for large `t`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.erfc();
+/// ```
+pub fn erfc<S: Shape, E: Dtype, D: UnaryKernel<ErfcKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.erfc()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<ErfcKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [erfc]
+    pub fn erfc(self) -> Self {
+        self.try_erfc().unwrap()
+    }
+    /// See [erfc]
+    pub fn try_erfc(self) -> Result<Self, D::Err> {
+        try_unary_op(ErfcKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_erfc() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().erfc();
+        assert_close_to_literal!(r, [1.9953223, 1.8427008, 1.0, 0.15729921, 0.004677735]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [
+                -0.0041333971,
+                -0.083021499,
+                -0.22567583,
+                -0.083021499,
+                -0.0041333971
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use super::Expm1KernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for Expm1KernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/expm1.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(df(f(x)) Expm1KernelOp, half::f16, PTX, "expm1_fwd_f16", "expm1_bwd_f16");
+cuda_unary!(df(f(x)) Expm1KernelOp, f32, PTX, "expm1_fwd_f32", "expm1_bwd_f32");
+cuda_unary!(df(f(x)) Expm1KernelOp, f64, PTX, "expm1_fwd_f64", "expm1_bwd_f64");
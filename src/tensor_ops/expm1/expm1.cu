@@ -0,0 +1,21 @@
+#include "unary_op_macros.cuh"
+
+struct Expm1KernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T expm1_bwd(T y) {
+    T one = 1.0;
+    return y + one;
+}
+
+UNARY_OP(__half, expm1_fwd_f16, expm1_bwd_f16, Expm1KernelOp,
+        expm1g(x),
+        expm1_bwd(y))
+
+UNARY_OP(float, expm1_fwd_f32, expm1_bwd_f32, Expm1KernelOp,
+        expm1g(x),
+        expm1_bwd(y))
+
+UNARY_OP(double, expm1_fwd_f64, expm1_bwd_f64, Expm1KernelOp,
+        expm1g(x),
+        expm1_bwd(y))
@@ -0,0 +1,59 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Expm1KernelOp;
+
+/// `exp(t) - 1`, which is more accurate than [exp()](super::exp()) for `t` close to zero.
+///
+/// The derivative is `exp(t)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1e-8, 1.0]);
+/// let r = t.expm1();
+/// ```
+pub fn expm1<S: Shape, E: Dtype, D: UnaryKernel<Expm1KernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.expm1()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<Expm1KernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [expm1]
+    pub fn expm1(self) -> Self {
+        self.try_expm1().unwrap()
+    }
+    /// See [expm1]
+    pub fn try_expm1(self) -> Result<Self, D::Err> {
+        try_unary_op(Expm1KernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_expm1() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().expm1();
+        assert_close_to_literal!(r, [-0.86466472, -0.63212056, 0.0, 1.7182818, 6.3890561]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [0.027067057, 0.073575888, 0.2, 0.54365637, 1.4778112]
+        );
+    }
+}
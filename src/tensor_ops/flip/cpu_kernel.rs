@@ -0,0 +1,46 @@
+use crate::{
+    shapes::{Axes, Dtype, Shape},
+    tensor::{
+        cpu::{index_to_i, LendingIterator, NdIndex},
+        Cpu, GhostTensor, Tensor, ZerosTensor,
+    },
+};
+
+/// Returns the index that `idx` is moved to when flipping along `Ax`.
+fn flipped<S: Shape, Ax: Axes>(shape: &S, mut idx: S::Concrete) -> S::Concrete {
+    let dims = shape.concrete();
+    for ax in Ax::as_array().into_iter() {
+        let d = ax as usize;
+        idx[d] = dims[d] - 1 - idx[d];
+    }
+    idx
+}
+
+impl<E: Dtype> super::FlipKernel<E> for Cpu {
+    fn forward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let mut out = self.try_zeros_like(&inp.shape)?;
+        let mut out_iter = out.iter_mut_with_index();
+        while let Some((x, i)) = out_iter.next() {
+            *x = inp[flipped::<S, Ax>(&inp.shape, i)];
+        }
+        Ok(out)
+    }
+
+    fn backward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut out_idx = NdIndex::new(out.shape, out.strides);
+        while let Some((i_out, i)) = out_idx.next_with_idx() {
+            let i = flipped::<S, Ax>(&inp.shape, i);
+            grad_inp[index_to_i(&inp.shape, &inp.strides, i)] += grad_out[i_out];
+        }
+        Ok(())
+    }
+}
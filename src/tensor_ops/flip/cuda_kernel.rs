@@ -0,0 +1,94 @@
+use crate::{
+    shapes::{Axes, Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::LaunchAsync;
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/flip.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &["flip_fwd_f16", "flip_bwd_f16"];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &["flip_fwd_f32", "flip_bwd_f32"];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &["flip_fwd_f64", "flip_bwd_f64"];
+}
+
+/// 1 for every dimension that is flipped, 0 otherwise
+fn flip_flags<S: Shape, Ax: Axes>() -> Vec<usize> {
+    let mut flags = std::vec![0; S::NUM_DIMS];
+    for ax in Ax::as_array().into_iter() {
+        flags[ax as usize] = 1;
+    }
+    flags
+}
+
+impl<E: Dtype> super::FlipKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+
+        let numel = inp.shape.num_elements();
+        let mut out = unsafe { self.alloc_empty::<E>(numel) }?;
+        let dims = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+        let flip = self.dev.htod_copy(flip_flags::<S, Ax>())?;
+
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            S::NUM_DIMS,
+            numel,
+            &dims,
+            &inp_strides,
+            &flip,
+            inp.data.as_ref(),
+            &mut out,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok(self.build_tensor(inp.shape, inp.shape.strides(), out))
+    }
+
+    fn backward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let numel = out.shape.num_elements();
+        let dims = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+        let out_strides = self.dev.htod_copy(out.strides.into())?;
+        let flip = self.dev.htod_copy(flip_flags::<S, Ax>())?;
+
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            S::NUM_DIMS,
+            numel,
+            &dims,
+            &inp_strides,
+            &out_strides,
+            &flip,
+            grad_inp,
+            grad_out,
+        );
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
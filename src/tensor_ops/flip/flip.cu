@@ -0,0 +1,85 @@
+#include "cuda_utils.cuh"
+
+// Computes the index into `inp` that the `i`th element of the flipped tensor reads from.
+__device__ size_t get_flipped_index(
+    size_t i,
+    const size_t num_dims,
+    const size_t *dims,
+    const size_t *inp_strides,
+    const size_t *flip
+) {
+    size_t inp_i = 0;
+    for (int d = num_dims - 1; d >= 0; d--) {
+        size_t j = i % dims[d];
+        i /= dims[d];
+        if (flip[d]) {
+            j = dims[d] - 1 - j;
+        }
+        inp_i += j * inp_strides[d];
+    }
+    return inp_i;
+}
+
+template<typename T>
+__device__ void flip_fwd(
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *dims,
+    const size_t *inp_strides,
+    const size_t *flip,
+    const T *inp,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    out[i] = inp[get_flipped_index(i, num_dims, dims, inp_strides, flip)];
+}
+
+template<typename T>
+__device__ void flip_bwd(
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *dims,
+    const size_t *inp_strides,
+    const size_t *out_strides,
+    const size_t *flip,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t inp_i = get_flipped_index(i, num_dims, dims, inp_strides, flip);
+    size_t out_i = get_strided_index(i, num_dims, dims, out_strides);
+    atomicAdd(grad_inp + inp_i, grad_out[out_i]);
+}
+
+#define FLIP(TY, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *dims, \
+    const size_t *inp_strides, \
+    const size_t *flip, \
+    const TY *inp, \
+    TY *out \
+) { flip_fwd(num_dims, numel, dims, inp_strides, flip, inp, out); } \
+extern "C" __global__ void BWD( \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *dims, \
+    const size_t *inp_strides, \
+    const size_t *out_strides, \
+    const size_t *flip, \
+    TY *grad_inp, \
+    const TY *grad_out \
+) { flip_bwd(num_dims, numel, dims, inp_strides, out_strides, flip, grad_inp, grad_out); }
+
+FLIP(__half, flip_fwd_f16, flip_bwd_f16);
+FLIP(float, flip_fwd_f32, flip_bwd_f32);
+FLIP(double, flip_fwd_f64, flip_bwd_f64);
@@ -0,0 +1,118 @@
+use crate::{
+    shapes::{Axes, Dtype, HasAxes, HasShape, Shape},
+    tensor::*,
+};
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+pub trait FlipKernel<E: Dtype>: DeviceStorage {
+    fn forward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err>;
+    fn backward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Reverses the order of the data along one or more axes.
+///
+/// **pytorch equivalent** `torch.flip`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+/// let r = t.clone().flip::<Axis<1>>();
+/// assert_eq!(r.array(), [[3.0, 2.0, 1.0], [6.0, 5.0, 4.0]]);
+/// let r = t.flip::<Axes2<0, 1>>();
+/// assert_eq!(r.array(), [[6.0, 5.0, 4.0], [3.0, 2.0, 1.0]]);
+/// ```
+pub trait Flip: HasShape + HasErr {
+    /// Reverses the order of the data along the axes `Ax`.
+    fn flip<Ax: Axes>(self) -> Self
+    where
+        Self::Shape: HasAxes<Ax>,
+    {
+        self.try_flip::<Ax>().unwrap()
+    }
+
+    /// Reverses the order of the data along the axes `Ax`.
+    fn try_flip<Ax: Axes>(self) -> Result<Self, Self::Err>
+    where
+        Self::Shape: HasAxes<Ax>;
+}
+
+impl<S: Shape, E: Dtype, D: FlipKernel<E>, T: Tape<E, D>> Flip for Tensor<S, E, D, T> {
+    fn try_flip<Ax: Axes>(self) -> Result<Self, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward::<S, Ax>(&inp)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            inp.device
+                .backward::<S, Ax>(&inp_ghost, grad_inp, &out_ghost, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_flip_2d() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        assert_close_to_literal!(
+            t.clone().flip::<Axis<0>>(),
+            [[4.0, 5.0, 6.0], [1.0, 2.0, 3.0]]
+        );
+        assert_close_to_literal!(
+            t.clone().flip::<Axis<1>>(),
+            [[3.0, 2.0, 1.0], [6.0, 5.0, 4.0]]
+        );
+        assert_close_to_literal!(t.flip::<Axes2<0, 1>>(), [[6.0, 5.0, 4.0], [3.0, 2.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_flip_broadcasted_backward() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([1.0, 2.0, 3.0]).to_dtype::<TestDtype>();
+        let w = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let y = t
+            .leaky_trace()
+            .broadcast::<Rank2<2, 3>, _>()
+            .flip::<Axes2<0, 1>>();
+        assert_close_to_literal!(y, [[3.0, 2.0, 1.0]; 2]);
+        let g = (y * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [9.0, 7.0, 5.0]);
+    }
+
+    #[test]
+    fn test_flip_twice_is_identity() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let r = t.clone().flip::<Axes2<0, 2>>().flip::<Axes2<2, 0>>();
+        assert_eq!(r.array(), t.array());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{
+    shapes::{Axes, Dtype, Shape},
+    tensor::{
+        reference::{physical_index, ravel_index, unravel_index},
+        GhostTensor, Reference, Tensor,
+    },
+};
+
+/// Returns the index into `inp` of the `i`th (row major) entry of the flipped tensor.
+fn source_index<S: Shape, Ax: Axes>(shape: &S, i: usize) -> S::Concrete {
+    let dims = shape.concrete();
+    let mut idx = unravel_index(shape, i);
+    for ax in Ax::as_array().into_iter() {
+        let d = ax as usize;
+        idx[d] = dims[d] - 1 - idx[d];
+    }
+    idx
+}
+
+impl<E: Dtype> super::FlipKernel<E> for Reference {
+    fn forward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let out = (0..inp.shape.num_elements())
+            .map(|i| inp[source_index::<S, Ax>(&inp.shape, i)])
+            .collect();
+        Ok(self.build_tensor(inp.shape, out))
+    }
+
+    fn backward<S: Shape, Ax: Axes>(
+        &self,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for i in 0..out.shape.num_elements() {
+            let i_inp = source_index::<S, Ax>(&inp.shape, i);
+            grad_inp[ravel_index::<S>(&inp.strides, &i_inp)] +=
+                grad_out[physical_index(&out.shape, &out.strides, i)];
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use super::FloorKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for FloorKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/floor.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(const_df() FloorKernelOp, half::f16, PTX, "floor_fwd_f16", "floor_bwd_f16");
+cuda_unary!(const_df() FloorKernelOp, f32, PTX, "floor_fwd_f32", "floor_bwd_f32");
+cuda_unary!(const_df() FloorKernelOp, f64, PTX, "floor_fwd_f64", "floor_bwd_f64");
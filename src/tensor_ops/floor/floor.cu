@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+struct FloorKernelOp {};
+
+UNARY_OP(__half, floor_fwd_f16, floor_bwd_f16, FloorKernelOp,
+        floorg(x),
+        0.0)
+
+UNARY_OP(float, floor_fwd_f32, floor_bwd_f32, FloorKernelOp,
+        floorg(x),
+        0.0)
+
+UNARY_OP(double, floor_fwd_f64, floor_bwd_f64, FloorKernelOp,
+        floorg(x),
+        0.0)
@@ -12,6 +12,8 @@ pub struct GeLUKernelOp;
 
 /// [Gaussian Linear Unit (GeLU)](https://paperswithcode.com/method/gelu). `0.5 * x * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3)))`
 ///
+/// This is the `tanh` approximation of GeLU, see [accurate_gelu()](super::accurate_gelu()) for the exact version.
+///
 /// Examples:
 /// ```rust
 /// # use dfdx::prelude::*;
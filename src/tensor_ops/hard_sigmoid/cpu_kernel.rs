@@ -0,0 +1,21 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::HardSigmoidKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        let six = F::from(6.0).unwrap();
+        let half = F::from(0.5).unwrap();
+        (x / six + half).max(F::zero()).min(F::one())
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        let three = F::from(3.0).unwrap();
+        if x > -three && x < three {
+            F::one() / F::from(6.0).unwrap()
+        } else {
+            F::zero()
+        }
+    }
+}
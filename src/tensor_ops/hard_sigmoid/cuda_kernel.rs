@@ -0,0 +1,29 @@
+use super::HardSigmoidKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for HardSigmoidKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/hard_sigmoid.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(
+    HardSigmoidKernelOp,
+    half::f16,
+    PTX,
+    "hard_sigmoid_fwd_f16",
+    "hard_sigmoid_bwd_f16"
+);
+cuda_unary!(
+    HardSigmoidKernelOp,
+    f32,
+    PTX,
+    "hard_sigmoid_fwd_f32",
+    "hard_sigmoid_bwd_f32"
+);
+cuda_unary!(
+    HardSigmoidKernelOp,
+    f64,
+    PTX,
+    "hard_sigmoid_fwd_f64",
+    "hard_sigmoid_bwd_f64"
+);
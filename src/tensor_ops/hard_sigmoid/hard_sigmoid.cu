@@ -0,0 +1,33 @@
+#include "unary_op_macros.cuh"
+
+struct HardSigmoidKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T hard_sigmoid_fwd(T x) {
+    T zero = 0.0;
+    T one = 1.0;
+    T half = 0.5;
+    T six = 6.0;
+    return ming(maxg(x / six + half, zero), one);
+}
+
+template<typename T>
+__device__ __forceinline__ T hard_sigmoid_bwd(T x) {
+    T zero = 0.0;
+    T one = 1.0;
+    T three = 3.0;
+    T six = 6.0;
+    return x > -three && x < three ? one / six : zero;
+}
+
+UNARY_OP(__half, hard_sigmoid_fwd_f16, hard_sigmoid_bwd_f16, HardSigmoidKernelOp,
+        hard_sigmoid_fwd(x),
+        hard_sigmoid_bwd(x))
+
+UNARY_OP(float, hard_sigmoid_fwd_f32, hard_sigmoid_bwd_f32, HardSigmoidKernelOp,
+        hard_sigmoid_fwd(x),
+        hard_sigmoid_bwd(x))
+
+UNARY_OP(double, hard_sigmoid_fwd_f64, hard_sigmoid_bwd_f64, HardSigmoidKernelOp,
+        hard_sigmoid_fwd(x),
+        hard_sigmoid_bwd(x))
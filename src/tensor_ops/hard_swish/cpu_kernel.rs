@@ -0,0 +1,23 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::HardSwishKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        let three = F::from(3.0).unwrap();
+        let six = F::from(6.0).unwrap();
+        x * (x + three).max(F::zero()).min(six) / six
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        let three = F::from(3.0).unwrap();
+        if x < -three {
+            F::zero()
+        } else if x > three {
+            F::one()
+        } else {
+            x / three + F::from(0.5).unwrap()
+        }
+    }
+}
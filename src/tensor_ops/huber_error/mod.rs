@@ -55,7 +55,7 @@ impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
         T: Merge<R>,
     {
         let delta = E::from_f64(delta.into()).unwrap();
-        try_binary_op(HuberErrorKernelOp { delta }, self, rhs)
+        try_binary_op("huber_error", HuberErrorKernelOp { delta }, self, rhs)
     }
 }
 
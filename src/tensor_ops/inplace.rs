@@ -0,0 +1,305 @@
+//! In-place versions of tensor ops for tensors without a tape.
+//!
+//! These keep the id of the tensor, so it can be updated without re-binding it.
+//! [Tensor::copy_from_tensor] writes into the existing buffer when nothing else
+//! references it. The arithmetic ops share the buffer with the op they run, and
+//! only replace it once the op has succeeded, so if one of the `try_*` methods
+//! returns an error the tensor is left unchanged.
+
+use crate::{
+    shapes::{Dtype, Shape, ShapeError},
+    tensor::{DeviceStorage, HasErr, NoneTape, Tensor},
+};
+
+use super::{clamp::ClampKernelOp, ops::UnaryKernel, TryAdd, TryDiv, TryMul, TrySub};
+
+use std::sync::Arc;
+
+impl<S: Shape, E: Dtype, D: DeviceStorage> Tensor<S, E, D, NoneTape> {
+    /// Replaces `self` with `f(self)`, keeping the same id. `self` is untouched if
+    /// `f` fails.
+    fn try_update(&mut self, f: impl FnOnce(Self) -> Result<Self, D::Err>) -> Result<(), D::Err> {
+        let inp = Tensor {
+            id: self.id,
+            data: self.data.clone(),
+            shape: self.shape,
+            strides: self.strides,
+            offset: self.offset,
+            device: self.device.clone(),
+            tape: NoneTape,
+        };
+        let out = f(inp)?;
+        self.data = out.data;
+        self.strides = out.strides;
+        self.offset = out.offset;
+        Ok(())
+    }
+
+    /// Computes `self = self + rhs` in place. `rhs` can be a tensor or a scalar.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut a = dev.tensor([1.0, 2.0, 3.0]);
+    /// a.add_assign(dev.tensor([1.0, 1.0, 1.0]));
+    /// a.add_assign(0.5);
+    /// assert_eq!(a.array(), [2.5, 3.5, 4.5]);
+    /// ```
+    pub fn add_assign<Rhs>(&mut self, rhs: Rhs)
+    where
+        Self: TryAdd<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_add_assign(rhs).unwrap()
+    }
+
+    /// See [Tensor::add_assign]
+    pub fn try_add_assign<Rhs>(&mut self, rhs: Rhs) -> Result<(), D::Err>
+    where
+        Self: TryAdd<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_update(|t| t.try_add(rhs))
+    }
+
+    /// Computes `self = self - rhs` in place. `rhs` can be a tensor or a scalar.
+    pub fn sub_assign<Rhs>(&mut self, rhs: Rhs)
+    where
+        Self: TrySub<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_sub_assign(rhs).unwrap()
+    }
+
+    /// See [Tensor::sub_assign]
+    pub fn try_sub_assign<Rhs>(&mut self, rhs: Rhs) -> Result<(), D::Err>
+    where
+        Self: TrySub<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_update(|t| t.try_sub(rhs))
+    }
+
+    /// Computes `self = self * rhs` in place. `rhs` can be a tensor or a scalar.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut a = dev.tensor([1.0, 2.0, 3.0]);
+    /// a.mul_assign(2.0);
+    /// assert_eq!(a.array(), [2.0, 4.0, 6.0]);
+    /// ```
+    pub fn mul_assign<Rhs>(&mut self, rhs: Rhs)
+    where
+        Self: TryMul<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_mul_assign(rhs).unwrap()
+    }
+
+    /// See [Tensor::mul_assign]
+    pub fn try_mul_assign<Rhs>(&mut self, rhs: Rhs) -> Result<(), D::Err>
+    where
+        Self: TryMul<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_update(|t| t.try_mul(rhs))
+    }
+
+    /// Computes `self = self / rhs` in place. `rhs` can be a tensor or a scalar.
+    pub fn div_assign<Rhs>(&mut self, rhs: Rhs)
+    where
+        Self: TryDiv<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_div_assign(rhs).unwrap()
+    }
+
+    /// See [Tensor::div_assign]
+    pub fn try_div_assign<Rhs>(&mut self, rhs: Rhs) -> Result<(), D::Err>
+    where
+        Self: TryDiv<Rhs> + HasErr<Err = D::Err>,
+    {
+        self.try_update(|t| t.try_div(rhs))
+    }
+
+    /// Clamps all elements between `min` and `max` in place. See [super::clamp()].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut a = dev.tensor([-1.0, 0.0, 1.0]);
+    /// a.clamp_(-0.5, 0.5);
+    /// assert_eq!(a.array(), [-0.5, 0.0, 0.5]);
+    /// ```
+    pub fn clamp_(&mut self, min: impl Into<f64>, max: impl Into<f64>)
+    where
+        D: UnaryKernel<ClampKernelOp<E>, E>,
+    {
+        self.try_clamp_(min, max).unwrap()
+    }
+
+    /// See [Tensor::clamp_]
+    pub fn try_clamp_(&mut self, min: impl Into<f64>, max: impl Into<f64>) -> Result<(), D::Err>
+    where
+        D: UnaryKernel<ClampKernelOp<E>, E>,
+    {
+        self.try_update(|t| t.try_clamp(min, max))
+    }
+
+    /// Copies the values of `src` into `self`. If neither tensor is a view or
+    /// broadcasted and nothing else references `self`'s data, the existing buffer
+    /// is re-used.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut a = dev.tensor([1.0, 2.0, 3.0]);
+    /// a.copy_from_tensor(&dev.tensor([4.0, 5.0, 6.0]));
+    /// assert_eq!(a.array(), [4.0, 5.0, 6.0]);
+    /// ```
+    pub fn copy_from_tensor<T>(&mut self, src: &Tensor<S, E, D, T>) {
+        self.try_copy_from_tensor(src).unwrap()
+    }
+
+    /// Fallible version of [Tensor::copy_from_tensor]. Returns a [ShapeError] if
+    /// the shapes of `self` and `src` differ.
+    pub fn try_copy_from_tensor<T>(&mut self, src: &Tensor<S, E, D, T>) -> Result<(), D::Err> {
+        if self.shape != src.shape {
+            return Err(ShapeError::new("copy_from_tensor", &self.shape, &src.shape).into());
+        }
+        let reuse = self.strides == src.strides && self.is_whole() && src.is_whole();
+        match Arc::get_mut(&mut self.data) {
+            Some(data) if reuse => data.clone_from(&src.data),
+            _ => {
+                // otherwise just share `src`'s data, which is copied on the next write
+                self.data = src.data.clone();
+                self.strides = src.strides;
+                self.offset = src.offset;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, Rhs> std::ops::AddAssign<Rhs>
+    for Tensor<S, E, D, NoneTape>
+where
+    Self: TryAdd<Rhs> + HasErr<Err = D::Err>,
+{
+    fn add_assign(&mut self, rhs: Rhs) {
+        self.try_add_assign(rhs).unwrap()
+    }
+}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, Rhs> std::ops::SubAssign<Rhs>
+    for Tensor<S, E, D, NoneTape>
+where
+    Self: TrySub<Rhs> + HasErr<Err = D::Err>,
+{
+    fn sub_assign(&mut self, rhs: Rhs) {
+        self.try_sub_assign(rhs).unwrap()
+    }
+}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, Rhs> std::ops::MulAssign<Rhs>
+    for Tensor<S, E, D, NoneTape>
+where
+    Self: TryMul<Rhs> + HasErr<Err = D::Err>,
+{
+    fn mul_assign(&mut self, rhs: Rhs) {
+        self.try_mul_assign(rhs).unwrap()
+    }
+}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, Rhs> std::ops::DivAssign<Rhs>
+    for Tensor<S, E, D, NoneTape>
+where
+    Self: TryDiv<Rhs> + HasErr<Err = D::Err>,
+{
+    fn div_assign(&mut self, rhs: Rhs) {
+        self.try_div_assign(rhs).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_inplace_scalar_ops() {
+        let dev: TestDevice = Default::default();
+        let mut a = dev.tensor([1.0, 2.0, 3.0]).to_dtype::<TestDtype>();
+        a += TestDtype::from_f32(1.0).unwrap();
+        a *= TestDtype::from_f32(3.0).unwrap();
+        a -= TestDtype::from_f32(2.0).unwrap();
+        a /= TestDtype::from_f32(2.0).unwrap();
+        assert_close_to_literal!(a, [2.0, 3.5, 5.0]);
+        a.clamp_(2.5, 4.0);
+        assert_close_to_literal!(a, [2.5, 3.5, 4.0]);
+    }
+
+    #[test]
+    fn test_inplace_tensor_ops() {
+        let dev: TestDevice = Default::default();
+        let mut a = dev.tensor([[1.0, 2.0], [3.0, 4.0]]).to_dtype::<TestDtype>();
+        let b = dev.tensor([0.5, 2.0]).to_dtype::<TestDtype>();
+        a += b.clone().broadcast::<Rank2<2, 2>, Axis<0>>();
+        assert_close_to_literal!(a, [[1.5, 4.0], [3.5, 6.0]]);
+        a *= b.clone().broadcast::<Rank2<2, 2>, Axis<0>>();
+        assert_close_to_literal!(a, [[0.75, 8.0], [1.75, 12.0]]);
+        a -= a.clone();
+        assert_close_to_literal!(a, [[0.0; 2]; 2]);
+    }
+
+    #[test]
+    fn test_inplace_keeps_id_and_buffer() {
+        let dev: Cpu = Default::default();
+        let mut a: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+        let b: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+        let id = a.id;
+        a.mul_assign(2.0);
+        a.add_assign(b.clone());
+        a.clamp_(-1.0, 1.0);
+        assert_eq!(a.id, id);
+        let ptr = a.data.as_ptr();
+        a.copy_from_tensor(&b);
+        assert_eq!(a.id, id);
+        assert_eq!(a.data.as_ptr(), ptr);
+        assert_eq!(a.array(), b.array());
+    }
+
+    #[test]
+    fn test_inplace_error_leaves_tensor_unchanged() {
+        let dev: TestDevice = Default::default();
+        let mut a: Tensor<(usize,), TestDtype, _> = dev.tensor_from_vec(std::vec![1.0, 2.0], (2,));
+        let b: Tensor<(usize,), TestDtype, _> = dev.tensor_from_vec(std::vec![1.0, 2.0, 3.0], (3,));
+        let err = a.try_add_assign(b.clone()).unwrap_err();
+        assert_eq!(shape_error(err), Some(ShapeError::new("add", &(2,), &(3,))));
+        assert_eq!(a.as_vec(), [1.0, 2.0]);
+
+        let err = a.try_copy_from_tensor(&b).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("copy_from_tensor", &(2,), &(3,)))
+        );
+        assert_eq!(a.as_vec(), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_inplace_does_not_modify_clones() {
+        let dev: TestDevice = Default::default();
+        let mut a = dev.tensor([1.0, 2.0]).to_dtype::<TestDtype>();
+        let a_clone = a.clone();
+        a += TestDtype::from_f32(1.0).unwrap();
+        assert_close_to_literal!(a, [2.0, 3.0]);
+        assert_close_to_literal!(a_clone, [1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_copy_from_view() {
+        let dev: TestDevice = Default::default();
+        let a = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let mut b: Tensor<Rank2<1, 2>, TestDtype, _> = dev.zeros();
+        b.copy_from_tensor(&a.clone().slice((1.., 1..)).realize());
+        assert_close_to_literal!(b, [[5.0, 6.0]]);
+        b += TestDtype::from_f32(1.0).unwrap();
+        assert_close_to_literal!(b, [[6.0, 7.0]]);
+        assert_close_to_literal!(a, [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    }
+}
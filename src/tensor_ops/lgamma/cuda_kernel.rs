@@ -0,0 +1,17 @@
+use super::LGammaKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for LGammaKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/lgamma.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(
+    LGammaKernelOp,
+    half::f16,
+    PTX,
+    "lgamma_fwd_f16",
+    "lgamma_bwd_f16"
+);
+cuda_unary!(LGammaKernelOp, f32, PTX, "lgamma_fwd_f32", "lgamma_bwd_f32");
+cuda_unary!(LGammaKernelOp, f64, PTX, "lgamma_fwd_f64", "lgamma_bwd_f64");
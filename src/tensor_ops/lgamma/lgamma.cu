@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+struct LGammaKernelOp {};
+
+UNARY_OP(__half, lgamma_fwd_f16, lgamma_bwd_f16, LGammaKernelOp,
+        lgammag(x),
+        digammag(x))
+
+UNARY_OP(float, lgamma_fwd_f32, lgamma_bwd_f32, LGammaKernelOp,
+        lgammag(x),
+        digammag(x))
+
+UNARY_OP(double, lgamma_fwd_f64, lgamma_bwd_f64, LGammaKernelOp,
+        lgammag(x),
+        digammag(x))
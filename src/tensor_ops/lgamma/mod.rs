@@ -0,0 +1,65 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct LGammaKernelOp;
+
+/// Natural log of the absolute value of the [gamma function](https://en.wikipedia.org/wiki/Gamma_function). `ln(|gamma(t)|)`
+///
+/// The derivative is [digamma()](super::digamma()). Non positive integers are poles, where this is `inf`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([0.5, 1.0, 2.0, 3.0]);
+/// let r = t.lgamma();
+/// ```
+pub fn lgamma<S: Shape, E: Dtype, D: UnaryKernel<LGammaKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.lgamma()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<LGammaKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [lgamma]
+    pub fn lgamma(self) -> Self {
+        self.try_lgamma().unwrap()
+    }
+    /// See [lgamma]
+    pub fn try_lgamma(self) -> Result<Self, D::Err> {
+        try_unary_op(LGammaKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_lgamma() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-0.5, 0.5, 1.0, 2.5, 4.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().lgamma();
+        assert_close_to_literal!(r, [1.2655121, 0.57236494, 0.0, 0.28468287, 1.7917595]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [
+                0.0072979948,
+                -0.39270201,
+                -0.11544313,
+                0.14063133,
+                0.25122353
+            ]
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{cpu::NdIndex, Cpu, GhostTensor},
+};
+
+use std::vec::Vec;
+
+impl<E: Dtype> super::LinalgKernel<E> for Cpu {
+    fn grad_to_vec<S: Shape>(
+        &self,
+        t: &GhostTensor<S, E, Self>,
+        grad: &Self::Vec<E>,
+    ) -> Result<Vec<E>, Self::Err> {
+        let mut idx = NdIndex::new(t.shape, t.strides);
+        Ok((0..t.shape.num_elements())
+            .map(|_| grad[idx.next().unwrap()])
+            .collect())
+    }
+
+    fn add_to_grad<S: Shape>(
+        &self,
+        t: &GhostTensor<S, E, Self>,
+        grad: &mut Self::Vec<E>,
+        src: &[E],
+    ) -> Result<(), Self::Err> {
+        let mut idx = NdIndex::new(t.shape, t.strides);
+        for &x in src {
+            grad[idx.next().unwrap()] += x;
+        }
+        Ok(())
+    }
+}
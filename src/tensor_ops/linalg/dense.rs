@@ -0,0 +1,512 @@
+//! Dense linear algebra on single row major matrices. Square matrices are `n x n`,
+//! and right hand sides of solves are `n x k`.
+
+use num_traits::Float;
+use std::vec::Vec;
+
+const MAX_SWEEPS: usize = 100;
+
+fn sum<F: Float>(iter: impl Iterator<Item = F>) -> F {
+    iter.fold(F::zero(), |acc, x| acc + x)
+}
+
+fn half<F: Float>() -> F {
+    F::from(0.5).unwrap()
+}
+
+fn two<F: Float>() -> F {
+    F::one() + F::one()
+}
+
+pub(super) fn eye<F: Float>(n: usize) -> Vec<F> {
+    let mut out = std::vec![F::zero(); n * n];
+    for i in 0..n {
+        out[i * n + i] = F::one();
+    }
+    out
+}
+
+pub(super) fn transpose<F: Float>(a: &[F], rows: usize, cols: usize) -> Vec<F> {
+    let mut out = std::vec![F::zero(); rows * cols];
+    for i in 0..rows {
+        for j in 0..cols {
+            out[j * rows + i] = a[i * cols + j];
+        }
+    }
+    out
+}
+
+/// Multiplies the `n x k` matrix `a` with the `k x m` matrix `b`.
+pub(super) fn matmul<F: Float>(a: &[F], b: &[F], n: usize, k: usize, m: usize) -> Vec<F> {
+    let mut out = std::vec![F::zero(); n * m];
+    for i in 0..n {
+        for l in 0..k {
+            let x = a[i * k + l];
+            for j in 0..m {
+                out[i * m + j] = out[i * m + j] + x * b[l * m + j];
+            }
+        }
+    }
+    out
+}
+
+/// LU decomposition with partial pivoting, `P A = L U`. Returns `L` (without its unit
+/// diagonal) and `U` packed into one matrix, the row of `A` that each row came from,
+/// and the sign of the permutation.
+fn lu<F: Float>(a: &[F], n: usize) -> (Vec<F>, Vec<usize>, F) {
+    let mut lu = a.to_vec();
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut sign = F::one();
+    for j in 0..n {
+        let mut p = j;
+        for i in j + 1..n {
+            if lu[i * n + j].abs() > lu[p * n + j].abs() {
+                p = i;
+            }
+        }
+        if p != j {
+            for c in 0..n {
+                lu.swap(j * n + c, p * n + c);
+            }
+            perm.swap(j, p);
+            sign = -sign;
+        }
+        let pivot = lu[j * n + j];
+        if pivot.is_zero() {
+            // the matrix is singular, and the column is already eliminated
+            continue;
+        }
+        for i in j + 1..n {
+            let f = lu[i * n + j] / pivot;
+            lu[i * n + j] = f;
+            for c in j + 1..n {
+                lu[i * n + c] = lu[i * n + c] - f * lu[j * n + c];
+            }
+        }
+    }
+    (lu, perm, sign)
+}
+
+/// Subtracts `f` times row `src` from row `dst` of the `n x k` matrix `x`.
+fn sub_row<F: Float>(x: &mut [F], k: usize, dst: usize, src: usize, f: F) {
+    for c in 0..k {
+        x[dst * k + c] = x[dst * k + c] - f * x[src * k + c];
+    }
+}
+
+/// Solves `A X = B` for `X`. Singular matrices result in infinities or NaNs.
+pub(super) fn solve<F: Float>(a: &[F], b: &[F], n: usize, k: usize) -> Vec<F> {
+    let (lu, perm, _) = lu(a, n);
+    let mut x = std::vec![F::zero(); n * k];
+    for (i, &p) in perm.iter().enumerate() {
+        x[i * k..(i + 1) * k].copy_from_slice(&b[p * k..(p + 1) * k]);
+    }
+    for i in 0..n {
+        for j in 0..i {
+            sub_row(&mut x, k, i, j, lu[i * n + j]);
+        }
+    }
+    for i in (0..n).rev() {
+        for j in i + 1..n {
+            sub_row(&mut x, k, i, j, lu[i * n + j]);
+        }
+        let d = lu[i * n + i];
+        x[i * k..(i + 1) * k].iter_mut().for_each(|x| *x = *x / d);
+    }
+    x
+}
+
+pub(super) fn inverse<F: Float>(a: &[F], n: usize) -> Vec<F> {
+    solve(a, &eye(n), n, n)
+}
+
+pub(super) fn det<F: Float>(a: &[F], n: usize) -> F {
+    let (lu, _, sign) = lu(a, n);
+    (0..n).fold(sign, |acc, i| acc * lu[i * n + i])
+}
+
+/// Returns the sign and the log of the absolute value of the determinant.
+pub(super) fn slogdet<F: Float>(a: &[F], n: usize) -> (F, F) {
+    let (lu, _, mut sign) = lu(a, n);
+    let mut logabsdet = F::zero();
+    for i in 0..n {
+        let d = lu[i * n + i];
+        if d.is_zero() {
+            return (F::zero(), F::neg_infinity());
+        }
+        sign = sign * d.signum();
+        logabsdet = logabsdet + d.abs().ln();
+    }
+    (sign, logabsdet)
+}
+
+/// Solves `A X = B` for `X`, where only the lower (or upper) triangle of `A` is read.
+pub(super) fn tri_solve<F: Float>(a: &[F], b: &[F], n: usize, k: usize, upper: bool) -> Vec<F> {
+    let mut x = b.to_vec();
+    for step in 0..n {
+        let i = if upper { n - 1 - step } else { step };
+        let solved = if upper { i + 1..n } else { 0..i };
+        for j in solved {
+            sub_row(&mut x, k, i, j, a[i * n + j]);
+        }
+        let d = a[i * n + i];
+        x[i * k..(i + 1) * k].iter_mut().for_each(|x| *x = *x / d);
+    }
+    x
+}
+
+/// Returns the lower triangular `L` with `A = L L^T`, reading only the lower triangle
+/// of `A`. Matrices that aren't positive definite result in NaNs.
+pub(super) fn cholesky<F: Float>(a: &[F], n: usize) -> Vec<F> {
+    let mut l = std::vec![F::zero(); n * n];
+    for j in 0..n {
+        let d = (a[j * n + j] - sum((0..j).map(|k| l[j * n + k].powi(2)))).sqrt();
+        l[j * n + j] = d;
+        for i in j + 1..n {
+            let s = a[i * n + j] - sum((0..j).map(|k| l[i * n + k] * l[j * n + k]));
+            l[i * n + j] = s / d;
+        }
+    }
+    l
+}
+
+/// Householder QR decomposition `A = Q R`, where the diagonal of `R` is non-negative.
+pub(super) fn qr<F: Float>(a: &[F], n: usize) -> (Vec<F>, Vec<F>) {
+    let mut r = a.to_vec();
+    let mut q: Vec<F> = eye(n);
+    for j in 0..n {
+        let norm = sum((j..n).map(|i| r[i * n + j].powi(2))).sqrt();
+        if norm.is_zero() {
+            continue;
+        }
+        let alpha = if r[j * n + j] > F::zero() {
+            -norm
+        } else {
+            norm
+        };
+        let mut v: Vec<F> = (0..n)
+            .map(|i| if i < j { F::zero() } else { r[i * n + j] })
+            .collect();
+        v[j] = v[j] - alpha;
+        let v_norm2 = sum(v.iter().map(|&x| x * x));
+        if v_norm2.is_zero() {
+            continue;
+        }
+        // R = H R and Q = Q H, where H = I - 2 v v^T / |v|^2
+        for c in 0..n {
+            let f = two::<F>() * sum((j..n).map(|i| v[i] * r[i * n + c])) / v_norm2;
+            for i in j..n {
+                r[i * n + c] = r[i * n + c] - f * v[i];
+            }
+        }
+        for row in 0..n {
+            let f = two::<F>() * sum((j..n).map(|i| q[row * n + i] * v[i])) / v_norm2;
+            for i in j..n {
+                q[row * n + i] = q[row * n + i] - f * v[i];
+            }
+        }
+    }
+    for j in 0..n {
+        for i in j + 1..n {
+            r[i * n + j] = F::zero();
+        }
+        if r[j * n + j] < F::zero() {
+            for c in 0..n {
+                r[j * n + c] = -r[j * n + c];
+                q[c * n + j] = -q[c * n + j];
+            }
+        }
+    }
+    (q, r)
+}
+
+/// Returns `(cos(theta), sin(theta))` of the Jacobi rotation that zeroes the off
+/// diagonal entry of a symmetric 2x2 problem, where `zeta = cot(2 theta)`.
+fn jacobi_rotation<F: Float>(zeta: F) -> (F, F) {
+    let t = F::one() / (zeta.abs() + (F::one() + zeta * zeta).sqrt());
+    let t = if zeta < F::zero() { -t } else { t };
+    let c = F::one() / (F::one() + t * t).sqrt();
+    (c, c * t)
+}
+
+/// Rotates the columns `p` and `q` of the `n x n` matrix `a`.
+fn rotate_cols<F: Float>(a: &mut [F], n: usize, p: usize, q: usize, (c, s): (F, F)) {
+    for i in 0..n {
+        let (ap, aq) = (a[i * n + p], a[i * n + q]);
+        a[i * n + p] = c * ap - s * aq;
+        a[i * n + q] = s * ap + c * aq;
+    }
+}
+
+/// Rotates the rows `p` and `q` of the `n x n` matrix `a`.
+fn rotate_rows<F: Float>(a: &mut [F], n: usize, p: usize, q: usize, (c, s): (F, F)) {
+    for k in 0..n {
+        let (ap, aq) = (a[p * n + k], a[q * n + k]);
+        a[p * n + k] = c * ap - s * aq;
+        a[q * n + k] = s * ap + c * aq;
+    }
+}
+
+/// Permutes the columns of the `n x n` matrix `a`, so that column `j` of the result is
+/// column `order[j]` of `a`.
+fn permute_cols<F: Float>(a: &[F], n: usize, order: &[usize]) -> Vec<F> {
+    (0..n * n).map(|i| a[i - i % n + order[i % n]]).collect()
+}
+
+/// Singular value decomposition `A = U diag(S) V^T` with one sided Jacobi rotations.
+/// The singular values are sorted in descending order. Returns `(U, S, V^T)`.
+pub(super) fn svd<F: Float>(a: &[F], n: usize) -> (Vec<F>, Vec<F>, Vec<F>) {
+    let mut u = a.to_vec();
+    let mut v = eye(n);
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha = sum((0..n).map(|i| u[i * n + p].powi(2)));
+                let beta = sum((0..n).map(|i| u[i * n + q].powi(2)));
+                let gamma = sum((0..n).map(|i| u[i * n + p] * u[i * n + q]));
+                if gamma.abs() <= F::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let rotation = jacobi_rotation((beta - alpha) / (two::<F>() * gamma));
+                rotate_cols(&mut u, n, p, q, rotation);
+                rotate_cols(&mut v, n, p, q, rotation);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    let norms: Vec<F> = (0..n)
+        .map(|j| sum((0..n).map(|i| u[i * n + j].powi(2))).sqrt())
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap());
+    let s: Vec<F> = order.iter().map(|&j| norms[j]).collect();
+    let mut u = permute_cols(&u, n, &order);
+    let v = permute_cols(&v, n, &order);
+
+    // normalize the columns of U, and replace the ones of (numerically) zero singular
+    // values with orthonormal vectors
+    let s_max = s.first().copied().unwrap_or_else(F::zero);
+    let tol = s_max * F::from(n).unwrap() * F::epsilon();
+    for j in 0..n {
+        if s[j] > tol {
+            for i in 0..n {
+                u[i * n + j] = u[i * n + j] / s[j];
+            }
+            continue;
+        }
+        for e in 0..n {
+            let mut col: Vec<F> = (0..n)
+                .map(|i| if i == e { F::one() } else { F::zero() })
+                .collect();
+            for k in 0..j {
+                let dot = sum((0..n).map(|i| u[i * n + k] * col[i]));
+                for (i, x) in col.iter_mut().enumerate() {
+                    *x = *x - dot * u[i * n + k];
+                }
+            }
+            let norm = sum(col.iter().map(|&x| x * x)).sqrt();
+            if norm > half() {
+                for (i, x) in col.into_iter().enumerate() {
+                    u[i * n + j] = x / norm;
+                }
+                break;
+            }
+        }
+    }
+    (u, s, transpose(&v, n, n))
+}
+
+/// Eigen decomposition `A = V diag(W) V^T` of the symmetric part `(A + A^T) / 2` of `A`
+/// with cyclic Jacobi rotations. The eigenvalues are sorted in ascending order.
+/// Returns `(W, V)`, where the columns of `V` are the eigenvectors.
+pub(super) fn eigh<F: Float>(a: &[F], n: usize) -> (Vec<F>, Vec<F>) {
+    let mut m: Vec<F> = (0..n * n)
+        .map(|i| half::<F>() * (a[i] + a[(i % n) * n + i / n]))
+        .collect();
+    let norm2 = sum(m.iter().map(|&x| x * x));
+    let mut v = eye(n);
+    for _ in 0..MAX_SWEEPS {
+        let off = sum((0..n * n).filter(|i| i % n > i / n).map(|i| m[i].powi(2)));
+        if off <= F::epsilon() * F::epsilon() * norm2 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let mpq = m[p * n + q];
+                if mpq.is_zero() {
+                    continue;
+                }
+                let zeta = (m[q * n + q] - m[p * n + p]) / (two::<F>() * mpq);
+                let rotation = jacobi_rotation(zeta);
+                // M = J^T M J and V = V J
+                rotate_cols(&mut m, n, p, q, rotation);
+                rotate_rows(&mut m, n, p, q, rotation);
+                rotate_cols(&mut v, n, p, q, rotation);
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| m[i * n + i].partial_cmp(&m[j * n + j]).unwrap());
+    let w = order.iter().map(|&i| m[i * n + i]).collect();
+    (w, permute_cols(&v, n, &order))
+}
+
+/// Gradient of `Y = A^-1`.
+pub(super) fn inverse_bwd<F: Float>(y: &[F], gy: &[F], n: usize) -> Vec<F> {
+    let yt = transpose(y, n, n);
+    let ga = matmul(&matmul(&yt, gy, n, n, n), &yt, n, n, n);
+    ga.into_iter().map(|x| -x).collect()
+}
+
+/// Gradient of the determinant `d` of `A`.
+pub(super) fn det_bwd<F: Float>(a: &[F], d: F, gd: F, n: usize) -> Vec<F> {
+    let inv_t = transpose(&inverse(a, n), n, n);
+    inv_t.into_iter().map(|x| gd * d * x).collect()
+}
+
+/// Gradient of `log|det A|`.
+pub(super) fn logabsdet_bwd<F: Float>(a: &[F], g: F, n: usize) -> Vec<F> {
+    let inv_t = transpose(&inverse(a, n), n, n);
+    inv_t.into_iter().map(|x| g * x).collect()
+}
+
+/// Gradients of `A` and `B` for `X = A^-1 B`.
+pub(super) fn solve_bwd<F: Float>(
+    a: &[F],
+    x: &[F],
+    gx: &[F],
+    n: usize,
+    k: usize,
+) -> (Vec<F>, Vec<F>) {
+    let gb = solve(&transpose(a, n, n), gx, n, k);
+    let ga = matmul(&gb, &transpose(x, n, k), n, k, n);
+    (ga.into_iter().map(|x| -x).collect(), gb)
+}
+
+/// Gradients of `A` and `B` for `X = A^-1 B` with a lower (or upper) triangular `A`.
+pub(super) fn tri_solve_bwd<F: Float>(
+    a: &[F],
+    x: &[F],
+    gx: &[F],
+    n: usize,
+    k: usize,
+    upper: bool,
+) -> (Vec<F>, Vec<F>) {
+    let gb = tri_solve(&transpose(a, n, n), gx, n, k, !upper);
+    let ga = matmul(&gb, &transpose(x, n, k), n, k, n);
+    let ga = ga
+        .into_iter()
+        .enumerate()
+        .map(|(i, g)| {
+            let (row, col) = (i / n, i % n);
+            let in_triangle = if upper { col >= row } else { col <= row };
+            if in_triangle {
+                -g
+            } else {
+                F::zero()
+            }
+        })
+        .collect();
+    (ga, gb)
+}
+
+/// Gradient of `L = cholesky(A)`, which is symmetric.
+pub(super) fn cholesky_bwd<F: Float>(l: &[F], gl: &[F], n: usize) -> Vec<F> {
+    let m = matmul(&transpose(l, n, n), gl, n, n, n);
+    // the symmetric matrix with the lower triangle of `m`, and half its diagonal
+    let mut s = std::vec![F::zero(); n * n];
+    for i in 0..n {
+        for j in 0..=i {
+            s[i * n + j] = half::<F>() * m[i * n + j];
+            s[j * n + i] = half::<F>() * m[i * n + j];
+        }
+    }
+    // L^-T S L^-1
+    let lt = transpose(l, n, n);
+    let x = tri_solve(&lt, &s, n, n, true);
+    transpose(&tri_solve(&lt, &transpose(&x, n, n), n, n, true), n, n)
+}
+
+/// Gradient of `(Q, R) = qr(A)`.
+pub(super) fn qr_bwd<F: Float>(q: &[F], r: &[F], gq: &[F], gr: &[F], n: usize) -> Vec<F> {
+    let gr_rt = matmul(gr, &transpose(r, n, n), n, n, n);
+    let qt_gq = matmul(&transpose(q, n, n), gq, n, n, n);
+    // G = gR R^T + tril(N - N^T, -1) with N = Q^T gQ - gR R^T
+    let mut g = gr_rt.clone();
+    for i in 0..n {
+        for j in 0..i {
+            let nij = qt_gq[i * n + j] - gr_rt[i * n + j];
+            let nji = qt_gq[j * n + i] - gr_rt[j * n + i];
+            g[i * n + j] = g[i * n + j] + nij - nji;
+        }
+    }
+    // Q G R^-T
+    let y = matmul(q, &g, n, n, n);
+    transpose(&tri_solve(r, &transpose(&y, n, n), n, n, true), n, n)
+}
+
+/// Returns the `n x n` matrix with entries `x_ij / (d_j - d_i)` off the diagonal, and
+/// 0 on the diagonal or where `d_i == d_j`.
+fn scale_by_gaps<F: Float>(x: &[F], d: &[F], n: usize) -> Vec<F> {
+    (0..n * n)
+        .map(|i| {
+            let gap = d[i % n] - d[i / n];
+            if gap.is_zero() {
+                F::zero()
+            } else {
+                x[i] / gap
+            }
+        })
+        .collect()
+}
+
+/// Returns `X - X^T`.
+fn skew<F: Float>(x: &[F], n: usize) -> Vec<F> {
+    let xt = transpose(x, n, n);
+    x.iter().zip(xt).map(|(&a, b)| a - b).collect()
+}
+
+/// Gradient of `(U, S, V^T) = svd(A)`.
+pub(super) fn svd_bwd<F: Float>(
+    u: &[F],
+    s: &[F],
+    vt: &[F],
+    gu: &[F],
+    gs: &[F],
+    gvt: &[F],
+    n: usize,
+) -> Vec<F> {
+    let gv = transpose(gvt, n, n);
+    let s2: Vec<F> = s.iter().map(|&x| x * x).collect();
+    let j = scale_by_gaps(&skew(&matmul(&transpose(u, n, n), gu, n, n, n), n), &s2, n);
+    let k = scale_by_gaps(&skew(&matmul(vt, &gv, n, n, n), n), &s2, n);
+    // U [J S + diag(gS) + S K] V^T
+    let mut inner: Vec<F> = (0..n * n)
+        .map(|i| j[i] * s[i % n] + s[i / n] * k[i])
+        .collect();
+    for (i, &g) in gs.iter().enumerate() {
+        inner[i * n + i] = inner[i * n + i] + g;
+    }
+    matmul(&matmul(u, &inner, n, n, n), vt, n, n, n)
+}
+
+/// Gradient of `(W, V) = eigh(A)`, which is symmetric.
+pub(super) fn eigh_bwd<F: Float>(w: &[F], v: &[F], gw: &[F], gv: &[F], n: usize) -> Vec<F> {
+    let vt = transpose(v, n, n);
+    let mut inner = scale_by_gaps(&matmul(&vt, gv, n, n, n), w, n);
+    for (i, &g) in gw.iter().enumerate() {
+        inner[i * n + i] = inner[i * n + i] + g;
+    }
+    let ga = matmul(&matmul(v, &inner, n, n, n), &vt, n, n, n);
+    let gat = transpose(&ga, n, n);
+    ga.iter()
+        .zip(gat)
+        .map(|(&a, b)| half::<F>() * (a + b))
+        .collect()
+}
@@ -0,0 +1,923 @@
+//! Differentiable linear algebra on batches of square matrices.
+//!
+//! The matrices are the last two dimensions of a tensor, and any leading dimensions
+//! are batch dimensions. See [SquareMatrix] for the supported shapes. All the
+//! operations are methods on [Tensor], and also available as free functions in this
+//! module.
+//!
+//! ```rust
+//! # use dfdx::prelude::*;
+//! # let dev: Cpu = Default::default();
+//! let a: Tensor<Rank3<4, 3, 3>, f64, _> = dev.sample_normal();
+//! let b: Tensor<Rank3<4, 3, 2>, f64, _> = dev.sample_normal();
+//! let x = a.clone().solve(b.clone());
+//! let r = a.matmul(x) - b;
+//! assert!(r.abs().max::<Rank0, _>().array() < 1e-8);
+//! ```
+//!
+//! The decompositions are computed with row major matrices on the host, and
+//! [LinalgKernel] moves gradients between device buffers and the host. It is
+//! implemented for [Cpu] with `f32` and `f64`.
+//!
+//! Operations with several outputs, like [Tensor::qr()], record a backward op for
+//! each output, and share the tape of the input between the outputs with
+//! [Tape::share]. The gradient reaches the input and earlier operations through any
+//! of the outputs that backward is called on.
+
+use crate::{shapes::*, tensor::*};
+use num_traits::Float;
+use std::vec::Vec;
+
+mod cpu_kernel;
+mod dense;
+
+/// The sign and log absolute determinant of matrices `S`, see [Tensor::slogdet].
+type SignLogDet<S, E, D, T> = (
+    Tensor<<S as SquareMatrix>::Batch, E, D>,
+    Tensor<<S as SquareMatrix>::Batch, E, D, T>,
+);
+/// `Q` and `R` of matrices `S`, see [Tensor::qr].
+type Qr<S, E, D, T> = (Tensor<S, E, D, T>, Tensor<S, E, D, T>);
+/// `U`, `S` and `V^T` of matrices `S`, see [Tensor::svd].
+type Svd<S, E, D, T> = (
+    Tensor<S, E, D, T>,
+    Tensor<<S as SquareMatrix>::Vector, E, D, T>,
+    Tensor<S, E, D, T>,
+);
+/// Eigenvalues and eigenvectors of matrices `S`, see [Tensor::eigh].
+type Eigh<S, E, D, T> = (
+    Tensor<<S as SquareMatrix>::Vector, E, D, T>,
+    Tensor<S, E, D, T>,
+);
+
+/// Shapes whose last two dimensions form square matrices. `N x N` matrices in
+/// [Const] dimensions are square at compile time, and matrices with `usize`
+/// dimensions are checked at runtime.
+pub trait SquareMatrix: Shape {
+    /// The shape with the last dimension removed, e.g. `(B, N)` for `(B, N, N)`.
+    /// Eigenvalues and singular values have this shape.
+    type Vector: Shape;
+    /// The batch dimensions, e.g. `(B,)` for `(B, N, N)`. Determinants have this shape.
+    type Batch: Shape;
+
+    fn vector(&self) -> Self::Vector;
+    fn batch(&self) -> Self::Batch;
+}
+
+impl<N: Dim> SquareMatrix for (N, N) {
+    type Vector = (N,);
+    type Batch = ();
+
+    fn vector(&self) -> Self::Vector {
+        (self.0,)
+    }
+    fn batch(&self) -> Self::Batch {}
+}
+
+impl<B: Dim, N: Dim> SquareMatrix for (B, N, N) {
+    type Vector = (B, N);
+    type Batch = (B,);
+
+    fn vector(&self) -> Self::Vector {
+        (self.0, self.1)
+    }
+    fn batch(&self) -> Self::Batch {
+        (self.0,)
+    }
+}
+
+impl<B: Dim, C: Dim, N: Dim> SquareMatrix for (B, C, N, N) {
+    type Vector = (B, C, N);
+    type Batch = (B, C);
+
+    fn vector(&self) -> Self::Vector {
+        (self.0, self.1, self.2)
+    }
+    fn batch(&self) -> Self::Batch {
+        (self.0, self.1)
+    }
+}
+
+/// Shapes of the right hand sides `B` of solves `A X = B`, where `A` has shape `S`.
+/// These are `N x K` matrices with the same batch dimensions as `A`, e.g. `(B, N, K)`
+/// for `(B, N, N)`.
+pub trait SolveRhs<S: SquareMatrix>: Shape {}
+impl<N: Dim, K: Dim> SolveRhs<(N, N)> for (N, K) {}
+impl<B: Dim, N: Dim, K: Dim> SolveRhs<(B, N, N)> for (B, N, K) {}
+impl<B: Dim, C: Dim, N: Dim, K: Dim> SolveRhs<(B, C, N, N)> for (B, C, N, K) {}
+
+pub trait LinalgKernel<E: Dtype>: DeviceStorage + TensorFromVec<E> {
+    /// Returns the entries of `grad`, the gradient of `t`, in row major order.
+    fn grad_to_vec<S: Shape>(
+        &self,
+        t: &GhostTensor<S, E, Self>,
+        grad: &Self::Vec<E>,
+    ) -> Result<Vec<E>, Self::Err>;
+
+    /// Adds the row major entries of `src` to `grad`, the gradient of `t`.
+    fn add_to_grad<S: Shape>(
+        &self,
+        t: &GhostTensor<S, E, Self>,
+        grad: &mut Self::Vec<E>,
+        src: &[E],
+    ) -> Result<(), Self::Err>;
+}
+
+/// Returns the number of matrices in `shape` and their size `n`, or a [ShapeError] if
+/// the matrices aren't square.
+fn square_dims<S: Shape>(name: &'static str, shape: &S) -> Result<(usize, usize), ShapeError> {
+    let dims = shape.concrete();
+    let dims = dims.as_ref();
+    let (rows, cols) = (dims[S::NUM_DIMS - 2], dims[S::NUM_DIMS - 1]);
+    if rows != cols {
+        return Err(ShapeError::new(name, &(rows,), &(cols,)));
+    }
+    Ok((dims[..S::NUM_DIMS - 2].iter().product(), rows))
+}
+
+/// Returns the number of columns of the right hand side `b` of a solve with the
+/// square matrices `a`, or a [ShapeError] if their batch dimensions or rows differ.
+fn rhs_cols<S: Shape, R: Shape>(name: &'static str, a: &S, b: &R) -> Result<usize, ShapeError> {
+    let (a_dims, b_dims) = (a.concrete(), b.concrete());
+    let (a_dims, b_dims) = (a_dims.as_ref(), b_dims.as_ref());
+    if a_dims[..S::NUM_DIMS - 1] != b_dims[..R::NUM_DIMS - 1] {
+        return Err(ShapeError::new(name, a, b));
+    }
+    Ok(b_dims[R::NUM_DIMS - 1])
+}
+
+/// Splits the row major `data` into `count` matrices of equal size.
+fn matrices<E>(data: &[E], count: usize) -> impl Iterator<Item = &[E]> {
+    let size = data.len().checked_div(count).unwrap_or(0);
+    (0..count).map(move |i| &data[i * size..(i + 1) * size])
+}
+
+/// Calls `f` with the `i`th matrix of every entry of `data` for each of the `count`
+/// matrices, and concatenates the results.
+fn map_batch<E: Copy, const M: usize>(
+    count: usize,
+    data: [&[E]; M],
+    f: impl Fn([&[E]; M]) -> Vec<E>,
+) -> Vec<E> {
+    let sizes = data.map(|d| d.len().checked_div(count).unwrap_or(0));
+    let mut out = Vec::new();
+    for i in 0..count {
+        out.extend(f(std::array::from_fn(|j| {
+            &data[j][i * sizes[j]..(i + 1) * sizes[j]]
+        })));
+    }
+    out
+}
+
+/// Records the backward op of `out` on `tape`, which adds `f(grad_out)` to the gradient
+/// of `inp`. Both gradients are row major.
+fn record<S: Shape, Dst: Shape, E: Dtype, D: LinalgKernel<E>, T: Tape<E, D>>(
+    inp: &GhostTensor<S, E, D>,
+    out: Tensor<Dst, E, D>,
+    mut tape: T,
+    f: impl 'static + FnOnce(Vec<E>) -> Vec<E>,
+) -> Tensor<Dst, E, D, T> {
+    let inp_ghost = inp.clone();
+    let out_ghost = out.ghost();
+    let device = out.device.clone();
+    tape.add_backward_op(move |grads| {
+        grads.try_alloc_for(&inp_ghost)?;
+        grads.try_alloc_for(&out_ghost)?;
+        let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+        let grad = f(device.grad_to_vec(&out_ghost, grad_out)?);
+        device.add_to_grad(&inp_ghost, grad_inp, &grad)
+    });
+    out.put_tape(tape)
+}
+
+/// Returns `len` zeros.
+fn zeros<E: Float>(len: usize) -> Vec<E> {
+    std::vec![E::zero(); len]
+}
+
+impl<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Inverts every matrix. Singular matrices result in infinities or NaNs.
+    ///
+    /// **pytorch equivalent** `torch.linalg.inv(t)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[2.0, 0.0], [0.0, 4.0]]);
+    /// assert_eq!(t.inverse().array(), [[0.5, 0.0], [0.0, 0.25]]);
+    /// ```
+    pub fn inverse(self) -> Self {
+        self.try_inverse().unwrap()
+    }
+
+    /// Fallible version of [Tensor::inverse]. Returns a [ShapeError] if the matrices
+    /// aren't square.
+    pub fn try_inverse(self) -> Result<Self, D::Err> {
+        let (count, n) = square_dims("inverse", &self.shape)?;
+        let a = self.as_vec();
+        let y = map_batch(count, [&a[..]], |[a]| dense::inverse(a, n));
+        let (inp, tape) = self.split_tape();
+        let out = inp.device.try_tensor_from_vec(y.clone(), inp.shape)?;
+        Ok(record(&inp.ghost(), out, tape, move |g| {
+            map_batch(count, [&y[..], &g[..]], |[y, g]| {
+                dense::inverse_bwd(y, g, n)
+            })
+        }))
+    }
+
+    /// Computes the determinant of every matrix.
+    ///
+    /// **pytorch equivalent** `torch.linalg.det(t)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[[2.0, 1.0], [4.0, 3.0]], [[2.0, 0.0], [0.0, 3.0]]]);
+    /// assert_eq!(t.det().array(), [2.0, 6.0]);
+    /// ```
+    pub fn det(self) -> Tensor<S::Batch, E, D, T> {
+        self.try_det().unwrap()
+    }
+
+    /// Fallible version of [Tensor::det]. Returns a [ShapeError] if the matrices
+    /// aren't square.
+    pub fn try_det(self) -> Result<Tensor<S::Batch, E, D, T>, D::Err> {
+        let (count, n) = square_dims("det", &self.shape)?;
+        let a = self.as_vec();
+        let d = map_batch(count, [&a[..]], |[a]| std::vec![dense::det(a, n)]);
+        let (inp, tape) = self.split_tape();
+        let out = inp
+            .device
+            .try_tensor_from_vec(d.clone(), inp.shape.batch())?;
+        Ok(record(&inp.ghost(), out, tape, move |g| {
+            map_batch(count, [&a[..], &d[..], &g[..]], |[a, d, g]| {
+                dense::det_bwd(a, d[0], g[0], n)
+            })
+        }))
+    }
+
+    /// Computes the sign and the natural log of the absolute value of the determinant
+    /// of every matrix. This is more accurate than [Tensor::det] for determinants that
+    /// are very small or very large. Singular matrices have sign 0 and a log of `-inf`.
+    ///
+    /// Only the log has a gradient.
+    ///
+    /// **pytorch equivalent** `torch.linalg.slogdet(t)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+    /// let (sign, logabsdet) = t.slogdet();
+    /// assert_eq!(sign.array(), -1.0);
+    /// assert!((logabsdet.array() - 2.0f32.ln()).abs() < 1e-6);
+    /// ```
+    pub fn slogdet(self) -> SignLogDet<S, E, D, T> {
+        self.try_slogdet().unwrap()
+    }
+
+    /// Fallible version of [Tensor::slogdet]. Returns a [ShapeError] if the matrices
+    /// aren't square.
+    pub fn try_slogdet(self) -> Result<SignLogDet<S, E, D, T>, D::Err> {
+        let (count, n) = square_dims("slogdet", &self.shape)?;
+        let a = self.as_vec();
+        let (mut sign, mut logabsdet) = (Vec::new(), Vec::new());
+        for a in matrices(&a, count) {
+            let (s, l) = dense::slogdet(a, n);
+            sign.push(s);
+            logabsdet.push(l);
+        }
+        let (inp, tape) = self.split_tape();
+        let batch = inp.shape.batch();
+        let sign = inp.device.try_tensor_from_vec(sign, batch)?;
+        let logabsdet = inp.device.try_tensor_from_vec(logabsdet, batch)?;
+        let logabsdet = record(&inp.ghost(), logabsdet, tape, move |g| {
+            map_batch(count, [&a[..], &g[..]], |[a, g]| {
+                dense::logabsdet_bwd(a, g[0], n)
+            })
+        });
+        Ok((sign, logabsdet))
+    }
+
+    /// Computes the lower triangular `L` with `A = L L^T` for every symmetric positive
+    /// definite matrix `A`. Only the lower triangle of `A` is read, and matrices that
+    /// aren't positive definite result in NaNs.
+    ///
+    /// The gradient is symmetric.
+    ///
+    /// **pytorch equivalent** `torch.linalg.cholesky(t)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[4.0, 2.0], [2.0, 5.0]]);
+    /// assert_eq!(t.cholesky().array(), [[2.0, 0.0], [1.0, 2.0]]);
+    /// ```
+    pub fn cholesky(self) -> Self {
+        self.try_cholesky().unwrap()
+    }
+
+    /// Fallible version of [Tensor::cholesky]. Returns a [ShapeError] if the matrices
+    /// aren't square.
+    pub fn try_cholesky(self) -> Result<Self, D::Err> {
+        let (count, n) = square_dims("cholesky", &self.shape)?;
+        let a = self.as_vec();
+        let l = map_batch(count, [&a[..]], |[a]| dense::cholesky(a, n));
+        let (inp, tape) = self.split_tape();
+        let out = inp.device.try_tensor_from_vec(l.clone(), inp.shape)?;
+        Ok(record(&inp.ghost(), out, tape, move |g| {
+            map_batch(count, [&l[..], &g[..]], |[l, g]| {
+                dense::cholesky_bwd(l, g, n)
+            })
+        }))
+    }
+
+    /// Computes the QR decomposition `A = Q R` of every matrix, where `Q` is orthogonal
+    /// and `R` is upper triangular with a non-negative diagonal. Returns `(Q, R)`.
+    ///
+    /// The gradient requires `R` to be invertible. See the [module docs](self) for how
+    /// the gradients of the outputs are tracked.
+    ///
+    /// **pytorch equivalent** `torch.linalg.qr(t)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[0.0, 2.0], [1.0, 3.0]]);
+    /// let (q, r) = t.qr();
+    /// assert_eq!(q.array(), [[0.0, 1.0], [1.0, 0.0]]);
+    /// assert_eq!(r.array(), [[1.0, 3.0], [0.0, 2.0]]);
+    /// ```
+    pub fn qr(self) -> Qr<S, E, D, T> {
+        self.try_qr().unwrap()
+    }
+
+    /// Fallible version of [Tensor::qr]. Returns a [ShapeError] if the matrices
+    /// aren't square.
+    pub fn try_qr(self) -> Result<Qr<S, E, D, T>, D::Err> {
+        let (count, n) = square_dims("qr", &self.shape)?;
+        let a = self.as_vec();
+        let (mut q, mut r) = (Vec::new(), Vec::new());
+        for a in matrices(&a, count) {
+            let (qi, ri) = dense::qr(a, n);
+            q.extend(qi);
+            r.extend(ri);
+        }
+        let (inp, tape) = self.split_tape();
+        let inp_ghost = inp.ghost();
+        let q_out = inp.device.try_tensor_from_vec(q.clone(), inp.shape)?;
+        let r_out = inp.device.try_tensor_from_vec(r.clone(), inp.shape)?;
+
+        let mut tapes = tape.share(2).into_iter();
+        let (q1, r1) = (q.clone(), r.clone());
+        let q_out = record(&inp_ghost, q_out, tapes.next().unwrap(), move |g| {
+            let gr = zeros::<E>(g.len());
+            map_batch(
+                count,
+                [&q1[..], &r1[..], &g[..], &gr[..]],
+                |[q, r, gq, gr]| dense::qr_bwd(q, r, gq, gr, n),
+            )
+        });
+        let r_out = record(&inp_ghost, r_out, tapes.next().unwrap(), move |g| {
+            let gq = zeros::<E>(g.len());
+            map_batch(count, [&q[..], &r[..], &gq[..], &g[..]], |[q, r, gq, gr]| {
+                dense::qr_bwd(q, r, gq, gr, n)
+            })
+        });
+        Ok((q_out, r_out))
+    }
+
+    /// Computes the singular value decomposition `A = U diag(S) V^T` of every matrix.
+    /// The singular values are sorted in descending order. Returns `(U, S, V^T)`.
+    ///
+    /// The gradient requires the singular values to be distinct, except for the
+    /// gradient of `S` alone. See the [module docs](self) for how the gradients of the
+    /// outputs are tracked.
+    ///
+    /// **pytorch equivalent** `torch.linalg.svd(t)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[0.0, 2.0], [-3.0, 0.0]]);
+    /// let (u, s, vt) = t.svd();
+    /// assert_eq!(s.array(), [3.0, 2.0]);
+    /// assert_eq!(u.array(), [[0.0, 1.0], [-1.0, 0.0]]);
+    /// assert_eq!(vt.array(), [[1.0, 0.0], [0.0, 1.0]]);
+    /// ```
+    pub fn svd(self) -> Svd<S, E, D, T> {
+        self.try_svd().unwrap()
+    }
+
+    /// Fallible version of [Tensor::svd]. Returns a [ShapeError] if the matrices
+    /// aren't square.
+    pub fn try_svd(self) -> Result<Svd<S, E, D, T>, D::Err> {
+        let (count, n) = square_dims("svd", &self.shape)?;
+        let a = self.as_vec();
+        let (mut u, mut s, mut vt) = (Vec::new(), Vec::new(), Vec::new());
+        for a in matrices(&a, count) {
+            let (ui, si, vti) = dense::svd(a, n);
+            u.extend(ui);
+            s.extend(si);
+            vt.extend(vti);
+        }
+        let (inp, tape) = self.split_tape();
+        let inp_ghost = inp.ghost();
+        let u_out = inp.device.try_tensor_from_vec(u.clone(), inp.shape)?;
+        let s_out = inp
+            .device
+            .try_tensor_from_vec(s.clone(), inp.shape.vector())?;
+        let vt_out = inp.device.try_tensor_from_vec(vt.clone(), inp.shape)?;
+
+        // the gradient is linear in the gradients of the outputs, so every output
+        // adds its part with zeros for the others
+        let svd_bwd = move |gu: &[E], gs: &[E], gvt: &[E]| {
+            map_batch(
+                count,
+                [&u[..], &s[..], &vt[..], gu, gs, gvt],
+                |[u, s, vt, gu, gs, gvt]| dense::svd_bwd(u, s, vt, gu, gs, gvt, n),
+            )
+        };
+        let (u_len, s_len) = (n * n * count, n * count);
+        let (bwd1, bwd2) = (svd_bwd.clone(), svd_bwd.clone());
+        let mut tapes = tape.share(3).into_iter();
+        let u_out = record(&inp_ghost, u_out, tapes.next().unwrap(), move |g| {
+            bwd1(&g, &zeros::<E>(s_len), &zeros::<E>(u_len))
+        });
+        let s_out = record(&inp_ghost, s_out, tapes.next().unwrap(), move |g| {
+            bwd2(&zeros::<E>(u_len), &g, &zeros::<E>(u_len))
+        });
+        let vt_out = record(&inp_ghost, vt_out, tapes.next().unwrap(), move |g| {
+            svd_bwd(&zeros::<E>(u_len), &zeros::<E>(s_len), &g)
+        });
+        Ok((u_out, s_out, vt_out))
+    }
+
+    /// Computes the eigen decomposition `A = V diag(W) V^T` of every symmetric matrix
+    /// `A`. Only the symmetric part `(A + A^T) / 2` is used. The eigenvalues are sorted
+    /// in ascending order, and the columns of `V` are the eigenvectors. Returns
+    /// `(W, V)`.
+    ///
+    /// The gradient is symmetric, and requires the eigenvalues to be distinct, except
+    /// for the gradient of `W` alone. See the [module docs](self) for how the
+    /// gradients of the outputs are tracked.
+    ///
+    /// **pytorch equivalent** `torch.linalg.eigh(t)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[3.0, 0.0], [0.0, 1.0]]);
+    /// let (w, v) = t.eigh();
+    /// assert_eq!(w.array(), [1.0, 3.0]);
+    /// assert_eq!(v.array(), [[0.0, 1.0], [1.0, 0.0]]);
+    /// ```
+    pub fn eigh(self) -> Eigh<S, E, D, T> {
+        self.try_eigh().unwrap()
+    }
+
+    /// Fallible version of [Tensor::eigh]. Returns a [ShapeError] if the matrices
+    /// aren't square.
+    pub fn try_eigh(self) -> Result<Eigh<S, E, D, T>, D::Err> {
+        let (count, n) = square_dims("eigh", &self.shape)?;
+        let a = self.as_vec();
+        let (mut w, mut v) = (Vec::new(), Vec::new());
+        for a in matrices(&a, count) {
+            let (wi, vi) = dense::eigh(a, n);
+            w.extend(wi);
+            v.extend(vi);
+        }
+        let (inp, tape) = self.split_tape();
+        let inp_ghost = inp.ghost();
+        let w_out = inp
+            .device
+            .try_tensor_from_vec(w.clone(), inp.shape.vector())?;
+        let v_out = inp.device.try_tensor_from_vec(v.clone(), inp.shape)?;
+
+        let eigh_bwd = move |gw: &[E], gv: &[E]| {
+            map_batch(count, [&w[..], &v[..], gw, gv], |[w, v, gw, gv]| {
+                dense::eigh_bwd(w, v, gw, gv, n)
+            })
+        };
+        let (w_len, v_len) = (n * count, n * n * count);
+        let bwd = eigh_bwd.clone();
+        let mut tapes = tape.share(2).into_iter();
+        let w_out = record(&inp_ghost, w_out, tapes.next().unwrap(), move |g| {
+            bwd(&g, &zeros::<E>(v_len))
+        });
+        let v_out = record(&inp_ghost, v_out, tapes.next().unwrap(), move |g| {
+            eigh_bwd(&zeros::<E>(w_len), &g)
+        });
+        Ok((w_out, v_out))
+    }
+
+    /// Solves `A X = B` for `X`, where `A` are the matrices of `self`. Singular matrices
+    /// result in infinities or NaNs.
+    ///
+    /// **pytorch equivalent** `torch.linalg.solve(t, b)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a = dev.tensor([[2.0, 0.0], [0.0, 4.0]]);
+    /// let b = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+    /// assert_eq!(a.solve(b).array(), [[0.5, 1.0], [0.75, 1.0]]);
+    /// ```
+    pub fn solve<Rhs: SolveRhs<S>, R: Tape<E, D>>(
+        self,
+        b: Tensor<Rhs, E, D, R>,
+    ) -> Tensor<Rhs, E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_solve(b).unwrap()
+    }
+
+    /// Fallible version of [Tensor::solve]. Returns a [ShapeError] if the matrices
+    /// aren't square, or if the batch dimensions or rows of `b` differ.
+    pub fn try_solve<Rhs: SolveRhs<S>, R: Tape<E, D>>(
+        self,
+        b: Tensor<Rhs, E, D, R>,
+    ) -> Result<Tensor<Rhs, E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        self.try_solve_op("solve", b, None)
+    }
+
+    /// Solves `A X = B` for `X`, where `A` are the lower (or upper) triangular
+    /// matrices of `self`. Only the lower (or upper) triangle of `A` is read, and gets
+    /// a gradient.
+    ///
+    /// **pytorch equivalent** `torch.linalg.solve_triangular(t, b, upper=upper)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let a = dev.tensor([[2.0, 9.0], [1.0, 1.0]]);
+    /// let b = dev.tensor([[2.0], [3.0]]);
+    /// assert_eq!(a.triangular_solve(b, false).array(), [[1.0], [2.0]]);
+    /// ```
+    pub fn triangular_solve<Rhs: SolveRhs<S>, R: Tape<E, D>>(
+        self,
+        b: Tensor<Rhs, E, D, R>,
+        upper: bool,
+    ) -> Tensor<Rhs, E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_triangular_solve(b, upper).unwrap()
+    }
+
+    /// Fallible version of [Tensor::triangular_solve]. Returns a [ShapeError] if the
+    /// matrices aren't square, or if the batch dimensions or rows of `b` differ.
+    pub fn try_triangular_solve<Rhs: SolveRhs<S>, R: Tape<E, D>>(
+        self,
+        b: Tensor<Rhs, E, D, R>,
+        upper: bool,
+    ) -> Result<Tensor<Rhs, E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        self.try_solve_op("triangular_solve", b, Some(upper))
+    }
+
+    /// Solves with general matrices if `triangle` is `None`, and otherwise with lower
+    /// or upper (if `Some(true)`) triangular matrices.
+    fn try_solve_op<Rhs: SolveRhs<S>, R: Tape<E, D>>(
+        self,
+        name: &'static str,
+        b: Tensor<Rhs, E, D, R>,
+        triangle: Option<bool>,
+    ) -> Result<Tensor<Rhs, E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        let (count, n) = square_dims(name, &self.shape)?;
+        let k = rhs_cols(name, &self.shape, &b.shape)?;
+        let (a_data, b_data) = (self.as_vec(), b.as_vec());
+        let x = map_batch(count, [&a_data[..], &b_data[..]], |[a, b]| match triangle {
+            None => dense::solve(a, b, n, k),
+            Some(upper) => dense::tri_solve(a, b, n, k, upper),
+        });
+
+        let (a, a_tape) = self.split_tape();
+        let (b, b_tape) = b.split_tape();
+        let mut tape = a_tape.merge(b_tape);
+        let out = a.device.try_tensor_from_vec(x.clone(), b.shape)?;
+        let (a_ghost, b_ghost, out_ghost) = (a.ghost(), b.ghost(), out.ghost());
+        let device = a.device.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&a_ghost)?;
+            grads.try_alloc_for(&b_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_a, grad_b, grad_out) = grads.muts_and_ref(&a_ghost, &b_ghost, &out_ghost);
+            let gx = device.grad_to_vec(&out_ghost, grad_out)?;
+            let (mut ga, mut gb) = (Vec::new(), Vec::new());
+            for ((a, x), gx) in matrices(&a_data, count)
+                .zip(matrices(&x, count))
+                .zip(matrices(&gx, count))
+            {
+                let (gai, gbi) = match triangle {
+                    None => dense::solve_bwd(a, x, gx, n, k),
+                    Some(upper) => dense::tri_solve_bwd(a, x, gx, n, k, upper),
+                };
+                ga.extend(gai);
+                gb.extend(gbi);
+            }
+            device.add_to_grad(&a_ghost, grad_a, &ga)?;
+            device.add_to_grad(&b_ghost, grad_b, &gb)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+/// Inverts every matrix. See [Tensor::inverse].
+pub fn inverse<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.inverse()
+}
+
+/// Computes the determinant of every matrix. See [Tensor::det].
+pub fn det<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S::Batch, E, D, T> {
+    t.det()
+}
+
+/// Computes the sign and the log of the absolute value of the determinant of every
+/// matrix. See [Tensor::slogdet].
+pub fn slogdet<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> SignLogDet<S, E, D, T> {
+    t.slogdet()
+}
+
+/// Computes the Cholesky decomposition of every matrix. See [Tensor::cholesky].
+pub fn cholesky<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.cholesky()
+}
+
+/// Computes the QR decomposition of every matrix. See [Tensor::qr].
+pub fn qr<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Qr<S, E, D, T> {
+    t.qr()
+}
+
+/// Computes the singular value decomposition of every matrix. See [Tensor::svd].
+pub fn svd<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Svd<S, E, D, T> {
+    t.svd()
+}
+
+/// Computes the eigen decomposition of every symmetric matrix. See [Tensor::eigh].
+pub fn eigh<S: SquareMatrix, E: Dtype + Float, D: LinalgKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Eigh<S, E, D, T> {
+    t.eigh()
+}
+
+/// Solves `A X = B` for `X`. See [Tensor::solve].
+pub fn solve<
+    S: SquareMatrix,
+    Rhs: SolveRhs<S>,
+    E: Dtype + Float,
+    D: LinalgKernel<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+>(
+    a: Tensor<S, E, D, T>,
+    b: Tensor<Rhs, E, D, R>,
+) -> Tensor<Rhs, E, D, T> {
+    a.solve(b)
+}
+
+/// Solves `A X = B` for `X` with triangular `A`. See [Tensor::triangular_solve].
+pub fn triangular_solve<
+    S: SquareMatrix,
+    Rhs: SolveRhs<S>,
+    E: Dtype + Float,
+    D: LinalgKernel<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+>(
+    a: Tensor<S, E, D, T>,
+    b: Tensor<Rhs, E, D, R>,
+    upper: bool,
+) -> Tensor<Rhs, E, D, T> {
+    a.triangular_solve(b, upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_inverse() {
+        let dev: Cpu = Default::default();
+        let a = dev.tensor([[1.0, 2.0], [3.0, 4.0]]).to_dtype::<TestDtype>();
+        let y = a.leaky_trace().inverse();
+        assert_close_to_literal!(y, [[-2.0, 1.0], [1.5, -0.5]]);
+        let g = y.sum().backward();
+        assert_close_to_literal!(g.get(&a), [[-0.5, 0.5], [0.5, -0.5]]);
+    }
+
+    #[test]
+    fn test_det_and_slogdet() {
+        let dev: Cpu = Default::default();
+        let a = dev
+            .tensor([[[1.0, 2.0], [3.0, 4.0]], [[2.0, 0.0], [0.0, 3.0]]])
+            .to_dtype::<TestDtype>();
+
+        let d = a.leaky_trace().det();
+        assert_close_to_literal!(d, [-2.0, 6.0]);
+        let g = d.sum().backward();
+        assert_close_to_literal!(
+            g.get(&a),
+            [[[4.0, -3.0], [-2.0, 1.0]], [[3.0, 0.0], [0.0, 2.0]]]
+        );
+
+        let (sign, logabsdet) = a.leaky_trace().slogdet();
+        assert_close_to_literal!(sign, [-1.0, 1.0]);
+        assert_close_to_literal!(logabsdet, [2.0f64.ln(), 6.0f64.ln()]);
+        let g = logabsdet.sum().backward();
+        assert_close_to_literal!(
+            g.get(&a),
+            [[[-2.0, 1.5], [1.0, -0.5]], [[0.5, 0.0], [0.0, 1.0 / 3.0]]]
+        );
+    }
+
+    #[test]
+    fn test_slogdet_singular() {
+        let dev: Cpu = Default::default();
+        let a = dev.tensor([[1.0, 2.0], [2.0, 4.0]]).to_dtype::<TestDtype>();
+        let (sign, logabsdet) = a.slogdet();
+        assert_eq!(sign.array(), TestDtype::zero());
+        assert_eq!(logabsdet.array(), TestDtype::neg_infinity());
+    }
+
+    #[test]
+    fn test_solve_matches_inverse() {
+        let dev: Cpu = Default::default();
+        let a = dev
+            .tensor([
+                [[4.0, 1.0, 0.5], [-1.0, 3.0, 1.0], [0.0, 2.0, 5.0]],
+                [[2.0, -1.0, 0.0], [1.0, 1.0, 3.0], [0.5, 0.0, -2.0]],
+            ])
+            .to_dtype::<TestDtype>();
+        let b: Tensor<Rank3<2, 3, 2>, TestDtype, _> = dev.sample_normal();
+
+        let x = a.leaky_trace().solve(b.leaky_trace());
+        let x2 = a.leaky_trace().inverse().matmul(b.leaky_trace());
+        assert_close_to_tensor!(x, x2);
+
+        let g = x.square().sum().backward();
+        let g2 = x2.square().sum().backward();
+        assert_close_to_tensor!(g.get(&a), g2.get(&a), 1e-4);
+        assert_close_to_tensor!(g.get(&b), g2.get(&b), 1e-4);
+    }
+
+    #[test]
+    fn test_triangular_solve() {
+        let dev: Cpu = Default::default();
+        let b = dev
+            .tensor([[1.0, -2.0], [3.0, 0.5]])
+            .to_dtype::<TestDtype>();
+        // the entries outside of the triangle are ignored
+        let a = dev.tensor([[2.0, 9.0], [1.0, 4.0]]).to_dtype::<TestDtype>();
+        let l = dev.tensor([[2.0, 0.0], [1.0, 4.0]]).to_dtype::<TestDtype>();
+        let u = dev.tensor([[2.0, 9.0], [0.0, 4.0]]).to_dtype::<TestDtype>();
+
+        for (upper, tri) in [(false, l), (true, u)] {
+            let x = a.leaky_trace().triangular_solve(b.leaky_trace(), upper);
+            let x2 = tri.leaky_trace().solve(b.leaky_trace());
+            assert_close_to_tensor!(x, x2);
+
+            let g = x.square().sum().backward();
+            let g2 = x2.square().sum().backward();
+            assert_close_to_tensor!(g.get(&b), g2.get(&b));
+            let mut ga = g2.get(&tri).array();
+            if upper {
+                ga[1][0] = TestDtype::zero();
+            } else {
+                ga[0][1] = TestDtype::zero();
+            }
+            assert_close_to_tensor!(g.get(&a), dev.tensor(ga));
+        }
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let dev: Cpu = Default::default();
+        let a = dev.tensor([[4.0, 2.0], [2.0, 5.0]]).to_dtype::<TestDtype>();
+        let l = a.leaky_trace().cholesky();
+        assert_close_to_literal!(l, [[2.0, 0.0], [1.0, 2.0]]);
+        let g = l.sum().backward();
+        assert_close_to_literal!(g.get(&a), [[0.1875, 0.125], [0.125, 0.25]]);
+    }
+
+    #[test]
+    fn test_qr() {
+        let dev: Cpu = Default::default();
+        let a = dev.tensor([[1.0, 2.0], [3.0, 4.0]]).to_dtype::<TestDtype>();
+        let (q, r) = a.leaky_trace().qr();
+        assert_close_to_tensor!(q.retaped::<NoneTape>().matmul(r.retaped::<NoneTape>()), a);
+        assert_close_to_literal!(
+            q.retaped::<NoneTape>()
+                .permute::<_, Axes2<1, 0>>()
+                .matmul(q.retaped::<NoneTape>()),
+            [[1.0, 0.0], [0.0, 1.0]]
+        );
+        assert_close_to_literal!(r, [[3.1622777, 4.4271887], [0.0, 0.6324555]]);
+
+        let g = (q.sum() + r.sum()).backward();
+        assert_close_to_literal!(g.get(&a), [[-1.0119289, 1.2649111], [1.3914022, 0.6324555]]);
+    }
+
+    #[test]
+    fn test_svd() {
+        let dev: Cpu = Default::default();
+        let a = dev
+            .tensor([
+                [[4.0, 1.0, 0.5], [-1.0, 3.0, 1.0], [0.0, 2.0, 5.0]],
+                [[2.0, -1.0, 0.0], [1.0, 1.0, 3.0], [0.5, 0.0, -2.0]],
+            ])
+            .to_dtype::<TestDtype>();
+        let (u, s, vt) = a.leaky_trace().svd();
+        assert_close_to_literal!(
+            s,
+            [
+                [5.9207545, 4.11118402, 2.30061558],
+                [3.75425798, 2.32789368, 0.85817135]
+            ]
+        );
+        let (u, vt) = (u.retaped::<NoneTape>(), vt.retaped::<NoneTape>());
+        let us = u.clone() * s.retaped::<NoneTape>().broadcast::<_, Axis<1>>();
+        assert_close_to_tensor!(us.matmul(vt.clone()), a);
+
+        // the gradient of the nuclear norm is U V^T
+        let g = s.sum().backward();
+        assert_close_to_tensor!(g.get(&a), u.matmul(vt));
+    }
+
+    #[test]
+    fn test_eigh() {
+        let dev: Cpu = Default::default();
+        let a = dev.tensor([[2.0, 1.0], [1.0, 2.0]]).to_dtype::<TestDtype>();
+        let (w, v) = a.leaky_trace().eigh();
+        assert_close_to_literal!(w, [1.0, 3.0]);
+        let v = v.retaped::<NoneTape>();
+        let vw = v.clone() * w.retaped::<NoneTape>().broadcast::<_, Axis<0>>();
+        assert_close_to_tensor!(vw.matmul(v.permute::<_, Axes2<1, 0>>()), a);
+
+        // the gradient of an eigenvalue is the outer product of its eigenvector
+        let weights = dev.tensor([0.0, 1.0]).to_dtype::<TestDtype>();
+        let g = (w * weights).sum().backward();
+        assert_close_to_literal!(g.get(&a), [[0.5, 0.5], [0.5, 0.5]]);
+    }
+
+    #[test]
+    fn test_decompositions_share_tape() {
+        let dev: Cpu = Default::default();
+        let a = dev
+            .tensor([[2.0, 1.0, 0.5], [1.0, 3.0, -1.0], [0.5, -1.0, 4.0]])
+            .to_dtype::<TestDtype>();
+        let b = a.clone() * 2.0;
+
+        // using only one output still reaches the operations before the decomposition
+        let (_, r) = (a.leaky_trace() * 2.0).qr();
+        let g = r.square().sum().backward();
+        let (_, r) = b.leaky_trace().qr();
+        let g_b = r.square().sum().backward();
+        assert_close_to_tensor!(g.get(&a), g_b.get(&b) * 2.0);
+
+        let (_, s, _) = (a.leaky_trace() * 2.0).svd();
+        let g = s.sum().backward();
+        let (_, s, _) = b.leaky_trace().svd();
+        let g_b = s.sum().backward();
+        assert_close_to_tensor!(g.get(&a), g_b.get(&b) * 2.0);
+
+        let (_, v) = (a.leaky_trace() * 2.0).eigh();
+        let g = v.sum().backward();
+        let (_, v) = b.leaky_trace().eigh();
+        let g_b = v.sum().backward();
+        assert_close_to_tensor!(g.get(&a), g_b.get(&b) * 2.0);
+    }
+
+    #[test]
+    fn test_linalg_shape_errors() {
+        let dev: Cpu = Default::default();
+        let a: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(2, 3));
+        assert!(matches!(a.try_inverse(), Err(CpuError::Shape(_))));
+
+        let a: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(2, 2));
+        let b: Tensor<(usize, Const<1>), TestDtype, _> = dev.zeros_like(&(3, Const));
+        assert!(matches!(a.try_solve(b), Err(CpuError::Shape(_))));
+    }
+}
@@ -0,0 +1,17 @@
+use super::Log1pKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for Log1pKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/log1p.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(
+    Log1pKernelOp,
+    half::f16,
+    PTX,
+    "log1p_fwd_f16",
+    "log1p_bwd_f16"
+);
+cuda_unary!(Log1pKernelOp, f32, PTX, "log1p_fwd_f32", "log1p_bwd_f32");
+cuda_unary!(Log1pKernelOp, f64, PTX, "log1p_fwd_f64", "log1p_bwd_f64");
@@ -0,0 +1,21 @@
+#include "unary_op_macros.cuh"
+
+struct Log1pKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T log1p_bwd(T x) {
+    T one = 1.0;
+    return recipg(one + x);
+}
+
+UNARY_OP(__half, log1p_fwd_f16, log1p_bwd_f16, Log1pKernelOp,
+        log1pg(x),
+        log1p_bwd(x))
+
+UNARY_OP(float, log1p_fwd_f32, log1p_bwd_f32, Log1pKernelOp,
+        log1pg(x),
+        log1p_bwd(x))
+
+UNARY_OP(double, log1p_fwd_f64, log1p_bwd_f64, Log1pKernelOp,
+        log1pg(x),
+        log1p_bwd(x))
@@ -0,0 +1,57 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Log1pKernelOp;
+
+/// `ln(1 + t)`, which is more accurate than [ln()](super::ln()) for `t` close to zero.
+///
+/// The derivative is `1 / (1 + t)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-0.5, 0.0, 1e-8, 1.0]);
+/// let r = t.log1p();
+/// ```
+pub fn log1p<S: Shape, E: Dtype, D: UnaryKernel<Log1pKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.log1p()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<Log1pKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [log1p]
+    pub fn log1p(self) -> Self {
+        self.try_log1p().unwrap()
+    }
+    /// See [log1p]
+    pub fn try_log1p(self) -> Result<Self, D::Err> {
+        try_unary_op(Log1pKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+    use std::f64::consts::LN_2;
+
+    #[test]
+    fn test_log1p() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-0.5, 0.0, 0.5, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().log1p();
+        assert_close_to_literal!(r, [-LN_2, 0.0, 0.40546511, LN_2, 1.0986123]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(g.get(&x), [0.4, 0.2, 0.13333333, 0.1, 0.066666667]);
+    }
+}
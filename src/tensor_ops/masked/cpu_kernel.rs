@@ -0,0 +1,83 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        cpu::{LendingIterator, NdIndex},
+        Cpu, GhostTensor, Tensor, TensorFromVec, ZerosTensor,
+    },
+};
+use std::vec::Vec;
+
+impl<E: Dtype> super::MaskedFillKernel<E> for Cpu {
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+        value: E,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let mut out = self.try_zeros_like(&inp.shape)?;
+        let mut mask_iter = mask.iter();
+        let mut inp_iter = inp.iter();
+        let mut out_iter = out.iter_mut();
+        while let Some((o, (m, x))) = out_iter.next().zip(mask_iter.next().zip(inp_iter.next())) {
+            *o = if *m { value } else { *x };
+        }
+        Ok(out)
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut inp_idx = NdIndex::new(inp.shape, inp.strides);
+        let mut mask_iter = mask.iter();
+        let mut i_out = 0;
+        while let Some((i_inp, m)) = inp_idx.next().zip(mask_iter.next()) {
+            if !*m {
+                grad_inp[i_inp] += grad_out[i_out];
+            }
+            i_out += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::MaskedSelectKernel<E> for Cpu {
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<(usize,), E, Self>, Self::Err> {
+        let mut out = Vec::new();
+        let mut mask_iter = mask.iter();
+        let mut inp_iter = inp.iter();
+        while let Some((m, x)) = mask_iter.next().zip(inp_iter.next()) {
+            if *m {
+                out.push(*x);
+            }
+        }
+        let len = out.len();
+        self.try_tensor_from_vec(out, (len,))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut inp_idx = NdIndex::new(inp.shape, inp.strides);
+        let mut mask_iter = mask.iter();
+        let mut i_out = 0;
+        while let Some((i_inp, m)) = inp_idx.next().zip(mask_iter.next()) {
+            if *m {
+                grad_inp[i_inp] += grad_out[i_out];
+                i_out += 1;
+            }
+        }
+        Ok(())
+    }
+}
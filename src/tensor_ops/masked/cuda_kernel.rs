@@ -0,0 +1,192 @@
+use crate::{
+    shapes::*,
+    tensor::{cpu::NdIndex, launch_cfg, Cuda, DeviceStorage, GhostTensor, Tensor},
+};
+use cudarc::driver::{CudaSlice, LaunchAsync};
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/masked.ptx"));
+
+trait HasCudaKernel<E> {
+    const MOD: &'static str;
+    const FNS: &'static [&'static str];
+}
+
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const MOD: &'static str = "masked_f16";
+    const FNS: &'static [&'static str] = &[
+        "masked_fill_fwd_f16",
+        "masked_fill_bwd_f16",
+        "masked_select_fwd_f16",
+        "masked_select_bwd_f16",
+    ];
+}
+
+impl HasCudaKernel<f32> for Cuda {
+    const MOD: &'static str = "masked_f32";
+    const FNS: &'static [&'static str] = &[
+        "masked_fill_fwd_f32",
+        "masked_fill_bwd_f32",
+        "masked_select_fwd_f32",
+        "masked_select_bwd_f32",
+    ];
+}
+
+impl HasCudaKernel<f64> for Cuda {
+    const MOD: &'static str = "masked_f64";
+    const FNS: &'static [&'static str] = &[
+        "masked_fill_fwd_f64",
+        "masked_fill_bwd_f64",
+        "masked_select_fwd_f64",
+        "masked_select_bwd_f64",
+    ];
+}
+
+impl Cuda {
+    /// Returns the physical indices into `inp` of the entries where `mask` is true,
+    /// in row major order.
+    fn masked_indices<S: Shape, E: Unit>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+    ) -> Vec<usize> {
+        let mask = self.tensor_to_vec(mask);
+        let mut inp_idx = NdIndex::new(inp.shape, inp.strides);
+        let mut idx = Vec::new();
+        for m in mask {
+            let i_inp = inp_idx.next().unwrap();
+            if m {
+                idx.push(i_inp);
+            }
+        }
+        idx
+    }
+}
+
+impl<E: Dtype> super::MaskedFillKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+        value: E,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::MOD, Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::MOD, Self::FNS)?;
+        }
+
+        let shape = inp.shape;
+        let strides = inp.shape.strides();
+        let numel = shape.num_elements();
+
+        let mut storage = unsafe { self.alloc_empty::<E>(numel) }?;
+
+        let dims: CudaSlice<usize> = self.dev.htod_copy(shape.concrete().into())?;
+        let mask_strides: CudaSlice<usize> = self.dev.htod_copy(mask.strides.into())?;
+        let inp_strides: CudaSlice<usize> = self.dev.htod_copy(inp.strides.into())?;
+
+        let fwd_fn = self.dev.get_func(Self::MOD, Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            numel,              // const size_t numel,
+            S::NUM_DIMS,        // const size_t num_dims,
+            &dims,              // const size_t *dims,
+            mask.data.as_ref(), // const bool *mask,
+            &mask_strides,      // const size_t *mask_strides,
+            inp.data.as_ref(),  // const float *inp,
+            &inp_strides,       // const size_t *inp_strides,
+            value,              // const float value,
+            &mut storage,       // float *out,
+        );
+        unsafe { fwd_fn.launch(cfg, params) }?;
+        Ok(self.build_tensor(shape, strides, storage))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let bwd_fn = self.dev.get_func(Self::MOD, Self::FNS[1]).unwrap();
+        let numel = inp.shape.num_elements();
+
+        let dims: CudaSlice<usize> = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let mask_strides: CudaSlice<usize> = self.dev.htod_copy(mask.strides.into())?;
+        let inp_strides: CudaSlice<usize> = self.dev.htod_copy(inp.strides.into())?;
+
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            numel,              // const size_t numel,
+            S::NUM_DIMS,        // const size_t num_dims,
+            &dims,              // const size_t *dims,
+            mask.data.as_ref(), // const bool *mask,
+            &mask_strides,      // const size_t *mask_strides,
+            grad_inp,           // float *grad_inp,
+            &inp_strides,       // const size_t *inp_strides,
+            grad_out,           // const float *grad_out,
+        );
+        unsafe { bwd_fn.launch(cfg, params) }?;
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::MaskedSelectKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<(usize,), E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::MOD, Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::MOD, Self::FNS)?;
+        }
+
+        let idx = self.masked_indices(mask, &inp.ghost());
+        let numel = idx.len();
+        let mut storage = unsafe { self.alloc_empty::<E>(numel) }?;
+        if numel > 0 {
+            let idx: CudaSlice<usize> = self.dev.htod_copy(idx)?;
+            let fwd_fn = self.dev.get_func(Self::MOD, Self::FNS[2]).unwrap();
+            let cfg = launch_cfg::<128>(numel as u32);
+            let params = (
+                numel,             // const size_t numel,
+                &idx,              // const size_t *idx,
+                inp.data.as_ref(), // const float *inp,
+                &mut storage,      // float *out,
+            );
+            unsafe { fwd_fn.launch(cfg, params) }?;
+        }
+        Ok(self.build_tensor((numel,), [1], storage))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let idx = self.masked_indices(mask, inp);
+        let numel = idx.len();
+        if numel == 0 {
+            return Ok(());
+        }
+        let idx: CudaSlice<usize> = self.dev.htod_copy(idx)?;
+        let bwd_fn = self.dev.get_func(Self::MOD, Self::FNS[3]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            numel,    // const size_t numel,
+            &idx,     // const size_t *idx,
+            grad_inp, // float *grad_inp,
+            grad_out, // const float *grad_out,
+        );
+        unsafe { bwd_fn.launch(cfg, params) }?;
+        Ok(())
+    }
+}
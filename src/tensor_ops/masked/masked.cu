@@ -0,0 +1,125 @@
+#include "cuda_utils.cuh"
+
+template<typename T>
+__device__ void masked_fill_fwd(
+    const size_t numel,
+    const size_t num_dims,
+    const size_t *dims,
+    const bool *mask,
+    const size_t *mask_strides,
+    const T *inp,
+    const size_t *inp_strides,
+    const T value,
+    T *out
+) {
+    unsigned int out_i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (out_i >= numel) {
+        return;
+    }
+
+    unsigned int mask_i = get_strided_index(out_i, num_dims, dims, mask_strides);
+    unsigned int inp_i = get_strided_index(out_i, num_dims, dims, inp_strides);
+
+    out[out_i] = mask[mask_i] ? value : inp[inp_i];
+}
+
+template<typename T>
+__device__ void masked_fill_bwd(
+    const size_t numel,
+    const size_t num_dims,
+    const size_t *dims,
+    const bool *mask,
+    const size_t *mask_strides,
+    T *grad_inp,
+    const size_t *inp_strides,
+    const T *grad_out
+) {
+    unsigned int out_i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (out_i >= numel) {
+        return;
+    }
+
+    unsigned int mask_i = get_strided_index(out_i, num_dims, dims, mask_strides);
+    unsigned int inp_i = get_strided_index(out_i, num_dims, dims, inp_strides);
+
+    if (!mask[mask_i]) {
+        atomicAdd(grad_inp + inp_i, grad_out[out_i]);
+    }
+}
+
+template<typename T>
+__device__ void masked_select_fwd(
+    const size_t numel,
+    const size_t *idx,
+    const T *inp,
+    T *out
+) {
+    unsigned int out_i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (out_i >= numel) {
+        return;
+    }
+
+    out[out_i] = inp[idx[out_i]];
+}
+
+template<typename T>
+__device__ void masked_select_bwd(
+    const size_t numel,
+    const size_t *idx,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int out_i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (out_i >= numel) {
+        return;
+    }
+
+    atomicAdd(grad_inp + idx[out_i], grad_out[out_i]);
+}
+
+#define MASKED(TYPENAME, FILL_FWD, FILL_BWD, SELECT_FWD, SELECT_BWD) \
+extern "C" __global__ void FILL_FWD( \
+    const size_t numel, \
+    const size_t num_dims, \
+    const size_t *dims, \
+    const bool *mask, \
+    const size_t *mask_strides, \
+    const TYPENAME *inp, \
+    const size_t *inp_strides, \
+    const TYPENAME value, \
+    TYPENAME *out \
+) { \
+    masked_fill_fwd(numel, num_dims, dims, mask, mask_strides, inp, inp_strides, value, out); \
+} \
+extern "C" __global__ void FILL_BWD( \
+    const size_t numel, \
+    const size_t num_dims, \
+    const size_t *dims, \
+    const bool *mask, \
+    const size_t *mask_strides, \
+    TYPENAME *grad_inp, \
+    const size_t *inp_strides, \
+    const TYPENAME *grad_out \
+) { \
+    masked_fill_bwd(numel, num_dims, dims, mask, mask_strides, grad_inp, inp_strides, grad_out); \
+} \
+extern "C" __global__ void SELECT_FWD( \
+    const size_t numel, \
+    const size_t *idx, \
+    const TYPENAME *inp, \
+    TYPENAME *out \
+) { \
+    masked_select_fwd(numel, idx, inp, out); \
+} \
+extern "C" __global__ void SELECT_BWD( \
+    const size_t numel, \
+    const size_t *idx, \
+    TYPENAME *grad_inp, \
+    const TYPENAME *grad_out \
+) { \
+    masked_select_bwd(numel, idx, grad_inp, grad_out); \
+}
+
+MASKED(__half, masked_fill_fwd_f16, masked_fill_bwd_f16, masked_select_fwd_f16, masked_select_bwd_f16);
+MASKED(float, masked_fill_fwd_f32, masked_fill_bwd_f32, masked_select_fwd_f32, masked_select_bwd_f32);
+MASKED(double, masked_fill_fwd_f64, masked_fill_bwd_f64, masked_select_fwd_f64, masked_select_bwd_f64);
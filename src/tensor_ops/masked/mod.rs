@@ -0,0 +1,256 @@
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{
+    shapes::{broadcast_strides_aligned, Dtype, Shape, ShapeError},
+    tensor::*,
+};
+
+pub trait MaskedFillKernel<E: Dtype>: DeviceStorage {
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+        value: E,
+    ) -> Result<Tensor<S, E, Self>, Self::Err>;
+
+    /// `grad_out` is the gradient of the contiguous output of [MaskedFillKernel::forward].
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+pub trait MaskedSelectKernel<E: Dtype>: DeviceStorage {
+    /// Returns the entries of `inp` where `mask` is true, in row major order.
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<(usize,), E, Self>, Self::Err>;
+
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Views `mask` as `shape`, where the shapes are aligned by their last dimension,
+/// and each dimension of `mask` has to either match `shape` or be 1.
+fn try_broadcast_mask<M: Shape, S: Shape, D: DeviceStorage>(
+    op: &'static str,
+    mask: Tensor<M, bool, D>,
+    shape: S,
+) -> Result<Tensor<S, bool, D>, ShapeError> {
+    let mask_dims = mask.shape.concrete();
+    let dims = shape.concrete();
+    let compatible = M::NUM_DIMS <= S::NUM_DIMS
+        && (0..M::NUM_DIMS).all(|i| {
+            let m = mask_dims[i];
+            m == 1 || m == dims[S::NUM_DIMS - M::NUM_DIMS + i]
+        });
+    if !compatible {
+        return Err(ShapeError::new(op, &shape, &mask.shape));
+    }
+    Ok(Tensor {
+        id: mask.id,
+        strides: broadcast_strides_aligned(&mask.shape, mask.strides, &shape),
+        data: mask.data,
+        shape,
+        offset: mask.offset,
+        device: mask.device,
+        tape: mask.tape,
+    })
+}
+
+/// Replaces the entries of `t` where `mask` is true with `value`. Equivalent to
+/// `torch.masked_fill`.
+///
+/// `mask` is broadcasted to the shape of `t` following numpy's rules, so it may have
+/// fewer dimensions, or dimensions of size 1. The filled entries get no gradient.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+/// let mask = dev.tensor([false, true, true]);
+/// let inf = f32::NEG_INFINITY;
+/// let r = t.masked_fill(mask, inf);
+/// assert_eq!(r.array(), [[1.0, inf, inf], [4.0, inf, inf]]);
+/// ```
+pub fn masked_fill<S: Shape, M: Shape, E: Dtype, D: MaskedFillKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    mask: Tensor<M, bool, D>,
+    value: impl Into<f64>,
+) -> Tensor<S, E, D, T> {
+    t.masked_fill(mask, value)
+}
+
+/// Returns the entries of `t` where `mask` is true as a 1d tensor, in row major order.
+/// Equivalent to `torch.masked_select`.
+///
+/// `mask` is broadcasted to the shape of `t` following numpy's rules. The length of
+/// the output is only known at runtime.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+/// let mask = dev.tensor([[true, false, true], [false, false, true]]);
+/// let r: Tensor<(usize,), f32, _> = t.masked_select(mask);
+/// assert_eq!(r.as_vec(), [1.0, 3.0, 6.0]);
+/// ```
+pub fn masked_select<S: Shape, M: Shape, E: Dtype, D: MaskedSelectKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    mask: Tensor<M, bool, D>,
+) -> Tensor<(usize,), E, D, T> {
+    t.masked_select(mask)
+}
+
+impl<S: Shape, E: Dtype, D: MaskedFillKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [masked_fill]
+    pub fn masked_fill<M: Shape>(self, mask: Tensor<M, bool, D>, value: impl Into<f64>) -> Self {
+        self.try_masked_fill(mask, value).unwrap()
+    }
+
+    /// See [masked_fill]
+    pub fn try_masked_fill<M: Shape>(
+        self,
+        mask: Tensor<M, bool, D>,
+        value: impl Into<f64>,
+    ) -> Result<Self, D::Err> {
+        let mask = try_broadcast_mask("masked_fill", mask, self.shape)?;
+        let value = E::from_f64(value.into()).unwrap();
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward(&mask, &inp, value)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            mask.device.backward(&mask, &inp_ghost, grad_inp, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+impl<S: Shape, E: Dtype, D: MaskedSelectKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [masked_select]
+    pub fn masked_select<M: Shape>(self, mask: Tensor<M, bool, D>) -> Tensor<(usize,), E, D, T> {
+        self.try_masked_select(mask).unwrap()
+    }
+
+    /// See [masked_select]
+    pub fn try_masked_select<M: Shape>(
+        self,
+        mask: Tensor<M, bool, D>,
+    ) -> Result<Tensor<(usize,), E, D, T>, D::Err> {
+        let mask = try_broadcast_mask("masked_select", mask, self.shape)?;
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward(&mask, &inp)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            mask.device.backward(&mask, &inp_ghost, grad_inp, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_masked_fill_broadcast_mask() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 2, 3>, TestDtype, _> = dev.sample_normal();
+        let mask = dev.tensor([[true, false, false], [false, true, true]]);
+        let r = t.leaky_trace().masked_fill(mask, -1.0);
+
+        let keep = dev.tensor([[false, true, true], [true, false, false]]);
+        let fill = dev.tensor([[[-1.0; 3]; 2]; 2]).to_dtype::<TestDtype>();
+        let expected = keep
+            .broadcast::<Rank3<2, 2, 3>, Axis<0>>()
+            .choose(t.leaky_trace(), fill);
+        assert_close_to_tensor!(r, expected);
+
+        let g1 = r.exp().sum().backward();
+        let g2 = expected.exp().sum().backward();
+        assert_close_to_tensor!(g1.get(&t), g2.get(&t));
+    }
+
+    #[test]
+    fn test_masked_fill_size_one_dims() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let mask: Tensor<(usize, Const<1>), bool, _> =
+            dev.tensor((vec![true, false, true], (3, Const)));
+        let r = t.leaky_trace().masked_fill(mask, 0.0);
+        assert_close_to_literal!(r, [[0.0, 0.0], [3.0, 4.0], [0.0, 0.0]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[0.0, 0.0], [1.0, 1.0], [0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_masked_select() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let mask = dev.tensor([[false, true, true], [true, false, false]]);
+        let r = t.leaky_trace().masked_select(mask);
+        assert_eq!(r.shape, (3,));
+        let w = dev.tensor([1.0, 2.0, 3.0]).to_dtype::<TestDtype>();
+        let r = r.realize::<Rank1<3>>();
+        assert_close_to_literal!(r, [2.0, 3.0, 4.0]);
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[0.0, 1.0, 2.0], [3.0, 0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_masked_select_broadcast_and_empty() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = t.clone().masked_select(dev.tensor([true, false, true]));
+        let a = t.array();
+        assert_eq!(r.as_vec(), [a[0][0], a[0][2], a[1][0], a[1][2]]);
+
+        let r = t.leaky_trace().masked_select(dev.tensor([false; 3]));
+        assert_eq!(r.shape, (0,));
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[0.0; 3]; 2]);
+    }
+
+    #[test]
+    fn test_try_masked_fill_shape_error() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let mask: Tensor<Rank1<2>, bool, _> = dev.tensor([true, false]);
+        let err = t.clone().try_masked_fill(mask.clone(), 1.0).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("masked_fill", &(2, 3), &(2,)))
+        );
+        let err = t.try_masked_select(mask).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("masked_select", &(2, 3), &(2,)))
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{reference::physical_index, GhostTensor, Reference, Tensor},
+};
+use std::vec::Vec;
+
+impl<E: Dtype> super::MaskedFillKernel<E> for Reference {
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+        value: E,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let shape = inp.shape;
+        let mut out = self.try_alloc_elem(shape.num_elements(), E::default())?;
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = if mask.data[mask.offset + physical_index(&shape, &mask.strides, i)] {
+                value
+            } else {
+                inp.data[inp.offset + physical_index(&shape, &inp.strides, i)]
+            };
+        }
+        Ok(self.build_tensor(shape, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let shape = inp.shape;
+        for (i, &go) in grad_out.iter().enumerate() {
+            if !mask.data[mask.offset + physical_index(&shape, &mask.strides, i)] {
+                grad_inp[physical_index(&shape, &inp.strides, i)] += go;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::MaskedSelectKernel<E> for Reference {
+    fn forward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<(usize,), E, Self>, Self::Err> {
+        let shape = inp.shape;
+        let out: Vec<E> = (0..shape.num_elements())
+            .filter(|&i| mask.data[mask.offset + physical_index(&shape, &mask.strides, i)])
+            .map(|i| inp.data[inp.offset + physical_index(&shape, &inp.strides, i)])
+            .collect();
+        Ok(self.build_tensor((out.len(),), out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        mask: &Tensor<S, bool, Self>,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let shape = inp.shape;
+        let selected = (0..shape.num_elements())
+            .filter(|&i| mask.data[mask.offset + physical_index(&shape, &mask.strides, i)]);
+        for (i, &go) in selected.zip(grad_out.iter()) {
+            grad_inp[physical_index(&shape, &inp.strides, i)] += go;
+        }
+        Ok(())
+    }
+}
@@ -38,6 +38,7 @@ fn naive_gemm<F: num_traits::Float + std::ops::AddAssign, M: Dim, K: Dim, N: Dim
 pub(crate) trait MatMulImpl<E> {
     #[allow(clippy::too_many_arguments)]
     fn matmul<M: Dim, K: Dim, N: Dim>(
+        &self,
         dims: (M, K, N),
         accum: bool,
         ap: *const E,
@@ -53,6 +54,7 @@ pub(crate) trait MatMulImpl<E> {
 impl MatMulImpl<half::f16> for Cpu {
     #[inline]
     fn matmul<M: Dim, K: Dim, N: Dim>(
+        &self,
         (m, k, n): (M, K, N),
         accum: bool,
         ap: *const half::f16,
@@ -90,7 +92,7 @@ impl MatMulImpl<half::f16> for Cpu {
                 false,
                 false,
                 false,
-                gemm::Parallelism::Rayon(rayon::current_num_threads()),
+                self.gemm_parallelism(),
             )
         }
     }
@@ -99,6 +101,7 @@ impl MatMulImpl<half::f16> for Cpu {
 impl MatMulImpl<f32> for Cpu {
     #[inline]
     fn matmul<M: Dim, K: Dim, N: Dim>(
+        &self,
         (m, k, n): (M, K, N),
         accum: bool,
         ap: *const f32,
@@ -132,7 +135,7 @@ impl MatMulImpl<f32> for Cpu {
                 false,
                 false,
                 false,
-                gemm::Parallelism::Rayon(rayon::current_num_threads()),
+                self.gemm_parallelism(),
             )
         }
     }
@@ -141,6 +144,7 @@ impl MatMulImpl<f32> for Cpu {
 impl MatMulImpl<f64> for Cpu {
     #[inline]
     fn matmul<M: Dim, K: Dim, N: Dim>(
+        &self,
         (m, k, n): (M, K, N),
         accum: bool,
         ap: *const f64,
@@ -174,7 +178,7 @@ impl MatMulImpl<f64> for Cpu {
                 false,
                 false,
                 false,
-                gemm::Parallelism::Rayon(rayon::current_num_threads()),
+                self.gemm_parallelism(),
             )
         }
     }
@@ -192,12 +196,12 @@ where
         let (m, k) = lhs.shape;
         let n = rhs.shape.1;
         let mut out = self.try_zeros_like(&(m, n))?;
-        Self::matmul(
+        self.matmul(
             (m, k, n),
             false,
-            lhs.data.as_ptr(),
+            lhs.data[lhs.offset..].as_ptr(),
             lhs.strides,
-            rhs.data.as_ptr(),
+            rhs.data[rhs.offset..].as_ptr(),
             rhs.strides,
             Arc::get_mut(&mut out.data).unwrap().as_mut_ptr(),
             out.strides,
@@ -215,20 +219,20 @@ where
         let (m, k) = lhs.shape;
         let n = rhs.shape.1;
         let strides = (m, n).strides();
-        Self::matmul(
+        self.matmul(
             (m, n, k),
             true,
             grad_out.as_ptr(),
             strides,
-            rhs.data.as_ptr(),
+            rhs.data[rhs.offset..].as_ptr(),
             [rhs.strides[1], rhs.strides[0]],
             grad_lhs.as_mut_ptr(),
             lhs.strides,
         );
-        Self::matmul(
+        self.matmul(
             (k, m, n),
             true,
-            lhs.data.as_ptr(),
+            lhs.data[lhs.offset..].as_ptr(),
             [lhs.strides[1], lhs.strides[0]],
             grad_out.as_ptr(),
             strides,
@@ -253,12 +257,12 @@ where
         let mut out = self.try_zeros_like(&(batch, m, n))?;
         let cp = Arc::get_mut(&mut out.data).unwrap();
         for i in 0..batch.size() {
-            Self::matmul(
+            self.matmul(
                 (m, k, n),
                 false,
-                lhs.data[i * lhs.strides[0]..].as_ptr(),
+                lhs.data[lhs.offset + i * lhs.strides[0]..].as_ptr(),
                 [lhs.strides[1], lhs.strides[2]],
-                rhs.data.as_ptr(),
+                rhs.data[rhs.offset..].as_ptr(),
                 rhs.strides,
                 cp[i * out.strides[0]..].as_mut_ptr(),
                 [out.strides[1], out.strides[2]],
@@ -278,20 +282,20 @@ where
         let n = rhs.shape.1;
         let strides = (batch, m, n).strides();
         for i in 0..batch.size() {
-            Self::matmul(
+            self.matmul(
                 (m, n, k),
                 true,
                 grad_out[i * strides[0]..].as_ptr(),
                 [strides[1], strides[2]],
-                rhs.data.as_ptr(),
+                rhs.data[rhs.offset..].as_ptr(),
                 [rhs.strides[1], rhs.strides[0]],
                 grad_lhs[i * lhs.strides[0]..].as_mut_ptr(),
                 [lhs.strides[1], lhs.strides[2]],
             );
-            Self::matmul(
+            self.matmul(
                 (k, m, n),
                 true,
-                lhs.data[i * lhs.strides[0]..].as_ptr(),
+                lhs.data[lhs.offset + i * lhs.strides[0]..].as_ptr(),
                 [lhs.strides[2], lhs.strides[1]],
                 grad_out[i * strides[0]..].as_ptr(),
                 [strides[1], strides[2]],
@@ -315,11 +319,11 @@ where
         let (b, m, k) = lhs.shape;
         let n = rhs.shape.2;
         let mut out = self.try_zeros_like(&(b, m, n))?;
-        let ap = lhs.data.as_ref();
-        let bp = rhs.data.as_ref();
+        let ap = &lhs.data[lhs.offset..];
+        let bp = &rhs.data[rhs.offset..];
         let cp = Arc::get_mut(&mut out.data).unwrap();
         for i in 0..b.size() {
-            Self::matmul(
+            self.matmul(
                 (m, k, n),
                 false,
                 ap[i * lhs.strides[0]..].as_ptr(),
@@ -344,20 +348,20 @@ where
         let n = rhs.shape.2;
         let strides = (b, m, n).strides();
         for i in 0..b.size() {
-            Self::matmul(
+            self.matmul(
                 (m, n, k),
                 true,
                 grad_out[i * strides[0]..].as_ptr(),
                 [strides[1], strides[2]],
-                rhs.data[i * rhs.strides[0]..].as_ptr(),
+                rhs.data[rhs.offset + i * rhs.strides[0]..].as_ptr(),
                 [rhs.strides[2], rhs.strides[1]],
                 grad_lhs[i * lhs.strides[0]..].as_mut_ptr(),
                 [lhs.strides[1], lhs.strides[2]],
             );
-            Self::matmul(
+            self.matmul(
                 (k, m, n),
                 true,
-                lhs.data[i * lhs.strides[0]..].as_ptr(),
+                lhs.data[lhs.offset + i * lhs.strides[0]..].as_ptr(),
                 [lhs.strides[2], lhs.strides[1]],
                 grad_out[i * strides[0]..].as_ptr(),
                 [strides[1], strides[2]],
@@ -384,12 +388,12 @@ where
         let cp = Arc::get_mut(&mut out.data).unwrap();
         for i in 0..b.size() {
             for j in 0..s.size() {
-                Self::matmul(
+                self.matmul(
                     (m, k, n),
                     false,
-                    lhs.data[i * lhs.strides[0] + j * lhs.strides[1]..].as_ptr(),
+                    lhs.data[lhs.offset + i * lhs.strides[0] + j * lhs.strides[1]..].as_ptr(),
                     [lhs.strides[2], lhs.strides[3]],
-                    rhs.data[i * rhs.strides[0] + j * rhs.strides[1]..].as_ptr(),
+                    rhs.data[rhs.offset + i * rhs.strides[0] + j * rhs.strides[1]..].as_ptr(),
                     [rhs.strides[2], rhs.strides[3]],
                     cp[i * out.strides[0] + j * out.strides[1]..].as_mut_ptr(),
                     [out.strides[2], out.strides[3]],
@@ -411,20 +415,20 @@ where
         let strides = (b, s, m, n).strides();
         for i in 0..b.size() {
             for j in 0..s.size() {
-                Self::matmul(
+                self.matmul(
                     (m, n, k),
                     true,
                     grad_out[i * strides[0] + j * strides[1]..].as_ptr(),
                     [strides[2], strides[3]],
-                    rhs.data[i * rhs.strides[0] + j * rhs.strides[1]..].as_ptr(),
+                    rhs.data[rhs.offset + i * rhs.strides[0] + j * rhs.strides[1]..].as_ptr(),
                     [rhs.strides[3], rhs.strides[2]],
                     grad_lhs[i * lhs.strides[0] + j * lhs.strides[1]..].as_mut_ptr(),
                     [lhs.strides[2], lhs.strides[3]],
                 );
-                Self::matmul(
+                self.matmul(
                     (k, m, n),
                     true,
-                    lhs.data[i * lhs.strides[0] + j * lhs.strides[1]..].as_ptr(),
+                    lhs.data[lhs.offset + i * lhs.strides[0] + j * lhs.strides[1]..].as_ptr(),
                     [lhs.strides[3], lhs.strides[2]],
                     grad_out[i * strides[0] + j * strides[1]..].as_ptr(),
                     [strides[2], strides[3]],
@@ -1,12 +1,13 @@
 #![allow(clippy::type_complexity)]
 
 pub(super) mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(feature = "cuda")]
 pub(super) mod cuda_kernel;
 
 use crate::{
-    shapes::{Const, Dim, Dtype, Shape},
+    shapes::{Const, Dim, Dtype, Shape, ShapeError},
     tensor::{DeviceStorage, HasErr, Merge, PutTape, SplitTape, Tape, Tensor},
 };
 
@@ -150,7 +151,9 @@ where
     fn try_matmul(self, rhs: Tensor<(K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
         let k1 = self.shape.0;
         let (k2, n) = rhs.shape;
-        assert_eq!(k1, k2);
+        if k1 != k2 {
+            return Err(ShapeError::new("matmul", &self.shape, &rhs.shape).into());
+        }
         let lhs = self.try_reshape_like(&(Const::<1>, k1))?;
         lhs.try_matmul(rhs)?.try_reshape_like(&(n,))
     }
@@ -165,7 +168,9 @@ where
     fn try_matmul(self, rhs: Tensor<(K,), E, D, R>) -> Result<Self::Output, Self::Err> {
         let (m, k1) = self.shape;
         let k2 = rhs.shape.0;
-        assert_eq!(k1, k2);
+        if k1 != k2 {
+            return Err(ShapeError::new("matmul", &self.shape, &rhs.shape).into());
+        }
         let rhs = rhs.try_reshape_like(&(k2, Const::<1>))?;
         self.try_matmul(rhs)?.try_reshape_like(&(m,))
     }
@@ -186,7 +191,9 @@ where
     /// let _: Tensor<Rank2<3, 4>, f32, _> = x.try_matmul(y);
     /// ```
     fn try_matmul(self, rhs: Tensor<(K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
-        assert_eq!(self.shape.1, rhs.shape.0);
+        if self.shape.1 != rhs.shape.0 {
+            return Err(ShapeError::new("matmul", &self.shape, &rhs.shape).into());
+        }
         try_binary_op(self, rhs, D::forward, D::backward)
     }
 }
@@ -223,7 +230,9 @@ where
     /// let _: Tensor<Rank3<1, 3, 4>, f32, _> = x.try_matmul(y);
     /// ```
     fn try_matmul(self, rhs: Tensor<(K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
-        assert_eq!(self.shape.2, rhs.shape.0);
+        if self.shape.2 != rhs.shape.0 {
+            return Err(ShapeError::new("matmul", &self.shape, &rhs.shape).into());
+        }
         try_binary_op(self, rhs, D::forward, D::backward)
     }
 }
@@ -261,8 +270,9 @@ where
     /// let _: Tensor<Rank3<1, 3, 4>, f32, _> = x.try_matmul(y);
     /// ```
     fn try_matmul(self, rhs: Tensor<(B, K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
-        assert_eq!(self.shape.0, rhs.shape.0);
-        assert_eq!(self.shape.2, rhs.shape.1);
+        if self.shape.0 != rhs.shape.0 || self.shape.2 != rhs.shape.1 {
+            return Err(ShapeError::new("matmul", &self.shape, &rhs.shape).into());
+        }
         try_binary_op(self, rhs, D::forward, D::backward)
     }
 }
@@ -300,9 +310,10 @@ where
     /// let _: Tensor<Rank3<1, 5, 3, 4>, f32, _> = x.try_matmul(y);
     /// ```
     fn try_matmul(self, rhs: Tensor<(B, S, K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
-        assert_eq!(self.shape.0, rhs.shape.0);
-        assert_eq!(self.shape.1, rhs.shape.1);
-        assert_eq!(self.shape.3, rhs.shape.2);
+        if self.shape.0 != rhs.shape.0 || self.shape.1 != rhs.shape.1 || self.shape.3 != rhs.shape.2
+        {
+            return Err(ShapeError::new("matmul", &self.shape, &rhs.shape).into());
+        }
         try_binary_op(self, rhs, D::forward, D::backward)
     }
 }
@@ -699,7 +710,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `3`,\n right: `4`"]
+    #[should_panic = "op: \"matmul\""]
     fn test_dynamic_matmul_matmat_fail() {
         let dev: TestDevice = Default::default();
         let x: Tensor<(Const<3>, usize), f32, _> = dev.zeros_like(&(Const, 3));
@@ -708,7 +719,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `3`,\n right: `4`"]
+    #[should_panic = "op: \"matmul\""]
     fn test_dynamic_matmul_matmatbr_fail() {
         let dev: TestDevice = Default::default();
         let x: Tensor<(Const<1>, Const<3>, usize), f32, _> = dev.zeros_like(&(Const, Const, 3));
@@ -717,7 +728,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `3`,\n right: `4`"]
+    #[should_panic = "op: \"matmul\""]
     fn test_dynamic_matmul_matmat_batch_fail() {
         let dev: TestDevice = Default::default();
         let x: Tensor<(Const<1>, Const<3>, usize), f32, _> = dev.zeros_like(&(Const, Const, 3));
@@ -726,7 +737,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `3`,\n right: `4`"]
+    #[should_panic = "op: \"matmul\""]
     fn test_dynamic_matmul_matmat_4d_fail() {
         let dev: TestDevice = Default::default();
         let x: Tensor<(Const<1>, Const<5>, Const<3>, usize), f32, _> =
@@ -737,7 +748,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `1`,\n right: `2`"]
+    #[should_panic = "op: \"matmul\""]
     fn test_dynamic_batch_batch3_fail() {
         let dev: TestDevice = Default::default();
         let x: Tensor<_, TestDtype, _> = dev.zeros_like(&(1, 2, 3));
@@ -746,7 +757,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `1`,\n right: `2`"]
+    #[should_panic = "op: \"matmul\""]
     fn test_dynamic_batch_batch4_fail() {
         let dev: TestDevice = Default::default();
         let x: Tensor<_, TestDtype, _> = dev.zeros_like(&(1, 1, 2, 3));
@@ -755,11 +766,23 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "left: `1`,\n right: `2`"]
+    #[should_panic = "op: \"matmul\""]
     fn test_dynamic_seq_batch4_fail() {
         let dev: TestDevice = Default::default();
         let x: Tensor<_, TestDtype, _> = dev.zeros_like(&(1, 1, 2, 3));
         let y = dev.zeros_like(&(1, 2, 3, 4));
         let _ = x.matmul(y);
     }
+
+    #[test]
+    fn test_dynamic_matmul_try_fail() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<_, TestDtype, _> = dev.zeros_like(&(2, 3));
+        let y: Tensor<_, TestDtype, _> = dev.zeros_like(&(4, 5));
+        let err = x.try_matmul(y).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("matmul", &(2, 3), &(4, 5)))
+        );
+    }
 }
@@ -0,0 +1,235 @@
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Reference, Tensor},
+};
+
+use std::vec::Vec;
+
+/// Dimensions of a (possibly batched) matmul, and the physical index functions
+/// for `lhs[b, m, k]` & `rhs[b, k, n]`.
+struct Naive<L, R> {
+    batch: usize,
+    m: usize,
+    k: usize,
+    n: usize,
+    lhs: L,
+    rhs: R,
+}
+
+impl<L: Fn(usize, usize, usize) -> usize, R: Fn(usize, usize, usize) -> usize> Naive<L, R> {
+    fn forward<E: Dtype>(&self, lhs: &[E], rhs: &[E]) -> Vec<E> {
+        let mut out = Vec::with_capacity(self.batch * self.m * self.n);
+        for b in 0..self.batch {
+            for i in 0..self.m {
+                for j in 0..self.n {
+                    let mut tmp = E::default();
+                    for k in 0..self.k {
+                        tmp += lhs[(self.lhs)(b, i, k)] * rhs[(self.rhs)(b, k, j)];
+                    }
+                    out.push(tmp);
+                }
+            }
+        }
+        out
+    }
+
+    fn backward<E: Dtype>(
+        &self,
+        lhs: &[E],
+        grad_lhs: &mut [E],
+        rhs: &[E],
+        grad_rhs: &mut [E],
+        grad_out: &[E],
+    ) {
+        for b in 0..self.batch {
+            for i in 0..self.m {
+                for j in 0..self.n {
+                    let go = grad_out[(b * self.m + i) * self.n + j];
+                    for k in 0..self.k {
+                        let (l, r) = ((self.lhs)(b, i, k), (self.rhs)(b, k, j));
+                        grad_lhs[l] += go * rhs[r];
+                        grad_rhs[r] += go * lhs[l];
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn mat<M: Dim, K: Dim, N: Dim, E: Dtype>(
+    lhs: &Tensor<(M, K), E, Reference>,
+    rhs: &Tensor<(K, N), E, Reference>,
+) -> Naive<impl Fn(usize, usize, usize) -> usize, impl Fn(usize, usize, usize) -> usize> {
+    let (ls, rs) = (lhs.strides, rhs.strides);
+    Naive {
+        batch: 1,
+        m: lhs.shape.0.size(),
+        k: lhs.shape.1.size(),
+        n: rhs.shape.1.size(),
+        lhs: move |_, i, k| i * ls[0] + k * ls[1],
+        rhs: move |_, k, j| k * rs[0] + j * rs[1],
+    }
+}
+
+fn mat_br<B: Dim, M: Dim, K: Dim, N: Dim, E: Dtype>(
+    lhs: &Tensor<(B, M, K), E, Reference>,
+    rhs: &Tensor<(K, N), E, Reference>,
+) -> Naive<impl Fn(usize, usize, usize) -> usize, impl Fn(usize, usize, usize) -> usize> {
+    let (ls, rs) = (lhs.strides, rhs.strides);
+    Naive {
+        batch: lhs.shape.0.size(),
+        m: lhs.shape.1.size(),
+        k: lhs.shape.2.size(),
+        n: rhs.shape.1.size(),
+        lhs: move |b, i, k| b * ls[0] + i * ls[1] + k * ls[2],
+        rhs: move |_, k, j| k * rs[0] + j * rs[1],
+    }
+}
+
+fn batch3<B: Dim, M: Dim, K: Dim, N: Dim, E: Dtype>(
+    lhs: &Tensor<(B, M, K), E, Reference>,
+    rhs: &Tensor<(B, K, N), E, Reference>,
+) -> Naive<impl Fn(usize, usize, usize) -> usize, impl Fn(usize, usize, usize) -> usize> {
+    let (ls, rs) = (lhs.strides, rhs.strides);
+    Naive {
+        batch: lhs.shape.0.size(),
+        m: lhs.shape.1.size(),
+        k: lhs.shape.2.size(),
+        n: rhs.shape.2.size(),
+        lhs: move |b, i, k| b * ls[0] + i * ls[1] + k * ls[2],
+        rhs: move |b, k, j| b * rs[0] + k * rs[1] + j * rs[2],
+    }
+}
+
+fn batch4<B: Dim, S: Dim, M: Dim, K: Dim, N: Dim, E: Dtype>(
+    lhs: &Tensor<(B, S, M, K), E, Reference>,
+    rhs: &Tensor<(B, S, K, N), E, Reference>,
+) -> Naive<impl Fn(usize, usize, usize) -> usize, impl Fn(usize, usize, usize) -> usize> {
+    let (ls, rs) = (lhs.strides, rhs.strides);
+    let s = lhs.shape.1.size();
+    Naive {
+        batch: lhs.shape.0.size() * s,
+        m: lhs.shape.2.size(),
+        k: lhs.shape.3.size(),
+        n: rhs.shape.3.size(),
+        lhs: move |b, i, k| (b / s) * ls[0] + (b % s) * ls[1] + i * ls[2] + k * ls[3],
+        rhs: move |b, k, j| (b / s) * rs[0] + (b % s) * rs[1] + k * rs[2] + j * rs[3],
+    }
+}
+
+impl<E: Dtype> super::MatMatKernel<E> for Reference {
+    fn forward<M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        rhs: &Tensor<(K, N), E, Self>,
+    ) -> Result<Tensor<(M, N), E, Self>, Self::Err> {
+        let out = mat(lhs, rhs).forward(&lhs.data[lhs.offset..], &rhs.data[rhs.offset..]);
+        Ok(self.build_tensor((lhs.shape.0, rhs.shape.1), out))
+    }
+
+    fn backward<M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<(K, N), E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        mat(lhs, rhs).backward(
+            &lhs.data[lhs.offset..],
+            grad_lhs,
+            &rhs.data[rhs.offset..],
+            grad_rhs,
+            grad_out,
+        );
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::MatMatBrKernel<E> for Reference {
+    fn forward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(B, M, K), E, Self>,
+        rhs: &Tensor<(K, N), E, Self>,
+    ) -> Result<Tensor<(B, M, N), E, Self>, Self::Err> {
+        let out = mat_br(lhs, rhs).forward(&lhs.data[lhs.offset..], &rhs.data[rhs.offset..]);
+        Ok(self.build_tensor((lhs.shape.0, lhs.shape.1, rhs.shape.1), out))
+    }
+
+    fn backward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(B, M, K), E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<(K, N), E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        mat_br(lhs, rhs).backward(
+            &lhs.data[lhs.offset..],
+            grad_lhs,
+            &rhs.data[rhs.offset..],
+            grad_rhs,
+            grad_out,
+        );
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::MatMatBatch3Kernel<E> for Reference {
+    fn forward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(B, M, K), E, Self>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+    ) -> Result<Tensor<(B, M, N), E, Self>, Self::Err> {
+        let out = batch3(lhs, rhs).forward(&lhs.data[lhs.offset..], &rhs.data[rhs.offset..]);
+        Ok(self.build_tensor((lhs.shape.0, lhs.shape.1, rhs.shape.2), out))
+    }
+
+    fn backward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(B, M, K), E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        batch3(lhs, rhs).backward(
+            &lhs.data[lhs.offset..],
+            grad_lhs,
+            &rhs.data[rhs.offset..],
+            grad_rhs,
+            grad_out,
+        );
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::MatMatBatch4Kernel<E> for Reference {
+    fn forward<B: Dim, S: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(B, S, M, K), E, Self>,
+        rhs: &Tensor<(B, S, K, N), E, Self>,
+    ) -> Result<Tensor<(B, S, M, N), E, Self>, Self::Err> {
+        let out = batch4(lhs, rhs).forward(&lhs.data[lhs.offset..], &rhs.data[rhs.offset..]);
+        let shape = (lhs.shape.0, lhs.shape.1, lhs.shape.2, rhs.shape.3);
+        Ok(self.build_tensor(shape, out))
+    }
+
+    fn backward<B: Dim, S: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(B, S, M, K), E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<(B, S, K, N), E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        batch4(lhs, rhs).backward(
+            &lhs.data[lhs.offset..],
+            grad_lhs,
+            &rhs.data[rhs.offset..],
+            grad_rhs,
+            grad_out,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+impl<E: Float + Dtype> super::MaxPool2DIndicesKernel<E> for Cpu {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, usize, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool2DOp,
+        inp: &Tensor<I, E, Self>,
+        idx: &mut Tensor<O, usize, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = inp.strides;
+        let ostr = idx.strides;
+
+        let buf = &inp.data[inp.offset..];
+        let idx_buf = Arc::make_mut(&mut idx.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for oh in 0..op.h_out {
+                    for ow in 0..op.w_out {
+                        let mut tmp = E::neg_infinity();
+                        let mut argmax = 0;
+                        for k1 in 0..op.kernel {
+                            let y = (oh * op.stride + op.dilation * k1).checked_sub(op.padding);
+                            for k2 in 0..op.kernel {
+                                let x = (ow * op.stride + op.dilation * k2).checked_sub(op.padding);
+                                if let Some((y, x)) = y.zip(x) {
+                                    if y < op.h_in && x < op.w_in {
+                                        let inp_idx =
+                                            b * istr[0] + c * istr[1] + y * istr[2] + x * istr[3];
+                                        if buf[inp_idx] > tmp {
+                                            tmp = buf[inp_idx];
+                                            argmax = y * op.w_in + x;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        idx_buf[b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3]] = argmax;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
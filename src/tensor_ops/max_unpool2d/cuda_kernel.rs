@@ -0,0 +1,61 @@
+use crate::{
+    shapes::*,
+    tensor::{launch_cfg, Cuda, Tensor},
+};
+
+use std::sync::Arc;
+
+use cudarc::driver::LaunchAsync;
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/max_unpool2d.ptx"));
+
+trait HasCudaKernel<E> {
+    const FWD: &'static str;
+}
+
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FWD: &'static str = "max_pool2d_indices_f16";
+}
+
+impl HasCudaKernel<f32> for Cuda {
+    const FWD: &'static str = "max_pool2d_indices_f32";
+}
+
+impl HasCudaKernel<f64> for Cuda {
+    const FWD: &'static str = "max_pool2d_indices_f64";
+}
+
+impl<E: Dtype> super::MaxPool2DIndicesKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, usize, Self>, Self::Err> {
+        let data = unsafe { self.alloc_empty::<usize>(s.num_elements()) }?;
+        Ok(self.build_tensor(s, s.strides(), data))
+    }
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool2DOp,
+        inp: &Tensor<I, E, Self>,
+        idx: &mut Tensor<O, usize, Self>,
+    ) -> Result<(), Self::Err> {
+        if !self.dev.has_func(Self::FWD, Self::FWD) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FWD, &[Self::FWD])?;
+        }
+
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+        let idx_strides = self.dev.htod_copy(idx.strides.into())?;
+        let fwd_fn = self.dev.get_func(Self::FWD, Self::FWD).unwrap();
+        let cfg = launch_cfg::<128>(idx.shape().num_elements() as u32);
+        let params = (
+            op,                           // const Pool2dOp op,
+            &inp_strides,                 // const size_t *inp_strides,
+            &idx_strides,                 // const size_t *idx_strides,
+            inp.data.as_ref(),            // const float *inp,
+            Arc::make_mut(&mut idx.data), // size_t *idx
+        );
+        unsafe { fwd_fn.launch(cfg, params) }?;
+        Ok(())
+    }
+}
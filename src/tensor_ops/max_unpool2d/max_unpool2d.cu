@@ -0,0 +1,82 @@
+#include "cuda_utils.cuh"
+
+enum Pool2dKind {
+    AVG,
+    MIN,
+    MAX,
+};
+
+struct Pool2dOp {
+    Pool2dKind kind;
+    size_t kernel;
+    size_t stride;
+    size_t padding;
+    size_t dilation;
+    size_t batch;
+    size_t chan;
+    size_t h_in;
+    size_t h_out;
+    size_t w_in;
+    size_t w_out;
+};
+
+template<typename T>
+__device__ void max_pool2d_indices(
+    const Pool2dOp op,
+    const size_t *inp_strides,
+    const size_t *idx_strides,
+    const T *inp, // 4d (Batch, Channels, Height, Width)
+    size_t *idx // 4d (Batch, Channels, HeightOut, WidthOut)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    const size_t numel = op.batch * op.chan * op.h_out * op.w_out;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int tmp_i = i;
+    const size_t ow = tmp_i % op.w_out;
+    tmp_i /= op.w_out;
+    const size_t oh = tmp_i % op.h_out;
+    tmp_i /= op.h_out;
+    const size_t c = tmp_i % op.chan;
+    tmp_i /= op.chan;
+    const size_t b = tmp_i % op.batch;
+
+    T tmp = -INFINITY;
+    size_t argmax = 0;
+    for(size_t k1 = 0; k1 < op.kernel; k1++) {
+        const size_t y_plus_p = oh * op.stride + op.dilation * k1;
+        if (y_plus_p < op.padding) { continue; }
+        const size_t y = y_plus_p - op.padding;
+        if (y >= op.h_in) { continue; }
+        for(size_t k2 = 0; k2 < op.kernel; k2++) {
+            const size_t x_plus_p = ow * op.stride + op.dilation * k2;
+            if (x_plus_p < op.padding) { continue; }
+            const size_t x = x_plus_p - op.padding;
+            if (x >= op.w_in) { continue; }
+            auto inp_i = b * inp_strides[0] + c * inp_strides[1] + y * inp_strides[2] + x * inp_strides[3];
+            if (inp[inp_i] > tmp) {
+                tmp = inp[inp_i];
+                argmax = y * op.w_in + x;
+            }
+        }
+    }
+
+    idx[b * idx_strides[0] + c * idx_strides[1] + oh * idx_strides[2] + ow * idx_strides[3]] = argmax;
+}
+
+#define MAX_POOL2D_INDICES(TYPENAME, fwd) \
+extern "C" __global__ void fwd( \
+    const Pool2dOp op, \
+    const size_t *inp_strides, \
+    const size_t *idx_strides, \
+    const TYPENAME *inp, \
+    size_t *idx \
+) { \
+    max_pool2d_indices(op, inp_strides, idx_strides, inp, idx); \
+}
+
+MAX_POOL2D_INDICES(__half, max_pool2d_indices_f16);
+MAX_POOL2D_INDICES(float, max_pool2d_indices_f32);
+MAX_POOL2D_INDICES(double, max_pool2d_indices_f64);
@@ -0,0 +1,439 @@
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{shapes::*, tensor::*};
+
+use super::{
+    pool2d::{Pool2DKernel, Pool2DOp},
+    reshape_to::ReshapeKernel,
+    scatter::ScatterKernel,
+    Pool2DKind, ReshapeTo, TryPool2D,
+};
+
+pub(super) trait MaxPool2DIndicesKernel<E: Dtype>: DeviceStorage {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, usize, Self>, Self::Err>;
+
+    /// Writes the position `y * w_in + x` of the maximum of each window into `idx`.
+    /// Ties go to the first maximum in row major order.
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: Pool2DOp,
+        inp: &Tensor<I, E, Self>,
+        idx: &mut Tensor<O, usize, Self>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Max pools images like [TryPool2D] with [Pool2DKind::Max], and also returns where
+/// each maximum came from. The indices are positions `y * W + x` into the `(H, W)` plane
+/// of the input, the same as pytorch's `MaxPool2d(return_indices=True)`, and can be
+/// passed to [TryMaxUnpool2D] to undo the pooling.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let x = dev.tensor([[[1.0, 5.0, 2.0, 0.0], [3.0, 4.0, 8.0, 6.0]]]);
+/// let (y, idx) = x.max_pool2d_with_indices(Const::<2>, Const::<2>, Const::<0>, Const::<1>);
+/// assert_eq!(y.as_vec(), [5.0, 8.0]);
+/// assert_eq!(idx.as_vec(), [1, 6]);
+/// ```
+pub trait TryMaxPool2DWithIndices<Kernel, Stride, Padding, Dilation>: Sized {
+    type Pooled;
+    type Indices;
+    type Error: std::fmt::Debug;
+
+    fn max_pool2d_with_indices(
+        self,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> (Self::Pooled, Self::Indices) {
+        self.try_max_pool2d_with_indices(kernel, stride, padding, dilation)
+            .unwrap()
+    }
+
+    fn try_max_pool2d_with_indices(
+        self,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<(Self::Pooled, Self::Indices), Self::Error>;
+}
+
+/// Scatters pooled images back into zeroed `(H, W)` images, at the positions given by
+/// the indices from [TryMaxPool2DWithIndices]. Every other entry of the output is zero.
+/// Equivalent to pytorch's `MaxUnpool2d` with an explicit `output_size`.
+///
+/// If an index appears more than once in the same image, which of the values ends up in
+/// the output is unspecified. Max pooling only produces duplicates when windows overlap,
+/// in which case the duplicated values are equal.
+///
+/// Returns a [ShapeError] if `indices` doesn't have the same shape as `self`, and
+/// **panics** if an index is not less than `H * W`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let y = dev.tensor([[[5.0, 8.0]]]);
+/// let idx = dev.tensor([[[1, 6]]]);
+/// let x: Tensor<Rank3<1, 2, 4>, f32, _> = y.max_unpool2d(idx, Const, Const);
+/// assert_eq!(x.array(), [[[0.0, 5.0, 0.0, 0.0], [0.0, 0.0, 8.0, 0.0]]]);
+/// ```
+pub trait TryMaxUnpool2D<H, W>: Sized {
+    type Indices;
+    type Unpooled;
+    type Error: std::fmt::Debug;
+
+    fn max_unpool2d(self, indices: Self::Indices, h: H, w: W) -> Self::Unpooled {
+        self.try_max_unpool2d(indices, h, w).unwrap()
+    }
+
+    fn try_max_unpool2d(
+        self,
+        indices: Self::Indices,
+        h: H,
+        w: W,
+    ) -> Result<Self::Unpooled, Self::Error>;
+}
+
+impl<Chan, Kernel, Stride, Padding, Dilation, H, W, E, D, T>
+    TryMaxPool2DWithIndices<Kernel, Stride, Padding, Dilation> for Tensor<(Chan, H, W), E, D, T>
+where
+    Chan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    H: Dim + TryPool2D<Kernel, Stride, Padding, Dilation>,
+    H::Pooled: Dim,
+    W: Dim + TryPool2D<Kernel, Stride, Padding, Dilation>,
+    W::Pooled: Dim,
+    E: Dtype,
+    D: MaxPool2DIndicesKernel<E> + Pool2DKernel<E> + ReshapeKernel<E> + ReshapeKernel<usize>,
+    T: Tape<E, D>,
+{
+    type Pooled = Tensor<(Chan, H::Pooled, W::Pooled), E, D, T>;
+    type Indices = Tensor<(Chan, H::Pooled, W::Pooled), usize, D>;
+    type Error = D::Err;
+
+    fn try_max_pool2d_with_indices(
+        self,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<(Self::Pooled, Self::Indices), Self::Error> {
+        let (chan, h, w) = self.shape;
+        let img = self.try_reshape_like(&(Const::<1>, chan, h, w))?;
+        let (out, idx) = img.try_max_pool2d_with_indices(kernel, stride, padding, dilation)?;
+        let (_, _, out_h, out_w) = out.shape;
+        Ok((
+            out.try_reshape_like(&(chan, out_h, out_w))?,
+            idx.try_reshape_like(&(chan, out_h, out_w))?,
+        ))
+    }
+}
+
+impl<Chan, Kernel, Stride, Padding, Dilation, Batch, H, W, E, D, T>
+    TryMaxPool2DWithIndices<Kernel, Stride, Padding, Dilation>
+    for Tensor<(Batch, Chan, H, W), E, D, T>
+where
+    Chan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Batch: Dim,
+    H: Dim + TryPool2D<Kernel, Stride, Padding, Dilation>,
+    H::Pooled: Dim,
+    W: Dim + TryPool2D<Kernel, Stride, Padding, Dilation>,
+    W::Pooled: Dim,
+    E: Dtype,
+    D: MaxPool2DIndicesKernel<E> + Pool2DKernel<E> + ReshapeKernel<E>,
+    T: Tape<E, D>,
+{
+    type Pooled = Tensor<(Batch, Chan, H::Pooled, W::Pooled), E, D, T>;
+    type Indices = Tensor<(Batch, Chan, H::Pooled, W::Pooled), usize, D>;
+    type Error = D::Err;
+
+    fn try_max_pool2d_with_indices(
+        self,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<(Self::Pooled, Self::Indices), Self::Error> {
+        let (batch, chan, h, w) = self.shape;
+        // the kernels only handle contiguous inputs, so views are copied first
+        let inp = self.try_contiguous()?;
+        let kind = Pool2DKind::Max;
+        let h_out = h.pool2d(kind, kernel, stride, padding, dilation);
+        let w_out = w.pool2d(kind, kernel, stride, padding, dilation);
+        let op = Pool2DOp {
+            kind,
+            stride: stride.size(),
+            padding: padding.size(),
+            kernel: kernel.size(),
+            dilation: dilation.size(),
+            batch: batch.size(),
+            chan: chan.size(),
+            h_in: h.size(),
+            h_out: h_out.size(),
+            w_in: w.size(),
+            w_out: w_out.size(),
+        };
+        let (img, tape) = inp.split_tape();
+        let mut idx = MaxPool2DIndicesKernel::alloc(&img.device, (batch, chan, h_out, w_out))?;
+        MaxPool2DIndicesKernel::forward(&img.device, op, &img, &mut idx)?;
+        let out = img
+            .put_tape(tape)
+            .try_pool2d(kind, kernel, stride, padding, dilation)?;
+        Ok((out, idx))
+    }
+}
+
+impl<Chan, PooledH, PooledW, H, W, E, D, T> TryMaxUnpool2D<H, W>
+    for Tensor<(Chan, PooledH, PooledW), E, D, T>
+where
+    Chan: Dim,
+    PooledH: Dim,
+    PooledW: Dim,
+    H: Dim,
+    W: Dim,
+    E: Dtype,
+    D: ScatterKernel<E> + ZerosTensor<E> + ReshapeKernel<E> + ReshapeKernel<usize>,
+    T: Tape<E, D> + Merge<NoneTape>,
+{
+    type Indices = Tensor<(Chan, PooledH, PooledW), usize, D>;
+    type Unpooled = Tensor<(Chan, H, W), E, D, T>;
+    type Error = D::Err;
+
+    fn try_max_unpool2d(
+        self,
+        indices: Self::Indices,
+        h: H,
+        w: W,
+    ) -> Result<Self::Unpooled, Self::Error> {
+        let (chan, pooled_h, pooled_w) = self.shape;
+        let img = self.try_reshape_like(&(Const::<1>, chan, pooled_h, pooled_w))?;
+        let (idx_chan, idx_h, idx_w) = indices.shape;
+        let indices = indices.try_reshape_like(&(Const::<1>, idx_chan, idx_h, idx_w))?;
+        let out = img.try_max_unpool2d(indices, h, w)?;
+        out.try_reshape_like(&(chan, h, w))
+    }
+}
+
+impl<Batch, Chan, PooledH, PooledW, H, W, E, D, T> TryMaxUnpool2D<H, W>
+    for Tensor<(Batch, Chan, PooledH, PooledW), E, D, T>
+where
+    Batch: Dim,
+    Chan: Dim,
+    PooledH: Dim,
+    PooledW: Dim,
+    H: Dim,
+    W: Dim,
+    E: Dtype,
+    D: ScatterKernel<E> + ZerosTensor<E> + ReshapeKernel<E> + ReshapeKernel<usize>,
+    T: Tape<E, D> + Merge<NoneTape>,
+{
+    type Indices = Tensor<(Batch, Chan, PooledH, PooledW), usize, D>;
+    type Unpooled = Tensor<(Batch, Chan, H, W), E, D, T>;
+    type Error = D::Err;
+
+    fn try_max_unpool2d(
+        self,
+        indices: Self::Indices,
+        h: H,
+        w: W,
+    ) -> Result<Self::Unpooled, Self::Error> {
+        if indices.shape.concrete() != self.shape.concrete() {
+            return Err(ShapeError::new("max_unpool2d", &indices.shape, &self.shape).into());
+        }
+        // unpooling is a scatter along the flattened `(H, W)` plane of each image
+        let (batch, chan, pooled_h, pooled_w) = self.shape;
+        let src_shape = (batch, chan, pooled_h.size() * pooled_w.size());
+        let src = self.try_reshape_like(&src_shape)?;
+        let indices = indices.try_reshape_like(&src_shape)?;
+        let (src, tape) = src.split_tape();
+        let dst = src
+            .device
+            .try_zeros_like(&(batch, chan, h.size() * w.size()))?
+            .put_tape(tape);
+        let out = dst.try_scatter(Axis::<2>, indices, src)?;
+        out.try_reshape_like(&(batch, chan, h, w))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_max_pool2d_with_indices() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[
+                [1.0, 2.0, 3.0, 4.0],
+                [8.0, 7.0, 6.0, 5.0],
+                [0.0, 9.0, -1.0, -2.0],
+                [3.0, 3.0, -4.0, -3.0],
+            ]])
+            .to_dtype::<TestDtype>();
+        let (y, idx) = x.max_pool2d_with_indices(Const::<2>, Const::<2>, Const::<0>, Const::<1>);
+        let y = y.realize::<Rank3<1, 2, 2>>();
+        let idx = idx.realize::<Rank3<1, 2, 2>>();
+        assert_close_to_literal!(y, [[[8.0, 6.0], [9.0, -1.0]]]);
+        assert_eq!(idx.array(), [[[4, 6], [9, 10]]]);
+    }
+
+    #[test]
+    fn test_max_pool2d_with_indices_matches_pool2d() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<2, 3, 7, 6>, TestDtype, _> = dev.sample_normal();
+        let (y, idx) = x
+            .clone()
+            .max_pool2d_with_indices(Const::<3>, Const::<2>, Const::<1>, Const::<1>);
+        let expected = x.clone().pool2d(
+            Pool2DKind::Max,
+            Const::<3>,
+            Const::<2>,
+            Const::<1>,
+            Const::<1>,
+        );
+        assert_eq!(y.as_vec(), expected.as_vec());
+
+        // every index points at the value it pooled
+        let x = x.as_vec();
+        let (y, idx) = (y.as_vec(), idx.as_vec());
+        let plane_out = idx.len() / 6;
+        for (i, (v, j)) in y.iter().zip(idx.iter()).enumerate() {
+            assert!(*j < 7 * 6);
+            assert_eq!(*v, x[(i / plane_out) * 7 * 6 + j]);
+        }
+    }
+
+    #[test]
+    fn test_max_pool2d_with_indices_padded_dilated() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[[-1.0, -2.0, -3.0], [-4.0, -5.0, -6.0], [-7.0, -8.0, -9.0]]])
+            .to_dtype::<TestDtype>();
+        let (y, idx) = x.max_pool2d_with_indices(Const::<2>, Const::<2>, Const::<1>, Const::<2>);
+        let y = y.realize::<Rank3<1, 2, 2>>();
+        // padding never wins, even though every real value is negative
+        assert_close_to_literal!(y, [[[-5.0, -5.0], [-5.0, -5.0]]]);
+        assert_eq!(idx.as_vec(), [4, 4, 4, 4]);
+    }
+
+    #[test]
+    fn test_max_pool2d_with_indices_sliced_input() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[[
+                [0.0, 0.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 2.0, 3.0, 4.0],
+                [0.0, 8.0, 7.0, 6.0, 5.0],
+                [0.0, 0.0, 9.0, -1.0, -2.0],
+                [0.0, 3.0, 3.0, -4.0, -3.0],
+            ]]])
+            .to_dtype::<TestDtype>();
+        let x = x.slice((.., .., 1.., 1..)).realize::<Rank4<1, 1, 4, 4>>();
+        let (y, idx) = x.max_pool2d_with_indices(Const::<2>, Const::<2>, Const::<0>, Const::<1>);
+        let y = y.realize::<Rank4<1, 1, 2, 2>>();
+        assert_close_to_literal!(y, [[[[8.0, 6.0], [9.0, -1.0]]]]);
+        assert_eq!(idx.as_vec(), [4, 6, 9, 10]);
+    }
+
+    #[test]
+    fn test_max_unpool2d() {
+        let dev: TestDevice = Default::default();
+        let y = dev
+            .tensor([[[1.0, 2.0], [3.0, 4.0]], [[-1.0, -2.0], [-3.0, -4.0]]])
+            .to_dtype::<TestDtype>();
+        let idx = dev.tensor([[[0, 3], [10, 8]], [[5, 2], [7, 11]]]);
+        let x: Tensor<Rank3<2, 3, 4>, TestDtype, _, _> =
+            y.leaky_trace().max_unpool2d(idx, Const, Const);
+        assert_close_to_literal!(
+            x,
+            [
+                [
+                    [1.0, 0.0, 0.0, 2.0],
+                    [0.0, 0.0, 0.0, 0.0],
+                    [4.0, 0.0, 3.0, 0.0]
+                ],
+                [
+                    [0.0, 0.0, -2.0, 0.0],
+                    [0.0, -1.0, 0.0, -3.0],
+                    [0.0, 0.0, 0.0, -4.0]
+                ],
+            ]
+        );
+        let g = (x * dev
+            .tensor([1.0, 2.0, 3.0, 4.0])
+            .to_dtype::<TestDtype>()
+            .broadcast())
+        .sum()
+        .backward();
+        assert_close_to_literal!(
+            g.get(&y),
+            [[[1.0, 4.0], [3.0, 1.0]], [[2.0, 3.0], [4.0, 4.0]]]
+        );
+    }
+
+    #[test]
+    fn test_max_pool2d_then_unpool2d() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[[
+                [1.0, 5.0, 2.0, 0.0],
+                [3.0, 4.0, 8.0, 6.0],
+                [7.0, 0.5, 1.0, 1.5],
+                [2.0, 9.0, 3.0, 4.0],
+            ]]])
+            .to_dtype::<TestDtype>();
+        let (y, idx) = x
+            .leaky_trace()
+            .max_pool2d_with_indices(Const::<2>, Const::<2>, Const::<0>, Const::<1>);
+        let r: Tensor<Rank4<1, 1, 4, 4>, TestDtype, _, _> = y.max_unpool2d(idx, Const, Const);
+        assert_close_to_literal!(
+            r,
+            [[[
+                [0.0, 5.0, 0.0, 0.0],
+                [0.0, 0.0, 8.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+                [0.0, 9.0, 0.0, 4.0],
+            ]]]
+        );
+        let g = r.sum().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [[[
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 1.0],
+            ]]]
+        );
+    }
+
+    #[test]
+    fn test_max_unpool2d_shape_error() {
+        let dev: TestDevice = Default::default();
+        let y: Tensor<(usize, usize, usize), TestDtype, _> = dev.zeros_like(&(2, 2, 2));
+        let idx: Tensor<(usize, usize, usize), usize, _> = dev.zeros_like(&(2, 2, 3));
+        let err = y.try_max_unpool2d(idx, Const::<4>, Const::<4>).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new(
+                "max_unpool2d",
+                &(1, 2, 2, 3),
+                &(1, 2, 2, 2)
+            ))
+        );
+    }
+}
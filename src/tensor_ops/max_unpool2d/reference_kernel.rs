@@ -0,0 +1,42 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+impl<E: Float + Dtype> super::MaxPool2DIndicesKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, usize, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool2DOp,
+        inp: &Tensor<I, E, Self>,
+        idx: &mut Tensor<O, usize, Self>,
+    ) -> Result<(), Self::Err> {
+        let (istr, ostr) = (inp.strides, idx.strides);
+        let idx_buf = Arc::make_mut(&mut idx.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for oh in 0..op.h_out {
+                    for ow in 0..op.w_out {
+                        let value = |&(y, x): &(usize, usize)| {
+                            inp.data
+                                [inp.offset + b * istr[0] + c * istr[1] + y * istr[2] + x * istr[3]]
+                        };
+                        let window = op.window(oh, ow);
+                        let max = window.iter().map(value).fold(E::neg_infinity(), E::max);
+                        let (y, x) = window
+                            .into_iter()
+                            .find(|p| value(p) == max)
+                            .unwrap_or((0, 0));
+                        idx_buf[b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3]] =
+                            y * op.w_in + x;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     shapes::{Axes, Dtype, HasAxes, ReduceShapeTo, Shape},
-    tensor::{Cpu, Tensor, ZerosTensor},
+    tensor::{is_dense, Cpu, Tensor, ZerosTensor},
     tensor_ops::utilities::reduction_utils::index_for_reductions,
 };
 
@@ -16,7 +16,7 @@ impl<E: Dtype + Float> super::MinReduceKernel<E> for Cpu {
         Src: ReduceShapeTo<Dst, Ax>,
     {
         let mut out = self.try_zeros_like(&dst)?;
-        if Dst::NUM_DIMS == 0 {
+        if Dst::NUM_DIMS == 0 && is_dense(&inp.shape, &inp.strides) {
             debug_assert_eq!(out.data.len(), 1);
             let mut tmp: E = E::infinity();
             for i in inp.buf_iter() {
@@ -25,7 +25,7 @@ impl<E: Dtype + Float> super::MinReduceKernel<E> for Cpu {
             std::sync::Arc::get_mut(&mut out.data).unwrap()[0] = tmp;
         } else {
             let num_elems_reduced = <Src as HasAxes<Ax>>::size(&inp.shape);
-            let inp_buf = inp.data.as_ref();
+            let inp_buf = &inp.data[inp.offset..];
             let mut idx = index_for_reductions::<Src, Ax>(inp.shape, inp.strides);
             for o in out.buf_iter_mut() {
                 let mut tmp: E = E::infinity();
@@ -50,7 +50,7 @@ impl<E: Dtype + Float> super::MinReduceKernel<E> for Cpu {
     {
         let num_elems_reduced = <Src as HasAxes<Ax>>::size(&inp.shape);
 
-        let inp_buf = inp.data.as_ref();
+        let inp_buf = &inp.data[inp.offset..];
         let mut inp_idx = index_for_reductions::<Src, Ax>(inp.shape, inp.strides);
 
         for (&o, &go) in out.buf_iter().zip(grad_out.iter()) {
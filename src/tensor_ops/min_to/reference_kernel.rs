@@ -0,0 +1,50 @@
+use crate::{
+    shapes::{Axes, Dtype, ReduceShapeTo, Shape},
+    tensor::{
+        reference::{ravel_index, reduce_index, unravel_index},
+        Reference, Tensor,
+    },
+};
+
+use num_traits::Float;
+
+impl<E: Dtype + Float> super::MinReduceKernel<E> for Reference {
+    fn forward<Src: Shape, Dst: Shape, Ax: Axes>(
+        &self,
+        dst: Dst,
+        inp: &Tensor<Src, E, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>
+    where
+        Src: ReduceShapeTo<Dst, Ax>,
+    {
+        let mut out = self.try_alloc_elem(dst.num_elements(), E::infinity())?;
+        let dst_strides = dst.strides();
+        for i in 0..inp.shape.num_elements() {
+            let idx = unravel_index(&inp.shape, i);
+            let j = ravel_index::<Dst>(&dst_strides, &reduce_index::<Src, Dst, Ax>(&idx));
+            out[j] = out[j].min(inp.data[inp.offset + ravel_index::<Src>(&inp.strides, &idx)]);
+        }
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape, Ax: Axes>(
+        &self,
+        inp: &Tensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>
+    where
+        Src: ReduceShapeTo<Dst, Ax>,
+    {
+        for i in 0..inp.shape.num_elements() {
+            let idx = unravel_index(&inp.shape, i);
+            let i_inp = ravel_index::<Src>(&inp.strides, &idx);
+            let j = ravel_index::<Dst>(&out.strides, &reduce_index::<Src, Dst, Ax>(&idx));
+            if inp.data[inp.offset + i_inp] == out.data[j] {
+                grad_inp[i_inp] += grad_out[j];
+            }
+        }
+        Ok(())
+    }
+}
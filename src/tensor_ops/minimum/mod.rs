@@ -43,7 +43,7 @@ impl<S: Shape, E: Dtype, D: Device<E>, LTape: Tape<E, D>> Tensor<S, E, D, LTape>
     where
         LTape: Merge<R>,
     {
-        try_binary_op(MinimumKernelOp, self, rhs)
+        try_binary_op("minimum", MinimumKernelOp, self, rhs)
     }
 }
 #[cfg(test)]
@@ -0,0 +1,16 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::MishKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        x * x.exp().ln_1p().tanh()
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        let tanh_sp = x.exp().ln_1p().tanh();
+        let sig = F::one() / (F::one() + x.neg().exp());
+        tanh_sp + x * sig * (F::one() - tanh_sp * tanh_sp)
+    }
+}
@@ -0,0 +1,29 @@
+#include "unary_op_macros.cuh"
+
+struct MishKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T mish_fwd(T x) {
+    T one = 1.0;
+    return x * tanhg(logg(one + expg(x)));
+}
+
+template<typename T>
+__device__ __forceinline__ T mish_bwd(T x) {
+    T one = 1.0;
+    T tanh_sp = tanhg(logg(one + expg(x)));
+    T sig = one / (one + expg(-x));
+    return tanh_sp + x * sig * (one - tanh_sp * tanh_sp);
+}
+
+UNARY_OP(__half, mish_fwd_f16, mish_bwd_f16, MishKernelOp,
+        mish_fwd(x),
+        mish_bwd(x))
+
+UNARY_OP(float, mish_fwd_f32, mish_bwd_f32, MishKernelOp,
+        mish_fwd(x),
+        mish_bwd(x))
+
+UNARY_OP(double, mish_fwd_f64, mish_bwd_f64, MishKernelOp,
+        mish_fwd(x),
+        mish_bwd(x))
@@ -0,0 +1,57 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct MishKernelOp;
+
+/// [Mish](https://paperswithcode.com/method/mish). `t * tanh(ln(1 + exp(t)))`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.mish();
+/// ```
+pub fn mish<S: Shape, E: Dtype, D: UnaryKernel<MishKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.mish()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<MishKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [mish]
+    pub fn mish(self) -> Self {
+        self.try_mish().unwrap()
+    }
+    /// See [mish]
+    pub fn try_mish(self) -> Result<Self, D::Err> {
+        try_unary_op(MishKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_mish() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().mish();
+        assert_close_to_literal!(r, [-0.25250148, -0.30340146, 0.0, 0.86509839, 1.94395896]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [-0.02167102, 0.01184335, 0.12, 0.20980724, 0.21386359]
+        );
+    }
+}
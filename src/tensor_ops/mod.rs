@@ -150,49 +150,85 @@ mod utilities;
 pub use utilities::*;
 
 mod abs;
+mod accurate_gelu;
+mod acos;
 mod add;
+mod asin;
+mod atan;
+mod atan2;
 mod attention_reshape;
 pub(crate) mod axpy;
 mod bce;
 mod boolean;
+mod broadcast_binary;
 mod broadcast_to;
+mod ceil;
 mod choose;
 mod clamp;
 mod cmp;
 mod concat;
 mod concat_along;
 mod cos;
+mod cosh;
+mod digamma;
 mod div;
 mod dropout;
+mod einsum;
+mod elu;
+mod erf;
+mod erfc;
 mod exp;
+mod expm1;
+mod flip;
+mod floor;
 mod gelu;
+mod hard_sigmoid;
+mod hard_swish;
 mod huber_error;
+mod inplace;
+mod lgamma;
+pub mod linalg;
 mod ln;
+mod log1p;
 mod log_softmax;
 mod logsumexp_to;
+mod masked;
 mod matmul;
 mod max_to;
 mod maximum;
 mod mean_to;
 mod min_to;
 mod minimum;
+mod mish;
 mod mul;
 mod nans_to;
 mod negate;
 mod normalize;
+mod pad;
 mod permute_to;
 mod pow;
 mod prelu;
 mod realize_to;
 mod recip;
+mod reduce_axis;
 mod relu;
+mod repeat;
 mod reshape_to;
 mod roll;
+mod round;
+mod scan;
+mod scatter;
 mod select_and_gather;
+mod selu;
 mod sigmoid;
+mod silu;
 mod sin;
+mod sinh;
 mod slice;
 mod softmax;
+mod softplus;
+mod sort;
+mod split_along;
 mod sqrt;
 mod square;
 mod stack;
@@ -202,15 +238,22 @@ mod sum_to;
 mod tanh;
 mod to_dtype;
 mod tri;
+mod unfold;
 mod var_to;
 
 pub use abs::abs;
+pub use accurate_gelu::accurate_gelu;
+pub use acos::acos;
 pub use add::{add, TryAdd};
+pub use asin::asin;
+pub use atan::atan;
+pub use atan2::atan2;
 pub use attention_reshape::TryAttentionReshape;
 pub use axpy::axpy;
 pub use bce::bce_with_logits;
 pub use boolean::{bool_and, bool_not, bool_or, bool_xor};
 pub use broadcast_to::BroadcastTo;
+pub use ceil::ceil;
 pub use choose::ChooseFrom;
 pub use clamp::clamp;
 pub use cmp::{eq, ge, gt, le, lt, ne, TryEq, TryGe, TryGt, TryLe, TryLt, TryNe};
@@ -218,24 +261,40 @@ pub use cmp::{eq, ge, gt, le, lt, ne, TryEq, TryGe, TryGt, TryLe, TryLt, TryNe};
 pub use concat::TryConcat;
 pub use concat_along::TryConcatAlong;
 pub use cos::cos;
+pub use cosh::cosh;
+pub use digamma::digamma;
 pub use div::{div, TryDiv};
 pub use dropout::dropout;
+pub use einsum::einsum;
+pub use elu::elu;
+pub use erf::erf;
+pub use erfc::erfc;
 pub use exp::exp;
+pub use expm1::expm1;
+pub use flip::Flip;
+pub use floor::floor;
 pub use gelu::gelu;
+pub use hard_sigmoid::hard_sigmoid;
+pub use hard_swish::hard_swish;
 pub use huber_error::huber_error;
+pub use lgamma::lgamma;
 pub use ln::ln;
+pub use log1p::log1p;
 pub use log_softmax::log_softmax;
 pub use logsumexp_to::LogSumExpTo;
+pub use masked::{masked_fill, masked_select};
 pub use matmul::{matmul, TryMatMul};
 pub use max_to::MaxTo;
 pub use maximum::maximum;
 pub use mean_to::MeanTo;
 pub use min_to::MinTo;
 pub use minimum::minimum;
+pub use mish::mish;
 pub use mul::{mul, TryMul};
 pub use nans_to::nans_to;
 pub use negate::negate;
 pub use normalize::normalize;
+pub use pad::{pad, PadMode};
 pub use permute_to::PermuteTo;
 pub use pow::{powf, powi};
 pub use prelu::{leakyrelu, prelu, TryPReLU};
@@ -244,11 +303,18 @@ pub use recip::recip;
 pub use relu::relu;
 pub use reshape_to::ReshapeTo;
 pub use roll::Roll;
+pub use round::round;
+pub use scan::ScanKind;
+pub use scatter::{index_select, scatter, scatter_add};
 pub use select_and_gather::{GatherTo, SelectTo};
+pub use selu::selu;
 pub use sigmoid::sigmoid;
+pub use silu::silu;
 pub use sin::sin;
+pub use sinh::sinh;
 pub use slice::slice;
 pub use softmax::softmax;
+pub use softplus::softplus;
 pub use sqrt::sqrt;
 pub use square::square;
 pub use stack::TryStack;
@@ -262,21 +328,39 @@ pub use var_to::VarTo;
 
 pub(crate) use to_dtype::ToDtypeKernel;
 
-#[cfg(feature = "nightly")]
+mod conv1d;
+pub use conv1d::TryConv1D;
+
 mod conv2d;
-#[cfg(feature = "nightly")]
 pub use conv2d::TryConv2D;
 
-#[cfg(feature = "nightly")]
+mod conv3d;
+pub use conv3d::TryConv3D;
+
+mod convtrans1d;
+pub use convtrans1d::{TryConvTrans1D, TryConvTrans1DTo};
+
 mod convtrans2d;
-#[cfg(feature = "nightly")]
 pub use convtrans2d::{ConvTransAlgebra, TryConvTrans2D, TryConvTrans2DTo};
 
+mod convtrans3d;
+pub use convtrans3d::{TryConvTrans3D, TryConvTrans3DTo};
+
 mod upscale2d;
 pub(crate) use upscale2d::Upscale2DKernel;
 pub use upscale2d::{Bilinear, GenericUpscale2D, NearestNeighbor, TryUpscale2D, UpscaleMethod};
 
-#[cfg(feature = "nightly")]
+mod pool1d;
+pub use pool1d::TryPool1D;
+
 mod pool2d;
-#[cfg(feature = "nightly")]
 pub use pool2d::{Pool2DKind, TryPool2D};
+
+mod max_unpool2d;
+pub use max_unpool2d::{TryMaxPool2DWithIndices, TryMaxUnpool2D};
+
+mod pool3d;
+pub use pool3d::TryPool3D;
+
+mod adaptive_pool;
+pub use adaptive_pool::{TryAdaptivePool1D, TryAdaptivePool2D, TryAdaptivePool3D};
@@ -56,7 +56,7 @@ where
     LhsTape: Merge<R>,
 {
     fn try_mul(self, rhs: Tensor<S, E, D, R>) -> Result<Self, Self::Err> {
-        try_binary_op(BinaryMulKernelOp, self, rhs)
+        try_binary_op("mul", BinaryMulKernelOp, self, rhs)
     }
 }
 
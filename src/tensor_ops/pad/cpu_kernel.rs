@@ -0,0 +1,66 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        cpu::{index_to_i, LendingIterator, NdIndex},
+        Cpu, GhostTensor, Tensor, ZerosTensor,
+    },
+};
+
+use super::PadMode;
+
+/// Returns the index into `inp` that the padded index `idx` reads from, or `None`
+/// if it is filled with a constant.
+fn source_index<S: Shape>(
+    mode: PadMode,
+    before: &S::Concrete,
+    inp: &S,
+    mut idx: S::Concrete,
+) -> Option<S::Concrete> {
+    let dims = inp.concrete();
+    for d in 0..S::NUM_DIMS {
+        idx[d] = mode.source_index(idx[d], before[d], dims[d])?;
+    }
+    Some(idx)
+}
+
+impl<E: Dtype> super::PadKernel<E> for Cpu {
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &Tensor<Src, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let value = match mode {
+            PadMode::Constant(value) => E::from_f64(value).unwrap(),
+            _ => E::default(),
+        };
+        let mut out = self.try_zeros_like(&dst)?;
+        let mut out_iter = out.iter_mut_with_index();
+        while let Some((x, i)) = out_iter.next() {
+            *x = match source_index(mode, &before, &inp.shape, i) {
+                Some(i) => inp[i],
+                None => value,
+            };
+        }
+        Ok(out)
+    }
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut out_idx = NdIndex::new(out.shape, out.strides);
+        while let Some((i_out, i)) = out_idx.next_with_idx() {
+            if let Some(i) = source_index(mode, &before, &inp.shape, i) {
+                grad_inp[index_to_i(&inp.shape, &inp.strides, i)] += grad_out[i_out];
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::LaunchAsync;
+
+use super::PadMode;
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/pad.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &["pad_fwd_f16", "pad_bwd_f16"];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &["pad_fwd_f32", "pad_bwd_f32"];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &["pad_fwd_f64", "pad_bwd_f64"];
+}
+
+/// The mode ids used in pad.cu
+fn mode_id(mode: PadMode) -> usize {
+    match mode {
+        PadMode::Constant(_) => 0,
+        PadMode::Reflect => 1,
+        PadMode::Replicate => 2,
+        PadMode::Circular => 3,
+    }
+}
+
+impl<E: Dtype> super::PadKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &Tensor<Src, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+
+        let value = match mode {
+            PadMode::Constant(value) => E::from_f64(value).unwrap(),
+            _ => E::default(),
+        };
+        let numel = dst.num_elements();
+        let mut out = unsafe { self.alloc_empty::<E>(numel) }?;
+        let inp_dims = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+        let out_dims = self.dev.htod_copy(dst.concrete().into())?;
+        let before = self.dev.htod_copy(before.into())?;
+
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            mode_id(mode),
+            value,
+            Src::NUM_DIMS,
+            numel,
+            &inp_dims,
+            &inp_strides,
+            &out_dims,
+            &before,
+            inp.data.as_ref(),
+            &mut out,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok(self.build_tensor(dst, dst.strides(), out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let numel = out.shape.num_elements();
+        let inp_dims = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+        let out_dims = self.dev.htod_copy(out.shape.concrete().into())?;
+        let out_strides = self.dev.htod_copy(out.strides.into())?;
+        let before = self.dev.htod_copy(before.into())?;
+
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            mode_id(mode),
+            Src::NUM_DIMS,
+            numel,
+            &inp_dims,
+            &inp_strides,
+            &out_dims,
+            &out_strides,
+            &before,
+            grad_inp,
+            grad_out,
+        );
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{shapes::*, tensor::*};
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+/// How [pad()] fills the added entries. See [Tensor::pad()] for an example of each.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PadMode {
+    /// Fills the padding with a constant value.
+    Constant(f64),
+    /// Mirrors the tensor at its edges, without repeating the edge itself. The padding
+    /// on each side has to be smaller than the padded dimension.
+    Reflect,
+    /// Repeats the edge of the tensor.
+    Replicate,
+    /// Wraps around to the other side of the tensor.
+    Circular,
+}
+
+impl PadMode {
+    /// Returns the index into a dimension of size `n` that the padded index `i` reads from,
+    /// where `before` entries are added at the start of the dimension. Returns `None` for
+    /// entries filled with a constant.
+    pub(crate) fn source_index(&self, i: usize, before: usize, n: usize) -> Option<usize> {
+        if i >= before && i - before < n {
+            return Some(i - before);
+        }
+        let j = i as isize - before as isize;
+        let n = n as isize;
+        let j = match self {
+            PadMode::Constant(_) => return None,
+            PadMode::Reflect if j < 0 => -j,
+            PadMode::Reflect => 2 * (n - 1) - j,
+            PadMode::Replicate => j.clamp(0, n - 1),
+            PadMode::Circular => j.rem_euclid(n),
+        };
+        Some(j as usize)
+    }
+}
+
+pub trait PadKernel<E: Dtype>: DeviceStorage {
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &Tensor<Src, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>;
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Pads the dimensions of a tensor, with the number of entries added before and after
+/// each dimension determined by a tuple of pads.
+///
+/// Each dimension is padded with either `(before, after)`, or `()` to leave it as is.
+/// All padded dimensions are changed to be of type usize, except those padded with `()`,
+/// whose types are not modified.
+///
+/// See [PadMode] for how the added entries are filled. The gradient of each entry
+/// that is copied from the input flows back to it.
+///
+/// See [Tensor::pad()] for examples.
+pub fn pad<S: PadShape<Pads>, Pads: 'static, E: Dtype, D: PadKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    pads: Pads,
+    mode: PadMode,
+) -> Tensor<S::Padded, E, D, T> {
+    t.pad(pads, mode)
+}
+
+impl<S: Shape, E: Dtype, D: PadKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Calls [pad()].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+    /// let r: Tensor<(usize, Const<2>), f32, _> = t.clone().pad(((1, 0), ()), PadMode::Constant(0.0));
+    /// assert_eq!(r.as_vec(), [0.0, 0.0, 1.0, 2.0, 3.0, 4.0]);
+    ///
+    /// let t = dev.tensor([1.0, 2.0, 3.0]);
+    /// let r = t.clone().pad(((2, 1),), PadMode::Reflect);
+    /// assert_eq!(r.as_vec(), [3.0, 2.0, 1.0, 2.0, 3.0, 2.0]);
+    /// let r = t.clone().pad(((2, 1),), PadMode::Replicate);
+    /// assert_eq!(r.as_vec(), [1.0, 1.0, 1.0, 2.0, 3.0, 3.0]);
+    /// let r = t.pad(((2, 1),), PadMode::Circular);
+    /// assert_eq!(r.as_vec(), [2.0, 3.0, 1.0, 2.0, 3.0, 1.0]);
+    /// ```
+    pub fn pad<Pads>(self, pads: Pads, mode: PadMode) -> Tensor<S::Padded, E, D, T>
+    where
+        S: PadShape<Pads>,
+        Pads: 'static,
+    {
+        self.try_pad(pads, mode).unwrap()
+    }
+
+    /// Fallible version of [Tensor::pad]. Returns a [ShapeError] if a dimension can't be
+    /// padded with `mode`, for example if it is empty.
+    pub fn try_pad<Pads>(
+        self,
+        pads: Pads,
+        mode: PadMode,
+    ) -> Result<Tensor<S::Padded, E, D, T>, D::Err>
+    where
+        S: PadShape<Pads>,
+        Pads: 'static,
+    {
+        let dst = self.shape.pad(&pads);
+        let (before, after) = S::pad_amounts(&pads);
+        let dims = self.shape.concrete();
+        for i in 0..S::NUM_DIMS {
+            let max_pad = before[i].max(after[i]);
+            let valid = max_pad == 0
+                || match mode {
+                    PadMode::Constant(_) => true,
+                    PadMode::Reflect => max_pad < dims[i],
+                    PadMode::Replicate | PadMode::Circular => dims[i] > 0,
+                };
+            if !valid {
+                return Err(ShapeError::new("pad", &self.shape, &dst).into());
+            }
+        }
+
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward(mode, before, &inp, dst)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            inp.device
+                .backward(mode, before, &inp_ghost, grad_inp, &out_ghost, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_pad_constant_2d() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([[1.0, 2.0], [3.0, 4.0]]).to_dtype::<TestDtype>();
+        let r = t
+            .leaky_trace()
+            .pad(((1, 0), (0, 2)), PadMode::Constant(9.0));
+        assert_eq!(r.shape, (3, 4));
+        let w = dev
+            .tensor([
+                [1.0, 2.0, 3.0, 4.0],
+                [5.0, 6.0, 7.0, 8.0],
+                [9.0, 10.0, 11.0, 12.0],
+            ])
+            .to_dtype::<TestDtype>();
+        let r = r.realize::<Rank2<3, 4>>();
+        assert_close_to_literal!(
+            r,
+            [
+                [9.0, 9.0, 9.0, 9.0],
+                [1.0, 2.0, 9.0, 9.0],
+                [3.0, 4.0, 9.0, 9.0]
+            ]
+        );
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[5.0, 6.0], [9.0, 10.0]]);
+    }
+
+    #[test]
+    fn test_pad_modes_1d() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([1.0, 2.0, 3.0, 4.0]).to_dtype::<TestDtype>();
+
+        let r = t.leaky_trace().pad(((2, 1),), PadMode::Reflect);
+        let r = r.realize::<Rank1<7>>();
+        assert_close_to_literal!(r, [3.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [1.0, 2.0, 3.0, 1.0]);
+
+        let r = t.leaky_trace().pad(((2, 1),), PadMode::Replicate);
+        let r = r.realize::<Rank1<7>>();
+        assert_close_to_literal!(r, [1.0, 1.0, 1.0, 2.0, 3.0, 4.0, 4.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [3.0, 1.0, 1.0, 2.0]);
+
+        let r = t.leaky_trace().pad(((2, 1),), PadMode::Circular);
+        let r = r.realize::<Rank1<7>>();
+        assert_close_to_literal!(r, [3.0, 4.0, 1.0, 2.0, 3.0, 4.0, 1.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [2.0, 1.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_pad_keeps_unpadded_dims() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let r: Tensor<(Const<2>, usize, Const<4>), TestDtype, _> =
+            t.clone().pad(((), (1, 1), ()), PadMode::Replicate);
+        assert_eq!(r.shape, (Const, 5, Const));
+        let a = t.array();
+        let r = r.realize::<Rank3<2, 5, 4>>().array();
+        for i in 0..2 {
+            assert_eq!(r[i][0], a[i][0]);
+            assert_eq!(r[i][1..4], a[i]);
+            assert_eq!(r[i][4], a[i][2]);
+        }
+    }
+
+    #[test]
+    fn test_try_pad_shape_error() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let err = t
+            .clone()
+            .try_pad(((), (3, 0)), PadMode::Reflect)
+            .unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("pad", &(2, 3), &(2, 6)))
+        );
+        assert!(t.try_pad(((), (2, 0)), PadMode::Reflect).is_ok());
+    }
+}
@@ -0,0 +1,121 @@
+#include "cuda_utils.cuh"
+
+#define PAD_CONSTANT 0
+#define PAD_REFLECT 1
+#define PAD_REPLICATE 2
+#define PAD_CIRCULAR 3
+
+// Computes the index into `inp` that the `i`th element of `out` reads from.
+// Returns false if the element is filled with a constant.
+__device__ bool get_padded_index(
+    size_t i,
+    const size_t mode,
+    const size_t num_dims,
+    const size_t *inp_dims,
+    const size_t *inp_strides,
+    const size_t *out_dims,
+    const size_t *before,
+    size_t *inp_i
+) {
+    *inp_i = 0;
+    for (int d = num_dims - 1; d >= 0; d--) {
+        long n = inp_dims[d];
+        long j = (long)(i % out_dims[d]) - (long)before[d];
+        i /= out_dims[d];
+
+        if (j < 0 || j >= n) {
+            if (mode == PAD_CONSTANT) {
+                return false;
+            } else if (mode == PAD_REFLECT) {
+                j = j < 0 ? -j : 2 * (n - 1) - j;
+            } else if (mode == PAD_REPLICATE) {
+                j = j < 0 ? 0 : n - 1;
+            } else {
+                j = ((j % n) + n) % n;
+            }
+        }
+        *inp_i += j * inp_strides[d];
+    }
+    return true;
+}
+
+template<typename T>
+__device__ void pad_fwd(
+    const size_t mode,
+    const T value,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *inp_dims,
+    const size_t *inp_strides,
+    const size_t *out_dims,
+    const size_t *before,
+    const T *inp,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t inp_i;
+    if (get_padded_index(i, mode, num_dims, inp_dims, inp_strides, out_dims, before, &inp_i)) {
+        out[i] = inp[inp_i];
+    } else {
+        out[i] = value;
+    }
+}
+
+template<typename T>
+__device__ void pad_bwd(
+    const size_t mode,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *inp_dims,
+    const size_t *inp_strides,
+    const size_t *out_dims,
+    const size_t *out_strides,
+    const size_t *before,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t inp_i;
+    if (get_padded_index(i, mode, num_dims, inp_dims, inp_strides, out_dims, before, &inp_i)) {
+        size_t out_i = get_strided_index(i, num_dims, out_dims, out_strides);
+        atomicAdd(grad_inp + inp_i, grad_out[out_i]);
+    }
+}
+
+#define PAD(TY, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t mode, \
+    const TY value, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *inp_dims, \
+    const size_t *inp_strides, \
+    const size_t *out_dims, \
+    const size_t *before, \
+    const TY *inp, \
+    TY *out \
+) { pad_fwd(mode, value, num_dims, numel, inp_dims, inp_strides, out_dims, before, inp, out); } \
+extern "C" __global__ void BWD( \
+    const size_t mode, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *inp_dims, \
+    const size_t *inp_strides, \
+    const size_t *out_dims, \
+    const size_t *out_strides, \
+    const size_t *before, \
+    TY *grad_inp, \
+    const TY *grad_out \
+) { pad_bwd(mode, num_dims, numel, inp_dims, inp_strides, out_dims, out_strides, before, grad_inp, grad_out); }
+
+PAD(__half, pad_fwd_f16, pad_bwd_f16);
+PAD(float, pad_fwd_f32, pad_bwd_f32);
+PAD(double, pad_fwd_f64, pad_bwd_f64);
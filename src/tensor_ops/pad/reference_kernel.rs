@@ -0,0 +1,66 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        reference::{physical_index, ravel_index, unravel_index},
+        GhostTensor, Reference, Tensor,
+    },
+};
+
+use super::PadMode;
+
+/// Returns the index into `inp` of the `i`th (row major) entry of `out`, or `None`
+/// if it is filled with a constant.
+fn source_index<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+    mode: PadMode,
+    before: &Src::Concrete,
+    inp: &Src,
+    out: &Dst,
+    i: usize,
+) -> Option<Src::Concrete> {
+    let dims = inp.concrete();
+    let mut idx = unravel_index(out, i);
+    for d in 0..Src::NUM_DIMS {
+        idx[d] = mode.source_index(idx[d], before[d], dims[d])?;
+    }
+    Some(idx)
+}
+
+impl<E: Dtype> super::PadKernel<E> for Reference {
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &Tensor<Src, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let value = match mode {
+            PadMode::Constant(value) => E::from_f64(value).unwrap(),
+            _ => E::default(),
+        };
+        let out = (0..dst.num_elements())
+            .map(|i| match source_index(mode, &before, &inp.shape, &dst, i) {
+                Some(i_inp) => inp[i_inp],
+                None => value,
+            })
+            .collect();
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>>(
+        &self,
+        mode: PadMode,
+        before: Src::Concrete,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for i in 0..out.shape.num_elements() {
+            if let Some(i_inp) = source_index(mode, &before, &inp.shape, &out.shape, i) {
+                grad_inp[ravel_index::<Src>(&inp.strides, &i_inp)] +=
+                    grad_out[physical_index(&out.shape, &out.strides, i)];
+            }
+        }
+        Ok(())
+    }
+}
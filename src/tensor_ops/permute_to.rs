@@ -45,6 +45,7 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T: Tape<E, D>> PermuteTo for Tensor<S,
             data: self.data,
             shape: self.shape.permuted(),
             strides: self.shape.permute_strides(self.strides),
+            offset: self.offset,
             device: self.device,
             tape: self.tape,
         })
@@ -0,0 +1,87 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+fn make_3d<S: Shape>(strides: S::Concrete) -> [usize; 3] {
+    match S::NUM_DIMS {
+        2 => [0, strides[0], strides[1]],
+        3 => [strides[0], strides[1], strides[2]],
+        _ => panic!("Only implemented for 2d & 3d arrays"),
+    }
+}
+
+impl<E: Float + Dtype> super::Pool1DKernel<E> for Cpu {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool1DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_3d::<I>(inp.strides);
+        let ostr = make_3d::<O>(out.strides);
+
+        let buf = &inp.data[inp.offset..];
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for ol in 0..op.l_out {
+                    let mut tmp = op.kind.init();
+                    for k in 0..op.kernel {
+                        let x = (ol * op.stride + op.dilation * k).checked_sub(op.padding);
+                        if let Some(x) = x {
+                            if x < op.l_in {
+                                tmp = op
+                                    .kind
+                                    .accum(&tmp, &buf[b * istr[0] + c * istr[1] + x * istr[2]]);
+                            }
+                        }
+                    }
+                    tmp = op.kind.normalize(tmp, op.kernel);
+                    out_buf[b * ostr[0] + c * ostr[1] + ol * ostr[2]] = tmp;
+                }
+            }
+        }
+        Ok(())
+    }
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool1DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_3d::<I>(inp.strides);
+        let ostr = make_3d::<O>(out.strides);
+
+        let inp_buf = &inp.data[inp.offset..];
+        let out_buf = out.data.as_ref();
+
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for ol in 0..op.l_out {
+                    let out_idx = b * ostr[0] + c * ostr[1] + ol * ostr[2];
+                    let go = grad_out[out_idx];
+                    let go = op.kind.normalize(go, op.kernel);
+                    let vo = out_buf[out_idx];
+
+                    for k in 0..op.kernel {
+                        let x = (ol * op.stride + op.dilation * k).checked_sub(op.padding);
+                        if let Some(x) = x {
+                            if x < op.l_in {
+                                let inp_idx = b * istr[0] + c * istr[1] + x * istr[2];
+                                grad_inp[inp_idx] += op.kind.filter(go, inp_buf[inp_idx], vo);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
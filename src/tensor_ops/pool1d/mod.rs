@@ -0,0 +1,389 @@
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{shapes::*, tensor::*};
+
+use super::{Pool2DKind, ReshapeTo};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Pool1DOp {
+    pub kind: Pool2DKind,
+    pub kernel: usize,
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+    pub batch: usize,
+    pub chan: usize,
+    pub l_in: usize,
+    pub l_out: usize,
+}
+
+pub(super) trait Pool1DKernel<E: Dtype>: DeviceStorage {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err>;
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: Pool1DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: Pool1DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Pooling with a 1d kernel over sequences with shape `(Channels, Length)` or
+/// `(Batch, Channels, Length)`.
+pub trait TryPool1D<Kernel, Stride, Padding, Dilation>: Sized {
+    type Pooled;
+    type Error: std::fmt::Debug;
+
+    fn pool1d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Self::Pooled {
+        self.try_pool1d(kind, kernel, stride, padding, dilation)
+            .unwrap()
+    }
+
+    fn try_pool1d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error>;
+}
+
+#[cfg(feature = "nightly")]
+impl<
+        const KERNEL: usize,
+        const STRIDE: usize,
+        const PADDING: usize,
+        const DILATION: usize,
+        const DIM: usize,
+    > TryPool1D<Const<KERNEL>, Const<STRIDE>, Const<PADDING>, Const<DILATION>> for Const<DIM>
+where
+    Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>: Sized,
+{
+    type Pooled = Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>;
+    type Error = std::convert::Infallible;
+    fn try_pool1d(
+        self,
+        _: Pool2DKind,
+        _: Const<KERNEL>,
+        _: Const<STRIDE>,
+        _: Const<PADDING>,
+        _: Const<DILATION>,
+    ) -> Result<Self::Pooled, Self::Error> {
+        Ok(Const)
+    }
+}
+
+impl<Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim>
+    TryPool1D<Kernel, Stride, Padding, Dilation> for usize
+{
+    type Pooled = usize;
+    type Error = std::convert::Infallible;
+    fn try_pool1d(
+        self,
+        _: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        Ok((self + 2 * padding.size() - 1)
+            .checked_sub(dilation.size() * (kernel.size() - 1))
+            .unwrap()
+            / stride.size()
+            + 1)
+    }
+}
+
+// Without `generic_const_exprs` the pooled size of a [Const] can't be named, so
+// it is computed at runtime instead.
+#[cfg(not(feature = "nightly"))]
+impl<const DIM: usize, Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim>
+    TryPool1D<Kernel, Stride, Padding, Dilation> for Const<DIM>
+{
+    type Pooled = usize;
+    type Error = std::convert::Infallible;
+    fn try_pool1d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        DIM.try_pool1d(kind, kernel, stride, padding, dilation)
+    }
+}
+
+impl<Chan, Kernel, Stride, Padding, Dilation, L, E, D, T>
+    TryPool1D<Kernel, Stride, Padding, Dilation> for Tensor<(Chan, L), E, D, T>
+where
+    Chan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    L: Dim + TryPool1D<Kernel, Stride, Padding, Dilation>,
+    L::Pooled: Dim,
+    E: Dtype,
+    D: Pool1DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+{
+    type Pooled = Tensor<(Chan, L::Pooled), E, D, T>;
+    type Error = D::Err;
+
+    fn try_pool1d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (chan, l) = self.shape;
+        let seq = self.try_reshape_like(&(Const::<1>, chan, l))?;
+        let out = seq.try_pool1d(kind, kernel, stride, padding, dilation)?;
+        let (_, _, out_l) = out.shape;
+        out.try_reshape_like(&(chan, out_l))
+    }
+}
+
+impl<Chan, Kernel, Stride, Padding, Dilation, Batch, L, E, D, T>
+    TryPool1D<Kernel, Stride, Padding, Dilation> for Tensor<(Batch, Chan, L), E, D, T>
+where
+    Chan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Batch: Dim,
+    L: Dim + TryPool1D<Kernel, Stride, Padding, Dilation>,
+    L::Pooled: Dim,
+    E: Dtype,
+    D: Pool1DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+{
+    type Pooled = Tensor<(Batch, Chan, L::Pooled), E, D, T>;
+    type Error = D::Err;
+
+    fn try_pool1d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (batch, chan, l) = self.shape;
+        // the kernels only handle contiguous inputs, so views are copied first
+        let inp = self.try_contiguous()?;
+        let l_out = l.pool1d(kind, kernel, stride, padding, dilation);
+        let op = Pool1DOp {
+            kind,
+            stride: stride.size(),
+            padding: padding.size(),
+            kernel: kernel.size(),
+            dilation: dilation.size(),
+            batch: batch.size(),
+            chan: chan.size(),
+            l_in: l.size(),
+            l_out: l_out.size(),
+        };
+        let (seq, mut tape) = inp.split_tape();
+        let mut out = seq.device.alloc((batch, chan, l_out))?;
+        Pool1DKernel::forward(&seq.device, op, &seq, &mut out)?;
+        let seq_ghost = seq.ghost();
+        let out_ghost = out.ghost();
+        let out_clone = out.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&seq_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_seq, grad_out) = grads.mut_and_ref(&seq_ghost, &out_ghost);
+            Pool1DKernel::backward(&seq.device, op, &seq, grad_seq, &out_clone, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_pool1d_max_grads() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[1.0, 1., 0.5, 0.2, 1.2], [0.2, 0.2, 0.5, 1.2, -1.0]])
+            .to_dtype::<TestDtype>();
+        let r = x
+            .leaky_trace()
+            .pool1d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<1>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank2<2, 4>>();
+        assert_close_to_literal!(r, [[1., 1., 0.5, 1.2], [0.2, 0.5, 1.2, 1.2]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&x), [[1., 2., 1., 0., 1.], [1., 1., 1., 2., 0.]]);
+    }
+
+    #[test]
+    fn test_pool1d_avg_padded() {
+        let dev: TestDevice = Default::default();
+        let x = dev.tensor([[1.0, 2., 3., 4., 5.]]).to_dtype::<TestDtype>();
+        let r = x
+            .leaky_trace()
+            .pool1d(
+                Pool2DKind::Avg,
+                Const::<3>,
+                Const::<2>,
+                Const::<1>,
+                Const::<1>,
+            )
+            .realize::<Rank2<1, 3>>();
+        assert_close_to_literal!(r, [[1.0, 3.0, 3.0]]);
+        let g = r.exp().sum().backward();
+        let (a, b, c) = (1f64.exp() / 3.0, 3f64.exp() / 3.0, 3f64.exp() / 3.0);
+        assert_close_to_literal!(g.get(&x), [[a, a + b, b, b + c, c]]);
+    }
+
+    #[test]
+    fn test_pool1d_min_dilated() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[3.0, -1., 2., 0., 5., -2.]])
+            .to_dtype::<TestDtype>();
+        let r = x
+            .leaky_trace()
+            .pool1d(
+                Pool2DKind::Min,
+                Const::<2>,
+                Const::<1>,
+                Const::<0>,
+                Const::<2>,
+            )
+            .realize::<Rank2<1, 4>>();
+        assert_close_to_literal!(r, [[2., -1., 2., -2.]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&x), [[0., 1., 2., 0., 0., 1.]]);
+    }
+
+    #[test]
+    fn test_pool1d_batched() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<3, 4, 10>, TestDtype, _> = dev.sample_normal();
+        let y = x
+            .leaky_trace()
+            .pool1d(
+                Pool2DKind::Max,
+                Const::<3>,
+                Const::<2>,
+                Const::<1>,
+                Const::<1>,
+            )
+            .realize::<Rank3<3, 4, 5>>();
+        let y_nt = y.retaped::<NoneTape>();
+        let g = y.exp().mean().backward();
+        for i in 0..3 {
+            let x_i = x.clone().select(dev.tensor(i));
+            let y_i = x_i
+                .leaky_trace()
+                .pool1d(
+                    Pool2DKind::Max,
+                    Const::<3>,
+                    Const::<2>,
+                    Const::<1>,
+                    Const::<1>,
+                )
+                .realize::<Rank2<4, 5>>();
+            assert_close_to_tensor!(
+                y_i.retaped::<NoneTape>(),
+                y_nt.clone().select(dev.tensor(i))
+            );
+            let g_i = y_i.exp().mean().backward();
+            assert_close_to_tensor!(g_i.get(&x_i) / 3.0, g.get(&x).select(dev.tensor(i)));
+        }
+    }
+
+    #[test]
+    fn test_pool1d_sliced_input() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 3, 8>, TestDtype, _> = dev.sample_normal();
+
+        let x_view = x
+            .leaky_trace()
+            .slice((.., .., 1..7))
+            .realize::<Rank3<2, 3, 6>>();
+        let y = x_view
+            .pool1d(
+                Pool2DKind::Avg,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank3<2, 3, 3>>();
+
+        let x_copy = x
+            .clone()
+            .slice((.., .., 1..7))
+            .realize::<Rank3<2, 3, 6>>()
+            .contiguous();
+        let y_true = x_copy
+            .leaky_trace()
+            .pool1d(
+                Pool2DKind::Avg,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank3<2, 3, 3>>();
+        assert_close_to_tensor!(y, y_true);
+
+        let g = y.square().sum().backward();
+        let g_true = y_true.square().sum().backward();
+        let x_grad = g.get(&x).slice((.., .., 1..7)).realize::<Rank3<2, 3, 6>>();
+        assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_pool1d_const_output() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 3, 7>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank3<2, 3, 4>, TestDtype, _> = x.pool1d(
+            Pool2DKind::Max,
+            Const::<3>,
+            Const::<2>,
+            Const::<1>,
+            Const::<1>,
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+fn make_3d<S: Shape>(strides: S::Concrete) -> [usize; 3] {
+    match S::NUM_DIMS {
+        2 => [0, strides[0], strides[1]],
+        3 => [strides[0], strides[1], strides[2]],
+        _ => panic!("Only implemented for 2d & 3d arrays"),
+    }
+}
+
+impl super::Pool1DOp {
+    /// All the input positions inside the window of output `ol`.
+    fn window(&self, ol: usize) -> std::vec::Vec<usize> {
+        (0..self.kernel)
+            .filter_map(|k| (ol * self.stride + self.dilation * k).checked_sub(self.padding))
+            .filter(|&x| x < self.l_in)
+            .collect()
+    }
+}
+
+impl<E: Float + Dtype> super::Pool1DKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool1DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_3d::<I>(inp.strides);
+        let ostr = make_3d::<O>(out.strides);
+        // NOTE: avg pooling always divides by the full kernel size, even for padded windows.
+        let scale = E::from(op.kernel).unwrap();
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for ol in 0..op.l_out {
+                    let values = op
+                        .window(ol)
+                        .into_iter()
+                        .map(|x| inp.data[inp.offset + b * istr[0] + c * istr[1] + x * istr[2]]);
+                    out_buf[b * ostr[0] + c * ostr[1] + ol * ostr[2]] = match op.kind {
+                        super::Pool2DKind::Avg => values.fold(E::zero(), |a, v| a + v) / scale,
+                        super::Pool2DKind::Min => values.fold(E::infinity(), E::min),
+                        super::Pool2DKind::Max => values.fold(E::neg_infinity(), E::max),
+                    };
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool1DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_3d::<I>(inp.strides);
+        let ostr = make_3d::<O>(out.strides);
+        let scale = E::from(op.kernel).unwrap();
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for ol in 0..op.l_out {
+                    let i_out = b * ostr[0] + c * ostr[1] + ol * ostr[2];
+                    for x in op.window(ol) {
+                        let i_inp = b * istr[0] + c * istr[1] + x * istr[2];
+                        grad_inp[i_inp] += match op.kind {
+                            super::Pool2DKind::Avg => grad_out[i_out] / scale,
+                            _ if inp.data[inp.offset + i_inp] == out.data[i_out] => grad_out[i_out],
+                            _ => E::zero(),
+                        };
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -13,7 +13,7 @@ fn make_4d<S: Shape>(strides: S::Concrete) -> [usize; 4] {
 }
 
 impl super::Pool2DKind {
-    fn init<E: Float>(&self) -> E {
+    pub(crate) fn init<E: Float>(&self) -> E {
         match self {
             super::Pool2DKind::Avg => E::zero(),
             super::Pool2DKind::Min => E::infinity(),
@@ -21,7 +21,7 @@ impl super::Pool2DKind {
         }
     }
 
-    fn accum<E: Float>(&self, accum: &E, item: &E) -> E {
+    pub(crate) fn accum<E: Float>(&self, accum: &E, item: &E) -> E {
         match self {
             super::Pool2DKind::Avg => *accum + *item,
             super::Pool2DKind::Min => accum.min(*item),
@@ -29,7 +29,7 @@ impl super::Pool2DKind {
         }
     }
 
-    fn normalize<E: Float + FromPrimitive>(&self, item: E, num_elements: usize) -> E {
+    pub(crate) fn normalize<E: Float + FromPrimitive>(&self, item: E, num_elements: usize) -> E {
         match self {
             super::Pool2DKind::Avg => item * E::from_f64(1.0 / num_elements as f64).unwrap(),
             super::Pool2DKind::Min => item,
@@ -37,7 +37,7 @@ impl super::Pool2DKind {
         }
     }
 
-    fn filter<E: Float>(&self, item: E, needle: E, haystack: E) -> E {
+    pub(crate) fn filter<E: Float>(&self, item: E, needle: E, haystack: E) -> E {
         match self {
             super::Pool2DKind::Avg => item,
             super::Pool2DKind::Min => {
@@ -71,7 +71,7 @@ impl<E: Float + Dtype> super::Pool2DKernel<E> for Cpu {
         let istr = make_4d::<I>(inp.strides);
         let ostr = make_4d::<O>(out.strides);
 
-        let buf = inp.data.as_ref();
+        let buf = &inp.data[inp.offset..];
         let out_buf = Arc::make_mut(&mut out.data);
         for b in 0..op.batch {
             for c in 0..op.chan {
@@ -110,7 +110,7 @@ impl<E: Float + Dtype> super::Pool2DKernel<E> for Cpu {
         let istr = make_4d::<I>(inp.strides);
         let ostr = make_4d::<O>(out.strides);
 
-        let inp_buf = inp.data.as_ref();
+        let inp_buf = &inp.data[inp.offset..];
         let out_buf = out.data.as_ref();
 
         for b in 0..op.batch {
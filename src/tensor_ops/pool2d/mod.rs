@@ -1,4 +1,5 @@
 mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -78,6 +79,7 @@ pub trait TryPool2D<Kernel, Stride, Padding, Dilation>: Sized {
     ) -> Result<Self::Pooled, Self::Error>;
 }
 
+#[cfg(feature = "nightly")]
 impl<
         const KERNEL: usize,
         const STRIDE: usize,
@@ -123,6 +125,26 @@ impl<Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim>
     }
 }
 
+// Without `generic_const_exprs` the pooled size of a [Const] can't be named, so
+// it is computed at runtime instead.
+#[cfg(not(feature = "nightly"))]
+impl<const DIM: usize, Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim>
+    TryPool2D<Kernel, Stride, Padding, Dilation> for Const<DIM>
+{
+    type Pooled = usize;
+    type Error = std::convert::Infallible;
+    fn try_pool2d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        DIM.try_pool2d(kind, kernel, stride, padding, dilation)
+    }
+}
+
 impl<Chan, Kernel, Stride, Padding, Dilation, H, W, E, D, T>
     TryPool2D<Kernel, Stride, Padding, Dilation> for Tensor<(Chan, H, W), E, D, T>
 where
@@ -172,7 +194,7 @@ where
     W: Dim + TryPool2D<Kernel, Stride, Padding, Dilation>,
     W::Pooled: Dim,
     E: Dtype,
-    D: Pool2DKernel<E>,
+    D: Pool2DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
     T: Tape<E, D>,
 {
     type Pooled = Tensor<(Batch, Chan, H::Pooled, W::Pooled), E, D, T>;
@@ -187,9 +209,8 @@ where
         dilation: Dilation,
     ) -> Result<Self::Pooled, Self::Error> {
         let (batch, chan, h, w) = self.shape;
-        if self.strides != self.shape.strides() {
-            panic!("Image input to pool2d must be contiguous");
-        }
+        // the kernels only handle contiguous inputs, so views are copied first
+        let inp = self.try_contiguous()?;
         let h_out = h.pool2d(kind, kernel, stride, padding, dilation);
         let w_out = w.pool2d(kind, kernel, stride, padding, dilation);
         let op = Pool2DOp {
@@ -205,9 +226,9 @@ where
             w_in: w.size(),
             w_out: w_out.size(),
         };
-        let (img, mut tape) = self.split_tape();
+        let (img, mut tape) = inp.split_tape();
         let mut out = img.device.alloc((batch, chan, h_out, w_out))?;
-        img.device.forward(op, &img, &mut out)?;
+        Pool2DKernel::forward(&img.device, op, &img, &mut out)?;
         let img_ghost = img.ghost();
         let out_ghost = out.ghost();
         let out_clone = out.clone();
@@ -215,8 +236,7 @@ where
             grads.try_alloc_for(&img_ghost)?;
             grads.try_alloc_for(&out_ghost)?;
             let (grad_img, grad_out) = grads.mut_and_ref(&img_ghost, &out_ghost);
-            img.device
-                .backward(op, &img, grad_img, &out_clone, grad_out)
+            Pool2DKernel::backward(&img.device, op, &img, grad_img, &out_clone, grad_out)
         });
         Ok(out.put_tape(tape))
     }
@@ -233,13 +253,16 @@ mod tests {
         let x = dev
             .tensor([[[1.0, 1., 0.5, 0.2], [0.2, 0.2, 0.5, 1.2]]])
             .to_dtype::<TestDtype>();
-        let r = x.leaky_trace().pool2d(
-            Pool2DKind::Max,
-            Const::<2>,
-            Const::<1>,
-            Const::<0>,
-            Const::<1>,
-        );
+        let r = x
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<1>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank3<1, 1, 3>>();
         assert_close_to_literal!(r, [[[1., 1., 1.2]]]);
         let g = r.sum().backward();
         assert_close_to_literal!(g.get(&x), [[[1., 2., 0., 0.], [0., 0., 0., 1.]]]);
@@ -251,13 +274,16 @@ mod tests {
         let x = dev
             .tensor([[[1., 1., 0.5, 0.2], [0.2, 0.2, 0.5, 1.2]]])
             .to_dtype::<TestDtype>();
-        let r = x.leaky_trace().pool2d(
-            Pool2DKind::Min,
-            Const::<2>,
-            Const::<1>,
-            Const::<0>,
-            Const::<1>,
-        );
+        let r = x
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Min,
+                Const::<2>,
+                Const::<1>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank3<1, 1, 3>>();
         assert_close_to_literal!(r, [[[0.2, 0.2, 0.2]]]);
         let g = r.sum().backward();
         assert_close_to_literal!(g.get(&x), [[[0., 0., 0., 1.], [1., 2., 0., 0.]]]);
@@ -267,13 +293,16 @@ mod tests {
     fn test_pool2d_3d_max2d() {
         let dev = TestDevice::seed_from_u64(234);
         let x: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
-        let r = x.leaky_trace().pool2d(
-            Pool2DKind::Max,
-            Const::<2>,
-            Const::<2>,
-            Const::<0>,
-            Const::<1>,
-        );
+        let r = x
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank3<2, 1, 2>>();
         assert_close_to_literal!(r, [[[1.79155397, 1.10126066]], [[1.14464748, 2.26301837]]]);
         let g = r.exp().mean().backward();
         #[rustfmt::skip]
@@ -290,13 +319,16 @@ mod tests {
     fn test_pool2d_3d_min2d() {
         let dev = TestDevice::seed_from_u64(234);
         let x: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
-        let r = x.leaky_trace().pool2d(
-            Pool2DKind::Min,
-            Const::<2>,
-            Const::<2>,
-            Const::<0>,
-            Const::<1>,
-        );
+        let r = x
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Min,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank3<2, 1, 2>>();
         assert_close_to_literal!(
             r,
             [[[-1.09635627, -1.07717276]], [[-0.01996479, -1.82562149]]]
@@ -316,13 +348,16 @@ mod tests {
     fn test_pool2d_4d_avg2d() {
         let dev = TestDevice::seed_from_u64(234);
         let x: Tensor<Rank4<2, 4, 2, 2>, TestDtype, _> = dev.sample_normal();
-        let r = x.leaky_trace().pool2d(
-            Pool2DKind::Avg,
-            Const::<1>,
-            Const::<2>,
-            Const::<0>,
-            Const::<1>,
-        );
+        let r = x
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Avg,
+                Const::<1>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank4<2, 4, 1, 1>>();
         assert_close_to_literal!(
             r,
             [
@@ -352,21 +387,27 @@ mod tests {
                 [16., 17., 18., 19., 20.],
             ]])
             .to_dtype::<TestDtype>();
-        let y_max = x.leaky_trace().pool2d(
-            Pool2DKind::Max,
-            Const::<2>,
-            Const::<1>,
-            Const::<0>,
-            Const::<2>,
-        );
+        let y_max = x
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<1>,
+                Const::<0>,
+                Const::<2>,
+            )
+            .realize::<Rank3<1, 2, 3>>();
         assert_close_to_literal!(y_max, [[[13., 14., 15.], [18., 19., 20.]]]);
-        let y_min = x.clone().pool2d(
-            Pool2DKind::Min,
-            Const::<2>,
-            Const::<1>,
-            Const::<0>,
-            Const::<2>,
-        );
+        let y_min = x
+            .clone()
+            .pool2d(
+                Pool2DKind::Min,
+                Const::<2>,
+                Const::<1>,
+                Const::<0>,
+                Const::<2>,
+            )
+            .realize::<Rank3<1, 2, 3>>();
         assert_close_to_literal!(y_min, [[[0., 1., 2.], [6., 7., 8.]]]);
 
         let grads = y_max.mean().backward();
@@ -381,4 +422,49 @@ mod tests {
             ]]
         );
     }
+
+    #[test]
+    fn test_pool2d_sliced_input() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<2, 3, 6, 6>, TestDtype, _> = dev.sample_normal();
+
+        let x_view = x
+            .leaky_trace()
+            .slice((.., .., 1..5, 1..5))
+            .realize::<Rank4<2, 3, 4, 4>>();
+        let y = x_view
+            .pool2d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank4<2, 3, 2, 2>>();
+
+        let x_copy = x
+            .clone()
+            .slice((.., .., 1..5, 1..5))
+            .realize::<Rank4<2, 3, 4, 4>>()
+            .contiguous();
+        let y_true = x_copy
+            .leaky_trace()
+            .pool2d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank4<2, 3, 2, 2>>();
+        assert_close_to_tensor!(y, y_true);
+
+        let g = y.square().sum().backward();
+        let g_true = y_true.square().sum().backward();
+        let x_grad = g
+            .get(&x)
+            .slice((.., .., 1..5, 1..5))
+            .realize::<Rank4<2, 3, 4, 4>>();
+        assert_close_to_tensor!(x_grad, g_true.get(&x_copy));
+    }
 }
@@ -0,0 +1,104 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+fn make_4d<S: Shape>(strides: S::Concrete) -> [usize; 4] {
+    match S::NUM_DIMS {
+        3 => [0, strides[0], strides[1], strides[2]],
+        4 => [strides[0], strides[1], strides[2], strides[3]],
+        _ => panic!("Only implemented for 3d & 4d arrays"),
+    }
+}
+
+impl super::Pool2DOp {
+    /// All the `(y, x)` input positions inside the window of output `(oh, ow)`.
+    pub(crate) fn window(&self, oh: usize, ow: usize) -> std::vec::Vec<(usize, usize)> {
+        let mut window = std::vec::Vec::new();
+        for k1 in 0..self.kernel {
+            for k2 in 0..self.kernel {
+                let y = (oh * self.stride + self.dilation * k1).checked_sub(self.padding);
+                let x = (ow * self.stride + self.dilation * k2).checked_sub(self.padding);
+                if let Some((y, x)) = y.zip(x) {
+                    if y < self.h_in && x < self.w_in {
+                        window.push((y, x));
+                    }
+                }
+            }
+        }
+        window
+    }
+}
+
+impl<E: Float + Dtype> super::Pool2DKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool2DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_4d::<I>(inp.strides);
+        let ostr = make_4d::<O>(out.strides);
+        // NOTE: avg pooling always divides by the full kernel size, even for padded windows.
+        let scale = E::from(op.kernel * op.kernel).unwrap();
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for oh in 0..op.h_out {
+                    for ow in 0..op.w_out {
+                        let values = op.window(oh, ow).into_iter().map(|(y, x)| {
+                            inp.data
+                                [inp.offset + b * istr[0] + c * istr[1] + y * istr[2] + x * istr[3]]
+                        });
+                        out_buf[b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3]] = match op
+                            .kind
+                        {
+                            super::Pool2DKind::Avg => values.fold(E::zero(), |a, v| a + v) / scale,
+                            super::Pool2DKind::Min => values.fold(E::infinity(), E::min),
+                            super::Pool2DKind::Max => values.fold(E::neg_infinity(), E::max),
+                        };
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool2DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_4d::<I>(inp.strides);
+        let ostr = make_4d::<O>(out.strides);
+        let scale = E::from(op.kernel * op.kernel).unwrap();
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for oh in 0..op.h_out {
+                    for ow in 0..op.w_out {
+                        let i_out = b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3];
+                        for (y, x) in op.window(oh, ow) {
+                            let i_inp = b * istr[0] + c * istr[1] + y * istr[2] + x * istr[3];
+                            grad_inp[i_inp] += match op.kind {
+                                super::Pool2DKind::Avg => grad_out[i_out] / scale,
+                                _ if inp.data[inp.offset + i_inp] == out.data[i_out] => {
+                                    grad_out[i_out]
+                                }
+                                _ => E::zero(),
+                            };
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
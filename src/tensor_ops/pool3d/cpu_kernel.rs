@@ -0,0 +1,131 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+fn make_5d<S: Shape>(strides: S::Concrete) -> [usize; 5] {
+    match S::NUM_DIMS {
+        4 => [0, strides[0], strides[1], strides[2], strides[3]],
+        5 => [strides[0], strides[1], strides[2], strides[3], strides[4]],
+        _ => panic!("Only implemented for 4d & 5d arrays"),
+    }
+}
+
+impl super::Pool3DOp {
+    /// The input index along an axis of size `dim_in`, if output `o` and kernel
+    /// offset `k` don't land in the padding.
+    #[inline(always)]
+    fn inp_idx(&self, o: usize, k: usize, dim_in: usize) -> Option<usize> {
+        (o * self.stride + self.dilation * k)
+            .checked_sub(self.padding)
+            .filter(|&i| i < dim_in)
+    }
+}
+
+impl<E: Float + Dtype> super::Pool3DKernel<E> for Cpu {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool3DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_5d::<I>(inp.strides);
+        let ostr = make_5d::<O>(out.strides);
+        let kernel_numel = op.kernel * op.kernel * op.kernel;
+
+        let buf = &inp.data[inp.offset..];
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    for oh in 0..op.h_out {
+                        for ow in 0..op.w_out {
+                            let mut tmp = op.kind.init();
+                            for k1 in 0..op.kernel {
+                                let z = op.inp_idx(od, k1, op.d_in);
+                                for k2 in 0..op.kernel {
+                                    let y = op.inp_idx(oh, k2, op.h_in);
+                                    for k3 in 0..op.kernel {
+                                        let x = op.inp_idx(ow, k3, op.w_in);
+                                        if let Some(((z, y), x)) = z.zip(y).zip(x) {
+                                            let inp_idx = b * istr[0]
+                                                + c * istr[1]
+                                                + z * istr[2]
+                                                + y * istr[3]
+                                                + x * istr[4];
+                                            tmp = op.kind.accum(&tmp, &buf[inp_idx]);
+                                        }
+                                    }
+                                }
+                            }
+                            let out_idx = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            out_buf[out_idx] = op.kind.normalize(tmp, kernel_numel);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool3DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_5d::<I>(inp.strides);
+        let ostr = make_5d::<O>(out.strides);
+        let kernel_numel = op.kernel * op.kernel * op.kernel;
+
+        let inp_buf = &inp.data[inp.offset..];
+        let out_buf = out.data.as_ref();
+
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    for oh in 0..op.h_out {
+                        for ow in 0..op.w_out {
+                            let out_idx = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            let go = op.kind.normalize(grad_out[out_idx], kernel_numel);
+                            let vo = out_buf[out_idx];
+
+                            for k1 in 0..op.kernel {
+                                let z = op.inp_idx(od, k1, op.d_in);
+                                for k2 in 0..op.kernel {
+                                    let y = op.inp_idx(oh, k2, op.h_in);
+                                    for k3 in 0..op.kernel {
+                                        let x = op.inp_idx(ow, k3, op.w_in);
+                                        if let Some(((z, y), x)) = z.zip(y).zip(x) {
+                                            let inp_idx = b * istr[0]
+                                                + c * istr[1]
+                                                + z * istr[2]
+                                                + y * istr[3]
+                                                + x * istr[4];
+                                            grad_inp[inp_idx] +=
+                                                op.kind.filter(go, inp_buf[inp_idx], vo);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,354 @@
+mod cpu_kernel;
+mod reference_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{shapes::*, tensor::*};
+
+use super::{Pool2DKind, ReshapeTo};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Pool3DOp {
+    pub kind: Pool2DKind,
+    pub kernel: usize,
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+    pub batch: usize,
+    pub chan: usize,
+    pub d_in: usize,
+    pub d_out: usize,
+    pub h_in: usize,
+    pub h_out: usize,
+    pub w_in: usize,
+    pub w_out: usize,
+}
+
+pub(super) trait Pool3DKernel<E: Dtype>: DeviceStorage {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err>;
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: Pool3DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: Pool3DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Pooling with a 3d kernel over volumes with shape `(Channels, Depth, Height, Width)` or
+/// `(Batch, Channels, Depth, Height, Width)`.
+pub trait TryPool3D<Kernel, Stride, Padding, Dilation>: Sized {
+    type Pooled;
+    type Error: std::fmt::Debug;
+
+    fn pool3d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Self::Pooled {
+        self.try_pool3d(kind, kernel, stride, padding, dilation)
+            .unwrap()
+    }
+
+    fn try_pool3d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error>;
+}
+
+#[cfg(feature = "nightly")]
+impl<
+        const KERNEL: usize,
+        const STRIDE: usize,
+        const PADDING: usize,
+        const DILATION: usize,
+        const DIM: usize,
+    > TryPool3D<Const<KERNEL>, Const<STRIDE>, Const<PADDING>, Const<DILATION>> for Const<DIM>
+where
+    Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>: Sized,
+{
+    type Pooled = Const<{ (DIM + 2 * PADDING - DILATION * (KERNEL - 1) - 1) / STRIDE + 1 }>;
+    type Error = std::convert::Infallible;
+    fn try_pool3d(
+        self,
+        _: Pool2DKind,
+        _: Const<KERNEL>,
+        _: Const<STRIDE>,
+        _: Const<PADDING>,
+        _: Const<DILATION>,
+    ) -> Result<Self::Pooled, Self::Error> {
+        Ok(Const)
+    }
+}
+
+impl<Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim>
+    TryPool3D<Kernel, Stride, Padding, Dilation> for usize
+{
+    type Pooled = usize;
+    type Error = std::convert::Infallible;
+    fn try_pool3d(
+        self,
+        _: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        Ok((self + 2 * padding.size() - 1)
+            .checked_sub(dilation.size() * (kernel.size() - 1))
+            .unwrap()
+            / stride.size()
+            + 1)
+    }
+}
+
+// Without `generic_const_exprs` the pooled size of a [Const] can't be named, so
+// it is computed at runtime instead.
+#[cfg(not(feature = "nightly"))]
+impl<const DIM: usize, Kernel: Dim, Stride: Dim, Padding: Dim, Dilation: Dim>
+    TryPool3D<Kernel, Stride, Padding, Dilation> for Const<DIM>
+{
+    type Pooled = usize;
+    type Error = std::convert::Infallible;
+    fn try_pool3d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        DIM.try_pool3d(kind, kernel, stride, padding, dilation)
+    }
+}
+
+impl<Chan, Kernel, Stride, Padding, Dilation, Z, H, W, E, D, T>
+    TryPool3D<Kernel, Stride, Padding, Dilation> for Tensor<(Chan, Z, H, W), E, D, T>
+where
+    Chan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Z: Dim + TryPool3D<Kernel, Stride, Padding, Dilation>,
+    Z::Pooled: Dim,
+    H: Dim + TryPool3D<Kernel, Stride, Padding, Dilation>,
+    H::Pooled: Dim,
+    W: Dim + TryPool3D<Kernel, Stride, Padding, Dilation>,
+    W::Pooled: Dim,
+    E: Dtype,
+    D: Pool3DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+{
+    type Pooled = Tensor<(Chan, Z::Pooled, H::Pooled, W::Pooled), E, D, T>;
+    type Error = D::Err;
+
+    fn try_pool3d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (chan, d, h, w) = self.shape;
+        let vol = self.try_reshape_like(&(Const::<1>, chan, d, h, w))?;
+        let out = vol.try_pool3d(kind, kernel, stride, padding, dilation)?;
+        let (_, _, out_d, out_h, out_w) = out.shape;
+        out.try_reshape_like(&(chan, out_d, out_h, out_w))
+    }
+}
+
+impl<Chan, Kernel, Stride, Padding, Dilation, Batch, Z, H, W, E, D, T>
+    TryPool3D<Kernel, Stride, Padding, Dilation> for Tensor<(Batch, Chan, Z, H, W), E, D, T>
+where
+    Chan: Dim,
+    Kernel: Dim,
+    Stride: Dim,
+    Padding: Dim,
+    Dilation: Dim,
+    Batch: Dim,
+    Z: Dim + TryPool3D<Kernel, Stride, Padding, Dilation>,
+    Z::Pooled: Dim,
+    H: Dim + TryPool3D<Kernel, Stride, Padding, Dilation>,
+    H::Pooled: Dim,
+    W: Dim + TryPool3D<Kernel, Stride, Padding, Dilation>,
+    W::Pooled: Dim,
+    E: Dtype,
+    D: Pool3DKernel<E> + crate::tensor_ops::reshape_to::ReshapeKernel<E>,
+    T: Tape<E, D>,
+{
+    type Pooled = Tensor<(Batch, Chan, Z::Pooled, H::Pooled, W::Pooled), E, D, T>;
+    type Error = D::Err;
+
+    fn try_pool3d(
+        self,
+        kind: Pool2DKind,
+        kernel: Kernel,
+        stride: Stride,
+        padding: Padding,
+        dilation: Dilation,
+    ) -> Result<Self::Pooled, Self::Error> {
+        let (batch, chan, d, h, w) = self.shape;
+        // the kernels only handle contiguous inputs, so views are copied first
+        let inp = self.try_contiguous()?;
+        let d_out = d.pool3d(kind, kernel, stride, padding, dilation);
+        let h_out = h.pool3d(kind, kernel, stride, padding, dilation);
+        let w_out = w.pool3d(kind, kernel, stride, padding, dilation);
+        let op = Pool3DOp {
+            kind,
+            stride: stride.size(),
+            padding: padding.size(),
+            kernel: kernel.size(),
+            dilation: dilation.size(),
+            batch: batch.size(),
+            chan: chan.size(),
+            d_in: d.size(),
+            d_out: d_out.size(),
+            h_in: h.size(),
+            h_out: h_out.size(),
+            w_in: w.size(),
+            w_out: w_out.size(),
+        };
+        let (vol, mut tape) = inp.split_tape();
+        let mut out = vol.device.alloc((batch, chan, d_out, h_out, w_out))?;
+        Pool3DKernel::forward(&vol.device, op, &vol, &mut out)?;
+        let vol_ghost = vol.ghost();
+        let out_ghost = out.ghost();
+        let out_clone = out.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&vol_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_vol, grad_out) = grads.mut_and_ref(&vol_ghost, &out_ghost);
+            Pool3DKernel::backward(&vol.device, op, &vol, grad_vol, &out_clone, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_pool3d_max_grads() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[
+                [[1.0, 2., 3.], [4., 5., 6.]],
+                [[7., 8., 9.], [10., 11., 12.]],
+            ]])
+            .to_dtype::<TestDtype>();
+        let r = x
+            .leaky_trace()
+            .pool3d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<1>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank4<1, 1, 1, 2>>();
+        assert_close_to_literal!(r, [[[[11., 12.]]]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [[[[0., 0., 0.], [0., 0., 0.]], [[0., 0., 0.], [0., 1., 1.]]]]
+        );
+    }
+
+    #[test]
+    fn test_pool3d_avg_padded() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([[[[1.0, 2.], [3., 4.]], [[5., 6.], [7., 8.]]]])
+            .to_dtype::<TestDtype>();
+        let r = x
+            .leaky_trace()
+            .pool3d(
+                Pool2DKind::Avg,
+                Const::<2>,
+                Const::<2>,
+                Const::<1>,
+                Const::<1>,
+            )
+            .realize::<Rank4<1, 2, 2, 2>>();
+        assert_close_to_literal!(
+            r,
+            [[[[0.125, 0.25], [0.375, 0.5]], [[0.625, 0.75], [0.875, 1.0]]]]
+        );
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&x), [[[[0.125; 2]; 2]; 2]]);
+    }
+
+    #[test]
+    fn test_pool3d_batched() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank5<3, 2, 4, 5, 6>, TestDtype, _> = dev.sample_normal();
+        let y = x
+            .leaky_trace()
+            .pool3d(
+                Pool2DKind::Max,
+                Const::<2>,
+                Const::<2>,
+                Const::<0>,
+                Const::<1>,
+            )
+            .realize::<Rank5<3, 2, 2, 2, 3>>();
+        let y_nt = y.retaped::<NoneTape>();
+        let g = y.exp().mean().backward();
+        for i in 0..3 {
+            let x_i = x.clone().select(dev.tensor(i));
+            let y_i = x_i
+                .leaky_trace()
+                .pool3d(
+                    Pool2DKind::Max,
+                    Const::<2>,
+                    Const::<2>,
+                    Const::<0>,
+                    Const::<1>,
+                )
+                .realize::<Rank4<2, 2, 2, 3>>();
+            assert_close_to_tensor!(
+                y_i.retaped::<NoneTape>(),
+                y_nt.clone().select(dev.tensor(i))
+            );
+            let g_i = y_i.exp().mean().backward();
+            assert_close_to_tensor!(g_i.get(&x_i) / 3.0, g.get(&x).select(dev.tensor(i)));
+        }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_pool3d_const_output() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank5<3, 2, 4, 5, 6>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank5<3, 2, 2, 2, 3>, TestDtype, _> = x.pool3d(
+            Pool2DKind::Avg,
+            Const::<2>,
+            Const::<2>,
+            Const::<0>,
+            Const::<1>,
+        );
+    }
+}
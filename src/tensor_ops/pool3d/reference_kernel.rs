@@ -0,0 +1,128 @@
+use crate::{shapes::*, tensor::*};
+
+use std::sync::Arc;
+
+use num_traits::Float;
+
+fn make_5d<S: Shape>(strides: S::Concrete) -> [usize; 5] {
+    match S::NUM_DIMS {
+        4 => [0, strides[0], strides[1], strides[2], strides[3]],
+        5 => [strides[0], strides[1], strides[2], strides[3], strides[4]],
+        _ => panic!("Only implemented for 4d & 5d arrays"),
+    }
+}
+
+impl super::Pool3DOp {
+    /// All the `(z, y, x)` input positions inside the window of output `(od, oh, ow)`.
+    fn window(&self, od: usize, oh: usize, ow: usize) -> std::vec::Vec<(usize, usize, usize)> {
+        let axis = |o: usize, dim_in: usize| {
+            (0..self.kernel)
+                .filter_map(move |k| {
+                    (o * self.stride + self.dilation * k).checked_sub(self.padding)
+                })
+                .filter(move |&i| i < dim_in)
+        };
+        let mut window = std::vec::Vec::new();
+        for z in axis(od, self.d_in) {
+            for y in axis(oh, self.h_in) {
+                for x in axis(ow, self.w_in) {
+                    window.push((z, y, x));
+                }
+            }
+        }
+        window
+    }
+}
+
+impl<E: Float + Dtype> super::Pool3DKernel<E> for Reference {
+    fn alloc<S: Shape>(&self, s: S) -> Result<Tensor<S, E, Self>, Self::Err> {
+        self.try_zeros_like(&s)
+    }
+
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool3DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_5d::<I>(inp.strides);
+        let ostr = make_5d::<O>(out.strides);
+        // NOTE: avg pooling always divides by the full kernel size, even for padded windows.
+        let scale = E::from(op.kernel * op.kernel * op.kernel).unwrap();
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    for oh in 0..op.h_out {
+                        for ow in 0..op.w_out {
+                            let values = op.window(od, oh, ow).into_iter().map(|(z, y, x)| {
+                                inp.data[inp.offset
+                                    + b * istr[0]
+                                    + c * istr[1]
+                                    + z * istr[2]
+                                    + y * istr[3]
+                                    + x * istr[4]]
+                            });
+                            let i_out = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            out_buf[i_out] = match op.kind {
+                                super::Pool2DKind::Avg => {
+                                    values.fold(E::zero(), |a, v| a + v) / scale
+                                }
+                                super::Pool2DKind::Min => values.fold(E::infinity(), E::min),
+                                super::Pool2DKind::Max => values.fold(E::neg_infinity(), E::max),
+                            };
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::Pool3DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_5d::<I>(inp.strides);
+        let ostr = make_5d::<O>(out.strides);
+        let scale = E::from(op.kernel * op.kernel * op.kernel).unwrap();
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                for od in 0..op.d_out {
+                    for oh in 0..op.h_out {
+                        for ow in 0..op.w_out {
+                            let i_out = b * ostr[0]
+                                + c * ostr[1]
+                                + od * ostr[2]
+                                + oh * ostr[3]
+                                + ow * ostr[4];
+                            for (z, y, x) in op.window(od, oh, ow) {
+                                let i_inp = b * istr[0]
+                                    + c * istr[1]
+                                    + z * istr[2]
+                                    + y * istr[3]
+                                    + x * istr[4];
+                                grad_inp[i_inp] += match op.kind {
+                                    super::Pool2DKind::Avg => grad_out[i_out] / scale,
+                                    _ if inp.data[inp.offset + i_inp] == out.data[i_out] => {
+                                        grad_out[i_out]
+                                    }
+                                    _ => E::zero(),
+                                };
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
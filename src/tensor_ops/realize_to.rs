@@ -2,6 +2,9 @@ use crate::{shapes::*, tensor::*};
 
 /// Realizes the concrete shape of the tensor as another compatable shape,
 /// or returns the original tensor if the new shape's dimensions are incompatable.
+///
+/// This can also convert between static shapes and [DynShape], in which case
+/// the number of dimensions must match as well.
 /// ```rust
 /// # use dfdx::prelude::*;
 /// # let dev: Cpu = Default::default();
@@ -12,13 +15,15 @@ use crate::{shapes::*, tensor::*};
 ///     Ok(new) => println!("Shape was properly realized, returned new tensor"),
 ///     Err(old) => println!("Shape could not be realized, returned the original tensor"),
 /// }
+/// let b: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+/// let b = b.realize::<DynShape>();
+/// assert_eq!(b.shape().dims(), &[2, 3]);
+/// assert!(b.try_realize::<(usize, usize, usize)>().is_err());
 /// ```
 pub trait RealizeTo: HasErr + HasShape {
     /// Realizes the concrete shape of the tensor as another compatable shape,
     /// or returns the original tensor if the new shape's dimensions are incompatable.
-    fn realize<Dst: Shape<Concrete = <<Self as HasShape>::Shape as Shape>::Concrete>>(
-        self,
-    ) -> Self::WithShape<Dst>
+    fn realize<Dst: Shape>(self) -> Self::WithShape<Dst>
     where
         Self::Shape: RealizeShapeTo<Dst>,
         Self: std::fmt::Debug,
@@ -28,15 +33,13 @@ pub trait RealizeTo: HasErr + HasShape {
 
     /// Realizes the concrete shape of the tensor as another compatable shape,
     /// or returns the original tensor if the new shape's dimensions are incompatable.
-    fn try_realize<Dst: Shape<Concrete = <<Self as HasShape>::Shape as Shape>::Concrete>>(
-        self,
-    ) -> Result<Self::WithShape<Dst>, Self>
+    fn try_realize<Dst: Shape>(self) -> Result<Self::WithShape<Dst>, Self>
     where
         Self::Shape: RealizeShapeTo<Dst>;
 }
 
 impl<S: Shape, E: Dtype, D: DeviceStorage, T: Tape<E, D>> RealizeTo for Tensor<S, E, D, T> {
-    fn try_realize<Dst: Shape<Concrete = S::Concrete>>(self) -> Result<Self::WithShape<Dst>, Self>
+    fn try_realize<Dst: Shape>(self) -> Result<Self::WithShape<Dst>, Self>
     where
         Self::Shape: RealizeShapeTo<Dst>,
     {
@@ -44,8 +47,9 @@ impl<S: Shape, E: Dtype, D: DeviceStorage, T: Tape<E, D>> RealizeTo for Tensor<S
             Ok(Tensor {
                 id: self.id,
                 data: self.data,
-                strides: self.strides,
+                strides: self.shape.realized_strides(self.strides),
                 shape: dst_shape,
+                offset: self.offset,
                 device: self.device,
                 tape: self.tape,
             })
@@ -224,4 +228,88 @@ mod tests {
         let x = x.try_realize::<(usize, usize, usize, Const<9>)>().unwrap();
         let _ = x.try_realize::<(usize, usize, usize, usize)>().unwrap();
     }
+
+    #[test]
+    fn test_realize_dyn() {
+        let dev: TestDevice = Default::default();
+        let src: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let dst = src.clone().realize::<DynShape>();
+        assert_eq!(dst.shape().dims(), &[2, 3, 4]);
+        assert_eq!(dst.strides, dst.shape().strides());
+        assert_eq!(src.as_vec(), dst.as_vec());
+
+        let mut dst = dst.try_realize::<Rank2<6, 4>>().unwrap_err();
+        dst = dst.try_realize::<Rank3<2, 4, 3>>().unwrap_err();
+        dst = dst.try_realize::<Rank4<1, 2, 3, 4>>().unwrap_err();
+        let back = dst.realize::<(Const<2>, usize, Const<4>)>();
+        assert_eq!(back.strides, src.strides);
+        assert_eq!(src.as_vec(), back.as_vec());
+
+        let scalar = dev
+            .tensor(1.0)
+            .to_dtype::<TestDtype>()
+            .realize::<DynShape>();
+        assert_eq!(scalar.shape().rank(), 0);
+        assert_eq!(scalar.realize::<Rank0>().array(), TestDtype::ONE);
+    }
+
+    #[test]
+    fn test_realize_dyn_rank6() {
+        let dev: TestDevice = Default::default();
+        let src: Tensor<Rank6<2, 1, 3, 1, 2, 2>, TestDtype, _> = dev.sample_normal();
+        let dst = src.clone().realize::<DynShape>();
+        assert_eq!(dst.shape().dims(), &[2, 1, 3, 1, 2, 2]);
+        assert_eq!(dst.strides, src.strides);
+        let dst = dst.realize::<DynShape>();
+        assert_eq!(dst.shape().rank(), 6);
+        let dst = dst.try_realize::<Rank5<2, 3, 1, 2, 2>>().unwrap_err();
+        let back = dst.realize::<Rank6<2, 1, 3, 1, 2, 2>>();
+        assert_eq!(src.as_vec(), back.as_vec());
+
+        // the padding of a lower rank DynShape must not realize as rank 6
+        let src: Tensor<Rank3<3, 2, 2>, TestDtype, _> = dev.zeros();
+        let dst = src.realize::<DynShape>();
+        let dst = dst.realize::<DynShape>();
+        assert_eq!(dst.shape().dims(), &[3, 2, 2]);
+        let dst = dst.try_realize::<Rank6<1, 1, 1, 3, 2, 2>>().unwrap_err();
+        assert!(dst
+            .try_realize::<(usize, usize, usize, usize, usize, usize)>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_realize_dyn_broadcasted() {
+        let dev: TestDevice = Default::default();
+        let src: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let dst = src
+            .clone()
+            .broadcast::<Rank2<2, 3>, _>()
+            .realize::<DynShape>();
+        assert_eq!(dst.strides[4..], [0, 1]);
+        let dst = dst.realize::<Rank2<2, 3>>();
+        assert_eq!(dst.array(), [src.array(); 2]);
+    }
+
+    #[test]
+    fn test_dyn_ops_and_backward() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 5>, TestDtype, _> = dev.sample_normal();
+        let g1 = t.leaky_trace().exp().sum().backward();
+        let g2 = t.leaky_trace().realize::<DynShape>().exp().sum().backward();
+        assert_eq!(g1.get(&t).array(), g2.get(&t).array());
+
+        let shape = DynShape::new(&[2, 3]);
+        let a = dev.tensor_from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], shape);
+        let a = a.to_dtype::<TestDtype>();
+        let b = a.clone() * a.clone() + a.clone();
+        let b = b.realize::<Rank2<2, 3>>();
+        assert_close_to_literal!(b, [[2.0, 6.0, 12.0], [20.0, 30.0, 42.0]]);
+
+        let r = a.clone().sum::<_, Axis<5>>();
+        assert_eq!(r.shape, [1, 1, 1, 1, 2]);
+        assert_close_to_literal!(r.reshape_like(&(Const::<2>,)), [6.0, 15.0]);
+
+        let s = a.clone().softmax::<Axis<5>>().realize::<Rank2<2, 3>>();
+        assert_close_to_tensor!(s, a.realize::<Rank2<2, 3>>().softmax::<Axis<1>>());
+    }
 }
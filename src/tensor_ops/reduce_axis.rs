@@ -0,0 +1,164 @@
+//! Reductions of [DynShape] tensors along an axis that is only known at runtime.
+//!
+//! The axes of [DynShape] refer to its padded dimensions (see [DynShape]), so the
+//! typed reductions can only reduce the last axis (`Axis<5>`) or everything (`()`).
+//! These reductions instead take the axis in terms of the dimensions the shape
+//! was created with, e.g. axis 0 of `DynShape::new(&[2, 3])` is the dimension of size 2.
+//!
+//! They move the axis to the end as a view and use the typed `Axis<5>` reductions,
+//! so backward works like for any other op.
+
+use crate::{shapes::*, tensor::*};
+
+use super::{Device, MaxTo, MeanTo, MinTo, ReshapeTo, SumTo};
+
+use std::vec::Vec;
+
+impl<E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<DynShape, E, D, T> {
+    /// Views `self` with `axis` moved to the last padded dimension, and returns the
+    /// shape of the result of reducing `axis`.
+    ///
+    /// **Panics** if `axis` is not less than the rank of the shape.
+    fn reduce_axis_to_last(self, op: &str, axis: usize) -> (Self, DynShape) {
+        let rank = self.shape.rank();
+        assert!(
+            axis < rank,
+            "{op}: axis {axis} is out of range for a shape of rank {rank}"
+        );
+        let axis = MAX_DYN_DIMS - rank + axis;
+        let perm: Vec<usize> = (0..MAX_DYN_DIMS)
+            .filter(|&i| i != axis)
+            .chain([axis])
+            .collect();
+        let mut dims: Vec<usize> = self.shape.dims().into();
+        dims.remove(axis + rank - MAX_DYN_DIMS);
+        (self.permuted_dyn(&perm), DynShape::new(&dims))
+    }
+
+    /// Sums `self` along `axis`, where `axis` is an index into [DynShape::dims()].
+    ///
+    /// **Panics** if `axis` is not less than the rank of the shape.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor_from_vec(vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0], DynShape::new(&[2, 3]));
+    /// let r = t.sum_axis(0);
+    /// assert_eq!(r.shape().dims(), &[3]);
+    /// assert_eq!(r.as_vec(), [5.0, 7.0, 9.0]);
+    /// ```
+    pub fn sum_axis(self, axis: usize) -> Self {
+        self.try_sum_axis(axis).unwrap()
+    }
+
+    /// Fallible version of [Tensor::sum_axis]
+    pub fn try_sum_axis(self, axis: usize) -> Result<Self, D::Err> {
+        let (t, dst) = self.reduce_axis_to_last("sum_axis", axis);
+        t.try_sum::<_, Axis<5>>()?.try_reshape_like(&dst)
+    }
+
+    /// Averages `self` along `axis`, where `axis` is an index into [DynShape::dims()].
+    ///
+    /// **Panics** if `axis` is not less than the rank of the shape.
+    pub fn mean_axis(self, axis: usize) -> Self {
+        self.try_mean_axis(axis).unwrap()
+    }
+
+    /// Fallible version of [Tensor::mean_axis]
+    pub fn try_mean_axis(self, axis: usize) -> Result<Self, D::Err> {
+        let (t, dst) = self.reduce_axis_to_last("mean_axis", axis);
+        t.try_mean::<_, Axis<5>>()?.try_reshape_like(&dst)
+    }
+
+    /// Max of `self` along `axis`, where `axis` is an index into [DynShape::dims()].
+    ///
+    /// **Panics** if `axis` is not less than the rank of the shape.
+    pub fn max_axis(self, axis: usize) -> Self {
+        self.try_max_axis(axis).unwrap()
+    }
+
+    /// Fallible version of [Tensor::max_axis]
+    pub fn try_max_axis(self, axis: usize) -> Result<Self, D::Err> {
+        let (t, dst) = self.reduce_axis_to_last("max_axis", axis);
+        t.try_max::<_, Axis<5>>()?.try_reshape_like(&dst)
+    }
+
+    /// Min of `self` along `axis`, where `axis` is an index into [DynShape::dims()].
+    ///
+    /// **Panics** if `axis` is not less than the rank of the shape.
+    pub fn min_axis(self, axis: usize) -> Self {
+        self.try_min_axis(axis).unwrap()
+    }
+
+    /// Fallible version of [Tensor::min_axis]
+    pub fn try_min_axis(self, axis: usize) -> Result<Self, D::Err> {
+        let (t, dst) = self.reduce_axis_to_last("min_axis", axis);
+        t.try_min::<_, Axis<5>>()?.try_reshape_like(&dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_sum_axis_matches_static() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let r = t.leaky_trace().realize::<DynShape>().sum_axis(1);
+        assert_eq!(r.shape().dims(), &[2, 4]);
+        let r2 = t.leaky_trace().sum::<Rank2<2, 4>, _>();
+        assert_close_to_tensor!(r.realize::<Rank2<2, 4>>(), r2);
+
+        let d = t.leaky_trace().realize::<DynShape>();
+        let g = d.sum_axis(0).exp().sum().backward();
+        let g2 = t
+            .leaky_trace()
+            .sum::<Rank2<3, 4>, _>()
+            .exp()
+            .sum()
+            .backward();
+        assert_close_to_tensor!(g.get(&t), g2.get(&t));
+    }
+
+    #[test]
+    fn test_reduce_axis_to_scalar() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([1.0, -2.0, 3.0, 6.0])
+            .to_dtype::<TestDtype>()
+            .realize::<DynShape>();
+        assert_eq!(t.clone().sum_axis(0).shape().dims(), &[] as &[usize]);
+        assert_close_to_literal!(t.clone().sum_axis(0).realize::<Rank0>(), 8.0);
+        assert_close_to_literal!(t.clone().mean_axis(0).realize::<Rank0>(), 2.0);
+        assert_close_to_literal!(t.clone().max_axis(0).realize::<Rank0>(), 6.0);
+        assert_close_to_literal!(t.min_axis(0).realize::<Rank0>(), -2.0);
+    }
+
+    #[test]
+    fn test_mean_max_min_axis_2d() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0, 3.0], [-2.0, 4.0, -6.0]])
+            .to_dtype::<TestDtype>();
+        let d = t.clone().realize::<DynShape>();
+
+        let r = d.clone().mean_axis(1).realize::<Rank1<2>>();
+        assert_close_to_literal!(r, [2.0, -4.0 / 3.0]);
+        let r = d.clone().leaky_trace().max_axis(0).realize::<Rank1<3>>();
+        assert_close_to_literal!(r, [1.0, 4.0, 3.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[1.0, 0.0, 1.0], [0.0, 1.0, 0.0]]);
+        let r = d.min_axis(1).realize::<Rank1<2>>();
+        assert_close_to_literal!(r, [1.0, -6.0]);
+    }
+
+    #[test]
+    #[should_panic = "sum_axis: axis 2 is out of range for a shape of rank 2"]
+    fn test_sum_axis_out_of_range() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<DynShape, TestDtype, _> = dev.zeros_like(&DynShape::new(&[2, 3]));
+        let _ = t.sum_axis(2);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        cpu::{index_to_i, LendingIterator, NdIndex},
+        Cpu, GhostTensor, Tensor, ZerosTensor,
+    },
+};
+
+use super::RepeatOp;
+
+impl<E: Dtype> super::RepeatKernel<E> for Cpu {
+    fn forward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        op: RepeatOp,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let n = inp.shape.concrete()[op.axis];
+        let mut out = self.try_zeros_like(&dst)?;
+        let mut out_iter = out.iter_mut_with_index();
+        while let Some((x, mut i)) = out_iter.next() {
+            i[op.axis] = op.source_index(i[op.axis], n);
+            *x = inp[i];
+        }
+        Ok(out)
+    }
+
+    fn backward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        op: RepeatOp,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let n = inp.shape.concrete()[op.axis];
+        let mut out_idx = NdIndex::new(out.shape, out.strides);
+        while let Some((i_out, mut i)) = out_idx.next_with_idx() {
+            i[op.axis] = op.source_index(i[op.axis], n);
+            grad_inp[index_to_i(&inp.shape, &inp.strides, i)] += grad_out[i_out];
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{shapes::*, tensor::*};
+
+use super::{reshape_to::ReshapeKernel, BroadcastTo, ReshapeTo};
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+#[derive(Copy, Clone, Debug)]
+pub struct RepeatOp {
+    pub(crate) axis: usize,
+    pub(crate) repeats: usize,
+    /// Whether each element is repeated, instead of the whole axis.
+    pub(crate) interleave: bool,
+}
+
+impl RepeatOp {
+    /// Returns the index along the axis of the input that index `i` along the
+    /// axis of the output reads from, where `n` is the size of the input's axis.
+    pub(crate) fn source_index(&self, i: usize, n: usize) -> usize {
+        if self.interleave {
+            i / self.repeats
+        } else {
+            i % n
+        }
+    }
+}
+
+pub trait RepeatKernel<E: Dtype>: DeviceStorage {
+    fn forward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        op: RepeatOp,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>;
+
+    fn backward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        op: RepeatOp,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+impl<S: Shape, E: Dtype, D: RepeatKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Copies the whole tensor `repeats` times along `Ax`.
+    ///
+    /// **pytorch equivalent** `torch.Tensor.repeat` along a single dimension.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+    /// let r = t.tile(Axis::<1>, 2);
+    /// assert_eq!(r.shape(), &(Const::<2>, 4));
+    /// assert_eq!(r.as_vec(), [1.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0, 4.0]);
+    /// ```
+    pub fn tile<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        ax: Ax,
+        repeats: usize,
+    ) -> Tensor<S::Replaced, E, D, T>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        self.try_tile(ax, repeats).unwrap()
+    }
+
+    /// Fallible version of [Tensor::tile]
+    pub fn try_tile<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        _: Ax,
+        repeats: usize,
+    ) -> Result<Tensor<S::Replaced, E, D, T>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        self.try_repeat_along::<Ax>(repeats, false)
+    }
+
+    /// Repeats each element `repeats` times along `Ax`. This is how key/value heads
+    /// are shared between query heads in grouped-query attention.
+    ///
+    /// **pytorch equivalent** `torch.repeat_interleave`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+    /// let r = t.repeat_interleave(Axis::<1>, 2);
+    /// assert_eq!(r.shape(), &(Const::<2>, 4));
+    /// assert_eq!(r.as_vec(), [1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]);
+    /// ```
+    pub fn repeat_interleave<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        ax: Ax,
+        repeats: usize,
+    ) -> Tensor<S::Replaced, E, D, T>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        self.try_repeat_interleave(ax, repeats).unwrap()
+    }
+
+    /// Fallible version of [Tensor::repeat_interleave]
+    pub fn try_repeat_interleave<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        _: Ax,
+        repeats: usize,
+    ) -> Result<Tensor<S::Replaced, E, D, T>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        self.try_repeat_along::<Ax>(repeats, true)
+    }
+
+    fn try_repeat_along<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        repeats: usize,
+        interleave: bool,
+    ) -> Result<Tensor<S::Replaced, E, D, T>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        let axis = Ax::as_array()[0] as usize;
+        let op = RepeatOp {
+            axis,
+            repeats,
+            interleave,
+        };
+        let dst = self
+            .shape
+            .replace_along(self.shape.concrete()[axis] * repeats);
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward(op, &inp, dst)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            inp.device
+                .backward(op, &inp_ghost, grad_inp, &out_ghost, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+impl<S: Shape, E: Dtype, D: ReshapeKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Like [super::BroadcastTo::broadcast], but the result owns a contiguous copy of
+    /// the data instead of being a view with strides of 0.
+    ///
+    /// **pytorch equivalent** `torch.Tensor.expand(...).contiguous()`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([1.0, 2.0]);
+    /// let r: Tensor<Rank2<3, 2>, f32, _> = t.expand_copy();
+    /// assert_eq!(r.array(), [[1.0, 2.0]; 3]);
+    /// ```
+    pub fn expand_copy<Dst: ConstShape, Ax: Axes>(self) -> Tensor<Dst, E, D, T>
+    where
+        S: BroadcastShapeTo<Dst, Ax>,
+    {
+        self.try_expand_copy_like::<Dst, Ax>(&Default::default())
+            .unwrap()
+    }
+
+    /// Fallible version of [Tensor::expand_copy]
+    pub fn try_expand_copy<Dst: ConstShape, Ax: Axes>(self) -> Result<Tensor<Dst, E, D, T>, D::Err>
+    where
+        S: BroadcastShapeTo<Dst, Ax>,
+    {
+        self.try_expand_copy_like::<Dst, Ax>(&Default::default())
+    }
+
+    /// Same as [Tensor::expand_copy], but the target shape is given
+    pub fn expand_copy_like<Dst: Shape, Ax: Axes>(self, dst: &Dst) -> Tensor<Dst, E, D, T>
+    where
+        S: BroadcastShapeTo<Dst, Ax>,
+    {
+        self.try_expand_copy_like(dst).unwrap()
+    }
+
+    /// Fallible version of [Tensor::expand_copy_like]
+    pub fn try_expand_copy_like<Dst: Shape, Ax: Axes>(
+        self,
+        dst: &Dst,
+    ) -> Result<Tensor<Dst, E, D, T>, D::Err>
+    where
+        S: BroadcastShapeTo<Dst, Ax>,
+    {
+        self.try_broadcast_like::<Dst, Ax>(dst)?.try_contiguous()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_tile_forward_backward() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([[1.0, 2.0], [3.0, 4.0]]).to_dtype::<TestDtype>();
+        let w = dev
+            .tensor([
+                [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0, 10.0, 11.0, 12.0],
+            ])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().tile(Axis::<1>, 3);
+        assert_eq!(r.shape, (Const, 6));
+        let r = r.realize::<Rank2<2, 6>>();
+        assert_close_to_literal!(
+            r,
+            [
+                [1.0, 2.0, 1.0, 2.0, 1.0, 2.0],
+                [3.0, 4.0, 3.0, 4.0, 3.0, 4.0]
+            ]
+        );
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[9.0, 12.0], [27.0, 30.0]]);
+    }
+
+    #[test]
+    fn test_repeat_interleave_forward_backward() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([[1.0, 2.0], [3.0, 4.0]]).to_dtype::<TestDtype>();
+        let w = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0], [7.0, 8.0]])
+            .to_dtype::<TestDtype>();
+        let r = t
+            .leaky_trace()
+            .repeat_interleave(Axis::<0>, 2)
+            .realize::<Rank2<4, 2>>();
+        assert_close_to_literal!(r, [[1.0, 2.0], [1.0, 2.0], [3.0, 4.0], [3.0, 4.0]]);
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[4.0, 6.0], [12.0, 14.0]]);
+    }
+
+    #[test]
+    fn test_repeat_interleave_broadcasted_input() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([1.0, 2.0, 3.0]).to_dtype::<TestDtype>();
+        let r = t
+            .leaky_trace()
+            .broadcast::<Rank2<2, 3>, _>()
+            .repeat_interleave(Axis::<1>, 2);
+        assert_eq!(r.shape, (Const, 6));
+        let r = r.realize::<Rank2<2, 6>>();
+        assert_close_to_literal!(r, [[1.0, 1.0, 2.0, 2.0, 3.0, 3.0]; 2]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [4.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn test_tile_zero_repeats() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = t.tile(Axis::<0>, 0);
+        assert_eq!(r.shape, (0, Const));
+        assert!(r.as_vec().is_empty());
+    }
+
+    #[test]
+    fn test_expand_copy() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([1.0, 2.0, 3.0]).to_dtype::<TestDtype>();
+        let r: Tensor<Rank2<2, 3>, TestDtype, _, _> = t.leaky_trace().expand_copy();
+        assert_eq!(r.strides, [3, 1]);
+        assert_close_to_literal!(r, [[1.0, 2.0, 3.0]; 2]);
+        let g = r.exp().sum().backward();
+        assert_close_to_literal!(
+            g.get(&t),
+            [
+                2.0 * f64::exp(1.0),
+                2.0 * f64::exp(2.0),
+                2.0 * f64::exp(3.0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        reference::{physical_index, ravel_index, unravel_index},
+        GhostTensor, Reference, Tensor,
+    },
+};
+
+use super::RepeatOp;
+
+/// Returns the index into `inp` of the `i`th (row major) entry of `out`.
+fn source_index<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+    op: RepeatOp,
+    inp: &S,
+    out: &Dst,
+    i: usize,
+) -> S::Concrete {
+    let mut idx = unravel_index(out, i);
+    idx[op.axis] = op.source_index(idx[op.axis], inp.concrete()[op.axis]);
+    idx
+}
+
+impl<E: Dtype> super::RepeatKernel<E> for Reference {
+    fn forward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        op: RepeatOp,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let out = (0..dst.num_elements())
+            .map(|i| inp[source_index(op, &inp.shape, &dst, i)])
+            .collect();
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        op: RepeatOp,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for i in 0..out.shape.num_elements() {
+            let i_inp = source_index(op, &inp.shape, &out.shape, i);
+            grad_inp[ravel_index::<S>(&inp.strides, &i_inp)] +=
+                grad_out[physical_index(&out.shape, &out.strides, i)];
+        }
+        Ok(())
+    }
+}
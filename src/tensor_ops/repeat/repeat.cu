@@ -0,0 +1,100 @@
+#include "cuda_utils.cuh"
+
+// Computes the index into `inp` that the `i`th element of `out` reads from.
+__device__ size_t get_repeated_index(
+    size_t i,
+    const size_t axis,
+    const size_t repeats,
+    const size_t interleave,
+    const size_t num_dims,
+    const size_t *inp_dims,
+    const size_t *inp_strides,
+    const size_t *out_dims
+) {
+    size_t inp_i = 0;
+    for (int d = num_dims - 1; d >= 0; d--) {
+        size_t j = i % out_dims[d];
+        i /= out_dims[d];
+        if (d == axis) {
+            j = interleave ? j / repeats : j % inp_dims[d];
+        }
+        inp_i += j * inp_strides[d];
+    }
+    return inp_i;
+}
+
+template<typename T>
+__device__ void repeat_fwd(
+    const size_t axis,
+    const size_t repeats,
+    const size_t interleave,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *inp_dims,
+    const size_t *inp_strides,
+    const size_t *out_dims,
+    const T *inp,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    out[i] = inp[get_repeated_index(i, axis, repeats, interleave, num_dims, inp_dims, inp_strides, out_dims)];
+}
+
+template<typename T>
+__device__ void repeat_bwd(
+    const size_t axis,
+    const size_t repeats,
+    const size_t interleave,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *inp_dims,
+    const size_t *inp_strides,
+    const size_t *out_dims,
+    const size_t *out_strides,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t inp_i = get_repeated_index(i, axis, repeats, interleave, num_dims, inp_dims, inp_strides, out_dims);
+    size_t out_i = get_strided_index(i, num_dims, out_dims, out_strides);
+    atomicAdd(grad_inp + inp_i, grad_out[out_i]);
+}
+
+#define REPEAT(TY, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t axis, \
+    const size_t repeats, \
+    const size_t interleave, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *inp_dims, \
+    const size_t *inp_strides, \
+    const size_t *out_dims, \
+    const TY *inp, \
+    TY *out \
+) { repeat_fwd(axis, repeats, interleave, num_dims, numel, inp_dims, inp_strides, out_dims, inp, out); } \
+extern "C" __global__ void BWD( \
+    const size_t axis, \
+    const size_t repeats, \
+    const size_t interleave, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *inp_dims, \
+    const size_t *inp_strides, \
+    const size_t *out_dims, \
+    const size_t *out_strides, \
+    TY *grad_inp, \
+    const TY *grad_out \
+) { repeat_bwd(axis, repeats, interleave, num_dims, numel, inp_dims, inp_strides, out_dims, out_strides, grad_inp, grad_out); }
+
+REPEAT(__half, repeat_fwd_f16, repeat_bwd_f16);
+REPEAT(float, repeat_fwd_f32, repeat_bwd_f32);
+REPEAT(double, repeat_fwd_f64, repeat_bwd_f64);
@@ -1,4 +1,5 @@
 mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -85,13 +86,16 @@ pub trait ReshapeTo: HasErr + HasShape {
 
 impl<S: Shape, E: Dtype, D: ReshapeKernel<E>, T: Tape<E, D>> ReshapeTo for Tensor<S, E, D, T> {
     fn try_reshape_like<Dst: Shape>(self, dst: &Dst) -> Result<Self::WithShape<Dst>, Self::Err> {
-        assert_eq!(self.shape().num_elements(), dst.num_elements());
+        if self.shape().num_elements() != dst.num_elements() {
+            return Err(ShapeError::new("reshape_like", self.shape(), dst).into());
+        }
         if self.shape.strides() == self.strides {
             Ok(Tensor {
                 id: self.id,
                 data: self.data,
                 shape: *dst,
                 strides: dst.strides(),
+                offset: self.offset,
                 device: self.device,
                 tape: self.tape,
             })
@@ -128,6 +132,17 @@ mod tests {
         let _ = t.reshape_like(&(7,));
     }
 
+    #[test]
+    fn test_invalid_try_reshape() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<(usize,), TestDtype, _> = dev.zeros_like(&(5,));
+        let err = t.try_reshape_like(&(7,)).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("reshape_like", &(5,), &(7,)))
+        );
+    }
+
     #[test]
     fn test_unit_non_contiguous_reshapes() {
         let dev: TestDevice = Default::default();
@@ -0,0 +1,30 @@
+use crate::shapes::{Dtype, Shape};
+use crate::tensor::{reference::physical_index, Reference, Tensor};
+
+impl<E: Dtype> super::ReshapeKernel<E> for Reference {
+    fn forward<Src: Shape, Dst: Shape>(
+        &self,
+        dst: &Dst,
+        inp: &Tensor<Src, E, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let mut out = self.try_alloc_elem(dst.num_elements(), E::default())?;
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = inp.data[inp.offset + physical_index(&inp.shape, &inp.strides, i)];
+        }
+        Ok(self.build_tensor(*dst, out))
+    }
+    fn backward<Src: Shape, Dst: Shape>(
+        &self,
+        dst: &Dst,
+        inp: &Tensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let dst_strides = dst.strides();
+        for i in 0..inp.shape.num_elements() {
+            grad_inp[physical_index(&inp.shape, &inp.strides, i)] +=
+                grad_out[physical_index(dst, &dst_strides, i)];
+        }
+        Ok(())
+    }
+}
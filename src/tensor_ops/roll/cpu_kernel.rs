@@ -22,13 +22,14 @@ impl<E: Dtype> super::RollKernel<E> for Cpu {
                 .zip(strides)
                 .map(|(i, s)| i * s)
                 .sum::<usize>();
-            data[new_i] = inp.data[old_i];
+            data[new_i] = inp.data[inp.offset + old_i];
         }
         Ok(Tensor {
             id: unique_id(),
             data: Arc::new(data),
             shape: inp.shape,
             strides,
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         })
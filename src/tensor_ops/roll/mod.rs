@@ -6,6 +6,7 @@ use crate::{
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
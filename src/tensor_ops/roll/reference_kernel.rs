@@ -0,0 +1,45 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        reference::{ravel_index, unravel_index},
+        Reference, Tensor,
+    },
+};
+
+impl<E: Dtype> super::RollKernel<E> for Reference {
+    fn forward<S: Shape>(
+        &self,
+        op: super::RollOp,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let dims = inp.shape.concrete();
+        let strides = inp.shape.strides();
+        let mut out = self.try_alloc_elem(inp.shape.num_elements(), E::default())?;
+        for i in 0..inp.shape.num_elements() {
+            let idx = unravel_index(&inp.shape, i);
+            let mut rolled = idx;
+            rolled[op.axis] = (idx[op.axis] + op.amount) % dims[op.axis];
+            out[ravel_index::<S>(&strides, &rolled)] =
+                inp.data[inp.offset + ravel_index::<S>(&inp.strides, &idx)];
+        }
+        Ok(self.build_tensor(inp.shape, out))
+    }
+    fn backward<S: Shape>(
+        &self,
+        op: super::RollOp,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let dims = inp.shape.concrete();
+        let strides = inp.shape.strides();
+        for i in 0..inp.shape.num_elements() {
+            let idx = unravel_index(&inp.shape, i);
+            let mut rolled = idx;
+            rolled[op.axis] = (idx[op.axis] + op.amount) % dims[op.axis];
+            grad_inp[ravel_index::<S>(&inp.strides, &idx)] +=
+                grad_out[ravel_index::<S>(&strides, &rolled)];
+        }
+        Ok(())
+    }
+}
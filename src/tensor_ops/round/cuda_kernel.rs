@@ -0,0 +1,11 @@
+use super::RoundKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for RoundKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/round.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(const_df() RoundKernelOp, half::f16, PTX, "round_fwd_f16", "round_bwd_f16");
+cuda_unary!(const_df() RoundKernelOp, f32, PTX, "round_fwd_f32", "round_bwd_f32");
+cuda_unary!(const_df() RoundKernelOp, f64, PTX, "round_fwd_f64", "round_bwd_f64");
@@ -0,0 +1,57 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct RoundKernelOp;
+
+/// Rounds to the nearest integer, with halfway cases rounded away from zero.
+///
+/// The derivative is zero everywhere, so no gradient flows back through this.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.5, 0.5, 1.5, 2.0]);
+/// let r = t.round();
+/// assert_eq!(r.array(), [-2.0, 1.0, 2.0, 2.0]);
+/// ```
+pub fn round<S: Shape, E: Dtype, D: UnaryKernel<RoundKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.round()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<RoundKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [round]
+    pub fn round(self) -> Self {
+        self.try_round().unwrap()
+    }
+    /// See [round]
+    pub fn try_round(self) -> Result<Self, D::Err> {
+        try_unary_op(RoundKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_round() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-1.5, -0.4, 0.0, 0.6, 1.5])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().round();
+        assert_close_to_literal!(r, [-2.0, -0.0, 0.0, 1.0, 2.0]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(g.get(&x), [0.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+}
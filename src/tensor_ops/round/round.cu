@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+struct RoundKernelOp {};
+
+UNARY_OP(__half, round_fwd_f16, round_bwd_f16, RoundKernelOp,
+        roundg(x),
+        0.0)
+
+UNARY_OP(float, round_fwd_f32, round_bwd_f32, RoundKernelOp,
+        roundg(x),
+        0.0)
+
+UNARY_OP(double, round_fwd_f64, round_bwd_f64, RoundKernelOp,
+        roundg(x),
+        0.0)
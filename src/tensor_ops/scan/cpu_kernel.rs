@@ -0,0 +1,94 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        cpu::{index_to_i, NdIndex},
+        unique_id, Cpu, Tensor,
+    },
+};
+
+use num_traits::Float;
+use std::{sync::Arc, vec::Vec};
+
+use super::ScanOp;
+
+/// Returns the index of the first element of every line along `axis`,
+/// once for `strides[0]` and once for `strides[1]`.
+fn line_starts<S: Shape>(axis: usize, shape: &S, strides: [&S::Concrete; 2]) -> Vec<[usize; 2]> {
+    let mut starts = Vec::new();
+    let mut idx = NdIndex::new(*shape, *strides[0]);
+    while let Some((i, idx)) = idx.next_with_idx() {
+        if idx[axis] == 0 {
+            starts.push([i, index_to_i(shape, strides[1], idx)]);
+        }
+    }
+    starts
+}
+
+impl<E: Dtype + Float> super::ScanKernel<E> for Cpu {
+    fn forward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let n = inp.shape.concrete()[op.axis];
+        let strides = inp.shape.strides();
+        let (inp_s, out_s) = (inp.strides[op.axis], strides[op.axis]);
+        let mut data = self.try_alloc_zeros::<E>(inp.shape.num_elements())?;
+
+        let mut x = Vec::with_capacity(n);
+        let mut y = std::vec![E::zero(); n];
+        for [i_inp, i_out] in line_starts(op.axis, &inp.shape, [&inp.strides, &strides]) {
+            x.clear();
+            x.extend(
+                op.order(n)
+                    .map(|t| inp.data[inp.offset + i_inp + t * inp_s]),
+            );
+            op.kind.forward(&x, &mut y);
+            for (s, t) in op.order(n).enumerate() {
+                data[i_out + t * out_s] = y[s];
+            }
+        }
+
+        Ok(Tensor {
+            id: unique_id(),
+            data: Arc::new(data),
+            shape: inp.shape,
+            strides,
+            offset: 0,
+            device: self.clone(),
+            tape: Default::default(),
+        })
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let n = inp.shape.concrete()[op.axis];
+        let (inp_s, out_s) = (inp.strides[op.axis], out.strides[op.axis]);
+
+        let mut x = Vec::with_capacity(n);
+        let mut y = Vec::with_capacity(n);
+        let mut gy = Vec::with_capacity(n);
+        let mut gx = std::vec![E::zero(); n];
+        for [i_inp, i_out] in line_starts(op.axis, &inp.shape, [&inp.strides, &out.strides]) {
+            x.clear();
+            y.clear();
+            gy.clear();
+            for t in op.order(n) {
+                x.push(inp.data[inp.offset + i_inp + t * inp_s]);
+                y.push(out.data[out.offset + i_out + t * out_s]);
+                gy.push(grad_out[i_out + t * out_s]);
+            }
+            op.kind.backward(&x, &y, &gy, &mut gx);
+            for (s, t) in op.order(n).enumerate() {
+                grad_inp[i_inp + t * inp_s] += gx[s];
+            }
+        }
+        Ok(())
+    }
+}
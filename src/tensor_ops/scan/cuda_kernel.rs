@@ -0,0 +1,127 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::LaunchAsync;
+
+use super::{ScanKind, ScanOp};
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/scan.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &["scan_fwd_f16", "scan_bwd_f16"];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &["scan_fwd_f32", "scan_bwd_f32"];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &["scan_fwd_f64", "scan_bwd_f64"];
+}
+
+/// The kind ids used in scan.cu
+fn kind_id(kind: ScanKind) -> usize {
+    match kind {
+        ScanKind::Sum => 0,
+        ScanKind::Prod => 1,
+        ScanKind::Max => 2,
+        ScanKind::Min => 3,
+        ScanKind::LogSumExp => 4,
+    }
+}
+
+/// The dims of `shape` followed by `inp_strides` and `out_strides`, as used by scan.cu
+fn info<S: Shape>(shape: &S, inp_strides: S::Concrete, out_strides: S::Concrete) -> Vec<usize> {
+    let mut info = Vec::with_capacity(3 * S::NUM_DIMS);
+    info.extend(shape.concrete());
+    info.extend(inp_strides);
+    info.extend(out_strides);
+    info
+}
+
+/// The number of lines along the axis of `op`, each of which is scanned by one thread.
+fn num_lines<S: Shape>(op: ScanOp, shape: &S) -> usize {
+    let n = shape.concrete()[op.axis];
+    if n == 0 {
+        0
+    } else {
+        shape.num_elements() / n
+    }
+}
+
+impl<E: Dtype> super::ScanKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+
+        let strides = inp.shape.strides();
+        let num_lines = num_lines(op, &inp.shape);
+        let mut out = unsafe { self.alloc_empty::<E>(inp.shape.num_elements()) }?;
+        let info = self.dev.htod_copy(info(&inp.shape, inp.strides, strides))?;
+
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(num_lines as u32);
+        let params = (
+            kind_id(op.kind),
+            op.axis,
+            op.reverse as usize,
+            S::NUM_DIMS,
+            num_lines,
+            &info,
+            inp.data.as_ref(),
+            &mut out,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok(self.build_tensor(inp.shape, strides, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let num_lines = num_lines(op, &inp.shape);
+        let info = self
+            .dev
+            .htod_copy(info(&inp.shape, inp.strides, out.strides))?;
+        // logcumsumexp recomputes its output in f64 here
+        let scratch_len = match op.kind {
+            ScanKind::LogSumExp => inp.shape.num_elements(),
+            _ => 1,
+        };
+        let mut scratch = unsafe { self.alloc_empty::<f64>(scratch_len) }?;
+
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(num_lines as u32);
+        let params = (
+            kind_id(op.kind),
+            op.axis,
+            op.reverse as usize,
+            S::NUM_DIMS,
+            num_lines,
+            &info,
+            inp.data.as_ref(),
+            grad_inp,
+            out.data.as_ref(),
+            grad_out,
+            &mut scratch,
+        );
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
@@ -0,0 +1,433 @@
+use crate::{shapes::*, tensor::*};
+
+use num_traits::{Float, NumCast};
+use std::vec::Vec;
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+/// The reduction that a cumulative scan applies, see [Tensor::scan].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScanKind {
+    Sum,
+    Prod,
+    Max,
+    Min,
+    LogSumExp,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ScanOp {
+    pub(crate) axis: usize,
+    pub(crate) kind: ScanKind,
+    pub(crate) reverse: bool,
+}
+
+impl ScanOp {
+    /// The indices along the axis in the order they are scanned.
+    pub(crate) fn order(&self, n: usize) -> impl Iterator<Item = usize> {
+        let reverse = self.reverse;
+        (0..n).map(move |s| if reverse { n - 1 - s } else { s })
+    }
+}
+
+impl ScanKind {
+    /// Computes the inclusive scan of `x` into `y`.
+    pub(crate) fn forward<E: Float>(&self, x: &[E], y: &mut [E]) {
+        let mut acc = match x.first() {
+            Some(&x0) => x0,
+            None => return,
+        };
+        y[0] = acc;
+        for (j, &xj) in x.iter().enumerate().skip(1) {
+            acc = match self {
+                ScanKind::Sum => acc + xj,
+                ScanKind::Prod => acc * xj,
+                ScanKind::Max => acc.max(xj),
+                ScanKind::Min => acc.min(xj),
+                ScanKind::LogSumExp => {
+                    let m = acc.max(xj);
+                    if m == E::neg_infinity() {
+                        m
+                    } else {
+                        m + ((acc - m).exp() + (xj - m).exp()).ln()
+                    }
+                }
+            };
+            y[j] = acc;
+        }
+    }
+
+    /// Computes the gradient `gx` of the inclusive scan of `x`, where `y` is the result
+    /// of the scan and `gy` its gradient.
+    pub(crate) fn backward<E: Float>(&self, x: &[E], y: &[E], gy: &[E], gx: &mut [E]) {
+        let n = x.len();
+        match self {
+            ScanKind::Sum => {
+                let mut acc = E::zero();
+                for k in (0..n).rev() {
+                    acc = acc + gy[k];
+                    gx[k] = acc;
+                }
+            }
+            ScanKind::Prod => {
+                // d y[j] / d x[k] is y[k - 1] * x[k + 1] * ... * x[j], which avoids dividing
+                // by zeros in `x`. So gx[k] = y[k - 1] * r[k], where
+                // r[k] = sum_{j >= k} gy[j] * x[k + 1] * ... * x[j].
+                let mut r = E::zero();
+                for k in (0..n).rev() {
+                    r = if k + 1 < n {
+                        gy[k] + x[k + 1] * r
+                    } else {
+                        gy[k]
+                    };
+                    gx[k] = if k == 0 { r } else { y[k - 1] * r };
+                }
+            }
+            ScanKind::Max | ScanKind::Min => {
+                gx.iter_mut().for_each(|g| *g = E::zero());
+                let mut best = 0;
+                for j in 0..n {
+                    let replace = match self {
+                        ScanKind::Max => x[j] >= x[best],
+                        _ => x[j] <= x[best],
+                    };
+                    if replace {
+                        best = j;
+                    }
+                    gx[best] = gx[best] + gy[j];
+                }
+            }
+            ScanKind::LogSumExp => {
+                // y is recomputed in f64, because exp(y[k] - y[k + 1]) loses precision
+                // when y is large and rounded to E.
+                let x: Vec<f64> = x.iter().map(|x| x.to_f64().unwrap()).collect();
+                let mut y = std::vec![0.0; n];
+                self.forward(&x, &mut y);
+                // r[k] = sum_{j >= k} gy[j] * exp(y[k] - y[j]), where y is non-decreasing
+                let mut r = 0.0;
+                for k in (0..n).rev() {
+                    if y[k] == f64::NEG_INFINITY {
+                        // x[..=k] are all -inf, and don't affect y
+                        gx[k] = E::zero();
+                        continue;
+                    }
+                    let g = gy[k].to_f64().unwrap();
+                    r = if k + 1 < n {
+                        g + (y[k] - y[k + 1]).exp() * r
+                    } else {
+                        g
+                    };
+                    gx[k] = <E as NumCast>::from((x[k] - y[k]).exp() * r).unwrap();
+                }
+            }
+        }
+    }
+}
+
+pub trait ScanKernel<E: Dtype>: DeviceStorage {
+    fn forward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err>;
+
+    fn backward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+impl<S: Shape, E: Dtype, D: ScanKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Cumulative sum along `Ax`.
+    ///
+    /// **pytorch equivalent** `torch.cumsum`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let r = t.cumsum(Axis::<1>);
+    /// assert_eq!(r.array(), [[1.0, 3.0, 6.0], [4.0, 9.0, 15.0]]);
+    /// ```
+    pub fn cumsum<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Self
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Sum, false).unwrap()
+    }
+
+    /// Fallible version of [Tensor::cumsum]
+    pub fn try_cumsum<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Result<Self, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Sum, false)
+    }
+
+    /// Cumulative product along `Ax`.
+    ///
+    /// **pytorch equivalent** `torch.cumprod`.
+    pub fn cumprod<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Self
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Prod, false).unwrap()
+    }
+
+    /// Fallible version of [Tensor::cumprod]
+    pub fn try_cumprod<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Result<Self, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Prod, false)
+    }
+
+    /// Cumulative maximum along `Ax`. The gradient flows to the last occurrence
+    /// of each running maximum.
+    ///
+    /// **pytorch equivalent** `torch.cummax(...).values`.
+    pub fn cummax<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Self
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Max, false).unwrap()
+    }
+
+    /// Fallible version of [Tensor::cummax]
+    pub fn try_cummax<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Result<Self, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Max, false)
+    }
+
+    /// Cumulative minimum along `Ax`. The gradient flows to the last occurrence
+    /// of each running minimum.
+    ///
+    /// **pytorch equivalent** `torch.cummin(...).values`.
+    pub fn cummin<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Self
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Min, false).unwrap()
+    }
+
+    /// Fallible version of [Tensor::cummin]
+    pub fn try_cummin<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Result<Self, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::Min, false)
+    }
+
+    /// Numerically stable cumulative `ln(sum(exp(x)))` along `Ax`.
+    ///
+    /// **pytorch equivalent** `torch.logcumsumexp`.
+    pub fn logcumsumexp<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Self
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::LogSumExp, false).unwrap()
+    }
+
+    /// Fallible version of [Tensor::logcumsumexp]
+    pub fn try_logcumsumexp<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax) -> Result<Self, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, ScanKind::LogSumExp, false)
+    }
+
+    /// Inclusive cumulative scan along `Ax`. If `reverse` is true, the scan starts at
+    /// the last element of the axis, so the last element of the result is the last
+    /// element of the input.
+    ///
+    /// Discounted returns can be computed with a reversed sum:
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let rewards = dev.tensor([1.0, 1.0, 1.0]);
+    /// let r = rewards.scan(Axis::<0>, ScanKind::Sum, true);
+    /// assert_eq!(r.array(), [3.0, 2.0, 1.0]);
+    /// ```
+    pub fn scan<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax, kind: ScanKind, reverse: bool) -> Self
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_scan(ax, kind, reverse).unwrap()
+    }
+
+    /// Fallible version of [Tensor::scan]
+    pub fn try_scan<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        _: Ax,
+        kind: ScanKind,
+        reverse: bool,
+    ) -> Result<Self, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        let op = ScanOp {
+            axis: Ax::as_array()[0] as usize,
+            kind,
+            reverse,
+        };
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward(op, &inp)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        let out_clone = out.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            inp.device
+                .backward(op, &inp, grad_inp, &out_clone, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_cumsum_2d() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let w = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().cumsum(Axis::<0>);
+        assert_close_to_literal!(r, [[1.0, 2.0, 3.0], [5.0, 7.0, 9.0]]);
+        let g = (r * w.clone()).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[5.0, 7.0, 9.0], [4.0, 5.0, 6.0]]);
+
+        let r = t.leaky_trace().cumsum(Axis::<1>);
+        assert_close_to_literal!(r, [[1.0, 3.0, 6.0], [4.0, 9.0, 15.0]]);
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[6.0, 5.0, 3.0], [15.0, 11.0, 6.0]]);
+    }
+
+    #[test]
+    fn test_reverse_cumsum_discounted() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().scan(Axis::<1>, ScanKind::Sum, true);
+        assert_close_to_literal!(r, [[6.0, 5.0, 3.0], [15.0, 11.0, 6.0]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [[1.0, 2.0, 3.0]; 2]);
+    }
+
+    #[test]
+    fn test_cumsum_broadcasted() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([1.0, 2.0]).to_dtype::<TestDtype>();
+        let r = t
+            .leaky_trace()
+            .broadcast::<Rank2<3, 2>, _>()
+            .cumsum(Axis::<0>);
+        assert_close_to_literal!(r, [[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [6.0, 6.0]);
+    }
+
+    #[test]
+    fn test_cumprod_with_zero() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([2.0, 0.0, 3.0, 4.0]).to_dtype::<TestDtype>();
+        let r = t.leaky_trace().cumprod(Axis::<0>);
+        assert_close_to_literal!(r, [2.0, 0.0, 0.0, 0.0]);
+        let g = r.sum().backward();
+        // d/dx1 = x0 + x0*x2 + x0*x2*x3 = 2 + 6 + 24
+        assert_close_to_literal!(g.get(&t), [1.0, 32.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_cumprod_with_two_zeros() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([2.0, 0.0, 3.0, 0.0, 5.0])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().cumprod(Axis::<0>);
+        let g = r.sum().backward();
+        // only x1 changes y[1] and y[2], and nothing changes y[3] or y[4]
+        assert_close_to_literal!(g.get(&t), [1.0, 8.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_reverse_cumprod() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([2.0, 3.0, 4.0]).to_dtype::<TestDtype>();
+        let r = t.leaky_trace().scan(Axis::<0>, ScanKind::Prod, true);
+        assert_close_to_literal!(r, [24.0, 12.0, 4.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [12.0, 12.0, 10.0]);
+    }
+
+    #[test]
+    fn test_cummax_cummin() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([1.0, 3.0, 2.0, 3.0, 0.0])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().cummax(Axis::<0>);
+        assert_close_to_literal!(r, [1.0, 3.0, 3.0, 3.0, 3.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [1.0, 2.0, 0.0, 2.0, 0.0]);
+
+        let r = t.leaky_trace().cummin(Axis::<0>);
+        assert_close_to_literal!(r, [1.0, 1.0, 1.0, 1.0, 0.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [4.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_logcumsumexp() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let r = t.leaky_trace().logcumsumexp(Axis::<1>);
+        let r2 = t.leaky_trace().exp().cumsum(Axis::<1>).ln();
+        assert_close_to_tensor!(r, r2);
+
+        let g = r.exp().mean().backward();
+        let g2 = r2.exp().mean().backward();
+        assert_close_to_tensor!(g.get(&t), g2.get(&t));
+    }
+
+    #[test]
+    fn test_logcumsumexp_large_values() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([1000.0, 1000.0, -1000.0])
+            .to_dtype::<TestDtype>();
+        let r = t.leaky_trace().logcumsumexp(Axis::<0>);
+        assert_close_to_literal!(r, [1000.0, 1000.0 + 2f64.ln(), 1000.0 + 2f64.ln()]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [2.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_logcumsumexp_neg_inf() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([f32::NEG_INFINITY, f32::NEG_INFINITY, 0.0])
+            .to_dtype::<TestDtype>();
+        let g = t.leaky_trace().logcumsumexp(Axis::<0>).sum().backward();
+        // compared exactly, since NaNs pass assert_close
+        assert_eq!(g.get(&t).to_dtype::<f32>().array(), [0.0, 0.0, 1.0]);
+    }
+}
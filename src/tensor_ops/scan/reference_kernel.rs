@@ -0,0 +1,74 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        reference::{ravel_index, unravel_index},
+        Reference, Tensor,
+    },
+};
+
+use num_traits::Float;
+use std::vec::Vec;
+
+use super::ScanOp;
+
+/// Returns the indices of the elements of every line along the axis, in scan order.
+fn lines<S: Shape>(op: ScanOp, shape: &S) -> Vec<Vec<S::Concrete>> {
+    let n = shape.concrete()[op.axis];
+    (0..shape.num_elements())
+        .map(|i| unravel_index(shape, i))
+        .filter(|idx| idx[op.axis] == 0)
+        .map(|idx| {
+            op.order(n)
+                .map(|t| {
+                    let mut idx = idx;
+                    idx[op.axis] = t;
+                    idx
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl<E: Dtype + Float> super::ScanKernel<E> for Reference {
+    fn forward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let strides = inp.shape.strides();
+        let mut out = std::vec![E::zero(); inp.shape.num_elements()];
+        for line in lines(op, &inp.shape) {
+            let x: Vec<E> = line.iter().map(|&idx| inp[idx]).collect();
+            let mut y = std::vec![E::zero(); x.len()];
+            op.kind.forward(&x, &mut y);
+            for (idx, y) in line.iter().zip(y) {
+                out[ravel_index::<S>(&strides, idx)] = y;
+            }
+        }
+        Ok(self.build_tensor(inp.shape, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        op: ScanOp,
+        inp: &Tensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for line in lines(op, &inp.shape) {
+            let x: Vec<E> = line.iter().map(|&idx| inp[idx]).collect();
+            let y: Vec<E> = line.iter().map(|&idx| out[idx]).collect();
+            let gy: Vec<E> = line
+                .iter()
+                .map(|idx| grad_out[ravel_index::<S>(&out.strides, idx)])
+                .collect();
+            let mut gx = std::vec![E::zero(); x.len()];
+            op.kind.backward(&x, &y, &gy, &mut gx);
+            for (idx, gx) in line.iter().zip(gx) {
+                grad_inp[ravel_index::<S>(&inp.strides, idx)] += gx;
+            }
+        }
+        Ok(())
+    }
+}
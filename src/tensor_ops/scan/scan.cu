@@ -0,0 +1,193 @@
+#include "cuda_utils.cuh"
+
+#define SCAN_SUM 0
+#define SCAN_PROD 1
+#define SCAN_MAX 2
+#define SCAN_MIN 3
+#define SCAN_LOGSUMEXP 4
+
+// Computes the index of the first element of line `i` along `axis`.
+__device__ size_t get_line_start(
+    size_t i,
+    const size_t axis,
+    const size_t num_dims,
+    const size_t *dims,
+    const size_t *strides
+) {
+    size_t start = 0;
+    for (int d = num_dims - 1; d >= 0; d--) {
+        if (d == axis) {
+            continue;
+        }
+        start += (i % dims[d]) * strides[d];
+        i /= dims[d];
+    }
+    return start;
+}
+
+template<typename T>
+__device__ void scan_fwd(
+    const size_t kind,
+    const size_t axis,
+    const size_t reverse,
+    const size_t num_dims,
+    const size_t num_lines,
+    const size_t *info,
+    const T *inp,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= num_lines) {
+        return;
+    }
+
+    const size_t *dims = info;
+    const size_t *inp_strides = info + num_dims;
+    const size_t *out_strides = info + 2 * num_dims;
+    const size_t n = dims[axis];
+    const size_t inp_i = get_line_start(i, axis, num_dims, dims, inp_strides);
+    const size_t out_i = get_line_start(i, axis, num_dims, dims, out_strides);
+    const size_t inp_s = inp_strides[axis];
+    const size_t out_s = out_strides[axis];
+
+    T acc = 0.0;
+    for (size_t s = 0; s < n; s++) {
+        size_t t = reverse ? n - 1 - s : s;
+        T x = inp[inp_i + t * inp_s];
+        if (s == 0) {
+            acc = x;
+        } else if (kind == SCAN_SUM) {
+            acc = acc + x;
+        } else if (kind == SCAN_PROD) {
+            acc = acc * x;
+        } else if (kind == SCAN_MAX) {
+            acc = maxg(acc, x);
+        } else if (kind == SCAN_MIN) {
+            acc = ming(acc, x);
+        } else {
+            T m = maxg(acc, x);
+            if (static_cast<float>(m) != -INFINITY) {
+                acc = m + logg(expg(acc - m) + expg(x - m));
+            }
+        }
+        out[out_i + t * out_s] = acc;
+    }
+}
+
+template<typename T>
+__device__ void scan_bwd(
+    const size_t kind,
+    const size_t axis,
+    const size_t reverse,
+    const size_t num_dims,
+    const size_t num_lines,
+    const size_t *info,
+    const T *inp,
+    T *grad_inp,
+    const T *out,
+    const T *grad_out,
+    double *scratch
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= num_lines) {
+        return;
+    }
+
+    const size_t *dims = info;
+    const size_t *inp_strides = info + num_dims;
+    const size_t *out_strides = info + 2 * num_dims;
+    const size_t n = dims[axis];
+    const size_t inp_i = get_line_start(i, axis, num_dims, dims, inp_strides);
+    const size_t out_i = get_line_start(i, axis, num_dims, dims, out_strides);
+    const size_t inp_s = inp_strides[axis];
+    const size_t out_s = out_strides[axis];
+    #define X(s) inp[inp_i + (reverse ? n - 1 - (s) : (s)) * inp_s]
+    #define Y(s) out[out_i + (reverse ? n - 1 - (s) : (s)) * out_s]
+    #define GY(s) grad_out[out_i + (reverse ? n - 1 - (s) : (s)) * out_s]
+    #define GX(s) grad_inp + inp_i + (reverse ? n - 1 - (s) : (s)) * inp_s
+    #define SCRATCH(s) scratch[out_i + (reverse ? n - 1 - (s) : (s)) * out_s]
+
+    T zero = 0.0;
+    if (kind == SCAN_SUM) {
+        T acc = zero;
+        for (size_t s = n; s-- > 0;) {
+            acc = acc + GY(s);
+            atomicAdd(GX(s), acc);
+        }
+    } else if (kind == SCAN_PROD) {
+        // d y[j] / d x[k] is y[k - 1] * x[k + 1] * ... * x[j], see ScanKind::backward
+        T r = zero;
+        for (size_t k = n; k-- > 0;) {
+            r = k + 1 < n ? GY(k) + X(k + 1) * r : GY(k);
+            atomicAdd(GX(k), k == 0 ? r : Y(k - 1) * r);
+        }
+    } else if (kind == SCAN_MAX || kind == SCAN_MIN) {
+        size_t best = 0;
+        for (size_t j = 0; j < n; j++) {
+            if (kind == SCAN_MAX ? X(j) >= X(best) : X(j) <= X(best)) {
+                best = j;
+            }
+            atomicAdd(GX(best), GY(j));
+        }
+    } else {
+        // y is recomputed in double into `scratch`, see ScanKind::backward
+        double acc = 0.0;
+        for (size_t s = 0; s < n; s++) {
+            double x = static_cast<double>(X(s));
+            if (s == 0) {
+                acc = x;
+            } else {
+                double m = fmax(acc, x);
+                if (m != -INFINITY) {
+                    acc = m + log(exp(acc - m) + exp(x - m));
+                }
+            }
+            SCRATCH(s) = acc;
+        }
+        double r = 0.0;
+        for (size_t k = n; k-- > 0;) {
+            if (SCRATCH(k) == -INFINITY) {
+                continue;
+            }
+            double g = static_cast<double>(GY(k));
+            r = k + 1 < n ? g + exp(SCRATCH(k) - SCRATCH(k + 1)) * r : g;
+            T gx = exp(static_cast<double>(X(k)) - SCRATCH(k)) * r;
+            atomicAdd(GX(k), gx);
+        }
+    }
+
+    #undef X
+    #undef Y
+    #undef GY
+    #undef GX
+    #undef SCRATCH
+}
+
+#define SCAN(TY, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t kind, \
+    const size_t axis, \
+    const size_t reverse, \
+    const size_t num_dims, \
+    const size_t num_lines, \
+    const size_t *info, \
+    const TY *inp, \
+    TY *out \
+) { scan_fwd(kind, axis, reverse, num_dims, num_lines, info, inp, out); } \
+extern "C" __global__ void BWD( \
+    const size_t kind, \
+    const size_t axis, \
+    const size_t reverse, \
+    const size_t num_dims, \
+    const size_t num_lines, \
+    const size_t *info, \
+    const TY *inp, \
+    TY *grad_inp, \
+    const TY *out, \
+    const TY *grad_out, \
+    double *scratch \
+) { scan_bwd(kind, axis, reverse, num_dims, num_lines, info, inp, grad_inp, out, grad_out, scratch); }
+
+SCAN(__half, scan_fwd_f16, scan_bwd_f16);
+SCAN(float, scan_fwd_f32, scan_bwd_f32);
+SCAN(double, scan_fwd_f64, scan_bwd_f64);
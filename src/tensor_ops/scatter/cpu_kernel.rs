@@ -0,0 +1,99 @@
+use crate::shapes::{Dim, Dtype, Shape};
+use crate::tensor::{
+    cpu::{index_to_i, LendingIterator, NdIndex},
+    Cpu, GhostTensor, Tensor, ZerosTensor,
+};
+
+impl<E: Dtype> super::IndexSelectKernel<E> for Cpu {
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &Tensor<Src, E, Self>,
+        idx: &Tensor<(New,), usize, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let mut out = self.try_zeros_like(&dst)?;
+        let mut out_iter = out.iter_mut_with_index();
+        while let Some((x, mut i)) = out_iter.next() {
+            i[axis] = idx[[i[axis]]];
+            *x = inp[i];
+        }
+        Ok(out)
+    }
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<(New,), usize, Self>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut out_idx = NdIndex::new(out.shape, out.strides);
+        while let Some((i_out, mut i)) = out_idx.next_with_idx() {
+            i[axis] = idx[[i[axis]]];
+            grad_inp[index_to_i(&inp.shape, &inp.strides, i)] += grad_out[i_out];
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::ScatterKernel<E> for Cpu {
+    fn forward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: super::ScatterOp,
+        dst: &Tensor<Dst, E, Self>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &Tensor<Src, E, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let mut out = self.try_zeros_like(&dst.shape)?;
+        let mut out_iter = out.iter_mut_with_index();
+        while let Some((x, i)) = out_iter.next() {
+            *x = dst[i];
+        }
+
+        let mut src_iter = src.iter_with_index();
+        while let Some((x, mut i)) = src_iter.next() {
+            i[op.axis] = idx[i];
+            if op.accumulate {
+                out[i] += *x;
+            } else {
+                out[i] = *x;
+            }
+        }
+        Ok(out)
+    }
+
+    fn backward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: super::ScatterOp,
+        dst: &GhostTensor<Dst, E, Self>,
+        grad_dst: &mut Self::Vec<E>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &GhostTensor<Src, E, Self>,
+        grad_src: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        // the output of forward is contiguous
+        let out_strides = dst.shape.strides();
+
+        // overwritten entries don't flow back to dst
+        let mut grad_kept = grad_out.clone();
+        let mut src_idx = NdIndex::new(src.shape, src.strides);
+        while let Some((i_src, mut i)) = src_idx.next_with_idx() {
+            i[op.axis] = idx[i];
+            let i_out = index_to_i(&dst.shape, &out_strides, i);
+            grad_src[i_src] += grad_out[i_out];
+            if !op.accumulate {
+                grad_kept[i_out] = Default::default();
+            }
+        }
+
+        let mut dst_idx = NdIndex::new(dst.shape, dst.strides);
+        while let Some((i_dst, i)) = dst_idx.next_with_idx() {
+            grad_dst[i_dst] += grad_kept[index_to_i(&dst.shape, &out_strides, i)];
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    shapes::{Dim, Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::{DeviceRepr, DeviceSlice, LaunchAsync};
+
+unsafe impl DeviceRepr for super::ScatterOp {}
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/scatter.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &[
+        "index_select_fwd_f16",
+        "index_select_bwd_f16",
+        "scatter_copy_f16",
+        "scatter_fwd_f16",
+        "scatter_bwd_src_f16",
+        "scatter_bwd_dst_f16",
+    ];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &[
+        "index_select_fwd_f32",
+        "index_select_bwd_f32",
+        "scatter_copy_f32",
+        "scatter_fwd_f32",
+        "scatter_bwd_src_f32",
+        "scatter_bwd_dst_f32",
+    ];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &[
+        "index_select_fwd_f64",
+        "index_select_bwd_f64",
+        "scatter_copy_f64",
+        "scatter_fwd_f64",
+        "scatter_bwd_src_f64",
+        "scatter_bwd_dst_f64",
+    ];
+}
+
+impl Cuda {
+    fn load_scatter_fns<E>(&self) -> Result<(), CudaError>
+    where
+        Self: HasCudaKernel<E>,
+    {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::IndexSelectKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &Tensor<Src, E, Self>,
+        idx: &Tensor<(New,), usize, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        self.load_scatter_fns::<E>()?;
+
+        let numel = dst.num_elements();
+        let mut out = unsafe { self.alloc_empty::<E>(numel) }?;
+        let inp_dims = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+        let out_dims = self.dev.htod_copy(dst.concrete().into())?;
+
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            axis,
+            Src::NUM_DIMS,
+            numel,
+            &inp_dims,
+            &inp_strides,
+            &out_dims,
+            idx.data.as_ref(),
+            idx.strides[0],
+            inp.data.as_ref(),
+            &mut out,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok(self.build_tensor(dst, dst.strides(), out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<(New,), usize, Self>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let numel = out.shape.num_elements();
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+        let out_dims = self.dev.htod_copy(out.shape.concrete().into())?;
+        let out_strides = self.dev.htod_copy(out.strides.into())?;
+
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            axis,
+            Src::NUM_DIMS,
+            numel,
+            &inp_strides,
+            &out_dims,
+            &out_strides,
+            idx.data.as_ref(),
+            idx.strides[0],
+            grad_inp,
+            grad_out,
+        );
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::ScatterKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: super::ScatterOp,
+        dst: &Tensor<Dst, E, Self>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &Tensor<Src, E, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        self.load_scatter_fns::<E>()?;
+
+        let numel = dst.shape.num_elements();
+        let strides = dst.shape.strides();
+        let mut out = unsafe { self.alloc_empty::<E>(numel) }?;
+        let dims = self.dev.htod_copy(dst.shape.concrete().into())?;
+        let dst_strides = self.dev.htod_copy(dst.strides.into())?;
+
+        let copy = self.dev.get_func(Self::FNS[0], Self::FNS[2]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            Dst::NUM_DIMS,
+            numel,
+            &dims,
+            &dst_strides,
+            dst.data.as_ref(),
+            &mut out,
+        );
+        unsafe { copy.launch(cfg, params) }?;
+
+        let src_numel = src.shape.num_elements();
+        let src_dims = self.dev.htod_copy(src.shape.concrete().into())?;
+        let src_strides = self.dev.htod_copy(src.strides.into())?;
+        let idx_strides = self.dev.htod_copy(idx.strides.into())?;
+        let out_strides = self.dev.htod_copy(strides.into())?;
+
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[3]).unwrap();
+        let cfg = launch_cfg::<128>(src_numel as u32);
+        let params = (
+            op,
+            Dst::NUM_DIMS,
+            src_numel,
+            &src_dims,
+            &src_strides,
+            &idx_strides,
+            &dims,
+            &out_strides,
+            idx.data.as_ref(),
+            src.data.as_ref(),
+            &mut out,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok(self.build_tensor(dst.shape, strides, out))
+    }
+
+    fn backward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: super::ScatterOp,
+        dst: &GhostTensor<Dst, E, Self>,
+        grad_dst: &mut Self::Vec<E>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &GhostTensor<Src, E, Self>,
+        grad_src: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut grad_kept = unsafe { self.alloc_empty::<E>(grad_out.len()) }?;
+        self.dev.dtod_copy(grad_out, &mut grad_kept)?;
+
+        let src_numel = src.shape.num_elements();
+        let src_dims = self.dev.htod_copy(src.shape.concrete().into())?;
+        let src_strides = self.dev.htod_copy(src.strides.into())?;
+        let idx_strides = self.dev.htod_copy(idx.strides.into())?;
+        // the output of forward is contiguous
+        let out_strides = self.dev.htod_copy(dst.shape.strides().into())?;
+
+        let bwd_src = self.dev.get_func(Self::FNS[0], Self::FNS[4]).unwrap();
+        let cfg = launch_cfg::<128>(src_numel as u32);
+        let params = (
+            op,
+            Dst::NUM_DIMS,
+            src_numel,
+            &src_dims,
+            &src_strides,
+            &idx_strides,
+            &out_strides,
+            idx.data.as_ref(),
+            grad_src,
+            grad_out,
+            &mut grad_kept,
+        );
+        unsafe { bwd_src.launch(cfg, params) }?;
+
+        let numel = dst.shape.num_elements();
+        let dims = self.dev.htod_copy(dst.shape.concrete().into())?;
+        let dst_strides = self.dev.htod_copy(dst.strides.into())?;
+
+        let bwd_dst = self.dev.get_func(Self::FNS[0], Self::FNS[5]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            Dst::NUM_DIMS,
+            numel,
+            &dims,
+            &dst_strides,
+            grad_dst,
+            &grad_kept,
+        );
+        unsafe { bwd_dst.launch(cfg, params) }?;
+        Ok(())
+    }
+}
@@ -0,0 +1,462 @@
+use crate::{shapes::*, tensor::*};
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct ScatterOp {
+    axis: usize,
+    accumulate: bool,
+}
+
+pub trait IndexSelectKernel<E: Dtype>: DeviceStorage {
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &Tensor<Src, E, Self>,
+        idx: &Tensor<(New,), usize, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>;
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<(New,), usize, Self>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+pub trait ScatterKernel<E: Dtype>: DeviceStorage {
+    /// Returns a contiguous copy of `dst`, with `src` written (or added) along `op.axis`
+    /// at the positions in `idx`.
+    fn forward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: ScatterOp,
+        dst: &Tensor<Dst, E, Self>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &Tensor<Src, E, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>;
+
+    /// `grad_out` is the gradient of the contiguous output of [ScatterKernel::forward].
+    #[allow(clippy::too_many_arguments)]
+    fn backward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: ScatterOp,
+        dst: &GhostTensor<Dst, E, Self>,
+        grad_dst: &mut Self::Vec<E>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &GhostTensor<Src, E, Self>,
+        grad_src: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+/// Selects the entries at `idx` along axis `Ax`, replacing that dimension with
+/// the length of `idx`. Equivalent to `torch.index_select`.
+///
+/// See [Tensor::index_select()] for an example.
+pub fn index_select<Ax, New: Dim, S, E: Dtype, D: IndexSelectKernel<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    ax: Ax,
+    idx: Tensor<(New,), usize, D>,
+) -> Tensor<S::Replaced, E, D, T>
+where
+    Ax: Axes<Array = [isize; 1]>,
+    S: ReplaceDimAlong<Ax, New>,
+{
+    t.index_select(ax, idx)
+}
+
+/// Adds `src` into a copy of `dst` along axis `Ax`, at the positions in `idx`.
+/// Equivalent to `torch.scatter_add`.
+///
+/// See [Tensor::scatter_add()] for an example.
+pub fn scatter_add<Ax, Dst, Src, E: Dtype, D: ScatterKernel<E>, T, R>(
+    dst: Tensor<Dst, E, D, T>,
+    ax: Ax,
+    idx: Tensor<Src, usize, D>,
+    src: Tensor<Src, E, D, R>,
+) -> Tensor<Dst, E, D, T>
+where
+    Ax: Axes<Array = [isize; 1]>,
+    Dst: Shape + HasAxes<Ax>,
+    Src: Shape<Concrete = Dst::Concrete> + HasAxes<Ax>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    dst.scatter_add(ax, idx, src)
+}
+
+/// Writes `src` into a copy of `dst` along axis `Ax`, at the positions in `idx`.
+/// Equivalent to `torch.scatter`.
+///
+/// See [Tensor::scatter()] for an example.
+pub fn scatter<Ax, Dst, Src, E: Dtype, D: ScatterKernel<E>, T, R>(
+    dst: Tensor<Dst, E, D, T>,
+    ax: Ax,
+    idx: Tensor<Src, usize, D>,
+    src: Tensor<Src, E, D, R>,
+) -> Tensor<Dst, E, D, T>
+where
+    Ax: Axes<Array = [isize; 1]>,
+    Dst: Shape + HasAxes<Ax>,
+    Src: Shape<Concrete = Dst::Concrete> + HasAxes<Ax>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    dst.scatter(ax, idx, src)
+}
+
+impl<S: Shape, E: Dtype, D: IndexSelectKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Selects the entries at the 1d `idx` along any axis.
+    ///
+    /// **Panics** if an index is out of bounds.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let r: Tensor<Rank2<2, 4>, f32, _> = t.index_select(Axis::<1>, dev.tensor([2, 0, 0, 1]));
+    /// assert_eq!(r.array(), [[3.0, 1.0, 1.0, 2.0], [6.0, 4.0, 4.0, 5.0]]);
+    /// ```
+    pub fn index_select<Ax: Axes<Array = [isize; 1]>, New: Dim>(
+        self,
+        ax: Ax,
+        idx: Tensor<(New,), usize, D>,
+    ) -> Tensor<S::Replaced, E, D, T>
+    where
+        S: ReplaceDimAlong<Ax, New>,
+    {
+        self.try_index_select(ax, idx).unwrap()
+    }
+
+    /// Fallible version of [Tensor::index_select]
+    pub fn try_index_select<Ax: Axes<Array = [isize; 1]>, New: Dim>(
+        self,
+        _: Ax,
+        idx: Tensor<(New,), usize, D>,
+    ) -> Result<Tensor<S::Replaced, E, D, T>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, New>,
+    {
+        let axis = Ax::as_array()[0] as usize;
+        let dst = self.shape.replace_along(idx.shape.0);
+        let (inp, mut tape) = self.split_tape();
+        let out = inp.device.forward(axis, &inp, &idx, dst)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            inp.device
+                .backward(axis, &inp_ghost, grad_inp, &idx, &out_ghost, grad_out)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+impl<Dst: Shape, E: Dtype, D: ScatterKernel<E>, T: Tape<E, D>> Tensor<Dst, E, D, T> {
+    /// Adds each entry of `src` into a copy of `self`, at the position given by
+    /// the matching entry of `idx` along axis `Ax`:
+    /// `out[.., idx[i, j, ..], ..] += src[.., i, j, ..]`.
+    ///
+    /// `idx` must have the same shape as `src`, and `src` must have the same shape as `self`
+    /// except along `Ax`. Duplicate indices are summed.
+    ///
+    /// **Panics** if an index is out of bounds.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let dst: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+    /// let src = dev.tensor([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+    /// let idx = dev.tensor([[0, 2, 2, 1], [1, 1, 0, 0]]);
+    /// let r = dst.scatter_add(Axis::<1>, idx, src);
+    /// assert_eq!(r.array(), [[1.0, 4.0, 5.0], [15.0, 11.0, 0.0]]);
+    /// ```
+    pub fn scatter_add<Ax, Src, R>(
+        self,
+        ax: Ax,
+        idx: Tensor<Src, usize, D>,
+        src: Tensor<Src, E, D, R>,
+    ) -> Self
+    where
+        Ax: Axes<Array = [isize; 1]>,
+        Dst: HasAxes<Ax>,
+        Src: Shape<Concrete = Dst::Concrete> + HasAxes<Ax>,
+        T: Merge<R>,
+        R: Tape<E, D>,
+    {
+        self.try_scatter_add(ax, idx, src).unwrap()
+    }
+
+    /// Fallible version of [Tensor::scatter_add]
+    pub fn try_scatter_add<Ax, Src, R>(
+        self,
+        _: Ax,
+        idx: Tensor<Src, usize, D>,
+        src: Tensor<Src, E, D, R>,
+    ) -> Result<Self, D::Err>
+    where
+        Ax: Axes<Array = [isize; 1]>,
+        Dst: HasAxes<Ax>,
+        Src: Shape<Concrete = Dst::Concrete> + HasAxes<Ax>,
+        T: Merge<R>,
+        R: Tape<E, D>,
+    {
+        let op = ScatterOp {
+            axis: Ax::as_array()[0] as usize,
+            accumulate: true,
+        };
+        self.try_scatter_op("scatter_add", op, idx, src)
+    }
+
+    /// Writes each entry of `src` into a copy of `self`, at the position given by
+    /// the matching entry of `idx` along axis `Ax`:
+    /// `out[.., idx[i, j, ..], ..] = src[.., i, j, ..]`.
+    ///
+    /// `idx` must have the same shape as `src`, and `src` must have the same shape as `self`
+    /// except along `Ax`. If an index appears more than once, which of the written values
+    /// ends up in the output is unspecified, and the gradients assume the indices are unique.
+    ///
+    /// **Panics** if an index is out of bounds.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let dst: Tensor<Rank1<4>, f32, _> = dev.zeros();
+    /// let r = dst.scatter(Axis::<0>, dev.tensor([3, 0]), dev.tensor([1.0, 2.0]));
+    /// assert_eq!(r.array(), [2.0, 0.0, 0.0, 1.0]);
+    /// ```
+    pub fn scatter<Ax, Src, R>(
+        self,
+        ax: Ax,
+        idx: Tensor<Src, usize, D>,
+        src: Tensor<Src, E, D, R>,
+    ) -> Self
+    where
+        Ax: Axes<Array = [isize; 1]>,
+        Dst: HasAxes<Ax>,
+        Src: Shape<Concrete = Dst::Concrete> + HasAxes<Ax>,
+        T: Merge<R>,
+        R: Tape<E, D>,
+    {
+        self.try_scatter(ax, idx, src).unwrap()
+    }
+
+    /// Fallible version of [Tensor::scatter]
+    pub fn try_scatter<Ax, Src, R>(
+        self,
+        _: Ax,
+        idx: Tensor<Src, usize, D>,
+        src: Tensor<Src, E, D, R>,
+    ) -> Result<Self, D::Err>
+    where
+        Ax: Axes<Array = [isize; 1]>,
+        Dst: HasAxes<Ax>,
+        Src: Shape<Concrete = Dst::Concrete> + HasAxes<Ax>,
+        T: Merge<R>,
+        R: Tape<E, D>,
+    {
+        let op = ScatterOp {
+            axis: Ax::as_array()[0] as usize,
+            accumulate: false,
+        };
+        self.try_scatter_op("scatter", op, idx, src)
+    }
+
+    fn try_scatter_op<Src: Shape<Concrete = Dst::Concrete>, R: Tape<E, D>>(
+        self,
+        name: &'static str,
+        op: ScatterOp,
+        idx: Tensor<Src, usize, D>,
+        src: Tensor<Src, E, D, R>,
+    ) -> Result<Self, D::Err>
+    where
+        T: Merge<R>,
+    {
+        if idx.shape.concrete() != src.shape.concrete() {
+            return Err(ShapeError::new(name, &idx.shape, &src.shape).into());
+        }
+        let dst_dims = self.shape.concrete();
+        let src_dims = src.shape.concrete();
+        if (0..Dst::NUM_DIMS).any(|i| i != op.axis && dst_dims[i] != src_dims[i]) {
+            return Err(ShapeError::new(name, &self.shape, &src.shape).into());
+        }
+
+        let (dst, tape) = self.split_tape();
+        let (src, rtape) = src.split_tape();
+        let mut tape = tape.merge(rtape);
+
+        let out = dst.device.forward(op, &dst, &idx, &src)?;
+
+        let dst_ghost = dst.ghost();
+        let src_ghost = src.ghost();
+        let out_ghost = out.ghost();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&dst_ghost)?;
+            grads.try_alloc_for(&src_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_dst, grad_src, grad_out) =
+                grads.muts_and_ref(&dst_ghost, &src_ghost, &out_ghost);
+            dst.device.backward(
+                op, &dst_ghost, grad_dst, &idx, &src_ghost, grad_src, grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_index_select_middle_axis() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 2>, TestDtype, _> = dev
+            .tensor([
+                [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]],
+                [[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]],
+            ])
+            .to_dtype::<TestDtype>();
+        let r: Tensor<Rank3<2, 4, 2>, TestDtype, _, _> = t
+            .leaky_trace()
+            .index_select(Axis::<1>, dev.tensor([2, 0, 2, 1]));
+        assert_close_to_literal!(
+            r,
+            [
+                [[5.0, 6.0], [1.0, 2.0], [5.0, 6.0], [3.0, 4.0]],
+                [[11.0, 12.0], [7.0, 8.0], [11.0, 12.0], [9.0, 10.0]],
+            ]
+        );
+        let g = r.sum().backward();
+        assert_close_to_literal!(
+            g.get(&t),
+            [
+                [[1.0, 1.0], [1.0, 1.0], [2.0, 2.0]],
+                [[1.0, 1.0], [1.0, 1.0], [2.0, 2.0]],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_select_usize_len() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 2>, TestDtype, _> = dev.sample_normal();
+        let idx: Tensor<(usize,), usize, _> = dev.tensor((vec![1, 1], (2,)));
+        let r = t.clone().index_select(Axis::<0>, idx);
+        assert_eq!(r.shape, (2, Const::<2>));
+        let t = t.array();
+        assert_eq!(r.as_vec(), [t[1], t[1]].concat());
+    }
+
+    #[test]
+    #[should_panic = "Index out of bounds"]
+    fn test_index_select_out_of_bounds() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let _ = t.index_select(Axis::<1>, dev.tensor([3]));
+    }
+
+    #[test]
+    fn test_scatter_add_duplicates() {
+        let dev: TestDevice = Default::default();
+        let dst: Tensor<Rank2<2, 3>, TestDtype, _> = dev
+            .tensor([[1.0, 1.0, 1.0], [2.0, 2.0, 2.0]])
+            .to_dtype::<TestDtype>();
+        let src: Tensor<Rank2<2, 4>, TestDtype, _> = dev
+            .tensor([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]])
+            .to_dtype::<TestDtype>();
+        let idx = dev.tensor([[0, 2, 2, 1], [1, 1, 0, 0]]);
+        let r = dst
+            .leaky_trace()
+            .scatter_add(Axis::<1>, idx, src.leaky_trace());
+        assert_close_to_literal!(r, [[2.0, 5.0, 6.0], [17.0, 13.0, 2.0]]);
+
+        let w = dev
+            .tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&dst), [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_close_to_literal!(g.get(&src), [[1.0, 3.0, 3.0, 2.0], [5.0, 5.0, 4.0, 4.0]]);
+    }
+
+    #[test]
+    fn test_scatter_add_is_index_select_adjoint() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let idx = dev.tensor([3, 0, 3]);
+        let g = x
+            .leaky_trace()
+            .index_select(Axis::<0>, idx.clone())
+            .sum()
+            .backward();
+        let ones: Tensor<Rank2<3, 3>, TestDtype, _> = dev.ones();
+        let scattered = dev.zeros::<Rank2<4, 3>>().scatter_add(
+            Axis::<0>,
+            idx.broadcast::<Rank2<3, 3>, Axis<1>>(),
+            ones,
+        );
+        assert_close_to_tensor!(g.get(&x), scattered);
+    }
+
+    #[test]
+    fn test_scatter() {
+        let dev: TestDevice = Default::default();
+        let dst: Tensor<Rank2<3, 2>, TestDtype, _> = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let src: Tensor<Rank2<1, 2>, TestDtype, _> =
+            dev.tensor([[-1.0, -2.0]]).to_dtype::<TestDtype>();
+        let r = dst
+            .leaky_trace()
+            .scatter(Axis::<0>, dev.tensor([[2, 0]]), src.leaky_trace());
+        assert_close_to_literal!(r, [[1.0, -2.0], [3.0, 4.0], [-1.0, 6.0]]);
+
+        let w = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let g = (r * w).sum().backward();
+        assert_close_to_literal!(g.get(&dst), [[1.0, 0.0], [3.0, 4.0], [0.0, 6.0]]);
+        assert_close_to_literal!(g.get(&src), [[5.0, 2.0]]);
+    }
+
+    #[test]
+    fn test_try_scatter_shape_error() {
+        let dev: TestDevice = Default::default();
+        let dst: Tensor<(usize, Const<3>), TestDtype, _> = dev.zeros_like(&(2, Const));
+        let src: Tensor<(usize, Const<3>), TestDtype, _> = dev.zeros_like(&(4, Const));
+        let idx: Tensor<(usize, Const<3>), usize, _> = dev.zeros_like(&(4, Const));
+        let err = dst
+            .clone()
+            .try_scatter_add(Axis::<1>, idx.clone(), src.clone())
+            .unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new(
+                "scatter_add",
+                &(2, Const::<3>),
+                &(4, Const::<3>)
+            ))
+        );
+
+        let bad_idx: Tensor<(usize, Const<3>), usize, _> = dev.zeros_like(&(3, Const));
+        let err = dst.try_scatter(Axis::<0>, bad_idx, src).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new(
+                "scatter",
+                &(3, Const::<3>),
+                &(4, Const::<3>)
+            ))
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::shapes::{Dim, Dtype, Shape};
+use crate::tensor::{
+    reference::{physical_index, ravel_index, unravel_index},
+    GhostTensor, Reference, Tensor,
+};
+use std::vec::Vec;
+
+impl<E: Dtype> super::IndexSelectKernel<E> for Reference {
+    fn forward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &Tensor<Src, E, Self>,
+        idx: &Tensor<(New,), usize, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let out = (0..dst.num_elements())
+            .map(|i| {
+                let mut i_inp = unravel_index(&dst, i);
+                i_inp[axis] = idx[[i_inp[axis]]];
+                inp[i_inp]
+            })
+            .collect();
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape<Concrete = Src::Concrete>, New: Dim>(
+        &self,
+        axis: usize,
+        inp: &GhostTensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<(New,), usize, Self>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for i in 0..out.shape.num_elements() {
+            let mut i_inp = unravel_index(&out.shape, i);
+            i_inp[axis] = idx[[i_inp[axis]]];
+            grad_inp[ravel_index::<Src>(&inp.strides, &i_inp)] +=
+                grad_out[physical_index(&out.shape, &out.strides, i)];
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::ScatterKernel<E> for Reference {
+    fn forward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: super::ScatterOp,
+        dst: &Tensor<Dst, E, Self>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &Tensor<Src, E, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let dims = dst.shape.concrete();
+        let out_strides = dst.shape.strides();
+        let mut out: Vec<E> = (0..dst.shape.num_elements())
+            .map(|i| dst[unravel_index(&dst.shape, i)])
+            .collect();
+        for i in 0..src.shape.num_elements() {
+            let i_src = unravel_index(&src.shape, i);
+            let mut i_out = i_src;
+            i_out[op.axis] = idx[i_src];
+            assert!(
+                i_out[op.axis] < dims[op.axis],
+                "Index out of bounds: index={i_out:?} shape={:?}",
+                dst.shape
+            );
+            let o = &mut out[ravel_index::<Dst>(&out_strides, &i_out)];
+            *o = if op.accumulate {
+                *o + src[i_src]
+            } else {
+                src[i_src]
+            };
+        }
+        Ok(self.build_tensor(dst.shape, out))
+    }
+
+    fn backward<Dst: Shape, Src: Shape<Concrete = Dst::Concrete>>(
+        &self,
+        op: super::ScatterOp,
+        dst: &GhostTensor<Dst, E, Self>,
+        grad_dst: &mut Self::Vec<E>,
+        idx: &Tensor<Src, usize, Self>,
+        src: &GhostTensor<Src, E, Self>,
+        grad_src: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let out_strides = dst.shape.strides();
+        let mut overwritten = std::vec![false; dst.shape.num_elements()];
+        for i in 0..src.shape.num_elements() {
+            let i_src = unravel_index(&src.shape, i);
+            let mut i_out = i_src;
+            i_out[op.axis] = idx[i_src];
+            let i_out = ravel_index::<Dst>(&out_strides, &i_out);
+            grad_src[physical_index(&src.shape, &src.strides, i)] += grad_out[i_out];
+            overwritten[i_out] = !op.accumulate;
+        }
+        for (i, &overwritten) in overwritten.iter().enumerate() {
+            if !overwritten {
+                grad_dst[physical_index(&dst.shape, &dst.strides, i)] += grad_out[i];
+            }
+        }
+        Ok(())
+    }
+}
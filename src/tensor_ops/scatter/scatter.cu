@@ -0,0 +1,249 @@
+#include "cuda_utils.cuh"
+
+struct ScatterOp {
+    size_t axis;
+    bool accumulate;
+};
+
+// Returns the offset of the `i`th (row major) element of `dims` into a buffer layed
+// out with `strides`, after replacing the index along `axis` with `new_idx`.
+__device__ size_t get_replaced_index(
+    size_t i,
+    const size_t axis,
+    const size_t new_idx,
+    const size_t num_dims,
+    const size_t *dims,
+    const size_t *strides
+) {
+    size_t out_i = 0;
+    for (int d = num_dims - 1; d >= 0; d--) {
+        size_t dim_i = i % dims[d];
+        out_i += (d == axis ? new_idx : dim_i) * strides[d];
+        i /= dims[d];
+    }
+    return out_i;
+}
+
+template<typename T>
+__device__ void index_select_fwd(
+    const size_t axis,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *inp_dims,
+    const size_t *inp_strides,
+    const size_t *out_dims,
+    const size_t *idx,
+    const size_t idx_stride,
+    const T *inp,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t elem_size = 1;
+    for (int d = num_dims - 1; d > axis; d--) {
+        elem_size *= out_dims[d];
+    }
+    size_t new_idx = idx[((i / elem_size) % out_dims[axis]) * idx_stride];
+    assert(new_idx < inp_dims[axis]);
+
+    out[i] = inp[get_replaced_index(i, axis, new_idx, num_dims, out_dims, inp_strides)];
+}
+
+template<typename T>
+__device__ void index_select_bwd(
+    const size_t axis,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *inp_strides,
+    const size_t *out_dims,
+    const size_t *out_strides,
+    const size_t *idx,
+    const size_t idx_stride,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t elem_size = 1;
+    for (int d = num_dims - 1; d > axis; d--) {
+        elem_size *= out_dims[d];
+    }
+    size_t new_idx = idx[((i / elem_size) % out_dims[axis]) * idx_stride];
+
+    size_t inp_i = get_replaced_index(i, axis, new_idx, num_dims, out_dims, inp_strides);
+    size_t out_i = get_strided_index(i, num_dims, out_dims, out_strides);
+    atomicAdd(grad_inp + inp_i, grad_out[out_i]);
+}
+
+template<typename T>
+__device__ void scatter_copy(
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *dims,
+    const size_t *dst_strides,
+    const T *dst,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    out[i] = dst[get_strided_index(i, num_dims, dims, dst_strides)];
+}
+
+template<typename T>
+__device__ void scatter_fwd(
+    const ScatterOp op,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *src_dims,
+    const size_t *src_strides,
+    const size_t *idx_strides,
+    const size_t *out_dims,
+    const size_t *out_strides,
+    const size_t *idx,
+    const T *src,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t new_idx = idx[get_strided_index(i, num_dims, src_dims, idx_strides)];
+    assert(new_idx < out_dims[op.axis]);
+
+    T item = src[get_strided_index(i, num_dims, src_dims, src_strides)];
+    size_t out_i = get_replaced_index(i, op.axis, new_idx, num_dims, src_dims, out_strides);
+    if (op.accumulate) {
+        atomicAdd(out + out_i, item);
+    } else {
+        out[out_i] = item;
+    }
+}
+
+template<typename T>
+__device__ void scatter_bwd_src(
+    const ScatterOp op,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *src_dims,
+    const size_t *src_strides,
+    const size_t *idx_strides,
+    const size_t *out_strides,
+    const size_t *idx,
+    T *grad_src,
+    const T *grad_out,
+    T *grad_kept
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t new_idx = idx[get_strided_index(i, num_dims, src_dims, idx_strides)];
+    size_t src_i = get_strided_index(i, num_dims, src_dims, src_strides);
+    size_t out_i = get_replaced_index(i, op.axis, new_idx, num_dims, src_dims, out_strides);
+    atomicAdd(grad_src + src_i, grad_out[out_i]);
+
+    // overwritten entries don't flow back to dst
+    if (!op.accumulate) {
+        grad_kept[out_i] = 0.0;
+    }
+}
+
+template<typename T>
+__device__ void scatter_bwd_dst(
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *dims,
+    const size_t *dst_strides,
+    T *grad_dst,
+    const T *grad_kept
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    atomicAdd(grad_dst + get_strided_index(i, num_dims, dims, dst_strides), grad_kept[i]);
+}
+
+#define SCATTER(TY, INDEX_SELECT_FWD, INDEX_SELECT_BWD, COPY, FWD, BWD_SRC, BWD_DST) \
+extern "C" __global__ void INDEX_SELECT_FWD( \
+    const size_t axis, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *inp_dims, \
+    const size_t *inp_strides, \
+    const size_t *out_dims, \
+    const size_t *idx, \
+    const size_t idx_stride, \
+    const TY *inp, \
+    TY *out \
+) { index_select_fwd(axis, num_dims, numel, inp_dims, inp_strides, out_dims, idx, idx_stride, inp, out); } \
+extern "C" __global__ void INDEX_SELECT_BWD( \
+    const size_t axis, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *inp_strides, \
+    const size_t *out_dims, \
+    const size_t *out_strides, \
+    const size_t *idx, \
+    const size_t idx_stride, \
+    TY *grad_inp, \
+    const TY *grad_out \
+) { index_select_bwd(axis, num_dims, numel, inp_strides, out_dims, out_strides, idx, idx_stride, grad_inp, grad_out); } \
+extern "C" __global__ void COPY( \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *dims, \
+    const size_t *dst_strides, \
+    const TY *dst, \
+    TY *out \
+) { scatter_copy(num_dims, numel, dims, dst_strides, dst, out); } \
+extern "C" __global__ void FWD( \
+    const ScatterOp op, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *src_dims, \
+    const size_t *src_strides, \
+    const size_t *idx_strides, \
+    const size_t *out_dims, \
+    const size_t *out_strides, \
+    const size_t *idx, \
+    const TY *src, \
+    TY *out \
+) { scatter_fwd(op, num_dims, numel, src_dims, src_strides, idx_strides, out_dims, out_strides, idx, src, out); } \
+extern "C" __global__ void BWD_SRC( \
+    const ScatterOp op, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *src_dims, \
+    const size_t *src_strides, \
+    const size_t *idx_strides, \
+    const size_t *out_strides, \
+    const size_t *idx, \
+    TY *grad_src, \
+    const TY *grad_out, \
+    TY *grad_kept \
+) { scatter_bwd_src(op, num_dims, numel, src_dims, src_strides, idx_strides, out_strides, idx, grad_src, grad_out, grad_kept); } \
+extern "C" __global__ void BWD_DST( \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *dims, \
+    const size_t *dst_strides, \
+    TY *grad_dst, \
+    const TY *grad_kept \
+) { scatter_bwd_dst(num_dims, numel, dims, dst_strides, grad_dst, grad_kept); }
+
+SCATTER(__half, index_select_fwd_f16, index_select_bwd_f16, scatter_copy_f16, scatter_fwd_f16, scatter_bwd_src_f16, scatter_bwd_dst_f16);
+SCATTER(float, index_select_fwd_f32, index_select_bwd_f32, scatter_copy_f32, scatter_fwd_f32, scatter_bwd_src_f32, scatter_bwd_dst_f32);
+SCATTER(double, index_select_fwd_f64, index_select_bwd_f64, scatter_copy_f64, scatter_fwd_f64, scatter_bwd_src_f64, scatter_bwd_dst_f64);
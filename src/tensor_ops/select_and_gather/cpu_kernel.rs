@@ -3,9 +3,22 @@
 use crate::shapes::{Axes, Dtype, RemoveDimTo, ReplaceDimTo, Shape};
 use crate::tensor::{
     cpu::{index_to_i, LendingIterator, NdIndex},
-    Cpu, Tensor, ZerosTensor,
+    unique_id, Cpu, Tensor, ZerosTensor,
 };
 
+/// The index into `inp`'s data (without its offset) of the first element
+/// selected by a 0d `idx` along the leading dimension.
+fn leading_select_start<Src: Shape, Idx: Shape, E: Dtype>(
+    inp: &Tensor<Src, E, Cpu>,
+    idx: &Tensor<Idx, usize, Cpu>,
+) -> usize {
+    let i = idx[Default::default()];
+    if i >= inp.shape.concrete()[0] {
+        panic!("Index out of bounds: index=[{i}] shape={:?}", inp.shape);
+    }
+    i * inp.strides[0]
+}
+
 impl<E: Dtype> super::ReplaceDimKernel<E> for Cpu {
     fn forward<Src: Shape, Dst: Shape, Idx: Shape>(
         &self,
@@ -93,6 +106,29 @@ impl<E: Dtype> super::RemoveDimKernel<E> for Cpu {
     {
         let ax = Src::Ax::as_array()[0] as usize;
 
+        if <Idx as Shape>::NUM_DIMS == 0 {
+            // selecting from the leading dimension is just a view into the same data
+            let dst: Dst = inp.shape.remove(idx.shape);
+            let start = leading_select_start(inp, idx);
+            let mut strides: Dst::Concrete = Default::default();
+            for j in 0..Dst::NUM_DIMS {
+                strides[j] = inp.strides[j + 1];
+            }
+            return Ok(Tensor {
+                id: unique_id(),
+                data: inp.data.clone(),
+                shape: dst,
+                strides,
+                offset: if dst.num_elements() == 0 {
+                    0
+                } else {
+                    inp.offset + start
+                },
+                device: self.clone(),
+                tape: Default::default(),
+            });
+        }
+
         let mut out = self.try_zeros_like(&inp.shape.remove(idx.shape))?;
         let mut out_iter = out.iter_mut_with_index();
         while let Some((x, i_replaced)) = out_iter.next() {
@@ -133,6 +169,20 @@ impl<E: Dtype> super::RemoveDimKernel<E> for Cpu {
         let ax = Src::Ax::as_array()[0] as usize;
 
         let mut out_idx = NdIndex::new(out.shape, out.strides);
+
+        if <Idx as Shape>::NUM_DIMS == 0 {
+            // grad_out has the same strides as the trailing dims of grad_inp
+            let start = leading_select_start(inp, idx);
+            while let Some((i_out, i_dst)) = out_idx.next_with_idx() {
+                // broadcasted dims share a single gradient, so only add it once
+                if (0..Dst::NUM_DIMS).any(|j| out.strides[j] == 0 && i_dst[j] != 0) {
+                    continue;
+                }
+                grad_inp[start + i_out] += grad_out[i_out];
+            }
+            return Ok(());
+        }
+
         while let Some((i_out, i_replaced)) = out_idx.next_with_idx() {
             let mut i_idx: <Idx as Shape>::Concrete = Default::default();
             let mut i_inp: Src::Concrete = Default::default();
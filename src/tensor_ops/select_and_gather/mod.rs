@@ -1,6 +1,7 @@
 #![allow(clippy::type_complexity)]
 
 mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -101,7 +102,7 @@ impl<Src: Shape, E: Dtype, D: RemoveDimKernel<E>, T: Tape<E, D>> SelectTo<D>
     where
         Self::Shape: RemoveDimTo<Dst, Idx>,
     {
-        self.shape().check(idx.shape());
+        self.shape().check(idx.shape())?;
         let (inp, mut tape) = self.split_tape();
         let out = inp.device.forward(&inp, &idx)?;
         let inp_ghost = inp.ghost();
@@ -173,7 +174,7 @@ impl<Src: Shape, E: Dtype, D: ReplaceDimKernel<E>, T: Tape<E, D>> GatherTo<D>
     where
         Self::Shape: ReplaceDimTo<Dst, Idx>,
     {
-        self.shape().check(idx.shape());
+        self.shape().check(idx.shape())?;
         let (inp, mut tape) = self.split_tape();
         let out = inp.device.forward(&inp, &idx)?;
 
@@ -197,7 +198,7 @@ mod tests {
     use crate::{tensor_ops::*, tests::*};
 
     #[test]
-    #[should_panic = "dimension 0 not the same"]
+    #[should_panic = "op: \"select\", lhs: DynShape([5, 3]), rhs: DynShape([7])"]
     fn test_select_wrong_index_shape_2d() {
         let dev: TestDevice = Default::default();
         let t: Tensor<_, TestDtype, _> = dev.sample_like(&(5, 3), rand_distr::StandardNormal);
@@ -206,7 +207,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "dimension 1 not the same"]
+    #[should_panic = "op: \"select\", lhs: DynShape([7, 5, 3]), rhs: DynShape([7, 4])"]
     fn test_select_wrong_index_shape_3d() {
         let dev: TestDevice = Default::default();
         let t: Tensor<_, TestDtype, _> = dev.sample_like(&(7, 5, 3), rand_distr::StandardNormal);
@@ -223,7 +224,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "dimension 0 not the same"]
+    #[should_panic = "op: \"gather\", lhs: DynShape([5, 3, 1]), rhs: DynShape([7, 4])"]
     fn test_gather_wrong_index_shape_3d1() {
         let dev: TestDevice = Default::default();
         let t: Tensor<_, TestDtype, _> = dev.sample_like(&(5, 3, 1), rand_distr::StandardNormal);
@@ -233,7 +234,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "dimension 1 not the same"]
+    #[should_panic = "op: \"gather\", lhs: DynShape([5, 3, 1]), rhs: DynShape([5, 4, 2])"]
     fn test_gather_wrong_index_shape_3d2() {
         let dev: TestDevice = Default::default();
         let t: Tensor<_, TestDtype, _> = dev.sample_like(&(5, 3, 1), rand_distr::StandardNormal);
@@ -273,6 +274,28 @@ mod tests {
         assert_eq!(g.get(&t).array(), expected);
     }
 
+    #[test]
+    fn test_select_leading_is_view() {
+        let dev: Cpu = Default::default();
+        let t: Tensor<Rank3<4, 2, 3>, f32, _> = dev.sample_normal();
+        let r = t.clone().select(dev.tensor(2));
+        assert!(std::sync::Arc::ptr_eq(&t.data, &r.data));
+        assert_eq!(r.array(), t.array()[2]);
+    }
+
+    #[test]
+    fn test_select_leading_broadcasted_backward() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let r = t
+            .leaky_trace()
+            .broadcast::<Rank2<4, 3>, _>()
+            .select(dev.tensor(1));
+        assert_eq!(r.array(), t.array());
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&t), [1.0; 3]);
+    }
+
     #[test]
     fn test_gather_1d_backward() {
         let dev: TestDevice = Default::default();
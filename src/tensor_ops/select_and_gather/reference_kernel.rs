@@ -0,0 +1,113 @@
+use crate::shapes::{Axes, Dtype, RemoveDimTo, ReplaceDimTo, Shape};
+use crate::tensor::{
+    reference::{physical_index, ravel_index, unravel_index},
+    Reference, Tensor,
+};
+
+/// Returns the index into `inp` that is read for the output index `i_out`. `offset`
+/// is the number of dimensions of `idx` that replace `ax`.
+fn inp_index<Src: Shape, Dst: Shape, Idx: Shape>(
+    ax: usize,
+    offset: usize,
+    i_out: &Dst::Concrete,
+    idx: &Tensor<Idx, usize, Reference>,
+) -> Src::Concrete {
+    let mut i_idx: Idx::Concrete = Default::default();
+    for j in 0..Idx::NUM_DIMS {
+        i_idx[j] = i_out[j];
+    }
+    let mut i_inp: Src::Concrete = Default::default();
+    for j in 0..Src::NUM_DIMS {
+        i_inp[j] = match j.cmp(&ax) {
+            std::cmp::Ordering::Less => i_out[j],
+            std::cmp::Ordering::Equal => idx[i_idx],
+            std::cmp::Ordering::Greater => i_out[j - 1 + offset],
+        };
+    }
+    i_inp
+}
+
+impl<E: Dtype> super::ReplaceDimKernel<E> for Reference {
+    fn forward<Src: Shape, Dst: Shape, Idx: Shape>(
+        &self,
+        inp: &Tensor<Src, E, Self>,
+        idx: &Tensor<Idx, usize, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>
+    where
+        Src: ReplaceDimTo<Dst, Idx>,
+    {
+        let ax = Src::Ax::as_array()[0] as usize;
+        assert!(Idx::NUM_DIMS >= ax);
+        let dst = inp.shape.replace(idx.shape);
+        let out = (0..dst.num_elements())
+            .map(|i| {
+                let i_out = unravel_index(&dst, i);
+                inp[inp_index::<Src, Dst, Idx>(ax, Idx::NUM_DIMS - ax, &i_out, idx)]
+            })
+            .collect();
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape, Idx: Shape>(
+        &self,
+        inp: &Tensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<Idx, usize, Self>,
+        out: &Tensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>
+    where
+        Src: ReplaceDimTo<Dst, Idx>,
+    {
+        let ax = Src::Ax::as_array()[0] as usize;
+        for i in 0..out.shape.num_elements() {
+            let i_out = unravel_index(&out.shape, i);
+            let i_inp = inp_index::<Src, Dst, Idx>(ax, Idx::NUM_DIMS - ax, &i_out, idx);
+            grad_inp[ravel_index::<Src>(&inp.strides, &i_inp)] +=
+                grad_out[physical_index(&out.shape, &out.strides, i)];
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::RemoveDimKernel<E> for Reference {
+    fn forward<Src: Shape, Dst: Shape, Idx: Shape>(
+        &self,
+        inp: &Tensor<Src, E, Self>,
+        idx: &Tensor<Idx, usize, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>
+    where
+        Src: RemoveDimTo<Dst, Idx>,
+    {
+        let ax = Src::Ax::as_array()[0] as usize;
+        let dst = inp.shape.remove(idx.shape);
+        let out = (0..dst.num_elements())
+            .map(|i| {
+                let i_out = unravel_index(&dst, i);
+                inp[inp_index::<Src, Dst, Idx>(ax, 0, &i_out, idx)]
+            })
+            .collect();
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape, Idx: Shape>(
+        &self,
+        inp: &Tensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<Idx, usize, Self>,
+        out: &Tensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>
+    where
+        Src: RemoveDimTo<Dst, Idx>,
+    {
+        let ax = Src::Ax::as_array()[0] as usize;
+        for i in 0..out.shape.num_elements() {
+            let i_out = unravel_index(&out.shape, i);
+            let i_inp = inp_index::<Src, Dst, Idx>(ax, 0, &i_out, idx);
+            grad_inp[ravel_index::<Src>(&inp.strides, &i_inp)] +=
+                grad_out[physical_index(&out.shape, &out.strides, i)];
+        }
+        Ok(())
+    }
+}
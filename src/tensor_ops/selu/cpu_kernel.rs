@@ -0,0 +1,27 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+const ALPHA: f64 = 1.6732632423543772;
+const SCALE: f64 = 1.0507009873554805;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::SeLUKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        let scale = F::from(SCALE).unwrap();
+        if x > F::zero() {
+            scale * x
+        } else {
+            scale * F::from(ALPHA).unwrap() * x.exp_m1()
+        }
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        let scale = F::from(SCALE).unwrap();
+        if x > F::zero() {
+            scale
+        } else {
+            scale * F::from(ALPHA).unwrap() * x.exp()
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use super::SeLUKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for SeLUKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/selu.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(SeLUKernelOp, half::f16, PTX, "selu_fwd_f16", "selu_bwd_f16");
+cuda_unary!(SeLUKernelOp, f32, PTX, "selu_fwd_f32", "selu_bwd_f32");
+cuda_unary!(SeLUKernelOp, f64, PTX, "selu_fwd_f64", "selu_bwd_f64");
@@ -0,0 +1,58 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SeLUKernelOp;
+
+/// [Scaled Exponential Linear Unit (SELU)](https://paperswithcode.com/method/selu).
+/// `scale * elu(t, alpha)` with the fixed constants `alpha = 1.6732632` and `scale = 1.0507010`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.selu();
+/// ```
+pub fn selu<S: Shape, E: Dtype, D: UnaryKernel<SeLUKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.selu()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<SeLUKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [selu]
+    pub fn selu(self) -> Self {
+        self.try_selu().unwrap()
+    }
+    /// See [selu]
+    pub fn try_selu(self) -> Result<Self, D::Err> {
+        try_unary_op(SeLUKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_selu() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().selu();
+        assert_close_to_literal!(r, [-1.52016647, -1.11133074, 0.0, 1.05070099, 2.10140197]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [0.04758657, 0.12935372, 0.35161987, 0.2101402, 0.2101402]
+        );
+    }
+}
@@ -0,0 +1,32 @@
+#include "unary_op_macros.cuh"
+
+struct SeLUKernelOp {};
+
+template<typename T>
+__device__ __forceinline__ T selu_fwd(T x) {
+    T zero = 0.0;
+    T one = 1.0;
+    T alpha = 1.6732632423543772;
+    T scale = 1.0507009873554805;
+    return x > zero ? scale * x : scale * alpha * (expg(x) - one);
+}
+
+template<typename T>
+__device__ __forceinline__ T selu_bwd(T x) {
+    T zero = 0.0;
+    T alpha = 1.6732632423543772;
+    T scale = 1.0507009873554805;
+    return x > zero ? scale : scale * alpha * expg(x);
+}
+
+UNARY_OP(__half, selu_fwd_f16, selu_bwd_f16, SeLUKernelOp,
+        selu_fwd(x),
+        selu_bwd(x))
+
+UNARY_OP(float, selu_fwd_f32, selu_bwd_f32, SeLUKernelOp,
+        selu_fwd(x),
+        selu_bwd(x))
+
+UNARY_OP(double, selu_fwd_f64, selu_bwd_f64, SeLUKernelOp,
+        selu_fwd(x),
+        selu_bwd(x))
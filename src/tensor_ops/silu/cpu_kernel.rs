@@ -0,0 +1,15 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::SiLUKernelOp {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        x / (F::one() + x.neg().exp())
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        let sig = F::one() / (F::one() + x.neg().exp());
+        sig * (F::one() + x * (F::one() - sig))
+    }
+}
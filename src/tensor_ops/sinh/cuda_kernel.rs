@@ -0,0 +1,11 @@
+use super::SinhKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for SinhKernelOp {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/sinh.ptx"));
+
+#[cfg(feature = "f16")]
+cuda_unary!(SinhKernelOp, half::f16, PTX, "sinh_fwd_f16", "sinh_bwd_f16");
+cuda_unary!(SinhKernelOp, f32, PTX, "sinh_fwd_f32", "sinh_bwd_f32");
+cuda_unary!(SinhKernelOp, f64, PTX, "sinh_fwd_f64", "sinh_bwd_f64");
@@ -0,0 +1,59 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SinhKernelOp;
+
+/// [Hyperbolic sine](https://en.wikipedia.org/wiki/Hyperbolic_functions). `(exp(t) - exp(-t)) / 2`
+///
+/// The derivative is `cosh(t)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.sinh();
+/// ```
+pub fn sinh<S: Shape, E: Dtype, D: UnaryKernel<SinhKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.sinh()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<SinhKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [sinh]
+    pub fn sinh(self) -> Self {
+        self.try_sinh().unwrap()
+    }
+    /// See [sinh]
+    pub fn try_sinh(self) -> Result<Self, D::Err> {
+        try_unary_op(SinhKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_sinh() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().sinh();
+        assert_close_to_literal!(r, [-3.6268604, -1.1752012, 0.0, 1.1752012, 3.6268604]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(
+            g.get(&x),
+            [0.75243914, 0.30861613, 0.2, 0.30861613, 0.75243914]
+        );
+    }
+}
@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+struct SinhKernelOp {};
+
+UNARY_OP(__half, sinh_fwd_f16, sinh_bwd_f16, SinhKernelOp,
+        sinhg(x),
+        coshg(x))
+
+UNARY_OP(float, sinh_fwd_f32, sinh_bwd_f32, SinhKernelOp,
+        sinhg(x),
+        coshg(x))
+
+UNARY_OP(double, sinh_fwd_f64, sinh_bwd_f64, SinhKernelOp,
+        sinhg(x),
+        coshg(x))
@@ -1,4 +1,4 @@
-use crate::prelude::cpu::{LendingIterator, NdIndex};
+use crate::prelude::cpu::NdIndex;
 
 use super::*;
 
@@ -9,20 +9,24 @@ impl<E: Unit> SliceKernel<E> for Cpu {
         slice: &Slice,
     ) -> Result<Tensor<Src::Sliced, E, Self>, Self::Err> {
         let dst = inp.shape.slice(slice).unwrap();
-        let mut out = self.try_zeros_like(&dst)?;
-
-        let mut inp_idx = NdIndex::new(dst, inp.strides);
-        let mut out_iter = out.iter_mut();
 
         let start_idx = NdIndex::new(inp.shape, inp.strides)
             .get_strided_index(inp.shape.first_idx_in_slice(slice));
-        let view = &inp.data[start_idx..];
-
-        while let Some((inp_i, o)) = inp_idx.next().zip(out_iter.next()) {
-            *o = view[inp_i];
-        }
 
-        Ok(out)
+        // the output is a view into the same data, so nothing is copied
+        Ok(Tensor {
+            id: unique_id(),
+            data: inp.data.clone(),
+            shape: dst,
+            strides: inp.strides,
+            offset: if dst.num_elements() == 0 {
+                0
+            } else {
+                inp.offset + start_idx
+            },
+            device: self.clone(),
+            tape: Default::default(),
+        })
     }
 
     fn backward<Src: Shape + SliceShape<Slice>, Slice>(
@@ -34,15 +38,15 @@ impl<E: Unit> SliceKernel<E> for Cpu {
     ) -> Result<(), Self::Err> {
         let dst = inp.shape.slice(slice).unwrap();
 
-        let mut inp_idx = NdIndex::new(dst, inp.strides);
-        let mut out_iter = grad_out.iter();
+        // grad_out has the same strides as grad_inp, just without the offset
+        let mut idx = NdIndex::new(dst, inp.strides);
 
         let start_idx = NdIndex::new(inp.shape, inp.strides)
             .get_strided_index(inp.shape.first_idx_in_slice(slice));
         let view = &mut grad_inp[start_idx..];
 
-        while let Some((inp_i, o)) = inp_idx.next().zip(out_iter.next()) {
-            view[inp_i] = *o;
+        while let Some(i) = idx.next() {
+            view[i] = grad_out[i];
         }
 
         Ok(())
@@ -3,6 +3,7 @@ use crate::{shapes::*, tensor::*};
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 pub trait SliceKernel<E: Unit>: DeviceStorage {
     fn forward<Src: Shape + SliceShape<Slice>, Slice>(
@@ -27,6 +28,9 @@ pub trait SliceKernel<E: Unit>: DeviceStorage {
 /// sliced dimensions are changed to be of type usize except those sliced with `..`
 /// ([std::ops::RangeFull]), whose types are not modified.
 ///
+/// On [Cpu] the result is a view that shares data with the original tensor, so
+/// slicing doesn't copy anything.
+///
 /// Example:
 /// ```rust
 /// # use dfdx::prelude::*;
@@ -185,4 +189,65 @@ mod tests {
             [[0.; 4], [0.; 4], [0., 0., 22., 24.], [0., 0., 30., 32.]]
         );
     }
+
+    #[test]
+    fn test_slice_is_view() {
+        let dev: Cpu = Default::default();
+        let a: Tensor<Rank2<4, 3>, f32, _> = dev.sample_normal();
+        let b = a.clone().slice((1..3, 1..));
+        assert!(std::sync::Arc::ptr_eq(&a.data, &b.data));
+        let a_arr = a.array();
+        assert_eq!(
+            b.as_vec(),
+            [a_arr[1][1], a_arr[1][2], a_arr[2][1], a_arr[2][2]]
+        );
+    }
+
+    #[test]
+    fn test_slice_views_backward() {
+        let dev = TestDevice::default();
+        let a = dev
+            .tensor([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]])
+            .to_dtype::<TestDtype>();
+        let top: Tensor<Rank2<1, 2>, _, _, _> = a.leaky_trace().slice((..1, 1..)).realize();
+        let bot: Tensor<Rank2<1, 2>, _, _, _> = a.leaky_trace().slice((2.., ..2)).realize();
+        let c = top * bot;
+        assert_close_to_literal!(c, [[14., 24.]]);
+        let g = c.sum().backward();
+        assert_close_to_literal!(g.get(&a), [[0., 7., 8.], [0.; 3], [2., 3., 0.]]);
+    }
+
+    #[test]
+    fn test_ops_on_slice_views() {
+        let dev = TestDevice::default();
+        let a: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let view = a.clone().slice((1..3, 1..));
+        let copy: Tensor<Rank2<2, 2>, TestDtype, _> =
+            dev.tensor_from_vec(view.as_vec(), (2, 2)).realize();
+        let view: Tensor<Rank2<2, 2>, TestDtype, _> = view.realize();
+        assert_eq!(
+            view.clone().sum::<Rank0, _>().array(),
+            copy.clone().sum::<Rank0, _>().array()
+        );
+        assert_eq!(
+            view.clone().max::<Rank1<2>, Axis<1>>().array(),
+            copy.clone().max::<Rank1<2>, Axis<1>>().array()
+        );
+        assert_eq!(view.clone().exp().array(), copy.clone().exp().array());
+        assert_eq!(
+            view.clone().permute::<Rank2<2, 2>, _>().array(),
+            copy.clone().permute::<Rank2<2, 2>, _>().array()
+        );
+        assert_eq!(
+            view.clone().matmul(copy.clone()).array(),
+            copy.clone().matmul(copy.clone()).array()
+        );
+        let rows: Tensor<Rank2<2, 3>, TestDtype, _> = a.clone().slice((2.., ..)).realize();
+        let row: Tensor<Rank1<3>, TestDtype, _> = rows.clone().select(dev.tensor(1));
+        assert_eq!(row.array(), a.array()[3]);
+        assert_eq!(
+            (rows.clone() + rows.clone()).array(),
+            (rows.clone() * TestDtype::from_f32(2.0).unwrap()).array()
+        );
+    }
 }
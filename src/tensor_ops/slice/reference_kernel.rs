@@ -0,0 +1,56 @@
+use crate::{
+    shapes::{Shape, SliceShape, Unit},
+    tensor::{
+        reference::{ravel_index, unravel_index},
+        Reference, Tensor,
+    },
+};
+
+/// Returns the index into the un-sliced `Src` of every element of the sliced tensor.
+fn sliced_indices<Src: Shape + SliceShape<Slice>, Slice>(
+    shape: &Src,
+    strides: &Src::Concrete,
+    slice: &Slice,
+) -> (Src::Sliced, std::vec::Vec<usize>) {
+    let dst = shape.slice(slice).unwrap();
+    let start = unravel_index(shape, shape.first_idx_in_slice(slice));
+    let indices = (0..dst.num_elements())
+        .map(|i| {
+            let mut idx = unravel_index(&dst, i);
+            for d in 0..Src::NUM_DIMS {
+                idx[d] += start[d];
+            }
+            ravel_index::<Src>(strides, &idx)
+        })
+        .collect();
+    (dst, indices)
+}
+
+impl<E: Unit> super::SliceKernel<E> for Reference {
+    fn forward<Src: Shape + SliceShape<Slice>, Slice>(
+        &self,
+        inp: &Tensor<Src, E, Self>,
+        slice: &Slice,
+    ) -> Result<Tensor<Src::Sliced, E, Self>, Self::Err> {
+        let (dst, indices) = sliced_indices(&inp.shape, &inp.strides, slice);
+        let out = indices
+            .into_iter()
+            .map(|i| inp.data[inp.offset + i])
+            .collect();
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<Src: Shape + SliceShape<Slice>, Slice>(
+        &self,
+        inp: &Tensor<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+        slice: &Slice,
+    ) -> Result<(), Self::Err> {
+        let (_, indices) = sliced_indices(&inp.shape, &inp.strides, slice);
+        for (i, go) in indices.into_iter().zip(grad_out.iter()) {
+            grad_inp[i] = *go;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+
+impl<F: num_traits::Float> UnaryDerivative<F> for super::SoftplusKernelOp<F> {
+    const DF_USES_FX: bool = false;
+    const HAS_CONST_DF: bool = false;
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        let bx = self.beta * x;
+        if bx > self.threshold {
+            x
+        } else {
+            bx.exp().ln_1p() / self.beta
+        }
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        let bx = self.beta * x;
+        if bx > self.threshold {
+            F::one()
+        } else {
+            F::one() / (F::one() + bx.neg().exp())
+        }
+    }
+}
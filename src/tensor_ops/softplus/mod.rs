@@ -0,0 +1,82 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SoftplusKernelOp<E> {
+    pub beta: E,
+    pub threshold: E,
+}
+
+/// [Softplus](https://paperswithcode.com/method/softplus). `ln(1 + exp(beta * t)) / beta`
+///
+/// For numerical stability this is `t` wherever `beta * t > threshold`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 30.0]);
+/// let r = t.softplus(1.0, 20.0);
+/// assert_eq!(r.array()[3], 30.0);
+/// ```
+pub fn softplus<S: Shape, E: Dtype, D: UnaryKernel<SoftplusKernelOp<E>, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    beta: impl Into<f64>,
+    threshold: impl Into<f64>,
+) -> Tensor<S, E, D, T> {
+    t.softplus(beta, threshold)
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<SoftplusKernelOp<E>, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [softplus]
+    pub fn softplus(self, beta: impl Into<f64>, threshold: impl Into<f64>) -> Self {
+        self.try_softplus(beta, threshold).unwrap()
+    }
+    /// See [softplus]
+    pub fn try_softplus(
+        self,
+        beta: impl Into<f64>,
+        threshold: impl Into<f64>,
+    ) -> Result<Self, D::Err> {
+        try_unary_op(
+            SoftplusKernelOp {
+                beta: E::from_f64(beta.into()).unwrap(),
+                threshold: E::from_f64(threshold.into()).unwrap(),
+            },
+            self,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_softplus() {
+        let dev: TestDevice = Default::default();
+        let x = dev
+            .tensor([-2.0, -1.0, 0.0, 1.0, 2.0])
+            .to_dtype::<TestDtype>();
+        let r = x.leaky_trace().softplus(2.0, 3.0);
+        assert_close_to_literal!(r, [0.00907496, 0.06346401, 0.34657359, 1.06346401, 2.0]);
+        let g = r.mean().backward();
+        assert_close_to_literal!(g.get(&x), [0.00359724, 0.02384058, 0.1, 0.17615942, 0.2]);
+    }
+
+    #[test]
+    fn test_softplus_large_values() {
+        let dev: TestDevice = Default::default();
+        let x = dev.tensor([100.0, 1000.0]).to_dtype::<TestDtype>();
+        let r = x.leaky_trace().softplus(1.0, 20.0);
+        assert_close_to_literal!(r, [100.0, 1000.0]);
+        let g = r.sum().backward();
+        assert_close_to_literal!(g.get(&x), [1.0, 1.0]);
+    }
+}
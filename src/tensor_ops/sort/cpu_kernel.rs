@@ -0,0 +1,130 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{cpu::NdIndex, unique_id, Cpu, GhostTensor, Tensor},
+};
+
+use core::cmp::Ordering;
+use num_traits::Float;
+use std::{sync::Arc, vec::Vec};
+
+use super::{SortOp, Sorted};
+
+/// Total order where NaNs compare greater than every other value.
+fn compare<E: Float>(a: &E, b: &E) -> Ordering {
+    a.partial_cmp(b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Returns the index of the first element of every line along `op.axis`, in `inp`
+/// (without offset) and in the contiguous output.
+fn line_starts<S: Shape>(op: SortOp, shape: &S, strides: &S::Concrete) -> Vec<[usize; 2]> {
+    let out_dims = op.out_dims(shape);
+    let mut out_strides = out_dims;
+    let mut stride = 1;
+    for d in (0..S::NUM_DIMS).rev() {
+        out_strides[d] = stride;
+        stride *= out_dims[d];
+    }
+
+    let mut starts = Vec::new();
+    let mut idx = NdIndex::new(*shape, *strides);
+    while let Some((i, idx)) = idx.next_with_idx() {
+        if idx[op.axis] == 0 {
+            let i_out = idx.into_iter().zip(out_strides).map(|(i, s)| i * s).sum();
+            starts.push([i, i_out]);
+        }
+    }
+    starts
+}
+
+/// The stride of `op.axis` in the contiguous output.
+fn out_stride<S: Shape>(op: SortOp, shape: &S) -> usize {
+    op.out_dims(shape).into_iter().skip(op.axis + 1).product()
+}
+
+impl<E: Dtype + Float> super::SortKernel<E> for Cpu {
+    fn forward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Sorted<Dst, E, Self>, Self::Err> {
+        let numel = dst.num_elements();
+        let n = inp.shape.concrete()[op.axis];
+        let (inp_s, out_s) = (inp.strides[op.axis], out_stride(op, &inp.shape));
+        let mut values = self.try_alloc_zeros::<E>(numel)?;
+        let mut indices = self.try_alloc_zeros::<usize>(numel)?;
+
+        if numel > 0 {
+            let mut order: Vec<usize> = Vec::with_capacity(n);
+            for [i_inp, i_out] in line_starts(op, &inp.shape, &inp.strides) {
+                let x = |t: &usize| &inp.data[inp.offset + i_inp + t * inp_s];
+                let cmp = |a: &usize, b: &usize| {
+                    if op.descending {
+                        compare(x(b), x(a))
+                    } else {
+                        compare(x(a), x(b))
+                    }
+                };
+                order.clear();
+                if op.k == 1 {
+                    // only the first entry is needed, so skip the sort
+                    let first = (1..n).fold(0, |best, t| match cmp(&t, &best) {
+                        Ordering::Less => t,
+                        _ => best,
+                    });
+                    order.push(first);
+                } else {
+                    order.extend(0..n);
+                    order.sort_by(cmp);
+                }
+                for (r, &t) in order.iter().take(op.k).enumerate() {
+                    values[i_out + r * out_s] = *x(&t);
+                    indices[i_out + r * out_s] = t;
+                }
+            }
+        }
+
+        let strides = dst.strides();
+        let values = Tensor {
+            id: unique_id(),
+            data: Arc::new(values),
+            shape: dst,
+            strides,
+            offset: 0,
+            device: self.clone(),
+            tape: Default::default(),
+        };
+        let indices = Tensor {
+            id: unique_id(),
+            data: Arc::new(indices),
+            shape: dst,
+            strides,
+            offset: 0,
+            device: self.clone(),
+            tape: Default::default(),
+        };
+        Ok((values, indices))
+    }
+
+    fn backward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<Dst, usize, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        if idx.shape.num_elements() == 0 {
+            return Ok(());
+        }
+        let (inp_s, out_s) = (inp.strides[op.axis], out_stride(op, &inp.shape));
+        for [i_inp, i_out] in line_starts(op, &inp.shape, &inp.strides) {
+            for r in 0..op.k {
+                let t = idx.data[i_out + r * out_s];
+                grad_inp[i_inp + t * inp_s] += grad_out[i_out + r * out_s];
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::LaunchAsync;
+
+use super::{SortOp, Sorted};
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/sort.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &["sort_fwd_f16", "sort_bwd_f16"];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &["sort_fwd_f32", "sort_bwd_f32"];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &["sort_fwd_f64", "sort_bwd_f64"];
+}
+
+impl<E: Dtype> super::SortKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Sorted<Dst, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+
+        let numel = inp.shape.num_elements();
+        let mut values = unsafe { self.alloc_empty::<E>(dst.num_elements()) }?;
+        let mut indices = unsafe { self.alloc_empty::<usize>(dst.num_elements()) }?;
+        let dims = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            op.axis,
+            op.k,
+            op.descending as usize,
+            S::NUM_DIMS,
+            numel,
+            &dims,
+            &inp_strides,
+            inp.data.as_ref(),
+            &mut values,
+            &mut indices,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok((
+            self.build_tensor(dst, dst.strides(), values),
+            self.build_tensor(dst, dst.strides(), indices),
+        ))
+    }
+
+    fn backward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<Dst, usize, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let numel = idx.shape.num_elements();
+        let dims = self.dev.htod_copy(inp.shape.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            op.axis,
+            op.k,
+            S::NUM_DIMS,
+            numel,
+            &dims,
+            &inp_strides,
+            idx.data.as_ref(),
+            grad_inp,
+            grad_out,
+        );
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
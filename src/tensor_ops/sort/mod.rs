@@ -0,0 +1,378 @@
+use crate::{shapes::*, tensor::*};
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+/// Sorted values of shape `S`, and their indices along the sorted axis.
+type Sorted<S, E, D, T = NoneTape> = (Tensor<S, E, D, T>, Tensor<S, usize, D>);
+
+#[derive(Copy, Clone, Debug)]
+pub struct SortOp {
+    pub(crate) axis: usize,
+    /// How many of the sorted entries of every line are kept.
+    pub(crate) k: usize,
+    pub(crate) descending: bool,
+}
+
+impl SortOp {
+    /// The dimensions of the output, which are the dimensions of `shape` with
+    /// the axis replaced by `k`.
+    pub(crate) fn out_dims<S: Shape>(&self, shape: &S) -> S::Concrete {
+        let mut dims = shape.concrete();
+        dims[self.axis] = self.k;
+        dims
+    }
+}
+
+pub trait SortKernel<E: Dtype>: DeviceStorage {
+    /// Stable sorts every line of `inp` along `op.axis`, and returns the first `op.k`
+    /// values and their indices along the axis. The outputs are contiguous, with the
+    /// dimensions of `inp` where the axis has size `op.k`, and have the shape `dst`.
+    /// NaNs compare greater than every other value.
+    fn forward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Sorted<Dst, E, Self>, Self::Err>;
+
+    fn backward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<Dst, usize, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+impl<S: Shape, E: Dtype, D: SortKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Sorts the tensor along `Ax`, and returns the sorted values along with
+    /// the indices of the values along `Ax`. The sort is stable.
+    ///
+    /// **pytorch equivalent** `torch.sort(t, dim, descending, stable=True)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[3.0, 1.0, 2.0], [0.0, 5.0, 4.0]]);
+    /// let (values, idx) = t.sort(Axis::<1>, false);
+    /// assert_eq!(values.array(), [[1.0, 2.0, 3.0], [0.0, 4.0, 5.0]]);
+    /// assert_eq!(idx.array(), [[1, 2, 0], [0, 2, 1]]);
+    /// ```
+    pub fn sort<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax, descending: bool) -> Sorted<S, E, D, T>
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_sort(ax, descending).unwrap()
+    }
+
+    /// Fallible version of [Tensor::sort]
+    pub fn try_sort<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        _: Ax,
+        descending: bool,
+    ) -> Result<Sorted<S, E, D, T>, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        let axis = Ax::as_array()[0] as usize;
+        let op = SortOp {
+            axis,
+            k: self.shape.concrete()[axis],
+            descending,
+        };
+        let shape = self.shape;
+        self.try_sort_op(op, shape)
+    }
+
+    /// Returns the indices that sort the tensor along `Ax`.
+    ///
+    /// **pytorch equivalent** `torch.argsort(t, dim, descending, stable=True)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([3.0, 1.0, 2.0]);
+    /// assert_eq!(t.argsort(Axis::<0>, true).array(), [0, 2, 1]);
+    /// ```
+    pub fn argsort<Ax: Axes<Array = [isize; 1]>>(
+        &self,
+        ax: Ax,
+        descending: bool,
+    ) -> Tensor<S, usize, D>
+    where
+        S: HasAxes<Ax>,
+    {
+        self.try_argsort(ax, descending).unwrap()
+    }
+
+    /// Fallible version of [Tensor::argsort]
+    pub fn try_argsort<Ax: Axes<Array = [isize; 1]>>(
+        &self,
+        _: Ax,
+        descending: bool,
+    ) -> Result<Tensor<S, usize, D>, D::Err>
+    where
+        S: HasAxes<Ax>,
+    {
+        let axis = Ax::as_array()[0] as usize;
+        let op = SortOp {
+            axis,
+            k: self.shape.concrete()[axis],
+            descending,
+        };
+        let (_, idx) = self
+            .device
+            .forward(op, &self.retaped::<NoneTape>(), self.shape)?;
+        Ok(idx)
+    }
+
+    /// Returns the `K` largest values along `Ax` in descending order, along with their
+    /// indices along `Ax`. Ties are broken by the lower index.
+    ///
+    /// Returns a [ShapeError] if `Ax` has fewer than `K` elements.
+    ///
+    /// **pytorch equivalent** `torch.topk(t, K, dim)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[3.0, 1.0, 2.0], [0.0, 5.0, 4.0]]);
+    /// let (values, idx) = t.topk::<2, _>(Axis::<1>);
+    /// assert_eq!(values.array(), [[3.0, 2.0], [5.0, 4.0]]);
+    /// assert_eq!(idx.array(), [[0, 2], [1, 2]]);
+    /// ```
+    pub fn topk<const K: usize, Ax: Axes<Array = [isize; 1]>>(
+        self,
+        ax: Ax,
+    ) -> Sorted<S::Replaced, E, D, T>
+    where
+        S: ReplaceDimAlong<Ax, Const<K>>,
+    {
+        self.try_topk::<K, Ax>(ax).unwrap()
+    }
+
+    /// Fallible version of [Tensor::topk]
+    pub fn try_topk<const K: usize, Ax: Axes<Array = [isize; 1]>>(
+        self,
+        _: Ax,
+    ) -> Result<Sorted<S::Replaced, E, D, T>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, Const<K>>,
+    {
+        let dst = self.shape.replace_along(Const::<K>);
+        let axis = Ax::as_array()[0] as usize;
+        if self.shape.concrete()[axis] < K {
+            return Err(ShapeError::new("topk", &self.shape, &dst).into());
+        }
+        let op = SortOp {
+            axis,
+            k: K,
+            descending: true,
+        };
+        self.try_sort_op(op, dst)
+    }
+
+    /// Returns the index along `Ax` of the first maximum value. NaNs count as the
+    /// maximum.
+    ///
+    /// **pytorch equivalent** `torch.argmax(t, dim)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[3.0, 1.0, 3.0], [0.0, 5.0, 4.0]]);
+    /// let r = t.argmax::<Rank1<2>, _>(); // or `argmax::<_, Axis<1>>()`
+    /// assert_eq!(r.array(), [0, 1]);
+    /// ```
+    pub fn argmax<Dst: Shape, Ax: Axes<Array = [isize; 1]>>(&self) -> Tensor<Dst, usize, D>
+    where
+        S: ReduceShapeTo<Dst, Ax>,
+    {
+        self.try_argmax().unwrap()
+    }
+
+    /// Fallible version of [Tensor::argmax]. Returns a [ShapeError] if `Ax` is empty.
+    pub fn try_argmax<Dst: Shape, Ax: Axes<Array = [isize; 1]>>(
+        &self,
+    ) -> Result<Tensor<Dst, usize, D>, D::Err>
+    where
+        S: ReduceShapeTo<Dst, Ax>,
+    {
+        self.try_arg_reduce("argmax", true)
+    }
+
+    /// Returns the index along `Ax` of the first minimum value.
+    ///
+    /// **pytorch equivalent** `torch.argmin(t, dim)`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[3.0, 1.0, 1.0], [0.0, 5.0, 4.0]]);
+    /// let r = t.argmin::<Rank1<2>, _>();
+    /// assert_eq!(r.array(), [1, 0]);
+    /// ```
+    pub fn argmin<Dst: Shape, Ax: Axes<Array = [isize; 1]>>(&self) -> Tensor<Dst, usize, D>
+    where
+        S: ReduceShapeTo<Dst, Ax>,
+    {
+        self.try_argmin().unwrap()
+    }
+
+    /// Fallible version of [Tensor::argmin]. Returns a [ShapeError] if `Ax` is empty.
+    pub fn try_argmin<Dst: Shape, Ax: Axes<Array = [isize; 1]>>(
+        &self,
+    ) -> Result<Tensor<Dst, usize, D>, D::Err>
+    where
+        S: ReduceShapeTo<Dst, Ax>,
+    {
+        self.try_arg_reduce("argmin", false)
+    }
+
+    fn try_arg_reduce<Dst: Shape, Ax: Axes<Array = [isize; 1]>>(
+        &self,
+        name: &'static str,
+        descending: bool,
+    ) -> Result<Tensor<Dst, usize, D>, D::Err>
+    where
+        S: ReduceShapeTo<Dst, Ax>,
+    {
+        let dst: Dst = self.shape.reduced();
+        let axis = Ax::as_array()[0] as usize;
+        if self.shape.concrete()[axis] == 0 && dst.num_elements() > 0 {
+            return Err(ShapeError::new(name, &self.shape, &dst).into());
+        }
+        let op = SortOp {
+            axis,
+            k: 1,
+            descending,
+        };
+        let (_, idx) = self.device.forward(op, &self.retaped::<NoneTape>(), dst)?;
+        Ok(idx)
+    }
+
+    fn try_sort_op<Dst: Shape>(self, op: SortOp, dst: Dst) -> Result<Sorted<Dst, E, D, T>, D::Err> {
+        let (inp, mut tape) = self.split_tape();
+        let (out, idx) = inp.device.forward(op, &inp, dst)?;
+        let inp_ghost = inp.ghost();
+        let out_ghost = out.ghost();
+        let idx_clone = idx.clone();
+        tape.add_backward_op(move |grads| {
+            grads.try_alloc_for(&inp_ghost)?;
+            grads.try_alloc_for(&out_ghost)?;
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+            inp.device
+                .backward(op, &inp_ghost, grad_inp, &idx_clone, grad_out)
+        });
+        Ok((out.put_tape(tape), idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_sort_and_argsort() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[3.0, 1.0, 2.0, 1.0], [0.0, -1.0, 4.0, 4.0]])
+            .to_dtype::<TestDtype>();
+        assert_eq!(
+            t.argsort(Axis::<1>, false).array(),
+            [[1, 3, 2, 0], [1, 0, 2, 3]]
+        );
+        assert_eq!(
+            t.argsort(Axis::<1>, true).array(),
+            [[0, 2, 1, 3], [2, 3, 0, 1]]
+        );
+        assert_eq!(
+            t.argsort(Axis::<0>, false).array(),
+            [[1, 1, 0, 0], [0, 0, 1, 1]]
+        );
+
+        let w = dev
+            .tensor([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]])
+            .to_dtype::<TestDtype>();
+        let (values, idx) = t.leaky_trace().sort(Axis::<1>, false);
+        assert_close_to_literal!(values, [[1.0, 1.0, 2.0, 3.0], [-1.0, 0.0, 4.0, 4.0]]);
+        assert_eq!(idx.array(), [[1, 3, 2, 0], [1, 0, 2, 3]]);
+        let g = (values * w).sum().backward();
+        assert_close_to_literal!(g.get(&t), [[4.0, 1.0, 3.0, 2.0], [6.0, 5.0, 7.0, 8.0]]);
+    }
+
+    #[test]
+    fn test_sort_permuted_input() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[3.0, 1.0], [1.0, 2.0], [2.0, 0.0]])
+            .to_dtype::<TestDtype>();
+        let (values, idx) = t
+            .leaky_trace()
+            .permute::<Rank2<2, 3>, _>()
+            .sort(Axis::<1>, true);
+        assert_close_to_literal!(values, [[3.0, 2.0, 1.0], [2.0, 1.0, 0.0]]);
+        assert_eq!(idx.array(), [[0, 2, 1], [1, 0, 2]]);
+        let g = values.exp().sum().backward();
+        assert_close_to_tensor!(g.get(&t), t.exp());
+    }
+
+    #[test]
+    fn test_topk() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 5.0, 3.0, 5.0], [2.0, 0.0, 1.0, 4.0]])
+            .to_dtype::<TestDtype>();
+        let (values, idx) = t.leaky_trace().topk::<2, _>(Axis::<1>);
+        assert_close_to_literal!(values, [[5.0, 5.0], [4.0, 2.0]]);
+        assert_eq!(idx.array(), [[1, 3], [3, 0]]);
+        let g = values.square().sum().backward();
+        assert_close_to_literal!(g.get(&t), [[0.0, 10.0, 0.0, 10.0], [4.0, 0.0, 0.0, 8.0]]);
+    }
+
+    #[test]
+    fn test_topk_too_large() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<(usize, Const<3>), TestDtype, _> = dev.zeros_like(&(2, Const));
+        let err = t.try_topk::<4, _>(Axis::<1>).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("topk", &(2, 3), &(2, 4)))
+        );
+    }
+
+    #[test]
+    fn test_argmax_argmin() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 5.0, 3.0, 5.0], [2.0, 0.0, 0.0, 4.0]])
+            .to_dtype::<TestDtype>();
+        assert_eq!(t.argmax::<_, Axis<1>>().array(), [1, 3]);
+        assert_eq!(t.argmin::<_, Axis<1>>().array(), [0, 1]);
+        assert_eq!(t.argmax::<Rank1<4>, _>().array(), [1, 0, 0, 0]);
+        assert_eq!(t.argmin::<Rank1<4>, _>().array(), [0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_argmax_nan() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([1.0, f64::NAN, 3.0]).to_dtype::<TestDtype>();
+        assert_eq!(t.argmax::<Rank0, _>().array(), 1);
+        assert_eq!(t.argmin::<Rank0, _>().array(), 0);
+    }
+
+    #[test]
+    fn test_argmax_empty_axis() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<(Const<2>, usize), TestDtype, _> = dev.zeros_like(&(Const, 0));
+        let err = t.try_argmax::<Rank1<2>, _>().unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("argmax", &(2, 0), &(2,)))
+        );
+    }
+}
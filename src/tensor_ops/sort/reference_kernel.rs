@@ -0,0 +1,109 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        reference::{ravel_index, unravel_index},
+        GhostTensor, Reference, Tensor,
+    },
+};
+
+use num_traits::Float;
+use std::vec::Vec;
+
+use super::{SortOp, Sorted};
+
+/// Returns the indices into the input of the entries of every line along `op.axis`,
+/// in the order they are sorted.
+fn sorted_lines<S: Shape, E: Dtype + Float>(
+    op: SortOp,
+    inp: &Tensor<S, E, Reference>,
+) -> Vec<(S::Concrete, Vec<usize>)> {
+    let n = inp.shape.concrete()[op.axis];
+    (0..inp.shape.num_elements())
+        .map(|i| unravel_index(&inp.shape, i))
+        .filter(|idx| idx[op.axis] == 0)
+        .map(|start| {
+            let x = |t: usize| {
+                let mut idx = start;
+                idx[op.axis] = t;
+                inp[idx]
+            };
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|&a, &b| {
+                let (a, b) = if op.descending {
+                    (x(b), x(a))
+                } else {
+                    (x(a), x(b))
+                };
+                a.partial_cmp(&b)
+                    .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+            });
+            (start, order)
+        })
+        .collect()
+}
+
+/// The row major index into the output of `idx` (which is an index into the input)
+/// after replacing its position along the axis with `r`.
+fn out_index<S: Shape>(op: SortOp, shape: &S, mut idx: S::Concrete, r: usize) -> usize {
+    let dims = op.out_dims(shape);
+    idx[op.axis] = r;
+    let mut i = 0;
+    for d in 0..S::NUM_DIMS {
+        i = i * dims[d] + idx[d];
+    }
+    i
+}
+
+impl<E: Dtype + Float> super::SortKernel<E> for Reference {
+    fn forward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Sorted<Dst, E, Self>, Self::Err> {
+        let numel = dst.num_elements();
+        let mut values = std::vec![E::zero(); numel];
+        let mut indices = std::vec![0; numel];
+        if numel > 0 {
+            for (start, order) in sorted_lines(op, inp) {
+                for (r, &t) in order.iter().take(op.k).enumerate() {
+                    let mut idx = start;
+                    idx[op.axis] = t;
+                    let i_out = out_index(op, &inp.shape, start, r);
+                    values[i_out] = inp[idx];
+                    indices[i_out] = t;
+                }
+            }
+        }
+        Ok((
+            self.build_tensor(dst, values),
+            self.build_tensor(dst, indices),
+        ))
+    }
+
+    fn backward<S: Shape, Dst: Shape>(
+        &self,
+        op: SortOp,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        idx: &Tensor<Dst, usize, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        if idx.shape.num_elements() == 0 {
+            return Ok(());
+        }
+        for i in 0..inp.shape.num_elements() {
+            let start = unravel_index(&inp.shape, i);
+            if start[op.axis] != 0 {
+                continue;
+            }
+            for r in 0..op.k {
+                let i_out = out_index(op, &inp.shape, start, r);
+                let mut i_inp = start;
+                i_inp[op.axis] = idx.data[idx.offset + i_out];
+                grad_inp[ravel_index::<S>(&inp.strides, &i_inp)] += grad_out[i_out];
+            }
+        }
+        Ok(())
+    }
+}
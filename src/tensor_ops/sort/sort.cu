@@ -0,0 +1,133 @@
+#include "cuda_utils.cuh"
+
+// Whether `a` at position `i` comes before `b` at position `j` in a stable sort,
+// where NaNs compare greater than every other value.
+template<typename T>
+__device__ bool sorts_before(T a, size_t i, T b, size_t j, const size_t descending) {
+    bool a_nan = isnang(a);
+    bool b_nan = isnang(b);
+    bool less = (!a_nan && b_nan) || (!a_nan && !b_nan && a < b);
+    bool greater = (a_nan && !b_nan) || (!a_nan && !b_nan && a > b);
+    if (descending ? greater : less) {
+        return true;
+    }
+    return !less && !greater && i < j;
+}
+
+// Every thread computes the rank of one element of `inp` within its line along
+// `axis`, and writes it to the output if the rank is less than `k`.
+template<typename T>
+__device__ void sort_fwd(
+    const size_t axis,
+    const size_t k,
+    const size_t descending,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *dims,
+    const size_t *inp_strides,
+    const T *inp,
+    T *out,
+    size_t *out_idx
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    // decompose i into the position along the axis, and the line start in inp & out
+    size_t t = 0;
+    size_t inp_start = 0;
+    size_t out_start = 0;
+    size_t out_stride = 1;
+    size_t out_s = 0;
+    size_t rem = i;
+    for (int d = num_dims - 1; d >= 0; d--) {
+        size_t j = rem % dims[d];
+        rem /= dims[d];
+        if (d == axis) {
+            t = j;
+            out_s = out_stride;
+            out_stride *= k;
+        } else {
+            inp_start += j * inp_strides[d];
+            out_start += j * out_stride;
+            out_stride *= dims[d];
+        }
+    }
+
+    const size_t n = dims[axis];
+    const size_t inp_s = inp_strides[axis];
+    const T x = inp[inp_start + t * inp_s];
+    size_t rank = 0;
+    for (size_t u = 0; u < n; u++) {
+        if (sorts_before(inp[inp_start + u * inp_s], u, x, t, descending)) {
+            rank++;
+        }
+    }
+
+    if (rank < k) {
+        out[out_start + rank * out_s] = x;
+        out_idx[out_start + rank * out_s] = t;
+    }
+}
+
+// Every thread handles one element of the output.
+template<typename T>
+__device__ void sort_bwd(
+    const size_t axis,
+    const size_t k,
+    const size_t num_dims,
+    const size_t numel,
+    const size_t *dims,
+    const size_t *inp_strides,
+    const size_t *out_idx,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t inp_start = 0;
+    size_t rem = i;
+    for (int d = num_dims - 1; d >= 0; d--) {
+        size_t len = d == axis ? k : dims[d];
+        size_t j = rem % len;
+        rem /= len;
+        if (d != axis) {
+            inp_start += j * inp_strides[d];
+        }
+    }
+
+    atomicAdd(grad_inp + inp_start + out_idx[i] * inp_strides[axis], grad_out[i]);
+}
+
+#define SORT(TY, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t axis, \
+    const size_t k, \
+    const size_t descending, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *dims, \
+    const size_t *inp_strides, \
+    const TY *inp, \
+    TY *out, \
+    size_t *out_idx \
+) { sort_fwd(axis, k, descending, num_dims, numel, dims, inp_strides, inp, out, out_idx); } \
+extern "C" __global__ void BWD( \
+    const size_t axis, \
+    const size_t k, \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t *dims, \
+    const size_t *inp_strides, \
+    const size_t *out_idx, \
+    TY *grad_inp, \
+    const TY *grad_out \
+) { sort_bwd(axis, k, num_dims, numel, dims, inp_strides, out_idx, grad_inp, grad_out); }
+
+SORT(__half, sort_fwd_f16, sort_bwd_f16);
+SORT(float, sort_fwd_f32, sort_bwd_f32);
+SORT(double, sort_fwd_f64, sort_bwd_f64);
@@ -0,0 +1,51 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{cpu::NdIndex, unique_id, Cpu, GhostTensor, Tensor},
+};
+
+impl<E: Dtype> super::SplitAlongKernel<E> for Cpu {
+    fn forward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        // the output is a view into the same data, so nothing is copied
+        Ok(Tensor {
+            id: unique_id(),
+            data: inp.data.clone(),
+            shape: dst,
+            strides: inp.strides,
+            offset: if dst.num_elements() == 0 {
+                0
+            } else {
+                inp.offset + start * inp.strides[ax]
+            },
+            device: self.clone(),
+            tape: Default::default(),
+        })
+    }
+
+    fn backward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        if out.shape.num_elements() == 0 {
+            return Ok(());
+        }
+
+        // grad_out has the same strides as grad_inp, just without the offset
+        let view = &mut grad_inp[start * inp.strides[ax]..];
+        let mut idx = NdIndex::new(out.shape, out.strides);
+        while let Some(i) = idx.next() {
+            view[i] += grad_out[i];
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::LaunchAsync;
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/split_along.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &["split_along_fwd_f16", "split_along_bwd_f16"];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &["split_along_fwd_f32", "split_along_bwd_f32"];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &["split_along_fwd_f64", "split_along_bwd_f64"];
+}
+
+impl<E: Dtype> super::SplitAlongKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+
+        let numel = dst.num_elements();
+        let mut out = unsafe { self.alloc_empty::<E>(numel) }?;
+        let out_dims = self.dev.htod_copy(dst.concrete().into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            S::NUM_DIMS,
+            numel,
+            start * inp.strides[ax],
+            &out_dims,
+            &inp_strides,
+            inp.data.as_ref(),
+            &mut out,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok(self.build_tensor(dst, dst.strides(), out))
+    }
+
+    fn backward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let numel = out.shape.num_elements();
+        let out_dims = self.dev.htod_copy(out.shape.concrete().into())?;
+        let out_strides = self.dev.htod_copy(out.strides.into())?;
+        let inp_strides = self.dev.htod_copy(inp.strides.into())?;
+
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (
+            S::NUM_DIMS,
+            numel,
+            start * inp.strides[ax],
+            &out_dims,
+            &out_strides,
+            &inp_strides,
+            grad_inp,
+            grad_out,
+        );
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
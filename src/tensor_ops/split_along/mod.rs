@@ -0,0 +1,295 @@
+use crate::{shapes::*, tensor::*};
+use std::vec::Vec;
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+/// A piece of a tensor with shape `S` that is split along `Ax`.
+type Piece<S, Ax, E, D, T> = Tensor<<S as ReplaceDimAlong<Ax, usize>>::Replaced, E, D, T>;
+/// The pieces returned by [Tensor::split_along].
+type Pieces<S, Ax, E, D, T, const N: usize> = [Piece<S, Ax, E, D, T>; N];
+/// The pieces returned by [Tensor::chunk].
+type Chunks<S, Ax, E, D, T> = Vec<Piece<S, Ax, E, D, T>>;
+
+pub trait SplitAlongKernel<E: Dtype>: DeviceStorage {
+    /// Returns the entries of `inp` with shape `dst`, starting at index `start` along `ax`.
+    fn forward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>;
+
+    fn backward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+impl<S: Shape, E: Dtype, D: SplitAlongKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Splits the tensor along `Ax` into pieces of the given `sizes`, which have to add up
+    /// to the size of the axis. This is the inverse of [super::TryConcatAlong].
+    ///
+    /// On [Cpu] the pieces are views that share data with the original tensor.
+    ///
+    /// The tape of `self` is shared between the pieces with [Tape::share], so the gradient
+    /// reaches `self` through any of the pieces that backward is called on.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let [a, b] = t.split_along(Axis::<1>, [1, 2]);
+    /// assert_eq!(a.shape(), &(Const::<2>, 1));
+    /// assert_eq!(a.as_vec(), [1.0, 4.0]);
+    /// assert_eq!(b.as_vec(), [2.0, 3.0, 5.0, 6.0]);
+    /// ```
+    pub fn split_along<Ax: Axes<Array = [isize; 1]>, const N: usize>(
+        self,
+        ax: Ax,
+        sizes: [usize; N],
+    ) -> Pieces<S, Ax, E, D, T, N>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        self.try_split_along(ax, sizes).unwrap()
+    }
+
+    /// Fallible version of [Tensor::split_along]. Returns a [ShapeError] if `sizes` don't
+    /// add up to the size of the axis.
+    pub fn try_split_along<Ax: Axes<Array = [isize; 1]>, const N: usize>(
+        self,
+        _: Ax,
+        sizes: [usize; N],
+    ) -> Result<Pieces<S, Ax, E, D, T, N>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        let pieces = self.try_split_sizes::<Ax>("split_along", &sizes)?;
+        Ok(pieces.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    /// Splits the tensor along `Ax` into `n` pieces of equal size. If the size of the
+    /// axis isn't divisible by `n`, the last piece is smaller, and there might be
+    /// fewer than `n` pieces. Equivalent to `torch.chunk`.
+    ///
+    /// See [Tensor::split_along] for how the gradients of the pieces are tracked.
+    ///
+    /// **Panics** if `n` is 0, see [Tensor::try_chunk] for a fallible version.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t = dev.tensor([1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// let chunks = t.chunk(Axis::<0>, 2);
+    /// assert_eq!(chunks.len(), 2);
+    /// assert_eq!(chunks[0].as_vec(), [1.0, 2.0, 3.0]);
+    /// assert_eq!(chunks[1].as_vec(), [4.0, 5.0]);
+    /// ```
+    pub fn chunk<Ax: Axes<Array = [isize; 1]>>(self, ax: Ax, n: usize) -> Chunks<S, Ax, E, D, T>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        self.try_chunk(ax, n).unwrap()
+    }
+
+    /// Fallible version of [Tensor::chunk]. Returns a [ShapeError] if `n` is 0.
+    pub fn try_chunk<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        _: Ax,
+        n: usize,
+    ) -> Result<Chunks<S, Ax, E, D, T>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        if n == 0 {
+            return Err(ShapeError::new("chunk", &self.shape, &(n,)).into());
+        }
+        let len = self.shape.concrete()[Ax::as_array()[0] as usize];
+        let chunk_size = (len + n - 1) / n;
+        let mut sizes = Vec::new();
+        let mut remaining = len;
+        while remaining > 0 {
+            sizes.push(chunk_size.min(remaining));
+            remaining -= chunk_size.min(remaining);
+        }
+        self.try_split_sizes::<Ax>("chunk", &sizes)
+    }
+
+    fn try_split_sizes<Ax: Axes<Array = [isize; 1]>>(
+        self,
+        name: &'static str,
+        sizes: &[usize],
+    ) -> Result<Chunks<S, Ax, E, D, T>, D::Err>
+    where
+        S: ReplaceDimAlong<Ax, usize>,
+    {
+        let ax = Ax::as_array()[0] as usize;
+        let total: usize = sizes.iter().sum();
+        if total != self.shape.concrete()[ax] {
+            let dst = self.shape.replace_along(total);
+            return Err(ShapeError::new(name, &self.shape, &dst).into());
+        }
+
+        let (inp, tape) = self.split_tape();
+        let inp_ghost = inp.ghost();
+        let mut tapes = tape.share(sizes.len()).into_iter();
+        let mut pieces = Vec::with_capacity(sizes.len());
+        let mut start = 0;
+        for &size in sizes {
+            let out = inp
+                .device
+                .forward(ax, start, &inp, inp.shape.replace_along(size))?;
+            let mut piece_tape = tapes.next().unwrap();
+            let device = inp.device.clone();
+            let inp_ghost = inp_ghost.clone();
+            let out_ghost = out.ghost();
+            piece_tape.add_backward_op(move |grads| {
+                grads.try_alloc_for(&inp_ghost)?;
+                grads.try_alloc_for(&out_ghost)?;
+                let (grad_inp, grad_out) = grads.mut_and_ref(&inp_ghost, &out_ghost);
+                device.backward(ax, start, &inp_ghost, grad_inp, &out_ghost, grad_out)
+            });
+            pieces.push(out.put_tape(piece_tape));
+            start += size;
+        }
+        Ok(pieces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_split_along_middle_axis() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 5, 3>, TestDtype, _> = dev.sample_normal();
+        let [a, b, c] = t.clone().split_along(Axis::<1>, [2, 0, 3]);
+        assert_eq!(a.shape, (Const, 2, Const));
+        assert_eq!(b.shape, (Const, 0, Const));
+        assert_eq!(c.shape, (Const, 3, Const));
+
+        let r: Tensor<Rank3<2, 5, 3>, _, _> = ((a, b).concat_along(Axis::<1>), c)
+            .concat_along(Axis::<1>)
+            .realize();
+        assert_close_to_tensor!(r, t);
+    }
+
+    #[test]
+    fn test_split_along_glu_backward() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+
+        let [a, b] = t.leaky_trace().split_along(Axis::<1>, [2, 2]);
+        let r = a.realize::<Rank2<3, 2>>() * b.realize::<Rank2<3, 2>>().sigmoid();
+
+        let a2 = t.leaky_trace().slice((.., ..2)).realize::<Rank2<3, 2>>();
+        let b2 = t.leaky_trace().slice((.., 2..)).realize::<Rank2<3, 2>>();
+        let r2 = a2 * b2.sigmoid();
+        assert_close_to_tensor!(r, r2);
+
+        let g = r.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_close_to_tensor!(g.get(&t), g2.get(&t));
+    }
+
+    #[test]
+    fn test_split_along_first_piece_only() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let [a, _] = t.leaky_trace().split_along(Axis::<0>, [1, 2]);
+        let g = a.square().sum().backward();
+        assert_close_to_literal!(g.get(&t), [[2.0, 4.0], [0.0, 0.0], [0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_split_along_last_piece_only() {
+        let dev: TestDevice = Default::default();
+        let t = dev
+            .tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])
+            .to_dtype::<TestDtype>();
+        let [_, b] = (t.leaky_trace() * 2.0).split_along(Axis::<0>, [1, 2]);
+        let g = b.square().sum().backward();
+        assert_close_to_literal!(g.get(&t), [[0.0, 0.0], [24.0, 32.0], [40.0, 48.0]]);
+    }
+
+    #[test]
+    fn test_split_along_twice() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let [a, b] = t.leaky_trace().exp().split_along(Axis::<0>, [1, 3]);
+        let [_, c, d] = b.split_along(Axis::<0>, [1, 1, 1]);
+        let r = a.sum::<Rank0, _>() + c.sum() * 2.0 + d.sum() * 3.0;
+        let g = r.backward();
+
+        let scale = dev
+            .tensor([1.0, 0.0, 2.0, 3.0])
+            .to_dtype::<TestDtype>()
+            .broadcast::<Rank2<4, 3>, _>();
+        assert_close_to_tensor!(g.get(&t), t.exp() * scale);
+    }
+
+    #[test]
+    fn test_chunk_uneven() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<7, 2>, TestDtype, _> = dev.sample_normal();
+        let chunks = t.leaky_trace().chunk(Axis::<0>, 3);
+        let sizes: Vec<usize> = chunks.iter().map(|c| c.shape.0).collect();
+        assert_eq!(sizes, [3, 3, 1]);
+
+        let a = t.array();
+        assert_eq!(chunks[2].as_vec(), a[6]);
+
+        let mut chunks = chunks.into_iter();
+        let c0 = chunks.next().unwrap();
+        let c1 = chunks.next().unwrap();
+        let c2 = chunks.next().unwrap();
+        let r = c0.sum::<Rank0, _>() + (c1 * 2.0).sum() + (c2 * 3.0).sum();
+        let g = r.backward();
+        assert_close_to_literal!(
+            g.get(&t),
+            [
+                [1.0, 1.0],
+                [1.0, 1.0],
+                [1.0, 1.0],
+                [2.0, 2.0],
+                [2.0, 2.0],
+                [2.0, 2.0],
+                [3.0, 3.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_split_along_shape_error() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let err = t.try_split_along(Axis::<1>, [1, 1]).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("split_along", &(2, 3), &(2, 2)))
+        );
+    }
+
+    #[test]
+    fn test_try_chunk_zero() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let err = t.try_chunk(Axis::<1>, 0).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("chunk", &(Const::<2>, Const::<3>), &(0,)))
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        reference::{physical_index, ravel_index, unravel_index},
+        GhostTensor, Reference, Tensor,
+    },
+};
+
+/// Returns the index into the input of the `i`th (row major) entry of a piece
+/// that starts at `start` along `ax`.
+fn source_index<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+    ax: usize,
+    start: usize,
+    out: &Dst,
+    i: usize,
+) -> S::Concrete {
+    let mut idx = unravel_index(out, i);
+    idx[ax] += start;
+    idx
+}
+
+impl<E: Dtype> super::SplitAlongKernel<E> for Reference {
+    fn forward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &Tensor<S, E, Self>,
+        dst: Dst,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err> {
+        let out = (0..dst.num_elements())
+            .map(|i| inp[source_index::<S, Dst>(ax, start, &dst, i)])
+            .collect();
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<S: Shape, Dst: Shape<Concrete = S::Concrete>>(
+        &self,
+        ax: usize,
+        start: usize,
+        inp: &GhostTensor<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &GhostTensor<Dst, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for i in 0..out.shape.num_elements() {
+            let i_inp = source_index::<S, Dst>(ax, start, &out.shape, i);
+            grad_inp[ravel_index::<S>(&inp.strides, &i_inp)] +=
+                grad_out[physical_index(&out.shape, &out.strides, i)];
+        }
+        Ok(())
+    }
+}
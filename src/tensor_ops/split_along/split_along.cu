@@ -0,0 +1,65 @@
+#include "cuda_utils.cuh"
+
+template<typename T>
+__device__ void split_along_fwd(
+    const size_t num_dims,
+    const size_t numel,
+    const size_t start,
+    const size_t *out_dims,
+    const size_t *inp_strides,
+    const T *inp,
+    T *out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    out[i] = inp[start + get_strided_index(i, num_dims, out_dims, inp_strides)];
+}
+
+template<typename T>
+__device__ void split_along_bwd(
+    const size_t num_dims,
+    const size_t numel,
+    const size_t start,
+    const size_t *out_dims,
+    const size_t *out_strides,
+    const size_t *inp_strides,
+    T *grad_inp,
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t inp_i = start + get_strided_index(i, num_dims, out_dims, inp_strides);
+    size_t out_i = get_strided_index(i, num_dims, out_dims, out_strides);
+    atomicAdd(grad_inp + inp_i, grad_out[out_i]);
+}
+
+#define SPLIT_ALONG(TY, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t start, \
+    const size_t *out_dims, \
+    const size_t *inp_strides, \
+    const TY *inp, \
+    TY *out \
+) { split_along_fwd(num_dims, numel, start, out_dims, inp_strides, inp, out); } \
+extern "C" __global__ void BWD( \
+    const size_t num_dims, \
+    const size_t numel, \
+    const size_t start, \
+    const size_t *out_dims, \
+    const size_t *out_strides, \
+    const size_t *inp_strides, \
+    TY *grad_inp, \
+    const TY *grad_out \
+) { split_along_bwd(num_dims, numel, start, out_dims, out_strides, inp_strides, grad_inp, grad_out); }
+
+SPLIT_ALONG(__half, split_along_fwd_f16, split_along_bwd_f16);
+SPLIT_ALONG(float, split_along_fwd_f32, split_along_bwd_f32);
+SPLIT_ALONG(double, split_along_fwd_f64, split_along_bwd_f64);
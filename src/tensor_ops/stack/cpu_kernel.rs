@@ -24,19 +24,19 @@ impl<E: Dtype> super::StackKernel<E> for Cpu {
         let shape: S::Larger = inp[0].shape().add_dim(num);
 
         // build the new strides
+        let span = inp[0].span();
         let mut strides = shape.strides();
-        strides[0] = inp[0].data.len();
+        strides[0] = span;
         for d in 1..<S::Larger as Shape>::NUM_DIMS {
             strides[d] = item_strides[d - 1];
         }
 
         // copy the data
-        let mut data = self.try_alloc_elem(inp.len() * inp[0].data.len(), E::default())?;
+        let mut data = self.try_alloc_elem(inp.len() * span, E::default())?;
         let mut i = 0;
         for item in inp {
-            let buf: &[E] = item.data.as_ref();
-            data[i..i + buf.len()].copy_from_slice(buf);
-            i += buf.len();
+            data[i..i + span].copy_from_slice(&item.data[item.offset..item.offset + span]);
+            i += span;
         }
 
         Ok(Tensor {
@@ -44,6 +44,7 @@ impl<E: Dtype> super::StackKernel<E> for Cpu {
             data: std::sync::Arc::new(data),
             shape,
             strides,
+            offset: 0,
             device: self.clone(),
             tape: Default::default(),
         })
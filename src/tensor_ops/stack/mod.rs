@@ -5,6 +5,7 @@ use std::vec::Vec;
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 /// Stack an array or vec of tensors together along a new dimension.
 ///
@@ -149,7 +150,9 @@ where
     let device = tensors[0].device.clone();
     let shape = *tensors[0].shape();
     for t in tensors.iter() {
-        assert_eq!(t.shape(), &shape);
+        if t.shape() != &shape {
+            return Err(ShapeError::new("stack", &shape, t.shape()).into());
+        }
     }
 
     // we map to storage refs so kernels don't have to know about tensors
@@ -0,0 +1,62 @@
+use crate::{
+    shapes::*,
+    tensor::{unique_id, Reference, Tensor},
+};
+
+use std::vec::Vec;
+
+impl<E: Dtype> super::StackKernel<E> for Reference {
+    fn forward<S: Shape, Num: Dim>(
+        &self,
+        num: Num,
+        inp: &[Tensor<S, E, Self>],
+    ) -> Result<Tensor<S::Larger, E, Self>, Self::Err>
+    where
+        S: super::AddDim<Num>,
+    {
+        debug_assert_eq!(inp.len(), num.size());
+
+        // NOTE: backward only receives the gradient buffers, so the output
+        // has to keep the physical layout of each item.
+        let item_strides = inp[0].strides;
+        for i in inp.iter() {
+            assert_eq!(i.strides, item_strides);
+        }
+        let shape: S::Larger = inp[0].shape().add_dim(num);
+        let span = inp[0].span();
+        let mut strides = shape.strides();
+        strides[0] = span;
+        for d in 1..<S::Larger as Shape>::NUM_DIMS {
+            strides[d] = item_strides[d - 1];
+        }
+
+        let mut data = Vec::with_capacity(inp.len() * span);
+        for item in inp {
+            data.extend_from_slice(&item.data[item.offset..item.offset + span]);
+        }
+
+        Ok(Tensor {
+            id: unique_id(),
+            data: std::sync::Arc::new(data),
+            shape,
+            strides,
+            offset: 0,
+            device: self.clone(),
+            tape: Default::default(),
+        })
+    }
+    fn backward(
+        &self,
+        grad_inp: Vec<&mut Self::Vec<E>>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let mut offset = 0;
+        for item in grad_inp {
+            for i in 0..item.len() {
+                item[i] += grad_out[offset + i];
+            }
+            offset += item.len();
+        }
+        Ok(())
+    }
+}
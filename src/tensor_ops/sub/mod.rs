@@ -57,7 +57,7 @@ where
     LTape: Merge<R>,
 {
     fn try_sub(self, rhs: Tensor<S, E, D, R>) -> Result<Self, Self::Err> {
-        try_binary_op(BinarySubKernelOp, self, rhs)
+        try_binary_op("sub", BinarySubKernelOp, self, rhs)
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::{
     shapes::{Axes, Dtype, HasAxes, ReduceShapeTo, Shape},
-    tensor::{Cpu, Tensor, Tensorlike, ZerosTensor},
+    tensor::{is_dense, Cpu, Tensor, Tensorlike, ZerosTensor},
     tensor_ops::utilities::reduction_utils::index_for_reductions,
 };
 
@@ -14,9 +14,10 @@ impl<E: Dtype> super::SumKernel<E> for Cpu {
         Src: ReduceShapeTo<Dst, Ax>,
     {
         let mut out = self.try_zeros_like(&dst)?;
-        if Dst::NUM_DIMS == 0 {
+        let numel = inp.shape.num_elements();
+        if Dst::NUM_DIMS == 0 && numel > 0 && is_dense(&inp.shape, &inp.strides) {
             debug_assert_eq!(out.data.len(), 1);
-            let scale = E::from_usize(inp.shape.num_elements() / inp.data.len()).unwrap();
+            let scale = E::from_usize(numel / inp.span()).unwrap();
             let mut tmp: E = Default::default();
             for v in inp.buf_iter() {
                 tmp += *v;
@@ -24,7 +25,7 @@ impl<E: Dtype> super::SumKernel<E> for Cpu {
             std::sync::Arc::get_mut(&mut out.data).unwrap()[0] = tmp * scale;
         } else {
             let num_elems_reduced = <Src as HasAxes<Ax>>::size(&inp.shape);
-            let inp_buf = inp.data.as_ref();
+            let inp_buf = &inp.data[inp.offset..];
             let mut idx = index_for_reductions::<Src, Ax>(inp.shape, inp.strides);
             for o in out.buf_iter_mut() {
                 let mut tmp: E = Default::default();
@@ -46,10 +47,11 @@ impl<E: Dtype> super::SumKernel<E> for Cpu {
     where
         Src: ReduceShapeTo<Dst, Ax>,
     {
-        if Dst::NUM_DIMS == 0 {
+        let numel = inp.shape().num_elements();
+        if Dst::NUM_DIMS == 0 && numel > 0 && is_dense(inp.shape(), &inp.strides()) {
             debug_assert_eq!(grad_out.len(), 1);
             let v = grad_out[0];
-            let scale = E::from_usize(inp.shape().num_elements() / inp.len()).unwrap();
+            let scale = E::from_usize(numel / inp.len()).unwrap();
             for i in grad_inp.iter_mut() {
                 *i += v * scale;
             }
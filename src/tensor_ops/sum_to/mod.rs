@@ -1,4 +1,5 @@
 mod cpu_kernel;
+mod reference_kernel;
 
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -174,4 +175,14 @@ mod tests {
         let g = c.backward();
         assert_close_to_literal!(g.get(&a), [8.0; 3]);
     }
+
+    #[test]
+    fn test_sum_empty_to_0d() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<(usize, Const<3>), TestDtype, _> = dev.zeros_like(&(0, Const));
+        let r = a.leaky_trace().sum::<Rank0, _>();
+        assert_close_to_literal!(r, 0.0);
+        let g = r.backward();
+        assert_eq!(g.get(&a).as_vec().len(), 0);
+    }
 }
@@ -0,0 +1,47 @@
+use crate::{
+    shapes::{Axes, Dtype, ReduceShapeTo, Shape},
+    tensor::{
+        reference::{physical_index, ravel_index, reduce_index, unravel_index},
+        Reference, Tensor, Tensorlike,
+    },
+};
+
+impl<E: Dtype> super::SumKernel<E> for Reference {
+    fn forward<Src: Shape, Dst: Shape, Ax: Axes>(
+        &self,
+        dst: Dst,
+        inp: &Tensor<Src, E, Self>,
+    ) -> Result<Tensor<Dst, E, Self>, Self::Err>
+    where
+        Src: ReduceShapeTo<Dst, Ax>,
+    {
+        let mut out = self.try_alloc_elem(dst.num_elements(), E::default())?;
+        let dst_strides = dst.strides();
+        for i in 0..inp.shape.num_elements() {
+            let idx = unravel_index(&inp.shape, i);
+            let j = ravel_index::<Dst>(&dst_strides, &reduce_index::<Src, Dst, Ax>(&idx));
+            out[j] += inp.data[inp.offset + ravel_index::<Src>(&inp.strides, &idx)];
+        }
+        Ok(self.build_tensor(dst, out))
+    }
+
+    fn backward<Src: Shape, Dst: Shape, Ax: Axes>(
+        &self,
+        dst: Dst,
+        inp: &impl Tensorlike<Src, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>
+    where
+        Src: ReduceShapeTo<Dst, Ax>,
+    {
+        let shape = *inp.shape();
+        let dst_strides = dst.strides();
+        for i in 0..shape.num_elements() {
+            let idx = unravel_index(&shape, i);
+            let j = ravel_index::<Dst>(&dst_strides, &reduce_index::<Src, Dst, Ax>(&idx));
+            grad_inp[physical_index(&shape, &inp.strides(), i)] += grad_out[j];
+        }
+        Ok(())
+    }
+}
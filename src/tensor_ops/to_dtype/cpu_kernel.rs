@@ -5,8 +5,7 @@ use crate::prelude::{cpu::CachableVec, Cpu, Shape, Tensor, Unit};
 
 impl<E1: Unit + AsPrimitive<E2>, E2: Unit> super::ToDtypeKernel<E1, E2> for Cpu {
     fn forward<S: Shape>(inp: Tensor<S, E1, Self>) -> Result<Tensor<S, E2, Self>, Self::Err> {
-        let data: &[E1] = inp.data.as_ref();
-        let data: Vec<E2> = data.iter().map(|x| (*x).as_()).collect();
+        let data: Vec<E2> = inp.buf_iter().map(|x| (*x).as_()).collect();
         let data = CachableVec {
             data,
             cache: inp.device.cache.clone(),
@@ -17,6 +16,7 @@ impl<E1: Unit + AsPrimitive<E2>, E2: Unit> super::ToDtypeKernel<E1, E2> for Cpu
             data: Arc::new(data),
             shape: inp.shape,
             strides: inp.strides,
+            offset: 0,
             device: inp.device.clone(),
             tape: inp.tape,
         })
@@ -1,6 +1,7 @@
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
+mod reference_kernel;
 
 use crate::prelude::{DeviceStorage, Shape, Tensor, Unit};
 
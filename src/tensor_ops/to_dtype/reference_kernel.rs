@@ -0,0 +1,10 @@
+use num_traits::AsPrimitive;
+
+use crate::prelude::{Reference, Shape, Tensor, Unit};
+
+impl<E1: Unit + AsPrimitive<E2>, E2: Unit> super::ToDtypeKernel<E1, E2> for Reference {
+    fn forward<S: Shape>(inp: Tensor<S, E1, Self>) -> Result<Tensor<S, E2, Self>, Self::Err> {
+        let data = inp.as_vec().into_iter().map(|x| x.as_()).collect();
+        Ok(inp.device.build_tensor(inp.shape, data))
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        cpu::{LendingIterator, NdIndex},
+        Cpu, GhostTensor, Tensor, ZerosTensor,
+    },
+};
+
+use super::{img_strides, UnfoldOp};
+
+/// Returns the offset of the image entry that `cols_idx` reads from, or `None` if it
+/// is padding.
+fn img_offset<C: Shape>(
+    op: &UnfoldOp,
+    strides: &[usize; 4],
+    cols_idx: C::Concrete,
+) -> Option<usize> {
+    let [b, c, y, x] = op.img_index([cols_idx[0], cols_idx[1], cols_idx[2]])?;
+    Some(b * strides[0] + c * strides[1] + y * strides[2] + x * strides[3])
+}
+
+impl<E: Dtype> super::UnfoldKernel<E> for Cpu {
+    fn forward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &Tensor<I, E, Self>,
+        cols: C,
+    ) -> Result<Tensor<C, E, Self>, Self::Err> {
+        let strides = img_strides::<I>(&img.strides);
+        let mut out = self.try_zeros_like(&cols)?;
+        let mut out_iter = out.iter_mut_with_index();
+        while let Some((x, i)) = out_iter.next() {
+            if let Some(i_img) = img_offset::<C>(&op, &strides, i) {
+                *x = img.data[img.offset + i_img];
+            }
+        }
+        Ok(out)
+    }
+
+    fn backward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &mut Self::Vec<E>,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let strides = img_strides::<I>(&img.strides);
+        let mut cols_idx = NdIndex::new(cols.shape, cols.strides);
+        while let Some((i_cols, i)) = cols_idx.next_with_idx() {
+            if let Some(i_img) = img_offset::<C>(&op, &strides, i) {
+                grad_img[i_img] += grad_cols[i_cols];
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::FoldKernel<E> for Cpu {
+    fn forward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &Tensor<C, E, Self>,
+        img: I,
+    ) -> Result<Tensor<I, E, Self>, Self::Err> {
+        let strides = img_strides::<I>(&img.strides());
+        let mut out = self.try_zeros_like(&img)?;
+        let buf = std::sync::Arc::get_mut(&mut out.data).unwrap();
+        let mut cols_idx = NdIndex::new(cols.shape, cols.strides);
+        while let Some((i_cols, i)) = cols_idx.next_with_idx() {
+            if let Some(i_img) = img_offset::<C>(&op, &strides, i) {
+                buf[i_img] += cols.data[cols.offset + i_cols];
+            }
+        }
+        Ok(out)
+    }
+
+    fn backward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &mut Self::Vec<E>,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let strides = img_strides::<I>(&img.strides);
+        let mut cols_idx = NdIndex::new(cols.shape, cols.strides);
+        while let Some((i_cols, i)) = cols_idx.next_with_idx() {
+            if let Some(i_img) = img_offset::<C>(&op, &strides, i) {
+                grad_cols[i_cols] += grad_img[i_img];
+            }
+        }
+        Ok(())
+    }
+}
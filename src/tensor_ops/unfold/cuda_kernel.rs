@@ -0,0 +1,173 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::*,
+};
+
+use cudarc::driver::{CudaSlice, DeviceRepr, LaunchAsync};
+
+use super::{img_strides, UnfoldOp};
+
+unsafe impl DeviceRepr for UnfoldOp {}
+
+impl UnfoldOp {
+    /// The number of entries in the columns.
+    fn cols_numel(&self) -> usize {
+        self.batch * self.chan * self.kernel_h * self.kernel_w * self.h_out * self.w_out
+    }
+}
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/unfold.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+#[cfg(feature = "f16")]
+impl HasCudaKernel<half::f16> for Cuda {
+    const FNS: &'static [&'static str] = &["unfold_gather_f16", "unfold_scatter_f16"];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &["unfold_gather_f32", "unfold_scatter_f32"];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &["unfold_gather_f64", "unfold_scatter_f64"];
+}
+
+impl Cuda {
+    fn load_unfold<E: Dtype>(&self) -> Result<(), CudaError>
+    where
+        Self: HasCudaKernel<E>,
+    {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the image entries that each column entry reads from to `cols`.
+    fn unfold_gather<E: Dtype>(
+        &self,
+        op: UnfoldOp,
+        img_strides: [usize; 4],
+        cols_strides: [usize; 3],
+        img: &CudaSlice<E>,
+        cols: &mut CudaSlice<E>,
+    ) -> Result<(), CudaError>
+    where
+        Self: HasCudaKernel<E>,
+    {
+        self.load_unfold::<E>()?;
+        let numel = op.cols_numel();
+        let img_strides = self.dev.htod_copy(img_strides.into())?;
+        let cols_strides = self.dev.htod_copy(cols_strides.into())?;
+        let gather = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (op, numel, &img_strides, &cols_strides, img, cols);
+        unsafe { gather.launch(cfg, params) }?;
+        Ok(())
+    }
+
+    /// Adds each column entry to the image entry it reads from.
+    fn unfold_scatter<E: Dtype>(
+        &self,
+        op: UnfoldOp,
+        img_strides: [usize; 4],
+        cols_strides: [usize; 3],
+        img: &mut CudaSlice<E>,
+        cols: &CudaSlice<E>,
+    ) -> Result<(), CudaError>
+    where
+        Self: HasCudaKernel<E>,
+    {
+        self.load_unfold::<E>()?;
+        let numel = op.cols_numel();
+        let img_strides = self.dev.htod_copy(img_strides.into())?;
+        let cols_strides = self.dev.htod_copy(cols_strides.into())?;
+        let scatter = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg::<128>(numel as u32);
+        let params = (op, numel, &img_strides, &cols_strides, img, cols);
+        unsafe { scatter.launch(cfg, params) }?;
+        Ok(())
+    }
+}
+
+fn cols_strides<C: Shape>(strides: &C::Concrete) -> [usize; 3] {
+    [strides[0], strides[1], strides[2]]
+}
+
+impl<E: Dtype> super::UnfoldKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &Tensor<I, E, Self>,
+        cols: C,
+    ) -> Result<Tensor<C, E, Self>, Self::Err> {
+        let mut out = self.dev.alloc_zeros::<E>(cols.num_elements())?;
+        self.unfold_gather(
+            op,
+            img_strides::<I>(&img.strides),
+            cols_strides::<C>(&cols.strides()),
+            img.data.as_ref(),
+            &mut out,
+        )?;
+        Ok(self.build_tensor(cols, cols.strides(), out))
+    }
+
+    fn backward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &mut Self::Vec<E>,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        self.unfold_scatter(
+            op,
+            img_strides::<I>(&img.strides),
+            cols_strides::<C>(&cols.strides),
+            grad_img,
+            grad_cols,
+        )
+    }
+}
+
+impl<E: Dtype> super::FoldKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &Tensor<C, E, Self>,
+        img: I,
+    ) -> Result<Tensor<I, E, Self>, Self::Err> {
+        let mut out = self.dev.alloc_zeros::<E>(img.num_elements())?;
+        self.unfold_scatter(
+            op,
+            img_strides::<I>(&img.strides()),
+            cols_strides::<C>(&cols.strides),
+            &mut out,
+            cols.data.as_ref(),
+        )?;
+        Ok(self.build_tensor(img, img.strides(), out))
+    }
+
+    fn backward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &mut Self::Vec<E>,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        self.unfold_gather(
+            op,
+            img_strides::<I>(&img.strides),
+            cols_strides::<C>(&cols.strides),
+            grad_img,
+            grad_cols,
+        )
+    }
+}
@@ -0,0 +1,568 @@
+use crate::{shapes::*, tensor::*};
+
+mod cpu_kernel;
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+mod reference_kernel;
+
+/// A batch of `B` tensors with two runtime dimensions, like the columns of [Tensor::unfold2d].
+type Batched2D<B, E, D, T> = Tensor<(B, usize, usize), E, D, T>;
+/// A batch of `B` tensors with three runtime dimensions, like the images of [Tensor::fold2d].
+type Batched3D<B, E, D, T> = Tensor<(B, usize, usize, usize), E, D, T>;
+
+/// Describes the sliding windows of [Tensor::unfold2d] and [Tensor::fold2d]. The 1d
+/// versions use an image of height 1 and a kernel of height 1.
+///
+/// The columns have the shape `(batch, chan * kernel_h * kernel_w, h_out * w_out)`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct UnfoldOp {
+    pub(crate) kernel_h: usize,
+    pub(crate) kernel_w: usize,
+    pub(crate) stride_h: usize,
+    pub(crate) stride_w: usize,
+    pub(crate) padding_h: usize,
+    pub(crate) padding_w: usize,
+    pub(crate) dilation_h: usize,
+    pub(crate) dilation_w: usize,
+    pub(crate) batch: usize,
+    pub(crate) chan: usize,
+    pub(crate) h_in: usize,
+    pub(crate) w_in: usize,
+    pub(crate) h_out: usize,
+    pub(crate) w_out: usize,
+}
+
+impl UnfoldOp {
+    /// A 1d op is a 2d op over an image of height 1.
+    fn new_1d(
+        [kernel, stride, padding, dilation]: [usize; 4],
+        batch: usize,
+        chan: usize,
+        len: usize,
+        len_out: usize,
+    ) -> Self {
+        Self {
+            kernel_h: 1,
+            kernel_w: kernel,
+            stride_h: 1,
+            stride_w: stride,
+            padding_h: 0,
+            padding_w: padding,
+            dilation_h: 1,
+            dilation_w: dilation,
+            batch,
+            chan,
+            h_in: 1,
+            w_in: len,
+            h_out: 1,
+            w_out: len_out,
+        }
+    }
+
+    /// Returns the `[batch, chan, y, x]` index into the image that the entry
+    /// `[batch, row, col]` of the columns reads from, or `None` if it is padding.
+    pub(crate) fn img_index(&self, [b, row, col]: [usize; 3]) -> Option<[usize; 4]> {
+        let kj = row % self.kernel_w;
+        let ki = (row / self.kernel_w) % self.kernel_h;
+        let c = row / (self.kernel_w * self.kernel_h);
+        let (oh, ow) = (col / self.w_out, col % self.w_out);
+        let y = (oh * self.stride_h + ki * self.dilation_h).wrapping_sub(self.padding_h);
+        let x = (ow * self.stride_w + kj * self.dilation_w).wrapping_sub(self.padding_w);
+        (y < self.h_in && x < self.w_in).then_some([b, c, y, x])
+    }
+
+    /// The shape of the columns.
+    fn cols_shape<B: Dim>(&self, batch: B) -> (B, usize, usize) {
+        (
+            batch,
+            self.chan * self.kernel_h * self.kernel_w,
+            self.h_out * self.w_out,
+        )
+    }
+}
+
+/// Returns the number of windows that fit along a dimension of size `dim`.
+fn num_windows(
+    dim: usize,
+    kernel: usize,
+    stride: usize,
+    padding: usize,
+    dilation: usize,
+) -> Option<usize> {
+    if kernel == 0 || stride == 0 || dilation == 0 {
+        return None;
+    }
+    (dim + 2 * padding)
+        .checked_sub(dilation * (kernel - 1) + 1)
+        .map(|n| n / stride + 1)
+}
+
+/// The strides of a 3d `(batch, chan, len)` or 4d `(batch, chan, h, w)` image as
+/// 4d strides.
+pub(crate) fn img_strides<S: Shape>(strides: &S::Concrete) -> [usize; 4] {
+    let s = strides.as_ref();
+    match S::NUM_DIMS {
+        3 => [s[0], s[1], 0, s[2]],
+        _ => [s[0], s[1], s[2], s[3]],
+    }
+}
+
+pub trait UnfoldKernel<E: Dtype>: DeviceStorage {
+    /// Copies the sliding windows of `img` into columns of shape `cols`.
+    fn forward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &Tensor<I, E, Self>,
+        cols: C,
+    ) -> Result<Tensor<C, E, Self>, Self::Err>;
+
+    fn backward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &mut Self::Vec<E>,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+pub trait FoldKernel<E: Dtype>: DeviceStorage {
+    /// Sums the columns `cols` back into an image of shape `img`.
+    fn forward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &Tensor<C, E, Self>,
+        img: I,
+    ) -> Result<Tensor<I, E, Self>, Self::Err>;
+
+    fn backward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &mut Self::Vec<E>,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+impl<B: Dim, C: Dim, H: Dim, W: Dim, E: Dtype, D: UnfoldKernel<E>, T: Tape<E, D>>
+    Tensor<(B, C, H, W), E, D, T>
+{
+    /// Extracts the sliding `kernel x kernel` windows of a batch of images into columns,
+    /// also known as im2col. The output has shape
+    /// `(batch, chan * kernel * kernel, h_out * w_out)`, where the rows are ordered
+    /// by channel, then kernel row, then kernel column.
+    ///
+    /// **pytorch equivalent** `torch.nn.functional.unfold`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let img = dev.tensor([[[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]]]);
+    /// let cols = img.unfold2d(2, 1, 0, 1);
+    /// assert_eq!(cols.shape(), &(Const::<1>, 4, 2));
+    /// assert_eq!(cols.as_vec(), [1.0, 2.0, 2.0, 3.0, 4.0, 5.0, 5.0, 6.0]);
+    /// ```
+    pub fn unfold2d(
+        self,
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Batched2D<B, E, D, T> {
+        self.try_unfold2d(kernel, stride, padding, dilation)
+            .unwrap()
+    }
+
+    /// Fallible version of [Tensor::unfold2d]. Returns a [ShapeError] if the window
+    /// doesn't fit into the padded image.
+    pub fn try_unfold2d(
+        self,
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Result<Batched2D<B, E, D, T>, D::Err> {
+        let (batch, chan, h, w) = self.shape;
+        let h_out = num_windows(h.size(), kernel, stride, padding, dilation);
+        let w_out = num_windows(w.size(), kernel, stride, padding, dilation);
+        let (h_out, w_out) = match (h_out, w_out) {
+            (Some(h_out), Some(w_out)) => (h_out, w_out),
+            _ => {
+                let window = (kernel, kernel);
+                return Err(ShapeError::new("unfold2d", &self.shape, &window).into());
+            }
+        };
+        let op = UnfoldOp {
+            kernel_h: kernel,
+            kernel_w: kernel,
+            stride_h: stride,
+            stride_w: stride,
+            padding_h: padding,
+            padding_w: padding,
+            dilation_h: dilation,
+            dilation_w: dilation,
+            batch: batch.size(),
+            chan: chan.size(),
+            h_in: h.size(),
+            w_in: w.size(),
+            h_out,
+            w_out,
+        };
+        try_unfold(op, self, op.cols_shape(batch))
+    }
+}
+
+impl<B: Dim, C: Dim, L: Dim, E: Dtype, D: UnfoldKernel<E> + FoldKernel<E>, T: Tape<E, D>>
+    Tensor<(B, C, L), E, D, T>
+{
+    /// Extracts the sliding windows of size `kernel` of a batch of sequences
+    /// `(batch, chan, len)` into columns of shape `(batch, chan * kernel, len_out)`.
+    /// See [Tensor::unfold2d].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let seq = dev.tensor([[[1.0, 2.0, 3.0, 4.0]]]);
+    /// let cols = seq.unfold1d(3, 1, 0, 1);
+    /// assert_eq!(cols.shape(), &(Const::<1>, 3, 2));
+    /// assert_eq!(cols.as_vec(), [1.0, 2.0, 2.0, 3.0, 3.0, 4.0]);
+    /// ```
+    pub fn unfold1d(
+        self,
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Batched2D<B, E, D, T> {
+        self.try_unfold1d(kernel, stride, padding, dilation)
+            .unwrap()
+    }
+
+    /// Fallible version of [Tensor::unfold1d]. Returns a [ShapeError] if the window
+    /// doesn't fit into the padded sequence.
+    pub fn try_unfold1d(
+        self,
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Result<Batched2D<B, E, D, T>, D::Err> {
+        let (batch, chan, len) = self.shape;
+        let len_out = match num_windows(len.size(), kernel, stride, padding, dilation) {
+            Some(len_out) => len_out,
+            None => return Err(ShapeError::new("unfold1d", &self.shape, &(kernel,)).into()),
+        };
+        let op = UnfoldOp::new_1d(
+            [kernel, stride, padding, dilation],
+            batch.size(),
+            chan.size(),
+            len.size(),
+            len_out,
+        );
+        try_unfold(op, self, op.cols_shape(batch))
+    }
+
+    /// Sums columns of shape `(batch, chan * kernel * kernel, h_out * w_out)` back into
+    /// a batch of images of shape `(batch, chan, h, w)`, where `(h, w) = img_size`.
+    /// Entries of overlapping windows are added up. This is the adjoint of
+    /// [Tensor::unfold2d], also known as col2im.
+    ///
+    /// **pytorch equivalent** `torch.nn.functional.fold`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let img: Tensor<Rank4<1, 1, 2, 3>, f32, _> = dev.ones();
+    /// let cols = img.unfold2d(2, 1, 0, 1);
+    /// let counts = cols.fold2d((2, 3), 2, 1, 0, 1);
+    /// assert_eq!(counts.as_vec(), [1.0, 2.0, 1.0, 1.0, 2.0, 1.0]);
+    /// ```
+    pub fn fold2d(
+        self,
+        img_size: (usize, usize),
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Batched3D<B, E, D, T> {
+        self.try_fold2d(img_size, kernel, stride, padding, dilation)
+            .unwrap()
+    }
+
+    /// Fallible version of [Tensor::fold2d]. Returns a [ShapeError] if the shape of
+    /// the columns doesn't match the windows of an image of size `img_size`.
+    pub fn try_fold2d(
+        self,
+        (h, w): (usize, usize),
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Result<Batched3D<B, E, D, T>, D::Err> {
+        let (batch, rows, cols) = self.shape;
+        let h_out = num_windows(h, kernel, stride, padding, dilation);
+        let w_out = num_windows(w, kernel, stride, padding, dilation);
+        let window = kernel * kernel;
+        let op = match (h_out, w_out) {
+            (Some(h_out), Some(w_out))
+                if window > 0 && rows.size() % window == 0 && cols.size() == h_out * w_out =>
+            {
+                UnfoldOp {
+                    kernel_h: kernel,
+                    kernel_w: kernel,
+                    stride_h: stride,
+                    stride_w: stride,
+                    padding_h: padding,
+                    padding_w: padding,
+                    dilation_h: dilation,
+                    dilation_w: dilation,
+                    batch: batch.size(),
+                    chan: rows.size() / window,
+                    h_in: h,
+                    w_in: w,
+                    h_out,
+                    w_out,
+                }
+            }
+            _ => {
+                let expected = (
+                    batch.size(),
+                    window,
+                    h_out.unwrap_or(0) * w_out.unwrap_or(0),
+                );
+                return Err(ShapeError::new("fold2d", &self.shape, &expected).into());
+            }
+        };
+        try_fold(op, self, (batch, op.chan, h, w))
+    }
+
+    /// Sums columns of shape `(batch, chan * kernel, len_out)` back into a batch of
+    /// sequences of shape `(batch, chan, len)`. This is the adjoint of [Tensor::unfold1d].
+    /// See [Tensor::fold2d].
+    pub fn fold1d(
+        self,
+        len: usize,
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Batched2D<B, E, D, T> {
+        self.try_fold1d(len, kernel, stride, padding, dilation)
+            .unwrap()
+    }
+
+    /// Fallible version of [Tensor::fold1d]
+    pub fn try_fold1d(
+        self,
+        len: usize,
+        kernel: usize,
+        stride: usize,
+        padding: usize,
+        dilation: usize,
+    ) -> Result<Batched2D<B, E, D, T>, D::Err> {
+        let (batch, rows, cols) = self.shape;
+        let len_out = num_windows(len, kernel, stride, padding, dilation);
+        let op = match len_out {
+            Some(len_out) if kernel > 0 && rows.size() % kernel == 0 && cols.size() == len_out => {
+                UnfoldOp::new_1d(
+                    [kernel, stride, padding, dilation],
+                    batch.size(),
+                    rows.size() / kernel,
+                    len,
+                    len_out,
+                )
+            }
+            _ => {
+                let expected = (batch.size(), kernel, len_out.unwrap_or(0));
+                return Err(ShapeError::new("fold1d", &self.shape, &expected).into());
+            }
+        };
+        try_fold(op, self, (batch, op.chan, len))
+    }
+}
+
+fn try_unfold<I: Shape, C: Shape, E: Dtype, D: UnfoldKernel<E>, T: Tape<E, D>>(
+    op: UnfoldOp,
+    img: Tensor<I, E, D, T>,
+    cols: C,
+) -> Result<Tensor<C, E, D, T>, D::Err> {
+    let (img, mut tape) = img.split_tape();
+    let out = img.device.forward(op, &img, cols)?;
+    let img_ghost = img.ghost();
+    let out_ghost = out.ghost();
+    tape.add_backward_op(move |grads| {
+        grads.try_alloc_for(&img_ghost)?;
+        grads.try_alloc_for(&out_ghost)?;
+        let (grad_img, grad_out) = grads.mut_and_ref(&img_ghost, &out_ghost);
+        img.device
+            .backward(op, &img_ghost, grad_img, &out_ghost, grad_out)
+    });
+    Ok(out.put_tape(tape))
+}
+
+fn try_fold<C: Shape, I: Shape, E: Dtype, D: FoldKernel<E>, T: Tape<E, D>>(
+    op: UnfoldOp,
+    cols: Tensor<C, E, D, T>,
+    img: I,
+) -> Result<Tensor<I, E, D, T>, D::Err> {
+    let (cols, mut tape) = cols.split_tape();
+    let out = cols.device.forward(op, &cols, img)?;
+    let cols_ghost = cols.ghost();
+    let out_ghost = out.ghost();
+    tape.add_backward_op(move |grads| {
+        grads.try_alloc_for(&cols_ghost)?;
+        grads.try_alloc_for(&out_ghost)?;
+        let (grad_cols, grad_out) = grads.mut_and_ref(&cols_ghost, &out_ghost);
+        cols.device
+            .backward(op, &cols_ghost, grad_cols, &out_ghost, grad_out)
+    });
+    Ok(out.put_tape(tape))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_unfold2d_padding_and_stride() {
+        let dev: TestDevice = Default::default();
+        let img = dev
+            .tensor([[[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]]])
+            .to_dtype::<TestDtype>();
+        let cols = img.leaky_trace().unfold2d(2, 2, 1, 1);
+        assert_eq!(cols.shape, (Const, 4, 4));
+        let cols = cols.realize::<Rank3<1, 4, 4>>();
+        assert_close_to_literal!(
+            cols,
+            [[
+                [0.0, 0.0, 0.0, 5.0],
+                [0.0, 0.0, 4.0, 6.0],
+                [0.0, 2.0, 0.0, 8.0],
+                [1.0, 3.0, 7.0, 9.0],
+            ]]
+        );
+        let g = cols.sum().backward();
+        assert_close_to_literal!(
+            g.get(&img),
+            [[[[1.0, 1.0, 1.0], [1.0, 1.0, 1.0], [1.0, 1.0, 1.0]]]]
+        );
+    }
+
+    #[test]
+    fn test_unfold2d_as_conv() {
+        let dev: TestDevice = Default::default();
+        let img = dev
+            .tensor([[
+                [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]],
+                [[0.0, 1.0, 0.0], [1.0, 0.0, 1.0]],
+            ]])
+            .to_dtype::<TestDtype>();
+        let w = dev
+            .tensor([[1.0, 0.0, 0.0, -1.0, 1.0, 1.0, 1.0, 1.0]])
+            .to_dtype::<TestDtype>();
+
+        // a 2x2 convolution with 2 input channels & 1 output channel
+        let cols = img
+            .leaky_trace()
+            .unfold2d(2, 1, 0, 1)
+            .realize::<Rank3<1, 8, 2>>();
+        let out = w
+            .leaky_trace()
+            .broadcast::<Rank3<1, 1, 8>, Axis<0>>()
+            .matmul(cols);
+        assert_close_to_literal!(out, [[[-2.0, -2.0]]]);
+
+        let g = out.sum().backward();
+        assert_close_to_literal!(
+            g.get(&img),
+            [[
+                [[1.0, 1.0, 0.0], [0.0, -1.0, -1.0]],
+                [[1.0, 2.0, 1.0], [1.0, 2.0, 1.0]]
+            ]]
+        );
+        assert_close_to_literal!(g.get(&w), [[3.0, 5.0, 9.0, 11.0, 1.0, 1.0, 1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_fold2d_overlap_counts() {
+        let dev: TestDevice = Default::default();
+        let img: Tensor<Rank4<2, 3, 4, 5>, TestDtype, _> = dev.ones();
+        let counts = img
+            .unfold2d(2, 1, 0, 1)
+            .fold2d((4, 5), 2, 1, 0, 1)
+            .realize::<Rank4<2, 3, 4, 5>>();
+        assert_close_to_literal!(
+            counts,
+            [[[
+                [1.0, 2.0, 2.0, 2.0, 1.0],
+                [2.0, 4.0, 4.0, 4.0, 2.0],
+                [2.0, 4.0, 4.0, 4.0, 2.0],
+                [1.0, 2.0, 2.0, 2.0, 1.0]
+            ]; 3]; 2]
+        );
+    }
+
+    #[test]
+    fn test_unfold1d_dilation_and_fold1d() {
+        let dev: TestDevice = Default::default();
+        let seq = dev
+            .tensor([[[1.0, 2.0, 3.0, 4.0, 5.0], [6.0, 7.0, 8.0, 9.0, 10.0]]])
+            .to_dtype::<TestDtype>();
+        let cols = seq.leaky_trace().unfold1d(2, 1, 0, 2);
+        assert_eq!(cols.shape, (Const, 4, 3));
+        let cols = cols.realize::<Rank3<1, 4, 3>>();
+        assert_close_to_literal!(
+            cols,
+            [[
+                [1.0, 2.0, 3.0],
+                [3.0, 4.0, 5.0],
+                [6.0, 7.0, 8.0],
+                [8.0, 9.0, 10.0]
+            ]]
+        );
+        let g = cols.sum().backward();
+        assert_close_to_literal!(
+            g.get(&seq),
+            [[[1.0, 1.0, 2.0, 1.0, 1.0], [1.0, 1.0, 2.0, 1.0, 1.0]]]
+        );
+
+        let w = dev
+            .tensor([[
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0],
+                [10.0, 11.0, 12.0],
+            ]])
+            .to_dtype::<TestDtype>();
+        let folded = w
+            .leaky_trace()
+            .fold1d(5, 2, 1, 0, 2)
+            .realize::<Rank3<1, 2, 5>>();
+        assert_close_to_literal!(
+            folded,
+            [[[1.0, 2.0, 7.0, 5.0, 6.0], [7.0, 8.0, 19.0, 11.0, 12.0]]]
+        );
+        let g = folded.sum().backward();
+        assert_close_to_literal!(g.get(&w), [[[1.0; 3]; 4]]);
+    }
+
+    #[test]
+    fn test_unfold_shape_errors() {
+        let dev: TestDevice = Default::default();
+        let img: Tensor<Rank4<1, 1, 2, 2>, TestDtype, _> = dev.zeros();
+        let err = img.try_unfold2d(3, 1, 0, 1).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("unfold2d", &(1, 1, 2, 2), &(3, 3)))
+        );
+
+        let cols: Tensor<Rank3<1, 4, 3>, TestDtype, _> = dev.zeros();
+        let err = cols.try_fold2d((3, 3), 2, 1, 0, 1).unwrap_err();
+        assert_eq!(
+            shape_error(err),
+            Some(ShapeError::new("fold2d", &(1, 4, 3), &(1, 4, 4)))
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{
+        reference::{physical_index, ravel_index, unravel_index},
+        GhostTensor, Reference, Tensor,
+    },
+};
+
+use super::UnfoldOp;
+
+/// Returns the index into the image that the `i`th (row major) entry of the columns
+/// reads from, or `None` if it is padding. 3d images leave out the `y` index.
+fn img_index<C: Shape, I: Shape>(op: &UnfoldOp, cols: &C, i: usize) -> Option<I::Concrete> {
+    let idx = unravel_index(cols, i);
+    let [b, c, y, x] = op.img_index([idx[0], idx[1], idx[2]])?;
+    let mut img_idx: I::Concrete = Default::default();
+    img_idx[0] = b;
+    img_idx[1] = c;
+    if I::NUM_DIMS == 3 {
+        img_idx[2] = x;
+    } else {
+        img_idx[2] = y;
+        img_idx[3] = x;
+    }
+    Some(img_idx)
+}
+
+impl<E: Dtype> super::UnfoldKernel<E> for Reference {
+    fn forward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &Tensor<I, E, Self>,
+        cols: C,
+    ) -> Result<Tensor<C, E, Self>, Self::Err> {
+        let out = (0..cols.num_elements())
+            .map(|i| match img_index::<C, I>(&op, &cols, i) {
+                Some(i_img) => img[i_img],
+                None => E::default(),
+            })
+            .collect();
+        Ok(self.build_tensor(cols, out))
+    }
+
+    fn backward<I: Shape, C: Shape>(
+        &self,
+        op: UnfoldOp,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &mut Self::Vec<E>,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for i in 0..cols.shape.num_elements() {
+            if let Some(i_img) = img_index::<C, I>(&op, &cols.shape, i) {
+                grad_img[ravel_index::<I>(&img.strides, &i_img)] +=
+                    grad_cols[physical_index(&cols.shape, &cols.strides, i)];
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype> super::FoldKernel<E> for Reference {
+    fn forward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &Tensor<C, E, Self>,
+        img: I,
+    ) -> Result<Tensor<I, E, Self>, Self::Err> {
+        let strides = img.strides();
+        let mut out = std::vec![E::default(); img.num_elements()];
+        for i in 0..cols.shape.num_elements() {
+            if let Some(i_img) = img_index::<C, I>(&op, &cols.shape, i) {
+                out[ravel_index::<I>(&strides, &i_img)] += cols[unravel_index(&cols.shape, i)];
+            }
+        }
+        Ok(self.build_tensor(img, out))
+    }
+
+    fn backward<C: Shape, I: Shape>(
+        &self,
+        op: UnfoldOp,
+        cols: &GhostTensor<C, E, Self>,
+        grad_cols: &mut Self::Vec<E>,
+        img: &GhostTensor<I, E, Self>,
+        grad_img: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        for i in 0..cols.shape.num_elements() {
+            if let Some(i_img) = img_index::<C, I>(&op, &cols.shape, i) {
+                grad_cols[physical_index(&cols.shape, &cols.strides, i)] +=
+                    grad_img[ravel_index::<I>(&img.strides, &i_img)];
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+#include "cuda_utils.cuh"
+
+struct UnfoldOp {
+    size_t kernel_h;
+    size_t kernel_w;
+    size_t stride_h;
+    size_t stride_w;
+    size_t padding_h;
+    size_t padding_w;
+    size_t dilation_h;
+    size_t dilation_w;
+    size_t batch;
+    size_t chan;
+    size_t h_in;
+    size_t w_in;
+    size_t h_out;
+    size_t w_out;
+};
+
+// Computes the indices into `img` and `cols` of the `i`th (row major) entry of
+// the columns. Returns false if the entry is padding.
+__device__ bool get_unfold_index(
+    size_t i,
+    const UnfoldOp op,
+    const size_t *img_strides,
+    const size_t *cols_strides,
+    size_t *img_i,
+    size_t *cols_i
+) {
+    const size_t l_out = op.h_out * op.w_out;
+    const size_t rows = op.chan * op.kernel_h * op.kernel_w;
+    const size_t col = i % l_out;
+    const size_t row = (i / l_out) % rows;
+    const size_t b = i / (l_out * rows);
+
+    const size_t kj = row % op.kernel_w;
+    const size_t ki = (row / op.kernel_w) % op.kernel_h;
+    const size_t c = row / (op.kernel_w * op.kernel_h);
+    const size_t y = (col / op.w_out) * op.stride_h + ki * op.dilation_h - op.padding_h;
+    const size_t x = (col % op.w_out) * op.stride_w + kj * op.dilation_w - op.padding_w;
+    if (y >= op.h_in || x >= op.w_in) {
+        return false;
+    }
+
+    *img_i = b * img_strides[0] + c * img_strides[1] + y * img_strides[2] + x * img_strides[3];
+    *cols_i = b * cols_strides[0] + row * cols_strides[1] + col * cols_strides[2];
+    return true;
+}
+
+template<typename T>
+__device__ void unfold_gather(
+    const UnfoldOp op,
+    const size_t numel,
+    const size_t *img_strides,
+    const size_t *cols_strides,
+    const T *img,
+    T *cols
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t img_i, cols_i;
+    if (get_unfold_index(i, op, img_strides, cols_strides, &img_i, &cols_i)) {
+        cols[cols_i] += img[img_i];
+    }
+}
+
+template<typename T>
+__device__ void unfold_scatter(
+    const UnfoldOp op,
+    const size_t numel,
+    const size_t *img_strides,
+    const size_t *cols_strides,
+    T *img,
+    const T *cols
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= numel) {
+        return;
+    }
+
+    size_t img_i, cols_i;
+    if (get_unfold_index(i, op, img_strides, cols_strides, &img_i, &cols_i)) {
+        atomicAdd(img + img_i, cols[cols_i]);
+    }
+}
+
+#define UNFOLD(TY, GATHER, SCATTER) \
+extern "C" __global__ void GATHER( \
+    const UnfoldOp op, \
+    const size_t numel, \
+    const size_t *img_strides, \
+    const size_t *cols_strides, \
+    const TY *img, \
+    TY *cols \
+) { unfold_gather(op, numel, img_strides, cols_strides, img, cols); } \
+extern "C" __global__ void SCATTER( \
+    const UnfoldOp op, \
+    const size_t numel, \
+    const size_t *img_strides, \
+    const size_t *cols_strides, \
+    TY *img, \
+    const TY *cols \
+) { unfold_scatter(op, numel, img_strides, cols_strides, img, cols); }
+
+UNFOLD(__half, unfold_gather_f16, unfold_scatter_f16);
+UNFOLD(float, unfold_gather_f32, unfold_scatter_f32);
+UNFOLD(double, unfold_gather_f64, unfold_scatter_f64);
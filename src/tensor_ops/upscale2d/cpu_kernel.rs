@@ -30,7 +30,7 @@ impl<E: Float + Unit + std::ops::AddAssign + std::ops::DivAssign>
         let y_ratio = (op.h_in as f32) / (op.h_out as f32);
         let x_ratio = (op.w_in as f32) / (op.w_out as f32);
 
-        let buf = inp.data.as_ref();
+        let buf = &inp.data[inp.offset..];
         let out_buf = Arc::make_mut(&mut out.data);
         for b in 0..op.batch {
             for c in 0..op.chan {
@@ -94,7 +94,7 @@ impl<E: Float + Dtype> super::Upscale2DKernel<E, Bilinear> for Cpu {
         let y_ratio = ((op.h_in - 1) as f32) / ((op.h_out - 1) as f32);
         let x_ratio = ((op.w_in - 1) as f32) / ((op.w_out - 1) as f32);
 
-        let buf = inp.data.as_ref();
+        let buf = &inp.data[inp.offset..];
         let out_buf = Arc::make_mut(&mut out.data);
         for b in 0..op.batch {
             for c in 0..op.chan {
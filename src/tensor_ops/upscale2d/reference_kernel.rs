@@ -0,0 +1,149 @@
+use crate::shapes::*;
+use crate::tensor::{reference::ravel_index, Reference, Tensor};
+
+use num_traits::Float;
+use std::vec::Vec;
+
+use super::{Bilinear, NearestNeighbor, Upscale2DOp};
+
+/// An input pixel `(y, x)` that contributes to an output pixel with `weight`.
+type Tap<E> = (usize, usize, E);
+
+/// Index into either a `(C, H, W)` or `(B, C, H, W)` tensor.
+fn index<S: Shape>(strides: &S::Concrete, b: usize, c: usize, y: usize, x: usize) -> usize {
+    let mut idx: S::Concrete = Default::default();
+    match S::NUM_DIMS {
+        3 => [c, y, x].iter().enumerate().for_each(|(d, &i)| idx[d] = i),
+        4 => [b, c, y, x]
+            .iter()
+            .enumerate()
+            .for_each(|(d, &i)| idx[d] = i),
+        _ => panic!("Only implemented for 3d & 4d arrays"),
+    }
+    ravel_index::<S>(strides, &idx)
+}
+
+fn nearest<E: Float>(op: &Upscale2DOp, y_out: usize, x_out: usize) -> Vec<Tap<E>> {
+    let y_ratio = (op.h_in as f32) / (op.h_out as f32);
+    let x_ratio = (op.w_in as f32) / (op.w_out as f32);
+    let y = ((y_ratio * y_out as f32).floor() as usize).min(op.h_in - 1);
+    let x = ((x_ratio * x_out as f32).floor() as usize).min(op.w_in - 1);
+    std::vec![(y, x, E::one())]
+}
+
+fn bilinear<E: Float>(op: &Upscale2DOp, y_out: usize, x_out: usize) -> Vec<Tap<E>> {
+    let y_ratio = ((op.h_in - 1) as f32) / ((op.h_out - 1) as f32);
+    let x_ratio = ((op.w_in - 1) as f32) / ((op.w_out - 1) as f32);
+
+    let y_frac = y_ratio * y_out as f32;
+    let y0 = y_frac.floor().min((op.h_in - 1) as f32);
+    let y1 = y_frac.ceil().min((op.h_in - 1) as f32);
+    let yw = E::from(y_frac - y0).unwrap();
+
+    let x_frac = x_ratio * x_out as f32;
+    let x0 = x_frac.floor().min((op.w_in - 1) as f32);
+    let x1 = x_frac.ceil().min((op.w_in - 1) as f32);
+    let xw = E::from(x_frac - x0).unwrap();
+
+    let [y0, y1, x0, x1] = [y0, y1, x0, x1].map(|q| q as usize);
+    let one = E::one();
+    std::vec![
+        (y0, x0, (one - xw) * (one - yw)),
+        (y0, x1, xw * (one - yw)),
+        (y1, x0, (one - xw) * yw),
+        (y1, x1, xw * yw),
+    ]
+}
+
+fn forward<I: Shape, O: Shape, E: Float + Dtype>(
+    op: Upscale2DOp,
+    inp: &Tensor<I, E, Reference>,
+    out: &mut Tensor<O, E, Reference>,
+    taps: impl Fn(&Upscale2DOp, usize, usize) -> Vec<Tap<E>>,
+) {
+    let out_strides = out.strides;
+    let out_buf = std::sync::Arc::make_mut(&mut out.data);
+    for b in 0..op.batch {
+        for c in 0..op.chan {
+            for y_out in 0..op.h_out {
+                for x_out in 0..op.w_out {
+                    let mut tmp = E::zero();
+                    for (y, x, w) in taps(&op, y_out, x_out) {
+                        tmp += w * inp.data[inp.offset + index::<I>(&inp.strides, b, c, y, x)];
+                    }
+                    out_buf[index::<O>(&out_strides, b, c, y_out, x_out)] = tmp;
+                }
+            }
+        }
+    }
+}
+
+fn backward<I: Shape, O: Shape, E: Float + Dtype>(
+    op: Upscale2DOp,
+    inp: &Tensor<I, E, Reference>,
+    grad_inp: &mut [E],
+    out: &Tensor<O, E, Reference>,
+    grad_out: &[E],
+    taps: impl Fn(&Upscale2DOp, usize, usize) -> Vec<Tap<E>>,
+) {
+    for b in 0..op.batch {
+        for c in 0..op.chan {
+            for y_out in 0..op.h_out {
+                for x_out in 0..op.w_out {
+                    let go = grad_out[index::<O>(&out.strides, b, c, y_out, x_out)];
+                    for (y, x, w) in taps(&op, y_out, x_out) {
+                        grad_inp[index::<I>(&inp.strides, b, c, y, x)] += w * go;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Float + Dtype> super::Upscale2DKernel<E, NearestNeighbor> for Reference {
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        forward(op, inp, out, nearest);
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        backward(op, inp, grad_inp, out, grad_out, nearest);
+        Ok(())
+    }
+}
+
+impl<E: Float + Dtype> super::Upscale2DKernel<E, Bilinear> for Reference {
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        forward(op, inp, out, bilinear);
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        backward(op, inp, grad_inp, out, grad_out, bilinear);
+        Ok(())
+    }
+}
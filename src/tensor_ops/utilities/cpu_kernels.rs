@@ -60,6 +60,7 @@ impl<E: Dtype, Op: UnaryDerivative<E>> UnaryKernel<Op, E> for Cpu {
                     data: inp.data.clone(),
                     shape: inp.shape,
                     strides: inp.strides,
+                    offset: inp.offset,
                     device: self.clone(),
                     tape: Default::default(),
                 }
@@ -69,7 +70,8 @@ impl<E: Dtype, Op: UnaryDerivative<E>> UnaryKernel<Op, E> for Cpu {
                 inp.id = unique_id();
                 inp
             }
-        };
+        }
+        .try_compact()?;
         // NOTE: we can iterate over buf here because we know inp & out
         // have exact same strides due to clone.
         for x in out.buf_iter_mut() {
@@ -92,14 +94,16 @@ impl<E: Dtype, Op: UnaryDerivative<E>> UnaryKernel<Op, E> for Cpu {
                     *x += df * grad_out[i];
                 }
             }
-            (None, Some(out)) => {
+            (None, Some(out_buf)) => {
+                let out_buf = &out_buf[out.offset()..];
                 for (i, x) in grad_inp.iter_mut().enumerate() {
-                    *x += op.df(&out[i]) * grad_out[i];
+                    *x += op.df(&out_buf[i]) * grad_out[i];
                 }
             }
-            (Some(inp), None) => {
+            (Some(inp_buf), None) => {
+                let inp_buf = &inp_buf[inp.offset()..];
                 for (i, x) in grad_inp.iter_mut().enumerate() {
-                    *x += op.df(&inp[i]) * grad_out[i];
+                    *x += op.df(&inp_buf[i]) * grad_out[i];
                 }
             }
             _ => unreachable!(),
@@ -129,8 +133,8 @@ impl<E: Dtype, Op: BinaryDerivative<E>> BinaryKernel<Op, E> for Cpu {
                 Ok(out)
             }
             (Cow::Owned(mut lhs), Cow::Owned(mut rhs)) => {
-                let lhs_valid = lhs.strides == lhs.shape.strides();
-                let rhs_valid = rhs.strides == rhs.shape.strides();
+                let lhs_valid = lhs.strides == lhs.shape.strides() && lhs.is_whole();
+                let rhs_valid = rhs.strides == rhs.shape.strides() && rhs.is_whole();
                 if lhs_valid || rhs_valid {
                     let lhs_count = std::sync::Arc::strong_count(&lhs.data);
                     let rhs_count = std::sync::Arc::strong_count(&rhs.data);
@@ -138,14 +142,14 @@ impl<E: Dtype, Op: BinaryDerivative<E>> BinaryKernel<Op, E> for Cpu {
                         rhs.id = unique_id();
                         let mut lhs_idx = NdIndex::new(lhs.shape, lhs.strides);
                         for r in rhs.buf_iter_mut() {
-                            *r = op.f(&lhs.data[lhs_idx.next().unwrap()], r);
+                            *r = op.f(&lhs.data[lhs.offset + lhs_idx.next().unwrap()], r);
                         }
                         Ok(rhs)
                     } else {
                         lhs.id = unique_id();
                         let mut rhs_idx = NdIndex::new(rhs.shape, rhs.strides);
                         for l in lhs.buf_iter_mut() {
-                            *l = op.f(l, &rhs.data[rhs_idx.next().unwrap()]);
+                            *l = op.f(l, &rhs.data[rhs.offset + rhs_idx.next().unwrap()]);
                         }
                         Ok(lhs)
                     }
@@ -179,8 +183,8 @@ impl<E: Dtype, Op: BinaryDerivative<E>> BinaryKernel<Op, E> for Cpu {
                 for &go in grad_out.iter() {
                     let lhs_i = lhs_idx.next().unwrap();
                     let rhs_i = rhs_idx.next().unwrap();
-                    let l = &lhs_buf[lhs_i];
-                    let r = &rhs_buf[rhs_i];
+                    let l = &lhs_buf[lhs.offset() + lhs_i];
+                    let r = &rhs_buf[rhs.offset() + rhs_i];
                     grad_lhs[lhs_i] += op.dfdx(l, r) * go;
                     grad_rhs[rhs_i] += op.dfdy(l, r) * go;
                 }
@@ -158,3 +158,99 @@ __device__ __forceinline__ __half absg(__half a) { return __habs(a); }
 __device__ __forceinline__ float copysigng(float a, float b) { return copysignf(a, b); }
 __device__ __forceinline__ double copysigng(double a, double b) { return copysign(a, b); }
 __device__ __forceinline__ __half copysigng(__half a, __half b) { return __float2half(copysignf(__half2float(a), __half2float(b))); }
+__device__ __forceinline__ float erfg(float a) { return erff(a); }
+__device__ __forceinline__ double erfg(double a) { return erf(a); }
+__device__ __forceinline__ __half erfg(__half a) { return __float2half(erff(__half2float(a))); }
+__device__ __forceinline__ float erfcg(float a) { return erfcf(a); }
+__device__ __forceinline__ double erfcg(double a) { return erfc(a); }
+__device__ __forceinline__ __half erfcg(__half a) { return __float2half(erfcf(__half2float(a))); }
+__device__ __forceinline__ float lgammag(float a) { return lgammaf(a); }
+__device__ __forceinline__ double lgammag(double a) { return lgamma(a); }
+__device__ __forceinline__ __half lgammag(__half a) { return __float2half(lgammaf(__half2float(a))); }
+__device__ __forceinline__ float log1pg(float a) { return log1pf(a); }
+__device__ __forceinline__ double log1pg(double a) { return log1p(a); }
+__device__ __forceinline__ __half log1pg(__half a) { return __float2half(log1pf(__half2float(a))); }
+__device__ __forceinline__ float expm1g(float a) { return expm1f(a); }
+__device__ __forceinline__ double expm1g(double a) { return expm1(a); }
+__device__ __forceinline__ __half expm1g(__half a) { return __float2half(expm1f(__half2float(a))); }
+__device__ __forceinline__ float asing(float a) { return asinf(a); }
+__device__ __forceinline__ double asing(double a) { return asin(a); }
+__device__ __forceinline__ __half asing(__half a) { return __float2half(asinf(__half2float(a))); }
+__device__ __forceinline__ float acosg(float a) { return acosf(a); }
+__device__ __forceinline__ double acosg(double a) { return acos(a); }
+__device__ __forceinline__ __half acosg(__half a) { return __float2half(acosf(__half2float(a))); }
+__device__ __forceinline__ float atang(float a) { return atanf(a); }
+__device__ __forceinline__ double atang(double a) { return atan(a); }
+__device__ __forceinline__ __half atang(__half a) { return __float2half(atanf(__half2float(a))); }
+__device__ __forceinline__ float sinhg(float a) { return sinhf(a); }
+__device__ __forceinline__ double sinhg(double a) { return sinh(a); }
+__device__ __forceinline__ __half sinhg(__half a) { return __float2half(sinhf(__half2float(a))); }
+__device__ __forceinline__ float coshg(float a) { return coshf(a); }
+__device__ __forceinline__ double coshg(double a) { return cosh(a); }
+__device__ __forceinline__ __half coshg(__half a) { return __float2half(coshf(__half2float(a))); }
+__device__ __forceinline__ float floorg(float a) { return floorf(a); }
+__device__ __forceinline__ double floorg(double a) { return floor(a); }
+__device__ __forceinline__ __half floorg(__half a) { return __float2half(floorf(__half2float(a))); }
+__device__ __forceinline__ float ceilg(float a) { return ceilf(a); }
+__device__ __forceinline__ double ceilg(double a) { return ceil(a); }
+__device__ __forceinline__ __half ceilg(__half a) { return __float2half(ceilf(__half2float(a))); }
+__device__ __forceinline__ float roundg(float a) { return roundf(a); }
+__device__ __forceinline__ double roundg(double a) { return round(a); }
+__device__ __forceinline__ __half roundg(__half a) { return __float2half(roundf(__half2float(a))); }
+__device__ __forceinline__ float atan2g(float a, float b) { return atan2f(a, b); }
+__device__ __forceinline__ double atan2g(double a, double b) { return atan2(a, b); }
+__device__ __forceinline__ __half atan2g(__half a, __half b) { return __float2half(atan2f(__half2float(a), __half2float(b))); }
+
+// digamma & trigamma are computed in double precision: shift x up with the recurrence
+// relations, then use the asymptotic expansions. Negative values use the reflection formulas.
+__device__ double digamma_f64(double x) {
+    const double pi = 3.141592653589793;
+    if (isnan(x) || (x <= 0.0 && x == floor(x))) {
+        return nan("");
+    }
+    double result = 0.0;
+    if (x < 0.0) {
+        result = -pi / tan(pi * x);
+        x = 1.0 - x;
+    }
+    while (x < 10.0) {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    double x2 = 1.0 / (x * x);
+    double series = x2 * (1.0 / 12.0 - x2 * (1.0 / 120.0 - x2 * (1.0 / 252.0 - x2 * (1.0 / 240.0 - x2 / 132.0))));
+    return result + log(x) - 0.5 / x - series;
+}
+
+__device__ double trigamma_f64(double x) {
+    const double pi = 3.141592653589793;
+    if (isnan(x)) {
+        return x;
+    }
+    if (x <= 0.0 && x == floor(x)) {
+        return INFINITY;
+    }
+    double result = 0.0;
+    double sign = 1.0;
+    if (x < 0.0) {
+        double s = sin(pi * x);
+        result = pi * pi / (s * s);
+        sign = -1.0;
+        x = 1.0 - x;
+    }
+    double shifted = 0.0;
+    while (x < 10.0) {
+        shifted += 1.0 / (x * x);
+        x += 1.0;
+    }
+    double x2 = 1.0 / (x * x);
+    double series = x2 * (1.0 / 6.0 - x2 * (1.0 / 30.0 - x2 * (1.0 / 42.0 - x2 * (1.0 / 30.0 - x2 * 5.0 / 66.0))));
+    return result + sign * (shifted + 1.0 / x + 0.5 * x2 + series / x);
+}
+
+__device__ __forceinline__ float digammag(float a) { return digamma_f64(a); }
+__device__ __forceinline__ double digammag(double a) { return digamma_f64(a); }
+__device__ __forceinline__ __half digammag(__half a) { return __float2half(digamma_f64(__half2float(a))); }
+__device__ __forceinline__ float trigammag(float a) { return trigamma_f64(a); }
+__device__ __forceinline__ double trigammag(double a) { return trigamma_f64(a); }
+__device__ __forceinline__ __half trigammag(__half a) { return __float2half(trigamma_f64(__half2float(a))); }
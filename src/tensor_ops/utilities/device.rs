@@ -39,8 +39,20 @@ pub trait Device<E: Dtype>:
     + super::super::select_and_gather::ReplaceDimKernel<E>
     + super::super::select_and_gather::RemoveDimKernel<E>
     + super::super::choose::ChooseKernel<E>
+    + super::super::masked::MaskedFillKernel<E>
+    + super::super::masked::MaskedSelectKernel<E>
     + super::super::slice::SliceKernel<E>
+    + super::super::pad::PadKernel<E>
+    + super::super::split_along::SplitAlongKernel<E>
     + super::super::roll::RollKernel<E>
+    + super::super::flip::FlipKernel<E>
+    + super::super::repeat::RepeatKernel<E>
+    + super::super::scan::ScanKernel<E>
+    + super::super::sort::SortKernel<E>
+    + super::super::unfold::UnfoldKernel<E>
+    + super::super::unfold::FoldKernel<E>
+    + super::super::scatter::IndexSelectKernel<E>
+    + super::super::scatter::ScatterKernel<E>
 
     // matmuls
     + super::super::matmul::MatMatKernel<E>
@@ -86,6 +98,14 @@ pub trait Device<E: Dtype>:
     + UnaryKernel<super::super::negate::NegateKernelOp, E>
     + UnaryKernel<super::super::relu::ReLUKernelOp, E>
     + UnaryKernel<super::super::gelu::GeLUKernelOp, E>
+    + UnaryKernel<super::super::accurate_gelu::AccurateGeLUKernelOp, E>
+    + UnaryKernel<super::super::silu::SiLUKernelOp, E>
+    + UnaryKernel<super::super::mish::MishKernelOp, E>
+    + UnaryKernel<super::super::elu::ELUKernelOp<E>, E>
+    + UnaryKernel<super::super::selu::SeLUKernelOp, E>
+    + UnaryKernel<super::super::softplus::SoftplusKernelOp<E>, E>
+    + UnaryKernel<super::super::hard_sigmoid::HardSigmoidKernelOp, E>
+    + UnaryKernel<super::super::hard_swish::HardSwishKernelOp, E>
     + UnaryKernel<super::super::sigmoid::SigmoidKernelOp, E>
     + UnaryKernel<super::super::sin::SinKernelOp, E>
     + UnaryKernel<super::super::sqrt::SqrtKernelOp, E>
@@ -94,6 +114,20 @@ pub trait Device<E: Dtype>:
     + UnaryKernel<super::super::pow::PowfKernelOp<E>, E>
     + UnaryKernel<super::super::pow::PowiKernelOp, E>
     + UnaryKernel<super::super::recip::RecipKernelOp, E>
+    + UnaryKernel<super::super::erf::ErfKernelOp, E>
+    + UnaryKernel<super::super::erfc::ErfcKernelOp, E>
+    + UnaryKernel<super::super::lgamma::LGammaKernelOp, E>
+    + UnaryKernel<super::super::digamma::DigammaKernelOp, E>
+    + UnaryKernel<super::super::log1p::Log1pKernelOp, E>
+    + UnaryKernel<super::super::expm1::Expm1KernelOp, E>
+    + UnaryKernel<super::super::asin::AsinKernelOp, E>
+    + UnaryKernel<super::super::acos::AcosKernelOp, E>
+    + UnaryKernel<super::super::atan::AtanKernelOp, E>
+    + UnaryKernel<super::super::sinh::SinhKernelOp, E>
+    + UnaryKernel<super::super::cosh::CoshKernelOp, E>
+    + UnaryKernel<super::super::floor::FloorKernelOp, E>
+    + UnaryKernel<super::super::ceil::CeilKernelOp, E>
+    + UnaryKernel<super::super::round::RoundKernelOp, E>
 
     // to_dtype
     + super::super::to_dtype::ToDtypeKernel<f32, E>
@@ -106,6 +140,7 @@ pub trait Device<E: Dtype>:
     + BinaryKernel<super::super::huber_error::HuberErrorKernelOp<E>, E>
     + BinaryKernel<super::super::maximum::MaximumKernelOp, E>
     + BinaryKernel<super::super::minimum::MinimumKernelOp, E>
+    + BinaryKernel<super::super::atan2::Atan2KernelOp, E>
     + crate::tensor_ops::axpy::AxpyKernel<E>
 {
 }
@@ -115,6 +150,9 @@ impl Device<half::f16> for crate::tensor::Cpu {}
 impl Device<f32> for crate::tensor::Cpu {}
 impl Device<f64> for crate::tensor::Cpu {}
 
+impl Device<f32> for crate::tensor::Reference {}
+impl Device<f64> for crate::tensor::Reference {}
+
 #[cfg(all(feature = "cuda", feature = "f16"))]
 impl Device<half::f16> for crate::tensor::Cuda {}
 
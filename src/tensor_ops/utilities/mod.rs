@@ -5,6 +5,8 @@ pub(crate) mod cuda_kernels;
 mod device;
 pub(crate) mod ops;
 pub(crate) mod reduction_utils;
+mod reference_kernels;
+pub(crate) mod special;
 
 pub use backward::Backward;
 pub use device::Device;
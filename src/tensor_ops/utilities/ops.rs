@@ -1,5 +1,5 @@
 use crate::{
-    shapes::{Dtype, HasShape, Shape},
+    shapes::{Dtype, HasShape, Shape, ShapeError},
     tensor::{DeviceStorage, Merge, PutTape, SplitTape, Tape, Tensor, Tensorlike},
 };
 use std::borrow::Cow;
@@ -96,11 +96,14 @@ pub(crate) fn try_binary_op<
     RhsTape,
     LhsTape: Tape<E, D> + Merge<RhsTape>,
 >(
+    name: &'static str,
     op: Op,
     lhs: Tensor<S, E, D, LhsTape>,
     rhs: Tensor<S, E, D, RhsTape>,
 ) -> Result<Tensor<S, E, D, LhsTape>, D::Err> {
-    assert_eq!(lhs.shape(), rhs.shape());
+    if lhs.shape() != rhs.shape() {
+        return Err(ShapeError::new(name, lhs.shape(), rhs.shape()).into());
+    }
     let (lhs, ltape) = lhs.split_tape();
     let (rhs, rtape) = rhs.split_tape();
     let lhs_ghost = lhs.ghost();
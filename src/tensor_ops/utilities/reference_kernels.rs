@@ -0,0 +1,100 @@
+use std::borrow::Cow;
+
+use super::{
+    cpu_kernels::{BinaryDerivative, UnaryDerivative},
+    ops::{BinaryKernel, UnaryKernel},
+};
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{reference::physical_index, Reference, Tensor, Tensorlike},
+};
+
+impl<E: Dtype, Op: UnaryDerivative<E>> UnaryKernel<Op, E> for Reference {
+    const BACKWARD_WITHOUT_INP: bool = false;
+    const BACKWARD_WITHOUT_DATA: bool = false;
+
+    fn forward<S: Shape>(
+        &self,
+        op: Op,
+        inp: Cow<Tensor<S, E, Self>>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let numel = inp.shape.num_elements();
+        let mut out = std::vec::Vec::with_capacity(numel);
+        for i in 0..numel {
+            let x = &inp.data[inp.offset + physical_index(&inp.shape, &inp.strides, i)];
+            out.push(op.f(x));
+        }
+        Ok(self.build_tensor(inp.shape, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        op: Op,
+        inp: &impl Tensorlike<S, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &impl Tensorlike<S, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let inp_buf = inp.data().unwrap();
+        let shape = *inp.shape();
+        let (inp_strides, out_strides) = (inp.strides(), out.strides());
+        for i in 0..shape.num_elements() {
+            let i_inp = physical_index(&shape, &inp_strides, i);
+            let i_out = physical_index(&shape, &out_strides, i);
+            let x = &inp_buf[inp.offset() + i_inp];
+            let df = if Op::DF_USES_FX {
+                op.df(&op.f(x))
+            } else {
+                op.df(x)
+            };
+            grad_inp[i_inp] += df * grad_out[i_out];
+        }
+        Ok(())
+    }
+}
+
+impl<E: Dtype, Op: BinaryDerivative<E>> BinaryKernel<Op, E> for Reference {
+    const BACKWARD_WITHOUT_DATA: bool = false;
+
+    fn forward<S: Shape>(
+        &self,
+        op: Op,
+        lhs: Cow<Tensor<S, E, Self>>,
+        rhs: Cow<Tensor<S, E, Self>>,
+    ) -> Result<Tensor<S, E, Self>, Self::Err> {
+        let shape = lhs.shape;
+        let mut out = std::vec::Vec::with_capacity(shape.num_elements());
+        for i in 0..shape.num_elements() {
+            let l = &lhs.data[lhs.offset + physical_index(&shape, &lhs.strides, i)];
+            let r = &rhs.data[rhs.offset + physical_index(&shape, &rhs.strides, i)];
+            out.push(op.f(l, r));
+        }
+        Ok(self.build_tensor(shape, out))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        op: Op,
+        lhs: &impl Tensorlike<S, E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &impl Tensorlike<S, E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let (lhs_buf, rhs_buf) = (lhs.data().unwrap(), rhs.data().unwrap());
+        let shape = *lhs.shape();
+        let (lhs_strides, rhs_strides) = (lhs.strides(), rhs.strides());
+        // NOTE: the output of forward is always contiguous
+        for (i, &go) in grad_out.iter().enumerate() {
+            let i_lhs = physical_index(&shape, &lhs_strides, i);
+            let i_rhs = physical_index(&shape, &rhs_strides, i);
+            let (l, r) = (
+                &lhs_buf[lhs.offset() + i_lhs],
+                &rhs_buf[rhs.offset() + i_rhs],
+            );
+            grad_lhs[i_lhs] += op.dfdx(l, r) * go;
+            grad_rhs[i_rhs] += op.dfdy(l, r) * go;
+        }
+        Ok(())
+    }
+}
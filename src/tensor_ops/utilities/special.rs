@@ -0,0 +1,232 @@
+//! Scalar special functions used by the cpu kernels. These are computed in `f64`
+//! and converted back to the kernel's dtype, since [num_traits::Float] doesn't
+//! provide them.
+
+use std::f64::consts::PI;
+
+/// `1 / sqrt(pi)`
+const FRAC_1_SQRT_PI: f64 = 0.564_189_583_547_756_3;
+
+/// The [error function](https://en.wikipedia.org/wiki/Error_function).
+pub(crate) fn erf(x: f64) -> f64 {
+    if x.is_nan() {
+        x
+    } else if x.abs() < 3.0 {
+        erf_series(x)
+    } else {
+        (1.0 - erfc_cont_frac(x.abs())).copysign(x)
+    }
+}
+
+/// The complementary error function `1 - erf(x)`, which keeps its precision
+/// for large `x`.
+pub(crate) fn erfc(x: f64) -> f64 {
+    if x.is_nan() {
+        x
+    } else if x < 3.0 {
+        if x > -3.0 {
+            1.0 - erf_series(x)
+        } else {
+            2.0 - erfc_cont_frac(-x)
+        }
+    } else {
+        erfc_cont_frac(x)
+    }
+}
+
+/// `ln(|gamma(x)|)`, using the [Lanczos approximation](https://en.wikipedia.org/wiki/Lanczos_approximation)
+/// with `g = 7`. Poles at non positive integers are `inf`.
+pub(crate) fn lgamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x.is_nan() {
+        x
+    } else if x <= 0.0 && x == x.floor() {
+        f64::INFINITY
+    } else if x < 0.5 {
+        // reflection formula gamma(x) * gamma(1 - x) = pi / sin(pi * x)
+        (PI / (PI * x).sin().abs()).ln() - lgamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let mut a = COEFS[0];
+        for (i, &c) in COEFS.iter().enumerate().skip(1) {
+            a += c / (x + i as f64);
+        }
+        let t = x + G + 0.5;
+        0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+    }
+}
+
+/// The [digamma function](https://en.wikipedia.org/wiki/Digamma_function), the derivative of
+/// [lgamma]. Poles at non positive integers are `nan`.
+pub(crate) fn digamma(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY || (x <= 0.0 && x == x.floor()) {
+        f64::NAN
+    } else if x < 0.0 {
+        // reflection formula psi(1 - x) - psi(x) = pi / tan(pi * x)
+        digamma(1.0 - x) - PI / (PI * x).tan()
+    } else {
+        // shift x up with psi(x + 1) = psi(x) + 1 / x, then use the asymptotic expansion
+        let mut x = x;
+        let mut result = 0.0;
+        while x < 10.0 {
+            result -= x.recip();
+            x += 1.0;
+        }
+        let x2 = (x * x).recip();
+        let series = x2
+            * (1.0 / 12.0
+                - x2 * (1.0 / 120.0 - x2 * (1.0 / 252.0 - x2 * (1.0 / 240.0 - x2 / 132.0))));
+        result + x.ln() - 0.5 / x - series
+    }
+}
+
+/// The [trigamma function](https://en.wikipedia.org/wiki/Trigamma_function), the derivative of
+/// [digamma]. Poles at non positive integers are `inf`.
+pub(crate) fn trigamma(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        f64::NAN
+    } else if x <= 0.0 && x == x.floor() {
+        f64::INFINITY
+    } else if x < 0.0 {
+        // reflection formula psi1(1 - x) + psi1(x) = pi^2 / sin^2(pi * x)
+        let s = (PI * x).sin();
+        PI * PI / (s * s) - trigamma(1.0 - x)
+    } else {
+        // shift x up with psi1(x + 1) = psi1(x) - 1 / x^2, then use the asymptotic expansion
+        let mut x = x;
+        let mut result = 0.0;
+        while x < 10.0 {
+            result += (x * x).recip();
+            x += 1.0;
+        }
+        let x2 = (x * x).recip();
+        let series = x2
+            * (1.0 / 6.0
+                - x2 * (1.0 / 30.0 - x2 * (1.0 / 42.0 - x2 * (1.0 / 30.0 - x2 * 5.0 / 66.0))));
+        result + x.recip() + 0.5 * x2 + series / x
+    }
+}
+
+/// `erf(x) = 2 / sqrt(pi) * exp(-x^2) * sum_n 2^n x^(2n + 1) / (1 * 3 * ... * (2n + 1))`.
+///
+/// All the terms have the same sign, so this doesn't suffer from cancellation.
+fn erf_series(x: f64) -> f64 {
+    let x_sq = x * x;
+    let mut term = x;
+    let mut sum = x;
+    let mut n = 0.0;
+    while term.abs() > sum.abs() * f64::EPSILON {
+        n += 1.0;
+        term *= 2.0 * x_sq / (2.0 * n + 1.0);
+        sum += term;
+    }
+    2.0 * FRAC_1_SQRT_PI * (-x_sq).exp() * sum
+}
+
+/// Continued fraction `erfc(x) = exp(-x^2) / sqrt(pi) / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...))))`
+/// for `x >= 3`.
+fn erfc_cont_frac(x: f64) -> f64 {
+    let mut f = x;
+    for k in (1..=60).rev() {
+        f = x + 0.5 * k as f64 / f;
+    }
+    FRAC_1_SQRT_PI * (-x * x).exp() / f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erf() {
+        let expected = [
+            (0.0, 0.0),
+            (0.1, 0.1124629160182849),
+            (0.5, 0.5204998778130465),
+            (1.0, 0.8427007929497149),
+            (2.0, 0.9953222650189527),
+            (2.9, 0.9999589021219005),
+            (3.5, 0.9999992569016276),
+            (5.0, 0.9999999999984626),
+        ];
+        for (x, y) in expected {
+            assert!((erf(x) - y).abs() < 1e-14, "erf({x}) = {}", erf(x));
+            assert!((erf(-x) + y).abs() < 1e-14, "erf({}) = {}", -x, erf(-x));
+        }
+        assert!(erf(f64::NAN).is_nan());
+        assert_eq!(erf(f64::INFINITY), 1.0);
+        assert_eq!(erf(f64::NEG_INFINITY), -1.0);
+    }
+
+    #[test]
+    fn test_erfc() {
+        let expected = [
+            (-1.0, 1.842700792949715),
+            (0.5, 0.4795001221869535),
+            (2.9, 4.109787809945886e-5),
+            (3.0, 2.2090496998585438e-5),
+            (5.0, 1.5374597944280351e-12),
+            (10.0, 2.088487583762545e-45),
+        ];
+        for (x, y) in expected {
+            assert!(((erfc(x) - y) / y).abs() < 1e-10, "erfc({x}) = {}", erfc(x));
+        }
+    }
+
+    #[test]
+    fn test_lgamma() {
+        let expected = [
+            (0.5, 0.5723649429247004),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (3.5, 1.2009736023470738),
+            (10.0, 12.801827480081467),
+            (100.0, 359.1342053695754),
+            (0.001, 6.907178885383854),
+            (-0.5, 1.265512123484645),
+            (-2.5, -0.05624371649767457),
+        ];
+        for (x, y) in expected {
+            assert!((lgamma(x) - y).abs() < 1e-12, "lgamma({x}) = {}", lgamma(x));
+        }
+        assert_eq!(lgamma(0.0), f64::INFINITY);
+        assert_eq!(lgamma(-3.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_digamma_and_trigamma() {
+        let expected = [
+            (0.5, -1.9635100260214235, 4.934802200544679),
+            (1.0, -0.5772156649015329, 1.6449340668482264),
+            (3.5, 1.103156640645243, 0.3303577561002349),
+            (25.0, 3.198742512851974, 0.04081066325722558),
+            (-0.5, 0.03648997397857652, 8.934802200544679),
+            (-2.5, 1.103156640645243, 9.539246644989124),
+        ];
+        for (x, psi, psi1) in expected {
+            assert!(
+                (digamma(x) - psi).abs() < 1e-12,
+                "digamma({x}) = {}",
+                digamma(x)
+            );
+            assert!(
+                (trigamma(x) - psi1).abs() < 1e-10,
+                "trigamma({x}) = {}",
+                trigamma(x)
+            );
+        }
+        assert!(digamma(0.0).is_nan());
+        assert!(digamma(-2.0).is_nan());
+    }
+}